@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use serde::Deserialize;
 
@@ -12,6 +13,8 @@ pub enum ComponentType {
     OutputStyles,
     Statusline,
     ConfigFile,
+    /// User-defined type from the config's `component_types:`, by name.
+    Custom(String),
 }
 
 impl ComponentType {
@@ -26,6 +29,7 @@ impl ComponentType {
             Self::OutputStyles => "output-styles",
             Self::Statusline => "statusline",
             Self::ConfigFile => "config",
+            Self::Custom(name) => name,
         }
     }
 }
@@ -40,6 +44,12 @@ pub enum InstallStatus {
     /// orphaned from older hibi versions). source_path = dest_path; never
     /// installed, only removable.
     External,
+    /// External file that hibi itself installed, from a component the
+    /// sources no longer ship (e.g. renamed upstream). Only removable.
+    Orphaned,
+    /// Hook directory in a source without a hook.yaml. Shown so one can
+    /// be scaffolded; source_path is the directory. Never installed.
+    Unconfigured,
 }
 
 impl InstallStatus {
@@ -50,6 +60,8 @@ impl InstallStatus {
             Self::Unchanged => "installed",
             Self::Managed => "managed",
             Self::External => "external",
+            Self::Orphaned => "orphaned",
+            Self::Unconfigured => "unconfig",
         }
     }
 }
@@ -66,14 +78,62 @@ pub struct HookConfig {
     pub description: Option<String>,
     #[serde(default)]
     pub deprecated: Option<bool>,
+    /// Ordering hint within the event's settings.json array. Lower values
+    /// run first; hooks without one sort as `DEFAULT_HOOK_PRIORITY`.
+    #[serde(default)]
+    pub priority: Option<i32>,
+    /// Default environment for the hook process. Written into the
+    /// settings.json command as `KEY=value` prefixes because hook entries
+    /// have no env field of their own; users can override values later.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Hostname globs the hook is meant for; empty means every machine.
+    #[serde(default)]
+    pub hosts: Vec<String>,
 }
 
+/// Claude Code hook events, offered when scaffolding a hook.yaml.
+pub const HOOK_EVENTS: [&str; 9] = [
+    "PreToolUse",
+    "PostToolUse",
+    "UserPromptSubmit",
+    "Notification",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// Starter hook.yaml for a hook called `name` that runs on `event`.
+pub fn hook_yaml_template(name: &str, event: &str) -> String {
+    format!(
+        "name: {name}\n\
+         event: {event}\n\
+         type: command\n\
+         timeout: 5000\n\
+         description: TODO describe what {name} does\n\
+         # priority: 100   # lower runs first among hooks on the same event\n\
+         # env:            # default environment for the hook process\n\
+         #   KEY: value\n"
+    )
+}
+
+/// Priority assumed for hooks that don't declare one (and for foreign
+/// settings.json entries the installer has no hook.yaml for).
+pub const DEFAULT_HOOK_PRIORITY: i32 = 100;
+
 impl HookConfig {
     /// Returns whether this hook is deprecated
     pub fn is_deprecated(&self) -> bool {
         self.deprecated.unwrap_or(false)
     }
 
+    /// Returns the declared priority, falling back to `DEFAULT_HOOK_PRIORITY`.
+    pub fn effective_priority(&self) -> i32 {
+        self.priority.unwrap_or(DEFAULT_HOOK_PRIORITY)
+    }
+
     /// Returns the platform-specific binary name for this hook
     pub fn binary_name(&self) -> String {
         if cfg!(windows) {
@@ -103,6 +163,23 @@ impl HookConfig {
             format!("~/{}/hooks/{}", dir_name, binary_name)
         }
     }
+
+    /// Returns the full settings.json command: `hook_command_path` preceded
+    /// by shell-quoted `KEY=value` assignments for each entry of `env`.
+    pub fn hook_command(&self, dest_dir: &Path, env: &BTreeMap<String, String>) -> String {
+        let path = self.hook_command_path(dest_dir);
+        let mut parts: Vec<String> = env
+            .iter()
+            .map(|(key, value)| {
+                let quoted = shlex::try_quote(value)
+                    .map(|q| q.into_owned())
+                    .unwrap_or_else(|_| value.clone());
+                format!("{}={}", key, quoted)
+            })
+            .collect();
+        parts.push(path);
+        parts.join(" ")
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -115,6 +192,10 @@ pub struct Component {
     pub status: InstallStatus,
     pub hook_config: Option<HookConfig>,
     pub source_name: String,
+    /// On-disk size of `source_path`, used for the batch size estimate.
+    pub size_bytes: u64,
+    /// Hostname globs from the `hosts:` filter; empty means every machine.
+    pub hosts: Vec<String>,
 }
 
 impl Component {
@@ -125,7 +206,11 @@ impl Component {
         dest_path: PathBuf,
         status: InstallStatus,
     ) -> Self {
-        let selected = !matches!(status, InstallStatus::Unchanged | InstallStatus::External);
+        let selected = !matches!(
+            status,
+            InstallStatus::Unchanged | InstallStatus::External | InstallStatus::Orphaned | InstallStatus::Unconfigured
+        );
+        let size_bytes = crate::fs::path_size(&source_path);
         Self {
             component_type,
             name,
@@ -135,6 +220,8 @@ impl Component {
             status,
             hook_config: None,
             source_name: "bundled".to_string(),
+            size_bytes,
+            hosts: Vec::new(),
         }
     }
 
@@ -154,14 +241,20 @@ impl Component {
         format!("{}/{}", self.component_type.display_name(), self.name)
     }
 
+    /// Whether the `hosts:` filter lets this machine have the component.
+    pub fn for_this_host(&self) -> bool {
+        crate::fs::hosts::host_matches(&self.hosts, crate::fs::hosts::this_host())
+    }
+
     /// Whether this component is eligible to enter the install queue.
     ///
     /// External components must not be installed (their `source_path` equals
     /// `dest_path`, so copying would be a no-op or self-corruption depending
-    /// on the OS). Deprecated hooks are blocked at install time.
+    /// on the OS), nor unconfigured hooks, which have nothing to copy yet.
+    /// Deprecated hooks are blocked at install time.
     /// Selection and component-type matching are handled by the caller.
     pub fn is_install_eligible(&self) -> bool {
-        if self.status == InstallStatus::External {
+        if matches!(self.status, InstallStatus::External | InstallStatus::Orphaned | InstallStatus::Unconfigured) {
             return false;
         }
         if let Some(config) = &self.hook_config {
@@ -171,12 +264,54 @@ impl Component {
         }
         true
     }
+
+    /// Whether the file is read into the model's context: agents, rules,
+    /// contexts and the markdown instruction files (CLAUDE.md, AGENTS.md).
+    pub fn is_context_file(&self) -> bool {
+        match self.component_type {
+            ComponentType::Agents | ComponentType::Rules | ComponentType::Contexts => true,
+            ComponentType::ConfigFile => self.name.ends_with(".md"),
+            _ => false,
+        }
+    }
+
+    /// Whether the file is loaded into every session rather than on demand:
+    /// rules and the markdown instruction files.
+    pub fn is_always_loaded(&self) -> bool {
+        self.is_context_file() && matches!(self.component_type, ComponentType::Rules | ComponentType::ConfigFile)
+    }
+
+    /// Rough token count of the source, at `BYTES_PER_TOKEN` bytes each.
+    pub fn estimated_tokens(&self) -> u64 {
+        self.size_bytes.div_ceil(BYTES_PER_TOKEN)
+    }
 }
 
+/// Average bytes per token for English prose and code; close enough for
+/// a budget warning without shipping a tokenizer.
+pub const BYTES_PER_TOKEN: u64 = 4;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn token_estimates_cover_context_files_only() {
+        let component = |t: ComponentType, name: &str| {
+            let mut c = Component::new(t, name.into(), PathBuf::new(), PathBuf::new(), InstallStatus::New);
+            c.size_bytes = 4001;
+            c
+        };
+        let rule = component(ComponentType::Rules, "style.md");
+        let agent = component(ComponentType::Agents, "reviewer.md");
+        assert_eq!(rule.estimated_tokens(), 1001);
+        assert!(rule.is_always_loaded());
+        assert!(agent.is_context_file() && !agent.is_always_loaded());
+        assert!(component(ComponentType::ConfigFile, "CLAUDE.md").is_always_loaded());
+        assert!(!component(ComponentType::ConfigFile, "settings.json").is_context_file());
+        assert!(!component(ComponentType::Skills, "a/SKILL.md").is_context_file());
+    }
+
     #[test]
     fn test_hook_config_deprecated_true() {
         let yaml = r#"
@@ -214,6 +349,44 @@ type: command
         assert_eq!(config.deprecated, None);
     }
 
+    #[test]
+    fn test_hook_config_priority() {
+        let yaml = r#"
+name: test-hook
+event: PreToolUse
+type: command
+priority: 10
+"#;
+        let config: HookConfig = serde_yaml_bw::from_str(yaml).unwrap();
+        assert_eq!(config.effective_priority(), 10);
+
+        let yaml = r#"
+name: test-hook
+event: PreToolUse
+type: command
+"#;
+        let config: HookConfig = serde_yaml_bw::from_str(yaml).unwrap();
+        assert_eq!(config.effective_priority(), DEFAULT_HOOK_PRIORITY);
+    }
+
+    #[test]
+    fn test_hook_command_prefixes_quoted_env() {
+        let yaml = r#"
+name: suggest-compact
+event: PreToolUse
+type: command
+env:
+  COMPACT_THRESHOLD: "50"
+  GREETING: hello world
+"#;
+        let config: HookConfig = serde_yaml_bw::from_str(yaml).unwrap();
+        let dest = Path::new("/home/u/.claude");
+        let cmd = config.hook_command(dest, &config.env);
+        let path = config.hook_command_path(dest);
+        assert_eq!(cmd, format!("COMPACT_THRESHOLD=50 GREETING='hello world' {}", path));
+        assert_eq!(config.hook_command(dest, &BTreeMap::new()), path);
+    }
+
     fn make_component(status: InstallStatus) -> Component {
         Component::new(
             ComponentType::Agents,
@@ -250,6 +423,18 @@ type: command
         assert!(!c.is_install_eligible());
     }
 
+    #[test]
+    fn unconfigured_hooks_are_not_selected_or_installed_until_scaffolded() {
+        let c = make_component(InstallStatus::Unconfigured);
+        assert!(!c.selected);
+        assert!(!c.is_install_eligible());
+
+        let config: HookConfig = serde_yaml_bw::from_str(&hook_yaml_template("lint-on-save", "PostToolUse")).unwrap();
+        assert_eq!((config.name.as_str(), config.event.as_str()), ("lint-on-save", "PostToolUse"));
+        assert_eq!(config.hook_type, "command");
+        assert!(config.env.is_empty());
+    }
+
     #[test]
     fn install_not_eligible_for_deprecated_hook() {
         let yaml = r#"
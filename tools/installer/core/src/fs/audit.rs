@@ -0,0 +1,144 @@
+//! Audit log of what the installer changed, for working out after the fact
+//! how an install went wrong. Every file copy, settings.json write and
+//! external command it runs appends one JSON object per line to
+//! `<dest_dir>/sessions/hibi-audit.jsonl`:
+//!
+//! ```text
+//! {"time":"2024-02-29T14:05:09+01:00","kind":"file_copy","action":"install","subject":"/home/me/.claude/agents/a.md","outcome":"ok"}
+//! {"time":"2024-02-29T14:05:11+01:00","kind":"command","action":"install","subject":"claude mcp add ...","outcome":"failed","error":"..."}
+//! ```
+//!
+//! Logging starts once `open` names the config dir being changed. Writing
+//! the log is best effort: a failure to log never fails the change itself.
+//! The log is moved to `hibi-audit.jsonl.1` once it passes
+//! `MAX_LOG_BYTES`, replacing the previous one.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// Log file name inside the sessions dir.
+pub const AUDIT_LOG_FILE: &str = "hibi-audit.jsonl";
+
+/// Size at which the log is rotated.
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+static LOG: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A file written into the config dir: copied, merged or restored.
+    FileCopy,
+    /// A write to settings.json.
+    Settings,
+    /// A CLI or shell command run on the user's behalf.
+    Command,
+    /// MCP server env vars filled in from an env file. Only the names and
+    /// the file are logged, never the values.
+    EnvFile,
+}
+
+#[derive(Serialize)]
+struct Event<'a> {
+    time: String,
+    kind: EventKind,
+    action: &'a str,
+    subject: &'a str,
+    outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Path of the audit log for `dest_dir`.
+pub fn log_path(dest_dir: &Path) -> PathBuf {
+    dest_dir.join("sessions").join(AUDIT_LOG_FILE)
+}
+
+/// Log changes to `dest_dir` from now on.
+pub fn open(dest_dir: &Path) {
+    if let Ok(mut log) = LOG.lock() {
+        *log = Some(log_path(dest_dir));
+    }
+}
+
+/// Append an event for `subject` with the outcome of `result`.
+pub fn record<T, E: Display>(kind: EventKind, action: &str, subject: &str, result: &Result<T, E>) {
+    let Ok(log) = LOG.lock() else { return };
+    let Some(path) = log.as_deref() else { return };
+    let _ = record_to(path, kind, action, subject, result);
+}
+
+/// `record` into the log at `path`.
+fn record_to<T, E: Display>(
+    path: &Path,
+    kind: EventKind,
+    action: &str,
+    subject: &str,
+    result: &Result<T, E>,
+) -> std::io::Result<()> {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let event = Event {
+        time: crate::local_time::machine_timestamp(secs),
+        kind,
+        action,
+        subject,
+        outcome: if result.is_ok() { "ok" } else { "failed" },
+        error: result.as_ref().err().map(|e| format!("{:#}", e)),
+    };
+    append(path, &event)
+}
+
+fn append(path: &Path, event: &Event) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > MAX_LOG_BYTES) {
+        std::fs::rename(path, rotated_path(path))?;
+    }
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+    std::fs::OpenOptions::new().create(true).append(true).open(path)?.write_all(line.as_bytes())
+}
+
+/// Where a full log is moved to.
+pub fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn events_are_appended_as_json_lines() {
+        // Written to an explicit path: the log `open` names is global and
+        // other tests' installs would land in it.
+        let dest = temp_dir("audit");
+        let path = log_path(&dest);
+        let subject = dest.join("agents").join("a.md").to_string_lossy().into_owned();
+        record_to(&path, EventKind::FileCopy, "install", &subject, &Ok::<(), String>(())).unwrap();
+        record_to(&path, EventKind::FileCopy, "install", &subject, &Err::<(), _>("disk full")).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let events: Vec<serde_json::Value> = content.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let _ = std::fs::remove_dir_all(&dest);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["kind"], "file_copy");
+        assert_eq!(events[0]["outcome"], "ok");
+        assert!(events[0].get("error").is_none());
+        assert_eq!(events[1]["outcome"], "failed");
+        assert_eq!(events[1]["error"], "disk full");
+        assert!(events[0]["time"].as_str().is_some_and(|t| t.contains('T')));
+    }
+}
@@ -0,0 +1,243 @@
+//! Snapshots of destination files taken before the installer overwrites them.
+//!
+//! Each install batch gets one snapshot directory under
+//! `<dest>/.hibi/backups/<timestamp>/` (e.g. `~/.claude/.hibi/backups/20260118-093015/`).
+//! Files keep their dest-relative layout inside it, so restoring is a plain
+//! copy back. The directory is only created once something is actually
+//! saved, and older snapshots beyond `MAX_SNAPSHOTS` are pruned at that point.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+use crate::maintenance::now_secs;
+
+/// Directory under the installer state dir (see `state`) that holds all
+/// snapshots.
+pub const BACKUP_DIR_NAME: &str = "backups";
+
+/// Snapshots kept per destination; the oldest are deleted beyond this.
+pub const MAX_SNAPSHOTS: usize = 10;
+
+/// Root of the snapshots for `dest_dir`.
+pub fn backup_root(dest_dir: &Path) -> PathBuf {
+    crate::fs::state::state_dir(dest_dir).join(BACKUP_DIR_NAME)
+}
+
+/// One install batch's backup target. Cheap to create: nothing touches
+/// disk until `save` finds a file worth keeping.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    dest_dir: PathBuf,
+    dir: PathBuf,
+}
+
+impl Snapshot {
+    /// Start a snapshot for `dest_dir`, named after the current UTC time.
+    pub fn new(dest_dir: &Path) -> Self {
+        let root = backup_root(dest_dir);
+        let stamp = format_timestamp(now_secs());
+        // Two batches in the same second get distinct directories.
+        let mut dir = root.join(&stamp);
+        let mut n = 2;
+        while dir.exists() {
+            dir = root.join(format!("{}-{}", stamp, n));
+            n += 1;
+        }
+        Self { dest_dir: dest_dir.to_path_buf(), dir }
+    }
+
+    /// Snapshot directory (may not exist yet).
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Copy `path` into the snapshot before it is overwritten.
+    ///
+    /// No-op when `path` does not exist, lies outside the destination, or
+    /// was already saved in this snapshot — the first copy is the
+    /// pre-batch version, which is the one worth restoring.
+    pub fn save(&self, path: &Path) -> Result<Option<PathBuf>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let Ok(rel) = path.strip_prefix(&self.dest_dir) else {
+            return Ok(None);
+        };
+        let target = self.dir.join(rel);
+        if target.exists() {
+            return Ok(None);
+        }
+
+        let first = !self.dir.exists();
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(path, &target)
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+        if first {
+            prune(&self.dest_dir, MAX_SNAPSHOTS)?;
+        }
+        Ok(Some(target))
+    }
+
+    /// Save `path` only if writing `new_content` would change it.
+    pub fn save_if_changed(&self, path: &Path, new_content: &Path) -> Result<Option<PathBuf>> {
+        match (std::fs::read(path), std::fs::read(new_content)) {
+            (Ok(old), Ok(new)) if old == new => Ok(None),
+            _ => self.save(path),
+        }
+    }
+}
+
+/// A snapshot found on disk.
+#[derive(Clone, Debug)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub dir: PathBuf,
+    /// Dest-relative paths of the files it holds.
+    pub files: Vec<PathBuf>,
+}
+
+/// All snapshots for `dest_dir`, newest first.
+pub fn list_snapshots(dest_dir: &Path) -> Result<Vec<SnapshotInfo>> {
+    let root = backup_root(dest_dir);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(&root)? {
+        let dir = entry?.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let mut files: Vec<PathBuf> = WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.path().strip_prefix(&dir).ok().map(Path::to_path_buf))
+            .collect();
+        files.sort();
+        snapshots.push(SnapshotInfo { name, dir, files });
+    }
+    // `YYYYMMDD-HHMMSS` names sort lexically in age order.
+    snapshots.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(snapshots)
+}
+
+/// Copy every file in snapshot `name` (or the newest one, for `"latest"`)
+/// back into `dest_dir`. Returns the dest paths that were restored.
+pub fn restore(dest_dir: &Path, name: &str) -> Result<Vec<PathBuf>> {
+    let snapshots = list_snapshots(dest_dir)?;
+    let snapshot = if name == "latest" {
+        snapshots.first()
+    } else {
+        snapshots.iter().find(|s| s.name == name)
+    }
+    .with_context(|| format!("No backup named '{}' in {}", name, backup_root(dest_dir).display()))?;
+
+    let mut restored = Vec::new();
+    for rel in &snapshot.files {
+        let target = dest_dir.join(rel);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(snapshot.dir.join(rel), &target)
+            .with_context(|| format!("Failed to restore {}", target.display()))?;
+        restored.push(target);
+    }
+    Ok(restored)
+}
+
+/// Delete all but the newest `keep` snapshots. Returns how many were removed.
+pub fn prune(dest_dir: &Path, keep: usize) -> Result<usize> {
+    let snapshots = list_snapshots(dest_dir)?;
+    let mut removed = 0;
+    for old in snapshots.iter().skip(keep) {
+        std::fs::remove_dir_all(&old.dir)
+            .with_context(|| format!("Failed to prune backup {}", old.dir.display()))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// `YYYYMMDD-HHMMSS` in UTC for a Unix timestamp.
+pub(crate) fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days (Howard Hinnant) for the proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year, month, day, rem / 3600, rem % 3600 / 60, rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn formats_utc_timestamp() {
+        assert_eq!(format_timestamp(0), "19700101-000000");
+        assert_eq!(format_timestamp(1_709_210_096), "20240229-123456");
+    }
+
+    #[test]
+    fn save_keeps_first_version_and_restore_copies_it_back() {
+        let dest = temp_dir("roundtrip");
+        let file = dest.join("agents").join("a.md");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "original").unwrap();
+
+        let snapshot = Snapshot::new(&dest);
+        assert!(snapshot.save(&file).unwrap().is_some());
+        std::fs::write(&file, "second").unwrap();
+        // Already saved in this snapshot: the pre-batch copy wins.
+        assert!(snapshot.save(&file).unwrap().is_none());
+        std::fs::write(&file, "final").unwrap();
+
+        let restored = restore(&dest, "latest").unwrap();
+        let content = std::fs::read_to_string(&file).unwrap();
+        let _ = std::fs::remove_dir_all(&dest);
+
+        assert_eq!(restored, vec![file]);
+        assert_eq!(content, "original");
+    }
+
+    #[test]
+    fn missing_file_creates_no_snapshot() {
+        let dest = temp_dir("missing");
+        let snapshot = Snapshot::new(&dest);
+        assert!(snapshot.save(&dest.join("settings.json")).unwrap().is_none());
+        let exists = snapshot.dir().exists();
+        let _ = std::fs::remove_dir_all(&dest);
+        assert!(!exists);
+    }
+
+    #[test]
+    fn prune_keeps_newest() {
+        let dest = temp_dir("prune");
+        for name in ["20250101-000000", "20250102-000000", "20250103-000000"] {
+            std::fs::create_dir_all(backup_root(&dest).join(name)).unwrap();
+        }
+        let removed = prune(&dest, 2).unwrap();
+        let names: Vec<String> = list_snapshots(&dest).unwrap().into_iter().map(|s| s.name).collect();
+        let _ = std::fs::remove_dir_all(&dest);
+
+        assert_eq!(removed, 1);
+        assert_eq!(names, vec!["20250103-000000", "20250102-000000"]);
+    }
+}
@@ -2,6 +2,8 @@ use std::path::Path;
 use anyhow::{Result, Context};
 use similar::{ChangeTag, TextDiff};
 
+use crate::component::{Component, ComponentType};
+
 /// Normalize path display to remove Windows extended-length prefix
 fn normalize_path_display(path: &Path) -> String {
     let path_str = path.display().to_string();
@@ -18,7 +20,7 @@ fn normalize_path_display(path: &Path) -> String {
 }
 
 /// Check if a file is likely binary by reading first few bytes
-fn is_binary_file(path: &Path) -> Result<bool> {
+pub(crate) fn is_binary_file(path: &Path) -> Result<bool> {
     use std::io::Read;
     let mut file = std::fs::File::open(path)?;
     let mut buffer = [0u8; 512];
@@ -67,7 +69,12 @@ pub fn compare_files(source: &Path, dest: &Path) -> Result<String> {
     let source_content = std::fs::read_to_string(source)
         .with_context(|| format!("Failed to read source file as UTF-8: {}", source.display()))?
         .replace("\r\n", "\n");
+    compare_content(source, &source_content, dest)
+}
 
+/// Diff of `source_content` (what would be installed from `source`)
+/// against the file at `dest`.
+fn compare_content(source: &Path, source_content: &str, dest: &Path) -> Result<String> {
     if !dest.exists() {
         // New file - show all as additions
         let mut output = String::new();
@@ -96,11 +103,11 @@ pub fn compare_files(source: &Path, dest: &Path) -> Result<String> {
         // Files are identical - show the content
         let mut output = String::new();
         output.push_str(&format!("=== {} (identical) ===\n\n", source.display()));
-        output.push_str(&source_content);
+        output.push_str(source_content);
         return Ok(output);
     }
 
-    let diff = TextDiff::from_lines(&dest_content, &source_content);
+    let diff = TextDiff::from_lines(&dest_content, source_content);
 
     let mut output = String::new();
     output.push_str(&format!("--- {}\n", normalize_path_display(dest)));
@@ -119,16 +126,80 @@ pub fn compare_files(source: &Path, dest: &Path) -> Result<String> {
     Ok(output)
 }
 
+/// Diff shown for a component: settings.json is merged on install, so it
+/// previews the merge result rather than a raw file-to-file diff; Cursor
+/// rules are compared in their converted form.
+pub fn component_diff(c: &Component) -> Result<String> {
+    if c.component_type == ComponentType::ConfigFile && c.name == "settings.json" {
+        compare_settings_merge(&c.source_path, &c.dest_path)
+    } else if crate::fs::installer::is_cursor_rule(c) {
+        let converted = crate::fs::installer::cursor_rule_content(&c.source_path)?;
+        compare_content(&c.source_path, &converted, &c.dest_path)
+    } else {
+        compare_files(&c.source_path, &c.dest_path)
+    }
+}
+
+/// Diff for settings.json: the current destination against what the
+/// installer's merge would actually write, rather than the raw source file.
+/// Keys whose existing values the merge replaces are listed up front.
+pub fn compare_settings_merge(source: &Path, dest: &Path) -> Result<String> {
+    let preview = crate::fs::installer::preview_settings_merge(source, dest)
+        .with_context(|| format!("Failed to preview merge into {}", dest.display()))?;
+
+    let mut output = String::new();
+    let Some(current) = preview.current else {
+        output.push_str("--- (new file)\n");
+        output.push_str(&format!("+++ {} (after merge)\n", normalize_path_display(dest)));
+        output.push_str("@@ new file @@\n");
+        for line in preview.merged.lines() {
+            output.push_str(&format!("+{}\n", line));
+        }
+        return Ok(output);
+    };
+
+    if current == preview.merged {
+        output.push_str(&format!("=== {} (merge makes no changes) ===\n\n", normalize_path_display(dest)));
+        output.push_str(&current);
+        return Ok(output);
+    }
+
+    if !preview.overwritten.is_empty() {
+        output.push_str("# Merge overwrites your values for:\n");
+        for key in &preview.overwritten {
+            output.push_str(&format!("#   {}\n", key));
+        }
+        output.push('\n');
+    }
+
+    output.push_str(&format!("--- {} (current)\n", normalize_path_display(dest)));
+    output.push_str(&format!("+++ {} (after merge)\n", normalize_path_display(dest)));
+
+    // Pretty-printed JSON has no trailing newline; add one so the last
+    // line diffs cleanly.
+    let current = format!("{}\n", current);
+    let merged = format!("{}\n", preview.merged);
+    let diff = TextDiff::from_lines(&current, &merged);
+    for change in diff.iter_all_changes() {
+        let sign = match change.tag() {
+            ChangeTag::Delete => "-",
+            ChangeTag::Insert => "+",
+            ChangeTag::Equal => " ",
+        };
+        output.push_str(sign);
+        output.push_str(change.value());
+    }
+
+    Ok(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use crate::test_util::temp_dir;
 
     fn unique_test_file(label: &str, contents: &str) -> std::path::PathBuf {
-        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
-        let dir = std::env::temp_dir().join(format!("hibi_diff_{label}_{nanos}"));
-        std::fs::create_dir_all(&dir).unwrap();
-        let path = dir.join("file.md");
+        let path = temp_dir(label).join("file.md");
         std::fs::write(&path, contents).unwrap();
         path
     }
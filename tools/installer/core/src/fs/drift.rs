@@ -0,0 +1,172 @@
+//! Drift report: every component of one CLI with its status, the content
+//! hashes of its source and installed copy, and when hibi last installed
+//! it. Written as JSON for tooling or as a Markdown table, so reports taken
+//! on several machines can be compared.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::component::Component;
+use crate::fs::backup::format_timestamp;
+use crate::maintenance::now_secs;
+use crate::fs::installer::{install_times, record_key};
+use crate::fs::scanner::hash_content;
+use crate::local_time::machine_timestamp;
+use crate::source::config::contract_tilde;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DriftFormat {
+    Json,
+    Markdown,
+}
+
+impl DriftFormat {
+    /// JSON for `.json` paths, Markdown otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => Self::Json,
+            _ => Self::Markdown,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// `hibi-drift-<timestamp>.<ext>`, timestamped in UTC.
+pub fn default_file_name(format: DriftFormat) -> String {
+    format!("hibi-drift-{}.{}", format_timestamp(now_secs()), format.extension())
+}
+
+#[derive(Debug, Serialize)]
+pub struct DriftEntry {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    pub name: String,
+    pub status: String,
+    /// `None` for files in dest that no source produces.
+    pub source: Option<String>,
+    pub dest: String,
+    pub source_hash: Option<String>,
+    pub dest_hash: Option<String>,
+    /// ISO 8601 time (with offset) of the last install through hibi, when
+    /// recorded.
+    pub installed_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DriftReport {
+    pub generated_at: String,
+    pub host: Option<String>,
+    pub target: String,
+    pub dest_dir: String,
+    pub components: Vec<DriftEntry>,
+}
+
+/// Drift of `components` installed for `target` into `dest_dir`. Hashes are
+/// blake3 of the content with carriage returns dropped; a file that can't
+/// be read has none.
+pub fn drift_report(components: &[Component], target: &str, dest_dir: &Path) -> DriftReport {
+    let times = install_times(dest_dir);
+    let hash = |path: &Path| path.is_file().then(|| hash_content(path).ok()).flatten();
+    let components = components.iter()
+        .map(|c| {
+            let external = c.source_path == c.dest_path;
+            DriftEntry {
+                component_type: c.component_type.display_name().to_string(),
+                name: c.name.clone(),
+                status: c.status.display().to_string(),
+                source: (!external).then(|| contract_tilde(&c.source_path)),
+                dest: contract_tilde(&c.dest_path),
+                source_hash: if external { None } else { hash(&c.source_path) },
+                dest_hash: hash(&c.dest_path),
+                installed_at: record_key(dest_dir, &c.dest_path)
+                    .and_then(|key| times.get(&key).copied())
+                    .map(machine_timestamp),
+            }
+        })
+        .collect();
+    DriftReport {
+        generated_at: machine_timestamp(now_secs()),
+        host: crate::fs::hosts::host_name(),
+        target: target.to_string(),
+        dest_dir: contract_tilde(dest_dir),
+        components,
+    }
+}
+
+pub fn render_drift_report(report: &DriftReport, format: DriftFormat) -> String {
+    match format {
+        DriftFormat::Json => serde_json::to_string_pretty(report).unwrap_or_default() + "\n",
+        DriftFormat::Markdown => render_markdown(report),
+    }
+}
+
+/// First 12 hex digits, enough to spot a difference in a table.
+fn short_hash(hash: &Option<String>) -> String {
+    hash.as_deref().map_or("-".to_string(), |h| format!("`{}`", &h[..h.len().min(12)]))
+}
+
+fn render_markdown(report: &DriftReport) -> String {
+    let mut out = format!(
+        "# hibi drift report\n\n{} on {}, `{}`, {}\n\n",
+        report.target,
+        report.host.as_deref().unwrap_or("unknown host"),
+        report.dest_dir,
+        report.generated_at,
+    );
+    out.push_str("| Component | Status | Source hash | Dest hash | Installed |\n|---|---|---|---|---|\n");
+    for entry in &report.components {
+        out.push_str(&format!(
+            "| {}/{} | {} | {} | {} | {} |\n",
+            entry.component_type,
+            entry.name.replace('|', "\\|"),
+            entry.status,
+            short_hash(&entry.source_hash),
+            short_hash(&entry.dest_hash),
+            entry.installed_at.as_deref().unwrap_or("-"),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::component::{ComponentType, InstallStatus};
+
+    #[test]
+    fn report_lists_hashes_and_install_times() {
+        let dir = temp_dir("drift");
+        let (source, dest) = (dir.join("src"), dir.join("dest"));
+        std::fs::create_dir_all(source.join("agents")).unwrap();
+        std::fs::create_dir_all(dest.join("agents")).unwrap();
+        std::fs::write(source.join("agents/a.md"), "same\n").unwrap();
+        std::fs::write(dest.join("agents/a.md"), "same\r\n").unwrap();
+        std::fs::write(dest.join("agents/mine.md"), "mine\n").unwrap();
+        std::fs::create_dir_all(dest.join(".hibi")).unwrap();
+        std::fs::write(dest.join(".hibi/installed.yaml"), "files: [agents/a.md]\ninstalled_at: {agents/a.md: 1709210096}\n").unwrap();
+        let components = vec![
+            Component::new(ComponentType::Agents, "a.md".into(), source.join("agents/a.md"), dest.join("agents/a.md"), InstallStatus::Unchanged),
+            Component::new(ComponentType::Agents, "mine.md".into(), dest.join("agents/mine.md"), dest.join("agents/mine.md"), InstallStatus::External),
+        ];
+
+        let report = drift_report(&components, "Claude Code", &dest);
+        let json: serde_json::Value = serde_json::from_str(&render_drift_report(&report, DriftFormat::Json)).unwrap();
+        let markdown = render_drift_report(&report, DriftFormat::Markdown);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let installed = &json["components"][0];
+        assert_eq!(installed["source_hash"], installed["dest_hash"], "line endings don't count as drift");
+        assert_eq!(installed["installed_at"], "2024-02-29T12:34:56Z");
+        assert_eq!(json["components"][1]["source"], serde_json::Value::Null);
+        assert_eq!(json["components"][1]["installed_at"], serde_json::Value::Null);
+        assert!(markdown.contains("| agents/mine.md | external | - | `"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Summary of how an agent's or skill's YAML frontmatter differs between
+//! its installed copy and its source, shown above the diff: a line diff of
+//! a long `keywords:` list or a reflowed description is hard to read.
+//!
+//! Entries are compared as text, split at unindented `key:` lines, so
+//! frontmatter that isn't strictly valid YAML (unquoted colons in a
+//! description are common) still summarizes.
+
+use crate::component::{Component, ComponentType};
+use super::keywords::parse_keywords;
+
+/// Longest value quoted in a summary line before it is cut.
+const VALUE_PREVIEW_CHARS: usize = 48;
+
+/// Top-level `(key, entry text)` pairs of `content`'s frontmatter, in
+/// order; entry text is the value plus any indented continuation lines.
+fn entries(content: &str) -> Vec<(String, String)> {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return Vec::new();
+    }
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for line in lines.take_while(|l| l.trim_end() != "---") {
+        let continuation = line.starts_with(char::is_whitespace) || line.trim_start().starts_with('#');
+        match (continuation, line.split_once(':'), entries.last_mut()) {
+            (false, Some((key, value)), _) if !key.contains(' ') => {
+                entries.push((key.to_string(), value.trim().to_string()));
+            }
+            (_, _, Some((_, text))) => {
+                text.push('\n');
+                text.push_str(line.trim());
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+fn preview(value: &str) -> String {
+    let flat = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > VALUE_PREVIEW_CHARS {
+        format!("{}…", flat.chars().take(VALUE_PREVIEW_CHARS).collect::<String>())
+    } else {
+        flat
+    }
+}
+
+/// One line per frontmatter entry that installing `source` over
+/// `installed` would change; empty when the frontmatter is the same.
+pub fn frontmatter_changes(installed: &str, source: &str) -> Vec<String> {
+    let (old, new) = (entries(installed), entries(source));
+    let find = |entries: &[(String, String)], key: &str| {
+        entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    };
+
+    let mut changes = Vec::new();
+    for (key, value) in &new {
+        match find(&old, key) {
+            Some(previous) if previous == *value => {}
+            Some(_) if key == "keywords" => {
+                let (before, after) = (parse_keywords(installed), parse_keywords(source));
+                let added: Vec<String> = after.iter().filter(|k| !before.contains(k)).map(|k| format!("+{}", k)).collect();
+                let removed: Vec<String> = before.iter().filter(|k| !after.contains(k)).map(|k| format!("-{}", k)).collect();
+                if added.is_empty() && removed.is_empty() {
+                    changes.push("keywords: reordered".to_string());
+                } else {
+                    changes.push(format!("keywords: {}", [added, removed].concat().join(" ")));
+                }
+            }
+            Some(previous) => changes.push(format!("{}: {} → {}", key, preview(&previous), preview(value))),
+            None => changes.push(format!("{} added: {}", key, preview(value))),
+        }
+    }
+    for (key, _) in old.iter().filter(|(key, _)| find(&new, key).is_none()) {
+        changes.push(format!("{} removed", key));
+    }
+    changes
+}
+
+/// `frontmatter_changes` for an installed agent or skill file; empty for
+/// other types and for files missing on either side.
+pub fn component_frontmatter_changes(c: &Component) -> Vec<String> {
+    if !matches!(c.component_type, ComponentType::Agents | ComponentType::Skills) || c.source_path == c.dest_path {
+        return Vec::new();
+    }
+    match (std::fs::read_to_string(&c.dest_path), std::fs::read_to_string(&c.source_path)) {
+        (Ok(installed), Ok(source)) => frontmatter_changes(&installed, &source),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_changes_are_summarized_per_entry() {
+        let installed = "---\nname: reviewer\ndescription: Reviews code.\nkeywords:\n  - review\n  - lint\ncolor: red\n---\nbody\n";
+        let source = "---\nname: reviewer\ndescription: Reviews code: style, bugs and tests.\nkeywords: [review, tests, security]\nmodel: opus\n---\nnew body\n";
+
+        assert_eq!(frontmatter_changes(installed, source), [
+            "description: Reviews code. → Reviews code: style, bugs and tests.",
+            "keywords: +tests +security -lint",
+            "model added: opus",
+            "color removed",
+        ]);
+        assert!(frontmatter_changes(installed, &installed.replace("body", "other")).is_empty());
+        assert!(frontmatter_changes("no frontmatter", "# still none").is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+//! The hooks registered in a CLI's settings.json as a POSIX shell script
+//! that registers them again with `jq`, for machines without hibi. Each
+//! entry is annotated from its hook.yaml when hibi knows the hook; entries
+//! added by hand are exported as they are.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::component::HookConfig;
+use crate::fs::backup::format_timestamp;
+use crate::maintenance::now_secs;
+use crate::fs::installer::split_env_prefix;
+use crate::source::config::contract_tilde;
+
+/// `hibi-hooks-<timestamp>.sh`, timestamped in UTC.
+pub fn default_file_name() -> String {
+    format!("hibi-hooks-{}.sh", format_timestamp(now_secs()))
+}
+
+/// Shell-quote `s` for the script; falls back to double quotes for text
+/// `shlex` refuses (a NUL byte), which settings.json can't hold anyway.
+fn quote(s: &str) -> String {
+    shlex::try_quote(s).map(|q| q.into_owned()).unwrap_or_else(|_| format!("{:?}", s))
+}
+
+const PRELUDE: &str = r#"set -eu
+
+command -v jq >/dev/null 2>&1 || { echo "jq is required" >&2; exit 1; }
+[ -f "$SETTINGS" ] || { mkdir -p "$(dirname "$SETTINGS")"; echo '{}' > "$SETTINGS"; }
+
+# add_hook EVENT ENTRY: append ENTRY to .hooks[EVENT] unless an identical
+# entry is registered already, so the script can be run twice.
+add_hook() {
+    tmp=$(mktemp)
+    jq --arg event "$1" --argjson entry "$2" '
+        .hooks[$event] //= []
+        | if any(.hooks[$event][]; . == $entry) then . else .hooks[$event] += [$entry] end
+    ' "$SETTINGS" > "$tmp"
+    mv "$tmp" "$SETTINGS"
+}
+"#;
+
+/// The script for the hooks registered in `<dest_dir>/settings.json`, in
+/// their execution order. `configs` are the hook.yaml files hibi knows,
+/// matched to entries by the program their command runs.
+pub fn hooks_script(dest_dir: &Path, configs: &[HookConfig]) -> Result<String> {
+    let settings_path = dest_dir.join("settings.json");
+    let settings: Value = match std::fs::read_to_string(&settings_path) {
+        Ok(content) => crate::fs::jsonc::parse(&content)
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))?,
+        Err(_) => Value::Object(Default::default()),
+    };
+    let dir_name = dest_dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let mut out = format!(
+        "#!/bin/sh\n\
+         # Hooks registered in {}, exported by hibi on {}.\n\
+         # Copy each hook's program to the path its command runs, then run\n\
+         # this script to register the hooks. Set SETTINGS to write elsewhere.\n\n\
+         SETTINGS=\"${{SETTINGS:-$HOME/{}/settings.json}}\"\n",
+        contract_tilde(&settings_path),
+        crate::local_time::machine_timestamp(now_secs()),
+        dir_name,
+    );
+    out.push_str(PRELUDE);
+
+    let mut count = 0;
+    if let Some(Value::Object(events)) = settings.get("hooks") {
+        for (event, entries) in events {
+            let Value::Array(entries) = entries else { continue };
+            for (i, entry) in entries.iter().enumerate() {
+                out.push('\n');
+                out.push_str(&annotation(event, i + 1, entry, dest_dir, configs));
+                let json = serde_json::to_string(entry)?;
+                out.push_str(&format!("add_hook {} {}\n", quote(event), quote(&json)));
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        out.push_str("\n# No hooks are registered.\n");
+    }
+    Ok(out)
+}
+
+/// Comment lines above one `add_hook`: where it runs, and what hibi knows
+/// about the hook, with control characters turned into spaces.
+fn annotation(event: &str, position: usize, entry: &Value, dest_dir: &Path, configs: &[HookConfig]) -> String {
+    let commands: Vec<&str> = entry.get("hooks")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|hook| hook.get("command").and_then(Value::as_str))
+        .collect();
+    let config = commands.iter().find_map(|cmd| {
+        let (_, program) = split_env_prefix(cmd);
+        configs.iter().find(|c| c.hook_command_path(dest_dir) == program)
+    });
+
+    let mut lines = vec![match config {
+        Some(config) => format!("# {} #{}: {}", event, position, config.name),
+        None => format!("# {} #{}: not managed by hibi", event, position),
+    }];
+    if let Some(description) = config.and_then(|c| c.description.as_deref()) {
+        lines.push(format!("#   {}", description));
+    }
+    if let Some(matcher) = entry.get("matcher").and_then(Value::as_str) {
+        lines.push(format!("#   matcher: {}", matcher));
+    }
+    for cmd in &commands {
+        let (env, program) = split_env_prefix(cmd);
+        lines.push(format!("#   runs: {}", program));
+        if !env.is_empty() {
+            let env: Vec<String> = env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            lines.push(format!("#   env: {}", env.join(" ")));
+        }
+    }
+    // A newline in a name, description or command would end the comment
+    // and run the rest as shell.
+    let lines: Vec<String> = lines.iter().map(|line| line.replace(char::is_control, " ")).collect();
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn script_registers_every_entry_with_annotations() {
+        let dest = temp_dir("hooks_script").join(".claude");
+        std::fs::create_dir_all(&dest).unwrap();
+        let config: HookConfig = serde_yaml_bw::from_str(
+            "name: guard\nevent: PreToolUse\ntype: command\ndescription: Blocks rm -rf\n",
+        ).unwrap();
+        let program = config.hook_command_path(&dest);
+        std::fs::write(dest.join("settings.json"), serde_json::json!({
+            "hooks": {
+                "PreToolUse": [
+                    {"matcher": "Bash", "hooks": [{"type": "command", "command": format!("LEVEL='it''s on' {}", program)}]},
+                ],
+                "Stop": [{"hooks": [{"type": "command", "command": "notify-send done"}]}],
+            }
+        }).to_string()).unwrap();
+
+        let script = hooks_script(&dest, &[config]).unwrap();
+        let _ = std::fs::remove_dir_all(dest.parent().unwrap());
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("SETTINGS=\"${SETTINGS:-$HOME/.claude/settings.json}\""));
+        assert!(script.contains("# PreToolUse #1: guard\n#   Blocks rm -rf\n#   matcher: Bash\n"));
+        assert!(script.contains(&format!("#   runs: {}\n#   env: LEVEL=its on\n", program)));
+        assert!(script.contains("# Stop #1: not managed by hibi\n#   runs: notify-send done\n"));
+        assert!(script.contains(r#"add_hook Stop '{"hooks":[{"command":"notify-send done","type":"command"}]}'"#));
+        assert_eq!(script.matches("\nadd_hook ").count(), 2);
+    }
+
+    #[test]
+    fn annotations_stay_on_comment_lines() {
+        let dest = std::path::Path::new("/home/me/.claude");
+        let config: HookConfig = serde_yaml_bw::from_str(
+            "name: guard\nevent: Stop\ntype: command\ndescription: \"Blocks\\nrm -rf ~\\r\"\n",
+        ).unwrap();
+        let entry = serde_json::json!({"hooks": [
+            {"type": "command", "command": config.hook_command_path(dest)},
+            {"type": "command", "command": "echo hi\nrm -rf ~"},
+        ]});
+
+        let comment = annotation("Stop", 1, &entry, dest, &[config]);
+
+        assert!(comment.contains("#   Blocks rm -rf ~ \n"), "{comment}");
+        assert!(comment.contains("#   runs: echo hi rm -rf ~\n"), "{comment}");
+        assert!(comment.lines().all(|line| line.starts_with('#')), "{comment}");
+    }
+}
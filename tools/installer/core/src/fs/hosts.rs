@@ -0,0 +1,91 @@
+//! `hosts:` filters, for dotfiles shared by several machines. A component
+//! lists hostname globs in its frontmatter (or its hook.yaml); on other
+//! machines it is left unselected and hidden from the list until the
+//! filter is switched off. Globs match case-insensitively against the full
+//! hostname and its first label, so `work-*` matches `work-mbp.local`.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::fs::scanner::glob_match;
+
+/// Frontmatter is read from this many leading bytes of a file.
+const FRONTMATTER_READ_LIMIT: u64 = 8 * 1024;
+
+/// Best-effort machine name, to tell reports from several machines apart.
+pub fn host_name() -> Option<String> {
+    std::env::var("HOSTNAME").ok()
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            let output = super::run_with_timeout(&mut Command::new("hostname"), 5).ok()?;
+            output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+        })
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// This machine's name, lowercased; empty when it can't be found.
+pub fn this_host() -> &'static str {
+    static HOST: OnceLock<String> = OnceLock::new();
+    HOST.get_or_init(|| host_name().unwrap_or_default().to_lowercase())
+}
+
+/// Whether `host` matches any of `patterns`; an empty list matches every
+/// host, and so does an unknown (empty) host name.
+pub fn host_matches(patterns: &[String], host: &str) -> bool {
+    if patterns.is_empty() || host.is_empty() {
+        return true;
+    }
+    let host = host.to_lowercase();
+    let short = host.split('.').next().unwrap_or(&host);
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.to_lowercase();
+        glob_match(pattern.as_bytes(), host.as_bytes()) || glob_match(pattern.as_bytes(), short.as_bytes())
+    })
+}
+
+/// The `hosts:` list in the frontmatter of `path` (a skill directory's
+/// `SKILL.md`); empty for files without one.
+pub fn read_hosts(path: &Path) -> Vec<String> {
+    let file = if path.is_dir() { path.join("SKILL.md") } else { path.to_path_buf() };
+    if file.extension().and_then(|e| e.to_str()) != Some("md") {
+        return Vec::new();
+    }
+    let mut head = String::new();
+    let read = std::fs::File::open(&file)
+        .and_then(|f| f.take(FRONTMATTER_READ_LIMIT).read_to_string(&mut head));
+    match read {
+        Ok(_) => super::keywords::parse_list(&head, "hosts"),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn globs_match_the_full_and_short_host_name() {
+        let hosts = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(host_matches(&[], "anything"));
+        assert!(host_matches(&hosts(&["work-*"]), "Work-MBP.local"));
+        assert!(host_matches(&hosts(&["*.corp.example.com"]), "build01.corp.example.com"));
+        assert!(host_matches(&hosts(&["home", "nas?"]), "nas2"));
+        assert!(!host_matches(&hosts(&["home", "nas?"]), "nas10"));
+        assert!(!host_matches(&hosts(&["work-*"]), "home-desktop"));
+        assert!(host_matches(&hosts(&["work-*"]), ""));
+
+        let dir = temp_dir("hosts");
+        std::fs::create_dir_all(dir.join("skill")).unwrap();
+        std::fs::write(dir.join("agent.md"), "---\nname: a\nhosts: [work-*, build?]\n---\nbody\n").unwrap();
+        std::fs::write(dir.join("skill/SKILL.md"), "---\nhosts:\n  - home\n---\n").unwrap();
+        assert_eq!(read_hosts(&dir.join("agent.md")), ["work-*", "build?"]);
+        assert_eq!(read_hosts(&dir.join("skill")), ["home"]);
+        assert!(read_hosts(&dir.join("missing.md")).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,171 @@
+//! Hunk-level apply from the diff view: the change from an installed file
+//! to what an install would write is split into hunks, and the file can be
+//! rewritten with only the hunks the user picked.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use similar::{ChangeTag, DiffTag, TextDiff};
+
+use crate::component::{Component, ComponentType};
+use crate::fs::backup::Snapshot;
+use crate::fs::diff::is_binary_file;
+
+/// Unchanged lines shown around each hunk, as in `diff -u`.
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hunk {
+    /// `@@ -old_start,old_len +new_start,new_len @@`
+    pub header: String,
+    /// Lines prefixed with `+`, `-` or a space, without their newline.
+    pub lines: Vec<String>,
+}
+
+/// The change from a file's current content to its new content, in hunks.
+#[derive(Clone, Debug)]
+pub struct HunkDiff {
+    old: String,
+    new: String,
+    /// The file has CRLF line endings: both sides are diffed with LF and
+    /// the applied content is written back with CRLF.
+    crlf: bool,
+    pub hunks: Vec<Hunk>,
+}
+
+impl HunkDiff {
+    pub fn new(old: &str, new: &str) -> Self {
+        let diff = TextDiff::from_lines(old, new);
+        let hunks = diff.grouped_ops(CONTEXT_LINES)
+            .iter()
+            .map(|group| {
+                let (first, last) = (&group[0], &group[group.len() - 1]);
+                let old_start = first.old_range().start;
+                let new_start = first.new_range().start;
+                let header = format!(
+                    "@@ -{},{} +{},{} @@",
+                    old_start + 1,
+                    last.old_range().end - old_start,
+                    new_start + 1,
+                    last.new_range().end - new_start,
+                );
+                let lines = group.iter()
+                    .flat_map(|op| diff.iter_changes(op))
+                    .map(|change| {
+                        let sign = match change.tag() {
+                            ChangeTag::Delete => '-',
+                            ChangeTag::Insert => '+',
+                            ChangeTag::Equal => ' ',
+                        };
+                        format!("{}{}", sign, change.value().trim_end_matches('\n'))
+                    })
+                    .collect();
+                Hunk { header, lines }
+            })
+            .collect();
+        Self { old: old.to_string(), new: new.to_string(), crlf: false, hunks }
+    }
+
+    /// The old content with only the hunks flagged in `selected` applied.
+    pub fn apply(&self, selected: &[bool]) -> String {
+        let diff = TextDiff::from_lines(&self.old, &self.new);
+        let mut hunk_of = HashMap::new();
+        for (i, group) in diff.grouped_ops(CONTEXT_LINES).iter().enumerate() {
+            for op in group.iter().filter(|op| op.tag() != DiffTag::Equal) {
+                hunk_of.insert((op.old_range().start, op.new_range().start), i);
+            }
+        }
+
+        let mut out = String::new();
+        for op in diff.ops() {
+            let take_new = op.tag() != DiffTag::Equal
+                && hunk_of.get(&(op.old_range().start, op.new_range().start))
+                    .is_some_and(|&i| selected.get(i).copied().unwrap_or(false));
+            if take_new {
+                op.new_range().filter_map(|i| diff.new_slice(i)).for_each(|l| out.push_str(l));
+            } else {
+                op.old_range().filter_map(|i| diff.old_slice(i)).for_each(|l| out.push_str(l));
+            }
+        }
+        if self.crlf { out.replace('\n', "\r\n") } else { out }
+    }
+}
+
+/// Hunks between `c`'s installed file and what installing it would write.
+/// `None` when the component isn't applied as a plain text file (new,
+/// external, binary, or settings.json, which is merged) or nothing differs.
+pub fn component_hunks(c: &Component) -> Result<Option<HunkDiff>> {
+    let (source, dest) = (&c.source_path, &c.dest_path);
+    if source == dest
+        || !source.is_file()
+        || !dest.is_file()
+        || (c.component_type == ComponentType::ConfigFile && c.name == "settings.json")
+        || is_binary_file(source).unwrap_or(true)
+        || is_binary_file(dest).unwrap_or(true)
+    {
+        return Ok(None);
+    }
+
+    let new = if crate::fs::installer::is_cursor_rule(c) {
+        crate::fs::installer::cursor_rule_content(source)?
+    } else {
+        read_text(source)?
+    };
+    let old = read_text(dest)?;
+    let mut hunks = HunkDiff::new(&old.replace("\r\n", "\n"), &new.replace("\r\n", "\n"));
+    hunks.crlf = old.contains("\r\n");
+    Ok((!hunks.hunks.is_empty()).then_some(hunks))
+}
+
+/// Write `c`'s dest with only the `selected` hunks applied, backing up
+/// the current file under `dest_dir` first.
+pub fn apply_component_hunks(dest_dir: &Path, c: &Component, hunks: &HunkDiff, selected: &[bool]) -> Result<()> {
+    Snapshot::new(dest_dir).save(&c.dest_path)?;
+    std::fs::write(&c.dest_path, hunks.apply(selected))
+        .with_context(|| format!("Failed to write {}", c.dest_path.display()))
+}
+
+fn read_text(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn only_selected_hunks_are_applied() {
+        let old: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        let new = old.replace("line 2\n", "line two\n").replace("line 18\n", "line eighteen\n");
+        let diff = HunkDiff::new(&old, &new);
+
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!(diff.hunks[0].header, "@@ -1,5 +1,5 @@");
+        assert!(diff.hunks[0].lines.contains(&"-line 2".to_string()));
+        assert!(diff.hunks[0].lines.contains(&"+line two".to_string()));
+
+        assert_eq!(diff.apply(&[true, true]), new);
+        assert_eq!(diff.apply(&[false, false]), old);
+        let second_only = diff.apply(&[false, true]);
+        assert!(second_only.contains("line 2\n") && second_only.contains("line eighteen\n"));
+    }
+
+    #[test]
+    fn crlf_files_keep_their_line_endings() {
+        let dir = temp_dir("hunks_crlf");
+        let (source, dest) = (dir.join("source.md"), dir.join("dest.md"));
+        std::fs::write(&source, "one\ntwo\nthree\n").unwrap();
+        std::fs::write(&dest, "one\r\n2\r\nthree\r\n").unwrap();
+        let c = Component::new(
+            ComponentType::Agents, "a.md".into(), source, dest, crate::component::InstallStatus::Modified,
+        );
+
+        let diff = component_hunks(&c).unwrap().unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(diff.hunks.len(), 1);
+        assert_eq!(diff.hunks[0].lines, [" one", "-2", "+two", " three"]);
+        assert_eq!(diff.apply(&[true]), "one\r\ntwo\r\nthree\r\n");
+    }
+}
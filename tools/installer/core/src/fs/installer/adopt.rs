@@ -0,0 +1,60 @@
+//! Pull an edited installed file back into its source ("adopt").
+//!
+//! The reverse of an install: when a component is Modified because its
+//! installed copy was edited in place, the installed content replaces the
+//! source file, so the edit lands in e.g. a dotfiles repo instead of being
+//! overwritten by the next install.
+
+use anyhow::{bail, Context, Result};
+
+use crate::component::Component;
+
+use super::cursor::is_cursor_rule;
+use super::{preserve_mtime, readonly};
+
+/// Copy `component`'s installed file over its source file. The source then
+/// also takes the installed file's mtime, so the pair scans as unchanged
+/// without being read.
+pub fn adopt_installed_version(component: &Component) -> Result<()> {
+    if is_cursor_rule(component) {
+        bail!("{} is rewritten for Cursor when installed; edit its source instead", component.name);
+    }
+    if !component.dest_path.is_file() {
+        bail!("{} is not installed", component.dest_path.display());
+    }
+    if !component.source_path.is_file() {
+        bail!("Source file {} not found", component.source_path.display());
+    }
+
+    readonly::write_over_readonly(&component.source_path, || {
+        std::fs::copy(&component.dest_path, &component.source_path)
+    })
+    .with_context(|| format!("Failed to copy {} into the source", component.dest_path.display()))?;
+    preserve_mtime(&component.dest_path, &component.source_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::component::{ComponentType, InstallStatus};
+
+    #[test]
+    fn installed_edits_replace_the_source_file() {
+        let dir = temp_dir("adopt");
+        let (source, dest) = (dir.join("source.md"), dir.join("dest.md"));
+        std::fs::write(&source, "original\n").unwrap();
+        std::fs::write(&dest, "edited in place\n").unwrap();
+        let component = Component::new(ComponentType::Agents, "a.md".into(), source.clone(), dest, InstallStatus::Modified);
+
+        adopt_installed_version(&component).unwrap();
+        let adopted = std::fs::read_to_string(&source).unwrap();
+        let missing = Component::new(ComponentType::Agents, "b.md".into(), source, dir.join("gone.md"), InstallStatus::Modified);
+        let err = adopt_installed_version(&missing).unwrap_err().to_string();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(adopted, "edited in place\n");
+        assert!(err.contains("is not installed"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Merge bases: a copy of each text file as hibi last installed it, kept
+//! under `<dest_dir>/.hibi/base/` by its dest-relative path. When both the
+//! source and the installed copy changed since, the two sets of edits are
+//! merged three-way instead of the source overwriting the local ones.
+//!
+//! Edits merge when they touch separate parts of the base; edits to the
+//! same or adjacent lines are a conflict, and the file is left to the
+//! keep/overwrite prompt as before. Files installed before bases were kept
+//! have none, so their local edits can't be told apart from upstream ones.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+use crate::component::Component;
+use super::installed::record_key;
+
+/// Directory inside the installer state dir (see `state`).
+pub const BASE_DIR: &str = "base";
+
+fn base_path(dest_dir: &Path, dest_path: &Path) -> Option<PathBuf> {
+    let key = record_key(dest_dir, dest_path)?;
+    Some(crate::fs::state::state_dir(dest_dir).join(BASE_DIR).join(key))
+}
+
+/// Remember the source of a just-installed text file as its merge base.
+/// Binary files keep none.
+pub(super) fn save_base(dest_dir: &Path, component: &Component) -> Result<()> {
+    let Some(path) = base_path(dest_dir, &component.dest_path) else { return Ok(()) };
+    let Ok(content) = std::fs::read_to_string(&component.source_path) else {
+        return forget_base(dest_dir, component);
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+pub(super) fn forget_base(dest_dir: &Path, component: &Component) -> Result<()> {
+    match base_path(dest_dir, &component.dest_path) {
+        Some(path) if path.exists() => std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove {}", path.display())),
+        _ => Ok(()),
+    }
+}
+
+/// The installed copy of `component` as last installed, if kept.
+fn read_base(dest_dir: &Path, component: &Component) -> Option<String> {
+    std::fs::read_to_string(base_path(dest_dir, &component.dest_path)?).ok()
+}
+
+/// Whether the installed copy was edited since hibi installed it; `true`
+/// when there is no base to tell.
+pub fn has_local_edits(component: &Component, dest_dir: &Path) -> bool {
+    match (read_base(dest_dir, component), std::fs::read_to_string(&component.dest_path)) {
+        (Some(base), Ok(local)) => base != local,
+        _ => true,
+    }
+}
+
+/// The source with the installed copy's local edits merged in, when there
+/// are local edits and they don't conflict with the source's.
+pub fn merged_content(component: &Component, dest_dir: &Path) -> Option<String> {
+    let base = read_base(dest_dir, component)?;
+    let local = std::fs::read_to_string(&component.dest_path).ok()?;
+    let source = std::fs::read_to_string(&component.source_path).ok()?;
+    if local == base {
+        return None;
+    }
+    merge3(&base, &local, &source)
+}
+
+/// One edit against the base: lines `range` replaced by `lines`.
+#[derive(PartialEq)]
+struct Edit<'a> {
+    range: Range<usize>,
+    lines: Vec<&'a str>,
+}
+
+/// Edits that turn `base` into `other`, in base order. Consecutive diff
+/// ops are joined, so a replaced block is one edit.
+fn edits<'a>(base: &[&str], other: &[&'a str]) -> Vec<Edit<'a>> {
+    let mut edits: Vec<Edit> = Vec::new();
+    let mut joinable = false;
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal { .. } = op {
+            joinable = false;
+            continue;
+        }
+        let (old, new) = (op.old_range(), op.new_range());
+        match edits.last_mut() {
+            Some(last) if joinable => {
+                last.range.end = old.end;
+                last.lines.extend(&other[new]);
+            }
+            _ => edits.push(Edit { range: old, lines: other[new].to_vec() }),
+        }
+        joinable = true;
+    }
+    edits
+}
+
+/// Three-way merge of `local` and `source`, both descended from `base`,
+/// by line. `None` when an edit on each side touches the same or
+/// adjacent base lines, unless both made the identical edit.
+pub fn merge3(base: &str, local: &str, source: &str) -> Option<String> {
+    let base: Vec<&str> = base.split_inclusive('\n').collect();
+    let local: Vec<&str> = local.split_inclusive('\n').collect();
+    let source: Vec<&str> = source.split_inclusive('\n').collect();
+    let (ours, theirs) = (edits(&base, &local), edits(&base, &source));
+
+    let mut merged = String::new();
+    let mut pos = 0;
+    let (mut ours, mut theirs) = (ours.iter().peekable(), theirs.iter().peekable());
+    loop {
+        let edit = match (ours.peek(), theirs.peek()) {
+            (Some(a), Some(b)) if a == b => {
+                theirs.next();
+                ours.next()
+            }
+            (Some(a), Some(b)) if a.range.start <= b.range.end && b.range.start <= a.range.end => return None,
+            (Some(a), Some(b)) if a.range.start < b.range.start => ours.next(),
+            (Some(_), Some(_)) | (None, Some(_)) => theirs.next(),
+            (Some(_), None) => ours.next(),
+            (None, None) => break,
+        }?;
+        merged.extend(base[pos..edit.range.start].iter().copied());
+        merged.extend(edit.lines.iter().copied());
+        pos = edit.range.end;
+    }
+    merged.extend(base[pos..].iter().copied());
+    Some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separate_edits_merge_and_overlapping_ones_conflict() {
+        let base = "title\n\none\ntwo\nthree\n\nfour\nfive\n";
+        let local = "title\n\none\ntwo (mine)\nthree\n\nfour\nfive\n";
+        let source = "title\n\none\ntwo\nthree\n\nfour\nfive\nsix\n";
+        assert_eq!(merge3(base, local, source).as_deref(), Some("title\n\none\ntwo (mine)\nthree\n\nfour\nfive\nsix\n"));
+        assert_eq!(merge3(base, source, source).as_deref(), Some(source));
+        assert_eq!(merge3(base, base, source).as_deref(), Some(source));
+
+        let upstream = "title\n\none\ntwo (theirs)\nthree\n\nfour\nfive\n";
+        assert_eq!(merge3(base, local, upstream), None);
+        let adjacent = "title\n\none\ntwo\nthree (theirs)\n\nfour\nfive\n";
+        assert_eq!(merge3(base, local, adjacent), None);
+    }
+}
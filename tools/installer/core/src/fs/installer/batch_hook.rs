@@ -0,0 +1,67 @@
+use std::process::Command;
+use std::sync::mpsc::{Receiver, Sender};
+use anyhow::Result;
+
+use super::process::{spawn_cancelable_process, ProcessConfig};
+
+/// Budget for a single pre/post batch command. Generous enough for a
+/// `git pull` over a slow link; Esc cancels sooner.
+pub const BATCH_HOOK_TIMEOUT_SECS: u64 = 120;
+
+/// Run a user-configured batch hook through the platform shell, so `~`,
+/// pipes and `&&` behave as they would in a terminal. Output lines are
+/// streamed to `output_tx`; a non-zero exit is an error.
+pub fn run_batch_hook(
+    command_line: &str,
+    cancel_rx: &Receiver<()>,
+    output_tx: Option<&Sender<String>>,
+) -> Result<()> {
+    let mut command = shell_command(command_line);
+    spawn_cancelable_process(
+        &mut command,
+        ProcessConfig {
+            timeout_secs: BATCH_HOOK_TIMEOUT_SECS,
+            cancel_rx,
+            item_name: command_line,
+            action: "run",
+            cleanup: None,
+            progress_tx: None,
+            output_tx,
+        },
+    )
+}
+
+fn shell_command(command_line: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command_line]);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command_line]);
+        cmd
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn streams_output_and_reports_failure() {
+        let (_cancel_tx, cancel_rx) = mpsc::channel();
+        let (output_tx, output_rx) = mpsc::channel();
+
+        run_batch_hook("echo one && echo two >&2", &cancel_rx, Some(&output_tx)).unwrap();
+        let mut lines: Vec<String> = output_rx.try_iter().collect();
+        lines.sort();
+        assert_eq!(lines, vec!["  one", "  two"]);
+
+        let err = run_batch_hook("echo broken >&2; exit 3", &cancel_rx, None).unwrap_err();
+        assert_eq!(err.to_string(), "Failed to run echo broken >&2; exit 3: broken");
+    }
+}
@@ -0,0 +1,209 @@
+//! Which OS and CPU a hook or statusline binary was built for, read from
+//! its ELF, Mach-O or PE header, so one that can't run on this machine is
+//! refused at install time instead of failing every time it's executed.
+
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+/// Bytes read from the start of a binary; covers the PE header offset
+/// and a fat Mach-O's architecture table.
+const HEADER_BYTES: u64 = 4096;
+
+/// Most slices a fat Mach-O is taken to have. Java class files share its
+/// magic, but their next field is a version number of 45 or more.
+const MAX_FAT_ARCHS: usize = 30;
+
+/// Hosts that never run ELF binaries, whatever their OS/ABI byte says.
+const NON_ELF_OSES: [&str; 2] = ["macos", "windows"];
+
+/// OS and CPU architectures (a universal Mach-O has several) a binary
+/// targets, named like `std::env::consts`.
+#[derive(Debug, PartialEq)]
+struct Target {
+    os: &'static str,
+    archs: Vec<&'static str>,
+}
+
+impl Target {
+    fn describe(&self) -> String {
+        format!("{}/{}", self.os, self.archs.join("+"))
+    }
+}
+
+fn u16_at(bytes: &[u8], at: usize, big_endian: bool) -> Option<u16> {
+    let b: [u8; 2] = bytes.get(at..at + 2)?.try_into().ok()?;
+    Some(if big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+}
+
+fn u32_at(bytes: &[u8], at: usize, big_endian: bool) -> Option<u32> {
+    let b: [u8; 4] = bytes.get(at..at + 4)?.try_into().ok()?;
+    Some(if big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+}
+
+/// OS from the ELF OS/ABI byte. Most toolchains write 0 (System V)
+/// whatever the target, so that one says nothing about the OS.
+fn elf_os(osabi: u8) -> &'static str {
+    match osabi {
+        0x02 => "netbsd",
+        0x03 => "linux",
+        0x06 => "solaris",
+        0x09 => "freebsd",
+        0x0c => "openbsd",
+        _ => "unknown",
+    }
+}
+
+fn elf_arch(machine: u16) -> &'static str {
+    match machine {
+        0x03 => "x86",
+        0x28 => "arm",
+        0x3e => "x86_64",
+        0xb7 => "aarch64",
+        0xf3 => "riscv64",
+        _ => "unknown",
+    }
+}
+
+fn macho_arch(cputype: u32) -> &'static str {
+    match cputype {
+        0x0000_0007 => "x86",
+        0x0100_0007 => "x86_64",
+        0x0000_000c => "arm",
+        0x0100_000c => "aarch64",
+        _ => "unknown",
+    }
+}
+
+fn pe_arch(machine: u16) -> &'static str {
+    match machine {
+        0x014c => "x86",
+        0x8664 => "x86_64",
+        0xaa64 => "aarch64",
+        _ => "unknown",
+    }
+}
+
+/// Target of the executable whose first bytes are `bytes`; `None` for
+/// anything else (scripts, text, truncated headers).
+fn parse_target(bytes: &[u8]) -> Option<Target> {
+    match bytes.get(..4)? {
+        [0x7f, b'E', b'L', b'F'] => {
+            let big_endian = *bytes.get(5)? == 2;
+            let arch = elf_arch(u16_at(bytes, 0x12, big_endian)?);
+            Some(Target { os: elf_os(*bytes.get(7)?), archs: vec![arch] })
+        }
+        // Universal binary: a big-endian table of the slices it contains.
+        [0xca, 0xfe, 0xba, 0xbe] => {
+            let count = u32_at(bytes, 4, true)? as usize;
+            if !(1..=MAX_FAT_ARCHS).contains(&count) {
+                return None;
+            }
+            let archs = (0..count)
+                .map(|i| u32_at(bytes, 8 + i * 20, true).map(macho_arch))
+                .collect::<Option<Vec<_>>>()?;
+            Some(Target { os: "macos", archs })
+        }
+        [0xcf, 0xfa, 0xed, 0xfe] | [0xce, 0xfa, 0xed, 0xfe] => {
+            Some(Target { os: "macos", archs: vec![macho_arch(u32_at(bytes, 4, false)?)] })
+        }
+        [b'M', b'Z', ..] => {
+            let pe = u32_at(bytes, 0x3c, false)? as usize;
+            if bytes.get(pe..pe + 4)? != b"PE\0\0" {
+                return None;
+            }
+            Some(Target { os: "windows", archs: vec![pe_arch(u16_at(bytes, pe + 4, false)?)] })
+        }
+        _ => None,
+    }
+}
+
+fn read_target(path: &Path) -> Option<Target> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path).ok()?.take(HEADER_BYTES).read_to_end(&mut bytes).ok()?;
+    parse_target(&bytes)
+}
+
+/// Why `target` can't run on `os`/`arch`, if it can't. Unrecognised
+/// architectures, and ELF binaries of unknown OS on an ELF host, are given
+/// the benefit of the doubt.
+fn mismatch(target: &Target, os: &str, arch: &str) -> Option<String> {
+    let os_unknown = target.os == "unknown" && !NON_ELF_OSES.contains(&os);
+    if target.os != os && !os_unknown {
+        return Some(format!("it is built for {}, and this machine runs {}", target.os, os));
+    }
+    if target.archs.contains(&arch) || target.archs.contains(&"unknown") {
+        return None;
+    }
+    Some(format!("it is built for {}, and this machine is {}/{}", target.describe(), os, arch))
+}
+
+/// Refuse to install `binary` (named `name`) if its header says it was
+/// built for another OS or CPU than the one running the installer.
+pub(super) fn ensure_runs_here(name: &str, binary: &Path) -> Result<()> {
+    let Some(target) = read_target(binary) else { return Ok(()) };
+    if let Some(reason) = mismatch(&target, std::env::consts::OS, std::env::consts::ARCH) {
+        bail!(
+            "{} would not run here: {}. Ask the source to publish a {} build, or build it from source on this machine",
+            name,
+            reason,
+            std::env::consts::ARCH
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf(osabi: u8, machine: u16) -> Vec<u8> {
+        let mut bytes = vec![0x7f, b'E', b'L', b'F', 2, 1, 1, osabi];
+        bytes.resize(0x12, 0);
+        bytes.extend(machine.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn headers_are_read_and_compared_with_the_host() {
+        let x86_linux = parse_target(&elf(0x03, 0x3e)).unwrap();
+        assert_eq!(x86_linux, Target { os: "linux", archs: vec!["x86_64"] });
+        assert_eq!(mismatch(&x86_linux, "linux", "x86_64"), None);
+        assert_eq!(
+            mismatch(&x86_linux, "linux", "aarch64").unwrap(),
+            "it is built for linux/x86_64, and this machine is linux/aarch64"
+        );
+        assert!(mismatch(&x86_linux, "macos", "aarch64").unwrap().contains("this machine runs macos"));
+
+        // System V ELF names no OS: fine on FreeBSD, still refused on macOS.
+        let sysv = parse_target(&elf(0x00, 0x3e)).unwrap();
+        assert_eq!(sysv, Target { os: "unknown", archs: vec!["x86_64"] });
+        assert_eq!(mismatch(&sysv, "freebsd", "x86_64"), None);
+        assert!(mismatch(&sysv, "macos", "x86_64").is_some());
+        assert_eq!(parse_target(&elf(0x09, 0xb7)).unwrap().os, "freebsd");
+
+        // Universal Mach-O with x86_64 and arm64 slices.
+        let mut fat = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2];
+        for cputype in [0x0100_0007u32, 0x0100_000c] {
+            fat.extend(cputype.to_be_bytes());
+            fat.extend([0; 16]);
+        }
+        let universal = parse_target(&fat).unwrap();
+        assert_eq!(universal.archs, vec!["x86_64", "aarch64"]);
+        assert_eq!(mismatch(&universal, "macos", "aarch64"), None);
+
+        // Java class file (major version 52) with the same magic.
+        assert_eq!(parse_target(&[0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 52]), None);
+
+        let mut pe = vec![b'M', b'Z'];
+        pe.resize(0x3c, 0);
+        pe.extend(0x40u32.to_le_bytes());
+        pe.extend(b"PE\0\0");
+        pe.extend(0xaa64u16.to_le_bytes());
+        assert_eq!(parse_target(&pe), Some(Target { os: "windows", archs: vec!["aarch64"] }));
+
+        assert_eq!(parse_target(b"#!/bin/sh\necho hi\n"), None);
+        assert_eq!(parse_target(&[0x7f, b'E', b'L', b'F']), None, "truncated");
+    }
+}
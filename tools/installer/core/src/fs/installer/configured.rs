@@ -0,0 +1,186 @@
+//! Detect MCP servers and plugins that are already set up exactly as an
+//! install would set them up, so the install can be skipped instead of
+//! re-running `mcp add` / `plugin install`.
+//!
+//! Every check errs towards "not configured": when the current state can't
+//! be read or parsed, the install simply runs as before.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::TargetCli;
+use crate::mcp::{McpScope, McpServer};
+use crate::plugin::Plugin;
+use crate::fs::{create_cli_command, run_with_timeout};
+use super::mcp::{
+    configured_mcp_entry, mcp_config_path, read_json_config, servers_key, split_command,
+};
+use super::process::QUICK_COMMAND_TIMEOUT_SECS;
+
+/// What a CLI-managed server runs, reduced to what `mcp get` reports.
+/// The command line is kept joined: Claude prints its arguments
+/// space-separated, so quoting can't be recovered from its output.
+#[derive(Debug, Default, PartialEq)]
+struct ServerSpec {
+    command: Option<String>,
+    url: Option<String>,
+    env: BTreeMap<String, String>,
+}
+
+fn desired_spec(server: &McpServer, env_values: &[(String, String)]) -> Option<ServerSpec> {
+    let env = env_values.iter().cloned().collect();
+    if server.is_http() {
+        return Some(ServerSpec { command: None, url: Some(server.def.url.clone()?), env });
+    }
+    let parts = split_command(&server.def.resolved_command()?)?;
+    Some(ServerSpec { command: Some(parts.join(" ")), url: None, env })
+}
+
+/// Whether `server` is already registered on `target_cli` with the same
+/// command or url and environment that installing it would register.
+/// Custom targets have no way to read a server back and always install.
+pub fn mcp_server_configured(
+    server: &McpServer,
+    target_cli: TargetCli,
+    scope: McpScope,
+    project_path: Option<&str>,
+    env_values: &[(String, String)],
+) -> bool {
+    match target_cli {
+        TargetCli::Cursor | TargetCli::OpenCode => {
+            let Ok(path) = mcp_config_path(target_cli, scope, project_path) else { return false };
+            let (Ok(config), Ok(entry)) = (read_json_config(&path), configured_mcp_entry(target_cli, server, env_values)) else {
+                return false;
+            };
+            config.get(servers_key(target_cli)).and_then(|servers| servers.get(&server.def.name)) == Some(&entry)
+        }
+        TargetCli::Claude => {
+            let mut cmd = create_cli_command(TargetCli::Claude);
+            cmd.args(["mcp", "get", &server.def.name]);
+            if let Some(path) = project_path {
+                cmd.current_dir(path);
+            }
+            let Some(stdout) = successful_stdout(&mut cmd) else { return false };
+            let Some((current_scope, current)) = parse_claude_mcp_get(&stdout) else { return false };
+            current_scope == scope.display() && Some(current) == desired_spec(server, env_values)
+        }
+        TargetCli::Codex => {
+            let mut cmd = create_cli_command(TargetCli::Codex);
+            cmd.args(["mcp", "get", &server.def.name, "--json"]);
+            let Some(stdout) = successful_stdout(&mut cmd) else { return false };
+            let current = parse_codex_mcp_get(&stdout);
+            current.is_some() && current == desired_spec(server, env_values)
+        }
+        TargetCli::Custom(_) => false,
+    }
+}
+
+fn successful_stdout(cmd: &mut std::process::Command) -> Option<String> {
+    let out = run_with_timeout(cmd, QUICK_COMMAND_TIMEOUT_SECS).ok()?;
+    out.status.success().then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// Lowercased scope word and server spec from `claude mcp get <name>`:
+///
+/// ```text
+/// fs:
+///   Scope: User config (available in all your projects)
+///   Type: stdio
+///   Command: npx
+///   Args: -y server-fs /tmp
+///   Environment:
+///     TOKEN=abc
+/// ```
+fn parse_claude_mcp_get(stdout: &str) -> Option<(String, ServerSpec)> {
+    let mut scope = None;
+    let (mut program, mut args) = (None, None);
+    let mut spec = ServerSpec::default();
+    let mut in_env = false;
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if in_env {
+            match trimmed.split_once('=') {
+                Some((key, value)) if !trimmed.contains(": ") => {
+                    spec.env.insert(key.to_string(), value.to_string());
+                    continue;
+                }
+                _ => in_env = false,
+            }
+        }
+        let Some((key, value)) = trimmed.split_once(':') else { continue };
+        let value = value.trim();
+        match key {
+            "Scope" => scope = value.split_whitespace().next().map(str::to_lowercase),
+            "Command" => program = Some(value.to_string()),
+            "Args" => args = Some(value.to_string()),
+            "URL" => spec.url = Some(value.to_string()),
+            "Environment" => in_env = true,
+            _ => {}
+        }
+    }
+    spec.command = program.map(|program| match args.filter(|a| !a.is_empty()) {
+        Some(args) => format!("{} {}", program, args),
+        None => program,
+    });
+    Some((scope?, spec))
+}
+
+/// Server spec from `codex mcp get <name> --json`, whose stdio or http
+/// settings sit under `transport`.
+fn parse_codex_mcp_get(stdout: &str) -> Option<ServerSpec> {
+    let root: Value = serde_json::from_str(stdout).ok()?;
+    let transport = root.get("transport").unwrap_or(&root);
+    let text = |key: &str| transport.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let command = text("command").map(|program| {
+        let args = transport.get("args").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str);
+        std::iter::once(program.as_str()).chain(args).collect::<Vec<_>>().join(" ")
+    });
+    let env = transport.get("env").and_then(Value::as_object).into_iter().flatten()
+        .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+        .collect();
+    Some(ServerSpec { command, url: text("url"), env })
+}
+
+/// Whether `plugin` is enabled in `~/.claude/settings.json` under its
+/// `name@marketplace` key.
+pub fn plugin_enabled(plugin: &Plugin) -> bool {
+    let Some(settings_path) = dirs::home_dir().map(|h| h.join(".claude").join("settings.json")) else {
+        return false;
+    };
+    let key = format!("{}@{}", plugin.def.name, plugin.def.marketplace);
+    read_json_config(&settings_path).ok()
+        .and_then(|settings| settings.get("enabledPlugins")?.get(&key)?.as_bool())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::{McpServerDef, McpStatus};
+
+    fn server(yaml: &str) -> McpServer {
+        let def: McpServerDef = serde_yaml_bw::from_str(yaml).unwrap();
+        McpServer::new(def, McpStatus::Installed)
+    }
+
+    #[test]
+    fn cli_reports_compare_against_the_server_definition() {
+        let fs = server("name: fs\ndescription: d\ncategory: c\ncommand: npx -y server-fs /tmp\n");
+        let env = [("TOKEN".to_string(), "abc".to_string())];
+        let claude = "fs:\n  Scope: User config (available in all your projects)\n  Status: ✓ Connected\n  Type: stdio\n  \
+            Command: npx\n  Args: -y server-fs /tmp\n  Environment:\n    TOKEN=abc\n\nTo remove this server, run: claude mcp remove \"fs\" -s user\n";
+        let (scope, spec) = parse_claude_mcp_get(claude).unwrap();
+        assert_eq!(scope, "user");
+        assert_eq!(Some(spec), desired_spec(&fs, &env));
+
+        let codex = r#"{"name":"fs","transport":{"type":"stdio","command":"npx","args":["-y","server-fs","/tmp"],"env":null}}"#;
+        assert_eq!(parse_codex_mcp_get(codex), desired_spec(&fs, &[]));
+        assert_ne!(parse_codex_mcp_get(codex), desired_spec(&fs, &env));
+
+        let web = server("name: web\ndescription: d\ncategory: c\ntype: http\nurl: https://w\n");
+        let (_, spec) = parse_claude_mcp_get("web:\n  Scope: Local config\n  Type: http\n  URL: https://w\n").unwrap();
+        assert_eq!(Some(spec), desired_spec(&web, &[]));
+    }
+}
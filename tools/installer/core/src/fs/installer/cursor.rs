@@ -0,0 +1,89 @@
+//! Writers for the Cursor target. Cursor reads rules as `.mdc` files with
+//! YAML frontmatter from `.cursor/rules/`, so they are converted rather
+//! than copied, and MCP servers from `mcp.json` (written by `mcp.rs`).
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::component::{Component, ComponentType};
+use crate::mcp::McpServer;
+use super::readonly::{create_parent_dirs, write_over_readonly};
+
+/// Extension Cursor expects for rule files.
+pub const CURSOR_RULE_EXTENSION: &str = "mdc";
+
+/// Whether `component` is a rule bound for Cursor (converted on install
+/// rather than copied byte for byte).
+pub fn is_cursor_rule(component: &Component) -> bool {
+    component.component_type == ComponentType::Rules
+        && component.dest_path.extension().is_some_and(|e| e == CURSOR_RULE_EXTENSION)
+}
+
+/// Rule file content as installed for Cursor. A source that already opens
+/// with frontmatter is kept as-is; otherwise an always-applied header is
+/// added, described by the first heading (or the file name).
+pub fn cursor_rule_content(source: &Path) -> Result<String> {
+    let body = std::fs::read_to_string(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?
+        .replace("\r\n", "\n");
+    if body.starts_with("---\n") {
+        return Ok(body);
+    }
+
+    let description = body
+        .lines()
+        .find_map(|l| l.strip_prefix('#').map(|h| h.trim_start_matches('#').trim()))
+        .filter(|h| !h.is_empty())
+        .map(str::to_string)
+        .or_else(|| source.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    // A JSON string is a valid YAML scalar, and quotes any `:` or `#`.
+    Ok(format!(
+        "---\ndescription: {}\nalwaysApply: true\n---\n\n{}",
+        serde_json::to_string(&description)?,
+        body
+    ))
+}
+
+/// Write a converted rule to its dest path.
+pub(super) fn write_cursor_rule(component: &Component) -> Result<()> {
+    let content = cursor_rule_content(&component.source_path)?;
+    if let Some(parent) = component.dest_path.parent() {
+        create_parent_dirs(parent)?;
+    }
+    write_over_readonly(&component.dest_path, || std::fs::write(&component.dest_path, &content))
+}
+
+/// Cursor's `mcpServers` entry: `command` + `args` (+ `env`), or `url`.
+pub(super) fn cursor_mcp_entry(server: &McpServer, command: Option<Vec<String>>, env: Map<String, Value>) -> Value {
+    let Some(command) = command else {
+        return json!({ "url": server.def.url });
+    };
+    let mut entry = json!({ "command": command[0], "args": command[1..] });
+    if !env.is_empty() {
+        entry["env"] = Value::Object(env);
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn rules_gain_frontmatter_unless_they_have_one() {
+        let dir = temp_dir("rules");
+        std::fs::write(dir.join("plain.md"), "# Style: tabs\n\nUse tabs.\n").unwrap();
+        std::fs::write(dir.join("fm.md"), "---\nglobs: \"*.rs\"\n---\nbody\n").unwrap();
+
+        let plain = cursor_rule_content(&dir.join("plain.md")).unwrap();
+        let fm = cursor_rule_content(&dir.join("fm.md")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(plain, "---\ndescription: \"Style: tabs\"\nalwaysApply: true\n---\n\n# Style: tabs\n\nUse tabs.\n");
+        assert_eq!(fm, "---\nglobs: \"*.rs\"\n---\nbody\n");
+    }
+}
@@ -0,0 +1,105 @@
+//! settings.json entries that point at files which aren't there: an
+//! `outputStyle` with no style file, or a `statusLine` command whose
+//! script was deleted. The CLI falls back silently in both cases, so the
+//! installer reports them on the tab that can put things right.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::settings::{output_styles_folder, read_settings, split_env_prefix};
+
+/// Styles the CLI ships itself; they have no file to look for.
+const BUILTIN_OUTPUT_STYLES: &[&str] = &["default", "explanatory", "learning"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DanglingSetting {
+    /// `outputStyle` names a style with no file in the styles folder.
+    OutputStyle { style: String },
+    /// `statusLine.command` runs `missing`, which doesn't exist.
+    StatusLine { command: String, missing: PathBuf },
+}
+
+/// Every dangling entry in `dest_dir`'s settings.json. An unreadable
+/// settings.json has none: its problems are reported elsewhere.
+pub fn dangling_settings(dest_dir: &Path) -> Vec<DanglingSetting> {
+    let Ok(settings) = read_settings(dest_dir) else { return Vec::new() };
+    let mut found = Vec::new();
+
+    if let Some(style) = settings.get("outputStyle").and_then(Value::as_str)
+        && !BUILTIN_OUTPUT_STYLES.contains(&style.to_lowercase().as_str())
+        && !style_exists(&dest_dir.join(output_styles_folder()), style)
+    {
+        found.push(DanglingSetting::OutputStyle { style: style.to_string() });
+    }
+
+    if let Some(command) = settings.get("statusLine").and_then(|s| s.get("command")).and_then(Value::as_str)
+        && let Some(missing) = missing_path(command)
+    {
+        found.push(DanglingSetting::StatusLine { command: command.to_string(), missing });
+    }
+    found
+}
+
+/// Whether a style file in `dir` is named `style`, by file name or by
+/// its frontmatter `name:`.
+fn style_exists(dir: &Path, style: &str) -> bool {
+    if dir.join(format!("{}.md", style)).is_file() {
+        return true;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else { return false };
+    entries.flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .any(|content| crate::fs::keywords::parse_list(&content, "name").first().is_some_and(|n| n == style))
+}
+
+/// First file the command names by path that doesn't exist. Bare program
+/// names are left to PATH and never reported.
+fn missing_path(command: &str) -> Option<PathBuf> {
+    let (_, command) = split_env_prefix(command);
+    let home = dirs::home_dir();
+    shlex::split(command)?
+        .iter()
+        .filter_map(|word| {
+            let rest = ["~/", "$HOME/", "${HOME}/"].iter().find_map(|prefix| word.strip_prefix(prefix));
+            match rest {
+                Some(rest) => home.as_ref().map(|home| home.join(rest)),
+                None => Some(PathBuf::from(word)).filter(|path| path.is_absolute()),
+            }
+        })
+        .find(|path| !path.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn reports_styles_and_statuslines_without_files() {
+        let dest = temp_dir("dangling");
+        let styles = dest.join(output_styles_folder());
+        std::fs::create_dir_all(&styles).unwrap();
+        let settings = |value: Value| std::fs::write(dest.join("settings.json"), value.to_string()).unwrap();
+        let script = dest.join("status.sh");
+
+        settings(serde_json::json!({
+            "outputStyle": "terse",
+            "statusLine": { "type": "command", "command": format!("bash {}", script.display()) },
+        }));
+        assert_eq!(dangling_settings(&dest), vec![
+            DanglingSetting::OutputStyle { style: "terse".to_string() },
+            DanglingSetting::StatusLine { command: format!("bash {}", script.display()), missing: script.clone() },
+        ]);
+
+        // A file named after the style, or declaring it, satisfies it.
+        std::fs::write(styles.join("short.md"), "---\nname: terse\n---\nBe brief.\n").unwrap();
+        std::fs::write(&script, "echo ok\n").unwrap();
+        assert!(dangling_settings(&dest).is_empty());
+
+        settings(serde_json::json!({ "outputStyle": "Explanatory", "statusLine": { "command": "npx ccstatus" } }));
+        assert!(dangling_settings(&dest).is_empty());
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+}
@@ -0,0 +1,116 @@
+//! Record of the files hibi copied into `dest_dir`.
+//!
+//! Kept in `<dest_dir>/.hibi/installed.yaml` as dest-relative paths. A file
+//! in dest that no source produces is an orphan when it is on this list
+//! (left behind by a component that was renamed or dropped upstream) and
+//! external otherwise (added by the user or another tool). The time of each
+//! file's last install is kept alongside for the drift report, and so are
+//! the settings.json entries hibi added, so removing the settings.json
+//! component strips only those.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::component::Component;
+use crate::fs::state::{load_yaml, save_yaml};
+
+/// File name inside the installer state dir (see `state`).
+pub const RECORD_FILE: &str = "installed.yaml";
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct InstalledRecord {
+    #[serde(default)]
+    files: BTreeSet<String>,
+    /// Unix seconds of each file's last install; missing for files recorded
+    /// before install times were kept.
+    #[serde(default)]
+    installed_at: BTreeMap<String, u64>,
+    /// `None` for records written before settings entries were tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    settings: Option<ManagedSettings>,
+}
+
+/// settings.json entries hibi created.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub(super) struct ManagedSettings {
+    /// Event -> commands of the hook entries added, without env prefix.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hooks: BTreeMap<String, BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_style: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_line: Option<serde_json::Value>,
+}
+
+fn record_path(dest_dir: &Path) -> PathBuf {
+    crate::fs::state::state_dir(dest_dir).join(RECORD_FILE)
+}
+
+/// `path` relative to `dest_dir`, with forward slashes.
+pub(crate) fn record_key(dest_dir: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(dest_dir).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+fn load_record(dest_dir: &Path) -> Result<InstalledRecord> {
+    load_yaml(&record_path(dest_dir))
+}
+
+fn save_record(dest_dir: &Path, record: &InstalledRecord) -> Result<()> {
+    save_yaml(&record_path(dest_dir), record)
+}
+
+/// Dest-relative paths of every file hibi installed into `dest_dir` and
+/// hasn't removed since; empty when the record is missing or unreadable.
+pub fn installed_files(dest_dir: &Path) -> BTreeSet<String> {
+    load_record(dest_dir).map(|record| record.files).unwrap_or_default()
+}
+
+/// Unix seconds of the last install of each recorded file, by the same
+/// dest-relative keys as `installed_files`.
+pub fn install_times(dest_dir: &Path) -> BTreeMap<String, u64> {
+    load_record(dest_dir).map(|record| record.installed_at).unwrap_or_default()
+}
+
+pub(super) fn record_installed(dest_dir: &Path, component: &Component) -> Result<()> {
+    let Some(key) = record_key(dest_dir, &component.dest_path) else { return Ok(()) };
+    let mut record = load_record(dest_dir)?;
+    record.installed_at.insert(key.clone(), crate::maintenance::now_secs());
+    record.files.insert(key);
+    save_record(dest_dir, &record)
+}
+
+pub(super) fn forget_installed(dest_dir: &Path, component: &Component) -> Result<()> {
+    let Some(key) = record_key(dest_dir, &component.dest_path) else { return Ok(()) };
+    let mut record = load_record(dest_dir)?;
+    record.installed_at.remove(&key);
+    if record.files.remove(&key) {
+        save_record(dest_dir, &record)?;
+    }
+    Ok(())
+}
+
+/// settings.json entries hibi created in `dest_dir`; `None` when the record
+/// predates tracking them (or can't be read).
+pub(super) fn managed_settings(dest_dir: &Path) -> Option<ManagedSettings> {
+    load_record(dest_dir).ok()?.settings
+}
+
+/// Change the tracked settings.json entries. Tracking starts with the
+/// first entry added; forgetting one on an untracked record is a no-op.
+pub(super) fn update_managed_settings(dest_dir: &Path, change: impl FnOnce(&mut ManagedSettings)) -> Result<()> {
+    let mut record = load_record(dest_dir)?;
+    let before = record.settings.clone();
+    change(record.settings.get_or_insert_with(ManagedSettings::default));
+    let untouched = match &before {
+        Some(_) => record.settings == before,
+        None => record.settings == Some(ManagedSettings::default()),
+    };
+    if !untouched {
+        save_record(dest_dir, &record)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,476 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{Receiver, Sender};
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::TargetCli;
+use crate::mcp::{McpServer, McpScope};
+use super::process::{
+    spawn_cancelable_process, run_with_timeout,
+    ProcessConfig, QUICK_COMMAND_TIMEOUT_SECS,
+};
+use crate::fs::create_cli_command;
+use super::cursor::cursor_mcp_entry;
+use super::opencode::opencode_mcp_entry;
+
+/// Split a command string into arguments.
+/// Uses shlex (POSIX rules) on Unix, Windows-aware splitting on Windows.
+pub(super) fn split_command(cmd: &str) -> Option<Vec<String>> {
+    #[cfg(windows)]
+    {
+        // Windows: split on whitespace, respecting double and single-quoted strings.
+        // Backslashes are literal (not escape chars) to support Windows paths.
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut quote_char: Option<char> = None;
+        for ch in cmd.chars() {
+            match ch {
+                '"' | '\'' if quote_char == Some(ch) => { quote_char = None; }
+                '"' | '\'' if quote_char.is_none() => { quote_char = Some(ch); }
+                ' ' | '\t' if quote_char.is_none() => {
+                    if !current.is_empty() {
+                        args.push(std::mem::take(&mut current));
+                    }
+                }
+                _ => current.push(ch),
+            }
+        }
+        if !current.is_empty() {
+            args.push(current);
+        }
+        if quote_char.is_some() { None } else { Some(args) }
+    }
+    #[cfg(not(windows))]
+    {
+        shlex::split(cmd)
+    }
+}
+
+/// Values substituted into a custom target's MCP templates (see
+/// `McpTemplates`).
+#[derive(Default)]
+pub(crate) struct TemplateValues<'a> {
+    pub name: &'a str,
+    pub url: &'a str,
+    pub command: Vec<String>,
+    pub env: Vec<String>,
+}
+
+/// Arguments for a custom target's command from `template`. `{command}`
+/// and `{env}` stand alone and expand to any number of arguments; a flag
+/// right before `{env}` is repeated for every variable and dropped when
+/// there are none.
+pub(crate) fn expand_mcp_template(template: &str, values: &TemplateValues) -> Result<Vec<String>> {
+    let tokens = split_command(template)
+        .ok_or_else(|| anyhow::anyhow!("Invalid MCP command template: {}", template))?;
+    let mut args = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        if token.starts_with('-') && tokens.peek().is_some_and(|next| next == "{env}") {
+            tokens.next();
+            for env in &values.env {
+                args.push(token.clone());
+                args.push(env.clone());
+            }
+            continue;
+        }
+        match token.as_str() {
+            "{env}" => args.extend(values.env.iter().cloned()),
+            "{command}" => args.extend(values.command.iter().cloned()),
+            _ => args.push(token.replace("{name}", values.name).replace("{url}", values.url)),
+        }
+    }
+    Ok(args)
+}
+
+/// `mcp_add` (or `mcp_add_http`) arguments for `server` on a custom target.
+fn custom_mcp_add_args(target_cli: TargetCli, server: &McpServer, env_values: &[(String, String)]) -> Result<Vec<String>> {
+    let templates = target_cli.custom().and_then(|t| t.mcp.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("{} has no MCP commands configured", target_cli.display_name()))?;
+    let mut values = TemplateValues {
+        name: &server.def.name,
+        env: env_values.iter().map(|(k, v)| format!("{}={}", k, v)).collect(),
+        ..Default::default()
+    };
+    let template = if server.is_http() {
+        values.url = server.def.url.as_deref()
+            .ok_or_else(|| anyhow::anyhow!("MCP server '{}' has no url", server.def.name))?;
+        templates.add_http.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("{} has no mcp_add_http command for HTTP servers", target_cli.display_name())
+        })?
+    } else {
+        let cmd_str = server.def.resolved_command()
+            .ok_or_else(|| anyhow::anyhow!("MCP server '{}' has no command", server.def.name))?;
+        values.command = split_command(&cmd_str)
+            .ok_or_else(|| anyhow::anyhow!("Invalid command syntax: {}", cmd_str))?;
+        &templates.add
+    };
+    expand_mcp_template(template, &values)
+}
+
+/// Command that removes the MCP server `name`.
+pub(crate) fn mcp_remove_command(name: &str, target_cli: TargetCli) -> Result<Command> {
+    let mut command = create_cli_command(target_cli);
+    match target_cli.custom() {
+        Some(target) => {
+            let template = target.mcp.as_ref().map(|m| m.remove.as_str()).ok_or_else(|| {
+                anyhow::anyhow!("{} has no MCP commands configured", target_cli.display_name())
+            })?;
+            command.args(expand_mcp_template(template, &TemplateValues { name, ..Default::default() })?);
+        }
+        None => {
+            command.args(["mcp", "remove", name]);
+        }
+    }
+    Ok(command)
+}
+
+/// Configuration for MCP server installation.
+pub struct McpInstallConfig<'a> {
+    pub scope: McpScope,
+    pub project_path: Option<&'a str>,
+    pub env_values: &'a [(String, String)],
+    pub target_cli: TargetCli,
+    pub timeout_secs: u64,
+    pub cancel_rx: &'a Receiver<()>,
+    /// Receives the command line run and cleanup progress lines if the
+    /// install is aborted.
+    pub progress_tx: Option<&'a Sender<String>>,
+}
+
+pub fn install_mcp_server(
+    server: &McpServer,
+    config: McpInstallConfig,
+) -> Result<()> {
+    if !config.target_cli.manages_mcp_via_cli() {
+        let path = mcp_config_path(config.target_cli, config.scope, config.project_path)?;
+        return add_configured_mcp_server(config.target_cli, &path, server, config.env_values);
+    }
+
+    let mut command = create_cli_command(config.target_cli);
+
+    match config.target_cli {
+        TargetCli::Claude => {
+            command.arg("mcp").arg("add");
+            command.arg("--scope").arg(config.scope.display());
+            command.arg(&server.def.name);
+
+            for (key, value) in config.env_values {
+                command.arg("-e").arg(format!("{}={}", key, value));
+            }
+
+            if server.is_http() {
+                command.arg("-t").arg("http");
+                if let Some(url) = &server.def.url {
+                    command.arg(url);
+                }
+            } else {
+                command.arg("--");
+                if let Some(cmd_str) = server.def.resolved_command() {
+                    let parts = split_command(&cmd_str)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid command syntax: {}", cmd_str))?;
+                    for part in parts {
+                        command.arg(part);
+                    }
+                }
+            }
+
+            if let Some(path) = config.project_path {
+                command.current_dir(path);
+            }
+        }
+        TargetCli::Codex | TargetCli::Cursor | TargetCli::OpenCode => {
+            command.arg("mcp").arg("add");
+            for (key, value) in config.env_values {
+                command.arg("--env").arg(format!("{}={}", key, value));
+            }
+
+            command.arg(&server.def.name);
+
+            if server.is_http() {
+                if let Some(url) = &server.def.url {
+                    command.arg("--url").arg(url);
+                }
+            } else {
+                command.arg("--");
+                if let Some(cmd_str) = server.def.resolved_command() {
+                    let parts = split_command(&cmd_str)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid command syntax: {}", cmd_str))?;
+                    for part in parts {
+                        command.arg(part);
+                    }
+                }
+            }
+        }
+        TargetCli::Custom(_) => {
+            command.args(custom_mcp_add_args(config.target_cli, server, config.env_values)?);
+        }
+    }
+
+    spawn_cancelable_process(
+        &mut command,
+        ProcessConfig {
+            timeout_secs: config.timeout_secs,
+            cancel_rx: config.cancel_rx,
+            item_name: &server.def.name,
+            action: "install MCP server",
+            cleanup: mcp_remove_command(&server.def.name, config.target_cli).ok(),
+            progress_tx: config.progress_tx,
+            output_tx: None,
+        },
+    )
+}
+
+/// Remove `server` from the scope it was installed in; `scope` and
+/// `project_path` pick the config file of a target without `mcp remove`.
+pub fn remove_mcp_server(
+    server: &McpServer,
+    target_cli: TargetCli,
+    scope: McpScope,
+    project_path: Option<&str>,
+    timeout_secs: u64,
+    cancel_rx: &Receiver<()>,
+    progress_tx: Option<&Sender<String>>,
+) -> Result<()> {
+    if !target_cli.manages_mcp_via_cli() {
+        let path = mcp_config_path(target_cli, scope, project_path)?;
+        return remove_configured_mcp_server(target_cli, &path, &server.def.name);
+    }
+
+    let mut command = mcp_remove_command(&server.def.name, target_cli)?;
+
+    spawn_cancelable_process(
+        &mut command,
+        ProcessConfig {
+            timeout_secs,
+            cancel_rx,
+            item_name: &server.def.name,
+            action: "remove MCP server",
+            cleanup: None,
+            progress_tx,
+            output_tx: None,
+        },
+    )
+}
+
+/// Config file holding the MCP servers of a target that has no `mcp add`
+/// command (see `TargetCli::manages_mcp_via_cli`): the project's file for
+/// a local install with a project path, the global one otherwise.
+pub fn mcp_config_path(target_cli: TargetCli, scope: McpScope, project_path: Option<&str>) -> Result<PathBuf> {
+    let (project_file, global_file) = match target_cli {
+        TargetCli::Cursor => (".cursor/mcp.json", "mcp.json"),
+        TargetCli::OpenCode => ("opencode.json", "opencode.json"),
+        TargetCli::Claude | TargetCli::Codex | TargetCli::Custom(_) => {
+            anyhow::bail!("{} registers MCP servers through its CLI", target_cli.display_name())
+        }
+    };
+    match (scope, project_path) {
+        (McpScope::Local, Some(project)) => Ok(Path::new(project).join(project_file)),
+        _ => Ok(target_cli.get_dest_dir()?.join(global_file)),
+    }
+}
+
+/// Key of the server map inside the config file.
+pub(super) fn servers_key(target_cli: TargetCli) -> &'static str {
+    match target_cli {
+        TargetCli::OpenCode => "mcp",
+        _ => "mcpServers",
+    }
+}
+
+/// Server names in the config file at `path`; empty when it is absent.
+pub fn configured_mcp_servers(target_cli: TargetCli, path: &Path) -> Result<Vec<String>> {
+    let config = read_json_config(path)?;
+    Ok(config
+        .get(servers_key(target_cli))
+        .and_then(Value::as_object)
+        .map(|servers| servers.keys().cloned().collect())
+        .unwrap_or_default())
+}
+
+/// Entry `server` gets in a config-file target's server map.
+pub(super) fn configured_mcp_entry(
+    target_cli: TargetCli,
+    server: &McpServer,
+    env_values: &[(String, String)],
+) -> Result<Value> {
+    let command = if server.is_http() {
+        if server.def.url.is_none() {
+            anyhow::bail!("MCP server '{}' has no url", server.def.name);
+        }
+        None
+    } else {
+        let cmd_str = server.def.resolved_command()
+            .ok_or_else(|| anyhow::anyhow!("MCP server '{}' has no command", server.def.name))?;
+        let parts = split_command(&cmd_str)
+            .filter(|parts| !parts.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid command syntax: {}", cmd_str))?;
+        Some(parts)
+    };
+    let env: Map<String, Value> = env_values.iter()
+        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+        .collect();
+    Ok(match target_cli {
+        TargetCli::OpenCode => opencode_mcp_entry(server, command, env),
+        _ => cursor_mcp_entry(server, command, env),
+    })
+}
+
+/// Add or replace `server` in the config file at `path`, keeping every
+/// other key and server.
+fn add_configured_mcp_server(
+    target_cli: TargetCli,
+    path: &Path,
+    server: &McpServer,
+    env_values: &[(String, String)],
+) -> Result<()> {
+    let entry = configured_mcp_entry(target_cli, server, env_values)?;
+    let mut config = read_json_config(path)?;
+    let key = servers_key(target_cli);
+    let root = config.as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("{} is not a JSON object", path.display()))?;
+    let servers = root.entry(key).or_insert_with(|| json!({}));
+    let servers = servers.as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("{} in {} is not a JSON object", key, path.display()))?;
+    servers.insert(server.def.name.clone(), entry);
+    write_json_config(path, &config)
+}
+
+/// Drop `name` from the config file at `path`. Missing entries are not an error.
+pub fn remove_configured_mcp_server(target_cli: TargetCli, path: &Path, name: &str) -> Result<()> {
+    let mut config = read_json_config(path)?;
+    let removed = config
+        .get_mut(servers_key(target_cli))
+        .and_then(Value::as_object_mut)
+        .is_some_and(|servers| servers.remove(name).is_some());
+    if removed {
+        write_json_config(path, &config)?;
+    }
+    Ok(())
+}
+
+pub(super) fn read_json_config(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(json!({}));
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if content.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn write_json_config(path: &Path, config: &Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(config)? + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Ensure the plugin's marketplace is registered before installation.
+///
+/// Step 1: Quick check if already added (non-cancelable, short timeout).
+/// Step 2: If not found, add it using `spawn_cancelable_process` (cancelable, respects timeout).
+pub(super) fn ensure_marketplace_added(
+    marketplace: &str,
+    source: &str,
+    timeout_secs: u64,
+    cancel_rx: &Receiver<()>,
+    progress_tx: Option<&Sender<String>>,
+) -> Result<()> {
+    // Step 1: Check if marketplace is already added (quick, non-cancelable)
+    let mut list_cmd = create_cli_command(TargetCli::Claude);
+    list_cmd.args(["plugin", "marketplace", "list"]);
+
+    match run_with_timeout(&mut list_cmd, QUICK_COMMAND_TIMEOUT_SECS) {
+        Ok(list_output) if list_output.status.success() => {
+            let stdout = String::from_utf8_lossy(&list_output.stdout);
+            if stdout.contains(marketplace) {
+                return Ok(()); // Already added
+            }
+        }
+        Ok(_) => {} // Non-success status: fall through to add
+        Err(_) => {} // Timeout or spawn error: fall through to add
+    }
+
+    // Step 2: Add the marketplace (cancelable, full timeout)
+    let mut command = create_cli_command(TargetCli::Claude);
+    command.args(["plugin", "marketplace", "add", source]);
+
+    spawn_cancelable_process(
+        &mut command,
+        ProcessConfig {
+            timeout_secs,
+            cancel_rx,
+            item_name: marketplace,
+            action: "add marketplace",
+            cleanup: None,
+            progress_tx,
+            output_tx: None,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::mcp::{McpServerDef, McpStatus};
+
+    fn server(yaml: &str) -> McpServer {
+        let def: McpServerDef = serde_yaml_bw::from_str(yaml).unwrap();
+        McpServer::new(def, McpStatus::NotInstalled)
+    }
+
+    #[test]
+    fn custom_target_templates_expand_server_values() {
+        let values = TemplateValues {
+            name: "fs",
+            url: "",
+            command: vec!["npx".into(), "-y".into(), "/a b".into()],
+            env: vec!["A=1".into(), "B=2".into()],
+        };
+        let add = expand_mcp_template("mcp add --env {env} {name} -- {command}", &values).unwrap();
+        assert_eq!(add, ["mcp", "add", "--env", "A=1", "--env", "B=2", "fs", "--", "npx", "-y", "/a b"]);
+
+        let http = TemplateValues { name: "web", url: "https://w", ..Default::default() };
+        let add = expand_mcp_template("mcp add -e {env} server:{name} {url}", &http).unwrap();
+        assert_eq!(add, ["mcp", "add", "server:web", "https://w"]);
+    }
+
+    #[test]
+    fn config_file_servers_are_added_and_removed_in_place() {
+        let dir = temp_dir("mcp_config");
+        let (cursor, opencode) = (dir.join("mcp.json"), dir.join("opencode.json"));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&cursor, r#"{"theme": 1, "mcpServers": {"mine": {"url": "http://x"}}}"#).unwrap();
+        let fs = server("name: fs\ndescription: d\ncategory: c\ncommand: npx -y server-fs \"/a b\"\n");
+        let web = server("name: web\ndescription: d\ncategory: c\ntype: http\nurl: https://w\n");
+        let env = [("TOKEN".to_string(), "t".to_string())];
+
+        add_configured_mcp_server(TargetCli::Cursor, &cursor, &fs, &env).unwrap();
+        add_configured_mcp_server(TargetCli::OpenCode, &opencode, &fs, &env).unwrap();
+        add_configured_mcp_server(TargetCli::OpenCode, &opencode, &web, &[]).unwrap();
+        let read = |path: &Path| -> Value { serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap() };
+
+        let config = read(&cursor);
+        assert_eq!(config["theme"], 1);
+        assert_eq!(config["mcpServers"]["fs"], json!({
+            "command": "npx", "args": ["-y", "server-fs", "/a b"], "env": {"TOKEN": "t"}
+        }));
+        assert_eq!(read(&opencode)["mcp"], json!({
+            "fs": {"type": "local", "command": ["npx", "-y", "server-fs", "/a b"], "enabled": true, "environment": {"TOKEN": "t"}},
+            "web": {"type": "remote", "url": "https://w", "enabled": true},
+        }));
+        assert_eq!(configured_mcp_servers(TargetCli::Cursor, &cursor).unwrap(), ["fs", "mine"]);
+
+        remove_configured_mcp_server(TargetCli::Cursor, &cursor, "fs").unwrap();
+        remove_configured_mcp_server(TargetCli::OpenCode, &opencode, "web").unwrap();
+        assert_eq!(configured_mcp_servers(TargetCli::Cursor, &cursor).unwrap(), ["mine"]);
+        assert_eq!(configured_mcp_servers(TargetCli::OpenCode, &opencode).unwrap(), ["fs"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use serde_json::Value;
+
+use super::installed::update_managed_settings;
+use crate::fs::audit::{self, EventKind};
+use super::settings::split_env_prefix;
+use super::settings_keys::released_keys;
+
+/// Merge source settings.json into dest, with deep merge and hook append logic.
+pub(super) fn merge_settings_json(source: &Path, dest: &Path) -> Result<()> {
+    let preview = preview_settings_merge(source, dest)?;
+    let merged: Value = serde_json::from_str(&preview.merged)?;
+    super::validate_settings(&merged, dest)?;
+
+    let written = match dest.parent() {
+        Some(parent) => std::fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+    .and_then(|_| std::fs::write(dest, &preview.merged));
+    audit::record(EventKind::Settings, "merge source settings.json", &dest.to_string_lossy(), &written);
+    written?;
+
+    if let Some(dest_dir) = dest.parent() {
+        let before = match &preview.current {
+            Some(current) => serde_json::from_str(current)?,
+            None => Value::Null,
+        };
+        track_added_settings(dest_dir, &before, &merged)?;
+    }
+    Ok(())
+}
+
+/// Record the hook entries the merge appended, and `outputStyle` and
+/// `statusLine` when it changed them, as created by the installer.
+fn track_added_settings(dest_dir: &Path, before: &Value, after: &Value) -> Result<()> {
+    let mut added_hooks: Vec<(String, String)> = Vec::new();
+    if let Some(Value::Object(events)) = after.get("hooks") {
+        for (event, entries) in events {
+            let existing = before.get("hooks").and_then(|h| h.get(event)).and_then(Value::as_array);
+            for entry in entries.as_array().into_iter().flatten() {
+                if existing.is_some_and(|e| e.contains(entry)) {
+                    continue;
+                }
+                let commands = entry.get("hooks").and_then(Value::as_array).into_iter().flatten()
+                    .filter_map(|hook| hook.get("command").and_then(Value::as_str));
+                added_hooks.extend(commands.map(|cmd| (event.clone(), split_env_prefix(cmd).1.to_string())));
+            }
+        }
+    }
+    let changed = |key: &str| after.get(key).filter(|value| before.get(key) != Some(*value)).cloned();
+    let output_style = changed("outputStyle").and_then(|v| v.as_str().map(str::to_string));
+    let status_line = changed("statusLine");
+    if added_hooks.is_empty() && output_style.is_none() && status_line.is_none() {
+        return Ok(());
+    }
+    update_managed_settings(dest_dir, |m| {
+        for (event, command) in added_hooks {
+            m.hooks.entry(event).or_default().insert(command);
+        }
+        if output_style.is_some() {
+            m.output_style = output_style;
+        }
+        if status_line.is_some() {
+            m.status_line = status_line;
+        }
+    })
+}
+
+/// What `merge_settings_json` would produce, without writing it.
+pub struct MergePreview {
+    /// Current dest, pretty-printed the same way as `merged` so a diff
+    /// shows only semantic changes. `None` when dest doesn't exist yet.
+    pub current: Option<String>,
+    /// Exactly the text an install writes.
+    pub merged: String,
+    /// Dotted key paths where the user's existing value is replaced by a
+    /// different one from source.
+    pub overwritten: Vec<String>,
+}
+
+/// File in the state dir holding a source's settings.json with the
+/// `source.local` overlay's merged in (see `layer_settings`).
+pub const LAYERED_SETTINGS_FILE: &str = "layered-settings.json";
+
+/// Merge the overlay settings.json at `overlay` onto the source one at
+/// `base`, the same way an install merges into dest, and write the result
+/// into `dest_dir`'s state dir. Returns where it was written.
+pub fn layer_settings(base: &Path, overlay: &Path, dest_dir: &Path) -> Result<PathBuf> {
+    let mut layered: Value = crate::fs::jsonc::parse(&std::fs::read_to_string(base)?)?;
+    merge_json_values(&mut layered, &crate::fs::jsonc::parse(&std::fs::read_to_string(overlay)?)?);
+    let path = crate::fs::state::state_dir(dest_dir).join(LAYERED_SETTINGS_FILE);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&layered)?)?;
+    Ok(path)
+}
+
+pub fn preview_settings_merge(source: &Path, dest: &Path) -> Result<MergePreview> {
+    let source_content = std::fs::read_to_string(source)?;
+    let mut source_json: Value = crate::fs::jsonc::parse(&source_content)?;
+    // Keys the user released from the installer are theirs to keep.
+    if let (Some(dest_dir), Value::Object(map)) = (dest.parent(), &mut source_json) {
+        for key in released_keys(dest_dir) {
+            map.remove(&key);
+        }
+    }
+
+    if !dest.exists() {
+        return Ok(MergePreview {
+            current: None,
+            merged: serde_json::to_string_pretty(&source_json)?,
+            overwritten: Vec::new(),
+        });
+    }
+
+    let dest_content = std::fs::read_to_string(dest)?;
+    let mut dest_json: Value = crate::fs::jsonc::parse(&dest_content)?;
+    let current = serde_json::to_string_pretty(&dest_json)?;
+
+    let mut overwritten = Vec::new();
+    collect_overwrites(&dest_json, &source_json, "", &mut overwritten);
+
+    merge_json_values(&mut dest_json, &source_json);
+
+    Ok(MergePreview {
+        current: Some(current),
+        merged: serde_json::to_string_pretty(&dest_json)?,
+        overwritten,
+    })
+}
+
+/// Walk the same path `merge_json_values` takes and record every leaf the
+/// merge replaces with a different value. `hooks` only ever appends, so it
+/// never overwrites.
+fn collect_overwrites(dest: &Value, source: &Value, prefix: &str, out: &mut Vec<String>) {
+    match (dest, source) {
+        (Value::Object(dest_map), Value::Object(source_map)) => {
+            for (key, source_value) in source_map {
+                if key == "hooks" {
+                    continue;
+                }
+                if let Some(dest_value) = dest_map.get(key) {
+                    let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                    collect_overwrites(dest_value, source_value, &path, out);
+                }
+            }
+        }
+        (dest, source) if dest != source => out.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
+fn merge_json_values(dest: &mut Value, source: &Value) {
+    match (dest, source) {
+        (Value::Object(dest_map), Value::Object(source_map)) => {
+            for (key, source_value) in source_map {
+                match dest_map.get_mut(key) {
+                    Some(dest_value) => {
+                        if key == "hooks" {
+                            merge_hooks(dest_value, source_value);
+                        } else {
+                            merge_json_values(dest_value, source_value);
+                        }
+                    }
+                    None => {
+                        dest_map.insert(key.clone(), source_value.clone());
+                    }
+                }
+            }
+        }
+        (dest, source) => {
+            *dest = source.clone();
+        }
+    }
+}
+
+fn merge_hooks(dest: &mut Value, source: &Value) {
+    if let (Value::Object(dest_hooks), Value::Object(source_hooks)) = (dest, source) {
+        for (hook_type, source_hook_array) in source_hooks {
+            match dest_hooks.get_mut(hook_type) {
+                Some(Value::Array(dest_array)) => {
+                    if let Value::Array(source_array) = source_hook_array {
+                        for source_item in source_array {
+                            if !dest_array.contains(source_item) {
+                                dest_array.push(source_item.clone());
+                            }
+                        }
+                    }
+                }
+                None => {
+                    dest_hooks.insert(hook_type.clone(), source_hook_array.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn preview_matches_written_merge_and_lists_overwrites() {
+        let dir = temp_dir("merge_preview");
+        let source = dir.join("source.json");
+        let dest = dir.join("settings.json");
+        std::fs::write(&source, r#"{"model":"opus","env":{"A":"1","B":"2"},"hooks":{"Stop":[{"hooks":[{"type":"command","command":"x"}]}]}}"#).unwrap();
+        std::fs::write(&dest, r#"{"model":"sonnet","env":{"A":"1"},"hooks":{"Stop":[{"hooks":[{"type":"command","command":"y"}]}]},"mine":true}"#).unwrap();
+
+        let preview = preview_settings_merge(&source, &dest).unwrap();
+        assert_eq!(preview.overwritten, vec!["model".to_string()]);
+        assert!(preview.current.as_deref().unwrap().contains("sonnet"));
+
+        merge_settings_json(&source, &dest).unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), preview.merged);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,15 +1,30 @@
 mod merge;
+mod binary_arch;
 mod process;
 mod settings;
 mod mcp;
 mod plugin;
+mod packs;
+mod batch_hook;
+mod cursor;
+mod opencode;
+mod readonly;
+mod settings_keys;
+mod settings_schema;
+mod adopt;
+mod installed;
+mod configured;
+mod base;
+mod dangling;
 
 use std::path::Path;
 use anyhow::Result;
 
-use crate::app::TargetCli;
-use crate::component::{Component, ComponentType};
+use crate::TargetCli;
+use crate::component::{Component, ComponentType, HookConfig};
 use crate::fs::{create_cli_command, run_with_timeout};
+use crate::fs::audit::{self, EventKind};
+use crate::fs::backup::Snapshot;
 use merge::merge_settings_json;
 use settings::{
     register_hook_in_settings, unregister_hook_from_settings,
@@ -18,12 +33,32 @@ use settings::{
 };
 
 // Re-export public API
-pub use mcp::{install_mcp_server, remove_mcp_server, McpInstallConfig};
-pub use plugin::{install_plugin, remove_plugin};
+pub use mcp::{
+    install_mcp_server, remove_mcp_server, McpInstallConfig,
+    mcp_config_path, configured_mcp_servers, remove_configured_mcp_server,
+};
+pub(crate) use mcp::{expand_mcp_template, mcp_remove_command};
+pub use plugin::{install_plugin, remove_plugin, remove_marketplace};
+pub use configured::{mcp_server_configured, plugin_enabled};
+pub use merge::{layer_settings, preview_settings_merge, MergePreview, LAYERED_SETTINGS_FILE};
+pub use adopt::adopt_installed_version;
+pub use dangling::{dangling_settings, DanglingSetting};
+pub use installed::{installed_files, install_times, RECORD_FILE as INSTALLED_RECORD_FILE};
+pub(crate) use installed::record_key;
+pub(crate) use settings::split_env_prefix;
+pub(crate) use settings_schema::validate_settings;
+pub use settings_schema::settings_problems;
+pub use base::{has_local_edits, merged_content, merge3, BASE_DIR};
+pub use packs::{stale_pack_files, remove_stale_pack_files, MANIFEST_FILE as PACK_MANIFEST_FILE};
+pub use settings_keys::{settings_keys, set_key_owner, KeyOwner, SettingsKey, OWNERSHIP_FILE as SETTINGS_KEYS_FILE};
+pub use batch_hook::{run_batch_hook, BATCH_HOOK_TIMEOUT_SECS};
+pub use process::ProcessFailure;
+pub use cursor::{is_cursor_rule, cursor_rule_content, CURSOR_RULE_EXTENSION};
 pub use settings::{
     set_output_style, unset_output_style,
     set_statusline, unset_statusline,
-    remove_managed_settings_sections,
+    remove_managed_settings_sections, MANAGED_SETTINGS_KEYS,
+    move_hook_in_settings, read_hook_positions, read_hook_env, set_hook_env,
 };
 
 /// Timeout for the pre-flight `--version` probe. Long enough to absorb
@@ -52,7 +87,13 @@ const PREFLIGHT_TIMEOUT_SECS: u64 = 8;
 /// also immediate (`spawn` returns `ErrorKind::NotFound` without waiting);
 /// the only slow path is a CLI that hangs on stdin or work it does at
 /// startup — rare in practice, hence the 8-second budget.
-pub(crate) fn preflight_cli_available(target_cli: TargetCli) -> Result<()> {
+///
+/// Targets that don't manage MCP servers through a CLI (Cursor, OpenCode) pass
+/// without a probe: hibi writes their config files itself.
+pub fn preflight_cli_available(target_cli: TargetCli) -> Result<()> {
+    if !target_cli.manages_mcp_via_cli() {
+        return Ok(());
+    }
     let mut cmd = create_cli_command(target_cli);
     cmd.args(["--version"]);
     match run_with_timeout(&mut cmd, PREFLIGHT_TIMEOUT_SECS) {
@@ -128,7 +169,30 @@ pub fn auto_cleanup_deprecated_hooks(source_dir: &Path, dest_dir: &Path) -> Vec<
     cleaned
 }
 
-pub fn install_component(component: &Component, _source_dir: &Path, dest_dir: &Path) -> Result<()> {
+/// Install a single component. `hook_peers` lists the configs of every
+/// known hook so a newly registered hook lands at its priority slot among
+/// already-registered ones; it is ignored for non-hook components.
+///
+/// With a `backup` snapshot, a dest file that differs from the source and
+/// any settings.json about to be rewritten are copied into it first.
+pub fn install_component(
+    component: &Component,
+    _source_dir: &Path,
+    dest_dir: &Path,
+    hook_peers: &[HookConfig],
+    backup: Option<&Snapshot>,
+) -> Result<()> {
+    if matches!(component.component_type, ComponentType::Hooks | ComponentType::Statusline) {
+        binary_arch::ensure_runs_here(&component.name, &component.source_path)?;
+    }
+
+    if let Some(snapshot) = backup {
+        snapshot.save_if_changed(&component.dest_path, &component.source_path)?;
+        if touches_settings(component) {
+            snapshot.save(&dest_dir.join("settings.json"))?;
+        }
+    }
+
     match &component.component_type {
         ComponentType::Hooks => {
             if let Some(config) = &component.hook_config {
@@ -137,7 +201,7 @@ pub fn install_component(component: &Component, _source_dir: &Path, dest_dir: &P
                 }
                 // Copy hook binary and register in settings.json
                 copy_file(component)?;
-                register_hook_in_settings(dest_dir, config)?;
+                register_hook_in_settings(dest_dir, config, hook_peers)?;
             } else {
                 copy_file(component)?;
             }
@@ -145,12 +209,14 @@ pub fn install_component(component: &Component, _source_dir: &Path, dest_dir: &P
         ComponentType::OutputStyles => {
             // Copy output style file
             copy_file(component)?;
+            installed::record_installed(dest_dir, component)?;
             // Auto-register in settings.json if no style is currently set
             register_output_style_in_settings(dest_dir, &component.name)?;
         }
         ComponentType::Statusline => {
             // Copy statusline file
             copy_file(component)?;
+            installed::record_installed(dest_dir, component)?;
             // Auto-register in settings.json if no statusline is currently set
             register_statusline_in_settings(dest_dir, &component.name)?;
         }
@@ -158,13 +224,34 @@ pub fn install_component(component: &Component, _source_dir: &Path, dest_dir: &P
             // Merge settings.json instead of overwriting
             merge_settings_json(&component.source_path, &component.dest_path)?;
         }
+        ComponentType::Rules if is_cursor_rule(component) => {
+            let result = cursor::write_cursor_rule(component);
+            audit::record(EventKind::FileCopy, "install as Cursor rule", &component.dest_path.to_string_lossy(), &result);
+            result?;
+        }
         _ => {
-            copy_file(component)?;
+            match base::merged_content(component, dest_dir) {
+                Some(merged) => write_merged(component, &merged)?,
+                None => copy_file(component)?,
+            }
+            installed::record_installed(dest_dir, component)?;
+            base::save_base(dest_dir, component)?;
+            packs::record_pack_file(dest_dir, component)?;
         }
     }
     Ok(())
 }
 
+/// Whether installing `component` may rewrite settings.json.
+fn touches_settings(component: &Component) -> bool {
+    match &component.component_type {
+        ComponentType::Hooks => component.hook_config.is_some(),
+        ComponentType::OutputStyles | ComponentType::Statusline => true,
+        ComponentType::ConfigFile => component.name == "settings.json",
+        _ => false,
+    }
+}
+
 pub fn remove_component(component: &Component, dest_dir: &Path) -> Result<()> {
     match &component.component_type {
         ComponentType::Hooks => {
@@ -184,6 +271,7 @@ pub fn remove_component(component: &Component, dest_dir: &Path) -> Result<()> {
             if component.dest_path.exists() {
                 std::fs::remove_file(&component.dest_path)?;
             }
+            installed::forget_installed(dest_dir, component)?;
             unregister_output_style_if_matches(dest_dir, &component.name)?;
         }
         ComponentType::ConfigFile if component.name == "settings.json" => {
@@ -195,12 +283,46 @@ pub fn remove_component(component: &Component, dest_dir: &Path) -> Result<()> {
             if component.dest_path.exists() {
                 std::fs::remove_file(&component.dest_path)?;
             }
+            installed::forget_installed(dest_dir, component)?;
+            base::forget_base(dest_dir, component)?;
+            packs::forget_pack_file(dest_dir, component)?;
         }
     }
     Ok(())
 }
 
+/// Give a fresh copy its source's mtime, so scans can tell the pair is
+/// unchanged from metadata alone. Skipped for a source modified within the
+/// racy window, whose next write might not change its mtime. Best effort:
+/// a copy that keeps its own mtime is simply compared by content.
+fn preserve_mtime(source: &Path, dest: &Path) {
+    let Ok(modified) = std::fs::metadata(source).and_then(|m| m.modified()) else { return };
+    if !crate::fs::scanner::is_settled(modified) {
+        return;
+    }
+    let file = std::fs::File::options().write(true).open(dest).or_else(|_| std::fs::File::open(dest));
+    if let Ok(file) = file {
+        let _ = file.set_modified(modified);
+    }
+}
+
+/// Write the three-way merge of the source and local edits over the
+/// installed copy; see `base`.
+fn write_merged(component: &Component, merged: &str) -> Result<()> {
+    let result = readonly::write_over_readonly(&component.dest_path, || std::fs::write(&component.dest_path, merged));
+    audit::record(EventKind::FileCopy, "merge", &component.dest_path.to_string_lossy(), &result);
+    result?;
+    Ok(())
+}
+
+/// Copy the component's file to its destination, logging the outcome.
 fn copy_file(component: &Component) -> Result<()> {
+    let result = copy_component_file(component);
+    audit::record(EventKind::FileCopy, "install", &component.dest_path.to_string_lossy(), &result);
+    result
+}
+
+fn copy_component_file(component: &Component) -> Result<()> {
     // Security: reject paths containing '..' to prevent path traversal
     if component.dest_path.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
         anyhow::bail!("Security: destination path contains '..' component: {:?}", component.dest_path);
@@ -208,11 +330,14 @@ fn copy_file(component: &Component) -> Result<()> {
 
     // Create parent directory if needed
     if let Some(parent) = component.dest_path.parent() {
-        std::fs::create_dir_all(parent)?;
+        readonly::create_parent_dirs(parent)?;
     }
 
-    // Copy file
-    std::fs::copy(&component.source_path, &component.dest_path)?;
+    // Copy file, over a read-only dest if need be
+    readonly::write_over_readonly(&component.dest_path, || {
+        std::fs::copy(&component.source_path, &component.dest_path)
+    })?;
+    preserve_mtime(&component.source_path, &component.dest_path);
 
     // Set executable permission for shell scripts (Unix only).
     // On Windows, .sh scripts are executed via Git Bash; .exe hooks are already executable.
@@ -0,0 +1,19 @@
+//! Entry format for the OpenCode target, whose MCP servers live under
+//! the `mcp` key of `opencode.json` (written by `mcp.rs`).
+
+use serde_json::{json, Map, Value};
+
+use crate::mcp::McpServer;
+
+/// OpenCode's `mcp` entry: a `local` server with the full command line as
+/// an array, or a `remote` one with a `url`.
+pub(super) fn opencode_mcp_entry(server: &McpServer, command: Option<Vec<String>>, env: Map<String, Value>) -> Value {
+    let Some(command) = command else {
+        return json!({ "type": "remote", "url": server.def.url, "enabled": true });
+    };
+    let mut entry = json!({ "type": "local", "command": command, "enabled": true });
+    if !env.is_empty() {
+        entry["environment"] = Value::Object(env);
+    }
+    entry
+}
@@ -0,0 +1,192 @@
+//! Per-file manifest for directory components ("packs").
+//!
+//! A skill pack is installed file by file, so files deleted upstream would
+//! otherwise linger in `dest_dir`. Every installed pack file is recorded in
+//! `<dest_dir>/.hibi/packs.yaml`; before an install, recorded files that no
+//! source produces any more are reported as stale so they can be removed.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::component::{Component, ComponentType, InstallStatus};
+use crate::fs::state::{load_yaml, save_yaml};
+
+/// File name inside the installer state dir (see `state`).
+pub const MANIFEST_FILE: &str = "packs.yaml";
+
+/// Top-level YAML structure for `packs.yaml`: pack key -> files
+/// relative to the pack directory.
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct PackManifest {
+    #[serde(default)]
+    packs: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A file inside a directory component, split into its pack key (e.g.
+/// `skills/rust-best-practices`) and its path relative to the pack. The key
+/// follows the dest path, so a remapped skills folder keeps working.
+fn pack_entry(dest_dir: &Path, component: &Component) -> Option<(String, String)> {
+    if component.component_type != ComponentType::Skills {
+        return None;
+    }
+    let rel = component.dest_path.strip_prefix(dest_dir).ok()?;
+    split_pack_path(&rel.to_string_lossy())
+}
+
+/// `skills/<pack>/<file...>` -> (`skills/<pack>`, `<file...>`).
+fn split_pack_path(rel: &str) -> Option<(String, String)> {
+    let rel = rel.replace('\\', "/");
+    let mut parts = rel.splitn(3, '/');
+    let (type_dir, pack, file) = (parts.next()?, parts.next()?, parts.next()?);
+    Some((format!("{}/{}", type_dir, pack), file.to_string()))
+}
+
+fn manifest_path(dest_dir: &Path) -> PathBuf {
+    crate::fs::state::state_dir(dest_dir).join(MANIFEST_FILE)
+}
+
+fn load_manifest(dest_dir: &Path) -> Result<PackManifest> {
+    load_yaml(&manifest_path(dest_dir))
+}
+
+fn save_manifest(dest_dir: &Path, manifest: &PackManifest) -> Result<()> {
+    save_yaml(&manifest_path(dest_dir), manifest)
+}
+
+/// Record an installed pack file. No-op for non-pack components.
+pub(super) fn record_pack_file(dest_dir: &Path, component: &Component) -> Result<()> {
+    let Some((pack, file)) = pack_entry(dest_dir, component) else { return Ok(()) };
+    let mut manifest = load_manifest(dest_dir)?;
+    if manifest.packs.entry(pack).or_default().insert(file) {
+        save_manifest(dest_dir, &manifest)?;
+    }
+    Ok(())
+}
+
+/// Forget a removed pack file. No-op for non-pack components.
+pub(super) fn forget_pack_file(dest_dir: &Path, component: &Component) -> Result<()> {
+    let Some((pack, file)) = pack_entry(dest_dir, component) else { return Ok(()) };
+    let mut manifest = load_manifest(dest_dir)?;
+    let Some(files) = manifest.packs.get_mut(&pack) else { return Ok(()) };
+    if files.remove(&file) {
+        if files.is_empty() {
+            manifest.packs.remove(&pack);
+        }
+        save_manifest(dest_dir, &manifest)?;
+    }
+    Ok(())
+}
+
+/// Files recorded for the packs touched by `installing` that no source
+/// component produces any more and that still exist in `dest_dir`.
+/// `all` is the full scanned component list.
+pub fn stale_pack_files(dest_dir: &Path, installing: &[&Component], all: &[Component]) -> Vec<PathBuf> {
+    let packs: BTreeSet<String> = installing.iter()
+        .filter_map(|c| pack_entry(dest_dir, c).map(|(pack, _)| pack))
+        .collect();
+    if packs.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(manifest) = load_manifest(dest_dir) else { return Vec::new() };
+    let current: HashSet<(String, String)> = all.iter()
+        .filter(|c| !matches!(c.status, InstallStatus::External | InstallStatus::Orphaned))
+        .filter_map(|c| pack_entry(dest_dir, c))
+        .collect();
+
+    let mut stale = Vec::new();
+    for pack in packs {
+        let Some(files) = manifest.packs.get(&pack) else { continue };
+        for file in files {
+            if current.contains(&(pack.clone(), file.clone())) {
+                continue;
+            }
+            let path = dest_dir.join(&pack).join(file);
+            if path.is_file() {
+                stale.push(path);
+            }
+        }
+    }
+    stale
+}
+
+/// Delete stale pack files, drop them from the manifest and prune empty
+/// directories left behind. Returns how many files were removed.
+pub fn remove_stale_pack_files(dest_dir: &Path, stale: &[PathBuf]) -> Result<usize> {
+    let mut manifest = load_manifest(dest_dir)?;
+    let mut removed = 0;
+
+    for path in stale {
+        let Ok(rel) = path.strip_prefix(dest_dir) else { continue };
+        let Some((pack, file)) = split_pack_path(&rel.to_string_lossy()) else { continue };
+
+        if path.is_file() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed += 1;
+        }
+        if let Some(files) = manifest.packs.get_mut(&pack) {
+            files.remove(&file);
+        }
+
+        // Prune now-empty parents up to (not including) the pack directory.
+        let pack_dir = dest_dir.join(&pack);
+        let mut dir = path.parent();
+        while let Some(d) = dir {
+            if d == pack_dir || std::fs::remove_dir(d).is_err() {
+                break;
+            }
+            dir = d.parent();
+        }
+    }
+
+    manifest.packs.retain(|_, files| !files.is_empty());
+    save_manifest(dest_dir, &manifest)?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn skill(dest: &Path, name: &str) -> Component {
+        let path = dest.join("skills").join(name);
+        Component::new(ComponentType::Skills, name.to_string(), path.clone(), path, InstallStatus::New)
+    }
+
+    #[test]
+    fn detects_and_removes_files_dropped_upstream() {
+        let dest = temp_dir("stale");
+        let kept = skill(&dest, "pack/SKILL.md");
+        let dropped = skill(&dest, "pack/refs/old.md");
+        for c in [&kept, &dropped] {
+            std::fs::create_dir_all(c.dest_path.parent().unwrap()).unwrap();
+            std::fs::write(&c.dest_path, "x").unwrap();
+            record_pack_file(&dest, c).unwrap();
+        }
+
+        // Upstream now only ships SKILL.md.
+        let all = vec![kept.clone()];
+        let stale = stale_pack_files(&dest, &[&kept], &all);
+        assert_eq!(stale, vec![dropped.dest_path.clone()]);
+
+        assert_eq!(remove_stale_pack_files(&dest, &stale).unwrap(), 1);
+        assert!(!dropped.dest_path.exists());
+        assert!(!dest.join("skills/pack/refs").exists());
+        assert!(kept.dest_path.exists());
+        assert!(stale_pack_files(&dest, &[&kept], &all).is_empty());
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn top_level_skill_files_are_not_packs() {
+        let dest = temp_dir("flat");
+        assert!(pack_entry(&dest, &skill(&dest, "single.md")).is_none());
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+}
@@ -0,0 +1,92 @@
+use std::process::Command;
+use std::sync::mpsc::{Receiver, Sender};
+use anyhow::Result;
+
+use crate::plugin::Plugin;
+use super::process::{
+    redacted_command, spawn_cancelable_process, run_with_timeout,
+    ProcessConfig, QUICK_COMMAND_TIMEOUT_SECS,
+};
+use super::mcp::ensure_marketplace_added;
+use crate::TargetCli;
+use crate::fs::audit::{self, EventKind};
+use crate::fs::create_cli_command;
+
+/// Command that uninstalls a partially installed plugin.
+fn cleanup_plugin_command(plugin: &Plugin) -> Command {
+    let mut command = create_cli_command(TargetCli::Claude);
+    command.args(["plugin", "uninstall", &plugin.def.name]);
+    command
+}
+
+pub fn install_plugin(
+    plugin: &Plugin,
+    timeout_secs: u64,
+    cancel_rx: &Receiver<()>,
+    progress_tx: Option<&Sender<String>>,
+) -> Result<()> {
+    ensure_marketplace_added(
+        &plugin.def.marketplace,
+        &plugin.def.source,
+        timeout_secs,
+        cancel_rx,
+        progress_tx,
+    )?;
+
+    let plugin_ref = format!("{}@{}", plugin.def.name, plugin.def.marketplace);
+    let mut command = create_cli_command(TargetCli::Claude);
+    command.args(["plugin", "install", &plugin_ref]);
+
+    spawn_cancelable_process(
+        &mut command,
+        ProcessConfig {
+            timeout_secs,
+            cancel_rx,
+            item_name: &plugin.def.name,
+            action: "install plugin",
+            cleanup: Some(cleanup_plugin_command(plugin)),
+            progress_tx,
+            output_tx: None,
+        },
+    )
+}
+
+pub fn remove_plugin(
+    plugin: &Plugin,
+    timeout_secs: u64,
+    cancel_rx: &Receiver<()>,
+    progress_tx: Option<&Sender<String>>,
+) -> Result<()> {
+    let mut command = create_cli_command(TargetCli::Claude);
+    command.args(["plugin", "uninstall", &plugin.def.name]);
+
+    spawn_cancelable_process(
+        &mut command,
+        ProcessConfig {
+            timeout_secs,
+            cancel_rx,
+            item_name: &plugin.def.name,
+            action: "remove plugin",
+            cleanup: None,
+            progress_tx,
+            output_tx: None,
+        },
+    )
+}
+
+/// Unregister a plugin marketplace via `claude plugin marketplace remove`.
+/// Non-cancelable; bounded by the quick-command timeout.
+pub fn remove_marketplace(marketplace: &str) -> Result<()> {
+    let mut command = create_cli_command(TargetCli::Claude);
+    command.args(["plugin", "marketplace", "remove", marketplace]);
+
+    let result = run_with_timeout(&mut command, QUICK_COMMAND_TIMEOUT_SECS).and_then(|output| {
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to remove marketplace {}: {}", marketplace, stderr.trim());
+        }
+        Ok(())
+    });
+    audit::record(EventKind::Command, "remove marketplace", &redacted_command(&command), &result);
+    result
+}
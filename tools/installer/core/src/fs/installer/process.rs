@@ -0,0 +1,393 @@
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use std::thread;
+use std::io::{BufRead, BufReader};
+use std::sync::mpsc::{Receiver, Sender};
+use anyhow::Result;
+use wait_timeout::ChildExt;
+
+use crate::fs::audit::{self, EventKind};
+
+/// Maximum capture size per output stream (1 MB) to prevent memory exhaustion.
+pub(super) const MAX_OUTPUT_BYTES: usize = 1_024 * 1_024;
+
+/// Default timeout for quick, non-cancelable operations (e.g., marketplace list).
+pub(super) const QUICK_COMMAND_TIMEOUT_SECS: u64 = 15;
+
+/// Timeout for cleanup commands (seconds).
+const CLEANUP_TIMEOUT_SECS: u64 = 10;
+
+/// How often a running cleanup reports that it is still going (seconds).
+const CLEANUP_REPORT_INTERVAL_SECS: u64 = 2;
+
+/// Wait time after killing a child process (milliseconds).
+const KILL_WAIT_MS: u64 = 500;
+
+/// Polling interval for process completion checks (milliseconds).
+const POLL_INTERVAL_MS: u64 = 100;
+
+/// How a cleanup command ended.
+#[derive(Debug, PartialEq)]
+pub(super) enum CleanupOutcome {
+    Succeeded,
+    Failed,
+    TimedOut,
+    Cancelled,
+}
+
+/// Send a progress line if the caller asked for them.
+fn report(progress_tx: Option<&Sender<String>>, line: String) {
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(line);
+    }
+}
+
+/// `program arg1 arg2` for log lines.
+fn describe_command(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|s| s.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Flags whose following `KEY=VALUE` argument holds an env value.
+const ENV_FLAGS: &[&str] = &["-e", "--env"];
+
+/// Shell-ready form of `command` for the processing log, so a failed step
+/// can be rerun by hand: arguments quoted, env values replaced with `REDACTED`,
+/// prefixed with a `cd` when it runs in another directory.
+pub(super) fn redacted_command(command: &Command) -> String {
+    let mut parts = vec![command.get_program().to_string_lossy().into_owned()];
+    let mut after_env_flag = false;
+    for arg in command.get_args() {
+        let arg = arg.to_string_lossy();
+        let shown = match arg.split_once('=') {
+            Some((key, _)) if after_env_flag => format!("{}=REDACTED", key),
+            _ => arg.into_owned(),
+        };
+        after_env_flag = ENV_FLAGS.contains(&shown.as_str());
+        parts.push(shown);
+    }
+    let line = parts.iter()
+        .map(|p| shlex::try_quote(p).map_or_else(|_| p.clone(), |q| q.into_owned()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    match command.get_current_dir() {
+        Some(dir) => format!("cd {} && {}", shlex::try_quote(&dir.to_string_lossy()).unwrap_or_default(), line),
+        None => line,
+    }
+}
+
+/// Run a cleanup command with a `CLEANUP_TIMEOUT_SECS` budget, streaming
+/// its progress to `progress_tx`. Another cancel signal aborts it. The
+/// child is killed on timeout or cancel so it can't linger as an orphan.
+pub(super) fn run_cleanup_command(
+    command: &mut Command,
+    cancel_rx: &Receiver<()>,
+    progress_tx: Option<&Sender<String>>,
+) -> CleanupOutcome {
+    let described = describe_command(command);
+    report(progress_tx, format!(
+        "[INFO] Cleaning up: {} (timeout {}s, Esc to abort)",
+        described, CLEANUP_TIMEOUT_SECS
+    ));
+
+    let mut child = match command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            report(progress_tx, format!("[ERR] Cleanup could not start: {}", e));
+            audit::record(EventKind::Command, "cleanup", &redacted_command(command), &Err::<(), _>(e));
+            return CleanupOutcome::Failed;
+        }
+    };
+
+    let start = Instant::now();
+    let mut last_report = 0;
+    let outcome = loop {
+        match child.wait_timeout(Duration::from_millis(POLL_INTERVAL_MS)) {
+            Ok(Some(status)) if status.success() => break CleanupOutcome::Succeeded,
+            Ok(Some(_)) | Err(_) => break CleanupOutcome::Failed,
+            Ok(None) => {}
+        }
+
+        if cancel_rx.try_recv().is_ok() {
+            let _ = child.kill();
+            break CleanupOutcome::Cancelled;
+        }
+
+        let elapsed = start.elapsed().as_secs();
+        if elapsed >= CLEANUP_TIMEOUT_SECS {
+            let _ = child.kill();
+            break CleanupOutcome::TimedOut;
+        }
+        if elapsed >= last_report + CLEANUP_REPORT_INTERVAL_SECS {
+            last_report = elapsed;
+            report(progress_tx, format!(
+                "[INFO] Cleanup still running ({}s/{}s)",
+                elapsed, CLEANUP_TIMEOUT_SECS
+            ));
+        }
+    };
+    let _ = child.wait_timeout(Duration::from_millis(KILL_WAIT_MS));
+
+    report(progress_tx, match outcome {
+        CleanupOutcome::Succeeded => "[OK] Cleanup complete".to_string(),
+        CleanupOutcome::Failed => format!("[WARN] Cleanup failed; run `{}` manually", described),
+        CleanupOutcome::TimedOut => format!(
+            "[WARN] Cleanup timed out after {}s; run `{}` manually",
+            CLEANUP_TIMEOUT_SECS, described
+        ),
+        CleanupOutcome::Cancelled => format!("[WARN] Cleanup aborted; run `{}` manually", described),
+    });
+    let result = match outcome {
+        CleanupOutcome::Succeeded => Ok(()),
+        CleanupOutcome::Failed => Err("failed"),
+        CleanupOutcome::TimedOut => Err("timed out"),
+        CleanupOutcome::Cancelled => Err("aborted"),
+    };
+    audit::record(EventKind::Command, "cleanup", &redacted_command(command), &result);
+    outcome
+}
+
+/// Kill a child process, wait briefly for it to terminate, then run the
+/// cleanup command if there is one. Returns the cleanup outcome.
+fn kill_and_cleanup(
+    child: &mut std::process::Child,
+    cleanup: Option<Command>,
+    cancel_rx: &Receiver<()>,
+    progress_tx: Option<&Sender<String>>,
+) -> Option<CleanupOutcome> {
+    let _ = child.kill();
+    let _ = child.wait_timeout(Duration::from_millis(KILL_WAIT_MS));
+    cleanup.map(|mut command| run_cleanup_command(&mut command, cancel_rx, progress_tx))
+}
+
+/// Suffix for abort errors describing what cleanup left behind.
+fn cleanup_suffix(outcome: Option<CleanupOutcome>) -> &'static str {
+    match outcome {
+        None => "",
+        Some(CleanupOutcome::Succeeded) => " (cleaned up)",
+        Some(_) => " (partial state left; manual cleanup needed)",
+    }
+}
+
+/// A command that exited non-zero, with what it printed. Displays as the
+/// one-line `Failed to <action> <item>: <stderr>` message; the processing
+/// view finds it in the error chain to show the full output.
+#[derive(Debug)]
+pub struct ProcessFailure {
+    pub message: String,
+    /// Command line as logged, env values redacted.
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl std::fmt::Display for ProcessFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ProcessFailure {}
+
+/// Read a child's output stream to its end, echoing each line to `echo`
+/// and keeping up to `MAX_OUTPUT_BYTES` of it.
+fn capture_output(stream: impl std::io::Read, echo: Option<Sender<String>>) -> String {
+    let mut output = String::new();
+    let mut truncated = false;
+    for line in BufReader::new(stream).lines().map_while(Result::ok) {
+        if let Some(tx) = &echo {
+            let _ = tx.send(format!("  {}", line));
+        }
+        if truncated {
+            continue;
+        }
+        if output.len() + line.len() + 1 > MAX_OUTPUT_BYTES {
+            output.push_str("[... output truncated]\n");
+            truncated = true;
+            continue;
+        }
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Format a process failure error message from stderr output.
+fn format_process_error(action: &str, item_name: &str, stderr_output: &str) -> String {
+    if stderr_output.trim().is_empty() {
+        format!("Failed to {} {}", action, item_name)
+    } else {
+        format!("Failed to {} {}: {}", action, item_name, stderr_output.trim())
+    }
+}
+
+/// Re-export the shared `run_with_timeout` for use within the installer module.
+pub(super) use crate::fs::run_with_timeout;
+use crate::fs::enrich_spawn_error;
+
+/// Configuration for a cancelable process operation.
+pub(super) struct ProcessConfig<'a> {
+    pub timeout_secs: u64,
+    pub cancel_rx: &'a Receiver<()>,
+    pub item_name: &'a str,
+    pub action: &'a str,
+    /// Command that undoes a partial operation after timeout or cancel.
+    pub cleanup: Option<Command>,
+    /// Receives the `[CMD]` line of the command run and cleanup progress
+    /// lines for the processing log.
+    pub progress_tx: Option<&'a Sender<String>>,
+    /// Receives the command's own stdout/stderr lines, indented, as they
+    /// are printed. `None` discards stdout and keeps stderr for errors only.
+    pub output_tx: Option<&'a Sender<String>>,
+}
+
+/// Spawn a process with timeout and cancellation support.
+///
+/// Captures stdout/stderr in background threads to prevent pipe blocking.
+/// Each stream is capped at `MAX_OUTPUT_BYTES` to prevent memory exhaustion.
+/// If `cleanup` is provided, it runs on timeout or cancellation; a further
+/// cancel signal aborts it. The outcome goes to the audit log.
+pub(super) fn spawn_cancelable_process(
+    command: &mut Command,
+    config: ProcessConfig,
+) -> Result<()> {
+    let action = config.action;
+    let result = run_cancelable_process(command, config);
+    audit::record(EventKind::Command, action, &redacted_command(command), &result);
+    result
+}
+
+fn run_cancelable_process(
+    command: &mut Command,
+    config: ProcessConfig,
+) -> Result<()> {
+    let has_cleanup = config.cleanup.is_some();
+    let mut cleanup_slot = config.cleanup;
+    let progress_tx = config.progress_tx;
+    report(progress_tx, format!("[CMD] {}", redacted_command(command)));
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| enrich_spawn_error(command, e))?;
+
+    let stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
+
+    let stdout_echo = config.output_tx.cloned();
+    let stdout_thread = thread::spawn(move || capture_output(stdout, stdout_echo));
+    let stderr_echo = config.output_tx.cloned();
+    let stderr_thread = thread::spawn(move || capture_output(stderr, stderr_echo));
+
+    let timeout_duration = Duration::from_secs(config.timeout_secs);
+    let start_time = Instant::now();
+
+    loop {
+        if start_time.elapsed() >= timeout_duration {
+            let outcome = kill_and_cleanup(&mut child, cleanup_slot.take(), config.cancel_rx, progress_tx);
+            // Threads exit naturally when pipes close after kill; no join needed on abort paths.
+            if has_cleanup {
+                anyhow::bail!("Installation timed out after {}s{}", config.timeout_secs, cleanup_suffix(outcome));
+            } else {
+                anyhow::bail!(
+                    "Failed to {} {}: timed out after {}s",
+                    config.action, config.item_name, config.timeout_secs
+                );
+            }
+        }
+
+        // Check process completion BEFORE cancel signal.
+        // This prevents silently rolling back a successful operation when
+        // a cancel signal arrives in the same tick as process completion.
+        match child.wait_timeout(Duration::from_millis(POLL_INTERVAL_MS)) {
+            Ok(Some(status)) => {
+                let stdout_output = stdout_thread.join().unwrap_or_default();
+                let stderr_output = stderr_thread.join().unwrap_or_default();
+
+                if !status.success() {
+                    return Err(ProcessFailure {
+                        message: format_process_error(config.action, config.item_name, &stderr_output),
+                        command: redacted_command(command),
+                        stdout: stdout_output,
+                        stderr: stderr_output,
+                    }.into());
+                }
+                return Ok(());
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let _ = child.kill();
+                anyhow::bail!("System error during wait: {}", e);
+            }
+        }
+
+        match config.cancel_rx.try_recv() {
+            Ok(_) => {
+                let outcome = kill_and_cleanup(&mut child, cleanup_slot.take(), config.cancel_rx, progress_tx);
+                if has_cleanup {
+                    anyhow::bail!("Cancelled by user{}", cleanup_suffix(outcome));
+                } else {
+                    anyhow::bail!("Cancelled by user");
+                }
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn cleanup_reports_success_and_failure() {
+        let (_cancel_tx, cancel_rx) = mpsc::channel();
+        let (progress_tx, progress_rx) = mpsc::channel();
+
+        let outcome = run_cleanup_command(&mut Command::new("true"), &cancel_rx, Some(&progress_tx));
+        assert_eq!(outcome, CleanupOutcome::Succeeded);
+        let outcome = run_cleanup_command(&mut Command::new("false"), &cancel_rx, Some(&progress_tx));
+        assert_eq!(outcome, CleanupOutcome::Failed);
+
+        let lines: Vec<String> = progress_rx.try_iter().collect();
+        assert!(lines[0].starts_with("[INFO] Cleaning up: true"));
+        assert_eq!(lines[1], "[OK] Cleanup complete");
+        assert!(lines.last().unwrap().contains("run `false` manually"));
+    }
+
+    #[test]
+    fn commands_are_logged_quoted_with_env_values_redacted() {
+        let mut command = Command::new("claude");
+        command.args(["mcp", "add", "-e", "API_KEY=s3cret", "--env", "X=1", "srv", "--", "npx", "a b"]);
+        assert_eq!(
+            redacted_command(&command),
+            "claude mcp add -e 'API_KEY=REDACTED' --env 'X=REDACTED' srv -- npx 'a b'"
+        );
+        command.current_dir("/tmp/my project");
+        assert!(redacted_command(&command).starts_with("cd '/tmp/my project' && claude "));
+    }
+
+    #[test]
+    fn cleanup_stops_on_second_cancel() {
+        let (cancel_tx, cancel_rx) = mpsc::channel();
+        cancel_tx.send(()).unwrap();
+
+        let mut command = Command::new("sleep");
+        command.arg("30");
+        let start = Instant::now();
+        assert_eq!(run_cleanup_command(&mut command, &cancel_rx, None), CleanupOutcome::Cancelled);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
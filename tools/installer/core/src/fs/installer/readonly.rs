@@ -0,0 +1,135 @@
+//! Writing over read-only destination files. A plain file the user made
+//! read-only is made writable for the write and read-only again after;
+//! anything else that refuses the write fails with the path and a fix.
+
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+/// Run `write` for `dest`. If it is refused because `dest` is a read-only
+/// regular file, lift the read-only bit, try once more, then put the
+/// file's original permissions back.
+pub(super) fn write_over_readonly<T>(dest: &Path, mut write: impl FnMut() -> io::Result<T>) -> Result<T> {
+    let err = match write() {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    if err.kind() != ErrorKind::PermissionDenied {
+        return Err(write_error(dest, err));
+    }
+
+    // Symlinks are left alone: their target may be shared with other tools.
+    let original = match std::fs::symlink_metadata(dest) {
+        Ok(meta) if meta.is_file() && meta.permissions().readonly() => meta.permissions(),
+        _ => return Err(write_error(dest, err)),
+    };
+    if std::fs::set_permissions(dest, owner_writable(original.clone())).is_err() {
+        return Err(anyhow!(
+            "{} is read-only and its permissions can't be changed (owned by another user?); run `chmod u+w {}` and retry",
+            dest.display(),
+            dest.display()
+        ));
+    }
+
+    let result = write();
+    let _ = std::fs::set_permissions(dest, original);
+    result.map_err(|e| write_error(dest, e))
+}
+
+/// `permissions` plus write access for the owner only (clearing the
+/// read-only flag on Unix would make the file writable by everyone).
+fn owner_writable(permissions: std::fs::Permissions) -> std::fs::Permissions {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::Permissions::from_mode(permissions.mode() | 0o200)
+    }
+    #[cfg(not(unix))]
+    {
+        let mut permissions = permissions;
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        permissions
+    }
+}
+
+/// Create `dir` and its parents, with the same targeted errors as writes.
+pub(super) fn create_parent_dirs(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).map_err(|e| write_error(dir, e))
+}
+
+/// Error for a write to `path` that failed with `err`, saying what to fix
+/// when the cause is permissions or a read-only mount.
+fn write_error(path: &Path, err: io::Error) -> anyhow::Error {
+    let dir = path.parent().unwrap_or(path);
+    match err.kind() {
+        ErrorKind::ReadOnlyFilesystem => anyhow!(
+            "{} is on a read-only filesystem; remount it read-write or install to another directory",
+            path.display()
+        ),
+        ErrorKind::PermissionDenied if path.is_file() => anyhow!(
+            "Permission denied writing {}; check its owner and permissions (`ls -l {}`)",
+            path.display(),
+            path.display()
+        ),
+        ErrorKind::PermissionDenied => anyhow!(
+            "Permission denied creating {}; make {} writable (`chmod u+w {}`) and retry",
+            path.display(),
+            dir.display(),
+            dir.display()
+        ),
+        _ => anyhow::Error::new(err).context(format!("Failed to write {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn readonly_file_is_retried_and_stays_readonly() {
+        let dir = temp_dir("retry");
+        let dest = dir.join("agent.md");
+        std::fs::write(&dest, "old").unwrap();
+        let mut perms = std::fs::metadata(&dest).unwrap().permissions();
+        perms.set_readonly(true);
+        std::fs::set_permissions(&dest, perms).unwrap();
+
+        // The first attempt is refused, as it would be for a non-root user.
+        let mut attempts = 0;
+        let result = write_over_readonly(&dest, || {
+            attempts += 1;
+            if attempts == 1 {
+                return Err(io::Error::from(ErrorKind::PermissionDenied));
+            }
+            assert!(!std::fs::metadata(&dest)?.permissions().readonly());
+            std::fs::write(&dest, "new")
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(attempts, 2);
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "new");
+        let perms = std::fs::metadata(&dest).unwrap().permissions();
+        assert!(perms.readonly());
+        std::fs::set_permissions(&dest, owner_writable(perms)).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refused_writes_name_the_path_and_the_fix() {
+        let dir = temp_dir("denied");
+        let dest = dir.join("missing.md");
+        let err = write_over_readonly(&dest, || Err::<(), _>(io::Error::from(ErrorKind::PermissionDenied)))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(&format!("make {} writable", dir.display())), "{err}");
+
+        let err = write_over_readonly(&dest, || Err::<(), _>(io::Error::from(ErrorKind::ReadOnlyFilesystem)))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("read-only filesystem"), "{err}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
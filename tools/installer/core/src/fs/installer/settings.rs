@@ -0,0 +1,946 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::installed::{managed_settings, update_managed_settings};
+use crate::component::{ComponentType, HookConfig, DEFAULT_HOOK_PRIORITY};
+use crate::fs::audit::{self, EventKind};
+
+/// Convert a Windows path to MSYS-style path for Git Bash compatibility.
+/// `C:\Users\...` -> `/c/Users/...`, handles any drive letter.
+#[allow(dead_code)]
+fn to_msys_path(path: &Path) -> String {
+    let s = path.to_string_lossy().replace('\\', "/");
+    if s.len() >= 3 && s.as_bytes()[1] == b':' && s.as_bytes()[0].is_ascii_alphabetic() {
+        let drive = (s.as_bytes()[0] as char).to_ascii_lowercase();
+        format!("/{}{}", drive, &s[2..])
+    } else {
+        s
+    }
+}
+
+/// Read settings.json from dest_dir, returning empty object if file doesn't exist.
+pub(super) fn read_settings(dest_dir: &Path) -> Result<Value> {
+    let settings_path = dest_dir.join("settings.json");
+    if settings_path.exists() {
+        let content = std::fs::read_to_string(&settings_path)?;
+        crate::fs::jsonc::parse(&content)
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))
+    } else {
+        Ok(serde_json::json!({}))
+    }
+}
+
+/// Write settings.json to dest_dir, creating parent directories if needed.
+/// `action` describes the change for the audit log.
+fn write_settings(dest_dir: &Path, settings: &Value, action: &str) -> Result<()> {
+    let settings_path = dest_dir.join("settings.json");
+    let result: Result<()> = (|| {
+        super::validate_settings(settings, &settings_path)?;
+        if let Some(parent) = settings_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let output = serde_json::to_string_pretty(settings)?;
+        std::fs::write(&settings_path, output)?;
+        Ok(())
+    })();
+    audit::record(EventKind::Settings, action, &settings_path.to_string_lossy(), &result);
+    result
+}
+
+pub fn set_output_style(dest_dir: &Path, style_name: &str) -> Result<()> {
+    let mut settings = read_settings(dest_dir)?;
+    settings["outputStyle"] = serde_json::json!(style_name);
+    write_settings(dest_dir, &settings, "set outputStyle")?;
+    update_managed_settings(dest_dir, |m| m.output_style = Some(style_name.to_string()))
+}
+
+/// The `statusLine` value that runs `<dest_dir>/statusline/<script_name>`.
+fn statusline_entry(dest_dir: &Path, script_name: &str) -> Value {
+    // Windows: convert to MSYS-style path for Git Bash compatibility
+    // macOS/Linux: use ~ shorthand derived from dest_dir
+    let statusline_command = if cfg!(windows) {
+        let abs_path = dest_dir.join("statusline").join(script_name);
+        let unix_path = to_msys_path(&abs_path);
+        format!("cat | {}", unix_path)
+    } else {
+        let dir_name = dest_dir.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".claude".to_string());
+        format!("~/{}/statusline/{}", dir_name, script_name)
+    };
+    serde_json::json!({
+        "type": "command",
+        "command": statusline_command
+    })
+}
+
+pub fn set_statusline(dest_dir: &Path, script_name: &str) -> Result<()> {
+    let mut settings = read_settings(dest_dir)?;
+    let status_line = statusline_entry(dest_dir, script_name);
+    settings["statusLine"] = status_line.clone();
+
+    write_settings(dest_dir, &settings, "set statusLine")?;
+    update_managed_settings(dest_dir, |m| m.status_line = Some(status_line))
+}
+
+pub fn unset_output_style(dest_dir: &Path) -> Result<()> {
+    let settings_path = dest_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    let mut settings = read_settings(dest_dir)?;
+    if let Value::Object(ref mut map) = settings {
+        map.remove("outputStyle");
+    }
+    write_settings(dest_dir, &settings, "unset outputStyle")?;
+    update_managed_settings(dest_dir, |m| m.output_style = None)
+}
+
+/// Remove `outputStyle` from settings.json ONLY if it currently points at
+/// `style_name`. Used during remove flow so that deleting an unrelated style
+/// file doesn't clear the user's chosen default.
+///
+/// `style_name` may carry a `.md` suffix (component name) or not (settings
+/// value); both forms compare equal after stripping.
+pub(super) fn unregister_output_style_if_matches(dest_dir: &Path, style_name: &str) -> Result<()> {
+    let settings_path = dest_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    let mut settings = read_settings(dest_dir)?;
+    let target = style_name.strip_suffix(".md").unwrap_or(style_name);
+    let current = settings.get("outputStyle").and_then(|v| v.as_str());
+
+    if current == Some(target) {
+        if let Value::Object(ref mut map) = settings {
+            map.remove("outputStyle");
+        }
+        write_settings(dest_dir, &settings, "unset outputStyle")?;
+        update_managed_settings(dest_dir, |m| m.output_style = None)?;
+    }
+    Ok(())
+}
+
+pub fn unset_statusline(dest_dir: &Path) -> Result<()> {
+    let settings_path = dest_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    let mut settings = read_settings(dest_dir)?;
+    if let Value::Object(ref mut map) = settings {
+        map.remove("statusLine");
+    }
+    write_settings(dest_dir, &settings, "unset statusLine")?;
+    update_managed_settings(dest_dir, |m| m.status_line = None)
+}
+
+/// Split a hook command into its leading `KEY=value` assignments and the
+/// remaining program path. Values are unquoted with POSIX rules; the path
+/// is returned verbatim so Windows backslashes survive.
+pub(crate) fn split_env_prefix(cmd: &str) -> (BTreeMap<String, String>, &str) {
+    let mut env = BTreeMap::new();
+    let mut rest = cmd.trim_start();
+
+    loop {
+        let token_len = shell_token_len(rest);
+        let token = &rest[..token_len];
+        let Some((key, _)) = token.split_once('=') else { break };
+        let is_name = !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_name || token_len == rest.len() {
+            break;
+        }
+        let value = shlex::split(token)
+            .and_then(|mut words| words.pop())
+            .and_then(|word| word.split_once('=').map(|(_, v)| v.to_string()))
+            .unwrap_or_default();
+        env.insert(key.to_string(), value);
+        rest = rest[token_len..].trim_start();
+    }
+
+    (env, rest)
+}
+
+/// Byte length of the first shell word in `s`, honouring quotes and
+/// backslash escapes so a quoted value containing spaces stays one word.
+fn shell_token_len(s: &str) -> usize {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, ch) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, ch) {
+            (None, '\\') | (Some('"'), '\\') => escaped = true,
+            (None, '\'') | (None, '"') => quote = Some(ch),
+            (Some(q), c) if c == q => quote = None,
+            (None, c) if c.is_whitespace() => return i,
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+/// Whether a settings.json hook command runs the binary at `path`,
+/// with or without an env prefix.
+fn command_runs(cmd: &str, path: &str) -> bool {
+    split_env_prefix(cmd).1 == path
+}
+
+/// Check if a hook with the given command path already exists in the event array.
+fn hook_exists_in_array(arr: &[Value], hook_path: &str) -> bool {
+    arr.iter().any(|item| {
+        item.get("hooks")
+            .and_then(|h| h.as_array())
+            .map(|hooks_arr| {
+                hooks_arr.iter().any(|hook| {
+                    hook.get("command")
+                        .and_then(|c| c.as_str())
+                        .map(|cmd| command_runs(cmd, hook_path))
+                        .unwrap_or(false)
+                })
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Build a hook entry JSON value from HookConfig.
+fn build_hook_entry(config: &HookConfig, hook_command: &str) -> Value {
+    let mut hook_entry = serde_json::json!({
+        "type": config.hook_type,
+        "command": hook_command
+    });
+    if let Some(timeout) = config.timeout {
+        hook_entry["timeout"] = serde_json::json!(timeout);
+    }
+    serde_json::json!({
+        "hooks": [hook_entry]
+    })
+}
+
+/// Return the first hook command inside an event-array entry, if any.
+fn entry_command(item: &Value) -> Option<&str> {
+    item.get("hooks")
+        .and_then(|h| h.as_array())
+        .and_then(|hooks_arr| hooks_arr.iter().find_map(|hook| hook.get("command")))
+        .and_then(|c| c.as_str())
+}
+
+/// Priority of an existing event-array entry, looked up by command path
+/// among the known hook configs. Entries the installer has no hook.yaml
+/// for (user-added hooks) sort as `DEFAULT_HOOK_PRIORITY`.
+fn entry_priority(item: &Value, dest_dir: &Path, peers: &[HookConfig]) -> i32 {
+    entry_command(item)
+        .and_then(|cmd| peers.iter().find(|p| command_runs(cmd, &p.hook_command_path(dest_dir))))
+        .map(|p| p.effective_priority())
+        .unwrap_or(DEFAULT_HOOK_PRIORITY)
+}
+
+/// Register a hook in settings.json, inserting it after the last entry of
+/// the same event whose priority is lower or equal. Existing entries are
+/// never reordered, so manual moves (`move_hook_in_settings`) survive
+/// later installs. `peers` supplies the priorities of other known hooks.
+pub(super) fn register_hook_in_settings(dest_dir: &Path, config: &HookConfig, peers: &[HookConfig]) -> Result<()> {
+    let hook_path = config.hook_command_path(dest_dir);
+    let mut settings = read_settings(dest_dir)?;
+
+    // Ensure hooks object and event array exist
+    if settings.get("hooks").is_none() {
+        settings["hooks"] = serde_json::json!({});
+    }
+    let event_name = &config.event;
+    let hooks = settings.get_mut("hooks").unwrap();
+    if hooks.get(event_name).is_none() {
+        hooks[event_name] = serde_json::json!([]);
+    }
+
+    let event_hooks = hooks.get_mut(event_name).unwrap();
+    let mut added = false;
+    if let Value::Array(arr) = event_hooks {
+        // An existing entry keeps its (possibly user-edited) env prefix.
+        if !hook_exists_in_array(arr, &hook_path) {
+            let priority = config.effective_priority();
+            let position = arr
+                .iter()
+                .rposition(|item| entry_priority(item, dest_dir, peers) <= priority)
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            let hook_command = config.hook_command(dest_dir, &config.env);
+            arr.insert(position, build_hook_entry(config, &hook_command));
+            added = true;
+        }
+    }
+
+    write_settings(dest_dir, &settings, &format!("register hook {}", config.name))?;
+    if added {
+        update_managed_settings(dest_dir, |m| {
+            m.hooks.entry(event_name.clone()).or_default().insert(hook_path);
+        })?;
+    }
+    Ok(())
+}
+
+/// Move a registered hook one slot earlier (`delta < 0`) or later
+/// (`delta > 0`) within its event array. Returns `false` when the hook
+/// isn't registered or is already at the edge.
+pub fn move_hook_in_settings(dest_dir: &Path, config: &HookConfig, delta: isize) -> Result<bool> {
+    let hook_path = config.hook_command_path(dest_dir);
+    let mut settings = read_settings(dest_dir)?;
+
+    let arr = match settings.get_mut("hooks").and_then(|h| h.get_mut(&config.event)) {
+        Some(Value::Array(arr)) => arr,
+        _ => return Ok(false),
+    };
+    let Some(current) = arr.iter().position(|item| {
+        entry_command(item).is_some_and(|cmd| command_runs(cmd, &hook_path))
+    }) else {
+        return Ok(false);
+    };
+    let target = current as isize + delta;
+    if target < 0 || target >= arr.len() as isize {
+        return Ok(false);
+    }
+    arr.swap(current, target as usize);
+
+    write_settings(dest_dir, &settings, &format!("move hook {}", config.name))?;
+    Ok(true)
+}
+
+/// Map each registered hook's program path (env prefix stripped) to its
+/// 1-based position and the size of its event array, e.g.
+/// `"~/.claude/hooks/x_linux" -> (2, 3)`.
+/// Used by the Hooks tab to show effective execution order.
+pub fn read_hook_positions(dest_dir: &Path) -> HashMap<String, (usize, usize)> {
+    let mut positions = HashMap::new();
+    let Ok(settings) = read_settings(dest_dir) else {
+        return positions;
+    };
+    let Some(Value::Object(hooks)) = settings.get("hooks") else {
+        return positions;
+    };
+    for event_hooks in hooks.values() {
+        if let Value::Array(arr) = event_hooks {
+            for (i, item) in arr.iter().enumerate() {
+                if let Some(cmd) = entry_command(item) {
+                    let (_, path) = split_env_prefix(cmd);
+                    positions.insert(path.to_string(), (i + 1, arr.len()));
+                }
+            }
+        }
+    }
+    positions
+}
+
+/// Read the env assignments of a registered hook's command. Returns
+/// `None` when the hook isn't registered in settings.json.
+pub fn read_hook_env(dest_dir: &Path, config: &HookConfig) -> Option<BTreeMap<String, String>> {
+    let hook_path = config.hook_command_path(dest_dir);
+    let settings = read_settings(dest_dir).ok()?;
+    let arr = settings.get("hooks")?.get(&config.event)?.as_array()?;
+    arr.iter()
+        .filter_map(entry_command)
+        .find(|cmd| command_runs(cmd, &hook_path))
+        .map(|cmd| split_env_prefix(cmd).0)
+}
+
+/// Rewrite a registered hook's command with a new env prefix. Returns
+/// `false` when the hook isn't registered.
+pub fn set_hook_env(dest_dir: &Path, config: &HookConfig, env: &BTreeMap<String, String>) -> Result<bool> {
+    let hook_path = config.hook_command_path(dest_dir);
+    let mut settings = read_settings(dest_dir)?;
+
+    let arr = match settings.get_mut("hooks").and_then(|h| h.get_mut(&config.event)) {
+        Some(Value::Array(arr)) => arr,
+        _ => return Ok(false),
+    };
+
+    let mut updated = false;
+    for item in arr.iter_mut() {
+        let Some(Value::Array(hooks_arr)) = item.get_mut("hooks") else { continue };
+        for hook in hooks_arr.iter_mut() {
+            let runs = hook.get("command")
+                .and_then(|c| c.as_str())
+                .is_some_and(|cmd| command_runs(cmd, &hook_path));
+            if runs {
+                hook["command"] = serde_json::json!(config.hook_command(dest_dir, env));
+                updated = true;
+            }
+        }
+    }
+
+    if updated {
+        write_settings(dest_dir, &settings, &format!("set env of hook {}", config.name))?;
+    }
+    Ok(updated)
+}
+
+pub(crate) fn unregister_hook_from_settings(dest_dir: &Path, config: &HookConfig) -> Result<()> {
+    let settings_path = dest_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    let mut settings = read_settings(dest_dir)?;
+
+    // Get hooks object
+    let hooks = match settings.get_mut("hooks") {
+        Some(Value::Object(h)) => h,
+        _ => return Ok(()),
+    };
+
+    // Get event array
+    let event_name = &config.event;
+    let event_hooks = match hooks.get_mut(event_name) {
+        Some(Value::Array(arr)) => arr,
+        _ => return Ok(()),
+    };
+
+    // Remove hook entries that run this hook's binary
+    let hook_path = config.hook_command_path(dest_dir);
+    event_hooks.retain(|item| {
+        !item.get("hooks")
+            .and_then(|h| h.as_array())
+            .map(|hooks_arr| {
+                hooks_arr.iter().any(|hook| {
+                    hook.get("command")
+                        .and_then(|c| c.as_str())
+                        .map(|cmd| command_runs(cmd, &hook_path))
+                        .unwrap_or(false)
+                })
+            })
+            .unwrap_or(false)
+    });
+
+    // Clean up empty structures
+    if event_hooks.is_empty() {
+        hooks.remove(event_name);
+    }
+    if hooks.is_empty() {
+        if let Value::Object(ref mut map) = settings {
+            map.remove("hooks");
+        }
+    }
+
+    write_settings(dest_dir, &settings, &format!("unregister hook {}", config.name))?;
+    update_managed_settings(dest_dir, |m| {
+        if let Some(commands) = m.hooks.get_mut(event_name) {
+            commands.retain(|cmd| *cmd != hook_path);
+            if commands.is_empty() {
+                m.hooks.remove(event_name);
+            }
+        }
+    })
+}
+
+/// Top-level settings.json keys the installer writes on the user's behalf.
+pub const MANAGED_SETTINGS_KEYS: [&str; 3] = ["hooks", "outputStyle", "statusLine"];
+
+/// Removes installer-managed sections from settings.json
+/// This includes: the hook entries, outputStyle and statusLine hibi added
+/// (per the installed record) and keys adopted on the Config tab, less any
+/// the user released. Without a record of added entries, the whole `hooks`
+/// key goes, and `outputStyle`/`statusLine` only when they point at a
+/// style or statusline hibi installed.
+/// Preserves user settings like env, model, enabledPlugins, etc.
+pub fn remove_managed_settings_sections(dest_dir: &Path) -> Result<()> {
+    let settings_path = dest_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    let owned = managed_settings(dest_dir);
+    let mut settings = read_settings(dest_dir)?;
+    if let Value::Object(ref mut map) = settings {
+        for key in super::settings_keys::removable_keys(dest_dir) {
+            let remove = match (&owned, key.as_str()) {
+                (Some(owned), "hooks") => {
+                    if let Some(Value::Object(hooks)) = map.get_mut("hooks") {
+                        strip_owned_hooks(hooks, &owned.hooks);
+                    }
+                    map.get("hooks").is_some_and(|h| h.as_object().is_some_and(|h| h.is_empty()))
+                }
+                (Some(owned), "outputStyle") => owned.output_style.as_deref()
+                    .is_some_and(|style| map.get("outputStyle").and_then(Value::as_str) == Some(style)),
+                (Some(owned), "statusLine") => {
+                    owned.status_line.is_some() && map.get("statusLine") == owned.status_line.as_ref()
+                }
+                (None, "outputStyle") => map.get("outputStyle").and_then(Value::as_str)
+                    .is_some_and(|style| is_installed_output_style(dest_dir, style)),
+                (None, "statusLine") => map.get("statusLine")
+                    .is_some_and(|status_line| is_installed_statusline(dest_dir, status_line)),
+                _ => true,
+            };
+            if remove {
+                map.remove(&key);
+            }
+        }
+    }
+    write_settings(dest_dir, &settings, "remove managed sections")?;
+    update_managed_settings(dest_dir, |m| *m = Default::default())
+}
+
+/// Whether `style` names an output style hibi installed, rather than one
+/// the user added by hand or from another tool.
+fn is_installed_output_style(dest_dir: &Path, style: &str) -> bool {
+    super::installed_files(dest_dir).contains(&format!("{}/{}.md", output_styles_folder(), style))
+}
+
+/// Folder under the config dir that output styles install into, after
+/// any `directories:` remapping.
+pub(super) fn output_styles_folder() -> String {
+    crate::source::config::load_directories()
+        .map(|(dirs, _)| dirs.dest_dir(&ComponentType::OutputStyles).to_string())
+        .unwrap_or_else(|_| ComponentType::OutputStyles.display_name().to_string())
+}
+
+/// Whether a `statusLine` value runs a statusline hibi installed, by bare
+/// name or by any path ending in its dest-relative path, rather than one
+/// the user added by hand or from another tool.
+fn is_installed_statusline(dest_dir: &Path, status_line: &Value) -> bool {
+    let Some(command) = status_line.as_str().or_else(|| status_line.get("command").and_then(Value::as_str)) else {
+        return false;
+    };
+    let command = command.trim().replace('\\', "/");
+    let folder = format!("{}/", statusline_folder());
+    super::installed_files(dest_dir).iter().any(|key| {
+        key.strip_prefix(&folder)
+            .is_some_and(|name| command == name || command.ends_with(&format!("/{}", key)))
+    })
+}
+
+/// Folder under the config dir that statuslines install into, after any
+/// `directories:` remapping.
+fn statusline_folder() -> String {
+    crate::source::config::load_directories()
+        .map(|(dirs, _)| dirs.dest_dir(&ComponentType::Statusline).to_string())
+        .unwrap_or_else(|_| ComponentType::Statusline.display_name().to_string())
+}
+
+/// Drop the hook entries whose command runs one hibi added, then events
+/// left empty.
+fn strip_owned_hooks(hooks: &mut serde_json::Map<String, Value>, owned: &BTreeMap<String, BTreeSet<String>>) {
+    for (event, commands) in owned {
+        if let Some(Value::Array(entries)) = hooks.get_mut(event) {
+            entries.retain(|entry| {
+                !entry_command(entry).is_some_and(|cmd| commands.iter().any(|owned| command_runs(cmd, owned)))
+            });
+            if entries.is_empty() {
+                hooks.remove(event);
+            }
+        }
+    }
+}
+
+/// Auto-register an output style in settings.json if no style is currently set
+pub(super) fn register_output_style_in_settings(dest_dir: &Path, style_name: &str) -> Result<()> {
+    let mut settings = read_settings(dest_dir)?;
+
+    // Only set if outputStyle is not already configured
+    if settings.get("outputStyle").is_none() {
+        let style_name = style_name.strip_suffix(".md").unwrap_or(style_name);
+        settings["outputStyle"] = serde_json::json!(style_name);
+        write_settings(dest_dir, &settings, "register outputStyle")?;
+        update_managed_settings(dest_dir, |m| m.output_style = Some(style_name.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Auto-register a statusline in settings.json if no statusline is currently set
+pub(super) fn register_statusline_in_settings(dest_dir: &Path, statusline_name: &str) -> Result<()> {
+    let mut settings = read_settings(dest_dir)?;
+
+    // Only set if statusLine is not already configured
+    if settings.get("statusLine").is_none() {
+        let status_line = statusline_entry(dest_dir, statusline_name);
+        settings["statusLine"] = status_line.clone();
+        write_settings(dest_dir, &settings, "register statusLine")?;
+        update_managed_settings(dest_dir, |m| m.status_line = Some(status_line))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_dest(label: &str) -> std::path::PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let dir = std::env::temp_dir().join(format!("hibi_settings_{label}_{nanos}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read_output_style(dest: &Path) -> Option<String> {
+        let path = dest.join("settings.json");
+        let raw = std::fs::read_to_string(&path).ok()?;
+        let v: Value = serde_json::from_str(&raw).ok()?;
+        v.get("outputStyle").and_then(|x| x.as_str()).map(String::from)
+    }
+
+    #[test]
+    fn unregister_clears_when_current_matches() {
+        let dest = unique_dest("clears");
+        std::fs::write(dest.join("settings.json"), r#"{"outputStyle":"hibi_default"}"#).unwrap();
+
+        // Component name carries .md; settings value does not -- both forms match.
+        unregister_output_style_if_matches(&dest, "hibi_default.md").unwrap();
+
+        assert!(read_output_style(&dest).is_none(), "matching style should be cleared");
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn unregister_preserves_when_current_differs() {
+        let dest = unique_dest("preserves");
+        std::fs::write(dest.join("settings.json"), r#"{"outputStyle":"important"}"#).unwrap();
+
+        // Removing an unrelated style must NOT clear the user's chosen default.
+        unregister_output_style_if_matches(&dest, "other.md").unwrap();
+
+        assert_eq!(read_output_style(&dest).as_deref(), Some("important"));
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn unregister_is_noop_without_settings_file() {
+        let dest = unique_dest("nofile");
+        // No settings.json.
+        unregister_output_style_if_matches(&dest, "anything.md").unwrap();
+        assert!(!dest.join("settings.json").exists(), "must not create settings.json");
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn unregister_is_noop_when_output_style_absent() {
+        let dest = unique_dest("absent");
+        std::fs::write(dest.join("settings.json"), r#"{"theme":"dark"}"#).unwrap();
+
+        unregister_output_style_if_matches(&dest, "anything.md").unwrap();
+
+        // Other keys must remain intact.
+        let raw = std::fs::read_to_string(dest.join("settings.json")).unwrap();
+        let v: Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(v.get("theme").and_then(|x| x.as_str()), Some("dark"));
+        assert!(v.get("outputStyle").is_none());
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    fn hook(name: &str, priority: Option<i32>) -> HookConfig {
+        HookConfig {
+            name: name.to_string(),
+            event: "PreToolUse".to_string(),
+            hook_type: "command".to_string(),
+            timeout: None,
+            description: None,
+            deprecated: None,
+            priority,
+            env: BTreeMap::new(),
+            hosts: Vec::new(),
+        }
+    }
+
+    fn event_commands(dest: &Path) -> Vec<String> {
+        let raw = std::fs::read_to_string(dest.join("settings.json")).unwrap();
+        let v: Value = serde_json::from_str(&raw).unwrap();
+        v["hooks"]["PreToolUse"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|item| entry_command(item).map(String::from))
+            .collect()
+    }
+
+    #[test]
+    fn register_inserts_by_priority() {
+        let dest = unique_dest("priority");
+        let late = hook("late", Some(200));
+        let early = hook("early", Some(10));
+        let mid = hook("mid", None);
+        let peers = vec![late.clone(), early.clone(), mid.clone()];
+
+        register_hook_in_settings(&dest, &late, &peers).unwrap();
+        register_hook_in_settings(&dest, &early, &peers).unwrap();
+        register_hook_in_settings(&dest, &mid, &peers).unwrap();
+
+        let expected: Vec<String> = [&early, &mid, &late]
+            .iter()
+            .map(|h| h.hook_command_path(&dest))
+            .collect();
+        assert_eq!(event_commands(&dest), expected);
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn move_hook_swaps_with_neighbor_and_stops_at_edges() {
+        let dest = unique_dest("move");
+        let a = hook("a", None);
+        let b = hook("b", None);
+        let peers = vec![a.clone(), b.clone()];
+        register_hook_in_settings(&dest, &a, &peers).unwrap();
+        register_hook_in_settings(&dest, &b, &peers).unwrap();
+
+        assert!(move_hook_in_settings(&dest, &b, -1).unwrap());
+        assert_eq!(event_commands(&dest)[0], b.hook_command_path(&dest));
+        assert!(!move_hook_in_settings(&dest, &b, -1).unwrap(), "already first");
+
+        let positions = read_hook_positions(&dest);
+        assert_eq!(positions.get(&a.hook_command_path(&dest)), Some(&(2, 2)));
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn split_env_prefix_handles_quotes_and_bare_paths() {
+        let (env, path) = split_env_prefix("A=1 B='two words' ~/.claude/hooks/x_linux");
+        assert_eq!(env.get("A").map(String::as_str), Some("1"));
+        assert_eq!(env.get("B").map(String::as_str), Some("two words"));
+        assert_eq!(path, "~/.claude/hooks/x_linux");
+
+        let (env, path) = split_env_prefix(r"C:\Users\me\.claude\hooks\x.exe");
+        assert!(env.is_empty());
+        assert_eq!(path, r"C:\Users\me\.claude\hooks\x.exe");
+    }
+
+    #[test]
+    fn hook_env_defaults_are_written_and_editable() {
+        let dest = unique_dest("env");
+        let mut config = hook("compact", None);
+        config.env.insert("COMPACT_THRESHOLD".to_string(), "50".to_string());
+        register_hook_in_settings(&dest, &config, &[]).unwrap();
+
+        let env = read_hook_env(&dest, &config).expect("registered");
+        assert_eq!(env.get("COMPACT_THRESHOLD").map(String::as_str), Some("50"));
+
+        let mut edited = env.clone();
+        edited.insert("COMPACT_THRESHOLD".to_string(), "80".to_string());
+        assert!(set_hook_env(&dest, &config, &edited).unwrap());
+
+        // Re-registering must keep the user's edited value, not the default.
+        register_hook_in_settings(&dest, &config, &[]).unwrap();
+        let env = read_hook_env(&dest, &config).unwrap();
+        assert_eq!(env.get("COMPACT_THRESHOLD").map(String::as_str), Some("80"));
+        assert_eq!(event_commands(&dest).len(), 1);
+        assert!(read_hook_positions(&dest).contains_key(&config.hook_command_path(&dest)));
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn unregister_leaves_hooks_whose_name_contains_it() {
+        let dest = unique_dest("unregister");
+        let guard = hook("guard", None);
+        let extra = hook("guard-extra", None);
+        register_hook_in_settings(&dest, &guard, &[]).unwrap();
+        register_hook_in_settings(&dest, &extra, &[]).unwrap();
+
+        unregister_hook_from_settings(&dest, &guard).unwrap();
+        assert_eq!(event_commands(&dest), [extra.hook_command_path(&dest)]);
+        let managed = super::super::installed::managed_settings(&dest).unwrap();
+        assert_eq!(managed.hooks["PreToolUse"].len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[test]
+    fn removal_strips_only_entries_hibi_added() {
+        let dest = unique_dest("manifest");
+        // A hook and statusLine of the user's, from before hibi.
+        std::fs::write(dest.join("settings.json"), r#"{
+            "hooks": {"PreToolUse": [{"hooks": [{"type": "command", "command": "~/bin/mine"}]}]},
+            "statusLine": {"type": "command", "command": "~/bin/status"}
+        }"#).unwrap();
+        register_hook_in_settings(&dest, &hook("guard", None), &[]).unwrap();
+        register_output_style_in_settings(&dest, "terse.md").unwrap();
+        register_statusline_in_settings(&dest, "hibi-status.sh").unwrap();
+        assert_eq!(event_commands(&dest).len(), 2);
+
+        remove_managed_settings_sections(&dest).unwrap();
+        let v: Value = serde_json::from_str(&std::fs::read_to_string(dest.join("settings.json")).unwrap()).unwrap();
+        assert_eq!(event_commands(&dest), ["~/bin/mine"]);
+        assert!(v.get("outputStyle").is_none());
+        assert_eq!(v["statusLine"]["command"], "~/bin/status");
+
+        // Without a record of added entries, all hooks go, but a style or
+        // statusline hibi didn't install stays.
+        std::fs::remove_dir_all(dest.join(".hibi")).unwrap();
+        set_output_style(&dest, "third-party").unwrap();
+        std::fs::remove_dir_all(dest.join(".hibi")).unwrap();
+        remove_managed_settings_sections(&dest).unwrap();
+        let v: Value = serde_json::from_str(&std::fs::read_to_string(dest.join("settings.json")).unwrap()).unwrap();
+        assert!(v.get("hooks").is_none());
+        assert_eq!(v["outputStyle"], "third-party");
+        assert_eq!(v["statusLine"]["command"], "~/bin/status");
+
+        // A file in statusline/ that hibi didn't install isn't enough.
+        std::fs::create_dir_all(dest.join("statusline")).unwrap();
+        std::fs::write(dest.join("statusline/hibi-status.sh"), "").unwrap();
+        set_statusline(&dest, "hibi-status.sh").unwrap();
+        std::fs::remove_dir_all(dest.join(".hibi")).unwrap();
+        remove_managed_settings_sections(&dest).unwrap();
+        let v: Value = serde_json::from_str(&std::fs::read_to_string(dest.join("settings.json")).unwrap()).unwrap();
+        assert!(v.get("statusLine").is_some());
+
+        let statusline = crate::component::Component::new(
+            ComponentType::Statusline,
+            "hibi-status.sh".into(),
+            dest.join("statusline/hibi-status.sh"),
+            dest.join("statusline/hibi-status.sh"),
+            crate::component::InstallStatus::Unchanged,
+        );
+        super::super::installed::record_installed(&dest, &statusline).unwrap();
+        remove_managed_settings_sections(&dest).unwrap();
+        let v: Value = serde_json::from_str(&std::fs::read_to_string(dest.join("settings.json")).unwrap()).unwrap();
+        assert!(v.get("statusLine").is_none());
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    /// Golden-file cases: each starts from `testdata/settings/<case>.input.json`,
+    /// runs its steps and compares the resulting settings.json with
+    /// `<case>.golden.json`. `<hook:NAME>` in either file stands for hook
+    /// `NAME`'s command path and `<statusline>` for the command running
+    /// `statusline/hibi-status.sh`; both differ per platform. Run with
+    /// `HIBI_UPDATE_GOLDEN=1` to rewrite the golden files after an
+    /// intended change.
+    mod golden {
+        use super::*;
+        use std::path::PathBuf;
+
+        fn testdata(file: &str) -> PathBuf {
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata").join("settings").join(file)
+        }
+
+        fn hook_on(name: &str, event: &str, priority: Option<i32>) -> HookConfig {
+            HookConfig { event: event.to_string(), ..hook(name, priority) }
+        }
+
+        /// Replace `from` with `to` in every string of `value`.
+        fn replace_in_strings(value: &mut Value, from: &str, to: &str) {
+            match value {
+                Value::String(s) => *s = s.replace(from, to),
+                Value::Array(items) => items.iter_mut().for_each(|v| replace_in_strings(v, from, to)),
+                Value::Object(map) => map.values_mut().for_each(|v| replace_in_strings(v, from, to)),
+                _ => {}
+            }
+        }
+
+        fn check(case: &str, hooks: &[&HookConfig], steps: impl FnOnce(&Path)) {
+            let dest = unique_dest(case);
+            // Longest first, so no path is replaced inside a longer one.
+            let mut paths: Vec<(String, String)> = hooks.iter()
+                .map(|h| (format!("<hook:{}>", h.name), h.hook_command_path(&dest)))
+                .collect();
+            let statusline = statusline_entry(&dest, "hibi-status.sh");
+            paths.push(("<statusline>".to_string(), statusline["command"].as_str().unwrap().to_string()));
+            paths.sort_by_key(|(_, path)| std::cmp::Reverse(path.len()));
+
+            if let Ok(input) = std::fs::read_to_string(testdata(&format!("{case}.input.json"))) {
+                let mut settings = crate::fs::jsonc::parse(&input).unwrap();
+                for (placeholder, path) in &paths {
+                    replace_in_strings(&mut settings, placeholder, path);
+                }
+                std::fs::write(dest.join("settings.json"), settings.to_string()).unwrap();
+            }
+            steps(&dest);
+
+            let mut actual: Value = serde_json::from_str(&std::fs::read_to_string(dest.join("settings.json")).unwrap()).unwrap();
+            let _ = std::fs::remove_dir_all(&dest);
+            for (placeholder, path) in &paths {
+                replace_in_strings(&mut actual, path, placeholder);
+            }
+            let actual = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+
+            let golden = testdata(&format!("{case}.golden.json"));
+            if std::env::var_os("HIBI_UPDATE_GOLDEN").is_some() {
+                std::fs::write(&golden, &actual).unwrap();
+                return;
+            }
+            let expected = std::fs::read_to_string(&golden).unwrap_or_default();
+            assert_eq!(actual, expected, "{} differs; rerun with HIBI_UPDATE_GOLDEN=1 if the change is intended", golden.display());
+        }
+
+        #[test]
+        fn merge_into_user_settings() {
+            let guard = hook("guard", None);
+            let lint = hook("lint", Some(10));
+            let notify = HookConfig { timeout: Some(30), ..hook_on("notify", "Stop", None) };
+            let peers = [guard.clone(), lint.clone(), notify.clone()];
+            check("merge_into_user_settings", &[&guard, &lint, &notify], |dest| {
+                // Default priority goes after the user's entry, a lower one before it.
+                register_hook_in_settings(dest, &guard, &peers).unwrap();
+                register_hook_in_settings(dest, &lint, &peers).unwrap();
+                register_hook_in_settings(dest, &notify, &peers).unwrap();
+                register_output_style_in_settings(dest, "terse.md").unwrap();
+                register_statusline_in_settings(dest, "hibi-status.sh").unwrap();
+            });
+        }
+
+        #[test]
+        fn register_is_idempotent() {
+            let mut compact = hook("compact", None);
+            compact.env.insert("COMPACT_THRESHOLD".to_string(), "50".to_string());
+            check("register_is_idempotent", &[&compact], |dest| {
+                for _ in 0..2 {
+                    register_hook_in_settings(dest, &compact, &[]).unwrap();
+                    // The user's style and statusline are left alone.
+                    register_output_style_in_settings(dest, "terse.md").unwrap();
+                    register_statusline_in_settings(dest, "hibi-status.sh").unwrap();
+                }
+            });
+        }
+
+        #[test]
+        fn unregister_several_hooks_per_event() {
+            let guard = hook("guard", None);
+            let lint = hook("lint", Some(10));
+            let format = hook("format", Some(200));
+            let notify = hook_on("notify", "Stop", None);
+            let peers = [guard.clone(), lint.clone(), format.clone(), notify.clone()];
+            check("unregister_several_hooks_per_event", &[&guard, &lint, &format, &notify], |dest| {
+                for config in &peers {
+                    register_hook_in_settings(dest, config, &peers).unwrap();
+                }
+                unregister_hook_from_settings(dest, &lint).unwrap();
+                // The last hook of an event takes the event with it.
+                unregister_hook_from_settings(dest, &notify).unwrap();
+            });
+        }
+
+        #[test]
+        fn unregister_last_hook_drops_hooks_key() {
+            let guard = hook("guard", None);
+            check("unregister_last_hook_drops_hooks_key", &[&guard], |dest| {
+                register_hook_in_settings(dest, &guard, &[]).unwrap();
+                unregister_hook_from_settings(dest, &guard).unwrap();
+            });
+        }
+
+        #[test]
+        fn managed_removal_keeps_user_keys() {
+            let guard = hook("guard", None);
+            let notify = hook_on("notify", "Stop", None);
+            check("managed_removal_keeps_user_keys", &[&guard, &notify], |dest| {
+                register_hook_in_settings(dest, &guard, &[]).unwrap();
+                register_hook_in_settings(dest, &notify, &[]).unwrap();
+                register_output_style_in_settings(dest, "terse.md").unwrap();
+                register_statusline_in_settings(dest, "hibi-status.sh").unwrap();
+                remove_managed_settings_sections(dest).unwrap();
+            });
+        }
+    }
+}
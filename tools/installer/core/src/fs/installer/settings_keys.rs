@@ -0,0 +1,193 @@
+//! Who owns each top-level settings.json key.
+//!
+//! Keys the source settings.json sets, plus `MANAGED_SETTINGS_KEYS`, are
+//! managed by the installer: installs merge into them and removing the
+//! settings.json component deletes them. Everything else belongs to the
+//! user. Per-key overrides are recorded in `<dest_dir>/.hibi/settings-keys.yaml`:
+//! an adopted key is managed even though source doesn't set it, a released
+//! key is left to the user even though source does.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::settings::MANAGED_SETTINGS_KEYS;
+use crate::fs::state::{load_yaml, save_yaml};
+
+/// File name inside the installer state dir (see `state`).
+pub const OWNERSHIP_FILE: &str = "settings-keys.yaml";
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct KeyOwnership {
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    adopted: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    released: BTreeSet<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyOwner {
+    Installer,
+    User,
+}
+
+/// One top-level key of the source or installed settings.json.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SettingsKey {
+    pub key: String,
+    pub owner: KeyOwner,
+    /// Set by the source settings.json.
+    pub in_source: bool,
+    /// Present in the installed settings.json.
+    pub installed: bool,
+    /// Owner differs from the default, by adopt or release.
+    pub overridden: bool,
+}
+
+fn ownership_path(dest_dir: &Path) -> PathBuf {
+    crate::fs::state::state_dir(dest_dir).join(OWNERSHIP_FILE)
+}
+
+fn load_ownership(dest_dir: &Path) -> Result<KeyOwnership> {
+    load_yaml(&ownership_path(dest_dir))
+}
+
+fn save_ownership(dest_dir: &Path, ownership: &KeyOwnership) -> Result<()> {
+    let path = ownership_path(dest_dir);
+    if ownership.adopted.is_empty() && ownership.released.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    save_yaml(&path, ownership)
+}
+
+/// Top-level keys of the JSON object at `path`; empty when it is missing.
+fn top_level_keys(path: &Path) -> Result<BTreeSet<String>> {
+    if !path.exists() {
+        return Ok(BTreeSet::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let json: Value = crate::fs::jsonc::parse(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(json.as_object().map(|map| map.keys().cloned().collect()).unwrap_or_default())
+}
+
+fn default_owner(key: &str, in_source: bool) -> KeyOwner {
+    if in_source || MANAGED_SETTINGS_KEYS.contains(&key) {
+        KeyOwner::Installer
+    } else {
+        KeyOwner::User
+    }
+}
+
+/// Every key of the source (if any) and installed settings.json, sorted,
+/// with its owner.
+pub fn settings_keys(source: Option<&Path>, dest_dir: &Path) -> Result<Vec<SettingsKey>> {
+    let source_keys = match source {
+        Some(source) => top_level_keys(source)?,
+        None => BTreeSet::new(),
+    };
+    let installed_keys = top_level_keys(&dest_dir.join("settings.json"))?;
+    let ownership = load_ownership(dest_dir)?;
+
+    Ok(source_keys.union(&installed_keys)
+        .map(|key| {
+            let in_source = source_keys.contains(key);
+            let default = default_owner(key, in_source);
+            let owner = if ownership.adopted.contains(key) {
+                KeyOwner::Installer
+            } else if ownership.released.contains(key) {
+                KeyOwner::User
+            } else {
+                default
+            };
+            SettingsKey {
+                key: key.clone(),
+                owner,
+                in_source,
+                installed: installed_keys.contains(key),
+                overridden: owner != default,
+            }
+        })
+        .collect())
+}
+
+/// Record `key` as owned by `owner`, dropping any override that matches
+/// the default. Returns whether anything changed.
+pub fn set_key_owner(dest_dir: &Path, key: &SettingsKey, owner: KeyOwner) -> Result<bool> {
+    if key.owner == owner {
+        return Ok(false);
+    }
+    let mut ownership = load_ownership(dest_dir)?;
+    ownership.adopted.remove(&key.key);
+    ownership.released.remove(&key.key);
+    if owner != default_owner(&key.key, key.in_source) {
+        match owner {
+            KeyOwner::Installer => ownership.adopted.insert(key.key.clone()),
+            KeyOwner::User => ownership.released.insert(key.key.clone()),
+        };
+    }
+    save_ownership(dest_dir, &ownership)?;
+    Ok(true)
+}
+
+/// Keys the user took over from the installer; merges leave them alone.
+pub(super) fn released_keys(dest_dir: &Path) -> BTreeSet<String> {
+    load_ownership(dest_dir).map(|o| o.released).unwrap_or_default()
+}
+
+/// Keys removing the settings.json component deletes: the always-managed
+/// ones plus adopted keys, minus released ones.
+pub(super) fn removable_keys(dest_dir: &Path) -> BTreeSet<String> {
+    let ownership = load_ownership(dest_dir).unwrap_or_default();
+    MANAGED_SETTINGS_KEYS.iter()
+        .map(|k| k.to_string())
+        .chain(ownership.adopted)
+        .filter(|k| !ownership.released.contains(k))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn adopt_and_release_override_the_default_owner() {
+        let dir = temp_dir("settings_keys");
+        let source = dir.join("source.json");
+        std::fs::write(&source, r#"{"model":"opus","env":{}}"#).unwrap();
+        std::fs::write(dir.join("settings.json"), r#"{"model":"sonnet","hooks":{},"theme":"dark"}"#).unwrap();
+
+        let keys = settings_keys(Some(&source), &dir).unwrap();
+        let owners: Vec<(&str, KeyOwner, bool, bool)> =
+            keys.iter().map(|k| (k.key.as_str(), k.owner, k.in_source, k.installed)).collect();
+        assert_eq!(owners, vec![
+            ("env", KeyOwner::Installer, true, false),
+            ("hooks", KeyOwner::Installer, false, true),
+            ("model", KeyOwner::Installer, true, true),
+            ("theme", KeyOwner::User, false, true),
+        ]);
+
+        assert!(set_key_owner(&dir, &keys[2], KeyOwner::User).unwrap());
+        assert!(set_key_owner(&dir, &keys[3], KeyOwner::Installer).unwrap());
+        assert!(!set_key_owner(&dir, &keys[1], KeyOwner::Installer).unwrap());
+        assert_eq!(released_keys(&dir), BTreeSet::from(["model".to_string()]));
+        let removable = removable_keys(&dir);
+        assert!(removable.contains("theme") && removable.contains("hooks") && !removable.contains("model"));
+
+        // Going back to the default owner drops the override, and the file.
+        let keys = settings_keys(Some(&source), &dir).unwrap();
+        assert!(keys[2].overridden && keys[3].overridden);
+        set_key_owner(&dir, &keys[2], KeyOwner::Installer).unwrap();
+        set_key_owner(&dir, &keys[3], KeyOwner::User).unwrap();
+        assert!(!ownership_path(&dir).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
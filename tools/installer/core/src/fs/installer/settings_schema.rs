@@ -0,0 +1,139 @@
+//! Shape checks for the settings.json keys Claude is strict about. A file
+//! that fails them makes Claude reject the whole config, so writes refuse
+//! it up front and name every offending key instead.
+//!
+//! Only `hooks`, `statusLine`, `outputStyle` and `env` are checked; other
+//! keys are left to Claude, and unknown hook events are allowed so newer
+//! events don't need a release here.
+
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+/// Every problem in `settings`, as `<key path>: <what is wrong>`.
+pub fn settings_problems(settings: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    let Some(map) = settings.as_object() else {
+        return vec!["(root): expected an object".to_string()];
+    };
+    if let Some(hooks) = map.get("hooks") {
+        check_hooks(hooks, &mut problems);
+    }
+    if let Some(status_line) = map.get("statusLine") {
+        check_status_line(status_line, &mut problems);
+    }
+    if let Some(style) = map.get("outputStyle")
+        && !style.is_string()
+    {
+        problems.push("outputStyle: expected a string".to_string());
+    }
+    if let Some(env) = map.get("env") {
+        match env.as_object() {
+            Some(vars) => problems.extend(vars.iter()
+                .filter(|(_, value)| !value.is_string())
+                .map(|(name, _)| format!("env.{}: expected a string", name))),
+            None => problems.push("env: expected an object".to_string()),
+        }
+    }
+    problems
+}
+
+fn check_hooks(hooks: &Value, problems: &mut Vec<String>) {
+    let Some(events) = hooks.as_object() else {
+        problems.push("hooks: expected an object of events".to_string());
+        return;
+    };
+    for (event, entries) in events {
+        let Some(entries) = entries.as_array() else {
+            problems.push(format!("hooks.{}: expected an array", event));
+            continue;
+        };
+        for (i, entry) in entries.iter().enumerate() {
+            let at = format!("hooks.{}[{}]", event, i);
+            if entry.get("matcher").is_some_and(|m| !m.is_string()) {
+                problems.push(format!("{}.matcher: expected a string", at));
+            }
+            let Some(commands) = entry.get("hooks").and_then(Value::as_array) else {
+                problems.push(format!("{}.hooks: expected an array", at));
+                continue;
+            };
+            for (j, command) in commands.iter().enumerate() {
+                let at = format!("{}.hooks[{}]", at, j);
+                match command.get("type").and_then(Value::as_str) {
+                    None => problems.push(format!("{}.type: expected a string", at)),
+                    Some("command") if !command.get("command").is_some_and(Value::is_string) => {
+                        problems.push(format!("{}.command: expected a string", at));
+                    }
+                    Some(_) => {}
+                }
+                if command.get("timeout").is_some_and(|t| !t.is_number()) {
+                    problems.push(format!("{}.timeout: expected a number", at));
+                }
+            }
+        }
+    }
+}
+
+fn check_status_line(status_line: &Value, problems: &mut Vec<String>) {
+    if !status_line.is_object() {
+        problems.push("statusLine: expected an object".to_string());
+        return;
+    }
+    if !status_line.get("type").is_some_and(Value::is_string) {
+        problems.push("statusLine.type: expected a string".to_string());
+    }
+    if !status_line.get("command").is_some_and(Value::is_string) {
+        problems.push("statusLine.command: expected a string".to_string());
+    }
+    if status_line.get("padding").is_some_and(|p| !p.is_number()) {
+        problems.push("statusLine.padding: expected a number".to_string());
+    }
+}
+
+/// Refuse to write `settings` to `path` when it has problems.
+pub(crate) fn validate_settings(settings: &Value, path: &Path) -> Result<()> {
+    let problems = settings_problems(settings);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "Not writing {}: Claude would reject these keys:\n  {}",
+        path.display(),
+        problems.join("\n  "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn every_offending_key_is_listed() {
+        let valid = json!({
+            "hooks": {"Stop": [{"hooks": [{"type": "command", "command": "~/.claude/hooks/x", "timeout": 5}]}]},
+            "statusLine": {"type": "command", "command": "~/.claude/statusline.sh"},
+            "outputStyle": "terse",
+            "env": {"A": "1"},
+            "model": 42,
+        });
+        assert!(settings_problems(&valid).is_empty());
+
+        let invalid = json!({
+            "hooks": {"Stop": [{"hooks": [{"type": "command"}]}], "PreToolUse": {"matcher": "Bash"}},
+            "statusLine": "~/.claude/statusline.sh",
+            "outputStyle": ["terse"],
+            "env": {"A": 1},
+        });
+        assert_eq!(settings_problems(&invalid), [
+            "hooks.PreToolUse: expected an array",
+            "hooks.Stop[0].hooks[0].command: expected a string",
+            "statusLine: expected an object",
+            "outputStyle: expected a string",
+            "env.A: expected a string",
+        ]);
+        let err = validate_settings(&invalid, Path::new("settings.json")).unwrap_err().to_string();
+        assert!(err.contains("Not writing settings.json") && err.contains("\n  env.A: expected a string"));
+    }
+}
@@ -0,0 +1,86 @@
+//! The install queue of the batch in progress, kept in
+//! `<dest_dir>/.hibi/queue.yaml` so a batch cut short by a crash or a
+//! killed terminal can be resumed at the next start. The frontend writes
+//! it as the batch starts and after every item, and removes it once the
+//! batch finishes; a file still there at startup means the last batch
+//! never did.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::fs::state::{load_yaml, save_yaml};
+
+/// File name inside the installer state dir (see `state`).
+pub const QUEUE_FILE: &str = "queue.yaml";
+
+/// What was left of an install batch when it was last saved.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterruptedQueue {
+    /// Label of the tab the batch ran on, as the frontend names it.
+    pub tab: String,
+    /// Items still to install, in queue order, by name. The first may
+    /// have been running when the batch stopped.
+    pub items: Vec<String>,
+    /// Items finished before the batch stopped.
+    #[serde(default)]
+    pub done: usize,
+    /// Items in the batch as started.
+    #[serde(default)]
+    pub total: usize,
+}
+
+fn queue_path(dest_dir: &Path) -> PathBuf {
+    crate::fs::state::state_dir(dest_dir).join(QUEUE_FILE)
+}
+
+/// The queue a batch left behind, if one didn't finish.
+pub fn load(dest_dir: &Path) -> Result<Option<InterruptedQueue>> {
+    let queue: InterruptedQueue = load_yaml(&queue_path(dest_dir))?;
+    Ok(Some(queue).filter(|q| !q.items.is_empty()))
+}
+
+/// Record what is left of the running batch; an empty queue clears it.
+pub fn save(dest_dir: &Path, queue: &InterruptedQueue) -> Result<()> {
+    if queue.items.is_empty() {
+        return clear(dest_dir);
+    }
+    save_yaml(&queue_path(dest_dir), queue)
+}
+
+/// Forget the queue: the batch finished or the user declined to resume.
+pub fn clear(dest_dir: &Path) -> Result<()> {
+    let path = queue_path(dest_dir);
+    if path.exists() {
+        std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn round_trips_until_empty() {
+        let dest = temp_dir("interrupted");
+        assert_eq!(load(&dest).unwrap(), None);
+
+        let mut queue = InterruptedQueue {
+            tab: "Skills".to_string(),
+            items: vec!["review".to_string(), "deploy".to_string()],
+            done: 3,
+            total: 5,
+        };
+        save(&dest, &queue).unwrap();
+        assert_eq!(load(&dest).unwrap(), Some(queue.clone()));
+
+        queue.items.clear();
+        save(&dest, &queue).unwrap();
+        assert_eq!(load(&dest).unwrap(), None);
+        assert!(!queue_path(&dest).exists());
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+}
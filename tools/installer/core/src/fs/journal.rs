@@ -0,0 +1,313 @@
+//! Record of what the last install/remove batch changed, so it can be undone.
+//!
+//! The journal lives at `<dest>/.hibi/backups/journal.json` next to the
+//! snapshots it points into (see `backup`). Only the most recent batch is
+//! kept: each finished batch replaces it, and a successful undo deletes it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::TargetCli;
+use crate::fs::audit::{self, EventKind};
+use crate::fs::backup::{backup_root, Snapshot};
+use crate::fs::run_with_timeout;
+use crate::mcp::McpScope;
+
+const JOURNAL_FILE: &str = "journal.json";
+
+/// Timeout for the `mcp remove` that undoes an MCP install.
+const MCP_UNDO_TIMEOUT_SECS: u64 = 30;
+
+/// One reversible change.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalEntry {
+    /// File the batch created; undo deletes it.
+    FileCreated { path: PathBuf },
+    /// File the batch overwrote or deleted; undo copies `backup` back.
+    FileReplaced { path: PathBuf, backup: PathBuf },
+    /// Top-level settings.json keys the batch changed, with their previous
+    /// values (`None` = the key was absent); undo puts those values back
+    /// and leaves every other key alone.
+    SettingsKeys { path: PathBuf, previous: BTreeMap<String, Option<Value>> },
+    /// MCP server the batch registered; undo runs `<cli> mcp remove` (or
+    /// edits the config file of `scope` for Cursor and OpenCode).
+    McpAdded {
+        name: String,
+        #[serde(default)]
+        scope: McpScope,
+        /// Project of a local-scope install.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        project_path: Option<String>,
+    },
+}
+
+/// Everything one batch changed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Journal {
+    /// What the batch did, e.g. "Installed 3 item(s)".
+    pub summary: String,
+    pub entries: Vec<JournalEntry>,
+    /// Changes the batch made that undo cannot reverse (plugin installs,
+    /// MCP removals), reported instead of silently skipped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub irreversible: Vec<String>,
+}
+
+impl Journal {
+    fn path(dest_dir: &Path) -> PathBuf {
+        backup_root(dest_dir).join(JOURNAL_FILE)
+    }
+
+    /// Replace the stored journal with this one.
+    pub fn save(&self, dest_dir: &Path) -> Result<()> {
+        let path = Self::path(dest_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The last batch's journal, if one is stored.
+    pub fn load(dest_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(dest_dir);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+}
+
+/// Pre-batch state of the files and settings a component batch may touch.
+/// Captured before the first item runs and turned into a `Journal` once
+/// the batch is done.
+#[derive(Clone, Debug)]
+pub struct Baseline {
+    dest_dir: PathBuf,
+    /// Each path the batch may write, and whether it existed beforehand.
+    files: Vec<(PathBuf, bool)>,
+    settings: Value,
+}
+
+impl Baseline {
+    /// Record which of `paths` exist and the current settings.json. With
+    /// `snapshot`, existing files are also copied into it — needed for
+    /// removals, which (unlike installs) do not back up on their own.
+    pub fn capture(dest_dir: &Path, paths: &[PathBuf], snapshot: Option<&Snapshot>) -> Result<Self> {
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let existed = path.is_file();
+            if existed && let Some(snapshot) = snapshot {
+                snapshot.save(path)?;
+            }
+            files.push((path.clone(), existed));
+        }
+        Ok(Self {
+            dest_dir: dest_dir.to_path_buf(),
+            files,
+            settings: read_settings(&dest_dir.join("settings.json")),
+        })
+    }
+
+    /// Entries describing how the files in `touched` and settings.json
+    /// differ from this baseline. `snapshot` is where overwritten files
+    /// were saved during the batch.
+    pub fn entries(&self, touched: &[PathBuf], snapshot: Option<&Snapshot>) -> Vec<JournalEntry> {
+        let mut entries = Vec::new();
+        for (path, existed) in self.files.iter().filter(|(p, _)| touched.contains(p)) {
+            if !existed {
+                if path.is_file() {
+                    entries.push(JournalEntry::FileCreated { path: path.clone() });
+                }
+                continue;
+            }
+            // Unchanged files were never saved, so there is nothing to put back.
+            let backup = snapshot
+                .and_then(|s| path.strip_prefix(&self.dest_dir).ok().map(|rel| s.dir().join(rel)));
+            if let Some(backup) = backup.filter(|b| b.is_file()) {
+                entries.push(JournalEntry::FileReplaced { path: path.clone(), backup });
+            }
+        }
+
+        let settings_path = self.dest_dir.join("settings.json");
+        let previous = changed_keys(&self.settings, &read_settings(&settings_path));
+        if !previous.is_empty() {
+            entries.push(JournalEntry::SettingsKeys { path: settings_path, previous });
+        }
+        entries
+    }
+}
+
+/// settings.json as JSON, or an empty object when missing or unreadable.
+fn read_settings(path: &Path) -> Value {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| crate::fs::jsonc::parse(&s).ok())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// Top-level keys whose value differs between `before` and `after`,
+/// mapped to their `before` value.
+fn changed_keys(before: &Value, after: &Value) -> BTreeMap<String, Option<Value>> {
+    let empty = serde_json::Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+    before.keys()
+        .chain(after.keys())
+        .filter(|k| before.get(*k) != after.get(*k))
+        .map(|k| (k.clone(), before.get(k).cloned()))
+        .collect()
+}
+
+/// Reverse the stored journal for `dest_dir`, newest change first, and
+/// delete it. Returns a one-line report. Individual failures are collected
+/// into the report rather than stopping the rest of the undo.
+pub fn undo(dest_dir: &Path, target_cli: TargetCli) -> Result<String> {
+    let journal = Journal::load(dest_dir)?
+        .context("Nothing to undo")?;
+
+    let mut failures = Vec::new();
+    for entry in journal.entries.iter().rev() {
+        let result = undo_entry(entry, target_cli);
+        if let Some((kind, subject)) = audit_subject(entry) {
+            audit::record(kind, "undo", &subject, &result);
+        }
+        if let Err(e) = result {
+            failures.push(format!("{:#}", e));
+        }
+    }
+    std::fs::remove_file(Journal::path(dest_dir))?;
+
+    let mut report = format!("Undid: {} ({} change(s))", journal.summary, journal.entries.len());
+    if !journal.irreversible.is_empty() {
+        report.push_str(&format!("; not undoable: {}", journal.irreversible.join(", ")));
+    }
+    if !failures.is_empty() {
+        report.push_str(&format!("; failed: {}", failures.join("; ")));
+    }
+    Ok(report)
+}
+
+/// How undoing `entry` shows up in the audit log; deleting a file the
+/// batch created isn't logged.
+fn audit_subject(entry: &JournalEntry) -> Option<(EventKind, String)> {
+    match entry {
+        JournalEntry::FileCreated { .. } => None,
+        JournalEntry::FileReplaced { path, .. } => Some((EventKind::FileCopy, path.to_string_lossy().into_owned())),
+        JournalEntry::SettingsKeys { path, .. } => Some((EventKind::Settings, path.to_string_lossy().into_owned())),
+        JournalEntry::McpAdded { name, .. } => Some((EventKind::Command, format!("mcp remove {}", name))),
+    }
+}
+
+fn undo_entry(entry: &JournalEntry, target_cli: TargetCli) -> Result<()> {
+    match entry {
+        JournalEntry::FileCreated { path } => {
+            if path.exists() {
+                std::fs::remove_file(path)
+                    .with_context(|| format!("Failed to delete {}", path.display()))?;
+            }
+        }
+        JournalEntry::FileReplaced { path, backup } => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(backup, path)
+                .with_context(|| format!("Failed to restore {}", path.display()))?;
+        }
+        JournalEntry::SettingsKeys { path, previous } => {
+            let mut settings = read_settings(path);
+            if let Some(obj) = settings.as_object_mut() {
+                for (key, value) in previous {
+                    match value {
+                        Some(value) => { obj.insert(key.clone(), value.clone()); }
+                        None => { obj.remove(key); }
+                    }
+                }
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&settings)?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        JournalEntry::McpAdded { name, scope, project_path } if !target_cli.manages_mcp_via_cli() => {
+            let path = crate::fs::installer::mcp_config_path(target_cli, *scope, project_path.as_deref())?;
+            crate::fs::installer::remove_configured_mcp_server(target_cli, &path, name)?;
+        }
+        JournalEntry::McpAdded { name, .. } => {
+            let mut cmd = crate::fs::installer::mcp_remove_command(name, target_cli)?;
+            let out = run_with_timeout(&mut cmd, MCP_UNDO_TIMEOUT_SECS)?;
+            if !out.status.success() {
+                anyhow::bail!(
+                    "Failed to remove MCP server {}: {}",
+                    name, String::from_utf8_lossy(&out.stderr).trim()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn undo_reverses_files_and_settings_keys() {
+        let dest = temp_dir("undo");
+        let settings = dest.join("settings.json");
+        let created = dest.join("hooks").join("new.sh");
+        let replaced = dest.join("agents").join("a.md");
+        std::fs::create_dir_all(replaced.parent().unwrap()).unwrap();
+        std::fs::write(&replaced, "mine").unwrap();
+        std::fs::write(&settings, r#"{"model": "opus"}"#).unwrap();
+
+        let snapshot = Snapshot::new(&dest);
+        let paths = vec![created.clone(), replaced.clone()];
+        let baseline = Baseline::capture(&dest, &paths, Some(&snapshot)).unwrap();
+
+        // The "batch": create a hook, overwrite an agent, register the hook.
+        std::fs::create_dir_all(created.parent().unwrap()).unwrap();
+        std::fs::write(&created, "#!/bin/sh").unwrap();
+        std::fs::write(&replaced, "theirs").unwrap();
+        std::fs::write(&settings, r#"{"model": "opus", "hooks": {"Stop": []}}"#).unwrap();
+
+        Journal { summary: "Installed 2 item(s)".into(), entries: baseline.entries(&paths, Some(&snapshot)), irreversible: Vec::new() }
+            .save(&dest)
+            .unwrap();
+        // A later, unrelated edit survives the undo.
+        std::fs::write(&settings, r#"{"model": "sonnet", "hooks": {"Stop": []}}"#).unwrap();
+
+        let report = undo(&dest, TargetCli::Claude).unwrap();
+        let after: Value = serde_json::from_str(&std::fs::read_to_string(&settings).unwrap()).unwrap();
+        let created_exists = created.exists();
+        let replaced_content = std::fs::read_to_string(&replaced).unwrap();
+        let journal_left = Journal::load(&dest).unwrap();
+        let _ = std::fs::remove_dir_all(&dest);
+
+        assert!(report.starts_with("Undid: Installed 2 item(s) (3 change(s))"));
+        assert!(!created_exists);
+        assert_eq!(replaced_content, "mine");
+        assert_eq!(after, serde_json::json!({"model": "sonnet"}));
+        assert!(journal_left.is_none());
+    }
+
+    #[test]
+    fn mcp_entries_keep_their_scope() {
+        let entry = JournalEntry::McpAdded {
+            name: "memory".into(),
+            scope: McpScope::Local,
+            project_path: Some("/work/app".into()),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(serde_json::from_str::<JournalEntry>(&json).unwrap(), entry);
+
+        // Journals written before the scope was recorded were user scope.
+        let old: JournalEntry = serde_json::from_str(r#"{"kind": "mcp_added", "name": "memory"}"#).unwrap();
+        assert_eq!(old, JournalEntry::McpAdded { name: "memory".into(), scope: McpScope::User, project_path: None });
+    }
+}
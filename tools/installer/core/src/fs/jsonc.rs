@@ -0,0 +1,107 @@
+//! Lenient reading of hand-edited JSON files such as settings.json: `//`
+//! and `/* */` comments and trailing commas are accepted (JSONC). hibi
+//! writes plain JSON, so comments don't survive an install that rewrites
+//! the file; the pre-install backup keeps the original.
+
+use serde_json::Value;
+
+/// Parse `content` as JSONC.
+pub fn parse(content: &str) -> serde_json::Result<Value> {
+    serde_json::from_str(&strip(content))
+}
+
+/// Whether `content` has comments or trailing commas that a rewrite
+/// would drop.
+pub fn has_extensions(content: &str) -> bool {
+    strip(content) != content
+}
+
+/// `content` with comments and trailing commas replaced by spaces. Line
+/// breaks are kept, so parse errors still point at the right line and
+/// column.
+pub fn strip(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    // Byte offset in `out` of a comma that may turn out to be trailing.
+    let mut comma: Option<usize> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                comma = None;
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    match chars[i] {
+                        '\\' if i + 1 < chars.len() => {
+                            out.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        '"' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(' ');
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                out.push_str("  ");
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                    i += 1;
+                }
+                if i < chars.len() {
+                    out.push_str("  ");
+                    i += 2;
+                }
+            }
+            ',' => {
+                comma = Some(out.len());
+                out.push(c);
+                i += 1;
+            }
+            '}' | ']' => {
+                if let Some(at) = comma.take() {
+                    out.replace_range(at..at + 1, " ");
+                }
+                out.push(c);
+                i += 1;
+            }
+            _ => {
+                if !c.is_whitespace() {
+                    comma = None;
+                }
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_and_trailing_commas_are_accepted() {
+        let content = "{\n  // theme\n  \"model\": \"opus\", /* for now */\n  \"url\": \"http://x//y\",\n  \"hooks\": {\"Stop\": [1, 2,],},\n}\n";
+        let value = parse(content).unwrap();
+        assert_eq!(value, serde_json::json!({"model": "opus", "url": "http://x//y", "hooks": {"Stop": [1, 2]}}));
+        assert!(has_extensions(content));
+        assert!(!has_extensions("{\"a\": \"/* not a comment */,}\"}"));
+
+        let err = parse("{\n  // note\n  \"a\": 1\n  \"b\": 2\n}").unwrap_err();
+        assert_eq!(err.line(), 4);
+    }
+}
@@ -0,0 +1,174 @@
+//! The `keywords:` list in an agent's or skill's YAML frontmatter, which
+//! prompt-matching guides use to decide what to inject. Only that entry is
+//! rewritten; every other frontmatter line and the body are kept byte for
+//! byte.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+const DELIMITER: &str = "---";
+
+/// Line range of the frontmatter body (between the `---` lines), if the
+/// document starts with one.
+fn frontmatter_range(lines: &[&str]) -> Option<(usize, usize)> {
+    if lines.first().map(|l| l.trim_end()) != Some(DELIMITER) {
+        return None;
+    }
+    let end = lines.iter().skip(1).position(|l| l.trim_end() == DELIMITER)? + 1;
+    Some((1, end))
+}
+
+/// Lines `[start, end)` holding the `key` entry: the key line plus any
+/// indented `- item` lines of a block list.
+fn list_entry(lines: &[&str], (start, end): (usize, usize), key: &str) -> Option<(usize, usize)> {
+    let at = (start..end).find(|&i| lines[i].strip_prefix(key).is_some_and(|rest| rest.starts_with(':')))?;
+    let items = lines[at + 1..end]
+        .iter()
+        .take_while(|l| l.trim_start().starts_with("- ") && l.starts_with(char::is_whitespace))
+        .count();
+    Some((at, at + 1 + items))
+}
+
+fn unquote(item: &str) -> String {
+    let item = item.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = item.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return inner.replace("\\\"", "\"");
+        }
+    }
+    item.to_string()
+}
+
+/// Split a flow sequence body on commas outside quotes.
+fn split_flow(body: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    for c in body.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) if current.trim().is_empty() => quote = Some(c),
+            (c, Some(q)) if c == q && !current.ends_with('\\') => quote = None,
+            (',', None) => {
+                items.push(unquote(&current));
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(unquote(&current));
+    items.into_iter().filter(|i| !i.is_empty()).collect()
+}
+
+/// Keywords in `content`'s frontmatter, in file order; empty when there
+/// is no frontmatter or no `keywords` entry.
+pub fn parse_keywords(content: &str) -> Vec<String> {
+    parse_list(content, "keywords")
+}
+
+/// A string list from the frontmatter, in flow (`[a, b]`), bare (`a, b`)
+/// or block (`- a`) form; empty when `key` is missing.
+pub fn parse_list(content: &str, key: &str) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(range) = frontmatter_range(&lines) else { return Vec::new() };
+    let Some((at, end)) = list_entry(&lines, range, key) else { return Vec::new() };
+
+    let value = lines[at][key.len() + 1..].trim();
+    if let Some(body) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return split_flow(body);
+    }
+    if !value.is_empty() {
+        return split_flow(value);
+    }
+    lines[at + 1..end]
+        .iter()
+        .map(|l| unquote(l.trim_start().trim_start_matches("- ")))
+        .filter(|i| !i.is_empty())
+        .collect()
+}
+
+fn format_item(item: &str) -> String {
+    if item.contains(|c: char| ",[]{}:#\"'".contains(c)) || item != item.trim() {
+        format!("\"{}\"", item.replace('"', "\\\""))
+    } else {
+        item.to_string()
+    }
+}
+
+/// `content` with its `keywords` entry replaced by `keywords` as a flow
+/// list (or dropped when empty). A missing entry is added at the end of
+/// the frontmatter.
+pub fn set_keywords(content: &str, keywords: &[String]) -> Result<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(range) = frontmatter_range(&lines) else {
+        bail!("no YAML frontmatter to hold the keywords");
+    };
+
+    let entry = (!keywords.is_empty()).then(|| {
+        let items: Vec<String> = keywords.iter().map(|k| format_item(k)).collect();
+        format!("keywords: [{}]", items.join(", "))
+    });
+    let (from, to) = list_entry(&lines, range, "keywords").unwrap_or((range.1, range.1));
+
+    let mut out: Vec<&str> = lines[..from].to_vec();
+    out.extend(entry.as_deref());
+    out.extend(&lines[to..]);
+
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut result = out.join(newline);
+    if content.ends_with('\n') {
+        result.push_str(newline);
+    }
+    Ok(result)
+}
+
+/// Keywords of the markdown file at `path`.
+pub fn read_keywords(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(parse_keywords(&content))
+}
+
+/// Rewrite the `keywords` entry of the markdown file at `path`.
+pub fn write_keywords(path: &Path, keywords: &[String]) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let updated = set_keywords(&content, keywords)
+        .with_context(|| format!("Can't set keywords in {}", path.display()))?;
+    std::fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn keywords_are_rewritten_without_touching_the_rest() {
+        let doc = "---\nname: tdd\nkeywords: [tdd, \"a, b\", 테스트]\ndescription: x\n---\n# Body\nkeywords: body\n";
+        assert_eq!(parse_keywords(doc), strings(&["tdd", "a, b", "테스트"]));
+
+        let updated = set_keywords(doc, &strings(&["tdd", "red:green"])).unwrap();
+        assert_eq!(updated, "---\nname: tdd\nkeywords: [tdd, \"red:green\"]\ndescription: x\n---\n# Body\nkeywords: body\n");
+        assert_eq!(parse_keywords(&updated), strings(&["tdd", "red:green"]));
+
+        // Block lists are read, and replaced by a flow list.
+        let block = "---\nname: a\nkeywords:\n  - one\n  - two\ntools: Read\n---\nbody";
+        assert_eq!(parse_keywords(block), strings(&["one", "two"]));
+        assert_eq!(set_keywords(block, &strings(&["three"])).unwrap(), "---\nname: a\nkeywords: [three]\ntools: Read\n---\nbody");
+
+        // Missing entries are appended; an empty list drops the entry.
+        let plain = "---\nname: a\n---\nbody\n";
+        let added = set_keywords(plain, &strings(&["x"])).unwrap();
+        assert_eq!(added, "---\nname: a\nkeywords: [x]\n---\nbody\n");
+        assert_eq!(set_keywords(&added, &[]).unwrap(), plain);
+
+        assert!(parse_keywords("# no frontmatter").is_empty());
+        assert!(set_keywords("# no frontmatter", &strings(&["x"])).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Moving a Claude Code setup to Codex. Codex reads the same skills and
+//! MCP servers, so those can be installed for it as they are; agents,
+//! commands, hooks, output styles and the rest have no Codex equivalent.
+
+use anyhow::Result;
+
+use crate::component::{Component, ComponentType, InstallStatus};
+use crate::source::config::DirectoryMap;
+use crate::source::ResolvedSource;
+use crate::TargetCli;
+
+/// What is installed for Claude, split by whether Codex can use it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MigrationPlan {
+    /// Skills installed for Claude from the sources, by component name.
+    pub skills: Vec<String>,
+    /// MCP servers in Claude's user config.
+    pub mcp_servers: Vec<String>,
+    /// Installed items Codex has no place for: type name and count.
+    pub not_portable: Vec<(String, usize)>,
+}
+
+impl MigrationPlan {
+    /// Whether Claude has anything installed at all.
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty() && self.mcp_servers.is_empty() && self.not_portable.is_empty()
+    }
+}
+
+/// Plan for the items installed for Claude from `sources`. Claude's MCP
+/// servers are read from `~/.claude.json` rather than `claude mcp list`,
+/// which health-checks every server.
+pub fn claude_to_codex(sources: &[ResolvedSource], directories: &DirectoryMap) -> Result<MigrationPlan> {
+    let claude_dir = TargetCli::Claude.get_dest_dir()?;
+    if !claude_dir.is_dir() {
+        return Ok(MigrationPlan::default());
+    }
+    let components = super::scanner::scan_all_sources(sources, &claude_dir, TargetCli::Claude, directories, crate::fs::scanner::ScanOptions::default())?;
+    let mcp_servers = dirs::home_dir()
+        .and_then(|home| super::installer::configured_mcp_servers(TargetCli::Claude, &home.join(".claude.json")).ok())
+        .unwrap_or_default();
+    let plugins = super::scanner::scan_all_plugin_sources(sources)?
+        .iter()
+        .filter(|p| super::installer::plugin_enabled(p))
+        .count();
+    Ok(plan_from(&components, mcp_servers, plugins))
+}
+
+fn plan_from(components: &[Component], mut mcp_servers: Vec<String>, plugins: usize) -> MigrationPlan {
+    let installed = components.iter().filter(|c| {
+        matches!(c.status, InstallStatus::Unchanged | InstallStatus::Modified | InstallStatus::Managed)
+    });
+    let mut plan = MigrationPlan::default();
+    for c in installed {
+        if c.component_type == ComponentType::Skills {
+            plan.skills.push(c.name.clone());
+            continue;
+        }
+        let label = c.component_type.display_name();
+        match plan.not_portable.iter_mut().find(|(l, _)| l == label) {
+            Some((_, count)) => *count += 1,
+            None => plan.not_portable.push((label.to_string(), 1)),
+        }
+    }
+    if plugins > 0 {
+        plan.not_portable.push(("plugins".to_string(), plugins));
+    }
+    mcp_servers.sort();
+    plan.mcp_servers = mcp_servers;
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skills_and_mcp_servers_carry_over() {
+        let component = |kind: ComponentType, name: &str, status: InstallStatus| {
+            Component::new(kind, name.into(), name.into(), name.into(), status)
+        };
+        let components = vec![
+            component(ComponentType::Skills, "review", InstallStatus::Unchanged),
+            component(ComponentType::Skills, "unused", InstallStatus::New),
+            component(ComponentType::Hooks, "fmt", InstallStatus::Modified),
+            component(ComponentType::Hooks, "lint", InstallStatus::Unchanged),
+            component(ComponentType::OutputStyles, "terse.md", InstallStatus::Unchanged),
+            component(ComponentType::Agents, "mine.md", InstallStatus::External),
+        ];
+        let plan = plan_from(&components, vec!["memory".into(), "context7".into()], 1);
+
+        assert_eq!(plan.skills, vec!["review"]);
+        assert_eq!(plan.mcp_servers, vec!["context7", "memory"]);
+        assert_eq!(plan.not_portable, vec![
+            ("hooks".to_string(), 2),
+            ("output-styles".to_string(), 1),
+            ("plugins".to_string(), 1),
+        ]);
+        assert!(plan_from(&[], Vec::new(), 0).is_empty());
+    }
+}
@@ -1,17 +1,53 @@
 pub mod scanner;
 pub mod diff;
+pub mod hunks;
 pub mod installer;
+pub mod backup;
+pub mod journal;
+pub mod report;
+pub mod drift;
+pub mod hooks_script;
+pub mod jsonc;
+pub mod pending;
+pub mod settings_history;
+pub mod keywords;
+pub mod frontmatter;
+pub mod hosts;
+pub mod state;
+pub mod audit;
+pub mod migration;
+pub mod interrupted;
 
 use std::process::{Command, Stdio};
 use std::time::Duration;
 use anyhow::Result;
 use wait_timeout::ChildExt;
-use crate::app::TargetCli;
+use crate::TargetCli;
 
-/// Application version string, derived from Cargo.toml at compile time.
-pub const VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
+/// Total size in bytes of a file, or of every file under a directory.
+/// Missing or unreadable paths count as zero.
+pub fn path_size(path: &std::path::Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Newest modification time of a file, or of any file under a directory.
+/// `None` when nothing under `path` is readable.
+pub fn path_modified(path: &std::path::Path) -> Option<std::time::SystemTime> {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()
+}
 
-/// Create a Command to run CLI (Claude or Codex).
+/// Create a Command to run a target's CLI (`TargetCli::program_name`).
 ///
 /// On Unix, the bare name is resolved through PATH by execvp.
 ///
@@ -27,11 +63,13 @@ pub const VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
 ///
 /// stdin is set to null to prevent blocking on interactive prompts.
 pub fn create_cli_command(target_cli: TargetCli) -> Command {
-    let cli_name = match target_cli {
-        TargetCli::Claude => "claude",
-        TargetCli::Codex => "codex",
-    };
-    let mut cmd = Command::new(resolve_cli_program(cli_name));
+    create_tool_command(target_cli.program_name())
+}
+
+/// Like `create_cli_command`, for other npm-distributed tools (e.g. `npm`)
+/// that need the same Windows shim and rc-less PATH resolution.
+pub fn create_tool_command(name: &str) -> Command {
+    let mut cmd = Command::new(resolve_cli_program(name));
     cmd.stdin(Stdio::null());
     cmd
 }
@@ -105,6 +143,80 @@ fn resolve_cli_program(cli_name: &str) -> std::ffi::OsString {
     }
 }
 
+/// What the installer found for a CLI binary at startup.
+#[derive(Clone, Debug, Default)]
+pub struct CliInfo {
+    /// Program that `create_cli_command` will actually spawn, or `None`
+    /// when nothing was found on PATH or in the known install locations.
+    pub path: Option<std::path::PathBuf>,
+    /// First line of `<cli> --version`, when the probe succeeded.
+    pub version: Option<String>,
+    /// Every distinct match on PATH, in lookup order. More than one means
+    /// the binary a user runs by hand may not be the one listed first.
+    pub candidates: Vec<std::path::PathBuf>,
+}
+
+/// Timeout for the startup `--version` probe. Kept short since it only
+/// feeds a label on the selection screen.
+const CLI_INFO_TIMEOUT_SECS: u64 = 5;
+
+/// Resolve the path, version, and PATH candidates for a CLI.
+///
+/// Blocks for up to `CLI_INFO_TIMEOUT_SECS`; call from a worker thread.
+pub fn probe_cli(target_cli: TargetCli) -> CliInfo {
+    let name = target_cli.program_name();
+    let program = std::path::PathBuf::from(resolve_cli_program(name));
+    let path = program.is_absolute().then_some(program);
+    let candidates = std::env::var_os("PATH")
+        .map(|p| path_candidates(name, &p))
+        .unwrap_or_default();
+
+    let version = path.as_ref().and_then(|_| {
+        let mut cmd = create_cli_command(target_cli);
+        cmd.arg("--version");
+        let out = run_with_timeout(&mut cmd, CLI_INFO_TIMEOUT_SECS).ok()?;
+        if !out.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .next()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+    });
+
+    CliInfo { path, version, candidates }
+}
+
+/// All files named like `cli_name` in the directories of `path_var`, in
+/// PATH order. Entries that resolve to the same file (a symlinked `/bin`
+/// on merged-usr systems, a directory listed twice) are reported once.
+fn path_candidates(cli_name: &str, path_var: &std::ffi::OsStr) -> Vec<std::path::PathBuf> {
+    #[cfg(windows)]
+    let names: Vec<String> = [".exe", ".cmd", ".bat"]
+        .iter()
+        .map(|ext| format!("{}{}", cli_name, ext))
+        .collect();
+    #[cfg(not(windows))]
+    let names = vec![cli_name.to_string()];
+
+    let mut seen = std::collections::HashSet::new();
+    let mut found = Vec::new();
+    for dir in std::env::split_paths(path_var) {
+        for name in &names {
+            let candidate = dir.join(name);
+            if !candidate.is_file() {
+                continue;
+            }
+            let key = std::fs::canonicalize(&candidate).unwrap_or_else(|_| candidate.clone());
+            if seen.insert(key) {
+                found.push(candidate);
+            }
+        }
+    }
+    found
+}
+
 /// Public docs URL surfaced in ENOENT hints. Centralised so a future
 /// rename of the upstream doc site only touches one line.
 const CLI_INSTALL_DOCS_URL: &str = "https://docs.claude.com/en/docs/claude-code/setup";
@@ -177,6 +289,7 @@ pub(crate) fn run_with_timeout(command: &mut Command, timeout_secs: u64) -> Resu
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::temp_dir;
 
     #[test]
     #[cfg(not(windows))]
@@ -194,7 +307,7 @@ mod tests {
         // verify resolution returns its absolute path (PATH walk wins
         // before the known-location fallback list).
         use std::os::unix::fs::PermissionsExt;
-        let dir = std::env::temp_dir().join("hibi_resolve_unix_test");
+        let dir = temp_dir("resolve_unix_test");
         let _ = std::fs::create_dir_all(&dir);
         let bin = dir.join("hibitestbin");
         std::fs::write(&bin, b"#!/bin/sh\nexit 0\n").unwrap();
@@ -260,12 +373,31 @@ mod tests {
         assert_eq!(resolved, std::ffi::OsString::from(unlikely));
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn path_candidates_lists_each_distinct_match_in_order() {
+        let root = temp_dir("candidates");
+        let (a, b, empty) = (root.join("a"), root.join("b"), root.join("empty"));
+        for dir in [&a, &b, &empty] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(a.join("hibicli"), b"").unwrap();
+        std::fs::write(b.join("hibicli"), b"").unwrap();
+
+        // `a` listed twice must still be reported once.
+        let path = std::env::join_paths([&empty, &a, &b, &a]).unwrap();
+        let found = path_candidates("hibicli", &path);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(found, vec![a.join("hibicli"), b.join("hibicli")]);
+    }
+
     #[test]
     #[cfg(windows)]
     fn windows_resolve_finds_shim_in_path() {
         // Write a fake `.cmd` shim into a temp dir, point PATH at it,
         // and verify resolution returns the absolute path.
-        let dir = std::env::temp_dir().join("hibi_resolve_test");
+        let dir = temp_dir("resolve_test");
         let _ = std::fs::create_dir_all(&dir);
         let shim = dir.join("hibitestshim.cmd");
         std::fs::write(&shim, b"@echo off\r\n").unwrap();
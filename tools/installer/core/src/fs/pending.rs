@@ -0,0 +1,258 @@
+//! Installs that need the network, queued while offline.
+//!
+//! MCP servers added through a CLI and plugins fetched from a marketplace
+//! fail straight away without a connection. Instead of failing, an install
+//! can be queued in `<dest_dir>/.hibi/pending.yaml` with everything it
+//! needs to run later (`hibi flush-pending`, or the prompt at startup).
+//! An operation leaves the queue once it succeeds or finds its target
+//! already configured; failed ones stay for the next retry.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::fs::installer::{
+    install_mcp_server, install_plugin, mcp_server_configured, plugin_enabled, McpInstallConfig,
+};
+use crate::mcp::{McpScope, McpServer, McpServerDef, McpStatus};
+use crate::plugin::{Plugin, PluginDef, PluginStatus};
+use crate::TargetCli;
+
+/// File name inside the installer state dir (see `state`).
+pub const PENDING_FILE: &str = "pending.yaml";
+
+/// Hosts that installs fetch from. Reaching any of them counts as online.
+const PROBE_HOSTS: &[&str] = &["registry.npmjs.org:443", "github.com:443"];
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Same budgets as an install from the TUI.
+const MCP_TIMEOUT_SECS: u64 = 120;
+const PLUGIN_TIMEOUT_SECS: u64 = 60;
+
+/// One queued install. MCP env values are kept as entered; the file is
+/// only readable by the user.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingOp {
+    McpServer {
+        server: McpServerDef,
+        scope: McpScope,
+        #[serde(default)]
+        project_path: Option<String>,
+        #[serde(default)]
+        env_values: Vec<(String, String)>,
+    },
+    Plugin {
+        plugin: PluginDef,
+    },
+}
+
+impl PendingOp {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::McpServer { server, .. } => &server.name,
+            Self::Plugin { plugin } => &plugin.name,
+        }
+    }
+
+    /// `MCP server` / `plugin`, for messages.
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            Self::McpServer { .. } => "MCP server",
+            Self::Plugin { .. } => "plugin",
+        }
+    }
+
+    fn same_target(&self, other: &PendingOp) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other) && self.name() == other.name()
+    }
+
+    /// Run the install; `Ok` when it succeeded or was already configured.
+    fn run(&self, target_cli: TargetCli) -> Result<()> {
+        // Never cancelled; the sender only has to outlive the install.
+        let (_cancel_tx, cancel_rx) = mpsc::channel();
+        match self {
+            Self::McpServer { server, scope, project_path, env_values } => {
+                let server = McpServer::new(server.clone(), McpStatus::NotInstalled);
+                if mcp_server_configured(&server, target_cli, *scope, project_path.as_deref(), env_values) {
+                    return Ok(());
+                }
+                install_mcp_server(&server, McpInstallConfig {
+                    scope: *scope,
+                    project_path: project_path.as_deref(),
+                    env_values,
+                    target_cli,
+                    timeout_secs: MCP_TIMEOUT_SECS,
+                    cancel_rx: &cancel_rx,
+                    progress_tx: None,
+                })
+            }
+            Self::Plugin { plugin } => {
+                let plugin = Plugin::new(plugin.clone(), PluginStatus::NotInstalled);
+                if plugin_enabled(&plugin) {
+                    return Ok(());
+                }
+                install_plugin(&plugin, PLUGIN_TIMEOUT_SECS, &cancel_rx, None)
+            }
+        }
+    }
+}
+
+/// Whether an install of this kind needs the network: plugins always do,
+/// MCP servers only when a CLI adds them (config-file targets are written
+/// directly).
+pub fn needs_network(target_cli: TargetCli, plugin: bool) -> bool {
+    plugin || target_cli.manages_mcp_via_cli()
+}
+
+/// Whether any install host can be reached. Blocks for up to a few
+/// seconds per host when offline; call from a worker thread.
+pub fn is_online() -> bool {
+    PROBE_HOSTS.iter().any(|host| {
+        host.to_socket_addrs()
+            .map(|mut addrs| addrs.any(|addr| TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).is_ok()))
+            .unwrap_or(false)
+    })
+}
+
+fn pending_path(dest_dir: &Path) -> PathBuf {
+    crate::fs::state::state_dir(dest_dir).join(PENDING_FILE)
+}
+
+/// The queue for `dest_dir`, oldest first; empty when there is none.
+pub fn load(dest_dir: &Path) -> Result<Vec<PendingOp>> {
+    crate::fs::state::load_yaml(&pending_path(dest_dir))
+}
+
+fn save(dest_dir: &Path, ops: &[PendingOp]) -> Result<()> {
+    let path = pending_path(dest_dir);
+    if ops.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Created private, so the env values are never readable by others,
+    // not even between creating and writing the file.
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        // A file from an older version keeps its mode when opened.
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::io::Write::write_all(&mut file, serde_yaml_bw::to_string(ops)?.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Add `op` to the queue, replacing an earlier one for the same server or
+/// plugin.
+pub fn queue(dest_dir: &Path, op: PendingOp) -> Result<()> {
+    let mut ops = load(dest_dir)?;
+    ops.retain(|queued| !queued.same_target(&op));
+    ops.push(op);
+    save(dest_dir, &ops)
+}
+
+/// Drop the whole queue.
+pub fn discard(dest_dir: &Path) -> Result<()> {
+    save(dest_dir, &[])
+}
+
+/// Outcome of one retried operation.
+pub struct FlushResult {
+    pub op: PendingOp,
+    /// The error, when the operation stays queued.
+    pub error: Option<String>,
+}
+
+/// Retry every queued operation in order, keeping the ones that fail.
+/// Fails without trying any while still offline. Call from a worker
+/// thread: each install may take minutes.
+pub fn flush(dest_dir: &Path, target_cli: TargetCli) -> Result<Vec<FlushResult>> {
+    let ops = load(dest_dir)?;
+    if ops.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !is_online() {
+        bail!("Still offline; {} install(s) left queued", ops.len());
+    }
+    let results: Vec<FlushResult> = ops
+        .into_iter()
+        .map(|op| {
+            let error = op.run(target_cli).err().map(|e| format!("{:#}", e));
+            FlushResult { op, error }
+        })
+        .collect();
+    let remaining: Vec<PendingOp> = results.iter()
+        .filter(|r| r.error.is_some())
+        .map(|r| r.op.clone())
+        .collect();
+    save(dest_dir, &remaining)?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn queue_replaces_the_same_target_and_round_trips() {
+        let dest = temp_dir("pending");
+        let server: McpServerDef = serde_yaml_bw::from_str(
+            "name: github\ndescription: GitHub\ncommand: npx -y @modelcontextprotocol/server-github\ncategory: dev\nenv: [GITHUB_TOKEN]\n",
+        ).unwrap();
+        let mcp = |token: &str| PendingOp::McpServer {
+            server: server.clone(),
+            scope: McpScope::Local,
+            project_path: Some("/work/app".to_string()),
+            env_values: vec![("GITHUB_TOKEN".to_string(), token.to_string())],
+        };
+        let plugin = PendingOp::Plugin {
+            plugin: PluginDef {
+                name: "github".to_string(),
+                marketplace: "official".to_string(),
+                source: "https://github.com/anthropics/claude-plugins-official.git".to_string(),
+                comment: None,
+            },
+        };
+
+        queue(&dest, mcp("old")).unwrap();
+        queue(&dest, plugin).unwrap();
+        queue(&dest, mcp("new")).unwrap();
+        let ops = load(&dest).unwrap();
+
+        assert_eq!(ops.len(), 2, "a plugin and a server of the same name are both kept");
+        assert!(matches!(&ops[0], PendingOp::Plugin { plugin } if plugin.marketplace == "official"));
+        let PendingOp::McpServer { scope, project_path, env_values, .. } = &ops[1] else { panic!() };
+        assert_eq!(*scope, McpScope::Local);
+        assert_eq!(project_path.as_deref(), Some("/work/app"));
+        assert_eq!(env_values, &[("GITHUB_TOKEN".to_string(), "new".to_string())]);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(pending_path(&dest)).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        discard(&dest).unwrap();
+        assert!(!pending_path(&dest).exists());
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+}
@@ -0,0 +1,149 @@
+//! Review report of pending component changes: one document holding the
+//! diff of every new or modified component, as Markdown or a standalone
+//! HTML page, for sharing before the changes are applied.
+
+use std::path::Path;
+
+use crate::component::{Component, InstallStatus};
+use crate::fs::backup::format_timestamp;
+use crate::maintenance::now_secs;
+use crate::fs::diff::component_diff;
+use crate::source::config::contract_tilde;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// HTML for `.html`/`.htm` paths, Markdown otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("html" | "htm") => Self::Html,
+            _ => Self::Markdown,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Whether installing `c` would change anything on disk.
+pub fn is_pending(c: &Component) -> bool {
+    matches!(c.status, InstallStatus::New | InstallStatus::Modified)
+}
+
+/// `hibi-report-<timestamp>.<ext>`, timestamped in UTC.
+pub fn default_file_name(format: ReportFormat) -> String {
+    format!("hibi-report-{}.{}", format_timestamp(now_secs()), format.extension())
+}
+
+/// The report for `components`. A component whose diff can't be computed
+/// gets the error in place of its diff rather than failing the report.
+pub fn render_report(components: &[&Component], format: ReportFormat) -> String {
+    let new = components.iter().filter(|c| c.status == InstallStatus::New).count();
+    let summary = format!("{} new, {} modified", new, components.len() - new);
+    let sections: Vec<Section> = components.iter()
+        .map(|c| Section {
+            title: format!("{}/{} ({})", c.component_type.display_name(), c.name, c.status.display()),
+            paths: format!("{} → {}", contract_tilde(&c.source_path), contract_tilde(&c.dest_path)),
+            diff: component_diff(c).unwrap_or_else(|e| format!("Could not diff: {:#}", e)),
+        })
+        .collect();
+
+    match format {
+        ReportFormat::Markdown => render_markdown(&summary, &sections),
+        ReportFormat::Html => render_html(&summary, &sections),
+    }
+}
+
+struct Section {
+    title: String,
+    paths: String,
+    diff: String,
+}
+
+const TITLE: &str = "hibi pending changes";
+
+fn render_markdown(summary: &str, sections: &[Section]) -> String {
+    let mut out = format!("# {}\n\n{}\n", TITLE, summary);
+    for s in sections {
+        // A fence longer than any backtick run in the diff keeps it closed.
+        let longest = s.diff.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest.max(2) + 1);
+        out.push_str(&format!("\n## {}\n\n`{}`\n\n{}diff\n{}", s.title, s.paths, fence, s.diff));
+        if !s.diff.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&fence);
+        out.push('\n');
+    }
+    out
+}
+
+fn render_html(summary: &str, sections: &[Section]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{TITLE}</title>\n<style>\
+body{{font-family:sans-serif;margin:2em}}pre{{background:#f6f8fa;padding:1em;overflow-x:auto}}\
+.add{{color:#116329;background:#dafbe1}}.del{{color:#82071e;background:#ffebe9}}.meta{{color:#6e7781}}\
+</style></head><body>\n<h1>{TITLE}</h1>\n<p>{}</p>\n",
+        escape_html(summary)
+    );
+    for s in sections {
+        out.push_str(&format!("<h2>{}</h2>\n<p><code>{}</code></p>\n<pre>", escape_html(&s.title), escape_html(&s.paths)));
+        for line in s.diff.lines() {
+            let class = if line.starts_with("+++") || line.starts_with("---") || line.starts_with("@@") {
+                Some("meta")
+            } else if line.starts_with('+') {
+                Some("add")
+            } else if line.starts_with('-') {
+                Some("del")
+            } else {
+                None
+            };
+            match class {
+                Some(class) => out.push_str(&format!("<span class=\"{}\">{}</span>\n", class, escape_html(line))),
+                None => out.push_str(&format!("{}\n", escape_html(line))),
+            }
+        }
+        out.push_str("</pre>\n");
+    }
+    out.push_str("</body></html>\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::component::ComponentType;
+
+    #[test]
+    fn markdown_and_html_reports_include_each_diff() {
+        let dir = temp_dir("report");
+        let source = dir.join("a.md");
+        std::fs::write(&source, "use ```fences``` <b>\n").unwrap();
+        let c = Component::new(ComponentType::Agents, "a.md".into(), source, dir.join("missing.md"), InstallStatus::New);
+
+        let md = render_report(&[&c], ReportFormat::Markdown);
+        let html = render_report(&[&c], ReportFormat::Html);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(md.contains("1 new, 0 modified"));
+        assert!(md.contains("## agents/a.md (new)"));
+        assert!(md.contains("````diff\n"));
+        assert!(md.contains("+use ```fences``` <b>\n````\n"));
+        assert!(html.contains("<span class=\"add\">+use ```fences``` &lt;b&gt;</span>"));
+        assert_eq!(ReportFormat::from_path(Path::new("r.HTML")), ReportFormat::Html);
+        assert_eq!(ReportFormat::from_path(Path::new("r.md")), ReportFormat::Markdown);
+    }
+}
@@ -0,0 +1,605 @@
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use walkdir::WalkDir;
+
+use crate::TargetCli;
+use crate::component::{Component, ComponentType, HookConfig, InstallStatus};
+use crate::fs::installer::{cursor_rule_content, is_cursor_rule, CURSOR_RULE_EXTENSION};
+use crate::source::config::DirectoryMap;
+
+use super::hash_cache::HashCache;
+
+/// Scan all files in a directory as a single component type (for `map_to` sources).
+pub(super) fn scan_flat(
+    source_dir: &Path,
+    dest_dir: &Path,
+    component_type: ComponentType,
+    cache: &HashCache,
+    threads: Option<usize>,
+) -> Result<Vec<Component>> {
+    let mut components = Vec::new();
+    scan_directory(source_dir, dest_dir, component_type, cache, threads, &mut components)?;
+    Ok(components)
+}
+
+/// Scan all component types from a source directory.
+/// Folder names come from `directories`, defaulting to the type name.
+/// The files of every folder are compared in one pool of `threads`
+/// threads; results keep the order below.
+pub(super) fn scan_components(
+    source_dir: &Path,
+    dest_dir: &Path,
+    target_cli: TargetCli,
+    directories: &DirectoryMap,
+    cache: &HashCache,
+    threads: Option<usize>,
+) -> Result<Vec<Component>> {
+    let types = match target_cli {
+        TargetCli::Claude => vec![
+            ComponentType::Agents,
+            ComponentType::Commands,
+            ComponentType::Contexts,
+            ComponentType::Rules,
+            ComponentType::Skills,
+            ComponentType::OutputStyles,
+        ],
+        TargetCli::Codex | TargetCli::OpenCode => vec![ComponentType::Skills],
+        TargetCli::Cursor => vec![ComponentType::Rules],
+        TargetCli::Custom(_) => target_cli.custom().map(|t| t.component_types.clone()).unwrap_or_default(),
+    };
+
+    let mut files = Vec::new();
+    for comp_type in types {
+        let source = source_dir.join(directories.source_dir(&comp_type));
+        let dest = dest_dir.join(directories.dest_dir(&comp_type));
+        list_files(&source, &dest, comp_type, &mut files)?;
+    }
+    let builtin = files.len();
+    for custom in directories.custom_types() {
+        let mut found = Vec::new();
+        list_files(&source_dir.join(&custom.source), &dest_dir.join(&custom.dest), custom.component_type(), &mut found)?;
+        files.extend(found.into_iter().filter(|f| matches_any(&custom.patterns, &f.source_path)));
+    }
+
+    let mut components = compare_files(files, cache, threads)?;
+    let custom = components.split_off(builtin);
+    if target_cli == TargetCli::Cursor {
+        let mut rules = Vec::new();
+        for rule in components {
+            if let Some(rule) = as_cursor_rule(rule)? {
+                rules.push(rule);
+            }
+        }
+        components = rules;
+    }
+    if target_cli == TargetCli::Claude {
+        scan_statusline(source_dir, dest_dir, cache, &mut components)?;
+        scan_hooks(source_dir, dest_dir, cache, &mut components)?;
+    }
+    add_config_files(source_dir, dest_dir, target_cli, cache, &mut components)?;
+    components.extend(custom);
+
+    Ok(components)
+}
+
+/// `f` applied to every item, spread over `threads` threads (every core
+/// by default) in contiguous chunks so the output keeps the input order.
+fn map_in_parallel<T, R, F>(items: Vec<T>, threads: Option<usize>, f: F) -> Result<Vec<R>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> Result<R> + Sync,
+{
+    let threads = threads
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    if threads <= 1 || items.len() < 2 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    let mut chunks: Vec<Vec<T>> = Vec::new();
+    let mut items = items.into_iter().peekable();
+    while items.peek().is_some() {
+        chunks.push(items.by_ref().take(chunk_size).collect());
+    }
+
+    let f = &f;
+    let results: Vec<Result<Vec<R>>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks.into_iter()
+            .map(|chunk| scope.spawn(move || chunk.into_iter().map(f).collect()))
+            .collect();
+        handles.into_iter()
+            .map(|h| h.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    });
+    let mut mapped = Vec::new();
+    for chunk in results {
+        mapped.extend(chunk?);
+    }
+    Ok(mapped)
+}
+
+/// Whether the file name matches one of the globs (`*` and `?` only). No
+/// patterns matches everything.
+fn matches_any(patterns: &[String], path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else { return false };
+    patterns.is_empty() || patterns.iter().any(|p| glob_match(p.as_bytes(), name.as_bytes()))
+}
+
+pub(crate) fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..])),
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// A source file and where it installs, before its status is known.
+struct FoundFile {
+    component_type: ComponentType,
+    name: String,
+    source_path: PathBuf,
+    dest_path: PathBuf,
+}
+
+fn scan_directory(
+    source_dir: &Path,
+    dest_dir: &Path,
+    component_type: ComponentType,
+    cache: &HashCache,
+    threads: Option<usize>,
+    components: &mut Vec<Component>,
+) -> Result<()> {
+    let mut files = Vec::new();
+    list_files(source_dir, dest_dir, component_type, &mut files)?;
+    components.extend(compare_files(files, cache, threads)?);
+    Ok(())
+}
+
+/// The installable files under `source_dir`, in walk order.
+fn list_files(
+    source_dir: &Path,
+    dest_dir: &Path,
+    component_type: ComponentType,
+    files: &mut Vec<FoundFile>,
+) -> Result<()> {
+    if !source_dir.exists() {
+        return Ok(());
+    }
+
+    // Skip skill-development artifacts.
+    // Convention: skill-creator writes iteration/benchmark outputs to
+    // `<skill>/workspace/`. See `src/skills/*/workspace/` and `.gitignore`.
+    // Pruning via `filter_entry` avoids descending the subtree entirely.
+    for entry in WalkDir::new(source_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !(e.file_type().is_dir() && e.file_name() == "workspace"))
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        // Skip *-ko.* files: Korean reference versions kept for developer
+        // readability but never installed (English originals are
+        // token-cheaper for the LLM).
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            if stem.ends_with("-ko") {
+                continue;
+            }
+        }
+
+        let relative = path.strip_prefix(source_dir)?;
+
+        // Security: reject path traversal attempts
+        if relative.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+            continue;
+        }
+
+        files.push(FoundFile {
+            component_type: component_type.clone(),
+            name: relative.to_string_lossy().to_string(),
+            source_path: path.to_path_buf(),
+            dest_path: dest_dir.join(relative),
+        });
+    }
+
+    Ok(())
+}
+
+/// Components for `files`, in the same order. Comparing contents reads
+/// both copies of every file; that is the slow part on large skill trees,
+/// so it runs across `threads` threads.
+fn compare_files(files: Vec<FoundFile>, cache: &HashCache, threads: Option<usize>) -> Result<Vec<Component>> {
+    map_in_parallel(files, threads, |file| {
+        let status = determine_status(&file.source_path, &file.dest_path, cache)?;
+        Ok(Component::new(file.component_type, file.name, file.source_path, file.dest_path, status))
+    })
+}
+
+fn scan_statusline(
+    source_dir: &Path,
+    dest_dir: &Path,
+    cache: &HashCache,
+    components: &mut Vec<Component>,
+) -> Result<()> {
+    let statusline_dir = source_dir.join("statusline");
+    if !statusline_dir.exists() {
+        return Ok(());
+    }
+
+    let binary_name = if cfg!(windows) {
+        "statusline.exe"
+    } else if cfg!(target_os = "macos") {
+        "statusline_macos"
+    } else {
+        "statusline_linux"
+    };
+
+    let binary_path = statusline_dir.join(binary_name);
+    if !binary_path.exists() {
+        return Ok(());
+    }
+
+    let dest_path = dest_dir.join("statusline").join(binary_name);
+    let status = determine_status(&binary_path, &dest_path, cache)?;
+
+    components.push(Component::new(
+        ComponentType::Statusline,
+        binary_name.to_string(),
+        binary_path,
+        dest_path,
+        status,
+    ));
+
+    Ok(())
+}
+
+fn scan_hooks(
+    source_dir: &Path,
+    dest_dir: &Path,
+    cache: &HashCache,
+    components: &mut Vec<Component>,
+) -> Result<()> {
+    let hooks_dir = source_dir.join("hooks");
+    if !hooks_dir.exists() {
+        return Ok(());
+    }
+
+    for entry in std::fs::read_dir(&hooks_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        let hook_name = path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+
+        // Listed rather than skipped, so a hook.yaml can be scaffolded.
+        let hook_yaml = path.join("hook.yaml");
+        if !hook_yaml.exists() {
+            components.push(Component::new(
+                ComponentType::Hooks,
+                hook_name.to_string(),
+                path.clone(),
+                dest_dir.join("hooks").join(hook_name),
+                InstallStatus::Unconfigured,
+            ));
+            continue;
+        }
+
+        let config_content = std::fs::read_to_string(&hook_yaml)?;
+        let config: HookConfig = serde_yaml_bw::from_str(&config_content)?;
+
+        scan_hook_entry(hook_name, &path, dest_dir, config, cache, components)?;
+    }
+
+    Ok(())
+}
+
+/// Process a single hook directory entry (deprecated or normal).
+fn scan_hook_entry(
+    hook_name: &str,
+    hook_dir: &Path,
+    dest_dir: &Path,
+    config: HookConfig,
+    cache: &HashCache,
+    components: &mut Vec<Component>,
+) -> Result<()> {
+    let binary_name = config.binary_name();
+
+    if config.is_deprecated() {
+        let dest_path = dest_dir.join("hooks").join(&binary_name);
+        if !dest_path.exists() {
+            return Ok(()); // Not installed, cannot uninstall
+        }
+        let component = Component::new(
+            ComponentType::Hooks,
+            hook_name.to_string(),
+            dest_path.clone(), // source_path = dest_path (uninstall only)
+            dest_path,
+            InstallStatus::Unchanged,
+        ).with_hook_config(config);
+        components.push(component);
+    } else {
+        let binary_path = hook_dir.join(&binary_name);
+        if !binary_path.exists() {
+            return Ok(());
+        }
+
+        let dest_path = dest_dir.join("hooks").join(&binary_name);
+        let status = determine_status(&binary_path, &dest_path, cache)?;
+
+        let component = Component::new(
+            ComponentType::Hooks,
+            hook_name.to_string(),
+            binary_path,
+            dest_path,
+            status,
+        ).with_hook_config(config);
+        components.push(component);
+    }
+
+    Ok(())
+}
+
+fn add_config_files(
+    source_dir: &Path,
+    dest_dir: &Path,
+    target_cli: TargetCli,
+    cache: &HashCache,
+    components: &mut Vec<Component>,
+) -> Result<()> {
+    let config_files = match target_cli {
+        TargetCli::Claude => vec!["settings.json", "CLAUDE.md"],
+        TargetCli::Codex | TargetCli::OpenCode => vec!["AGENTS.md"],
+        TargetCli::Cursor => vec![],
+        TargetCli::Custom(_) => target_cli.custom()
+            .map(|t| t.config_files.iter().map(String::as_str).collect())
+            .unwrap_or_default(),
+    };
+
+    for file in config_files {
+        let source_path = source_dir.join(file);
+        if source_path.exists() {
+            let dest_path = dest_dir.join(file);
+            let status = if file == "settings.json" && dest_path.exists() {
+                InstallStatus::Managed
+            } else {
+                determine_status(&source_path, &dest_path, cache)?
+            };
+
+            components.push(Component::new(
+                ComponentType::ConfigFile,
+                file.to_string(),
+                source_path,
+                dest_path,
+                status,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Retarget a scanned markdown rule at `<name>.mdc`, the form Cursor
+/// reads. Other files under `rules/` are dropped.
+fn as_cursor_rule(mut rule: Component) -> Result<Option<Component>> {
+    if rule.source_path.extension().is_none_or(|e| e != "md") {
+        return Ok(None);
+    }
+    rule.dest_path.set_extension(CURSOR_RULE_EXTENSION);
+    rule.status = cursor_rule_status(&rule.source_path, &rule.dest_path)?;
+    Ok(Some(rule))
+}
+
+/// Like `determine_status`, against the converted rule content.
+fn cursor_rule_status(source: &Path, dest: &Path) -> Result<InstallStatus> {
+    if !dest.exists() {
+        return Ok(InstallStatus::New);
+    }
+    let installed = std::fs::read(dest)?;
+    if normalize_line_endings(cursor_rule_content(source)?.as_bytes()) == normalize_line_endings(&installed) {
+        Ok(InstallStatus::Unchanged)
+    } else {
+        Ok(InstallStatus::Modified)
+    }
+}
+
+/// Re-read the status of one already-scanned component, for the targeted
+/// refresh after an install/remove batch. `None` means the item is gone:
+/// an external or deprecated hook (source = dest) whose file was removed.
+pub fn component_status(component: &Component) -> Result<Option<InstallStatus>> {
+    let dest = &component.dest_path;
+    if component.source_path == *dest {
+        return Ok(dest.exists().then_some(component.status.clone()));
+    }
+    if component.component_type == ComponentType::ConfigFile
+        && component.name == "settings.json"
+        && dest.exists()
+    {
+        return Ok(Some(InstallStatus::Managed));
+    }
+    if is_cursor_rule(component) {
+        return cursor_rule_status(&component.source_path, dest).map(Some);
+    }
+    determine_status(&component.source_path, dest, &HashCache::default()).map(Some)
+}
+
+/// Strip `\r` bytes so that CRLF and LF files compare as equal.
+/// This avoids false "Modified" status when the same content is checked out
+/// with different git `autocrlf` settings.
+fn normalize_line_endings(content: &[u8]) -> Vec<u8> {
+    content.iter().copied().filter(|&b| b != b'\r').collect()
+}
+
+/// Same size and modification time. Installs give a copied file its
+/// source's mtime, so this holds until either side is touched again. Files
+/// written moments ago can share an mtime tick by chance and don't count.
+fn same_metadata(source: &Path, dest: &Path) -> bool {
+    let (Ok(source), Ok(dest)) = (std::fs::metadata(source), std::fs::metadata(dest)) else {
+        return false;
+    };
+    source.len() == dest.len()
+        && matches!((source.modified(), dest.modified()), (Ok(a), Ok(b)) if a == b && super::is_settled(a))
+}
+
+fn determine_status(source: &Path, dest: &Path, cache: &HashCache) -> Result<InstallStatus> {
+    if !dest.exists() {
+        return Ok(InstallStatus::New);
+    }
+    // Untouched since the install: no need to read either file.
+    if same_metadata(source, dest) {
+        return Ok(InstallStatus::Unchanged);
+    }
+
+    // Hashes ignore CRLF/LF differences, and come from the cache when
+    // neither file changed since the last scan.
+    if cache.hash(source)? == cache.hash(dest)? {
+        Ok(InstallStatus::Unchanged)
+    } else {
+        Ok(InstallStatus::Modified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_test_dir(label: &str) -> std::path::PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let dir = std::env::temp_dir().join(format!("hibi_test_{label}_{nanos}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn glob_patterns_match_file_names() {
+        let md = vec!["*.md".to_string()];
+        assert!(matches_any(&md, Path::new("dir/notes.md")));
+        assert!(!matches_any(&md, Path::new("dir/notes.txt")));
+        assert!(matches_any(&["a?c.*".to_string()], Path::new("abc.json")));
+        assert!(matches_any(&[], Path::new("anything")));
+    }
+
+    #[test]
+    fn matching_metadata_skips_content_comparison() {
+        let dir = unique_test_dir("mtime_fast_path");
+        let (source, dest) = (dir.join("source.md"), dir.join("dest.md"));
+        std::fs::write(&source, "aaaa").unwrap();
+        std::fs::write(&dest, "bbbb").unwrap();
+        let set_mtime = |path: &Path, mtime: SystemTime| {
+            std::fs::File::options().write(true).open(path).unwrap().set_modified(mtime).unwrap();
+        };
+        let old = SystemTime::now() - std::time::Duration::from_secs(60);
+        set_mtime(&source, old);
+        set_mtime(&dest, old);
+
+        // Same size and mtime: trusted without reading the (different) content.
+        let fast = determine_status(&source, &dest, &HashCache::default()).unwrap();
+        // Touching the dest makes the content count again.
+        set_mtime(&dest, old + std::time::Duration::from_secs(1));
+        let compared = determine_status(&source, &dest, &HashCache::default()).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(fast, InstallStatus::Unchanged);
+        assert_eq!(compared, InstallStatus::Modified);
+    }
+
+    #[test]
+    fn parallel_map_keeps_order_and_reports_errors() {
+        let doubled = map_in_parallel((0..1000).collect(), None, |n: u32| Ok(n * 2)).unwrap();
+        assert_eq!(doubled, (0..1000).map(|n| n * 2).collect::<Vec<_>>());
+
+        let failed = map_in_parallel((0..100).collect(), None, |n: u32| {
+            if n == 42 { anyhow::bail!("bad {n}") } else { Ok(n) }
+        });
+        assert_eq!(failed.unwrap_err().to_string(), "bad 42");
+    }
+
+    #[test]
+    fn component_status_tracks_install_and_removal() {
+        let src = unique_test_dir("status_src");
+        let dst = unique_test_dir("status_dst");
+        std::fs::write(src.join("a.md"), "a").unwrap();
+
+        let mut out = Vec::new();
+        scan_directory(&src, &dst, ComponentType::Agents, &HashCache::default(), None, &mut out).unwrap();
+        let component = &out[0];
+        assert_eq!(component_status(component).unwrap(), Some(InstallStatus::New));
+
+        std::fs::write(dst.join("a.md"), "a").unwrap();
+        assert_eq!(component_status(component).unwrap(), Some(InstallStatus::Unchanged));
+
+        let external = Component::new(
+            ComponentType::Agents, "a.md".to_string(),
+            dst.join("a.md"), dst.join("a.md"), InstallStatus::External,
+        );
+        assert_eq!(component_status(&external).unwrap(), Some(InstallStatus::External));
+        std::fs::remove_file(dst.join("a.md")).unwrap();
+        assert_eq!(component_status(&external).unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&src);
+        let _ = std::fs::remove_dir_all(&dst);
+    }
+
+    #[test]
+    fn scan_directory_skips_workspace_subtree() {
+        let src = unique_test_dir("scan_ws_src");
+        let dst = unique_test_dir("scan_ws_dst");
+
+        // skill-a: one installable file + workspace artifacts that must be skipped
+        std::fs::create_dir_all(src.join("skill-a/workspace/iteration-1")).unwrap();
+        std::fs::write(src.join("skill-a/SKILL.md"), "ok").unwrap();
+        std::fs::write(src.join("skill-a/workspace/iteration-1/log.txt"), "junk").unwrap();
+        std::fs::write(src.join("skill-a/workspace/benchmark.json"), "{}").unwrap();
+
+        let mut out = Vec::new();
+        scan_directory(&src, &dst, ComponentType::Skills, &HashCache::default(), None, &mut out).unwrap();
+
+        assert_eq!(out.len(), 1, "only SKILL.md should be scanned");
+        assert!(out[0].name.ends_with("SKILL.md"));
+        assert!(!out.iter().any(|c| c.name.contains("workspace")));
+
+        let _ = std::fs::remove_dir_all(&src);
+        let _ = std::fs::remove_dir_all(&dst);
+    }
+
+    #[test]
+    fn scan_directory_skips_ko_suffix_files() {
+        let src = unique_test_dir("scan_ko_src");
+        let dst = unique_test_dir("scan_ko_dst");
+
+        // English originals (must install) + Korean reference versions (must skip)
+        std::fs::create_dir_all(src.join("skill-b")).unwrap();
+        std::fs::write(src.join("skill-b/SKILL.md"), "english").unwrap();
+        std::fs::write(src.join("skill-b/SKILL-ko.md"), "한국어").unwrap();
+        std::fs::write(src.join("skill-b/README.md"), "readme").unwrap();
+        std::fs::write(src.join("skill-b/README-ko.md"), "리드미").unwrap();
+        // Sanity: hyphenated names that are NOT -ko should still install
+        std::fs::write(src.join("skill-b/awesome-ko-thing.md"), "keep").unwrap();
+
+        let mut out = Vec::new();
+        scan_directory(&src, &dst, ComponentType::Skills, &HashCache::default(), None, &mut out).unwrap();
+
+        let names: Vec<_> = out.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.iter().any(|n| n.ends_with("SKILL.md")));
+        assert!(names.iter().any(|n| n.ends_with("README.md")));
+        assert!(names.iter().any(|n| n.ends_with("awesome-ko-thing.md")));
+        assert!(
+            !names.iter().any(|n| n.ends_with("-ko.md")),
+            "*-ko.md files must be excluded, got {names:?}"
+        );
+
+        let _ = std::fs::remove_dir_all(&src);
+        let _ = std::fs::remove_dir_all(&dst);
+    }
+}
@@ -3,8 +3,9 @@ use std::path::Path;
 use anyhow::Result;
 use walkdir::WalkDir;
 
-use crate::app::TargetCli;
+use crate::TargetCli;
 use crate::component::{Component, ComponentType, InstallStatus};
+use crate::source::config::DirectoryMap;
 
 /// Source label applied to externally-discovered files (those present in
 /// dest_dir but not produced by any configured source).
@@ -13,9 +14,9 @@ const EXTERNAL_LABEL: &str = "external";
 /// Component types we surface external files for.
 /// Excludes Hooks (binaries), Statusline (binaries), ConfigFile (special-case).
 ///
-/// Subdirectory name is derived from `ComponentType::display_name()` to keep
-/// a single source of truth (matches `scan_components` in components.rs and
-/// the `map_to` dispatch in scanner/mod.rs).
+/// Subdirectory name comes from `DirectoryMap::dest_dir` to keep a single
+/// source of truth (matches `scan_components` in components.rs and the
+/// `map_to` dispatch in scanner/mod.rs).
 fn external_types(target_cli: TargetCli) -> &'static [ComponentType] {
     match target_cli {
         TargetCli::Claude => &[
@@ -26,7 +27,11 @@ fn external_types(target_cli: TargetCli) -> &'static [ComponentType] {
             ComponentType::Skills,
             ComponentType::OutputStyles,
         ],
-        TargetCli::Codex => &[ComponentType::Skills],
+        TargetCli::Codex | TargetCli::OpenCode => &[ComponentType::Skills],
+        // Installed rules are renamed to `.mdc`, so they'd never match a
+        // source key; Cursor's own rules are left off the list.
+        TargetCli::Cursor => &[],
+        TargetCli::Custom(_) => target_cli.custom().map_or(&[], |t| t.component_types.as_slice()),
     }
 }
 
@@ -44,7 +49,8 @@ fn includes_companion_files(component_type: &ComponentType) -> bool {
 ///
 /// `existing_keys` is the set of `(component_type, name)` keys already
 /// claimed by source-based scans. Anything in dest under a known component
-/// type directory whose key is NOT in this set is reported as External.
+/// type directory whose key is NOT in this set is reported as External,
+/// or as Orphaned when hibi's install record lists it.
 ///
 /// File-format scope per component type is decided by
 /// `includes_companion_files`: markdown-only for most types, all files for
@@ -52,17 +58,26 @@ fn includes_companion_files(component_type: &ComponentType) -> bool {
 pub(super) fn scan_externals(
     dest_dir: &Path,
     target_cli: TargetCli,
+    directories: &DirectoryMap,
     existing_keys: &HashSet<(ComponentType, String)>,
 ) -> Result<Vec<Component>> {
     let mut external = Vec::new();
+    let installed = crate::fs::installer::installed_files(dest_dir);
 
     for comp_type in external_types(target_cli) {
-        let type_dir = dest_dir.join(comp_type.display_name());
+        let type_dir = dest_dir.join(directories.dest_dir(comp_type));
         if !type_dir.exists() {
             continue;
         }
         scan_type_directory(&type_dir, comp_type, existing_keys, &mut external)?;
     }
+    // Files hibi installed itself were left behind by a component the
+    // sources dropped or renamed.
+    for component in &mut external {
+        if crate::fs::installer::record_key(dest_dir, &component.dest_path).is_some_and(|key| installed.contains(&key)) {
+            component.status = InstallStatus::Orphaned;
+        }
+    }
 
     Ok(external)
 }
@@ -173,7 +188,7 @@ mod tests {
         std::fs::write(dest.join("agents/my-custom.md"), "# custom").unwrap();
 
         let existing = HashSet::new();
-        let out = scan_externals(&dest, TargetCli::Claude, &existing).unwrap();
+        let out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &existing).unwrap();
         assert_eq!(out.len(), 1);
         assert_eq!(out[0].name, "my-custom.md");
         assert_eq!(out[0].status, InstallStatus::External);
@@ -183,6 +198,31 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dest);
     }
 
+    #[test]
+    fn files_hibi_installed_become_orphans_once_no_source_ships_them() {
+        let dir = unique_test_dir("orphans");
+        let dest = dir.join("dest");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dest.join("agents")).unwrap();
+        std::fs::write(dir.join("src/old-name.md"), "# renamed upstream").unwrap();
+        std::fs::write(dest.join("agents/mine.md"), "# user file").unwrap();
+        let installed = Component::new(
+            ComponentType::Agents, "old-name.md".into(),
+            dir.join("src/old-name.md"), dest.join("agents/old-name.md"), InstallStatus::New,
+        );
+        crate::fs::installer::install_component(&installed, &dir, &dest, &[], None).unwrap();
+
+        let mut out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &HashSet::new()).unwrap();
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+        let statuses: Vec<_> = out.iter().map(|c| (c.name.as_str(), c.status.clone())).collect();
+        assert_eq!(statuses, [("mine.md", InstallStatus::External), ("old-name.md", InstallStatus::Orphaned)]);
+
+        // Removing the orphan drops it from the record too.
+        crate::fs::installer::remove_component(&out[1], &dest).unwrap();
+        assert!(crate::fs::installer::installed_files(&dest).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn skips_files_already_claimed_by_source() {
         let dest = unique_test_dir("skip_claimed");
@@ -193,7 +233,7 @@ mod tests {
         let mut existing = HashSet::new();
         existing.insert((ComponentType::Commands, "managed.md".to_string()));
 
-        let out = scan_externals(&dest, TargetCli::Claude, &existing).unwrap();
+        let out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &existing).unwrap();
         let names: Vec<_> = out.iter().map(|c| c.name.as_str()).collect();
         assert_eq!(names, vec!["extra.md"]);
 
@@ -209,7 +249,7 @@ mod tests {
         std::fs::write(dest.join("agents/notes.txt"), "txt").unwrap();
         std::fs::write(dest.join("agents/data.json"), "{}").unwrap();
 
-        let out = scan_externals(&dest, TargetCli::Claude, &HashSet::new()).unwrap();
+        let out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &HashSet::new()).unwrap();
         let names: Vec<_> = out.iter().map(|c| c.name.replace('\\', "/")).collect();
         assert_eq!(names, vec!["keep.md"]);
 
@@ -227,7 +267,7 @@ mod tests {
         std::fs::write(dest.join("skills/my-skill/config.toml"), "key='v'").unwrap();
         std::fs::write(dest.join("skills/my-skill/references/foo.md"), "ref").unwrap();
 
-        let out = scan_externals(&dest, TargetCli::Claude, &HashSet::new()).unwrap();
+        let out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &HashSet::new()).unwrap();
         let mut names: Vec<_> = out.iter().map(|c| c.name.replace('\\', "/")).collect();
         names.sort();
 
@@ -254,7 +294,7 @@ mod tests {
         std::fs::write(dest.join("skills/x/SKILL.md"), "ok").unwrap();
         std::fs::write(dest.join("skills/x/workspace/iter/log.md"), "junk").unwrap();
 
-        let out = scan_externals(&dest, TargetCli::Claude, &HashSet::new()).unwrap();
+        let out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &HashSet::new()).unwrap();
         assert!(out.iter().all(|c| !c.name.contains("workspace")));
         assert_eq!(out.len(), 1);
 
@@ -264,7 +304,7 @@ mod tests {
     #[test]
     fn empty_dest_dir_returns_nothing() {
         let dest = unique_test_dir("empty");
-        let out = scan_externals(&dest, TargetCli::Claude, &HashSet::new()).unwrap();
+        let out = scan_externals(&dest, TargetCli::Claude, &DirectoryMap::default(), &HashSet::new()).unwrap();
         assert!(out.is_empty());
         let _ = std::fs::remove_dir_all(&dest);
     }
@@ -277,7 +317,7 @@ mod tests {
         std::fs::write(dest.join("agents/leak.md"), "should not appear").unwrap();
         std::fs::write(dest.join("skills/s/SKILL.md"), "ok").unwrap();
 
-        let out = scan_externals(&dest, TargetCli::Codex, &HashSet::new()).unwrap();
+        let out = scan_externals(&dest, TargetCli::Codex, &DirectoryMap::default(), &HashSet::new()).unwrap();
         assert_eq!(out.len(), 1);
         assert_eq!(out[0].component_type, ComponentType::Skills);
 
@@ -0,0 +1,158 @@
+//! Content hashes of compared files, kept in `<dest_dir>/.hibi/cache.json`
+//! so a rescan only reads files whose size or mtime changed since the last
+//! one. Hashes are taken over the content with `\r` stripped, matching the
+//! line-ending-insensitive comparison in `determine_status`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// File name inside the installer state dir (see `state`).
+pub const CACHE_FILE: &str = "cache.json";
+
+/// Bumped when the hashed form changes, so old caches are ignored.
+const CACHE_VERSION: u32 = 1;
+
+/// Files modified this recently aren't cached: another write within the
+/// same mtime tick would leave size and mtime unchanged.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct Entry {
+    size: u64,
+    mtime_ns: u64,
+    hash: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<PathBuf, Entry>,
+}
+
+/// Hash cache for one scan. Lookups and inserts are thread-safe, so the
+/// parallel scan shares one instance. The default has no file: hashes are
+/// computed every time and never saved.
+#[derive(Default)]
+pub struct HashCache {
+    path: Option<PathBuf>,
+    loaded: HashMap<PathBuf, Entry>,
+    /// Entries for every file hashed during this scan; only these are saved,
+    /// so files that went away drop out of the cache.
+    used: Mutex<HashMap<PathBuf, Entry>>,
+}
+
+/// Whether a file last modified at `modified` is old enough that any
+/// further write will show up as a newer mtime.
+pub(crate) fn is_settled(modified: SystemTime) -> bool {
+    SystemTime::now().duration_since(modified).is_ok_and(|age| age >= RACY_WINDOW)
+}
+
+fn mtime_ns(metadata: &std::fs::Metadata) -> Option<u64> {
+    let mtime = metadata.modified().ok()?;
+    Some(mtime.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64)
+}
+
+/// blake3 of the file at `path` with carriage returns dropped, so CRLF
+/// and LF copies hash the same.
+pub(crate) fn hash_content(path: &Path) -> Result<String> {
+    let content = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut hasher = blake3::Hasher::new();
+    for chunk in content.split(|&b| b == b'\r') {
+        hasher.update(chunk);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+impl HashCache {
+    /// The cache saved under `dest_dir`; empty when missing, unreadable or
+    /// from another cache version.
+    pub fn load(dest_dir: &Path) -> Self {
+        let path = crate::fs::state::state_dir(dest_dir).join(CACHE_FILE);
+        let loaded = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.version == CACHE_VERSION)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+        Self { path: Some(path), loaded, used: Mutex::default() }
+    }
+
+    /// Hash of `path`'s content (line endings normalized), from the cache
+    /// when its size and mtime still match.
+    pub fn hash(&self, path: &Path) -> Result<String> {
+        let metadata = std::fs::metadata(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let size = metadata.len();
+        let mtime = mtime_ns(&metadata);
+
+        if let Some(entry) = self.loaded.get(path)
+            && Some(entry.mtime_ns) == mtime
+            && entry.size == size
+        {
+            self.used.lock().unwrap_or_else(|e| e.into_inner()).insert(path.to_path_buf(), entry.clone());
+            return Ok(entry.hash.clone());
+        }
+
+        let hash = hash_content(path)?;
+        let settled = metadata.modified().is_ok_and(is_settled);
+        if let (Some(mtime_ns), true) = (mtime, settled) {
+            let entry = Entry { size, mtime_ns, hash: hash.clone() };
+            self.used.lock().unwrap_or_else(|e| e.into_inner()).insert(path.to_path_buf(), entry);
+        }
+        Ok(hash)
+    }
+
+    /// Write the entries used by this scan back to disk. A no-op for the
+    /// default (file-less) cache and when nothing changed.
+    pub fn save(self) -> Result<()> {
+        let Some(path) = self.path else { return Ok(()) };
+        let entries = self.used.into_inner().unwrap_or_else(|e| e.into_inner());
+        if entries == self.loaded {
+            return Ok(());
+        }
+        let state_dir = path.parent().unwrap_or(Path::new("."));
+        if !state_dir.parent().is_some_and(Path::exists) {
+            return Ok(()); // Nothing installed yet; don't create the dest dir for a cache.
+        }
+        std::fs::create_dir_all(state_dir)?;
+        let file = CacheFile { version: CACHE_VERSION, entries };
+        std::fs::write(&path, serde_json::to_string(&file)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn cached_hashes_follow_size_and_mtime() {
+        let dir = temp_dir("hash_cache");
+        let file = dir.join("a.md");
+        std::fs::write(&file, "one\r\ntwo\n").unwrap();
+        // Age the file past the racy window so its hash is cacheable.
+        let old = SystemTime::now() - Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&file).unwrap().set_modified(old).unwrap();
+
+        let cache = HashCache::load(&dir);
+        let hash = cache.hash(&file).unwrap();
+        assert_eq!(hash, blake3::hash(b"one\ntwo\n").to_hex().to_string(), "line endings normalized");
+        cache.save().unwrap();
+
+        // A stale entry with matching metadata is trusted without reading...
+        let mut saved: CacheFile = serde_json::from_str(&std::fs::read_to_string(crate::fs::state::state_dir(&dir).join(CACHE_FILE)).unwrap()).unwrap();
+        saved.entries.get_mut(&file).unwrap().hash = "cached".to_string();
+        std::fs::write(crate::fs::state::state_dir(&dir).join(CACHE_FILE), serde_json::to_string(&saved).unwrap()).unwrap();
+        assert_eq!(HashCache::load(&dir).hash(&file).unwrap(), "cached");
+
+        // ...and a changed file is hashed again.
+        std::fs::write(&file, "three\n").unwrap();
+        assert_eq!(HashCache::load(&dir).hash(&file).unwrap(), blake3::hash(b"three\n").to_hex().to_string());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
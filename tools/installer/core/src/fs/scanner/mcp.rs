@@ -0,0 +1,283 @@
+use std::path::Path;
+use anyhow::Result;
+
+use crate::TargetCli;
+use crate::mcp::{McpCatalog, McpHealth, McpScope, McpServer, McpStatus};
+use crate::fs::create_cli_command;
+use super::validation::validate_mcp_server;
+
+/// Timeout for MCP server scan (seconds).
+/// Health checks across multiple servers can be slow; 30s is a reasonable ceiling.
+const MCP_SCAN_TIMEOUT_SECS: u64 = 30;
+
+/// Timeout for a single `npm view` registry lookup (seconds).
+const NPM_VIEW_TIMEOUT_SECS: u64 = 10;
+
+/// Latest published version of an npm package, via `npm view <pkg> version`.
+pub fn latest_npm_version(package: &str) -> Result<String> {
+    let mut cmd = crate::fs::create_tool_command("npm");
+    cmd.args(["view", package, "version"]);
+
+    let out = crate::fs::run_with_timeout(&mut cmd, NPM_VIEW_TIMEOUT_SECS)?;
+    if !out.status.success() {
+        anyhow::bail!("npm view {} failed: {}", package, String::from_utf8_lossy(&out.stderr).trim());
+    }
+    let version = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if version.is_empty() {
+        anyhow::bail!("npm view {} returned no version", package);
+    }
+    Ok(version)
+}
+
+/// Scan MCP catalog and mark each server as installed or not.
+pub(super) fn scan_with_installed(source_dir: &Path, installed: &[String]) -> Result<Vec<McpServer>> {
+    let catalog_path = source_dir.join("mcps/mcps.yaml");
+    if !catalog_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&catalog_path)?;
+    let catalog: McpCatalog = serde_yaml_bw::from_str(&content)?;
+
+    let servers = catalog
+        .servers
+        .into_iter()
+        .filter_map(|def| {
+            if validate_mcp_server(&def).is_some() {
+                return None;
+            }
+            let status = if installed.contains(&def.name) {
+                McpStatus::Installed
+            } else {
+                McpStatus::NotInstalled
+            };
+            Some(McpServer::new(def, status))
+        })
+        .collect();
+
+    Ok(servers)
+}
+
+/// Query the Claude CLI for installed MCP servers.
+pub(super) fn get_installed_claude_servers() -> (Vec<String>, Option<String>) {
+    let mut cmd = create_cli_command(TargetCli::Claude);
+    cmd.args(["mcp", "list"]);
+
+    match crate::fs::run_with_timeout(&mut cmd, MCP_SCAN_TIMEOUT_SECS) {
+        Ok(out) if out.status.success() => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let servers = stdout
+                .lines()
+                .filter(|line| {
+                    let trimmed = line.trim();
+                    !trimmed.is_empty()
+                        && !trimmed.starts_with("Checking")
+                        && trimmed.contains(':')
+                })
+                .filter_map(|line| {
+                    let name = line.trim().split(':').next()?.trim();
+                    if name.is_empty() { return None; }
+                    Some(name.to_string())
+                })
+                .collect();
+            (servers, None)
+        }
+        Ok(ref out) => (Vec::new(), format_scan_error("Claude", out)),
+        Err(e) => (Vec::new(), format_spawn_error(e)),
+    }
+}
+
+/// Claude MCP servers whose health check failed in `claude mcp list`.
+pub fn failing_claude_servers() -> Result<Vec<String>> {
+    Ok(claude_server_health()?
+        .into_iter()
+        .filter(|(_, health)| *health == McpHealth::Failed)
+        .map(|(name, _)| name)
+        .collect())
+}
+
+/// Health of every Claude MCP server, as reported by `claude mcp list`.
+pub fn claude_server_health() -> Result<Vec<(String, McpHealth)>> {
+    let mut cmd = create_cli_command(TargetCli::Claude);
+    cmd.args(["mcp", "list"]);
+
+    let out = crate::fs::run_with_timeout(&mut cmd, MCP_SCAN_TIMEOUT_SECS)?;
+    if !out.status.success() {
+        anyhow::bail!("claude mcp list failed: {}", String::from_utf8_lossy(&out.stderr).trim());
+    }
+    Ok(parse_server_health(&String::from_utf8_lossy(&out.stdout)))
+}
+
+/// Names and health from `name: command - ✓ Connected` /
+/// `name: command - ✗ Failed to connect` lines. Lines with neither
+/// marker (headers, servers still pending auth) are skipped.
+fn parse_server_health(stdout: &str) -> Vec<(String, McpHealth)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let health = if line.contains('✗') || line.contains("Failed") {
+                McpHealth::Failed
+            } else if line.contains('✓') || line.contains("Connected") {
+                McpHealth::Connected
+            } else {
+                return None;
+            };
+            let name = line.trim().split(':').next()?.trim();
+            (!name.is_empty() && !name.contains(' ')).then(|| (name.to_string(), health))
+        })
+        .collect()
+}
+
+/// Query the Codex CLI for installed MCP servers.
+pub(super) fn get_installed_codex_servers() -> (Vec<String>, Option<String>) {
+    let mut cmd = create_cli_command(TargetCli::Codex);
+    cmd.args(["mcp", "list"]);
+
+    match crate::fs::run_with_timeout(&mut cmd, MCP_SCAN_TIMEOUT_SECS) {
+        Ok(out) if out.status.success() => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let servers = stdout
+                .lines()
+                .filter_map(|line| {
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        return None;
+                    }
+                    // Skip header lines (contain "Name" or "Command" or "Url" columns)
+                    if trimmed.starts_with("Name") && (trimmed.contains("Command") || trimmed.contains("Url")) {
+                        return None;
+                    }
+                    let name = trimmed.split_whitespace().next()?.trim();
+                    if name.is_empty() || name == "Name" {
+                        return None;
+                    }
+                    Some(name.to_string())
+                })
+                .collect();
+            (servers, None)
+        }
+        Ok(ref out) => (Vec::new(), format_scan_error("Codex", out)),
+        Err(e) => (Vec::new(), format_spawn_error(e)),
+    }
+}
+
+/// Query a custom target through its `mcp_list` template, taking the first
+/// word of each output line as a server name. Without the template nothing
+/// counts as installed.
+pub(super) fn get_installed_custom_servers(target_cli: TargetCli) -> (Vec<String>, Option<String>) {
+    let Some(template) = target_cli.custom().and_then(|t| t.mcp.as_ref()?.list.as_deref()) else {
+        return (Vec::new(), None);
+    };
+    let args = match crate::fs::installer::expand_mcp_template(template, &Default::default()) {
+        Ok(args) => args,
+        Err(e) => return (Vec::new(), Some(format!("MCP scan failed: {:#}", e))),
+    };
+    let mut cmd = create_cli_command(target_cli);
+    cmd.args(args);
+
+    match crate::fs::run_with_timeout(&mut cmd, MCP_SCAN_TIMEOUT_SECS) {
+        Ok(out) if out.status.success() => {
+            let servers = String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .filter_map(|line| line.split_whitespace().next())
+                .map(|name| name.trim_end_matches(':').to_string())
+                .filter(|name| !name.is_empty())
+                .collect();
+            (servers, None)
+        }
+        Ok(ref out) => (Vec::new(), format_scan_error(target_cli.display_name(), out)),
+        Err(e) => (Vec::new(), format_spawn_error(e)),
+    }
+}
+
+/// Servers listed in the config files of a target without `mcp list`
+/// (Cursor's `mcp.json`, OpenCode's `opencode.json`), with the scope of
+/// the file: the project's (when there is one) and the global one.
+pub(super) fn get_configured_servers(
+    target_cli: TargetCli,
+    project_path: Option<&Path>,
+) -> (Vec<(String, McpScope)>, Option<String>) {
+    let project = project_path.map(|p| p.to_string_lossy().into_owned());
+    let mut files = Vec::new();
+    if project.is_some() {
+        files.push((McpScope::Local, project.as_deref()));
+    }
+    files.push((McpScope::User, None));
+
+    let listed: Result<Vec<(McpScope, std::path::PathBuf)>> = files.into_iter()
+        .map(|(scope, project)| Ok((scope, crate::fs::installer::mcp_config_path(target_cli, scope, project)?)))
+        .collect();
+    match listed.and_then(|files| servers_by_scope(target_cli, &files)) {
+        Ok(servers) => (servers, None),
+        Err(e) => (Vec::new(), Some(format!("MCP scan failed: {:#}", e))),
+    }
+}
+
+/// Server names in each of `files`, with the file's scope. A server in
+/// several files is listed once, with the first file's scope.
+fn servers_by_scope(target_cli: TargetCli, files: &[(McpScope, std::path::PathBuf)]) -> Result<Vec<(String, McpScope)>> {
+    let mut servers: Vec<(String, McpScope)> = Vec::new();
+    for (scope, path) in files {
+        for name in crate::fs::installer::configured_mcp_servers(target_cli, path)? {
+            if !servers.iter().any(|(n, _)| *n == name) {
+                servers.push((name, *scope));
+            }
+        }
+    }
+    Ok(servers)
+}
+
+/// Format a warning when `mcp list` exits with a non-success status code.
+fn format_scan_error(cli_label: &str, result: &std::process::Output) -> Option<String> {
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    let code = result.status.code().map(|c| c.to_string()).unwrap_or_else(|| "unknown".to_string());
+    Some(format!("MCP scan: {} CLI exited with code {}: {}", cli_label, code, stderr.trim()))
+}
+
+/// Format a warning when spawning/running the CLI command fails entirely.
+fn format_spawn_error(e: anyhow::Error) -> Option<String> {
+    let err_str = e.to_string();
+    let hint = if err_str.contains("timed out") {
+        " (health check timeout)"
+    } else if err_str.contains("os error 2") || err_str.contains("not found") || err_str.contains("The system cannot find") {
+        " (CLI not found in PATH)"
+    } else {
+        ""
+    };
+    Some(format!("MCP scan failed: {}{}", err_str, hint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn parse_server_health_reads_both_markers() {
+        let stdout = "Checking MCP server health...\n\n\
+            context7: npx -y @upstash/context7-mcp - ✓ Connected\n\
+            memory: npx -y @modelcontextprotocol/server-memory - ✗ Failed to connect\n";
+        assert_eq!(parse_server_health(stdout), vec![
+            ("context7".to_string(), McpHealth::Connected),
+            ("memory".to_string(), McpHealth::Failed),
+        ]);
+    }
+
+    #[test]
+    fn configured_servers_carry_the_scope_of_their_file() {
+        let dir = temp_dir("mcp_scopes");
+        let (project, global) = (dir.join("project.json"), dir.join("global.json"));
+        std::fs::write(&project, r#"{"mcpServers": {"memory": {}, "both": {}}}"#).unwrap();
+        std::fs::write(&global, r#"{"mcpServers": {"both": {}, "context7": {}}}"#).unwrap();
+
+        let files = [(McpScope::Local, project), (McpScope::User, global)];
+        let servers = servers_by_scope(TargetCli::Cursor, &files).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(servers, vec![
+            ("both".to_string(), McpScope::Local),
+            ("memory".to_string(), McpScope::Local),
+            ("context7".to_string(), McpScope::User),
+        ]);
+    }
+}
@@ -1,19 +1,27 @@
 mod validation;
 mod components;
+mod hash_cache;
 mod external;
 mod mcp;
 mod plugin;
 
+pub use components::component_status;
+pub use hash_cache::CACHE_FILE as HASH_CACHE_FILE;
+pub(crate) use hash_cache::{hash_content, is_settled};
+pub(crate) use components::glob_match;
+pub use mcp::{claude_server_health, failing_claude_servers, latest_npm_version};
+
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::path::Path;
 use anyhow::Result;
 
-use crate::app::TargetCli;
+use crate::TargetCli;
 use crate::component::{Component, ComponentType};
 use crate::mcp::McpServer;
 use crate::plugin::Plugin;
-use crate::source::ResolvedSource;
+use crate::source::{ResolvedSource, SourceKind};
+use crate::source::config::{DirectoryMap, LOCAL_OVERLAY_NAME};
 
 /// Merge items from multiple sources using a last-wins strategy.
 ///
@@ -64,6 +72,14 @@ fn parse_map_to(map_to: &str) -> Option<ComponentType> {
     }
 }
 
+/// How a scan runs, as opposed to what it looks at.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScanOptions {
+    /// Threads comparing source files with their installed copies
+    /// (`scan_threads:` in sources.yaml); `None` uses every core.
+    pub threads: Option<usize>,
+}
+
 /// Scan components from all sources. Later sources override earlier ones.
 /// Sources with `map_to` get flat-scanned as a single component type.
 ///
@@ -71,30 +87,50 @@ fn parse_map_to(map_to: &str) -> Option<ComponentType> {
 /// files that no source produces and reports them with
 /// `InstallStatus::External` so users can see (and optionally remove)
 /// orphaned or user-added files. Format scope per type is decided by the
-/// scanner (markdown-only for most, all files for Skills).
+/// scanner (markdown-only for most, all files for Skills). Folder names
+/// follow the `directories:` mapping; the bundled source keeps its own
+/// source layout and only the dest folders are remapped.
 pub fn scan_all_sources(
     sources: &[ResolvedSource],
     dest_dir: &Path,
     target_cli: TargetCli,
+    directories: &DirectoryMap,
+    options: ScanOptions,
 ) -> Result<Vec<Component>> {
+    let bundled_dirs = directories.dest_only();
+    let cache = hash_cache::HashCache::load(dest_dir);
     let mut merged = merge_scanned(
         sources,
         |source| {
+            let directories = if source.kind == SourceKind::Bundled { &bundled_dirs } else { directories };
             if let Some(map_to) = source.map_to.as_deref() {
                 if let Some(comp_type) = parse_map_to(map_to) {
-                    let type_dir = comp_type.display_name().to_lowercase();
-                    let dest = dest_dir.join(&type_dir);
-                    components::scan_flat(&source.path, &dest, comp_type)
+                    let dest = dest_dir.join(directories.dest_dir(&comp_type));
+                    components::scan_flat(&source.path, &dest, comp_type, &cache, options.threads)
                 } else {
                     Ok(Vec::new()) // Unknown map_to value, skip silently
                 }
             } else {
-                components::scan_components(&source.path, dest_dir, target_cli)
+                components::scan_components(&source.path, dest_dir, target_cli, directories, &cache, options.threads)
             }
         },
         |c| format!("{}/{}", c.component_type.display_name(), c.name.replace('\\', "/")),
         |c, label| c.source_name = label.to_string(),
     )?;
+    // A cache that can't be written only costs the next scan some time.
+    let _ = cache.save();
+    layer_overlay_settings(&mut merged, sources, dest_dir)?;
+
+    // Components another machine's `hosts:` filter claims start unselected.
+    for c in &mut merged {
+        c.hosts = match &c.hook_config {
+            Some(config) => config.hosts.clone(),
+            None => crate::fs::hosts::read_hosts(&c.source_path),
+        };
+        if !c.for_this_host() {
+            c.selected = false;
+        }
+    }
 
     // Reuse the same key shape as scan_directory uses for component name
     // (relative path with forward slashes).
@@ -103,29 +139,63 @@ pub fn scan_all_sources(
         .map(|c| (c.component_type.clone(), c.name.replace('\\', "/")))
         .collect();
 
-    let externals = external::scan_externals(dest_dir, target_cli, &existing_keys)?;
+    let externals = external::scan_externals(dest_dir, target_cli, directories, &existing_keys)?;
     merged.extend(externals);
 
     Ok(merged)
 }
 
+/// The overlay's settings.json is merged into the one it would replace,
+/// the way an install merges it into dest, so the overlay only needs the
+/// keys that differ on this machine.
+fn layer_overlay_settings(components: &mut [Component], sources: &[ResolvedSource], dest_dir: &Path) -> Result<()> {
+    let overlay = components.iter_mut().find(|c| {
+        c.component_type == ComponentType::ConfigFile
+            && c.name == "settings.json"
+            && c.source_name == LOCAL_OVERLAY_NAME
+    });
+    let Some(overlay) = overlay else { return Ok(()) };
+    let base = sources.iter()
+        .rev()
+        .filter(|s| s.label != LOCAL_OVERLAY_NAME && s.map_to.is_none())
+        .map(|s| s.path.join("settings.json"))
+        .find(|path| path.is_file());
+    if let Some(base) = base {
+        overlay.source_path = crate::fs::installer::layer_settings(&base, &overlay.source_path, dest_dir)?;
+    }
+    Ok(())
+}
+
 /// Scan MCP servers from all sources. Later sources override earlier ones.
 /// CLI command for installed servers runs only once (not per-source).
+/// `project_path` is the project whose config file holds local-scope
+/// servers, for targets that keep them in one.
 pub fn scan_all_mcp_sources(
     sources: &[ResolvedSource],
     target_cli: TargetCli,
+    project_path: Option<&Path>,
 ) -> Result<(Vec<McpServer>, Option<String>)> {
+    let mut scopes = Vec::new();
     let (installed, warning) = match target_cli {
         TargetCli::Claude => mcp::get_installed_claude_servers(),
         TargetCli::Codex => mcp::get_installed_codex_servers(),
+        TargetCli::Cursor | TargetCli::OpenCode => {
+            let (listed, warning) = mcp::get_configured_servers(target_cli, project_path);
+            scopes = listed;
+            (scopes.iter().map(|(name, _)| name.clone()).collect(), warning)
+        }
+        TargetCli::Custom(_) => mcp::get_installed_custom_servers(target_cli),
     };
 
-    let servers = merge_scanned(
+    let mut servers = merge_scanned(
         sources,
         |source| mcp::scan_with_installed(&source.path, &installed),
         |s| s.def.name.clone(),
         |s, label| s.source_name = label.to_string(),
     )?;
+    for server in &mut servers {
+        server.scope = scopes.iter().find(|(name, _)| *name == server.def.name).map(|&(_, scope)| scope);
+    }
 
     Ok((servers, warning))
 }
@@ -153,6 +223,40 @@ mod tests {
         dir
     }
 
+    #[test]
+    fn directories_remap_user_source_folders_only() {
+        use crate::source::config::DirectoryEntry;
+
+        let bundled = unique_dir("dirs_bundled");
+        let user = unique_dir("dirs_user");
+        let dest = unique_dir("dirs_dest");
+        std::fs::create_dir_all(bundled.join("contexts")).unwrap();
+        std::fs::write(bundled.join("contexts/base.md"), "b").unwrap();
+        std::fs::create_dir_all(user.join("prompts")).unwrap();
+        std::fs::write(user.join("prompts/mine.md"), "m").unwrap();
+
+        let entries = [("contexts".to_string(), DirectoryEntry::Source("prompts".into()))].into();
+        let (directories, _) = DirectoryMap::from_entries(&entries);
+        let mut user_source = ResolvedSource::bundled(&user);
+        user_source.kind = SourceKind::Local;
+        user_source.label = "dotfiles".into();
+        let sources = vec![ResolvedSource::bundled(&bundled), user_source];
+
+        let components = scan_all_sources(&sources, &dest, TargetCli::Claude, &directories, ScanOptions::default()).unwrap();
+        let contexts: Vec<_> = components.iter()
+            .filter(|c| c.component_type == ComponentType::Contexts)
+            .map(|c| (c.name.as_str(), c.dest_path.clone()))
+            .collect();
+        assert_eq!(contexts, vec![
+            ("base.md", dest.join("contexts/base.md")),
+            ("mine.md", dest.join("contexts/mine.md")),
+        ]);
+
+        for dir in [bundled, user, dest] {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+
     #[test]
     fn merges_source_components_with_external_dest_files() {
         // Layout:
@@ -179,7 +283,7 @@ mod tests {
         std::fs::write(dest.join("skills/my-tool/data.json"), "{}").unwrap();
 
         let sources = vec![ResolvedSource::bundled(&source)];
-        let components = scan_all_sources(&sources, &dest, TargetCli::Claude).unwrap();
+        let components = scan_all_sources(&sources, &dest, TargetCli::Claude, &DirectoryMap::default(), ScanOptions::default()).unwrap();
 
         // Index by (type, name) for assertion.
         let by_key = |t: ComponentType, n: &str| -> Option<&Component> {
@@ -236,7 +340,7 @@ mod tests {
         std::fs::write(dest.join("commands/hello.md"), "v1").unwrap();
 
         let sources = vec![ResolvedSource::bundled(&source)];
-        let components = scan_all_sources(&sources, &dest, TargetCli::Claude).unwrap();
+        let components = scan_all_sources(&sources, &dest, TargetCli::Claude, &DirectoryMap::default(), ScanOptions::default()).unwrap();
 
         let hellos: Vec<_> = components
             .iter()
@@ -58,7 +58,7 @@ fn get_installed_plugins() -> Vec<String> {
         Err(_) => return Vec::new(),
     };
 
-    let settings: Value = match serde_json::from_str(&content) {
+    let settings: Value = match crate::fs::jsonc::parse(&content) {
         Ok(s) => s,
         Err(_) => return Vec::new(),
     };
@@ -20,6 +20,14 @@ pub(super) fn is_safe_command(s: &str) -> bool {
     !s.is_empty() && !s.contains(['&', '|', '>', '<', ';', '`', '$', '(', ')', '%', '^', '!'])
 }
 
+/// Validate a pinned package version: semver-ish characters only, since it
+/// is spliced into the install command.
+pub(super) fn is_safe_version(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= 64
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+}
+
 /// Validate an MCP server definition from YAML.
 /// Returns an error description if invalid, None if valid.
 pub(super) fn validate_mcp_server(server: &McpServerDef) -> Option<String> {
@@ -48,6 +56,15 @@ pub(super) fn validate_mcp_server(server: &McpServerDef) -> Option<String> {
         }
     }
 
+    if let Some(version) = &server.version
+        && !is_safe_version(version)
+    {
+        return Some(format!(
+            "MCP server '{}': version must be alphanumeric/./-/+, got '{}'",
+            server.name, version
+        ));
+    }
+
     None
 }
 
@@ -149,6 +166,8 @@ mod tests {
             url: None,
             category: "docs".to_string(),
             env: vec![],
+            version: None,
+            env_file: None,
         };
         assert!(validate_mcp_server(&def).is_none());
     }
@@ -163,6 +182,8 @@ mod tests {
             url: None,
             category: "test".to_string(),
             env: vec![],
+            version: None,
+            env_file: None,
         };
         assert!(validate_mcp_server(&def).is_some());
     }
@@ -177,6 +198,8 @@ mod tests {
             url: Some("file:///etc/passwd".to_string()),
             category: "test".to_string(),
             env: vec![],
+            version: None,
+            env_file: None,
         };
         assert!(validate_mcp_server(&def).is_some());
     }
@@ -191,8 +214,27 @@ mod tests {
             url: None,
             category: "test".to_string(),
             env: vec![],
+            version: None,
+            env_file: None,
+        };
+        assert!(validate_mcp_server(&def).is_some());
+    }
+
+    #[test]
+    fn test_validate_mcp_server_bad_version() {
+        let def = McpServerDef {
+            name: "test".to_string(),
+            description: "Test".to_string(),
+            r#type: None,
+            command: Some("npx -y pkg".to_string()),
+            url: None,
+            category: "test".to_string(),
+            env: vec![],
+            version: Some("1.0 --evil".to_string()),
+            env_file: None,
         };
         assert!(validate_mcp_server(&def).is_some());
+        assert!(is_safe_version("1.2.3-beta.1"));
     }
 
     #[test]
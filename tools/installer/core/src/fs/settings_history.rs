@@ -0,0 +1,138 @@
+//! History of settings.json, built from the copies kept in backup
+//! snapshots (see `backup`). Each version can be diffed against the one
+//! before it, and a past version's installer-managed sections can be put
+//! back without touching the user's own keys.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::fs::backup::{list_snapshots, Snapshot};
+use crate::fs::diff::compare_files;
+use crate::fs::installer::MANAGED_SETTINGS_KEYS;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// One version of settings.json: the live file or a snapshot's copy.
+#[derive(Clone, Debug)]
+pub struct SettingsVersion {
+    /// Snapshot name (`YYYYMMDD-HHMMSS`), or `None` for the live file.
+    pub snapshot: Option<String>,
+    pub path: PathBuf,
+}
+
+impl SettingsVersion {
+    /// `current`, or when the snapshot was taken in local time.
+    pub fn label(&self) -> String {
+        self.snapshot.as_deref().map_or_else(|| "current".to_string(), crate::local_time::snapshot_display)
+    }
+}
+
+/// The live settings.json (if present) followed by every snapshot that
+/// holds a copy of it, newest first.
+pub fn settings_versions(dest_dir: &Path) -> Result<Vec<SettingsVersion>> {
+    let live = dest_dir.join(SETTINGS_FILE);
+    let mut versions: Vec<SettingsVersion> = live.is_file()
+        .then_some(SettingsVersion { snapshot: None, path: live })
+        .into_iter()
+        .collect();
+    for snapshot in list_snapshots(dest_dir)? {
+        if snapshot.files.iter().any(|f| f == Path::new(SETTINGS_FILE)) {
+            versions.push(SettingsVersion {
+                path: snapshot.dir.join(SETTINGS_FILE),
+                snapshot: Some(snapshot.name),
+            });
+        }
+    }
+    Ok(versions)
+}
+
+/// Diff from the version before `versions[index]` to it. The oldest
+/// version has nothing to compare with and is shown whole.
+pub fn version_diff(versions: &[SettingsVersion], index: usize) -> Result<String> {
+    let version = versions.get(index).context("No such settings version")?;
+    match versions.get(index + 1) {
+        Some(previous) => compare_files(&version.path, &previous.path),
+        None => {
+            let content = std::fs::read_to_string(&version.path)
+                .with_context(|| format!("Failed to read {}", version.path.display()))?;
+            Ok(format!("=== {} (oldest kept version) ===\n\n{}", version.label(), content))
+        }
+    }
+}
+
+/// Put the managed sections (hooks, output style, statusline) of the
+/// settings.json at `from` back into the live file: keys present there are
+/// copied, keys absent there are removed. Every other key is left alone.
+/// The live file is backed up first. Returns the keys that changed.
+pub fn restore_managed_sections(dest_dir: &Path, from: &Path) -> Result<Vec<String>> {
+    let live = dest_dir.join(SETTINGS_FILE);
+    let old = read_json(from)?;
+    let mut current = if live.is_file() { read_json(&live)? } else { Value::Object(Default::default()) };
+    let map = current.as_object_mut()
+        .with_context(|| format!("{} is not a JSON object", live.display()))?;
+
+    let mut changed = Vec::new();
+    for key in MANAGED_SETTINGS_KEYS {
+        let wanted = old.get(key);
+        if map.get(key) == wanted {
+            continue;
+        }
+        match wanted {
+            Some(value) => { map.insert(key.to_string(), value.clone()); }
+            None => { map.remove(key); }
+        }
+        changed.push(key.to_string());
+    }
+
+    if !changed.is_empty() {
+        crate::fs::installer::validate_settings(&current, &live)?;
+        Snapshot::new(dest_dir).save(&live)?;
+        std::fs::write(&live, serde_json::to_string_pretty(&current)?)
+            .with_context(|| format!("Failed to write {}", live.display()))?;
+    }
+    Ok(changed)
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    crate::fs::jsonc::parse(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::fs::backup::backup_root;
+
+    #[test]
+    fn history_lists_snapshots_and_restores_only_managed_keys() {
+        let dest = temp_dir("settings_history");
+        let old_dir = backup_root(&dest).join("20260101-000000");
+        std::fs::create_dir_all(&old_dir).unwrap();
+        std::fs::write(old_dir.join(SETTINGS_FILE), r#"{"model": "old", "outputStyle": "terse"}"#).unwrap();
+        std::fs::write(
+            dest.join(SETTINGS_FILE),
+            r#"{"model": "mine", "hooks": {"Stop": []}, "outputStyle": "verbose"}"#,
+        ).unwrap();
+
+        let versions = settings_versions(&dest).unwrap();
+        let snapshots: Vec<Option<&str>> = versions.iter().map(|v| v.snapshot.as_deref()).collect();
+        assert_eq!(snapshots, [None, Some("20260101-000000")]);
+        assert_eq!(versions[0].label(), "current");
+        let diff = version_diff(&versions, 0).unwrap();
+        assert!(diff.contains("-{\"model\": \"old\"") && diff.contains("+{\"model\": \"mine\""));
+        assert!(version_diff(&versions, 1).unwrap().contains("oldest kept version"));
+
+        let changed = restore_managed_sections(&dest, &versions[1].path).unwrap();
+        assert_eq!(changed, ["hooks", "outputStyle"]);
+        let live = read_json(&dest.join(SETTINGS_FILE)).unwrap();
+        assert_eq!(live, serde_json::json!({"model": "mine", "outputStyle": "terse"}));
+        // The pre-restore file was backed up, so it shows up in the history.
+        assert_eq!(settings_versions(&dest).unwrap().len(), 3);
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+}
@@ -0,0 +1,302 @@
+//! Installer-owned state inside a CLI's config dir.
+//!
+//! Everything the installer writes for its own bookkeeping lives under one
+//! `<dest_dir>/.hibi/` root (e.g. `~/.claude/.hibi/`):
+//!
+//! ```text
+//! .hibi/
+//!   backups/            pre-install snapshots and the undo journal
+//!   base/               files as last installed, to merge local edits
+//!   cache.json          content hashes of compared files
+//!   installed.yaml      files and settings.json entries hibi installed
+//!   layered-settings.json  settings.json with the source.local one merged in
+//!   packs.yaml          per-file manifest of installed skill packs
+//!   pending.yaml        installs queued while offline
+//!   queue.yaml          rest of an install batch that didn't finish
+//!   settings-keys.yaml  settings.json key ownership overrides
+//! ```
+//!
+//! Logs go to `<dest_dir>/sessions/` instead: the hooks' own logs and
+//! the installer's audit log (see `audit`).
+//!
+//! `usage` reports how much each part takes up; `clean` prunes the parts
+//! that can be rebuilt or are only kept for a while (backups, the hash
+//! cache, hook and audit logs) once they are older than a retention period. Records
+//! the installer needs to stay correct are never pruned.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::fs::{audit, backup};
+use crate::fs::journal::{Journal, JournalEntry};
+
+/// Directory under the CLI's config dir that holds all installer state.
+pub const STATE_DIR_NAME: &str = ".hibi";
+
+/// Root of the installer state for `dest_dir`.
+pub fn state_dir(dest_dir: &Path) -> PathBuf {
+    dest_dir.join(STATE_DIR_NAME)
+}
+
+/// Parse the YAML file at `path`; the default value when it doesn't exist.
+pub fn load_yaml<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_yaml_bw::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Write `value` to `path` as YAML, creating its directory first.
+pub fn save_yaml<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_yaml_bw::to_string(value)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Directory hooks log into; see `hook_log`. Hooks own it, so only its
+/// `.log` files count as state.
+fn hook_log_dir(dest_dir: &Path) -> PathBuf {
+    dest_dir.join("sessions")
+}
+
+/// What a part of the state is kept for, which decides whether `clean`
+/// may prune it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateKind {
+    Backups,
+    Cache,
+    Logs,
+    /// Needed to keep managing what is installed; never pruned.
+    Records,
+}
+
+impl StateKind {
+    pub fn prunable(self) -> bool {
+        self != Self::Records
+    }
+}
+
+/// Size of one part of the state.
+#[derive(Clone, Debug)]
+pub struct StateUsage {
+    pub label: &'static str,
+    pub kind: StateKind,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+fn hook_logs(dest_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(hook_log_dir(dest_dir)) else { return Vec::new() };
+    let mut logs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    logs.sort();
+    logs
+}
+
+/// The audit log and its rotated copy, where they exist.
+fn audit_logs(dest_dir: &Path) -> Vec<PathBuf> {
+    let log = audit::log_path(dest_dir);
+    [audit::rotated_path(&log), log].into_iter().filter(|p| p.is_file()).collect()
+}
+
+/// How much space each part of the state for `dest_dir` takes up. Parts
+/// that don't exist are reported with zero bytes.
+pub fn usage(dest_dir: &Path) -> Vec<StateUsage> {
+    let state = state_dir(dest_dir);
+    let part = |label, kind, path: PathBuf| StateUsage { label, kind, bytes: super::path_size(&path), path };
+    vec![
+        part("backups", StateKind::Backups, state.join(backup::BACKUP_DIR_NAME)),
+        part("hash cache", StateKind::Cache, state.join(crate::fs::scanner::HASH_CACHE_FILE)),
+        StateUsage {
+            label: "hook logs",
+            kind: StateKind::Logs,
+            bytes: hook_logs(dest_dir).iter().map(|p| super::path_size(p)).sum(),
+            path: hook_log_dir(dest_dir),
+        },
+        StateUsage {
+            label: "audit log",
+            kind: StateKind::Logs,
+            bytes: audit_logs(dest_dir).iter().map(|p| super::path_size(p)).sum(),
+            path: audit::log_path(dest_dir),
+        },
+        part("install record", StateKind::Records, state.join(crate::fs::installer::INSTALLED_RECORD_FILE)),
+        part("merge bases", StateKind::Records, state.join(crate::fs::installer::BASE_DIR)),
+        part("pack manifest", StateKind::Records, state.join(crate::fs::installer::PACK_MANIFEST_FILE)),
+        part("offline queue", StateKind::Records, state.join(crate::fs::pending::PENDING_FILE)),
+        part("interrupted batch", StateKind::Records, state.join(crate::fs::interrupted::QUEUE_FILE)),
+        part("settings keys", StateKind::Records, state.join(crate::fs::installer::SETTINGS_KEYS_FILE)),
+    ]
+}
+
+/// Parse a retention period such as `30d`, `2w` or `12h`.
+pub fn parse_retention(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let Ok(number) = number.parse::<u64>() else {
+        bail!("Invalid retention '{}' (expected e.g. 30d, 2w or 12h)", value);
+    };
+    let unit_secs = match unit {
+        "h" => 60 * 60,
+        "d" | "" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => bail!("Invalid retention unit '{}' in '{}' (expected h, d or w)", unit, value),
+    };
+    let Some(secs) = number.checked_mul(unit_secs) else {
+        bail!("Retention '{}' is too long", value);
+    };
+    Ok(Duration::from_secs(secs))
+}
+
+/// What `clean` removed.
+#[derive(Debug, Default)]
+pub struct CleanReport {
+    pub removed: Vec<PathBuf>,
+    pub freed_bytes: u64,
+}
+
+impl CleanReport {
+    fn remove(&mut self, path: &Path) -> Result<()> {
+        let bytes = super::path_size(path);
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        }
+        .with_context(|| format!("Failed to remove {}", path.display()))?;
+        self.freed_bytes += bytes;
+        self.removed.push(path.to_path_buf());
+        Ok(())
+    }
+}
+
+/// Snapshot directories the stored undo journal restores from; pruning
+/// them would break `undo`.
+fn journal_snapshots(dest_dir: &Path) -> Vec<PathBuf> {
+    let Ok(Some(journal)) = Journal::load(dest_dir) else { return Vec::new() };
+    journal.entries.iter()
+        .filter_map(|entry| match entry {
+            JournalEntry::FileReplaced { backup, .. } => Some(backup.as_path()),
+            _ => None,
+        })
+        .flat_map(Path::ancestors)
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// Delete backups, the hash cache and hook and audit logs for `dest_dir` that were
+/// last written more than `keep` before `now`. The snapshot the undo
+/// journal points into is kept regardless of age.
+pub fn clean(dest_dir: &Path, keep: Duration, now: SystemTime) -> Result<CleanReport> {
+    let cutoff = now.checked_sub(keep).unwrap_or(SystemTime::UNIX_EPOCH);
+    let expired = |path: &Path| super::path_modified(path).is_none_or(|modified| modified < cutoff);
+    let mut report = CleanReport::default();
+
+    let in_use = journal_snapshots(dest_dir);
+    for snapshot in backup::list_snapshots(dest_dir)? {
+        if !in_use.contains(&snapshot.dir) && expired(&snapshot.dir) {
+            report.remove(&snapshot.dir)?;
+        }
+    }
+
+    let cache = state_dir(dest_dir).join(crate::fs::scanner::HASH_CACHE_FILE);
+    if cache.is_file() && expired(&cache) {
+        report.remove(&cache)?;
+    }
+
+    for log in hook_logs(dest_dir).into_iter().chain(audit_logs(dest_dir)) {
+        if expired(&log) {
+            report.remove(&log)?;
+        }
+    }
+    Ok(report)
+}
+
+/// `1536` -> "1.5 KiB".
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn parses_retention_periods() {
+        assert_eq!(parse_retention("30d").unwrap(), Duration::from_secs(30 * 86_400));
+        assert_eq!(parse_retention("2w").unwrap(), Duration::from_secs(14 * 86_400));
+        assert_eq!(parse_retention("12h").unwrap(), Duration::from_secs(12 * 3600));
+        assert_eq!(parse_retention("7").unwrap(), Duration::from_secs(7 * 86_400));
+        assert!(parse_retention("d").is_err());
+        assert!(parse_retention("3m").is_err());
+        assert!(parse_retention("99999999999999999w").is_err());
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn clean_prunes_old_state_but_keeps_records_and_undo_snapshot() {
+        let dest = temp_dir("state_clean");
+        let backups = state_dir(&dest).join(backup::BACKUP_DIR_NAME);
+        let old = backups.join("20250101-000000");
+        let undo = backups.join("20250102-000000");
+        for dir in [&old, &undo, &hook_log_dir(&dest)] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let files = [
+            old.join("settings.json"),
+            undo.join("settings.json"),
+            state_dir(&dest).join(crate::fs::scanner::HASH_CACHE_FILE),
+            state_dir(&dest).join(crate::fs::installer::PACK_MANIFEST_FILE),
+            hook_log_dir(&dest).join("hooks.log"),
+        ];
+        for file in &files {
+            std::fs::write(file, "x").unwrap();
+        }
+        Journal {
+            summary: "Installed 1 item(s)".to_string(),
+            entries: vec![JournalEntry::FileReplaced {
+                path: dest.join("settings.json"),
+                backup: undo.join("settings.json"),
+            }],
+            irreversible: Vec::new(),
+        }
+        .save(&dest)
+        .unwrap();
+
+        // Nothing is older than a day yet.
+        let now = SystemTime::now();
+        assert!(clean(&dest, Duration::from_secs(86_400), now).unwrap().removed.is_empty());
+
+        let later = now + Duration::from_secs(40 * 86_400);
+        let report = clean(&dest, parse_retention("30d").unwrap(), later).unwrap();
+        let remaining: Vec<bool> = files.iter().map(|f| f.exists()).collect();
+        let _ = std::fs::remove_dir_all(&dest);
+
+        assert_eq!(report.removed.len(), 3);
+        assert_eq!(report.freed_bytes, 3);
+        assert_eq!(remaining, vec![false, true, false, true, false]);
+    }
+}
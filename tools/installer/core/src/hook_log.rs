@@ -0,0 +1,158 @@
+//! Reading hook logs for the in-installer log viewer.
+//!
+//! Hooks write to a shared `<dest_dir>/sessions/hooks.log` and optionally to
+//! per-hook `<dest_dir>/sessions/<hook-name>.log` files. Lines are free-form;
+//! the hook and level are recovered heuristically so the viewer can filter
+//! on them.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Shared log every hook may append to.
+const SHARED_LOG: &str = "hooks.log";
+
+/// Bytes read from the end of each log; the viewer only shows the tail,
+/// and logs can grow without bound while hooks keep writing.
+const TAIL_BYTES: u64 = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn display(&self) -> &str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        }
+    }
+
+    /// Recognise a level token such as `ERROR`, `[warn]` or `Info:`.
+    fn parse(token: &str) -> Option<Self> {
+        let word = token.trim_matches(|c: char| !c.is_ascii_alphabetic());
+        match word.to_ascii_uppercase().as_str() {
+            "DEBUG" | "TRACE" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" | "WARNING" => Some(Self::Warn),
+            "ERROR" | "ERR" | "FATAL" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogLine {
+    /// Hook the line belongs to, from the file name or a mention in the line.
+    pub hook: Option<String>,
+    pub level: Option<LogLevel>,
+    pub text: String,
+}
+
+/// Existing log files: the shared log plus one per known hook name.
+pub fn log_files(dest_dir: &Path, hook_names: &[String]) -> Vec<(Option<String>, PathBuf)> {
+    let dir = dest_dir.join("sessions");
+    std::iter::once((None, dir.join(SHARED_LOG)))
+        .chain(hook_names.iter().map(|name| (Some(name.clone()), dir.join(format!("{}.log", name)))))
+        .filter(|(_, path)| path.is_file())
+        .collect()
+}
+
+/// Modification time and length of each log file, used to skip re-reading
+/// unchanged logs.
+pub fn log_stamp(files: &[(Option<String>, PathBuf)]) -> Vec<(SystemTime, u64)> {
+    files.iter()
+        .filter_map(|(_, path)| {
+            let meta = std::fs::metadata(path).ok()?;
+            Some((meta.modified().ok()?, meta.len()))
+        })
+        .collect()
+}
+
+/// Last `max_lines` lines of every log file, shared log first.
+pub fn read_tail(files: &[(Option<String>, PathBuf)], hook_names: &[String], max_lines: usize) -> Vec<LogLine> {
+    let mut lines = Vec::new();
+    for (file_hook, path) in files {
+        let Some(content) = read_end(path, TAIL_BYTES) else { continue };
+        let all: Vec<&str> = content.lines().collect();
+        let start = all.len().saturating_sub(max_lines);
+        lines.extend(all[start..].iter().map(|l| parse_line(l, file_hook.as_deref(), hook_names)));
+    }
+    lines
+}
+
+/// The last `max_bytes` of `path`, with any invalid UTF-8 replaced. When
+/// the file is longer, the line cut off at the start is dropped.
+fn read_end(path: &Path, max_bytes: u64) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut bytes = Vec::new();
+    file.take(max_bytes).read_to_end(&mut bytes).ok()?;
+    let text = String::from_utf8_lossy(&bytes);
+    if start == 0 {
+        return Some(text.into_owned());
+    }
+    Some(text.split_once('\n').map(|(_, rest)| rest.to_string()).unwrap_or_default())
+}
+
+/// Attribute a line to a hook (file name wins, otherwise the first known
+/// hook name mentioned) and pick up the first level-looking token.
+fn parse_line(line: &str, file_hook: Option<&str>, hook_names: &[String]) -> LogLine {
+    let hook = file_hook
+        .map(str::to_string)
+        .or_else(|| hook_names.iter().find(|name| line.contains(name.as_str())).cloned());
+    let level = line.split_whitespace().take(4).find_map(LogLevel::parse);
+    LogLine { hook, level, text: line.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_finds_hook_and_level() {
+        let names = vec!["load-context".to_string(), "persist-session".to_string()];
+        let line = parse_line("2026-01-02T10:00:00 [WARN] persist-session: disk full", None, &names);
+        assert_eq!(line.hook.as_deref(), Some("persist-session"));
+        assert_eq!(line.level, Some(LogLevel::Warn));
+
+        let line = parse_line("started", Some("load-context"), &names);
+        assert_eq!(line.hook.as_deref(), Some("load-context"));
+        assert_eq!(line.level, None);
+    }
+
+    #[test]
+    fn tail_reads_only_the_end_and_keeps_invalid_utf8_lines() {
+        let dir = crate::test_util::temp_dir("hook_log_tail");
+        let path = dir.join("hooks.log");
+        let mut log = b"first line\nsecond line\n".to_vec();
+        log.extend_from_slice(b"bad \xff byte\nlast line\n");
+        std::fs::write(&path, &log).unwrap();
+
+        let whole = read_end(&path, 1024).unwrap();
+        let end = read_end(&path, 30).unwrap();
+        let files = vec![(None, path)];
+        let tail = read_tail(&files, &[], 2);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(whole.starts_with("first line\n"));
+        assert_eq!(end, "bad \u{fffd} byte\nlast line\n", "the cut-off line is dropped");
+        let texts: Vec<&str> = tail.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, ["bad \u{fffd} byte", "last line"]);
+    }
+
+    #[test]
+    fn levels_are_ordered_by_severity() {
+        assert!(LogLevel::Error > LogLevel::Warn);
+        assert_eq!(LogLevel::parse("error:"), Some(LogLevel::Error));
+        assert_eq!(LogLevel::parse("Informational"), None);
+    }
+}
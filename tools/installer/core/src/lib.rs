@@ -0,0 +1,28 @@
+//! Scanning, installing, settings merging and catalog parsing shared by
+//! every hibi frontend. The TUI in the `hibi` binary is a thin layer over
+//! this crate; anything that touches `~/.claude`/`~/.codex` or a source
+//! tree belongs here rather than in the frontend.
+//!
+//! The items re-exported here, [`sync`] first among them, are the stable
+//! API for other tools and follow semver with the workspace version. The
+//! modules are public so the `hibi` binary can reach everything, but
+//! their contents may change in any release.
+
+pub mod component;
+pub mod fs;
+pub mod hook_log;
+pub mod local_time;
+pub mod maintenance;
+pub mod mcp;
+pub mod migrate;
+pub mod notify;
+pub mod plugin;
+pub mod profile;
+pub mod source;
+mod sync;
+mod target;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
+pub use sync::{sync, SyncOptions, SyncReport};
+pub use target::{custom_targets, register_custom_targets, CustomTarget, McpTemplates, TargetCli};
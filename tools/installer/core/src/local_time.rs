@@ -0,0 +1,176 @@
+//! Times for people and for logs. Machine-readable output (drift reports,
+//! exported scripts) gets ISO 8601 with the local UTC offset, or `Z` when
+//! `log_utc: true` is set in `~/.hibi/sources.yaml` so logs from several
+//! machines line up. The TUI and `--list-backups` show local times in the
+//! order the locale (`LC_ALL`, `LC_TIME`, `LANG`) uses.
+//!
+//! The local offset can only be read safely while the process has a
+//! single thread, so `init` captures it once at startup; a DST change
+//! while hibi runs is not picked up. Without `init` everything is UTC.
+
+use std::sync::OnceLock;
+
+use time::{OffsetDateTime, UtcOffset};
+
+struct Clock {
+    offset: UtcOffset,
+    utc_logs: bool,
+}
+
+static CLOCK: OnceLock<Clock> = OnceLock::new();
+
+/// Capture the local UTC offset. Call before spawning any thread.
+pub fn init(utc_logs: bool) {
+    let offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+    let _ = CLOCK.set(Clock { offset, utc_logs });
+}
+
+fn clock() -> (UtcOffset, bool) {
+    CLOCK.get().map_or((UtcOffset::UTC, true), |c| (c.offset, c.utc_logs))
+}
+
+fn at(secs: u64, offset: UtcOffset) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(secs as i64)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+        .to_offset(offset)
+}
+
+/// `YYYY-MM-DDTHH:MM:SS+HH:MM` in local time, or `...Z` in UTC when
+/// `log_utc` is on.
+pub fn machine_timestamp(secs: u64) -> String {
+    let (offset, utc_logs) = clock();
+    iso8601(secs, if utc_logs { UtcOffset::UTC } else { offset })
+}
+
+fn iso8601(secs: u64, offset: UtcOffset) -> String {
+    let t = at(secs, offset);
+    let zone = if offset.is_utc() {
+        "Z".to_string()
+    } else {
+        let (h, m, _) = offset.as_hms();
+        format!("{}{:02}:{:02}", if offset.is_negative() { '-' } else { '+' }, h.abs(), m.abs())
+    };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        t.year(), u8::from(t.month()), t.day(), t.hour(), t.minute(), t.second(), zone
+    )
+}
+
+/// Local date and time to the minute, formatted for the user's locale.
+pub fn display_time(secs: u64) -> String {
+    format_display(secs, clock().0, DisplayFormat::from_locale(&locale()))
+}
+
+fn locale() -> String {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DisplayFormat {
+    /// `2024-02-29 14:05`: ISO order, also the fallback for `C`/`POSIX`.
+    Ymd,
+    /// `02/29/2024 2:05 PM`.
+    Mdy,
+    /// `29/02/2024 14:05` or `29.02.2024 14:05`.
+    Dmy(char),
+}
+
+impl DisplayFormat {
+    fn from_locale(locale: &str) -> Self {
+        // `de_DE.UTF-8@euro` -> language `de`, territory `DE`.
+        let tag = locale.split(['.', '@']).next().unwrap_or_default();
+        let (language, territory) = tag.split_once(['_', '-']).unwrap_or((tag, ""));
+        match (language, territory) {
+            ("en", "US" | "PH") => Self::Mdy,
+            ("en", "GB" | "AU" | "NZ" | "IE" | "IN" | "ZA")
+            | ("fr" | "es" | "it" | "pt" | "nl" | "el" | "vi", _) => Self::Dmy('/'),
+            ("de" | "ru" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "da" | "tr" | "uk" | "ro", _) => Self::Dmy('.'),
+            _ => Self::Ymd,
+        }
+    }
+}
+
+fn format_display(secs: u64, offset: UtcOffset, format: DisplayFormat) -> String {
+    let t = at(secs, offset);
+    let (year, month, day) = (t.year(), u8::from(t.month()), t.day());
+    match format {
+        DisplayFormat::Ymd => format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, t.hour(), t.minute()),
+        DisplayFormat::Mdy => {
+            let hour12 = match t.hour() % 12 {
+                0 => 12,
+                h => h,
+            };
+            let meridiem = if t.hour() < 12 { "AM" } else { "PM" };
+            format!("{:02}/{:02}/{:04} {}:{:02} {}", month, day, year, hour12, t.minute(), meridiem)
+        }
+        DisplayFormat::Dmy(sep) => {
+            format!("{:02}{sep}{:02}{sep}{:04} {:02}:{:02}", day, month, year, t.hour(), t.minute())
+        }
+    }
+}
+
+/// Unix time of a backup snapshot name (`YYYYMMDD-HHMMSS`, UTC, with an
+/// optional `-N` suffix for snapshots taken in the same second).
+pub fn parse_snapshot_name(name: &str) -> Option<u64> {
+    let stamp = name.get(..15)?;
+    if !(name.len() == 15 || name.as_bytes()[15] == b'-') || stamp.as_bytes()[8] != b'-' {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| -> Option<u32> {
+        let digits = &stamp[range];
+        digits.bytes().all(|b| b.is_ascii_digit()).then(|| digits.parse().ok())?
+    };
+    let date = time::Date::from_calendar_date(
+        field(0..4)? as i32,
+        time::Month::try_from(field(4..6)? as u8).ok()?,
+        field(6..8)? as u8,
+    ).ok()?;
+    let time = time::Time::from_hms(field(9..11)? as u8, field(11..13)? as u8, field(13..15)? as u8).ok()?;
+    u64::try_from(date.with_time(time).assume_utc().unix_timestamp()).ok()
+}
+
+/// Local display time of a snapshot, with its `-N` suffix kept as ` #N`,
+/// or its name when it isn't a timestamp.
+pub fn snapshot_display(name: &str) -> String {
+    match parse_snapshot_name(name) {
+        Some(secs) => match name.get(16..) {
+            Some(n) => format!("{} #{}", display_time(secs), n),
+            None => display_time(secs),
+        },
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_and_locales_are_applied() {
+        // 2024-02-29T12:34:56Z
+        let secs = 1_709_210_096;
+        let cest = UtcOffset::from_hms(2, 0, 0).unwrap();
+        let nst = UtcOffset::from_hms(-3, -30, 0).unwrap();
+        assert_eq!(iso8601(secs, UtcOffset::UTC), "2024-02-29T12:34:56Z");
+        assert_eq!(iso8601(secs, cest), "2024-02-29T14:34:56+02:00");
+        assert_eq!(iso8601(secs, nst), "2024-02-29T09:04:56-03:30");
+
+        let display = |locale| format_display(secs, cest, DisplayFormat::from_locale(locale));
+        assert_eq!(display(""), "2024-02-29 14:34");
+        assert_eq!(display("C.UTF-8"), "2024-02-29 14:34");
+        assert_eq!(display("en_US.UTF-8"), "02/29/2024 2:34 PM");
+        assert_eq!(display("en_GB.UTF-8"), "29/02/2024 14:34");
+        assert_eq!(display("de_DE.UTF-8@euro"), "29.02.2024 14:34");
+        assert_eq!(display("ja_JP.UTF-8"), "2024-02-29 14:34");
+
+        assert_eq!(parse_snapshot_name("20240229-123456"), Some(secs));
+        assert_eq!(parse_snapshot_name("20240229-123456-2"), Some(secs));
+        assert_eq!(parse_snapshot_name("20240230-123456"), None);
+        assert_eq!(parse_snapshot_name("manual-backup"), None);
+        assert_eq!(snapshot_display("manual-backup"), "manual-backup");
+    }
+}
@@ -0,0 +1,163 @@
+//! Weekly maintenance summary.
+//!
+//! `hibi --maintenance-summary` is meant to run as a SessionStart hook. At
+//! most once per `SUMMARY_INTERVAL_SECS` it scans for drift — components
+//! with pending changes, outdated hooks, MCP servers failing their health
+//! check — and prints one line describing it. The time of the last check is
+//! kept in `~/.hibi/maintenance.yaml`.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::TargetCli;
+use crate::component::{ComponentType, InstallStatus};
+use crate::fs::state::{load_yaml, save_yaml};
+use crate::source::ResolvedSource;
+use crate::source::config::DirectoryMap;
+
+/// Minimum time between two summaries.
+pub const SUMMARY_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Top-level YAML structure for `~/.hibi/maintenance.yaml`.
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct MaintenanceState {
+    #[serde(default)]
+    last_summary: Option<u64>,
+}
+
+/// Drift found by `collect_drift`.
+#[derive(Debug, Default, PartialEq)]
+pub struct DriftSummary {
+    /// Non-hook components available in a source but never installed.
+    pub new_components: usize,
+    /// Non-hook components whose source differs from the installed copy.
+    pub modified_components: usize,
+    /// Installed hooks whose source changed, or that are now deprecated.
+    pub outdated_hooks: Vec<String>,
+    /// MCP servers reported as failing by `claude mcp list`.
+    pub failing_mcp: Vec<String>,
+}
+
+impl DriftSummary {
+    pub fn is_empty(&self) -> bool {
+        self.new_components == 0
+            && self.modified_components == 0
+            && self.outdated_hooks.is_empty() && self.failing_mcp.is_empty()
+    }
+
+    /// Single-line notice, e.g.
+    /// "hibi: 2 new, 1 modified component(s), failing MCP: memory — run `hibi` to review".
+    pub fn notice(&self) -> String {
+        let mut parts = Vec::new();
+        if self.new_components + self.modified_components > 0 {
+            parts.push(format!(
+                "{} new, {} modified component(s)",
+                self.new_components, self.modified_components
+            ));
+        }
+        if !self.outdated_hooks.is_empty() {
+            parts.push(format!("outdated hooks: {}", self.outdated_hooks.join(", ")));
+        }
+        if !self.failing_mcp.is_empty() {
+            parts.push(format!("failing MCP: {}", self.failing_mcp.join(", ")));
+        }
+        format!("hibi: {} — run `hibi` to review", parts.join(", "))
+    }
+}
+
+/// Scan Claude components and MCP health for drift.
+pub fn collect_drift(
+    sources: &[ResolvedSource],
+    dest_dir: &Path,
+    directories: &DirectoryMap,
+) -> Result<DriftSummary> {
+    let components = crate::fs::scanner::scan_all_sources(sources, dest_dir, TargetCli::Claude, directories, crate::fs::scanner::ScanOptions::default())?;
+
+    let mut summary = DriftSummary::default();
+    for c in &components {
+        if c.component_type != ComponentType::Hooks {
+            match c.status {
+                InstallStatus::New => summary.new_components += 1,
+                InstallStatus::Modified => summary.modified_components += 1,
+                _ => {}
+            }
+            continue;
+        }
+        let deprecated_but_installed = c.status != InstallStatus::New
+            && c.hook_config.as_ref().is_some_and(|h| h.is_deprecated());
+        if c.status == InstallStatus::Modified || deprecated_but_installed {
+            summary.outdated_hooks.push(c.name.clone());
+        }
+    }
+
+    // An unavailable CLI is not drift; skip the MCP part quietly.
+    summary.failing_mcp = crate::fs::scanner::failing_claude_servers().unwrap_or_default();
+    Ok(summary)
+}
+
+fn state_path(home: &Path) -> PathBuf {
+    home.join(".hibi").join("maintenance.yaml")
+}
+
+fn load_state(home: &Path) -> Result<MaintenanceState> {
+    load_yaml(&state_path(home))
+}
+
+/// Seconds since the Unix epoch.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Whether a summary is due at `now`. A missing or unreadable state file
+/// counts as due.
+pub fn is_due(home: &Path, now: u64) -> bool {
+    load_state(home)
+        .ok()
+        .and_then(|s| s.last_summary)
+        .is_none_or(|last| now.saturating_sub(last) >= SUMMARY_INTERVAL_SECS)
+}
+
+/// Record that a summary ran at `now`.
+pub fn mark_done(home: &Path, now: u64) -> Result<()> {
+    save_yaml(&state_path(home), &MaintenanceState { last_summary: Some(now) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn due_once_per_interval() {
+        let home = temp_dir("due");
+        assert!(is_due(&home, 1_000));
+
+        mark_done(&home, 1_000).unwrap();
+        assert!(!is_due(&home, 1_000 + SUMMARY_INTERVAL_SECS - 1));
+        assert!(is_due(&home, 1_000 + SUMMARY_INTERVAL_SECS));
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn notice_lists_only_present_drift() {
+        let summary = DriftSummary {
+            new_components: 2,
+            modified_components: 1,
+            outdated_hooks: Vec::new(),
+            failing_mcp: vec!["memory".to_string()],
+        };
+        assert!(!summary.is_empty());
+        assert_eq!(
+            summary.notice(),
+            "hibi: 2 new, 1 modified component(s), failing MCP: memory — run `hibi` to review",
+        );
+        assert!(DriftSummary::default().is_empty());
+    }
+}
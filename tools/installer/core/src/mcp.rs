@@ -0,0 +1,284 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::source::config::expand_tilde;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpScope {
+    User,
+    Local,
+}
+
+impl McpScope {
+    pub fn display(&self) -> &str {
+        match self {
+            Self::User => "user",
+            Self::Local => "local",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            Self::User => Self::Local,
+            Self::Local => Self::User,
+        }
+    }
+}
+
+impl Default for McpScope {
+    fn default() -> Self {
+        Self::User
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpType {
+    Command,
+    Http,
+}
+
+impl Default for McpType {
+    fn default() -> Self {
+        Self::Command
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum McpStatus {
+    Installed,
+    NotInstalled,
+}
+
+impl McpStatus {
+    pub fn display(&self) -> &str {
+        match self {
+            Self::Installed => "installed",
+            Self::NotInstalled => "not installed",
+        }
+    }
+}
+
+/// Connection state reported by the CLI's `mcp list` health check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum McpHealth {
+    Connected,
+    Failed,
+}
+
+impl McpHealth {
+    pub fn display(&self) -> &str {
+        match self {
+            Self::Connected => "connected",
+            Self::Failed => "failing",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpServerDef {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub r#type: Option<McpType>,
+    pub command: Option<String>,
+    pub url: Option<String>,
+    pub category: String,
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// Pin for the npm package run by an `npx` command. Replaces any
+    /// `@latest`/`@x.y.z` suffix in `command` at install time.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// dotenv-format file to read `env` values from before prompting.
+    /// `~` is expanded; a relative path is taken from the project path.
+    #[serde(default)]
+    pub env_file: Option<String>,
+}
+
+/// Split `@scope/pkg@1.2.3` / `pkg@latest` into name and optional version.
+/// A leading `@` is the scope marker, not a version separator.
+fn split_package_spec(spec: &str) -> (&str, Option<&str>) {
+    match spec.get(1..).and_then(|rest| rest.rfind('@')) {
+        Some(i) => (&spec[..i + 1], Some(&spec[i + 2..])),
+        None => (spec, None),
+    }
+}
+
+impl McpServerDef {
+    /// Position of the package spec among the whitespace-separated tokens of
+    /// an `npx` command: the first non-flag argument after `npx`.
+    fn npx_package_token(tokens: &[&str]) -> Option<usize> {
+        let program = tokens.first()?;
+        if !matches!(*program, "npx" | "npx.cmd") {
+            return None;
+        }
+        tokens.iter()
+            .enumerate()
+            .skip(1)
+            .find(|(_, t)| !t.starts_with('-'))
+            .map(|(i, _)| i)
+    }
+
+    /// npm package name (without version) run by this server's `npx` command.
+    pub fn npm_package(&self) -> Option<String> {
+        let tokens: Vec<&str> = self.command.as_deref()?.split_whitespace().collect();
+        let idx = Self::npx_package_token(&tokens)?;
+        let (name, _) = split_package_spec(tokens[idx]);
+        Some(name.to_string())
+    }
+
+    /// The command to install with: `command` with the package pinned to
+    /// `version` when one is set.
+    pub fn resolved_command(&self) -> Option<String> {
+        let cmd = self.command.as_deref()?;
+        let Some(version) = &self.version else { return Some(cmd.to_string()) };
+
+        let mut tokens: Vec<String> = cmd.split_whitespace().map(str::to_string).collect();
+        let borrowed: Vec<&str> = tokens.iter().map(String::as_str).collect();
+        let Some(idx) = Self::npx_package_token(&borrowed) else { return Some(cmd.to_string()) };
+
+        let (name, _) = split_package_spec(&tokens[idx]);
+        tokens[idx] = format!("{}@{}", name, version);
+        Some(tokens.join(" "))
+    }
+
+    /// Where `env_file` points, resolved against `project_path`.
+    pub fn env_file_path(&self, project_path: &Path) -> Option<PathBuf> {
+        let path = expand_tilde(Path::new(self.env_file.as_deref()?));
+        Some(if path.is_absolute() { path } else { project_path.join(path) })
+    }
+
+    /// Values of this server's declared `env` vars found in its env file.
+    /// Empty when there is no file or it can't be read.
+    pub fn env_file_values(&self, project_path: &Path) -> Vec<(String, String)> {
+        let Some(content) = self.env_file_path(project_path).and_then(|p| std::fs::read_to_string(p).ok()) else {
+            return Vec::new();
+        };
+        let values = parse_dotenv(&content);
+        self.env.iter()
+            .filter_map(|var| values.iter().rev().find(|(k, _)| k == var).cloned())
+            .collect()
+    }
+}
+
+/// `KEY=value` pairs of a dotenv file, in order. Blank lines and `#`
+/// comments are skipped, a leading `export ` is allowed, and one pair of
+/// matching quotes around the value is removed.
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.strip_prefix("export ").unwrap_or(line).split_once('=')?;
+            let value = value.trim();
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|&q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+                .unwrap_or(value);
+            Some((key.trim().to_string(), unquoted.to_string()))
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct McpServer {
+    pub def: McpServerDef,
+    pub selected: bool,
+    pub status: McpStatus,
+    pub source_name: String,
+    /// Scope the server is installed in, for targets whose config files
+    /// tell (Cursor, OpenCode); `None` when installed elsewhere or not at
+    /// all.
+    pub scope: Option<McpScope>,
+}
+
+impl McpServer {
+    pub fn new(def: McpServerDef, status: McpStatus) -> Self {
+        Self {
+            def,
+            selected: false,
+            status,
+            source_name: "bundled".to_string(),
+            scope: None,
+        }
+    }
+
+    pub fn is_http(&self) -> bool {
+        matches!(self.def.r#type, Some(McpType::Http))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct McpCatalog {
+    pub servers: Vec<McpServerDef>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn def(command: &str, version: Option<&str>) -> McpServerDef {
+        McpServerDef {
+            name: "test".to_string(),
+            description: String::new(),
+            r#type: None,
+            command: Some(command.to_string()),
+            url: None,
+            category: "test".to_string(),
+            env: vec![],
+            version: version.map(str::to_string),
+            env_file: None,
+        }
+    }
+
+    #[test]
+    fn test_npm_package_strips_version_and_keeps_scope() {
+        assert_eq!(def("npx -y @upstash/context7-mcp", None).npm_package().as_deref(), Some("@upstash/context7-mcp"));
+        assert_eq!(def("npx -y @playwright/mcp@latest", None).npm_package().as_deref(), Some("@playwright/mcp"));
+        assert_eq!(def("npx shadcn@latest mcp", None).npm_package().as_deref(), Some("shadcn"));
+        assert_eq!(def("uvx mcp-atlassian", None).npm_package(), None);
+    }
+
+    #[test]
+    fn test_resolved_command_applies_pin() {
+        assert_eq!(
+            def("npx -y @playwright/mcp@latest", Some("0.0.41")).resolved_command().as_deref(),
+            Some("npx -y @playwright/mcp@0.0.41"),
+        );
+        assert_eq!(
+            def("npx shadcn@latest mcp", Some("2.1.0")).resolved_command().as_deref(),
+            Some("npx shadcn@2.1.0 mcp"),
+        );
+        // No pin: command is passed through untouched.
+        assert_eq!(
+            def("npx -y firecrawl-mcp", None).resolved_command().as_deref(),
+            Some("npx -y firecrawl-mcp"),
+        );
+    }
+
+    #[test]
+    fn env_file_supplies_declared_vars_only() {
+        let project = temp_dir("env_file");
+        std::fs::write(
+            project.join(".env"),
+            "# tokens\nexport API_KEY=\"abc=123\"\nOTHER=x\n\nURL = 'https://example.com'\nAPI_KEY=final\n",
+        ).unwrap();
+        let mut server = def("npx -y server", None);
+        server.env = vec!["API_KEY".into(), "URL".into(), "MISSING".into()];
+        server.env_file = Some(".env".into());
+
+        let values = server.env_file_values(&project);
+        let _ = std::fs::remove_dir_all(&project);
+
+        assert_eq!(parse_dotenv("export A=\"1=2\"\n#B=3\nC\n"), [("A".to_string(), "1=2".to_string())]);
+        assert_eq!(values, [
+            ("API_KEY".to_string(), "final".to_string()),
+            ("URL".to_string(), "https://example.com".to_string()),
+        ]);
+        assert_eq!(server.env_file_path(Path::new("/p")), Some(PathBuf::from("/p/.env")));
+    }
+}
@@ -0,0 +1,304 @@
+//! Versioned migrations for installer-owned state.
+//!
+//! `~/.hibi/manifest.yaml` records the `schema_version` the state files were
+//! last migrated to. On startup every migration newer than that runs in
+//! order, after the files they may touch have been copied into
+//! `~/.hibi/backups/`. The manifest is bumped after each successful step so a
+//! failure part-way resumes from the failing migration on the next run.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::TargetCli;
+use crate::fs::backup::{backup_root, BACKUP_DIR_NAME};
+use crate::fs::journal::{Journal, JournalEntry};
+use crate::fs::state::{load_yaml, save_yaml, state_dir, STATE_DIR_NAME};
+
+/// Schema version written by this build. Always equals the last entry in
+/// `MIGRATIONS`.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Top-level YAML structure for `~/.hibi/manifest.yaml`.
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Manifest {
+    #[serde(default)]
+    schema_version: u32,
+}
+
+/// Paths a migration may read or rewrite.
+pub struct MigrationContext {
+    pub home: PathBuf,
+}
+
+impl MigrationContext {
+    pub fn hibi_dir(&self) -> PathBuf {
+        self.home.join(".hibi")
+    }
+}
+
+struct Migration {
+    version: u32,
+    description: &'static str,
+    /// Returns a user-facing note when the migration changed something.
+    apply: fn(&MigrationContext) -> Result<Option<String>>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "remove orphaned bundled source cache",
+        apply: remove_bundled_cache,
+    },
+    Migration {
+        version: 2,
+        description: "move per-CLI installer state into <config dir>/.hibi/",
+        apply: move_state_into_state_dir,
+    },
+];
+
+/// Outcome of `run_pending`.
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Notes from migrations that changed something.
+    pub notes: Vec<String>,
+    /// Where state files were copied before migrating, if any existed.
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// Run every migration newer than the manifest's `schema_version`.
+pub fn run_pending(home: &Path) -> Result<MigrationReport> {
+    let ctx = MigrationContext { home: home.to_path_buf() };
+    let manifest_path = manifest_path(&ctx);
+    let mut manifest = load_manifest(&manifest_path)?;
+
+    let mut report = MigrationReport {
+        from_version: manifest.schema_version,
+        to_version: manifest.schema_version,
+        ..Default::default()
+    };
+
+    let pending: Vec<&Migration> = MIGRATIONS.iter()
+        .filter(|m| m.version > manifest.schema_version)
+        .collect();
+    if pending.is_empty() {
+        return Ok(report);
+    }
+
+    report.backup_dir = backup_state_files(&ctx, manifest.schema_version)?;
+
+    for migration in pending {
+        let note = (migration.apply)(&ctx).with_context(|| {
+            format!("Migration v{} ({}) failed", migration.version, migration.description)
+        })?;
+        report.notes.extend(note);
+
+        manifest.schema_version = migration.version;
+        save_manifest(&manifest_path, &manifest)?;
+        report.to_version = migration.version;
+    }
+
+    Ok(report)
+}
+
+fn manifest_path(ctx: &MigrationContext) -> PathBuf {
+    ctx.hibi_dir().join("manifest.yaml")
+}
+
+/// A missing manifest means a pre-migration install (version 0).
+fn load_manifest(path: &Path) -> Result<Manifest> {
+    load_yaml(path)
+}
+
+fn save_manifest(path: &Path, manifest: &Manifest) -> Result<()> {
+    save_yaml(path, manifest)
+}
+
+/// State files any migration may touch, relative to the home directory.
+fn backup_targets() -> [PathBuf; 3] {
+    [
+        Path::new(".hibi").join("sources.yaml"),
+        Path::new(".claude").join("settings.json"),
+        Path::new(".codex").join("config.toml"),
+    ]
+}
+
+/// Copy existing state files into `~/.hibi/backups/schema-v<from>-<secs>/`,
+/// keeping their home-relative layout. Returns `None` when there was
+/// nothing to back up.
+fn backup_state_files(ctx: &MigrationContext, from_version: u32) -> Result<Option<PathBuf>> {
+    let existing: Vec<PathBuf> = backup_targets()
+        .into_iter()
+        .filter(|rel| ctx.home.join(rel).is_file())
+        .collect();
+    if existing.is_empty() {
+        return Ok(None);
+    }
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backup_dir = ctx.hibi_dir()
+        .join("backups")
+        .join(format!("schema-v{}-{}", from_version, secs));
+
+    for rel in existing {
+        let target = backup_dir.join(&rel);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(ctx.home.join(&rel), &target)
+            .with_context(|| format!("Failed to back up {}", rel.display()))?;
+    }
+
+    Ok(Some(backup_dir))
+}
+
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/// v1: the bundled source is package-embedded; older versions synced it into
+/// `~/.hibi/cache/bundled`, which is now dead data.
+fn remove_bundled_cache(ctx: &MigrationContext) -> Result<Option<String>> {
+    let removed = crate::source::git::cleanup_bundled_cache(&ctx.home)?;
+    Ok(removed.then(|| "Removed orphaned bundled cache (~/.hibi/cache/bundled)".to_string()))
+}
+
+/// Pre-v2 state kept directly in each CLI's config dir, and its name under
+/// `<config dir>/.hibi/`.
+const LEGACY_STATE_FILES: [(&str, &str); 4] = [
+    (".hibi-backups", BACKUP_DIR_NAME),
+    (".hibi-cache.json", crate::fs::scanner::HASH_CACHE_FILE),
+    (".hibi-packs.yaml", crate::fs::installer::PACK_MANIFEST_FILE),
+    (".hibi-settings-keys.yaml", crate::fs::installer::SETTINGS_KEYS_FILE),
+];
+
+/// v2: backups, the hash cache and the ownership records moved from
+/// `.hibi-*` files beside the CLI's config into one `.hibi/` directory.
+/// Anything already present at the new location is left alone.
+fn move_state_into_state_dir(ctx: &MigrationContext) -> Result<Option<String>> {
+    let mut moved_into = Vec::new();
+    for cli in TargetCli::ALL {
+        let dest = ctx.home.join(cli.config_dir_name());
+        let state = state_dir(&dest);
+        let mut moved = false;
+        for (old, new) in LEGACY_STATE_FILES {
+            let (from, to) = (dest.join(old), state.join(new));
+            if !from.exists() || to.exists() {
+                continue;
+            }
+            std::fs::create_dir_all(&state)?;
+            std::fs::rename(&from, &to)
+                .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+            moved = true;
+        }
+        if moved {
+            rebase_journal(&dest)?;
+            moved_into.push(format!("~/{}/{}", cli.config_dir_name(), STATE_DIR_NAME));
+        }
+    }
+    Ok((!moved_into.is_empty()).then(|| format!("Moved installer state into {}", moved_into.join(", "))))
+}
+
+/// The undo journal records absolute snapshot paths; point the ones under
+/// the old backup directory at the moved snapshots.
+fn rebase_journal(dest: &Path) -> Result<()> {
+    let Some(mut journal) = Journal::load(dest)? else { return Ok(()) };
+    let (old_root, new_root) = (dest.join(".hibi-backups"), backup_root(dest));
+    for entry in &mut journal.entries {
+        if let JournalEntry::FileReplaced { backup, .. } = entry
+            && let Ok(rel) = backup.strip_prefix(&old_root)
+        {
+            *backup = new_root.join(rel);
+        }
+    }
+    journal.save(dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn current_version_matches_last_migration() {
+        assert_eq!(MIGRATIONS.last().map(|m| m.version), Some(CURRENT_SCHEMA_VERSION));
+        assert!(MIGRATIONS.windows(2).all(|w| w[0].version < w[1].version));
+    }
+
+    #[test]
+    fn runs_pending_backs_up_and_records_version() {
+        let home = temp_dir("pending");
+        let bundled = home.join(".hibi").join("cache").join("bundled");
+        std::fs::create_dir_all(&bundled).unwrap();
+        std::fs::create_dir_all(home.join(".claude")).unwrap();
+        std::fs::write(home.join(".claude").join("settings.json"), "{}").unwrap();
+
+        let report = run_pending(&home).unwrap();
+        assert_eq!((report.from_version, report.to_version), (0, CURRENT_SCHEMA_VERSION));
+        assert!(!bundled.exists());
+        assert_eq!(report.notes.len(), 1);
+
+        let backup = report.backup_dir.expect("settings.json existed");
+        assert!(backup.join(".claude").join("settings.json").is_file());
+
+        // Second run is a no-op: nothing pending, no new backup.
+        let again = run_pending(&home).unwrap();
+        assert_eq!(again.from_version, CURRENT_SCHEMA_VERSION);
+        assert!(again.notes.is_empty());
+        assert!(again.backup_dir.is_none());
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn moves_legacy_state_files_and_rebases_journal() {
+        let home = temp_dir("state");
+        let dest = home.join(".claude");
+        let snapshot = dest.join(".hibi-backups").join("20250101-000000");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("settings.json"), "{}").unwrap();
+        std::fs::write(dest.join(".hibi-packs.yaml"), "packs: {}\n").unwrap();
+        std::fs::write(
+            dest.join(".hibi-backups").join("journal.json"),
+            serde_json::to_string(&Journal {
+                summary: "Installed 1 item(s)".to_string(),
+                entries: vec![JournalEntry::FileReplaced {
+                    path: dest.join("settings.json"),
+                    backup: snapshot.join("settings.json"),
+                }],
+                irreversible: Vec::new(),
+            }).unwrap(),
+        ).unwrap();
+
+        let note = move_state_into_state_dir(&MigrationContext { home: home.clone() }).unwrap();
+        let journal = Journal::load(&dest).unwrap().unwrap();
+        let moved_snapshot = backup_root(&dest).join("20250101-000000");
+        let packs_moved = state_dir(&dest).join("packs.yaml").is_file();
+        let legacy_left = dest.join(".hibi-backups").exists() || dest.join(".hibi-packs.yaml").exists();
+        let _ = std::fs::remove_dir_all(&home);
+
+        assert_eq!(note.as_deref(), Some("Moved installer state into ~/.claude/.hibi"));
+        assert!(packs_moved && !legacy_left);
+        assert!(matches!(
+            &journal.entries[0],
+            JournalEntry::FileReplaced { backup, .. } if *backup == moved_snapshot.join("settings.json")
+        ));
+    }
+
+    #[test]
+    fn fresh_home_skips_backup() {
+        let home = temp_dir("fresh");
+        let report = run_pending(&home).unwrap();
+        assert!(report.backup_dir.is_none());
+        assert!(report.notes.is_empty());
+        assert!(manifest_path(&MigrationContext { home: home.clone() }).is_file());
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
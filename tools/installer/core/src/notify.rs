@@ -0,0 +1,149 @@
+//! Announcing a finished install/remove batch, so a long MCP or plugin
+//! batch can run in the background. Configured with `notify:` in
+//! `~/.hibi/sources.yaml`: a desktop notification through the platform's
+//! notifier and/or a JSON POST to a webhook (through `curl`, which every
+//! supported platform ships).
+
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::fs::run_with_timeout;
+use crate::source::config::NotifyConfig;
+
+/// Budget for one notifier or webhook call.
+const NOTIFY_TIMEOUT_SECS: u64 = 10;
+
+/// Summary of a finished batch, as POSTed to the webhook.
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchNotice {
+    pub event: &'static str,
+    pub target: String,
+    pub tab: String,
+    /// `install` or `remove`.
+    pub action: &'static str,
+    /// Strict mode stopped the batch and undid what it had changed.
+    pub rolled_back: bool,
+    pub succeeded: Vec<String>,
+    pub failed: Vec<String>,
+    pub finished_at: String,
+}
+
+impl BatchNotice {
+    pub fn new(target: &str, tab: &str, removing: bool, rolled_back: bool, succeeded: Vec<String>, failed: Vec<String>) -> Self {
+        Self {
+            event: "batch_complete",
+            target: target.to_string(),
+            tab: tab.to_string(),
+            action: if removing { "remove" } else { "install" },
+            rolled_back,
+            succeeded,
+            failed,
+            finished_at: crate::local_time::machine_timestamp(crate::maintenance::now_secs()),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        let done = match (self.rolled_back, self.action) {
+            (true, _) => "batch rolled back",
+            (false, "remove") => "removal finished",
+            _ => "install finished",
+        };
+        format!("hibi: {}", done)
+    }
+
+    pub fn body(&self) -> String {
+        let mut body = format!("{} / {}: {} succeeded", self.target, self.tab, self.succeeded.len());
+        if !self.failed.is_empty() {
+            body.push_str(&format!(", {} failed ({})", self.failed.len(), self.failed.join(", ")));
+        }
+        body
+    }
+}
+
+/// Send `notice` every way `config` asks for. Returns one message per
+/// channel that failed. Blocks for up to a few seconds per channel; call
+/// from a worker thread.
+pub fn send(config: &NotifyConfig, notice: &BatchNotice) -> Vec<String> {
+    let mut errors = Vec::new();
+    if config.desktop
+        && let Err(e) = desktop(&notice.title(), &notice.body())
+    {
+        errors.push(format!("desktop notification: {:#}", e));
+    }
+    if let Some(url) = &config.webhook
+        && let Err(e) = webhook(url, notice)
+    {
+        errors.push(format!("webhook: {:#}", e));
+    }
+    errors
+}
+
+fn desktop(title: &str, body: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title),
+        )]);
+        cmd
+    } else if cfg!(windows) {
+        bail!("not supported on Windows; use a webhook instead");
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.args(["--app-name=hibi", title, body]);
+        cmd
+    };
+    check(run_with_timeout(&mut command, NOTIFY_TIMEOUT_SECS)?)
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn webhook(url: &str, notice: &BatchNotice) -> Result<()> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        bail!("{} is not an http(s) URL", url);
+    }
+    let payload = serde_json::to_string(notice)?;
+    let mut command = Command::new("curl");
+    command.args([
+        "--silent", "--show-error", "--fail",
+        "--max-time", &NOTIFY_TIMEOUT_SECS.to_string(),
+        "--header", "Content-Type: application/json",
+        "--data-binary", &payload,
+        url,
+    ]);
+    check(run_with_timeout(&mut command, NOTIFY_TIMEOUT_SECS + 2).context("Failed to run curl")?)
+}
+
+fn check(output: std::process::Output) -> Result<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    bail!("{}", stderr.trim().lines().last().unwrap_or("exited with an error"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notice_summarises_the_batch() {
+        let notice = BatchNotice::new("Claude", "MCP Servers", false, false,
+            vec!["github".into(), "fetch".into()], vec!["slack".into()]);
+        assert_eq!(notice.title(), "hibi: install finished");
+        assert_eq!(notice.body(), "Claude / MCP Servers: 2 succeeded, 1 failed (slack)");
+        let json = serde_json::to_value(&notice).unwrap();
+        assert_eq!(json["event"], "batch_complete");
+        assert_eq!(json["action"], "install");
+        assert_eq!(json["failed"], serde_json::json!(["slack"]));
+
+        assert_eq!(applescript_string(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+        let err = webhook("ftp://example.com", &notice).unwrap_err();
+        assert!(err.to_string().contains("not an http(s) URL"));
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum PluginStatus {
     Installed,
@@ -13,7 +15,7 @@ impl PluginStatus {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PluginDef {
     pub name: String,
     pub marketplace: String, // marketplace name (e.g., "claude-plugins-official")
@@ -51,6 +53,33 @@ impl Plugin {
     }
 }
 
+/// Marketplaces that lost an installed plugin between `before` and `after`
+/// and no longer have any catalog plugin installed, in catalog order.
+/// These registrations are safe to offer for removal.
+pub fn unused_marketplaces(before: &[Plugin], after: &[Plugin]) -> Vec<String> {
+    let is_installed = |list: &[Plugin], p: &Plugin| {
+        list.iter().any(|o| {
+            o.def.name == p.def.name
+                && o.def.marketplace == p.def.marketplace
+                && o.status == PluginStatus::Installed
+        })
+    };
+
+    let mut unused: Vec<String> = Vec::new();
+    for plugin in after {
+        let marketplace = &plugin.def.marketplace;
+        if unused.contains(marketplace) || !is_installed(before, plugin) || is_installed(after, plugin) {
+            continue;
+        }
+        let still_used = after.iter()
+            .any(|p| &p.def.marketplace == marketplace && p.status == PluginStatus::Installed);
+        if !still_used {
+            unused.push(marketplace.clone());
+        }
+    }
+    unused
+}
+
 /// plugins.yaml 파싱 결과
 /// 형식: Vec<(marketplace_name, source_url, plugin_name, comment)>
 pub type PluginCatalog = Vec<(String, String, String, Option<String>)>;
@@ -266,4 +295,38 @@ marketplaces:
 
         assert_eq!(plugin.short_repo(), "anthropics/claude-plugins-official");
     }
+
+    fn plugin(name: &str, marketplace: &str, status: PluginStatus) -> Plugin {
+        Plugin::new(
+            PluginDef {
+                name: name.to_string(),
+                marketplace: marketplace.to_string(),
+                source: format!("https://github.com/example/{}.git", marketplace),
+                comment: None,
+            },
+            status,
+        )
+    }
+
+    #[test]
+    fn test_unused_marketplaces_after_removal() {
+        use PluginStatus::{Installed, NotInstalled};
+
+        let before = vec![
+            plugin("a", "official", Installed),
+            plugin("b", "official", Installed),
+            plugin("c", "skills", Installed),
+            plugin("d", "other", NotInstalled),
+        ];
+        // Removed "a" and "c": "official" still has "b", "skills" is empty,
+        // "other" never had anything installed so it isn't offered.
+        let after = vec![
+            plugin("a", "official", NotInstalled),
+            plugin("b", "official", Installed),
+            plugin("c", "skills", NotInstalled),
+            plugin("d", "other", NotInstalled),
+        ];
+
+        assert_eq!(unused_marketplaces(&before, &after), vec!["skills".to_string()]);
+    }
 }
@@ -0,0 +1,123 @@
+//! Named install profiles, read from `profiles.yaml` at a source's root.
+//!
+//! ```yaml
+//! profiles:
+//!   minimal:
+//!     description: Reviewer agents and the core rules
+//!     components: [agents/code-reviewer, rules/*, skills/tdd-workflow]
+//!     mcp_servers: [context7]
+//!     plugins: [rust-analyzer-lsp]
+//! ```
+//!
+//! Component entries are `<type>/<name>` as shown in the tabs (`agents`,
+//! `output-styles`, a custom type's name, ...). Every entry may use `*`
+//! and `?`; a component entry may leave off the file extension, and
+//! naming a folder selects everything under it.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::fs::scanner::glob_match;
+use crate::source::ResolvedSource;
+
+pub const PROFILES_FILE: &str = "profiles.yaml";
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Profile {
+    /// Key under `profiles:`.
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    #[serde(default)]
+    pub plugins: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// Profiles in one `profiles.yaml`, sorted by name.
+pub fn parse_profiles(content: &str) -> Result<Vec<Profile>> {
+    let file: ProfilesFile = serde_yaml_bw::from_str(content)?;
+    Ok(file.profiles
+        .into_iter()
+        .map(|(name, profile)| Profile { name, ..profile })
+        .collect())
+}
+
+/// Profiles from every source, sorted by name. A later source's profile
+/// replaces an earlier one with the same name, as with MCP servers and
+/// plugins. Files that fail to parse are skipped and reported.
+pub fn load_profiles(sources: &[ResolvedSource]) -> (Vec<Profile>, Vec<String>) {
+    let mut merged: BTreeMap<String, Profile> = BTreeMap::new();
+    let mut warnings = Vec::new();
+    for source in sources {
+        let path = source.path.join(PROFILES_FILE);
+        if !path.is_file() {
+            continue;
+        }
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| parse_profiles(&content))
+            .with_context(|| format!("Ignoring {}", path.display()));
+        match parsed {
+            Ok(profiles) => merged.extend(profiles.into_iter().map(|p| (p.name.clone(), p))),
+            Err(e) => warnings.push(format!("{:#}", e)),
+        }
+    }
+    (merged.into_values().collect(), warnings)
+}
+
+/// Whether profile entry `pattern` selects the item keyed `key`
+/// (`<type>/<name>` for components, the plain name otherwise).
+pub fn entry_matches(pattern: &str, key: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/').as_bytes();
+    let key = key.replace('\\', "/");
+    let without_ext = key.rsplit_once('.')
+        .filter(|(_, ext)| !ext.contains('/'))
+        .map(|(stem, _)| stem);
+    let folders = key.match_indices('/').map(|(i, _)| &key[..i]);
+
+    glob_match(pattern, key.as_bytes())
+        || without_ext.is_some_and(|stem| glob_match(pattern, stem.as_bytes()))
+        || folders.into_iter().any(|folder| glob_match(pattern, folder.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profiles_sorted_by_name() {
+        let profiles = parse_profiles(
+            "profiles:\n  work:\n    components: [agents/*]\n  minimal:\n    description: Bare\n    mcp_servers: [context7]\n",
+        )
+        .unwrap();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["minimal", "work"]);
+        assert_eq!(profiles[0].description.as_deref(), Some("Bare"));
+        assert_eq!(profiles[0].mcp_servers, ["context7"]);
+        assert!(profiles[0].components.is_empty());
+    }
+
+    #[test]
+    fn entries_match_globs_stems_and_folders() {
+        assert!(entry_matches("agents/architect.md", "agents/architect.md"));
+        assert!(entry_matches("agents/architect", "agents/architect.md"));
+        assert!(entry_matches("rules/*", "rules/perf.md"));
+        assert!(entry_matches("skills/tdd", "skills/tdd/SKILL.md"));
+        assert!(entry_matches("skills/tdd/", "skills/tdd/refs/a.md"));
+        assert!(entry_matches("context?", "context7"));
+        assert!(!entry_matches("skills/tdd", "skills/tdd-extra/SKILL.md"));
+        assert!(!entry_matches("agents/arch", "agents/architect.md"));
+    }
+}
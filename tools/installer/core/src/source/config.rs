@@ -0,0 +1,1016 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::component::ComponentType;
+use crate::target::{CustomTarget, McpTemplates};
+use crate::TargetCli;
+
+/// YAML config entry for a source.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum SourceEntry {
+    #[serde(rename = "git")]
+    Git {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<String>,
+        /// Subdirectory within the repo to use as source root (e.g., "config/claude").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        root: Option<String>,
+        /// Map all files to a specific component type (e.g., "rules", "skills").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        map_to: Option<String>,
+    },
+    #[serde(rename = "local")]
+    Local {
+        path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        root: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        map_to: Option<String>,
+    },
+}
+
+/// Runtime-resolved source kind.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SourceKind {
+    Bundled,
+    Git,
+    Local,
+}
+
+/// Runtime-resolved source with filesystem path.
+#[derive(Clone, Debug)]
+pub struct ResolvedSource {
+    pub label: String,
+    pub kind: SourceKind,
+    pub path: PathBuf,
+    pub is_stale: bool,
+    /// Retained for display; consumed during clone_or_update in resolve_entry.
+    #[allow(dead_code)]
+    pub branch: Option<String>,
+    /// Map all files to a specific component type (e.g., "rules", "skills").
+    pub map_to: Option<String>,
+}
+
+impl ResolvedSource {
+    pub fn bundled(path: &Path) -> Self {
+        Self {
+            label: "bundled".to_string(),
+            kind: SourceKind::Bundled,
+            path: path.to_path_buf(),
+            is_stale: false,
+            branch: None,
+            map_to: None,
+        }
+    }
+}
+
+/// `directories:` entry. A bare folder name remaps only the source folder;
+/// the long form can rename the dest folder too.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum DirectoryEntry {
+    Source(String),
+    Full {
+        source: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dest: Option<String>,
+    },
+}
+
+/// Component types whose folders can be remapped. Hooks and the statusline
+/// are referenced by fixed paths in settings.json, so they stay put.
+const REMAPPABLE_TYPES: [ComponentType; 6] = [
+    ComponentType::Agents,
+    ComponentType::Commands,
+    ComponentType::Contexts,
+    ComponentType::Rules,
+    ComponentType::Skills,
+    ComponentType::OutputStyles,
+];
+
+/// `component_types:` entry defining a whole new component category.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct CustomTypeEntry {
+    pub name: String,
+    pub source: String,
+    /// Dest folder; defaults to `source`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dest: Option<String>,
+    /// File name globs (`*.md`); empty means every file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// Only `copy` is supported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+}
+
+/// A validated custom component type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomType {
+    pub name: String,
+    pub source: String,
+    pub dest: String,
+    pub patterns: Vec<String>,
+}
+
+impl CustomType {
+    pub fn component_type(&self) -> ComponentType {
+        ComponentType::Custom(self.name.clone())
+    }
+}
+
+/// Names and folders the built-in types already own.
+const RESERVED_NAMES: [&str; 14] = [
+    "agents", "commands", "contexts", "rules", "skills", "hooks", "output-styles",
+    "styles", "statusline", "config", "mcp", "mcps", "plugins", "sessions",
+];
+
+/// Resolved `directories:` mapping: source and dest folder name per
+/// component type, plus any custom types. Types without an entry use their
+/// default folder.
+#[derive(Clone, Debug, Default)]
+pub struct DirectoryMap {
+    dirs: HashMap<ComponentType, (String, String)>,
+    custom: Vec<CustomType>,
+}
+
+impl DirectoryMap {
+    /// Build the map from config entries, skipping (with a warning) unknown
+    /// types and folder names that are not plain relative paths.
+    pub fn from_entries(entries: &BTreeMap<String, DirectoryEntry>) -> (Self, Vec<String>) {
+        let mut map = Self::default();
+        let mut warnings = Vec::new();
+
+        for (key, entry) in entries {
+            let Some(comp_type) = REMAPPABLE_TYPES.iter().find(|t| t.display_name() == key.to_lowercase()) else {
+                warnings.push(format!("directories: unknown component type '{}'", key));
+                continue;
+            };
+            let (source, dest) = match entry {
+                DirectoryEntry::Source(source) => (source.clone(), comp_type.display_name().to_string()),
+                DirectoryEntry::Full { source, dest } => (
+                    source.clone(),
+                    dest.clone().unwrap_or_else(|| comp_type.display_name().to_string()),
+                ),
+            };
+            if !is_plain_relative(&source) || !is_plain_relative(&dest) {
+                warnings.push(format!("directories: invalid folder for '{}'", key));
+                continue;
+            }
+            map.dirs.insert(comp_type.clone(), (source, dest));
+        }
+
+        (map, warnings)
+    }
+
+    /// Add the `component_types:` definitions, skipping (with a warning)
+    /// ones that clash with built-in or earlier names, use an unsupported
+    /// strategy, or have unsafe folders.
+    pub fn with_custom_types(mut self, entries: &[CustomTypeEntry], warnings: &mut Vec<String>) -> Self {
+        for entry in entries {
+            let name = entry.name.trim();
+            let dest = entry.dest.clone().unwrap_or_else(|| entry.source.clone());
+            let problem = if name.is_empty() {
+                Some("empty name".to_string())
+            } else if RESERVED_NAMES.contains(&name.to_lowercase().as_str())
+                || self.custom.iter().any(|c| c.name.eq_ignore_ascii_case(name))
+            {
+                Some("name already in use".to_string())
+            } else if entry.strategy.as_deref().is_some_and(|s| s != "copy") {
+                Some(format!("unsupported strategy '{}'", entry.strategy.as_deref().unwrap_or_default()))
+            } else if !is_plain_relative(&entry.source) || !is_plain_relative(&dest) {
+                Some("invalid folder".to_string())
+            } else if RESERVED_NAMES.contains(&dest.to_lowercase().as_str()) {
+                Some(format!("dest folder '{}' belongs to a built-in type", dest))
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                warnings.push(format!("component_types: skipping '{}': {}", entry.name, problem));
+                continue;
+            }
+            self.custom.push(CustomType {
+                name: name.to_string(),
+                source: entry.source.clone(),
+                dest,
+                patterns: entry.patterns.clone(),
+            });
+        }
+        self
+    }
+
+    /// Custom component types in config order.
+    pub fn custom_types(&self) -> &[CustomType] {
+        &self.custom
+    }
+
+    /// Source folder name for `comp_type`.
+    pub fn source_dir<'a>(&'a self, comp_type: &'a ComponentType) -> &'a str {
+        self.dirs.get(comp_type).map(|(source, _)| source.as_str()).unwrap_or(comp_type.display_name())
+    }
+
+    /// Dest folder name for `comp_type`.
+    pub fn dest_dir<'a>(&'a self, comp_type: &'a ComponentType) -> &'a str {
+        self.dirs.get(comp_type).map(|(_, dest)| dest.as_str()).unwrap_or(comp_type.display_name())
+    }
+
+    /// The same mapping with default source folders, for the bundled source
+    /// whose layout is fixed.
+    pub fn dest_only(&self) -> Self {
+        let dirs = self.dirs.iter()
+            .map(|(t, (_, dest))| (t.clone(), (t.display_name().to_string(), dest.clone())))
+            .collect();
+        Self { dirs, custom: self.custom.clone() }
+    }
+
+    /// Source folder names that differ from the defaults, including those
+    /// of custom types.
+    pub fn custom_source_dirs(&self) -> impl Iterator<Item = &str> {
+        self.dirs.values()
+            .map(|(source, _)| source.as_str())
+            .chain(self.custom.iter().map(|c| c.source.as_str()))
+    }
+}
+
+/// `targets:` entry describing a CLI hibi doesn't know about, e.g.
+///
+/// ```yaml
+/// targets:
+///   - name: Amp
+///     program: amp
+///     dest: ~/.config/amp
+///     tabs: [skills, config, mcp]
+///     config_files: [AGENTS.md]
+///     mcp_add: mcp add {name} --env {env} -- {command}
+///     mcp_add_http: mcp add {name} {url}
+///     mcp_remove: mcp remove {name}
+///     mcp_list: mcp list
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct CustomTargetEntry {
+    pub name: String,
+    pub program: String,
+    /// Config dir; `~/...` or relative to the home directory.
+    pub dest: String,
+    /// `agents`, `commands`, `contexts`, `rules`, `skills`,
+    /// `output-styles`, `config` and/or `mcp`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tabs: Vec<String>,
+    /// Files the `config` tab installs; defaults to `AGENTS.md`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub config_files: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_add: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_add_http: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_remove: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_list: Option<String>,
+}
+
+impl CustomTargetEntry {
+    /// The validated target, or why it can't be used.
+    fn resolve(&self, taken: &[CustomTarget]) -> std::result::Result<CustomTarget, String> {
+        let name = self.name.trim();
+        let config_dir = self.dest.trim().trim_start_matches("~/").trim_start_matches("~\\");
+        if name.is_empty() || self.program.trim().is_empty() {
+            return Err("name and program are required".to_string());
+        }
+        if TargetCli::ALL.iter().any(|t| t.display_name().eq_ignore_ascii_case(name))
+            || taken.iter().any(|t| t.name.eq_ignore_ascii_case(name))
+        {
+            return Err("name already in use".to_string());
+        }
+        if !is_plain_relative(config_dir) {
+            return Err(format!("dest '{}' must be inside the home directory", self.dest));
+        }
+
+        let mut component_types = Vec::new();
+        let (mut config, mut mcp) = (false, false);
+        for tab in &self.tabs {
+            match tab.trim().to_lowercase().as_str() {
+                "config" => config = true,
+                "mcp" | "mcps" => mcp = true,
+                // Hooks, the statusline and plugins are wired into Claude
+                // Code's settings.json, so only plain folder types qualify.
+                other => {
+                    let Some(comp_type) = REMAPPABLE_TYPES.iter().find(|t| t.display_name() == other) else {
+                        return Err(format!("unsupported tab '{}'", tab));
+                    };
+                    if !component_types.contains(comp_type) {
+                        component_types.push(comp_type.clone());
+                    }
+                }
+            }
+        }
+
+        if component_types.is_empty() && !config && !mcp {
+            return Err("no tabs".to_string());
+        }
+
+        let config_files = match (config, self.config_files.is_empty()) {
+            (false, _) => Vec::new(),
+            (true, true) => vec!["AGENTS.md".to_string()],
+            (true, false) => self.config_files.clone(),
+        };
+        if !config_files.iter().all(|f| is_plain_relative(f)) {
+            return Err("invalid config file name".to_string());
+        }
+
+        let mcp = match (mcp, &self.mcp_add, &self.mcp_remove) {
+            (false, _, _) => None,
+            (true, Some(add), Some(remove)) => Some(McpTemplates {
+                add: add.clone(),
+                add_http: self.mcp_add_http.clone(),
+                remove: remove.clone(),
+                list: self.mcp_list.clone(),
+            }),
+            (true, _, _) => return Err("the mcp tab needs mcp_add and mcp_remove".to_string()),
+        };
+
+        Ok(CustomTarget {
+            name: name.to_string(),
+            program: self.program.trim().to_string(),
+            config_dir: config_dir.to_string(),
+            component_types,
+            config_files,
+            mcp,
+        })
+    }
+}
+
+/// Validate `targets:` entries, skipping (with a warning) unusable ones.
+pub fn resolve_targets(entries: &[CustomTargetEntry]) -> (Vec<CustomTarget>, Vec<String>) {
+    let mut targets = Vec::new();
+    let mut warnings = Vec::new();
+    for entry in entries {
+        match entry.resolve(&targets) {
+            Ok(target) => targets.push(target),
+            Err(problem) => warnings.push(format!("targets: skipping '{}': {}", entry.name, problem)),
+        }
+    }
+    (targets, warnings)
+}
+
+/// A folder name made only of normal path components (no `..`, no root).
+fn is_plain_relative(name: &str) -> bool {
+    let path = Path::new(name);
+    !name.is_empty() && path.components().all(|c| matches!(c, std::path::Component::Normal(_)))
+}
+
+/// Top-level YAML structure for `~/.hibi/sources.yaml`.
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct SourcesConfig {
+    #[serde(default)]
+    sources: Vec<SourceEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_update: Option<bool>,
+    /// Component type -> custom source (and dest) folder name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    directories: BTreeMap<String, DirectoryEntry>,
+    /// User-defined component categories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    component_types: Vec<CustomTypeEntry>,
+    /// Commands run around every install/remove batch.
+    #[serde(default, skip_serializing_if = "BatchHooks::is_empty")]
+    batch_hooks: BatchHooks,
+    /// Seconds between background MCP health checks; absent means off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mcp_health_interval_secs: Option<u64>,
+    /// Stop a batch at its first failed item and roll back the rest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    strict: Option<bool>,
+    /// Estimated tokens the always-loaded files may add up to before the
+    /// list warns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token_budget: Option<u64>,
+    /// Theme to start with (`mocha`, `latte`, `high-contrast` or a custom
+    /// palette's name), over the one used last.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    theme: Option<String>,
+    /// Project directories local-scope MCP installs may write into; their
+    /// subdirectories are trusted too.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    trusted_projects: Vec<String>,
+    /// Most threads a scan may use; absent means one per core.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scan_threads: Option<usize>,
+    /// Refresh statuses when a batch finishes (default on); off leaves it
+    /// to `R`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_refresh: Option<bool>,
+    /// Seconds to wait after a batch before refreshing statuses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    refresh_delay_secs: Option<u64>,
+    /// Leave files with uncommitted changes in a local source out of
+    /// installs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    committed_only: Option<bool>,
+    /// Additional CLIs offered on the start screen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    targets: Vec<CustomTargetEntry>,
+    /// Desktop notification and/or webhook when a batch finishes.
+    #[serde(default, skip_serializing_if = "NotifyConfig::is_empty")]
+    notify: NotifyConfig,
+    /// Write timestamps in reports and exported scripts in UTC instead of
+    /// local time with an offset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    log_utc: Option<bool>,
+}
+
+/// Shell commands run before and after each install/remove batch, e.g.
+/// `git -C ~/dotfiles pull` before and `claude --version` after.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct BatchHooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post: Vec<String>,
+}
+
+impl BatchHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre.is_empty() && self.post.is_empty()
+    }
+}
+
+/// How to announce a finished install/remove batch, e.g.
+/// `notify: {desktop: true, webhook: https://hooks.example.com/hibi}`.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct NotifyConfig {
+    /// Show a desktop notification (`notify-send` on Linux, Notification
+    /// Center on macOS).
+    #[serde(default)]
+    pub desktop: bool,
+    /// URL the batch summary is POSTed to as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+}
+
+impl NotifyConfig {
+    pub fn is_empty(&self) -> bool {
+        !self.desktop && self.webhook.is_none()
+    }
+}
+
+/// Load source entries from `~/.hibi/sources.yaml`.
+/// Returns empty vec if file doesn't exist or is unparseable.
+/// Maximum allowed size for sources.yaml (64 KB).
+const MAX_CONFIG_SIZE: u64 = 65_536;
+
+pub fn load_config() -> Result<(Vec<SourceEntry>, bool)> {
+    let config = read_config()?;
+    let auto_update = config.auto_update.unwrap_or(true);
+    Ok((config.sources, auto_update))
+}
+
+/// Load the `directories:` mapping and `component_types:` from
+/// `~/.hibi/sources.yaml`, with warnings for entries that were skipped.
+pub fn load_directories() -> Result<(DirectoryMap, Vec<String>)> {
+    let config = read_config()?;
+    let (map, mut warnings) = DirectoryMap::from_entries(&config.directories);
+    let map = map.with_custom_types(&config.component_types, &mut warnings);
+    Ok((map, warnings))
+}
+
+/// Load `batch_hooks:` from `~/.hibi/sources.yaml`.
+pub fn load_batch_hooks() -> Result<BatchHooks> {
+    Ok(read_config()?.batch_hooks)
+}
+
+/// Shortest accepted `mcp_health_interval_secs`; one `mcp list` health
+/// check can itself take several seconds.
+pub const MIN_MCP_HEALTH_INTERVAL_SECS: u64 = 10;
+
+/// Load `mcp_health_interval_secs:` from `~/.hibi/sources.yaml`, raised to
+/// `MIN_MCP_HEALTH_INTERVAL_SECS`. `None` (the default) or `0` disables polling.
+pub fn load_mcp_health_interval() -> Result<Option<u64>> {
+    Ok(read_config()?
+        .mcp_health_interval_secs
+        .filter(|&secs| secs > 0)
+        .map(|secs| secs.max(MIN_MCP_HEALTH_INTERVAL_SECS)))
+}
+
+/// Load `strict:` from `~/.hibi/sources.yaml` (default off).
+pub fn load_strict_mode() -> Result<bool> {
+    Ok(read_config()?.strict.unwrap_or(false))
+}
+
+/// Load `committed_only:` from `~/.hibi/sources.yaml` (default off).
+pub fn load_committed_only() -> Result<bool> {
+    Ok(read_config()?.committed_only.unwrap_or(false))
+}
+
+/// Budget used when `token_budget:` is absent. Claude Code itself warns
+/// about instruction files past roughly this size.
+pub const DEFAULT_TOKEN_BUDGET: u64 = 10_000;
+
+/// Load `token_budget:` from `~/.hibi/sources.yaml`, falling back to
+/// `DEFAULT_TOKEN_BUDGET`.
+pub fn load_token_budget() -> Result<u64> {
+    Ok(read_config()?.token_budget.unwrap_or(DEFAULT_TOKEN_BUDGET))
+}
+
+/// Load `theme:` from `~/.hibi/sources.yaml`, if set.
+pub fn load_theme() -> Result<Option<String>> {
+    Ok(read_config()?.theme)
+}
+
+/// Load `notify:` from `~/.hibi/sources.yaml`.
+pub fn load_notify() -> Result<NotifyConfig> {
+    Ok(read_config()?.notify)
+}
+
+/// Load `log_utc:` from `~/.hibi/sources.yaml` (default off).
+pub fn load_log_utc() -> Result<bool> {
+    Ok(read_config()?.log_utc.unwrap_or(false))
+}
+
+/// Load `scan_threads:` from `~/.hibi/sources.yaml`; `None` (or `0`)
+/// means no cap.
+pub fn load_scan_threads() -> Result<Option<usize>> {
+    Ok(read_config()?.scan_threads.filter(|&n| n > 0))
+}
+
+/// When statuses are refreshed after an install/remove batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RefreshAfterBatch {
+    #[default]
+    Immediately,
+    /// In the background, this many seconds after the batch.
+    Delayed(u64),
+    /// Only when the user rescans with `R`.
+    Manual,
+}
+
+/// Load `auto_refresh:` and `refresh_delay_secs:` from `~/.hibi/sources.yaml`.
+pub fn load_refresh_after_batch() -> Result<RefreshAfterBatch> {
+    let config = read_config()?;
+    Ok(match (config.auto_refresh, config.refresh_delay_secs) {
+        (Some(false), _) => RefreshAfterBatch::Manual,
+        (_, Some(secs)) if secs > 0 => RefreshAfterBatch::Delayed(secs),
+        _ => RefreshAfterBatch::Immediately,
+    })
+}
+
+/// Load `targets:` from `~/.hibi/sources.yaml`, with warnings for entries
+/// that were skipped.
+pub fn load_targets() -> Result<(Vec<CustomTarget>, Vec<String>)> {
+    Ok(resolve_targets(&read_config()?.targets))
+}
+
+fn read_config() -> Result<SourcesConfig> {
+    let config_path = config_path()?;
+    if !config_path.exists() {
+        return Ok(SourcesConfig::default());
+    }
+
+    let metadata = std::fs::metadata(&config_path)?;
+    if metadata.len() > MAX_CONFIG_SIZE {
+        anyhow::bail!("sources.yaml exceeds 64KB size limit");
+    }
+
+    let content = std::fs::read_to_string(&config_path)?;
+    Ok(serde_yaml_bw::from_str(&content)?)
+}
+
+/// Path to `~/.hibi/sources.yaml`.
+fn config_path() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+    Ok(home.join(".hibi").join("sources.yaml"))
+}
+
+/// Label and folder name of the per-machine overlay source.
+pub const LOCAL_OVERLAY_NAME: &str = "source.local";
+
+/// `~/.hibi/source.local/`: files kept on this machine only, laid out like
+/// a source, that take precedence over every configured source.
+pub fn local_overlay_dir() -> Option<PathBuf> {
+    Some(dirs::home_dir()?.join(".hibi").join(LOCAL_OVERLAY_NAME))
+}
+
+/// Save source entries to `~/.hibi/sources.yaml`, keeping every other
+/// setting in the file as it is.
+pub fn save_config(entries: &[SourceEntry], auto_update: bool) -> Result<()> {
+    let mut config = read_config()?;
+    config.set_sources(entries, auto_update);
+    write_config(&config)
+}
+
+impl SourcesConfig {
+    fn set_sources(&mut self, entries: &[SourceEntry], auto_update: bool) {
+        self.sources = entries.to_vec();
+        // Omit auto_update from YAML when it's the default (true)
+        self.auto_update = if auto_update { None } else { Some(false) };
+    }
+}
+
+fn write_config(config: &SourcesConfig) -> Result<()> {
+    let config_path = config_path()?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let yaml = serde_yaml_bw::to_string(config)?;
+    std::fs::write(&config_path, yaml)?;
+    Ok(())
+}
+
+/// Load `trusted_projects:` from `~/.hibi/sources.yaml`.
+pub fn load_trusted_projects() -> Result<Vec<PathBuf>> {
+    Ok(read_config()?.trusted_projects.into_iter().map(PathBuf::from).collect())
+}
+
+/// `path` with `~` expanded, and symlinks and `..` resolved when it exists.
+pub fn project_key(path: &Path) -> PathBuf {
+    let path = expand_tilde(path);
+    std::fs::canonicalize(&path).unwrap_or(path)
+}
+
+/// Check a project path typed for local MCP scope: `~` expanded, made
+/// absolute against the working directory, and canonicalized. Fails with
+/// the resolved path when it isn't an existing, writable directory, which
+/// `claude mcp add` would otherwise only report halfway through a batch.
+pub fn resolve_project_path(input: &str) -> Result<PathBuf> {
+    let path = expand_tilde(Path::new(input.trim()));
+    let path = if path.is_absolute() { path } else { std::env::current_dir()?.join(path) };
+    let resolved = std::fs::canonicalize(&path)
+        .map_err(|_| anyhow::anyhow!("{} does not exist", path.display()))?;
+    if !resolved.is_dir() {
+        anyhow::bail!("{} is not a directory", resolved.display());
+    }
+    let probe = resolved.join(format!(".hibi-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"")
+        .map_err(|_| anyhow::anyhow!("{} is not writable", resolved.display()))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(resolved)
+}
+
+/// Whether `path` is one of `trusted`, or inside one.
+pub fn is_trusted_project(trusted: &[PathBuf], path: &Path) -> bool {
+    let path = project_key(path);
+    trusted.iter().any(|t| path.starts_with(project_key(t)))
+}
+
+/// Add `path` to `trusted_projects:` in `~/.hibi/sources.yaml`, returning
+/// the entry as stored.
+pub fn trust_project(path: &Path) -> Result<PathBuf> {
+    let key = project_key(path);
+    let mut config = read_config()?;
+    let entry = key.to_string_lossy().to_string();
+    if !config.trusted_projects.contains(&entry) {
+        config.trusted_projects.push(entry);
+        write_config(&config)?;
+    }
+    Ok(key)
+}
+
+/// Expand `~` prefix to home directory. Handles `~/path`, `~\path`, and bare `~`.
+pub fn expand_tilde(path: &Path) -> PathBuf {
+    let s = path.to_string_lossy();
+    if s == "~" {
+        return dirs::home_dir().unwrap_or_else(|| path.to_path_buf());
+    }
+    if s.starts_with("~/") || s.starts_with("~\\") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(&s[2..]);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Inverse of [`expand_tilde`] for display: paths under the home
+/// directory are shown as `~/...`.
+pub fn contract_tilde(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => Path::new("~").join(rest).to_string_lossy().to_string(),
+        None => path.to_string_lossy().to_string(),
+    }
+}
+
+/// Validate a git URL: HTTPS only, no credentials.
+pub fn validate_git_url(url: &str) -> Result<()> {
+    // Check credentials first to avoid leaking them in error messages
+    if url.contains('@') {
+        anyhow::bail!("Git URL must not contain credentials");
+    }
+    if !url.starts_with("https://") {
+        anyhow::bail!("Only HTTPS git URLs are allowed");
+    }
+    Ok(())
+}
+
+/// Validate a git branch name: no flags, no traversal.
+pub fn validate_branch(branch: &str) -> Result<()> {
+    if branch.starts_with('-') {
+        anyhow::bail!("Branch name must not start with '-': {}", branch);
+    }
+    if branch.contains("..") || branch.contains('\0') {
+        anyhow::bail!("Invalid branch name: {}", branch);
+    }
+    Ok(())
+}
+
+/// Validate a local path: no path traversal, not inside ~/.claude/.
+/// Canonicalizes to resolve symlinks before checking.
+pub fn validate_local_path(path: &Path) -> Result<()> {
+    let expanded = expand_tilde(path);
+
+    // Reject path traversal in the raw string
+    let s = expanded.to_string_lossy();
+    if s.contains("..") {
+        anyhow::bail!("Path traversal (..) not allowed in source path: {}", s);
+    }
+
+    // Canonicalize to resolve symlinks, then re-check
+    let canonical = expanded.canonicalize()
+        .unwrap_or_else(|_| expanded.clone());
+
+    if let Some(home) = dirs::home_dir() {
+        let claude_dir = home.join(".claude");
+        let canonical_claude = claude_dir.canonicalize()
+            .unwrap_or(claude_dir);
+        if canonical.starts_with(&canonical_claude) {
+            anyhow::bail!(
+                "Source path resolves to inside ~/.claude/: {}",
+                canonical.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn project_path_must_be_an_existing_writable_directory() {
+        let dir = temp_dir("project");
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        std::fs::write(dir.join("file.txt"), "x").unwrap();
+
+        let resolved = resolve_project_path(&format!(" {}/app/../app ", dir.display()));
+        let missing = resolve_project_path(&dir.join("nope").to_string_lossy()).unwrap_err().to_string();
+        let file = resolve_project_path(&dir.join("file.txt").to_string_lossy()).unwrap_err().to_string();
+        let leftovers = std::fs::read_dir(dir.join("app")).unwrap().count();
+        let canonical = std::fs::canonicalize(dir.join("app")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(resolved.unwrap(), canonical);
+        assert_eq!(leftovers, 0);
+        assert!(missing.ends_with("nope does not exist"), "{missing}");
+        assert!(file.ends_with("file.txt is not a directory"), "{file}");
+    }
+
+    #[test]
+    fn targets_are_validated_into_custom_targets() {
+        let entries: Vec<CustomTargetEntry> = serde_yaml_bw::from_str(r#"
+- name: Amp
+  program: amp
+  dest: ~/.config/amp
+  tabs: [skills, config, mcp]
+  mcp_add: mcp add {name} -- {command}
+  mcp_remove: mcp remove {name}
+- name: codex cli
+  program: other
+  dest: .other
+  tabs: [skills]
+- name: Hooked
+  program: hooked
+  dest: .hooked
+  tabs: [hooks]
+- name: NoMcp
+  program: nomcp
+  dest: ../outside
+  tabs: [mcp]
+"#).unwrap();
+        let (targets, warnings) = resolve_targets(&entries);
+
+        assert_eq!(targets.len(), 1);
+        let amp = &targets[0];
+        assert_eq!((amp.name.as_str(), amp.config_dir.as_str()), ("Amp", ".config/amp"));
+        assert_eq!(amp.component_types, [ComponentType::Skills]);
+        assert_eq!(amp.config_files, ["AGENTS.md"]);
+        assert_eq!(amp.mcp.as_ref().map(|m| m.remove.as_str()), Some("mcp remove {name}"));
+        assert_eq!(warnings, [
+            "targets: skipping 'codex cli': name already in use",
+            "targets: skipping 'Hooked': unsupported tab 'hooks'",
+            "targets: skipping 'NoMcp': dest '../outside' must be inside the home directory",
+        ]);
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        let path = Path::new("~/foo/bar");
+        let expanded = expand_tilde(path);
+        assert!(!expanded.to_string_lossy().starts_with('~'));
+        assert!(expanded.to_string_lossy().ends_with("foo/bar")
+            || expanded.to_string_lossy().ends_with("foo\\bar"));
+    }
+
+    #[test]
+    fn test_expand_tilde_bare() {
+        let path = Path::new("~");
+        let expanded = expand_tilde(path);
+        if dirs::home_dir().is_some() {
+            assert!(!expanded.to_string_lossy().contains('~'));
+        }
+    }
+
+    #[test]
+    fn test_trusted_projects_cover_their_subdirectories() {
+        let root = temp_dir("trust");
+        std::fs::create_dir_all(root.join("app/sub")).unwrap();
+        let trusted = vec![root.join("app")];
+
+        assert!(is_trusted_project(&trusted, &root.join("app")));
+        assert!(is_trusted_project(&trusted, &root.join("app/sub")));
+        assert!(is_trusted_project(&trusted, &root.join("app/sub/../sub")), "resolved before comparing");
+        assert!(!is_trusted_project(&trusted, &root));
+        assert!(!is_trusted_project(&trusted, &root.join("app-other")), "not a prefix match on the name");
+        assert!(!is_trusted_project(&[], &root.join("app")));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_directory_map_parses_short_and_long_forms() {
+        let yaml = "contexts: prompts\nrules: { source: guidelines, dest: team-rules }\nhooks: x\nskills: ../escape\n";
+        let entries: BTreeMap<String, DirectoryEntry> = serde_yaml_bw::from_str(yaml).unwrap();
+        let (map, warnings) = DirectoryMap::from_entries(&entries);
+
+        assert_eq!(map.source_dir(&ComponentType::Contexts), "prompts");
+        assert_eq!(map.dest_dir(&ComponentType::Contexts), "contexts");
+        assert_eq!(map.source_dir(&ComponentType::Rules), "guidelines");
+        assert_eq!(map.dest_dir(&ComponentType::Rules), "team-rules");
+        assert_eq!(map.source_dir(&ComponentType::Skills), "skills");
+        assert_eq!(warnings.len(), 2, "hooks is not remappable and ../escape is rejected");
+    }
+
+    #[test]
+    fn test_custom_types_reject_clashes_and_unknown_strategies() {
+        let yaml = "
+- { name: snippets, source: snippets, patterns: ['*.md'] }
+- { name: Agents, source: more-agents }
+- { name: Snippets, source: other }
+- { name: links, source: links, strategy: symlink }
+- { name: notes, source: notes, dest: rules }
+";
+        let entries: Vec<CustomTypeEntry> = serde_yaml_bw::from_str(yaml).unwrap();
+        let mut warnings = Vec::new();
+        let map = DirectoryMap::default().with_custom_types(&entries, &mut warnings);
+
+        assert_eq!(map.custom_types(), [CustomType {
+            name: "snippets".into(),
+            source: "snippets".into(),
+            dest: "snippets".into(),
+            patterns: vec!["*.md".into()],
+        }]);
+        assert_eq!(warnings.len(), 4);
+    }
+
+    #[test]
+    fn test_batch_hooks_default_to_empty_and_are_omitted_on_save() {
+        let config: SourcesConfig = serde_yaml_bw::from_str("batch_hooks:\n  pre: [\"git -C ~/dotfiles pull\"]\n").unwrap();
+        assert_eq!(config.batch_hooks.pre, ["git -C ~/dotfiles pull"]);
+        assert!(config.batch_hooks.post.is_empty());
+
+        let yaml = serde_yaml_bw::to_string(&SourcesConfig::default()).unwrap();
+        assert!(!yaml.contains("batch_hooks"));
+    }
+
+    #[test]
+    fn test_contract_tilde_round_trips() {
+        let path = Path::new("~/foo/bar");
+        assert_eq!(expand_tilde(Path::new(&contract_tilde(&expand_tilde(path)))), expand_tilde(path));
+        assert_eq!(contract_tilde(Path::new("/definitely/not/home")), "/definitely/not/home");
+    }
+
+    #[test]
+    fn test_validate_git_url_https_ok() {
+        assert!(validate_git_url("https://github.com/user/repo.git").is_ok());
+    }
+
+    #[test]
+    fn test_validate_git_url_ssh_rejected() {
+        assert!(validate_git_url("git@github.com:user/repo.git").is_err());
+    }
+
+    #[test]
+    fn test_validate_git_url_credentials_rejected() {
+        assert!(validate_git_url("https://user@github.com/repo.git").is_err());
+    }
+
+    #[test]
+    fn test_validate_git_url_http_rejected() {
+        assert!(validate_git_url("http://github.com/user/repo.git").is_err());
+    }
+
+    #[test]
+    fn test_validate_branch_normal_ok() {
+        assert!(validate_branch("main").is_ok());
+        assert!(validate_branch("feature/foo").is_ok());
+    }
+
+    #[test]
+    fn test_validate_branch_flag_rejected() {
+        assert!(validate_branch("--upload-pack=evil").is_err());
+        assert!(validate_branch("-c").is_err());
+    }
+
+    #[test]
+    fn test_validate_branch_traversal_rejected() {
+        assert!(validate_branch("main..evil").is_err());
+    }
+
+    #[test]
+    fn test_validate_local_path_traversal_rejected() {
+        assert!(validate_local_path(Path::new("/tmp/../etc/passwd")).is_err());
+    }
+
+    #[test]
+    fn test_validate_local_path_claude_dir_rejected() {
+        let home = dirs::home_dir().unwrap();
+        let claude_path = home.join(".claude").join("agents");
+        assert!(validate_local_path(&claude_path).is_err());
+    }
+
+    #[test]
+    fn test_validate_local_path_normal_ok() {
+        assert!(validate_local_path(Path::new("/tmp/my-configs")).is_ok());
+    }
+
+    #[test]
+    fn test_source_entry_yaml_roundtrip() {
+        let entries = vec![
+            SourceEntry::Git {
+                url: "https://github.com/user/repo.git".to_string(),
+                branch: Some("main".to_string()),
+                root: None,
+                map_to: None,
+            },
+            SourceEntry::Git {
+                url: "https://github.com/other/cfg.git".to_string(),
+                branch: None,
+                root: Some("config/claude".to_string()),
+                map_to: None,
+            },
+            SourceEntry::Local {
+                path: PathBuf::from("/tmp/my-configs"),
+                root: None,
+                map_to: Some("rules".to_string()),
+            },
+        ];
+
+        let config = super::SourcesConfig {
+            sources: entries.clone(),
+            auto_update: None,
+            ..Default::default()
+        };
+
+        let yaml = serde_yaml_bw::to_string(&config).unwrap();
+        let parsed: super::SourcesConfig = serde_yaml_bw::from_str(&yaml).unwrap();
+
+        assert_eq!(parsed.sources.len(), 3);
+        // Verify tag serialization produces "type: git" / "type: local"
+        assert!(yaml.contains("type: git"));
+        assert!(yaml.contains("type: local"));
+        assert!(yaml.contains("https://github.com/user/repo.git"));
+        assert!(yaml.contains("root: config/claude")); // root field serialized
+        assert!(yaml.contains("map_to: rules"));       // map_to field serialized
+        assert!(!yaml.contains("auto_update")); // Omitted when None
+    }
+
+    #[test]
+    fn saving_sources_keeps_the_other_settings() {
+        let mut config: SourcesConfig = serde_yaml_bw::from_str(r#"
+sources: []
+log_utc: true
+notify:
+  desktop: true
+  webhook: https://example.com/hook
+theme: latte
+strict: true
+"#).unwrap();
+        let entries = vec![SourceEntry::Local { path: PathBuf::from("/tmp/my-configs"), root: None, map_to: None }];
+        config.set_sources(&entries, false);
+
+        let saved: SourcesConfig = serde_yaml_bw::from_str(&serde_yaml_bw::to_string(&config).unwrap()).unwrap();
+        assert_eq!(saved.sources.len(), 1);
+        assert_eq!(saved.auto_update, Some(false));
+        assert_eq!(saved.log_utc, Some(true));
+        assert!(saved.notify.desktop);
+        assert_eq!(saved.notify.webhook.as_deref(), Some("https://example.com/hook"));
+        assert_eq!(saved.theme.as_deref(), Some("latte"));
+        assert_eq!(saved.strict, Some(true));
+    }
+}
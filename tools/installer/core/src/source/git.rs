@@ -10,6 +10,7 @@ use super::config::{validate_branch, validate_git_url};
 const CLONE_TIMEOUT_SECS: u64 = 60;
 const FETCH_TIMEOUT_SECS: u64 = 30;
 const RESET_TIMEOUT_SECS: u64 = 10;
+const STATUS_TIMEOUT_SECS: u64 = 10;
 
 /// Clone or update a git repository into the cache directory.
 /// Returns the local path to the cached repo.
@@ -78,9 +79,7 @@ pub fn remove_cache(url: &str) -> Result<bool> {
 /// this directory. The bundled source is now package-embedded, so the cache
 /// is dead data; this performs a one-time cleanup.
 /// Returns `Ok(true)` if the cache was removed, `Ok(false)` if none existed.
-pub fn cleanup_bundled_cache() -> Result<bool> {
-    let home = dirs::home_dir()
-        .ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+pub fn cleanup_bundled_cache(home: &Path) -> Result<bool> {
     // Fixed path (~/.hibi/cache/bundled), built from home_dir — safe by construction
     // (no external input, unlike remove_cache which sanitizes a user-supplied URL).
     let bundled_dir = home.join(".hibi").join("cache").join("bundled");
@@ -141,6 +140,59 @@ fn update_repo(repo_dir: &Path, branch: Option<&str>) -> Result<()> {
     )
 }
 
+/// One changed file in `git status`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusEntry {
+    /// The two-letter status, e.g. ` M` or `??`.
+    pub code: String,
+    /// The file, relative to the folder asked about.
+    pub path: String,
+    /// The old name of a renamed or copied file.
+    pub from: Option<String>,
+}
+
+impl std::fmt::Display for StatusEntry {
+    /// The entry as `git status --short` shows it.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.from {
+            Some(from) => write!(f, "{} {} -> {}", self.code, from, self.path),
+            None => write!(f, "{} {}", self.code, self.path),
+        }
+    }
+}
+
+/// `git status` of the files under `dir`, with paths relative to `dir`.
+/// `None` when `dir` isn't in a git work tree or git can't be run; empty
+/// when everything is committed.
+pub fn uncommitted_changes(dir: &Path) -> Option<Vec<StatusEntry>> {
+    let git = |args: &[&str]| {
+        let mut cmd = Command::new("git");
+        cmd.args(args).current_dir(dir);
+        crate::fs::run_with_timeout(&mut cmd, STATUS_TIMEOUT_SECS).ok().filter(|out| out.status.success())
+    };
+    // Porcelain paths are relative to the top of the work tree.
+    let prefix = git(&["rev-parse", "--show-prefix"])?;
+    let status = git(&["status", "--porcelain=v1", "-z", "--untracked-files=all", "--", "."])?;
+    Some(parse_status(
+        &String::from_utf8_lossy(&status.stdout),
+        String::from_utf8_lossy(&prefix.stdout).trim_end_matches('\n'),
+    ))
+}
+
+/// Parse `git status --porcelain=v1 -z` output. Paths aren't quoted and
+/// a rename or copy is followed by a second field with the old name.
+fn parse_status(out: &str, prefix: &str) -> Vec<StatusEntry> {
+    let relative = |path: &str| path.strip_prefix(prefix).unwrap_or(path).to_string();
+    let mut fields = out.split('\0').filter(|field| !field.is_empty());
+    let mut entries = Vec::new();
+    while let Some(field) = fields.next() {
+        let (Some(code), Some(path)) = (field.get(..2), field.get(3..)) else { continue };
+        let from = code.contains(['R', 'C']).then(|| fields.next().map(relative)).flatten();
+        entries.push(StatusEntry { code: code.to_string(), path: relative(path), from });
+    }
+    entries
+}
+
 fn run_git_command(args: &[&str], working_dir: Option<&Path>, timeout_secs: u64) -> Result<()> {
     let mut cmd = Command::new("git");
     cmd.args(args);
@@ -220,6 +272,17 @@ fn unix_timestamp_now() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn status_fields_split_on_nul_with_renames_taking_two() {
+        let out = " M sub/a.md\0R  sub/a -> b.md\0sub/old.md\0?? sub/with space.md\0";
+        let entries = parse_status(out, "sub/");
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["a.md", "a -> b.md", "with space.md"]);
+        assert_eq!(entries[1].from.as_deref(), Some("old.md"));
+        assert_eq!(entries[1].to_string(), "R  old.md -> a -> b.md");
+        assert_eq!(entries[2].to_string(), "?? with space.md");
+    }
+
     #[test]
     fn test_sanitize_label() {
         assert_eq!(
@@ -275,10 +338,8 @@ mod tests {
     fn test_cleanup_bundled_cache_path_guard() {
         // The cleanup target must live inside ~/.hibi/cache/ so the
         // defense-in-depth `starts_with` guard can never reject it.
-        // (We don't invoke cleanup_bundled_cache() against the real home
-        // dir here — that path is a shared global resource and racing test
-        // threads would both try to remove it. The behavioral no-op test
-        // lives in the source module tests, run-serialized there.)
+        // (cleanup_bundled_cache() itself is exercised against a temp home
+        // in the source module and migrate tests.)
         if let Some(home) = dirs::home_dir() {
             let cache_base = home.join(".hibi").join("cache");
             let bundled_dir = cache_base.join("bundled");
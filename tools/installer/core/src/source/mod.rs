@@ -0,0 +1,463 @@
+pub mod config;
+pub mod git;
+
+pub use config::{ResolvedSource, SourceEntry, SourceKind};
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+/// Environment variable that names the bundled source directory.
+pub const SOURCE_DIR_ENV: &str = "HIBI_SOURCE";
+
+/// Top-level entries every bundled source has.
+const BUNDLED_MARKERS: [&str; 2] = ["agents", "settings.json"];
+
+static SOURCE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir` as the bundled source instead of looking for one
+/// (`--source-dir`). Wins over `HIBI_SOURCE`; only the first call has an
+/// effect.
+pub fn set_source_dir(dir: PathBuf) {
+    let _ = SOURCE_DIR_OVERRIDE.set(dir);
+}
+
+/// Discover the source directory containing bundled components: the one
+/// given with `set_source_dir` or `HIBI_SOURCE`, otherwise the first of
+/// the package install locations that has one.
+/// Always uses the package-embedded source files (no remote sync).
+pub fn find_source_dir() -> Result<PathBuf> {
+    if let Some(dir) = SOURCE_DIR_OVERRIDE.get() {
+        return checked_source_dir(dir, "--source-dir");
+    }
+    if let Some(dir) = std::env::var_os(SOURCE_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return checked_source_dir(Path::new(&dir), SOURCE_DIR_ENV);
+    }
+    find_package_source_dir()
+}
+
+/// A source directory named by the user, or an error saying what it
+/// lacks. `origin` is where the path came from, for the message.
+fn checked_source_dir(dir: &Path, origin: &str) -> Result<PathBuf> {
+    let dir = config::expand_tilde(dir);
+    if !dir.is_dir() {
+        anyhow::bail!("{} {} is not a directory", origin, dir.display());
+    }
+    let missing = missing_markers(&dir);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "{} {} is not a hibi source: no {} (expected agents/ and settings.json at its top level)",
+            origin,
+            dir.display(),
+            missing.join(" or "),
+        );
+    }
+    Ok(dir.canonicalize().unwrap_or(dir))
+}
+
+fn missing_markers(path: &Path) -> Vec<&'static str> {
+    BUNDLED_MARKERS.into_iter().filter(|marker| !path.join(marker).exists()).collect()
+}
+
+/// Find the source dir from package-installed paths (exe-relative, cwd, etc.)
+fn find_package_source_dir() -> Result<PathBuf> {
+    let exe_dir = std::env::current_exe()?
+        .parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| anyhow::anyhow!("Cannot get executable directory"))?;
+
+    let candidates = [
+        exe_dir.clone(),                          // Scoop: exe and config in same dir
+        exe_dir.join("../share/hibi"),            // Homebrew standard
+        exe_dir.join("../share/hibi-ai"),         // Homebrew alternative
+        exe_dir.join("../../.."),                  // From target/release
+        exe_dir.join("../.."),                     // From target
+        std::env::current_dir()?,                  // Current directory
+        std::env::current_dir()?.join("config/ai/claude"),
+    ];
+
+    for candidate in candidates {
+        // canonicalize resolves `..` and symlinks; if it fails (path doesn't exist),
+        // the raw candidate is used — but marker check below will reject it safely.
+        let resolved = candidate.canonicalize().unwrap_or(candidate);
+        if validate_source_dir(&resolved) {
+            return Ok(resolved);
+        }
+    }
+
+    let default = std::env::current_dir()?.join("config/ai/claude");
+    if validate_source_dir(&default) {
+        return Ok(default);
+    }
+
+    anyhow::bail!(
+        "Cannot find source directory. Run from dotfiles root or config/ai/claude/tools/installer, \
+         or name it with --source-dir or {}",
+        SOURCE_DIR_ENV,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Sync & Resolve
+// ---------------------------------------------------------------------------
+
+/// Result of resolving sources: the resolved list plus any non-fatal warnings.
+pub struct ResolveResult {
+    pub sources: Vec<ResolvedSource>,
+    pub warnings: Vec<String>,
+    /// Component folder mapping from the config's `directories:` section.
+    pub directories: config::DirectoryMap,
+}
+
+/// Uncommitted changes in a source folder that is part of a git work tree.
+#[derive(Clone, Debug)]
+pub struct SourceChanges {
+    pub label: String,
+    pub path: PathBuf,
+    /// The changed files, paths relative to `path`.
+    pub status: Vec<git::StatusEntry>,
+}
+
+impl SourceChanges {
+    /// Whether `file` (a component's source file or folder) is, or holds,
+    /// an uncommitted change.
+    pub fn touches(&self, file: &Path) -> bool {
+        let Ok(relative) = file.strip_prefix(&self.path) else { return false };
+        self.status.iter().any(|entry| Path::new(&entry.path).starts_with(relative))
+    }
+}
+
+/// Local and bundled sources with uncommitted changes. Git sources are
+/// skipped: their cache is reset to the fetched commit on every sync.
+pub fn uncommitted_source_changes(sources: &[ResolvedSource]) -> Vec<SourceChanges> {
+    sources.iter()
+        .filter(|s| s.kind != SourceKind::Git)
+        .filter_map(|s| {
+            let status = git::uncommitted_changes(&s.path).filter(|status| !status.is_empty())?;
+            Some(SourceChanges { label: s.label.clone(), path: s.path.clone(), status })
+        })
+        .collect()
+}
+
+/// Result of a full sync operation (source resolve).
+pub struct SyncReport {
+    pub resolved: Vec<ResolvedSource>,
+    pub summaries: Vec<String>,
+    pub had_error: bool,
+}
+
+/// Resolve all user-configured sources. The bundled source is package-embedded
+/// (`source_dir`) and is never fetched from a remote.
+/// Checks `cancel_rx` before resolving for cooperative cancellation.
+pub fn sync_all_sources(
+    source_dir: &Path,
+    cancel_rx: &std::sync::mpsc::Receiver<()>,
+) -> SyncReport {
+    let mut summaries = Vec::new();
+
+    // Bundled source is package-embedded; nothing to fetch.
+    summaries.push("  bundled: local".to_string());
+
+    // Cooperative cancel check before resolving user sources
+    if cancel_rx.try_recv().is_ok() {
+        summaries.push("  cancelled".to_string());
+        return SyncReport {
+            resolved: vec![ResolvedSource::bundled(source_dir)],
+            summaries,
+            had_error: true,
+        };
+    }
+
+    // Resolve all sources using the package-embedded source dir
+    match resolve_all_sources(source_dir) {
+        Ok(r) => {
+            for s in &r.sources {
+                if s.kind == SourceKind::Git {
+                    if s.is_stale {
+                        summaries.push(format!("  {}: stale", s.label));
+                    } else {
+                        summaries.push(format!("  {}: updated", s.label));
+                    }
+                }
+            }
+            summaries.extend(r.warnings);
+            SyncReport { resolved: r.sources, summaries, had_error: false }
+        }
+        Err(e) => {
+            summaries.push(format!("  re-resolve failed: {}", e));
+            SyncReport {
+                resolved: vec![ResolvedSource::bundled(source_dir)],
+                summaries,
+                had_error: true,
+            }
+        }
+    }
+}
+
+/// Resolve all sources: bundled (implicit first) + config entries in order,
+/// then the `source.local` overlay when it exists.
+/// Last entry = highest priority.
+/// Warnings are collected instead of printed to stderr (TUI-safe).
+pub fn resolve_all_sources(bundled_dir: &Path) -> Result<ResolveResult> {
+    let mut sources = vec![ResolvedSource::bundled(bundled_dir)];
+    let mut warnings = Vec::new();
+    let overlay_dir = config::local_overlay_dir();
+
+    let (entries, auto_update) = match config::load_config() {
+        Ok(result) => result,
+        Err(e) => {
+            warnings.push(format!("Failed to load sources.yaml: {}", e));
+            let directories = config::DirectoryMap::default();
+            sources.extend(overlay_dir.and_then(|dir| local_overlay(&dir, &directories)));
+            return Ok(ResolveResult { sources, warnings, directories });
+        }
+    };
+    let directories = match config::load_directories() {
+        Ok((directories, dir_warnings)) => {
+            warnings.extend(dir_warnings);
+            directories
+        }
+        Err(_) => config::DirectoryMap::default(),
+    };
+
+    for entry in entries {
+        match resolve_entry(&entry, auto_update, &mut warnings) {
+            Ok(resolved) => {
+                if resolved.map_to.is_some() || validate_user_source_dir(&resolved.path, &directories) {
+                    sources.push(resolved);
+                } else {
+                    warnings.push(format!(
+                        "Source dir lacks expected structure, skipping: {}",
+                        resolved.path.display()
+                    ));
+                }
+            }
+            Err(e) => {
+                warnings.push(format!("Failed to resolve source: {}", e));
+            }
+        }
+    }
+
+    sources.extend(overlay_dir.and_then(|dir| local_overlay(&dir, &directories)));
+    Ok(ResolveResult { sources, warnings, directories })
+}
+
+/// The per-machine overlay at `dir`, scanned after every other source so
+/// its files win. `None` unless it holds something a source would.
+fn local_overlay(dir: &Path, directories: &config::DirectoryMap) -> Option<ResolvedSource> {
+    validate_user_source_dir(dir, directories).then(|| ResolvedSource {
+        label: config::LOCAL_OVERLAY_NAME.to_string(),
+        kind: SourceKind::Local,
+        path: dir.to_path_buf(),
+        is_stale: false,
+        branch: None,
+        map_to: None,
+    })
+}
+
+fn resolve_entry(
+    entry: &SourceEntry,
+    auto_update: bool,
+    warnings: &mut Vec<String>,
+) -> Result<ResolvedSource> {
+    match entry {
+        SourceEntry::Git { url, branch, root, map_to } => {
+            config::validate_git_url(url)?;
+            let cache_dir = git::cache_path_for(url)?;
+
+            let make_resolved = |base_path: std::path::PathBuf, stale: bool| {
+                let path = apply_root(base_path, root.as_deref());
+                ResolvedSource {
+                    label: url.clone(),
+                    kind: SourceKind::Git,
+                    path,
+                    is_stale: stale,
+                    branch: branch.clone(),
+                    map_to: map_to.clone(),
+                }
+            };
+
+            if auto_update {
+                match git::clone_or_update(url, branch, &cache_dir) {
+                    Ok(path) => Ok(make_resolved(path, false)),
+                    Err(e) => {
+                        if git::cache_exists(&cache_dir) {
+                            warnings.push(format!("Git fetch failed, using stale cache: {}", e));
+                            Ok(make_resolved(cache_dir, true))
+                        } else {
+                            Err(e)
+                        }
+                    }
+                }
+            } else if git::cache_exists(&cache_dir) {
+                Ok(make_resolved(cache_dir, false))
+            } else {
+                match git::clone_or_update(url, branch, &cache_dir) {
+                    Ok(path) => Ok(make_resolved(path, false)),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+        SourceEntry::Local { path, root, map_to } => {
+            config::validate_local_path(path)?;
+            let expanded = config::expand_tilde(path);
+
+            if !expanded.exists() {
+                anyhow::bail!("Local source path does not exist: {}", expanded.display());
+            }
+
+            let final_path = apply_root(expanded, root.as_deref());
+            let label = path.to_string_lossy().to_string();
+            Ok(ResolvedSource {
+                label,
+                kind: SourceKind::Local,
+                path: final_path,
+                is_stale: false,
+                branch: None,
+                map_to: map_to.clone(),
+            })
+        }
+    }
+}
+
+/// Apply `root` subdirectory to a base path.
+fn apply_root(base: std::path::PathBuf, root: Option<&str>) -> std::path::PathBuf {
+    match root {
+        Some(sub) if !sub.is_empty() => base.join(sub),
+        _ => base,
+    }
+}
+
+/// Strict check: bundled source must have both agents/ and settings.json.
+fn validate_source_dir(path: &Path) -> bool {
+    missing_markers(path).is_empty()
+}
+
+/// Permissive check: user sources just need at least one known marker,
+/// including any folder remapped via `directories:`.
+fn validate_user_source_dir(path: &Path, directories: &config::DirectoryMap) -> bool {
+    let markers = [
+        "agents", "commands", "contexts", "rules", "skills",
+        "hooks", "output-styles", "statusline",
+        "mcps/mcps.yaml", "plugins/plugins.yaml",
+        "settings.json", "CLAUDE.md", "AGENTS.md",
+    ];
+    markers.iter().copied()
+        .chain(directories.custom_source_dirs())
+        .any(|m| path.join(m).exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn source_changes_cover_changed_files_and_their_folders() {
+        let changes = SourceChanges {
+            label: "dotfiles".into(),
+            path: PathBuf::from("/src"),
+            status: vec![
+                entry(" M", "agents/a.md", None),
+                entry("??", "skills/new-skill/SKILL.md", None),
+                entry("R ", "rules/renamed.md", Some("rules/old.md")),
+            ],
+        };
+        assert!(changes.touches(Path::new("/src/agents/a.md")));
+        assert!(changes.touches(Path::new("/src/skills/new-skill")));
+        assert!(changes.touches(Path::new("/src/rules/renamed.md")));
+        assert!(!changes.touches(Path::new("/src/agents/b.md")));
+        assert!(!changes.touches(Path::new("/elsewhere/agents/a.md")));
+        assert!(!changes.touches(Path::new("/src/rules/old.md")));
+    }
+
+    fn entry(code: &str, path: &str, from: Option<&str>) -> git::StatusEntry {
+        git::StatusEntry { code: code.into(), path: path.into(), from: from.map(String::from) }
+    }
+
+    #[test]
+    fn named_source_dir_must_have_the_bundled_layout() {
+        let dir = temp_dir("named_source");
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+
+        let err = checked_source_dir(&dir, SOURCE_DIR_ENV).unwrap_err().to_string();
+        assert!(err.starts_with("HIBI_SOURCE "), "{err}");
+        assert!(err.contains("is not a hibi source: no settings.json"), "{err}");
+        let err = checked_source_dir(&dir.join("gone"), "--source-dir").unwrap_err().to_string();
+        assert!(err.ends_with("is not a directory"), "{err}");
+
+        std::fs::write(dir.join("settings.json"), "{}").unwrap();
+        let found = checked_source_dir(&dir, "--source-dir").unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(found.ends_with(dir.file_name().unwrap()));
+    }
+
+    #[test]
+    fn local_overlay_wins_over_the_shared_source() {
+        use crate::TargetCli;
+
+        let dir = temp_dir("overlay");
+        let (shared, overlay, dest) = (dir.join("shared"), dir.join(config::LOCAL_OVERLAY_NAME), dir.join("dest"));
+        std::fs::create_dir_all(shared.join("agents")).unwrap();
+        std::fs::create_dir_all(overlay.join("agents")).unwrap();
+        std::fs::create_dir_all(&dest).unwrap();
+        std::fs::write(shared.join("agents/reviewer.md"), "shared").unwrap();
+        std::fs::write(shared.join("agents/planner.md"), "shared").unwrap();
+        std::fs::write(overlay.join("agents/reviewer.md"), "this machine").unwrap();
+        std::fs::write(shared.join("settings.json"), r#"{"model":"opus","env":{"A":"1"}}"#).unwrap();
+        std::fs::write(overlay.join("settings.json"), r#"{"env":{"B":"2"}}"#).unwrap();
+
+        let directories = config::DirectoryMap::default();
+        let empty = local_overlay(&dir.join("missing"), &directories);
+        let sources = vec![ResolvedSource::bundled(&shared), local_overlay(&overlay, &directories).unwrap()];
+        let components = crate::fs::scanner::scan_all_sources(&sources, &dest, TargetCli::Claude, &directories, crate::fs::scanner::ScanOptions::default()).unwrap();
+        let settings = components.iter().find(|c| c.name == "settings.json").unwrap();
+        let layered: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&settings.source_path).unwrap()).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(empty.is_none());
+        let origin: Vec<(&str, &str, bool)> = components.iter()
+            .filter(|c| c.name != "settings.json")
+            .map(|c| (c.name.as_str(), c.source_name.as_str(), c.source_path.starts_with(&overlay)))
+            .collect();
+        assert_eq!(origin, [("planner.md", "bundled", false), ("reviewer.md", "source.local", true)]);
+        assert_eq!(settings.source_name, "source.local");
+        assert_eq!(layered, serde_json::json!({"model": "opus", "env": {"A": "1", "B": "2"}}));
+    }
+
+    /// Regression test: resolve_entry must use the base cache dir, not source.path,
+    /// when a `root` subdirectory is configured.
+    /// Bug history: clone_or_update once received "~/.hibi/cache/.../skills/" instead of "~/.hibi/cache/.../"
+    #[test]
+    fn test_cache_path_is_prefix_of_resolved_path_with_root() {
+        let url = "https://github.com/vercel-labs/agent-skills";
+        let cache_dir = git::cache_path_for(url).unwrap();
+        let resolved_path = apply_root(cache_dir.clone(), Some("skills"));
+
+        assert!(resolved_path.starts_with(&cache_dir));
+        assert_ne!(resolved_path, cache_dir);
+        assert!(!cache_dir.ends_with("skills"));
+    }
+
+    #[test]
+    fn test_cache_path_equals_resolved_path_without_root() {
+        let url = "https://github.com/user/repo.git";
+        let cache_dir = git::cache_path_for(url).unwrap();
+        let resolved_path = apply_root(cache_dir.clone(), None);
+
+        assert_eq!(resolved_path, cache_dir);
+    }
+
+    #[test]
+    fn test_cleanup_bundled_cache_noop_when_absent() {
+        // When the orphaned cache dir is absent, cleanup is a safe no-op
+        // returning Ok(false). Runs against a temp home so it can never
+        // touch a real user's cache.
+        let home = temp_dir("cleanup");
+        let result = git::cleanup_bundled_cache(&home);
+        assert!(result.is_ok());
+        assert!(!result.unwrap());
+    }
+}
@@ -0,0 +1,233 @@
+//! `hibi_core::sync`: install every pending component for one CLI without
+//! the TUI, for dotfiles managers (a chezmoi `run_after` script, say) and
+//! other frontends. It does what pressing `i` on every component tab
+//! would: new and modified components from the sources are installed,
+//! with local edits merged as usual; external and orphaned files are left
+//! alone.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::component::{Component, ComponentType, InstallStatus};
+use crate::fs;
+use crate::source;
+use crate::TargetCli;
+
+/// What [`sync`] installs and where. Build with [`SyncOptions::new`] and
+/// set the fields that differ; new fields get a default that keeps the
+/// previous behaviour.
+///
+/// Unlike an install batch in the TUI, a sync writes no undo journal,
+/// ignores `committed_only:` (uncommitted source files are installed as
+/// they are), and knows only the `targets:` CLIs already passed to
+/// [`register_custom_targets`](crate::register_custom_targets).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SyncOptions {
+    pub target: TargetCli,
+    /// The bundled source. `None` finds it as the `hibi` binary does:
+    /// `HIBI_SOURCE`, then next to the running executable.
+    pub source_dir: Option<PathBuf>,
+    /// Where to install. `None` is the target's own folder, e.g. `~/.claude`.
+    pub dest_dir: Option<PathBuf>,
+    /// Pull git sources before scanning them.
+    pub fetch: bool,
+    /// Only these component types; empty means all of them.
+    pub types: Vec<ComponentType>,
+    /// Report what would be installed without writing anything.
+    pub dry_run: bool,
+    /// Copy files about to be overwritten into a backup snapshot first.
+    pub backup: bool,
+}
+
+impl SyncOptions {
+    /// Install everything pending for `target`, with a backup and without
+    /// fetching.
+    pub fn new(target: TargetCli) -> Self {
+        Self {
+            target,
+            source_dir: None,
+            dest_dir: None,
+            fetch: false,
+            types: Vec::new(),
+            dry_run: false,
+            backup: true,
+        }
+    }
+}
+
+/// What [`sync`] did.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct SyncReport {
+    /// Components installed, or that would be on a dry run, as `type/name`.
+    pub installed: Vec<String>,
+    /// Components that failed to install, with the error. Files whose
+    /// local edits conflict with the source's are left alone and listed
+    /// here too.
+    pub failed: Vec<(String, String)>,
+    /// Components already up to date, as `type/name`.
+    pub unchanged: Vec<String>,
+    /// Source, fetch and migration messages worth showing to the user.
+    pub notes: Vec<String>,
+}
+
+impl SyncReport {
+    /// Whether every pending component was installed.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Install every pending component from the configured sources into the
+/// target's folder. Fails only when the sources can't be found or
+/// scanned; a component that fails to install is recorded in the report
+/// and the rest still run.
+pub fn sync(options: &SyncOptions) -> Result<SyncReport> {
+    let source_dir = match &options.source_dir {
+        Some(dir) => dir.clone(),
+        None => source::find_source_dir()?,
+    };
+    let dest_dir = match &options.dest_dir {
+        Some(dir) => dir.clone(),
+        None => options.target.get_dest_dir()?,
+    };
+
+    let mut notes = Vec::new();
+    if !options.dry_run && let Some(home) = dirs::home_dir() {
+        notes.extend(crate::migrate::run_pending(&home)?.notes);
+    }
+    if options.fetch {
+        let (_cancel_tx, cancel_rx) = std::sync::mpsc::channel::<()>();
+        notes.extend(source::sync_all_sources(&source_dir, &cancel_rx).summaries);
+    }
+    let resolved = source::resolve_all_sources(&source_dir)?;
+    notes.extend(resolved.warnings);
+
+    let components = fs::scanner::scan_all_sources(
+        &resolved.sources, &dest_dir, options.target, &resolved.directories, fs::scanner::ScanOptions::default(),
+    )?;
+    let mut report = install_pending(&components, &source_dir, &dest_dir, options);
+    report.notes.splice(0..0, notes);
+    Ok(report)
+}
+
+fn install_pending(
+    components: &[Component],
+    source_dir: &std::path::Path,
+    dest_dir: &std::path::Path,
+    options: &SyncOptions,
+) -> SyncReport {
+    let wanted = components.iter().filter(|c| {
+        (options.types.is_empty() || options.types.contains(&c.component_type))
+            && c.is_install_eligible()
+            && c.for_this_host()
+    });
+    let hook_peers: Vec<_> = components.iter().filter_map(|c| c.hook_config.clone()).collect();
+    let backup = (options.backup && !options.dry_run).then(|| fs::backup::Snapshot::new(dest_dir));
+
+    let mut report = SyncReport::default();
+    for component in wanted {
+        let label = format!("{}/{}", component.component_type.display_name(), component.name);
+        if !fs::report::is_pending(component) {
+            report.unchanged.push(label);
+            continue;
+        }
+        if is_conflicted(component, dest_dir) {
+            report.failed.push((label, "local edits conflict with the source; resolve them in hibi".to_string()));
+            continue;
+        }
+        if options.dry_run {
+            report.installed.push(label);
+            continue;
+        }
+        match fs::installer::install_component(component, source_dir, dest_dir, &hook_peers, backup.as_ref()) {
+            Ok(()) => report.installed.push(label),
+            Err(e) => report.failed.push((label, format!("{:#}", e))),
+        }
+    }
+    report
+}
+
+/// Whether installing would overwrite local edits that don't merge; the
+/// TUI asks about these one by one.
+fn is_conflicted(component: &Component, dest_dir: &std::path::Path) -> bool {
+    component.status == InstallStatus::Modified
+        && fs::installer::has_local_edits(component, dest_dir)
+        && fs::installer::merged_content(component, dest_dir).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::source::config::DirectoryMap;
+    use crate::source::ResolvedSource;
+
+    #[test]
+    fn installs_only_pending_components() {
+        let root = temp_dir("sync");
+        let (source, dest) = (root.join("source"), root.join("dest"));
+        std::fs::create_dir_all(source.join("agents")).unwrap();
+        std::fs::create_dir_all(source.join("commands")).unwrap();
+        std::fs::create_dir_all(dest.join("agents")).unwrap();
+        std::fs::write(source.join("agents/new.md"), "new\n").unwrap();
+        std::fs::write(source.join("agents/same.md"), "same\n").unwrap();
+        std::fs::write(source.join("commands/go.md"), "go\n").unwrap();
+        std::fs::write(dest.join("agents/same.md"), "same\n").unwrap();
+        std::fs::write(dest.join("agents/mine.md"), "mine\n").unwrap();
+
+        let scan = || {
+            let sources = vec![ResolvedSource::bundled(&source)];
+            fs::scanner::scan_all_sources(&sources, &dest, TargetCli::Claude, &DirectoryMap::default(), fs::scanner::ScanOptions::default()).unwrap()
+        };
+        let mut options = SyncOptions::new(TargetCli::Claude);
+        options.types = vec![ComponentType::Agents];
+        options.backup = false;
+
+        options.dry_run = true;
+        let report = install_pending(&scan(), &source, &dest, &options);
+        assert_eq!(report.installed, vec!["agents/new.md"]);
+        assert_eq!(report.unchanged, vec!["agents/same.md"]);
+        assert!(!dest.join("agents/new.md").exists());
+
+        options.dry_run = false;
+        let report = install_pending(&scan(), &source, &dest, &options);
+        assert!(report.is_success());
+        assert_eq!(std::fs::read_to_string(dest.join("agents/new.md")).unwrap(), "new\n");
+        assert_eq!(std::fs::read_to_string(dest.join("agents/mine.md")).unwrap(), "mine\n");
+        assert!(!dest.join("commands/go.md").exists());
+
+        let report = install_pending(&scan(), &source, &dest, &options);
+        assert!(report.installed.is_empty());
+        assert_eq!(report.unchanged.len(), 2);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn conflicting_local_edits_are_left_alone() {
+        let root = temp_dir("sync_conflict");
+        let (source, dest) = (root.join("source"), root.join("dest"));
+        std::fs::create_dir_all(source.join("agents")).unwrap();
+        std::fs::write(source.join("agents/edited.md"), "one\n").unwrap();
+
+        let scan = || {
+            let sources = vec![ResolvedSource::bundled(&source)];
+            fs::scanner::scan_all_sources(&sources, &dest, TargetCli::Claude, &DirectoryMap::default(), fs::scanner::ScanOptions::default()).unwrap()
+        };
+        let mut options = SyncOptions::new(TargetCli::Claude);
+        options.backup = false;
+        assert!(install_pending(&scan(), &source, &dest, &options).is_success());
+
+        std::fs::write(source.join("agents/edited.md"), "two\n").unwrap();
+        std::fs::write(dest.join("agents/edited.md"), "mine\n").unwrap();
+        let report = install_pending(&scan(), &source, &dest, &options);
+        let local = std::fs::read_to_string(dest.join("agents/edited.md")).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "agents/edited.md");
+        assert_eq!(local, "mine\n");
+    }
+}
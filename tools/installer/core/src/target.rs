@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+use crate::component::ComponentType;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TargetCli {
+    Claude,
+    Codex,
+    Cursor,
+    OpenCode,
+    /// The n-th target from `targets:` (see `custom_targets`).
+    Custom(usize),
+}
+
+/// `<program> ...` argument templates for managing a custom target's MCP
+/// servers. Placeholders: `{name}`, `{url}`, `{command}` (the server's
+/// command line, one argument per word) and `{env}` (one `KEY=VALUE` per
+/// variable; a flag right before it is repeated for each).
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpTemplates {
+    pub add: String,
+    /// For HTTP servers; they can't be added without it.
+    pub add_http: Option<String>,
+    pub remove: String,
+    /// Prints one installed server per line, name first; without it every
+    /// server shows as not installed.
+    pub list: Option<String>,
+}
+
+/// A CLI defined under `targets:` in sources.yaml rather than built in.
+/// Supports the generic subset: plain file copies into its config dir and
+/// MCP servers through its own command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomTarget {
+    pub name: String,
+    pub program: String,
+    /// Config dir relative to the home directory, e.g. `.config/amp`.
+    pub config_dir: String,
+    /// Folder-based types it gets a tab for, in tab order.
+    pub component_types: Vec<ComponentType>,
+    /// Files installed by the Config tab; empty means no Config tab.
+    pub config_files: Vec<String>,
+    /// Present when it has an MCP tab.
+    pub mcp: Option<McpTemplates>,
+}
+
+static CUSTOM_TARGETS: OnceLock<Vec<CustomTarget>> = OnceLock::new();
+
+/// Make the targets from `targets:` available as `TargetCli::Custom`.
+/// Only the first call has an effect.
+pub fn register_custom_targets(targets: Vec<CustomTarget>) {
+    let _ = CUSTOM_TARGETS.set(targets);
+}
+
+/// Registered custom targets; empty before `register_custom_targets`.
+pub fn custom_targets() -> &'static [CustomTarget] {
+    CUSTOM_TARGETS.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+impl TargetCli {
+    /// Every built-in target, in start-screen order.
+    pub const ALL: [TargetCli; 4] = [Self::Claude, Self::Codex, Self::Cursor, Self::OpenCode];
+
+    /// Built-in targets followed by the registered custom ones.
+    pub fn all() -> Vec<TargetCli> {
+        Self::ALL.into_iter()
+            .chain((0..custom_targets().len()).map(Self::Custom))
+            .collect()
+    }
+
+    /// Definition of a custom target; `None` for built-ins.
+    pub fn custom(&self) -> Option<&'static CustomTarget> {
+        match self {
+            Self::Custom(idx) => custom_targets().get(*idx),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Claude => "Claude Code",
+            Self::Codex => "Codex CLI",
+            Self::Cursor => "Cursor",
+            Self::OpenCode => "OpenCode",
+            Self::Custom(_) => self.custom().map_or("Unknown CLI", |t| t.name.as_str()),
+        }
+    }
+
+    pub fn config_dir_name(&self) -> &str {
+        match self {
+            Self::Claude => ".claude",
+            Self::Codex => ".codex",
+            Self::Cursor => ".cursor",
+            Self::OpenCode => ".config/opencode",
+            Self::Custom(_) => self.custom().map_or(".unknown", |t| t.config_dir.as_str()),
+        }
+    }
+
+    /// Executable name looked up on PATH.
+    pub fn program_name(&self) -> &str {
+        match self {
+            Self::Claude => "claude",
+            Self::Codex => "codex",
+            Self::Cursor => "cursor",
+            Self::OpenCode => "opencode",
+            Self::Custom(_) => self.custom().map_or("", |t| t.program.as_str()),
+        }
+    }
+
+    /// Whether MCP servers are registered through `<cli> mcp add/remove`
+    /// (or a custom target's templates). Cursor and OpenCode have no such
+    /// command; hibi edits their config files directly.
+    pub fn manages_mcp_via_cli(&self) -> bool {
+        !matches!(self, Self::Cursor | Self::OpenCode)
+    }
+
+    pub fn get_dest_dir(&self) -> Result<PathBuf> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        Ok(home.join(self.config_dir_name()))
+    }
+}
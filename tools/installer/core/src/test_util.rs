@@ -0,0 +1,18 @@
+//! Helpers for tests, here and in the `hibi` binary. Only built for this
+//! crate's tests and with the `test-util` feature, which the binary turns
+//! on as a dev-dependency; it is not part of the API.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A new empty directory under the system temp dir, unique to this call
+/// even across tests running in parallel. Tests remove it when done.
+pub fn temp_dir(label: &str) -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("hibi_{}_{}_{}", label, std::process::id(), n));
+    // A run with the same pid may have left it behind.
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap_or_else(|e| panic!("Failed to create {}: {}", dir.display(), e));
+    dir
+}
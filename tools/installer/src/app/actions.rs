@@ -0,0 +1,232 @@
+//! Registry of list-view keys that only make sense on some tabs. Each
+//! entry says where it applies; the status bar lists the entries that
+//! apply to the current tab and `run_tab_action` dispatches only those,
+//! so a key never silently does nothing. The command palette (Ctrl+P)
+//! lists them by name too. A new tab-specific action is one more entry
+//! here.
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::Tab;
+use super::App;
+use crate::component::InstallStatus;
+
+pub struct TabAction {
+    /// Status-bar hint, e.g. `[s] Set  [u] Unset`.
+    pub hint: &'static str,
+    pub keys: &'static [KeyCode],
+    /// Command palette name for each key, in `keys` order.
+    pub commands: &'static [&'static str],
+    pub applies: fn(&App) -> bool,
+    /// Called with the key pressed, for entries that bind several.
+    pub run: fn(&mut App, KeyCode) -> Result<()>,
+}
+
+fn component_tab(app: &App) -> bool {
+    app.tab_component_type(app.tab).is_some()
+}
+
+/// Tabs shown as a folder tree rather than a flat file list.
+fn folder_tab(app: &App) -> bool {
+    component_tab(app) && !matches!(app.tab, Tab::Hooks | Tab::Config | Tab::OutputStyles | Tab::Statusline)
+}
+
+/// In list order; the first entry that binds a key and applies wins.
+pub const TAB_ACTIONS: &[TabAction] = &[
+    TabAction {
+        hint: "[d] Diff",
+        keys: &[KeyCode::Char('d')],
+        commands: &["Diff against installed file"],
+        applies: component_tab,
+        run: |app, _| app.show_diff(),
+    },
+    TabAction {
+        hint: "[N/M/O] New/Modified/Orphaned",
+        keys: &[KeyCode::Char('N'), KeyCode::Char('M'), KeyCode::Char('O')],
+        commands: &["Select new items", "Select modified items", "Select orphaned items"],
+        applies: component_tab,
+        run: |app, key| {
+            app.select_by_status(match key {
+                KeyCode::Char('N') => InstallStatus::New,
+                KeyCode::Char('M') => InstallStatus::Modified,
+                _ => InstallStatus::Orphaned,
+            });
+            Ok(())
+        },
+    },
+    TabAction {
+        hint: "[p] Prune",
+        keys: &[KeyCode::Char('p')],
+        commands: &["Prune orphaned files"],
+        applies: component_tab,
+        run: |app, _| { app.open_prune_confirm(); Ok(()) },
+    },
+    TabAction {
+        hint: "[m] Mirror",
+        keys: &[KeyCode::Char('m')],
+        commands: &["Mirror tabs to match the source"],
+        applies: component_tab,
+        run: |app, _| { app.open_mirror(); Ok(()) },
+    },
+    TabAction {
+        hint: "[o] Scope",
+        keys: &[KeyCode::Char('o')],
+        commands: &["Switch MCP scope"],
+        applies: |app| app.tab == Tab::McpServers,
+        run: |app, _| { app.toggle_mcp_scope(); Ok(()) },
+    },
+    TabAction {
+        hint: "[U] Updates",
+        keys: &[KeyCode::Char('U')],
+        commands: &["Check MCP servers for updates"],
+        applies: |app| app.tab == Tab::McpServers,
+        run: |app, _| { app.start_mcp_update_check(); Ok(()) },
+    },
+    TabAction {
+        hint: "[K/J] Move up/down",
+        keys: &[KeyCode::Char('K'), KeyCode::Char('J')],
+        commands: &["Move hook up", "Move hook down"],
+        applies: |app| app.tab == Tab::Hooks,
+        run: |app, key| app.move_hook(if key == KeyCode::Char('K') { -1 } else { 1 }),
+    },
+    TabAction {
+        hint: "[e] Details",
+        keys: &[KeyCode::Char('e')],
+        commands: &["Hook details"],
+        applies: |app| app.tab == Tab::Hooks,
+        run: |app, _| { app.open_hook_detail(); Ok(()) },
+    },
+    TabAction {
+        hint: "[e] Keywords",
+        keys: &[KeyCode::Char('e')],
+        commands: &["Edit keywords"],
+        applies: |app| matches!(app.tab, Tab::Agents | Tab::Skills),
+        run: |app, _| app.open_keyword_edit(),
+    },
+    TabAction {
+        hint: "[L] Logs",
+        keys: &[KeyCode::Char('L')],
+        commands: &["Hook logs"],
+        applies: |app| app.tab == Tab::Hooks,
+        run: |app, _| { app.open_hook_logs(); Ok(()) },
+    },
+    TabAction {
+        hint: "[c] Create hook.yaml",
+        keys: &[KeyCode::Char('c')],
+        commands: &["Create hook.yaml"],
+        applies: |app| app.tab == Tab::Hooks,
+        run: |app, _| { app.open_hook_scaffold(); Ok(()) },
+    },
+    TabAction {
+        hint: "[x] Export script",
+        keys: &[KeyCode::Char('x')],
+        commands: &["Export hooks as a shell script"],
+        applies: |app| app.tab == Tab::Hooks,
+        run: |app, _| app.export_hooks_script(),
+    },
+    TabAction {
+        hint: "[s] Set  [u] Unset",
+        keys: &[KeyCode::Char('s'), KeyCode::Char('u')],
+        commands: &["Set as default", "Unset default"],
+        applies: |app| matches!(app.tab, Tab::OutputStyles | Tab::Statusline),
+        run: |app, key| match (key, app.tab) {
+            (KeyCode::Char('s'), Tab::OutputStyles) => app.set_default_style(),
+            (KeyCode::Char('s'), _) => app.set_statusline(),
+            (_, Tab::OutputStyles) => app.unset_default_style(),
+            _ => app.unset_statusline(),
+        },
+    },
+    TabAction {
+        hint: "[F] Fix missing default",
+        keys: &[KeyCode::Char('F')],
+        commands: &["Fix missing default"],
+        applies: |app| app.dangling_for_tab().is_some(),
+        run: |app, _| app.fix_dangling(),
+    },
+    TabAction {
+        hint: "[E/D] Export diffs/drift",
+        keys: &[KeyCode::Char('E'), KeyCode::Char('D')],
+        commands: &["Export diff report", "Export drift report (JSON)"],
+        applies: component_tab,
+        run: |app, key| if key == KeyCode::Char('E') { app.export_report() } else { app.export_drift_report() },
+    },
+    TabAction {
+        hint: "[H] History  [v] Keys",
+        keys: &[KeyCode::Char('H'), KeyCode::Char('v')],
+        commands: &["settings.json history", "settings.json keys and owners"],
+        applies: |app| app.tab == Tab::Config,
+        run: |app, key| if key == KeyCode::Char('H') { app.open_settings_history() } else { app.open_settings_keys() },
+    },
+    TabAction {
+        hint: "[T] All hosts",
+        keys: &[KeyCode::Char('T')],
+        commands: &["Show/hide items for other hosts"],
+        applies: |app| component_tab(app) && app.other_host_count() > 0,
+        run: |app, _| { app.toggle_all_hosts(); Ok(()) },
+    },
+    TabAction {
+        hint: "[P] Port from Claude",
+        keys: &[KeyCode::Char('P')],
+        commands: &["Port Claude Code skills and MCP servers"],
+        applies: |app| app.migration_plan.is_some() && matches!(app.tab, Tab::Skills | Tab::McpServers),
+        run: |app, _| { app.open_migration(); Ok(()) },
+    },
+    TabAction {
+        hint: "[W] Git status",
+        keys: &[KeyCode::Char('W')],
+        commands: &["Git status of sources"],
+        applies: |app| !app.source_changes.is_empty(),
+        run: |app, _| { app.open_git_status(); Ok(()) },
+    },
+    TabAction {
+        hint: "[h/l/←/→] Folder  [z/Z] Collapse/expand all",
+        keys: &[KeyCode::Char('z'), KeyCode::Char('Z')],
+        commands: &["Collapse all folders", "Expand all folders"],
+        applies: folder_tab,
+        run: |app, key| { app.set_all_folders_expanded(key == KeyCode::Char('Z')); Ok(()) },
+    },
+];
+
+impl App {
+    /// The tab-specific actions available right now.
+    pub fn tab_actions(&self) -> impl Iterator<Item = &'static TabAction> + '_ {
+        TAB_ACTIONS.iter().filter(|action| (action.applies)(self))
+    }
+
+    /// Run the available action bound to `key`; `false` when there is none.
+    pub fn run_tab_action(&mut self, key: KeyCode) -> Result<bool> {
+        let Some(action) = self.tab_actions().find(|action| action.keys.contains(&key)) else {
+            return Ok(false);
+        };
+        (action.run)(self, key)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn tab_actions_only_cover_keys_that_work_on_the_tab() {
+        use crossterm::event::KeyCode;
+
+        let mut app = App::for_test();
+        let hints = |app: &App| app.tab_actions().map(|a| a.hint).collect::<Vec<_>>().join("  ");
+        app.tab = Tab::Plugins;
+        assert!(!app.run_tab_action(KeyCode::Char('s')).unwrap());
+        assert!(!app.run_tab_action(KeyCode::Char('o')).unwrap());
+        assert_eq!(hints(&app), "");
+
+        app.tab = Tab::McpServers;
+        assert_eq!(hints(&app), "[o] Scope  [U] Updates");
+        app.tab = Tab::OutputStyles;
+        assert!(hints(&app).contains("[s] Set  [u] Unset"));
+        assert!(!hints(&app).contains("[o] Scope"));
+        app.tab = Tab::Agents;
+        assert!(hints(&app).contains("[e] Keywords"));
+        assert!(!hints(&app).contains("[e] Details"));
+        assert!(app.run_tab_action(KeyCode::Char('Z')).unwrap());
+    }
+}
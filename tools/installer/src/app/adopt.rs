@@ -0,0 +1,113 @@
+use anyhow::Result;
+
+use super::App;
+use crate::component::{Component, InstallStatus};
+use crate::fs;
+use crate::source::config::SourceKind;
+
+impl App {
+    /// Why `component` can't be adopted, if it can't: only edits to items
+    /// from a local source folder can be written back.
+    fn adopt_blocker(&self, component: &Component) -> Option<String> {
+        if component.status != InstallStatus::Modified {
+            return Some("Only modified items can be adopted".to_string());
+        }
+        if fs::installer::is_cursor_rule(component) {
+            return Some("Cursor rules are rewritten on install and can't be adopted".to_string());
+        }
+        self.source_write_blocker(component, "adopted")
+    }
+
+    /// Why `component`'s source file can't be written to from here, if it
+    /// can't: only local sources are the user's to change. `action` ends
+    /// the message, e.g. "adopted".
+    pub(super) fn source_write_blocker(&self, component: &Component, action: &str) -> Option<String> {
+        let kind = self.sources.iter()
+            .find(|s| s.label == component.source_name)
+            .map(|s| s.kind.clone());
+        match kind {
+            Some(SourceKind::Local) => None,
+            Some(SourceKind::Git) => Some(format!(
+                "{} comes from git source {}; commit the change there instead",
+                component.name, component.source_name
+            )),
+            _ => Some(format!("{} comes from the bundled source and can't be {}", component.name, action)),
+        }
+    }
+
+    /// `A` in the diff view: the first press asks to copy the installed
+    /// version shown in the diff back into the source, the second does it.
+    pub fn request_adopt(&mut self) -> Result<()> {
+        let Some(idx) = self.selected_component_index() else { return Ok(()) };
+        let Some(component) = self.components.get(idx) else { return Ok(()) };
+        if let Some(blocker) = self.adopt_blocker(component) {
+            self.status_message = Some(blocker);
+            return Ok(());
+        }
+        if !self.adopt_armed {
+            self.adopt_armed = true;
+            self.status_message = Some(format!(
+                "Copy the installed {} over {}? Press A again to confirm",
+                component.name,
+                crate::source::config::contract_tilde(&component.source_path),
+            ));
+            return Ok(());
+        }
+
+        self.adopt_armed = false;
+        fs::installer::adopt_installed_version(component)?;
+        let (name, source) = (component.display_name(), component.source_name.clone());
+        if let Ok(Some(status)) = fs::scanner::component_status(component) {
+            self.components[idx].status = status;
+        }
+        self.refresh_source_changes();
+        self.session_summary.changes.push(format!("Adopted {} into source {}", name, source));
+        self.status_message = Some(format!("Adopted installed {} into source {}", name, source));
+        self.close_diff();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn adopt_copies_the_installed_file_into_a_local_source_after_confirming() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crate::source::config::{ResolvedSource, SourceKind};
+
+        let dir = temp_dir("adopt_app");
+        let (source, dest) = (dir.join("source.md"), dir.join("dest.md"));
+        std::fs::write(&source, "v1\n").unwrap();
+        std::fs::write(&dest, "v1 edited\n").unwrap();
+
+        let mut app = App::for_test();
+        app.tab = Tab::Agents;
+        app.components = vec![
+            Component::new(ComponentType::Agents, "a.md".into(), source.clone(), dest, InstallStatus::Modified)
+                .with_source_name("dotfiles"),
+        ];
+        let mut dotfiles = ResolvedSource::bundled(&dir);
+        dotfiles.label = "dotfiles".into();
+        dotfiles.kind = SourceKind::Git;
+        app.sources = vec![dotfiles];
+
+        app.request_adopt().unwrap();
+        assert!(!app.adopt_armed, "git sources are refused");
+        assert!(app.status_message.as_deref().unwrap().contains("commit the change there"));
+
+        app.sources[0].kind = SourceKind::Local;
+        app.request_adopt().unwrap();
+        assert!(app.adopt_armed);
+        assert_eq!(std::fs::read_to_string(&source).unwrap(), "v1\n", "first press only asks");
+        app.request_adopt().unwrap();
+        let adopted = std::fs::read_to_string(&source).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(adopted, "v1 edited\n");
+        assert_eq!(app.components[0].status, InstallStatus::Unchanged);
+        assert_eq!(app.session_summary.changes, vec!["Adopted agents/a.md into source dotfiles"]);
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::mpsc;
+
+use crate::fs::CliInfo;
+
+use super::types::TargetCli;
+use super::App;
+
+/// Probe results for every CLI the selection screen offers.
+pub type CliProbePayload = Vec<(TargetCli, CliInfo)>;
+
+impl App {
+    /// Resolve which target CLI binaries will be spawned, and their
+    /// versions, on a background thread so startup never waits on a slow
+    /// `--version`. Results land in `cli_infos` via `poll_cli_probe`.
+    pub fn start_cli_probe(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let infos: CliProbePayload = TargetCli::all()
+                .into_iter()
+                .map(|cli| (cli, crate::fs::probe_cli(cli)))
+                .collect();
+            let _ = tx.send(infos);
+        });
+        self.cli_probe_rx = Some(rx);
+    }
+
+    /// Apply a finished probe, if any. Called every loop tick.
+    pub fn poll_cli_probe(&mut self) {
+        let Some(rx) = &self.cli_probe_rx else { return };
+        match rx.try_recv() {
+            Ok(infos) => {
+                self.cli_infos = infos;
+                self.cli_probe_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.cli_probe_rx = None,
+        }
+    }
+
+    /// Probe result for `cli`, once the background probe has finished.
+    pub fn cli_info(&self, cli: TargetCli) -> Option<&CliInfo> {
+        self.cli_infos.iter().find(|(c, _)| *c == cli).map(|(_, info)| info)
+    }
+}
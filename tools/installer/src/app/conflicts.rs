@@ -0,0 +1,165 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::View;
+use super::App;
+use crate::component::InstallStatus;
+use crate::fs;
+
+impl App {
+    /// Queue the install's modified files for a decision, one dialog each,
+    /// so local edits are never overwritten without asking. Files edited
+    /// only upstream, or whose local edits merge cleanly, need none.
+    /// `false` when there are none and the install can go on.
+    pub(super) fn open_conflicts(&mut self) -> bool {
+        if self.tab_component_type(self.tab).is_none() {
+            return false;
+        }
+        self.conflicts = self.processing_queue.iter()
+            .copied()
+            .filter(|&i| self.components.get(i).is_some_and(|c| {
+                c.status == InstallStatus::Modified
+                    && fs::installer::has_local_edits(c, &self.dest_dir)
+                    && fs::installer::merged_content(c, &self.dest_dir).is_none()
+            }))
+            .collect();
+        if self.conflicts.is_empty() {
+            return false;
+        }
+        self.diff_content = None;
+        self.current_view = View::ConflictResolve;
+        true
+    }
+
+    /// `o` overwrites, `O` overwrites this and every remaining file, `k`
+    /// keeps the local file (and deselects it), `s` skips it for this
+    /// install, `v` shows the diff. `Esc` cancels the whole install.
+    pub fn handle_conflict_key(&mut self, key: KeyCode) -> Result<()> {
+        let Some(&idx) = self.conflicts.first() else {
+            self.current_view = View::List;
+            return Ok(());
+        };
+        match key {
+            KeyCode::Char('o') => {
+                self.conflicts.remove(0);
+                self.next_conflict()?;
+            }
+            KeyCode::Char('O') => {
+                self.conflicts.clear();
+                self.next_conflict()?;
+            }
+            KeyCode::Char('k') => {
+                let name = self.components[idx].display_name();
+                self.components[idx].selected = false;
+                self.session_summary.changes.push(format!("Kept local edits: {}", name));
+                self.install_notes.push(format!("[SKIP] {}: kept local edits", name));
+                self.drop_conflict(idx)?;
+            }
+            KeyCode::Char('s') => {
+                let name = self.components[idx].display_name();
+                self.install_notes.push(format!("[SKIP] {}: skipped", name));
+                self.drop_conflict(idx)?;
+            }
+            KeyCode::Char('v') => {
+                self.diff_content = match self.diff_content {
+                    Some(_) => None,
+                    None => Some(fs::diff::component_diff(&self.components[idx])?),
+                };
+                self.diff_scroll = 0;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.diff_content.is_some() => self.scroll_diff_down(),
+            KeyCode::Up if self.diff_content.is_some() => self.scroll_diff_up(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.conflicts.clear();
+                self.install_notes.clear();
+                self.processing_queue.clear();
+                self.diff_content = None;
+                self.status_message = Some("Install cancelled".to_string());
+                self.current_view = View::List;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Take the file at `idx` out of the install.
+    fn drop_conflict(&mut self, idx: usize) -> Result<()> {
+        self.conflicts.remove(0);
+        self.processing_queue.retain(|&i| i != idx);
+        self.next_conflict()
+    }
+
+    /// Show the next conflict, or carry on with the install once every
+    /// file has been decided.
+    fn next_conflict(&mut self) -> Result<()> {
+        self.diff_content = None;
+        self.diff_scroll = 0;
+        if !self.conflicts.is_empty() {
+            return Ok(());
+        }
+        if self.processing_queue.is_empty() {
+            self.install_notes.clear();
+            self.status_message = Some("Nothing left to install".to_string());
+            self.current_view = View::List;
+            return Ok(());
+        }
+        self.continue_install()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn modified_files_ask_before_being_overwritten() {
+        use crate::app::View;
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crossterm::event::KeyCode;
+
+        let dir = temp_dir("conflicts");
+        let (source, dest) = (dir.join("src"), dir.join("dest"));
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&dest).unwrap();
+        let mut components = Vec::new();
+        for (name, status) in [("a.md", InstallStatus::Modified), ("b.md", InstallStatus::Modified), ("c.md", InstallStatus::New)] {
+            std::fs::write(source.join(name), "source\n").unwrap();
+            if status == InstallStatus::Modified {
+                std::fs::write(dest.join(name), "local edit\n").unwrap();
+            }
+            let mut c = Component::new(ComponentType::Agents, name.into(), source.join(name), dest.join(name), status);
+            c.selected = true;
+            components.push(c);
+        }
+
+        let mut app = App::for_test();
+        app.dest_dir = dest.clone();
+        app.tab = Tab::Agents;
+        app.components = components;
+        app.start_install(vec![0, 1, 2]).unwrap();
+        assert!(app.current_view == View::ConflictResolve);
+        app.handle_conflict_key(KeyCode::Char('v')).unwrap();
+        let diff = app.diff_content.clone().unwrap_or_default();
+        app.handle_conflict_key(KeyCode::Char('k')).unwrap();
+        assert_eq!(app.conflicts, [1]);
+        assert!(app.diff_content.is_none());
+        app.handle_conflict_key(KeyCode::Char('o')).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(diff.contains("-local edit") && diff.contains("+source"));
+        assert!(app.current_view == View::Installing);
+        assert_eq!(app.processing_queue, [1, 2]);
+        assert!(!app.components[0].selected);
+        assert!(app.processing_log.iter().any(|l| l == "[SKIP] agents/a.md: kept local edits"));
+
+        // Left over from a resume that stopped at its conflicts.
+        app.resumed_progress = Some(7);
+        app.current_view = View::List;
+        app.start_install(vec![0, 1]).unwrap();
+        assert_eq!(app.resumed_progress, None);
+        app.handle_conflict_key(KeyCode::Esc).unwrap();
+        assert!(app.current_view == View::List);
+        assert!(app.processing_queue.is_empty());
+    }
+}
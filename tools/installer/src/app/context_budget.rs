@@ -0,0 +1,62 @@
+//! Estimated context cost of agents, rules, contexts and instruction files
+//! (see `Component::estimated_tokens`), and the warning shown once the
+//! files loaded into every session exceed `token_budget:`.
+
+use crate::component::{Component, InstallStatus};
+
+use super::types::Tab;
+use super::App;
+
+impl App {
+    /// Estimated tokens of `tab`'s context files; `None` for tabs that
+    /// have none (hooks, skills, MCP, ...).
+    pub fn tab_context_tokens(&self, tab: Tab) -> Option<u64> {
+        let comp_type = self.tab_component_type(tab)?;
+        let mut files = self.components.iter()
+            .filter(|c| c.component_type == comp_type && c.is_context_file())
+            .peekable();
+        files.peek()?;
+        Some(files.map(Component::estimated_tokens).sum())
+    }
+
+    /// Estimated tokens of the always-loaded files in place after the
+    /// current selection is installed: everything already in the config
+    /// dir plus the selected new files.
+    pub fn always_loaded_tokens(&self) -> u64 {
+        self.components.iter()
+            .filter(|c| c.is_always_loaded() && (c.selected || c.status != InstallStatus::New))
+            .map(Component::estimated_tokens)
+            .sum()
+    }
+
+    /// Warning line when `always_loaded_tokens` is over the budget.
+    pub fn token_budget_warning(&self) -> Option<String> {
+        let tokens = self.always_loaded_tokens();
+        (tokens > self.token_budget).then(|| format!(
+            "always-loaded ~{} tokens, over the {} budget",
+            format_tokens(tokens),
+            format_tokens(self.token_budget),
+        ))
+    }
+}
+
+/// Token count with one decimal above a thousand: `850`, `12.3k`.
+pub fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1000 {
+        format!("{:.1}k", tokens as f64 / 1000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_tokens_abbreviates_thousands() {
+        assert_eq!(format_tokens(850), "850");
+        assert_eq!(format_tokens(1000), "1.0k");
+        assert_eq!(format_tokens(12_345), "12.3k");
+    }
+}
@@ -0,0 +1,60 @@
+//! `F` on the Styles and Statusline tabs: repair a default whose file is
+//! missing (see `fs::installer::dangling_settings`) by installing the file
+//! from the sources, or by unsetting the key when no source has it.
+
+use anyhow::Result;
+
+use super::types::Tab;
+use super::App;
+use crate::component::ComponentType;
+use crate::fs::installer::DanglingSetting;
+
+impl App {
+    /// The missing-file default shown on the current tab, if any.
+    pub fn dangling_for_tab(&self) -> Option<&DanglingSetting> {
+        self.dangling_settings.iter().find(|d| match d {
+            DanglingSetting::OutputStyle { .. } => self.tab == Tab::OutputStyles,
+            DanglingSetting::StatusLine { .. } => self.tab == Tab::Statusline,
+        })
+    }
+
+    /// Short warning for the list title, e.g. `terse.md missing`.
+    pub fn dangling_warning(&self) -> Option<String> {
+        self.dangling_for_tab().map(|d| match d {
+            DanglingSetting::OutputStyle { style } => format!("{}.md missing", style),
+            DanglingSetting::StatusLine { missing, .. } => format!("{} missing", missing.display()),
+        })
+    }
+
+    /// Index of the source component that installs the missing file.
+    fn dangling_source(&self, dangling: &DanglingSetting) -> Option<usize> {
+        self.components.iter().position(|c| match dangling {
+            DanglingSetting::OutputStyle { style } => c.component_type == ComponentType::OutputStyles
+                && c.name.strip_suffix(".md").unwrap_or(&c.name) == style,
+            DanglingSetting::StatusLine { missing, .. } => c.component_type == ComponentType::Statusline
+                && c.dest_path == *missing,
+        })
+    }
+
+    /// Install the missing file if a source has it, otherwise unset the
+    /// key that points at it.
+    pub fn fix_dangling(&mut self) -> Result<()> {
+        // Statuses from an earlier batch must be current before the next.
+        self.run_deferred_refresh(true);
+        let Some(dangling) = self.dangling_for_tab().cloned() else { return Ok(()) };
+        if let Some(idx) = self.dangling_source(&dangling) {
+            return self.start_install(vec![idx]);
+        }
+        match dangling {
+            DanglingSetting::OutputStyle { style } => {
+                self.unset_default_style()?;
+                self.status_message = Some(format!("No source has style '{}'; unset outputStyle", style));
+            }
+            DanglingSetting::StatusLine { missing, .. } => {
+                self.unset_statusline()?;
+                self.status_message = Some(format!("No source has {}; unset statusLine", missing.display()));
+            }
+        }
+        Ok(())
+    }
+}
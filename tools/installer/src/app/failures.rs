@@ -0,0 +1,126 @@
+//! Full output of the items that failed in the current batch. The log keeps
+//! one line per failure; `e` in the processing view opens a scrollable popup
+//! with the command and everything it printed (see
+//! `fs::installer::ProcessFailure`).
+
+use crossterm::event::KeyCode;
+
+use super::App;
+use crate::fs::installer::ProcessFailure;
+
+/// Lines scrolled by PageUp/PageDown in the popup.
+const PAGE_LINES: u16 = 10;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemFailure {
+    pub name: String,
+    /// The log line, without its `[ERR]` tag.
+    pub message: String,
+    /// Command line that failed, when the failure came from one.
+    pub command: Option<String>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ItemFailure {
+    /// Build from the error an item's thread returned. The captured output
+    /// is found anywhere in the error chain, so context added on the way up
+    /// doesn't hide it.
+    pub fn from_error(name: String, error: &anyhow::Error) -> Self {
+        let process = error.chain().find_map(|cause| cause.downcast_ref::<ProcessFailure>());
+        ItemFailure {
+            name,
+            message: format!("{:#}", error),
+            command: process.map(|p| p.command.clone()),
+            stdout: process.map(|p| p.stdout.clone()).unwrap_or_default(),
+            stderr: process.map(|p| p.stderr.clone()).unwrap_or_default(),
+        }
+    }
+
+    /// Popup body: the message, then each captured stream under a heading.
+    pub fn detail_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.message.lines().map(str::to_string).collect();
+        if let Some(command) = &self.command {
+            lines.push(String::new());
+            lines.push(format!("$ {}", command));
+        }
+        for (heading, output) in [("stderr", &self.stderr), ("stdout", &self.stdout)] {
+            if output.trim().is_empty() {
+                continue;
+            }
+            lines.push(String::new());
+            lines.push(format!("── {} ──", heading));
+            lines.extend(output.lines().map(str::to_string));
+        }
+        lines
+    }
+}
+
+impl App {
+    /// Remember a failed item's output for the detail popup.
+    pub(crate) fn record_failure(&mut self, idx: usize, error: &anyhow::Error) {
+        let name = self.processed_item_name(idx).unwrap_or_else(|| format!("item {}", idx + 1));
+        self.item_failures.push(ItemFailure::from_error(name, error));
+    }
+
+    /// Open the popup on the most recent failure.
+    pub fn open_failure_detail(&mut self) {
+        if let Some(last) = self.item_failures.len().checked_sub(1) {
+            self.failure_detail = Some(last);
+            self.failure_scroll = 0;
+        }
+    }
+
+    pub fn current_failure(&self) -> Option<&ItemFailure> {
+        self.failure_detail.and_then(|i| self.item_failures.get(i))
+    }
+
+    /// Keys while the popup is open: scroll, step between failures, close.
+    pub fn handle_failure_key(&mut self, key: KeyCode) {
+        let Some(current) = self.failure_detail else { return };
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('e') => self.failure_detail = None,
+            KeyCode::Down | KeyCode::Char('j') => self.failure_scroll = self.failure_scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.failure_scroll = self.failure_scroll.saturating_sub(1),
+            KeyCode::PageDown => self.failure_scroll = self.failure_scroll.saturating_add(PAGE_LINES),
+            KeyCode::PageUp => self.failure_scroll = self.failure_scroll.saturating_sub(PAGE_LINES),
+            KeyCode::Char('g') | KeyCode::Home => self.failure_scroll = 0,
+            KeyCode::Char('n') | KeyCode::Right if current + 1 < self.item_failures.len() => {
+                self.failure_detail = Some(current + 1);
+                self.failure_scroll = 0;
+            }
+            KeyCode::Char('p') | KeyCode::Left if current > 0 => {
+                self.failure_detail = Some(current - 1);
+                self.failure_scroll = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_output_is_found_through_context() {
+        let failure = ProcessFailure {
+            message: "Failed to install ctx: boom".to_string(),
+            command: "claude mcp add ctx".to_string(),
+            stdout: "resolving\n".to_string(),
+            stderr: "line one\nboom\n".to_string(),
+        };
+        let error = anyhow::Error::new(failure).context("MCP install failed");
+        let item = ItemFailure::from_error("ctx".to_string(), &error);
+        assert_eq!(item.message, "MCP install failed: Failed to install ctx: boom");
+        assert_eq!(item.command.as_deref(), Some("claude mcp add ctx"));
+        let lines = item.detail_lines();
+        assert!(lines.contains(&"$ claude mcp add ctx".to_string()));
+        let stderr = lines.iter().position(|l| l == "── stderr ──").unwrap();
+        assert_eq!(lines[stderr + 1..stderr + 3], ["line one".to_string(), "boom".to_string()]);
+        assert!(lines.contains(&"resolving".to_string()));
+
+        let plain = ItemFailure::from_error("a.md".to_string(), &anyhow::anyhow!("permission denied"));
+        assert_eq!(plain.detail_lines(), vec!["permission denied".to_string()]);
+    }
+}
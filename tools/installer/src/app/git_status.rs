@@ -0,0 +1,123 @@
+use crossterm::event::KeyCode;
+
+use super::types::View;
+use super::App;
+use crate::component::Component;
+use crate::source::SourceChanges;
+
+impl App {
+    /// The uncommitted changes covering `component`'s source file, if any.
+    pub fn uncommitted_source(&self, component: &Component) -> Option<&SourceChanges> {
+        self.source_changes.iter().find(|c| c.touches(&component.source_path))
+    }
+
+    /// List-view banner while a local source has uncommitted changes.
+    pub fn source_changes_banner(&self) -> Option<String> {
+        if self.source_changes.is_empty() {
+            return None;
+        }
+        let sources: Vec<String> = self.source_changes.iter()
+            .map(|c| format!("{} ({} file(s))", c.label, c.status.len()))
+            .collect();
+        let effect = if self.committed_only { "left out of installs" } else { "installed as they are" };
+        Some(format!("Uncommitted changes in {}: {}  [W] git status", sources.join(", "), effect))
+    }
+
+    /// With `committed_only:`, drop the items whose source file has
+    /// uncommitted changes from `indices`, returning their names.
+    pub(super) fn hold_back_uncommitted(&self, indices: &mut Vec<usize>) -> Vec<String> {
+        if !self.committed_only {
+            return Vec::new();
+        }
+        let mut held_back = Vec::new();
+        indices.retain(|&idx| {
+            let Some(component) = self.components.get(idx) else { return true };
+            let uncommitted = self.uncommitted_source(component).is_some();
+            if uncommitted {
+                held_back.push(component.name.clone());
+            }
+            !uncommitted
+        });
+        held_back
+    }
+
+    /// Check the local sources for uncommitted changes again, e.g. after
+    /// adopting wrote into one.
+    pub(super) fn refresh_source_changes(&mut self) {
+        self.source_changes = crate::source::uncommitted_source_changes(&self.sources);
+    }
+
+    pub fn open_git_status(&mut self) {
+        if self.source_changes.is_empty() {
+            self.status_message = Some("No uncommitted changes in the local sources".to_string());
+            return;
+        }
+        self.git_status_scroll = 0;
+        self.current_view = View::GitStatus;
+    }
+
+    /// `git status --short` of every source with changes, under a heading
+    /// naming the source.
+    pub fn git_status_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for changes in &self.source_changes {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{}  {}", changes.label, crate::source::config::contract_tilde(&changes.path)));
+            lines.extend(changes.status.iter().map(|line| format!("  {}", line)));
+        }
+        lines
+    }
+
+    pub fn handle_git_status_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('W') => self.current_view = View::List,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.git_status_scroll = (self.git_status_scroll + 1).min(self.git_status_lines().len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.git_status_scroll = self.git_status_scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn committed_only_leaves_uncommitted_source_files_out_of_installs() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crate::source::SourceChanges;
+
+        let dir = temp_dir("committed_only");
+        let mut app = App::for_test();
+        app.tab = Tab::Agents;
+        app.dest_dir = dir.join("dest");
+        app.components = ["a.md", "b.md"].iter()
+            .map(|n| {
+                let mut c = Component::new(ComponentType::Agents, (*n).into(), dir.join("src/agents").join(n), dir.join("dest").join(n), InstallStatus::New);
+                c.selected = true;
+                c
+            })
+            .collect();
+        app.source_changes = vec![SourceChanges {
+            label: "dotfiles".into(),
+            path: dir.join("src"),
+            status: vec![crate::source::git::StatusEntry {
+                code: " M".into(),
+                path: "agents/a.md".into(),
+                from: None,
+            }],
+        }];
+        assert!(app.source_changes_banner().unwrap().contains("dotfiles (1 file(s))"));
+
+        app.committed_only = true;
+        app.install_selected().unwrap();
+
+        assert_eq!(app.processing_queue, vec![1]);
+        assert!(app.session_summary.warnings.iter().any(|w| w.contains("a.md")));
+    }
+}
@@ -0,0 +1,131 @@
+//! One-line tips for first-time users, shown in the status bar border of
+//! the list view. Each tip belongs to a feature that is easy to miss and
+//! stops showing once its keys have been used a few times or it has been
+//! dismissed with `Esc`; both are remembered in `~/.hibi/ui.yaml`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crossterm::event::KeyCode;
+
+use super::types::{FocusArea, Tab};
+use super::{ui_state, App};
+
+/// Uses of a tip's keys after which it is considered learned.
+const LEARNED_AFTER: u32 = 3;
+
+/// Tips dismissed and key uses counted so far, keyed by hint id.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HintProgress {
+    pub dismissed: BTreeSet<String>,
+    pub uses: BTreeMap<String, u32>,
+}
+
+struct Hint {
+    /// Stable name stored in `ui.yaml`.
+    id: &'static str,
+    text: &'static str,
+    /// Keys that count as using the feature.
+    keys: &'static [KeyCode],
+    applies: fn(&App) -> bool,
+}
+
+fn content_focused(app: &App) -> bool {
+    app.focus == FocusArea::Content
+}
+
+/// In priority order; the first tip that applies and isn't learned shows.
+const HINTS: &[Hint] = &[
+    Hint {
+        id: "folders",
+        text: "[h/l] fold the folder under the cursor, [z/Z] collapse/expand every folder",
+        keys: &[KeyCode::Char('h'), KeyCode::Char('l'), KeyCode::Left, KeyCode::Right,
+            KeyCode::Char('z'), KeyCode::Char('Z')],
+        applies: |app| content_focused(app) && app.tree_views.get(&app.tab)
+            .is_some_and(|tree| tree.nodes.iter().any(|node| node.is_folder())),
+    },
+    Hint {
+        id: "mcp-scope",
+        text: "[o] switches MCP servers between your user config and a project",
+        keys: &[KeyCode::Char('o')],
+        applies: |app| content_focused(app) && app.tab == Tab::McpServers,
+    },
+    Hint {
+        id: "diff",
+        text: "[d] shows how an item differs from the installed copy",
+        keys: &[KeyCode::Char('d')],
+        applies: |app| content_focused(app) && app.tab_component_type(app.tab).is_some(),
+    },
+    Hint {
+        id: "tabs",
+        text: "[Tab] moves focus to the tab bar, then [h/l] switches tabs",
+        keys: &[KeyCode::Tab, KeyCode::BackTab],
+        applies: content_focused,
+    },
+    Hint {
+        id: "help",
+        text: "[?] lists every key for this tab",
+        keys: &[KeyCode::Char('?')],
+        applies: |_| true,
+    },
+];
+
+impl HintProgress {
+    fn learned(&self, hint: &Hint) -> bool {
+        self.dismissed.contains(hint.id) || self.uses.get(hint.id).copied().unwrap_or(0) >= LEARNED_AFTER
+    }
+}
+
+impl App {
+    fn current_hint(&self) -> Option<&'static Hint> {
+        HINTS.iter().find(|hint| (hint.applies)(self) && !self.hints.learned(hint))
+    }
+
+    /// Tip to show in the list view, if any.
+    pub fn hint_text(&self) -> Option<&'static str> {
+        self.current_hint().map(|hint| hint.text)
+    }
+
+    /// Count a list-view key press towards the tips it belongs to.
+    pub fn note_hint_key(&mut self, key: KeyCode) {
+        let used: Vec<&str> = HINTS.iter()
+            .filter(|hint| hint.keys.contains(&key) && (hint.applies)(self))
+            .map(|hint| hint.id)
+            .collect();
+        for id in used {
+            *self.hints.uses.entry(id.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Hide the current tip for good. Returns whether one was showing.
+    pub fn dismiss_hint(&mut self) -> bool {
+        let Some(hint) = self.current_hint() else { return false };
+        self.hints.dismissed.insert(hint.id.to_string());
+        if let Some(home) = dirs::home_dir()
+            && let Err(e) = ui_state::save_hints(&home, &self.hints)
+        {
+            self.status_message = Some(format!("Failed to save tip: {:#}", e));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_are_learned_by_use_or_dismissal() {
+        let diff = HINTS.iter().find(|h| h.id == "diff").unwrap();
+        let mut progress = HintProgress::default();
+        assert!(!progress.learned(diff));
+
+        progress.uses.insert("diff".to_string(), LEARNED_AFTER - 1);
+        assert!(!progress.learned(diff));
+        progress.uses.insert("diff".to_string(), LEARNED_AFTER);
+        assert!(progress.learned(diff));
+
+        let help = HINTS.iter().find(|h| h.id == "help").unwrap();
+        progress.dismissed.insert("help".to_string());
+        assert!(progress.learned(help));
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::App;
+use crate::component::HookConfig;
+use crate::fs;
+
+impl App {
+    /// Open the detail dialog for the highlighted hook. Env rows start from
+    /// the hook.yaml defaults, overridden by whatever is currently written
+    /// into settings.json.
+    pub fn open_hook_detail(&mut self) {
+        if self.tab != Tab::Hooks {
+            return;
+        }
+
+        let Some(idx) = self.selected_component_index() else { return };
+        let Some(config) = self.components.get(idx).and_then(|c| c.hook_config.as_ref()) else {
+            return;
+        };
+
+        let mut env = config.env.clone();
+        if let Some(current) = fs::installer::read_hook_env(&self.dest_dir, config) {
+            env.extend(current);
+        }
+
+        self.hook_detail_idx = Some(idx);
+        self.hook_env_rows = env.into_iter().collect();
+        self.hook_env_cursor = 0;
+        self.hook_env_editing = false;
+        self.hook_env_buffer.clear();
+        self.current_view = View::HookDetail;
+    }
+
+    pub fn current_hook_detail(&self) -> Option<&HookConfig> {
+        self.hook_detail_idx
+            .and_then(|idx| self.components.get(idx))
+            .and_then(|c| c.hook_config.as_ref())
+    }
+
+    pub fn handle_hook_detail_key(&mut self, key: KeyCode) -> Result<()> {
+        if self.hook_env_editing {
+            match key {
+                KeyCode::Esc => {
+                    self.hook_env_editing = false;
+                    self.hook_env_buffer.clear();
+                }
+                KeyCode::Enter => self.save_hook_env_value()?,
+                KeyCode::Backspace => { self.hook_env_buffer.pop(); }
+                KeyCode::Char(c) => self.hook_env_buffer.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_hook_detail(),
+            KeyCode::Down | KeyCode::Char('j') if self.hook_env_cursor + 1 < self.hook_env_rows.len() => {
+                self.hook_env_cursor += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.hook_env_cursor = self.hook_env_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some((_, value)) = self.hook_env_rows.get(self.hook_env_cursor) {
+                    self.hook_env_buffer = value.clone();
+                    self.hook_env_editing = true;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn save_hook_env_value(&mut self) -> Result<()> {
+        let Some(config) = self.current_hook_detail().cloned() else { return Ok(()) };
+        let Some(row) = self.hook_env_rows.get_mut(self.hook_env_cursor) else { return Ok(()) };
+
+        let previous = std::mem::replace(&mut row.1, self.hook_env_buffer.clone());
+        let env = self.hook_env_rows.iter().cloned().collect();
+
+        if fs::installer::set_hook_env(&self.dest_dir, &config, &env)? {
+            self.hook_env_editing = false;
+            self.hook_env_buffer.clear();
+            self.status_message = Some(format!("Updated env for hook {}", config.name));
+        } else {
+            // Not registered: nothing to write into, so keep the old value.
+            self.hook_env_rows[self.hook_env_cursor].1 = previous;
+            self.status_message = Some(format!("Install hook {} first", config.name));
+        }
+        Ok(())
+    }
+
+    pub fn close_hook_detail(&mut self) {
+        self.hook_detail_idx = None;
+        self.hook_env_rows.clear();
+        self.hook_env_editing = false;
+        self.hook_env_buffer.clear();
+        self.current_view = View::List;
+    }
+}
@@ -0,0 +1,92 @@
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::App;
+use crate::hook_log::{self, LogLevel, LogLine};
+
+/// Lines kept from the end of each log file.
+const TAIL_LINES: usize = 500;
+
+/// Minimum-level filter steps, cycled with `l`.
+const LEVEL_FILTERS: [Option<LogLevel>; 4] = [None, Some(LogLevel::Info), Some(LogLevel::Warn), Some(LogLevel::Error)];
+
+impl App {
+    /// Names of every hook known to the current sources.
+    fn hook_names(&self) -> Vec<String> {
+        self.components.iter()
+            .filter_map(|c| c.hook_config.as_ref().map(|h| h.name.clone()))
+            .collect()
+    }
+
+    pub fn open_hook_logs(&mut self) {
+        if self.tab != Tab::Hooks {
+            return;
+        }
+        self.hook_log_filter = None;
+        self.hook_log_level = None;
+        self.hook_log_scroll = 0;
+        self.hook_log_stamp.clear();
+        self.refresh_hook_logs();
+        self.current_view = View::HookLogs;
+    }
+
+    /// Re-read the logs if any file changed since the last read. Called
+    /// every tick while the viewer is open.
+    pub fn refresh_hook_logs(&mut self) {
+        let names = self.hook_names();
+        let files = hook_log::log_files(&self.dest_dir, &names);
+        let stamp = hook_log::log_stamp(&files);
+        if stamp == self.hook_log_stamp && !self.hook_log_lines.is_empty() {
+            return;
+        }
+        self.hook_log_lines = hook_log::read_tail(&files, &names, TAIL_LINES);
+        self.hook_log_stamp = stamp;
+    }
+
+    /// Lines passing the hook and level filters.
+    pub fn visible_hook_logs(&self) -> Vec<&LogLine> {
+        self.hook_log_lines.iter()
+            .filter(|l| self.hook_log_filter.is_none() || l.hook == self.hook_log_filter)
+            .filter(|l| match self.hook_log_level {
+                Some(min) => l.level.is_some_and(|level| level >= min),
+                None => true,
+            })
+            .collect()
+    }
+
+    pub fn handle_hook_logs_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.hook_log_lines.clear();
+                self.current_view = View::List;
+            }
+            KeyCode::Char('h') => {
+                // Cycle: all hooks -> each hook in turn -> all hooks.
+                let names = self.hook_names();
+                let next = match &self.hook_log_filter {
+                    None => names.first().cloned(),
+                    Some(current) => names.iter()
+                        .position(|n| n == current)
+                        .and_then(|i| names.get(i + 1))
+                        .cloned(),
+                };
+                self.hook_log_filter = next;
+                self.hook_log_scroll = 0;
+            }
+            KeyCode::Char('l') => {
+                let i = LEVEL_FILTERS.iter().position(|l| *l == self.hook_log_level).unwrap_or(0);
+                self.hook_log_level = LEVEL_FILTERS[(i + 1) % LEVEL_FILTERS.len()];
+                self.hook_log_scroll = 0;
+            }
+            // Scroll is counted back from the newest line.
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.hook_log_scroll = (self.hook_log_scroll + 1).min(self.visible_hook_logs().len().saturating_sub(1));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.hook_log_scroll = self.hook_log_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('G') => self.hook_log_scroll = 0,
+            _ => {}
+        }
+    }
+}
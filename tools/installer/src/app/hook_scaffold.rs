@@ -0,0 +1,64 @@
+use crossterm::event::KeyCode;
+use anyhow::{Context, Result};
+
+use crate::component::{hook_yaml_template, ComponentType, InstallStatus, HOOK_EVENTS};
+
+use super::types::View;
+use super::App;
+
+impl App {
+    /// The highlighted unconfigured hook: its index in `components`.
+    fn unconfigured_hook(&self) -> Option<usize> {
+        self.selected_component_index().filter(|&idx| {
+            self.components.get(idx).is_some_and(|c| {
+                c.component_type == ComponentType::Hooks && c.status == InstallStatus::Unconfigured
+            })
+        })
+    }
+
+    /// Ask which event the highlighted unconfigured hook runs on (`c` on
+    /// the Hooks tab), then write a hook.yaml for it.
+    pub fn open_hook_scaffold(&mut self) {
+        if self.unconfigured_hook().is_none() {
+            self.status_message = Some("Highlight an unconfigured hook to create its hook.yaml".to_string());
+            return;
+        }
+        self.hook_scaffold_index = 0;
+        self.current_view = View::HookScaffold;
+    }
+
+    pub fn handle_hook_scaffold_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.current_view = View::List,
+            KeyCode::Down | KeyCode::Char('j') if self.hook_scaffold_index + 1 < HOOK_EVENTS.len() => {
+                self.hook_scaffold_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.hook_scaffold_index = self.hook_scaffold_index.saturating_sub(1),
+            KeyCode::Enter => {
+                self.current_view = View::List;
+                self.write_hook_yaml(HOOK_EVENTS[self.hook_scaffold_index])?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Write the template into the hook's directory. An existing hook.yaml
+    /// (added since the scan) is never overwritten.
+    fn write_hook_yaml(&mut self, event: &str) -> Result<()> {
+        let Some(component) = self.unconfigured_hook().and_then(|idx| self.components.get(idx)) else { return Ok(()) };
+        let path = component.source_path.join("hook.yaml");
+        if path.exists() {
+            self.status_message = Some(format!("{} already exists; press R to rescan", path.display()));
+            return Ok(());
+        }
+        std::fs::write(&path, hook_yaml_template(&component.name, event))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.status_message = Some(format!(
+            "Created {}. Fill in the description, add the {}_<os> binaries next to it and press R to rescan",
+            path.display(),
+            component.name
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use super::{App, build_tree_views};
+
+impl App {
+    /// Components another machine's `hosts:` filter claims, on any tab.
+    pub fn other_host_count(&self) -> usize {
+        self.components.iter().filter(|c| !c.for_this_host()).count()
+    }
+
+    /// Show or hide the components meant for other machines. Hidden ones
+    /// are deselected so a batch never picks them up unseen.
+    pub fn toggle_all_hosts(&mut self) {
+        self.show_all_hosts = !self.show_all_hosts;
+        if !self.show_all_hosts {
+            for c in self.components.iter_mut().filter(|c| !c.for_this_host()) {
+                c.selected = false;
+            }
+        }
+        self.tree_views = build_tree_views(&self.components, &self.directories, self.show_all_hosts);
+        self.sort_trees();
+        self.status_message = Some(if self.show_all_hosts {
+            format!("Showing {} item(s) meant for other hosts", self.other_host_count())
+        } else {
+            format!("Hiding {} item(s) meant for other hosts", self.other_host_count())
+        });
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+
+use crate::fs::hunks::{apply_component_hunks, component_hunks};
+
+use super::App;
+
+impl App {
+    /// Load the hunks of the component shown in the diff view, when it can
+    /// be applied hunk by hunk. Nothing is staged at first.
+    pub(super) fn load_diff_hunks(&mut self, idx: usize) {
+        self.diff_hunks = self.components.get(idx).and_then(|c| component_hunks(c).ok().flatten());
+        self.hunk_staged = vec![false; self.diff_hunks.as_ref().map_or(0, |h| h.hunks.len())];
+        self.hunk_index = 0;
+        if self.diff_hunks.is_some() {
+            self.diff_content = Some(self.hunk_view());
+        }
+    }
+
+    /// Diff text in hunks, each header marked staged (`[x]`) or not and
+    /// the current one pointed at.
+    fn hunk_view(&self) -> String {
+        let Some(diff) = &self.diff_hunks else { return String::new() };
+        let mut out = String::new();
+        for (i, hunk) in diff.hunks.iter().enumerate() {
+            let staged = if self.hunk_staged[i] { "[x]" } else { "[ ]" };
+            let current = if i == self.hunk_index { " ◀" } else { "" };
+            out.push_str(&format!("{} {}{}\n", hunk.header, staged, current));
+            for line in &hunk.lines {
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Line of the current hunk's header in `hunk_view`.
+    fn hunk_offset(&self) -> usize {
+        self.diff_hunks.as_ref().map_or(0, |d| {
+            d.hunks[..self.hunk_index].iter().map(|h| h.lines.len() + 2).sum()
+        })
+    }
+
+    fn show_hunk(&mut self, index: usize) {
+        self.hunk_index = index;
+        self.diff_content = Some(self.hunk_view());
+        self.diff_scroll = u16::try_from(self.hunk_offset()).unwrap_or(u16::MAX);
+    }
+
+    /// `n`/`p` move between hunks, Space stages or unstages the current
+    /// one, `a` writes the staged hunks to the installed file. Returns
+    /// `false` for keys it doesn't handle.
+    pub fn handle_hunk_key(&mut self, key: crossterm::event::KeyCode) -> Result<bool> {
+        use crossterm::event::KeyCode;
+        let count = self.hunk_staged.len();
+        if self.diff_hunks.is_none() {
+            return Ok(false);
+        }
+        match key {
+            KeyCode::Char('n') if self.hunk_index + 1 < count => self.show_hunk(self.hunk_index + 1),
+            KeyCode::Char('p') if self.hunk_index > 0 => self.show_hunk(self.hunk_index - 1),
+            KeyCode::Char(' ') => {
+                self.hunk_staged[self.hunk_index] = !self.hunk_staged[self.hunk_index];
+                self.diff_content = Some(self.hunk_view());
+            }
+            KeyCode::Char('a') => self.apply_staged_hunks()?,
+            KeyCode::Char('n' | 'p') => {}
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn apply_staged_hunks(&mut self) -> Result<()> {
+        let staged = self.hunk_staged.iter().filter(|s| **s).count();
+        if staged == 0 {
+            self.status_message = Some("No hunks staged; press Space to stage one".to_string());
+            return Ok(());
+        }
+        let Some(idx) = self.selected_component_index() else { return Ok(()) };
+        let (Some(c), Some(diff)) = (self.components.get(idx), &self.diff_hunks) else { return Ok(()) };
+        apply_component_hunks(&self.dest_dir, c, diff, &self.hunk_staged)?;
+
+        let name = c.display_name();
+        if let Ok(Some(status)) = crate::fs::scanner::component_status(c) {
+            self.components[idx].status = status;
+        }
+        self.status_message = Some(format!(
+            "Applied {} of {} hunk(s) to {}",
+            staged,
+            self.hunk_staged.len(),
+            name
+        ));
+        // Show what is left to apply; nothing left closes the diff.
+        self.show_diff()?;
+        if self.diff_hunks.is_none() {
+            self.close_diff();
+        }
+        Ok(())
+    }
+}
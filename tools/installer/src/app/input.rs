@@ -1,5 +1,6 @@
 use anyhow::Result;
 
+use super::trust::TrustPending;
 use super::types::View;
 use super::App;
 use crate::mcp::McpScope;
@@ -40,6 +41,7 @@ impl App {
         self.env_input_values.clear();
         self.env_input_buffer.clear();
         self.processing_queue.clear();
+        self.install_notes.clear();
         self.current_view = View::List;
     }
 
@@ -66,24 +68,46 @@ impl App {
 
     pub fn project_path_char(&mut self, c: char) {
         self.project_path_buffer.push(c);
+        self.project_path_error = None;
     }
 
     pub fn project_path_backspace(&mut self) {
         self.project_path_buffer.pop();
+        self.project_path_error = None;
     }
 
+    /// Accept the typed path once it resolves to an existing, writable
+    /// directory; otherwise stay in the dialog and show why.
     pub fn project_path_submit(&mut self) {
         if !self.project_path_buffer.is_empty() {
-            self.mcp_project_path = self.project_path_buffer.clone();
-            self.status_message = Some(format!("MCP scope: local ({})", self.mcp_project_path));
+            match crate::source::config::resolve_project_path(&self.project_path_buffer) {
+                Ok(resolved) => {
+                    self.mcp_project_path = resolved.to_string_lossy().to_string();
+                    self.status_message = Some(format!("MCP scope: local ({})", self.mcp_project_path));
+                }
+                Err(e) => {
+                    self.project_path_error = Some(e.to_string());
+                    return;
+                }
+            }
         }
+        self.project_path_error = None;
         self.current_view = View::List;
+        self.require_trusted_project(TrustPending::ScopeChange);
     }
 
     pub fn project_path_cancel(&mut self) {
         // Revert to user scope if cancelled
         self.mcp_scope = McpScope::User;
+        self.project_path_error = None;
         self.status_message = Some("MCP scope: user".to_string());
         self.current_view = View::List;
     }
+
+    /// The project whose config file holds local-scope MCP servers, once
+    /// one is known.
+    pub(crate) fn mcp_project(&self) -> Option<std::path::PathBuf> {
+        (!self.mcp_project_path.is_empty())
+            .then(|| crate::source::config::expand_tilde(std::path::Path::new(&self.mcp_project_path)))
+    }
 }
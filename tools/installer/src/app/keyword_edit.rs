@@ -0,0 +1,141 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::App;
+use crate::fs;
+
+impl App {
+    /// Open the keyword list of the highlighted agent or skill (`e` on
+    /// those tabs), read from its source frontmatter.
+    pub fn open_keyword_edit(&mut self) -> Result<()> {
+        if !matches!(self.tab, Tab::Agents | Tab::Skills) {
+            return Ok(());
+        }
+        let Some(idx) = self.selected_component_index() else { return Ok(()) };
+        let Some(component) = self.components.get(idx) else { return Ok(()) };
+
+        self.keyword_rows = fs::keywords::read_keywords(&component.source_path)?;
+        self.keyword_edit_idx = Some(idx);
+        self.keyword_cursor = 0;
+        self.keyword_editing = false;
+        self.keyword_buffer.clear();
+        self.keywords_dirty = false;
+        self.current_view = View::KeywordEdit;
+        Ok(())
+    }
+
+    pub fn handle_keyword_edit_key(&mut self, key: KeyCode) -> Result<()> {
+        if self.keyword_editing {
+            match key {
+                KeyCode::Esc => {
+                    // A row added with `a` and never filled in goes away.
+                    if self.keyword_rows.get(self.keyword_cursor).is_some_and(|k| k.is_empty()) {
+                        self.remove_keyword();
+                    }
+                    self.keyword_editing = false;
+                    self.keyword_buffer.clear();
+                }
+                KeyCode::Enter => self.commit_keyword(),
+                KeyCode::Backspace => { self.keyword_buffer.pop(); }
+                KeyCode::Char(c) => self.keyword_buffer.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.close_keyword_edit(),
+            KeyCode::Down | KeyCode::Char('j') if self.keyword_cursor + 1 < self.keyword_rows.len() => {
+                self.keyword_cursor += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.keyword_cursor = self.keyword_cursor.saturating_sub(1),
+            KeyCode::Enter if !self.keyword_rows.is_empty() => {
+                self.keyword_buffer = self.keyword_rows[self.keyword_cursor].clone();
+                self.keyword_editing = true;
+            }
+            KeyCode::Char('a') => {
+                let at = if self.keyword_rows.is_empty() { 0 } else { self.keyword_cursor + 1 };
+                self.keyword_rows.insert(at, String::new());
+                self.keyword_cursor = at;
+                self.keyword_buffer.clear();
+                self.keyword_editing = true;
+            }
+            KeyCode::Char('d') => {
+                self.remove_keyword();
+                self.keywords_dirty = true;
+            }
+            KeyCode::Char('w') => self.save_keywords(false)?,
+            KeyCode::Char('i') => self.save_keywords(true)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Store the typed value in the edited row. Blank values and
+    /// duplicates of another row drop the row instead.
+    fn commit_keyword(&mut self) {
+        let value = self.keyword_buffer.trim().to_string();
+        let duplicate = self.keyword_rows.iter()
+            .enumerate()
+            .any(|(i, k)| i != self.keyword_cursor && *k == value);
+        if value.is_empty() || duplicate {
+            self.remove_keyword();
+        } else if let Some(row) = self.keyword_rows.get_mut(self.keyword_cursor) {
+            *row = value;
+        }
+        self.keywords_dirty = true;
+        self.keyword_editing = false;
+        self.keyword_buffer.clear();
+    }
+
+    fn remove_keyword(&mut self) {
+        if self.keyword_cursor < self.keyword_rows.len() {
+            self.keyword_rows.remove(self.keyword_cursor);
+        }
+        self.keyword_cursor = self.keyword_cursor.min(self.keyword_rows.len().saturating_sub(1));
+    }
+
+    /// Write the list back to the source file, then optionally install the
+    /// updated component right away.
+    fn save_keywords(&mut self, install: bool) -> Result<()> {
+        let Some(idx) = self.keyword_edit_idx else { return Ok(()) };
+        let Some(component) = self.components.get(idx) else { return Ok(()) };
+        let name = component.display_name();
+        if let Some(blocker) = self.source_write_blocker(component, "edited") {
+            self.status_message = Some(blocker);
+            return Ok(());
+        }
+
+        fs::keywords::write_keywords(&component.source_path, &self.keyword_rows)?;
+        if let Ok(Some(status)) = fs::scanner::component_status(component) {
+            self.components[idx].status = status;
+        }
+        self.keywords_dirty = false;
+        self.status_message = Some(format!("Saved {} keyword(s) for {}", self.keyword_rows.len(), name));
+
+        if !install {
+            return Ok(());
+        }
+        self.close_keyword_edit();
+        if !self.components[idx].is_install_eligible() {
+            self.status_message = Some(format!("Saved keywords for {}; it can't be installed from here", name));
+            return Ok(());
+        }
+        self.processing_queue = vec![idx];
+        self.is_removing = false;
+        self.complete_install_setup()
+    }
+
+    pub fn close_keyword_edit(&mut self) {
+        if self.keywords_dirty {
+            self.status_message = Some("Keyword changes discarded".to_string());
+        }
+        self.keyword_edit_idx = None;
+        self.keyword_rows.clear();
+        self.keyword_editing = false;
+        self.keyword_buffer.clear();
+        self.keywords_dirty = false;
+        self.current_view = View::List;
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::mcp::McpHealth;
+
+use super::types::{TargetCli, Tab, View};
+use super::App;
+
+/// `(server name, health)` pairs from one `claude mcp list`, or the error.
+pub type McpHealthPayload = Result<Vec<(String, McpHealth)>, String>;
+
+impl App {
+    /// Whether background health polling applies right now: enabled in
+    /// sources.yaml, Claude Code selected (Codex reports no health), and
+    /// the MCP list on screen.
+    pub fn mcp_health_polling(&self) -> bool {
+        self.mcp_health_interval.is_some()
+            && self.target_cli == Some(TargetCli::Claude)
+            && self.tab == Tab::McpServers
+            && self.current_view == View::List
+    }
+
+    /// Apply a finished health check and start the next one when due.
+    /// Called every loop tick; a check already in flight is never doubled.
+    pub fn tick_mcp_health(&mut self) {
+        if let Some(rx) = &self.mcp_health_rx {
+            match rx.try_recv() {
+                Ok(Ok(health)) => {
+                    self.mcp_health = health.into_iter().collect();
+                    self.mcp_health_rx = None;
+                }
+                Ok(Err(e)) => {
+                    self.status_message = Some(format!("MCP health check failed: {}", e));
+                    self.mcp_health_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => self.mcp_health_rx = None,
+            }
+        }
+
+        let Some(interval) = self.mcp_health_interval else { return };
+        let due = self.mcp_health_polled_at
+            .is_none_or(|at| at.elapsed() >= Duration::from_secs(interval));
+        if !self.mcp_health_polling() || !due {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = crate::fs::scanner::claude_server_health().map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.mcp_health_rx = Some(rx);
+        self.mcp_health_polled_at = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn mcp_health_polls_only_on_claude_mcp_list() {
+        let mut app = App::for_test();
+        app.tab = Tab::McpServers;
+        app.current_view = crate::app::View::List;
+        app.target_cli = Some(crate::app::TargetCli::Claude);
+        assert!(!app.mcp_health_polling(), "off unless configured");
+
+        app.mcp_health_interval = Some(30);
+        assert!(app.mcp_health_polling());
+        app.target_cli = Some(crate::app::TargetCli::Codex);
+        assert!(!app.mcp_health_polling());
+        app.target_cli = Some(crate::app::TargetCli::Claude);
+        app.tab = Tab::Agents;
+        assert!(!app.mcp_health_polling());
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::View;
+use super::App;
+
+/// Where an MCP env var's value comes from in the review table.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnvValueStatus {
+    /// Already exported in the installer's environment.
+    FromEnv,
+    /// Read from the server's `env_file`, shown as written.
+    FromFile { value: String, file: String },
+    /// Typed into the review table.
+    Entered(String),
+    Missing,
+}
+
+/// One line of the batch review table: a server, and one of its env vars
+/// (`None` for servers that declare none).
+#[derive(Clone, Debug)]
+pub struct McpReviewRow {
+    pub server_idx: usize,
+    pub var: Option<String>,
+    pub status: EnvValueStatus,
+}
+
+impl App {
+    /// Build the review table for the queued servers and switch to it.
+    pub(super) fn start_mcp_review(&mut self) {
+        let mut rows = Vec::new();
+        for &idx in &self.processing_queue {
+            let Some(server) = self.mcp_servers.get(idx) else { continue };
+            if server.def.env.is_empty() {
+                rows.push(McpReviewRow { server_idx: idx, var: None, status: EnvValueStatus::FromEnv });
+            }
+            let from_file = self.mcp_env_values.get(&idx);
+            let file = server.def.env_file.clone().unwrap_or_default();
+            for var in &server.def.env {
+                let value = from_file.and_then(|values| values.iter().find(|(v, _)| v == var));
+                let status = if std::env::var(var).is_ok() {
+                    EnvValueStatus::FromEnv
+                } else if let Some((_, value)) = value {
+                    EnvValueStatus::FromFile { value: value.clone(), file: file.clone() }
+                } else {
+                    EnvValueStatus::Missing
+                };
+                rows.push(McpReviewRow { server_idx: idx, var: Some(var.clone()), status });
+            }
+        }
+
+        self.mcp_review_rows = rows;
+        self.mcp_review_cursor = 0;
+        self.mcp_review_editing = false;
+        self.mcp_review_buffer.clear();
+        self.current_view = View::McpReview;
+    }
+
+    pub fn mcp_review_missing(&self) -> usize {
+        self.mcp_review_rows.iter()
+            .filter(|r| r.status == EnvValueStatus::Missing)
+            .count()
+    }
+
+    pub fn handle_mcp_review_key(&mut self, key: KeyCode) -> Result<()> {
+        if self.mcp_review_editing {
+            match key {
+                KeyCode::Esc => {
+                    self.mcp_review_editing = false;
+                    self.mcp_review_buffer.clear();
+                }
+                KeyCode::Enter if !self.mcp_review_buffer.is_empty() => {
+                    let value = std::mem::take(&mut self.mcp_review_buffer);
+                    if let Some(row) = self.mcp_review_rows.get_mut(self.mcp_review_cursor) {
+                        row.status = EnvValueStatus::Entered(value);
+                    }
+                    self.mcp_review_editing = false;
+                }
+                KeyCode::Backspace => { self.mcp_review_buffer.pop(); }
+                KeyCode::Char(c) => self.mcp_review_buffer.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.cancel_mcp_review(),
+            KeyCode::Down | KeyCode::Char('j') if self.mcp_review_cursor + 1 < self.mcp_review_rows.len() => {
+                self.mcp_review_cursor += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.mcp_review_cursor = self.mcp_review_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some(row) = self.mcp_review_rows.get(self.mcp_review_cursor)
+                    && row.var.is_some()
+                {
+                    self.mcp_review_buffer = match &row.status {
+                        EnvValueStatus::Entered(value) => value.clone(),
+                        _ => String::new(),
+                    };
+                    self.mcp_review_editing = true;
+                }
+            }
+            KeyCode::Char('s') => self.start_reviewed_mcp_install()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Start the batch. Refused while any required value is missing.
+    fn start_reviewed_mcp_install(&mut self) -> Result<()> {
+        let missing = self.mcp_review_missing();
+        if missing > 0 {
+            self.status_message = Some(format!("{} env value(s) still missing", missing));
+            return Ok(());
+        }
+
+        let mut values: HashMap<usize, Vec<(String, String)>> = HashMap::new();
+        for row in self.mcp_review_rows.drain(..) {
+            if let (Some(var), EnvValueStatus::Entered(value) | EnvValueStatus::FromFile { value, .. }) = (row.var, row.status) {
+                values.entry(row.server_idx).or_default().push((var, value));
+            }
+        }
+        self.mcp_env_values = values;
+        self.continue_mcp_install()
+    }
+
+    fn cancel_mcp_review(&mut self) {
+        self.mcp_review_rows.clear();
+        self.mcp_review_editing = false;
+        self.mcp_review_buffer.clear();
+        self.processing_queue.clear();
+        self.install_notes.clear();
+        self.current_view = View::List;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn mcp_review_blocks_start_until_missing_values_entered() {
+        use crate::app::View;
+        use crate::mcp::{McpServer, McpServerDef, McpStatus};
+        use crossterm::event::KeyCode;
+
+        let def = |name: &str, env: Vec<String>| McpServerDef {
+            name: name.into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env, version: None, env_file: None,
+        };
+        let mut app = App::for_test();
+        app.tab = Tab::McpServers;
+        app.mcp_servers = vec![
+            McpServer::new(def("plain", Vec::new()), McpStatus::NotInstalled),
+            McpServer::new(def("keyed", vec!["HIBI_TEST_UNSET_TOKEN".into()]), McpStatus::NotInstalled),
+        ];
+        app.processing_queue = vec![0, 1];
+        app.complete_install_setup().unwrap();
+        assert!(app.current_view == View::McpReview);
+        assert_eq!(app.mcp_review_missing(), 1);
+
+        app.handle_mcp_review_key(KeyCode::Char('s')).unwrap();
+        assert!(app.current_view == View::McpReview);
+
+        app.handle_mcp_review_key(KeyCode::Down).unwrap();
+        app.handle_mcp_review_key(KeyCode::Enter).unwrap();
+        app.handle_mcp_review_key(KeyCode::Char('x')).unwrap();
+        app.handle_mcp_review_key(KeyCode::Enter).unwrap();
+        app.handle_mcp_review_key(KeyCode::Char('s')).unwrap();
+        assert!(app.current_view == View::Installing);
+        assert_eq!(app.mcp_env_values.get(&1), Some(&vec![("HIBI_TEST_UNSET_TOKEN".to_string(), "x".to_string())]));
+    }
+}
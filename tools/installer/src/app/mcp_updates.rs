@@ -0,0 +1,63 @@
+use std::sync::mpsc;
+
+use super::types::Tab;
+use super::App;
+
+/// `(server name, newer version)` pairs from a finished update check.
+pub type McpUpdatePayload = Vec<(String, String)>;
+
+impl App {
+    /// Opt-in registry check for MCP servers with a pinned `version:`.
+    /// Runs `npm view` per package on a background thread; results land in
+    /// `mcp_updates` via `poll_mcp_updates`.
+    pub fn start_mcp_update_check(&mut self) {
+        if self.tab != Tab::McpServers || self.mcp_update_rx.is_some() {
+            return;
+        }
+
+        let pinned: Vec<(String, String, String)> = self.mcp_servers.iter()
+            .filter_map(|m| {
+                let version = m.def.version.clone()?;
+                let package = m.def.npm_package()?;
+                Some((m.def.name.clone(), package, version))
+            })
+            .collect();
+
+        if pinned.is_empty() {
+            self.status_message = Some("No MCP servers pin a version".to_string());
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let updates: McpUpdatePayload = pinned.into_iter()
+                .filter_map(|(name, package, pinned)| {
+                    let latest = crate::fs::scanner::latest_npm_version(&package).ok()?;
+                    (latest != pinned).then_some((name, latest))
+                })
+                .collect();
+            let _ = tx.send(updates);
+        });
+
+        self.mcp_update_rx = Some(rx);
+        self.status_message = Some("Checking npm registry for MCP updates...".to_string());
+    }
+
+    /// Apply a finished update check, if any. Called every loop tick.
+    pub fn poll_mcp_updates(&mut self) {
+        let Some(rx) = &self.mcp_update_rx else { return };
+        match rx.try_recv() {
+            Ok(updates) => {
+                self.status_message = Some(if updates.is_empty() {
+                    "Pinned MCP servers are up to date".to_string()
+                } else {
+                    format!("{} MCP update(s) available", updates.len())
+                });
+                self.mcp_updates = updates.into_iter().collect();
+                self.mcp_update_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.mcp_update_rx = None,
+        }
+    }
+}
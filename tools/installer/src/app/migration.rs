@@ -0,0 +1,112 @@
+//! After Codex is picked, offer to install for it the part of the Claude
+//! setup it can use (see `fs::migration`): one batch for the skills, then
+//! one for the MCP servers once the first is closed. `P` on the Codex tabs
+//! shows the plan again.
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{FocusArea, Tab, View};
+use super::App;
+use crate::component::{ComponentType, InstallStatus};
+use crate::mcp::McpStatus;
+
+impl App {
+    /// Skills and MCP servers installed for Claude but not yet for Codex,
+    /// as indices into `components` and `mcp_servers`.
+    pub fn migration_todo(&self) -> (Vec<usize>, Vec<usize>) {
+        let Some(plan) = &self.migration_plan else { return (Vec::new(), Vec::new()) };
+        let skills = self.components.iter()
+            .enumerate()
+            .filter(|(_, c)| c.component_type == ComponentType::Skills
+                && c.status == InstallStatus::New
+                && c.for_this_host()
+                && plan.skills.contains(&c.name))
+            .map(|(i, _)| i)
+            .collect();
+        let mcp = self.mcp_servers.iter()
+            .enumerate()
+            .filter(|(_, m)| m.status == McpStatus::NotInstalled && plan.mcp_servers.contains(&m.def.name))
+            .map(|(i, _)| i)
+            .collect();
+        (skills, mcp)
+    }
+
+    /// Show the plan after loading when something is left to port and the
+    /// offer wasn't turned down for good.
+    pub(super) fn offer_migration(&mut self) {
+        if self.migration_declined || self.current_view != View::List {
+            return;
+        }
+        let (skills, mcp) = self.migration_todo();
+        if !skills.is_empty() || !mcp.is_empty() {
+            self.current_view = View::Migration;
+        }
+    }
+
+    /// `P`: show the plan, even when everything is already ported.
+    pub fn open_migration(&mut self) {
+        match &self.migration_plan {
+            Some(plan) if !plan.is_empty() => self.current_view = View::Migration,
+            _ => self.status_message = Some("Nothing installed for Claude Code to port".to_string()),
+        }
+    }
+
+    /// `Enter` installs the portable items, `x` stops offering, `Esc`
+    /// closes for now.
+    pub fn handle_migration_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Enter => return self.start_migration(),
+            KeyCode::Char('x') => {
+                self.migration_declined = true;
+                self.status_message = Some("Won't offer again; [P] on the Skills or MCP tab shows the plan".to_string());
+                self.current_view = View::List;
+            }
+            KeyCode::Esc => self.current_view = View::List,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn start_migration(&mut self) -> Result<()> {
+        self.current_view = View::List;
+        let (skills, mcp) = self.migration_todo();
+        if skills.is_empty() && mcp.is_empty() {
+            self.status_message = Some("Everything portable is already installed for Codex".to_string());
+            return Ok(());
+        }
+        self.migration_followup = mcp;
+        if skills.is_empty() {
+            return self.continue_migration();
+        }
+        if !self.migration_followup.is_empty() {
+            self.install_notes.push(format!(
+                "[QUEUED] {} MCP server(s) from Claude Code install when this batch is closed",
+                self.migration_followup.len(),
+            ));
+        }
+        self.show_tab(Tab::Skills);
+        self.run_deferred_refresh(true);
+        self.queue_offline = false;
+        self.start_install(skills)
+    }
+
+    /// Install the MCP servers `start_migration` left for after the skills
+    /// batch. Called when a batch is closed.
+    pub(crate) fn continue_migration(&mut self) -> Result<()> {
+        if self.migration_followup.is_empty() || self.current_view != View::List {
+            return Ok(());
+        }
+        let mcp = std::mem::take(&mut self.migration_followup);
+        self.show_tab(Tab::McpServers);
+        self.run_deferred_refresh(true);
+        self.queue_offline = false;
+        self.start_install(mcp)
+    }
+
+    pub(super) fn show_tab(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.list_index = 0;
+        self.focus = FocusArea::Content;
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::App;
+use crate::component::InstallStatus;
+
+/// Pending work for one component tab in the mirror dialog.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MirrorCounts {
+    pub new: usize,
+    pub modified: usize,
+    pub orphaned: usize,
+}
+
+impl MirrorCounts {
+    pub fn is_empty(&self) -> bool {
+        self.new + self.modified + self.orphaned == 0
+    }
+}
+
+impl App {
+    /// What mirroring `tab` would install and delete.
+    pub fn mirror_counts(&self, tab: Tab) -> MirrorCounts {
+        let Some(comp_type) = self.tab_component_type(tab) else { return MirrorCounts::default() };
+        let mut counts = MirrorCounts::default();
+        for c in self.components.iter().filter(|c| c.component_type == comp_type) {
+            match c.status {
+                InstallStatus::New if c.is_install_eligible() => counts.new += 1,
+                InstallStatus::Modified if c.is_install_eligible() => counts.modified += 1,
+                InstallStatus::Orphaned => counts.orphaned += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+
+    /// `m` in the list: pick the component tabs to make match the source.
+    /// The current tab starts checked.
+    pub fn open_mirror(&mut self) {
+        if self.tab_component_type(self.tab).is_none() {
+            self.status_message = Some("Mirror works on component tabs".to_string());
+            return;
+        }
+        self.mirror_tabs = self.available_tabs.iter()
+            .filter(|&&tab| self.tab_component_type(tab).is_some())
+            .map(|&tab| (tab, tab == self.tab))
+            .collect();
+        self.mirror_index = self.mirror_tabs.iter().position(|(_, checked)| *checked).unwrap_or(0);
+        self.current_view = View::MirrorConfirm;
+    }
+
+    pub fn handle_mirror_key(&mut self, key: KeyCode) -> Result<()> {
+        let len = self.mirror_tabs.len();
+        match key {
+            KeyCode::Down | KeyCode::Char('j') if len > 0 => self.mirror_index = (self.mirror_index + 1) % len,
+            KeyCode::Up | KeyCode::Char('k') if len > 0 => self.mirror_index = (self.mirror_index + len - 1) % len,
+            KeyCode::Char(' ') => {
+                if let Some((_, checked)) = self.mirror_tabs.get_mut(self.mirror_index) {
+                    *checked = !*checked;
+                }
+            }
+            KeyCode::Char('a') => {
+                let all = self.mirror_tabs.iter().all(|(_, checked)| *checked);
+                self.mirror_tabs.iter_mut().for_each(|(_, checked)| *checked = !all);
+            }
+            KeyCode::Enter => self.run_mirror()?,
+            KeyCode::Esc | KeyCode::Char('q') => self.current_view = View::List,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Make the checked tabs match the source: delete their orphans (backed
+    /// up first), then install every new or modified item in one batch.
+    fn run_mirror(&mut self) -> Result<()> {
+        let types: Vec<_> = self.mirror_tabs.iter()
+            .filter(|(_, checked)| *checked)
+            .filter_map(|&(tab, _)| self.tab_component_type(tab))
+            .collect();
+        self.current_view = View::List;
+        if types.is_empty() {
+            self.status_message = Some("No tabs checked".to_string());
+            return Ok(());
+        }
+        // Statuses from an earlier batch must be current before the
+        // orphans and pending items are picked.
+        self.run_deferred_refresh(true);
+
+        let orphans: Vec<usize> = self.components.iter()
+            .enumerate()
+            .filter(|(_, c)| c.status == InstallStatus::Orphaned && types.contains(&c.component_type))
+            .map(|(i, _)| i)
+            .collect();
+        let pruned = (!orphans.is_empty()).then(|| self.prune_components(&orphans, true));
+
+        // Indices are taken after pruning, which shortens the list.
+        let pending: Vec<usize> = self.components.iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                matches!(c.status, InstallStatus::New | InstallStatus::Modified)
+                    && types.contains(&c.component_type)
+                    && c.is_install_eligible()
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if pending.is_empty() {
+            self.status_message = Some(pruned.unwrap_or_else(|| "Already in sync with the source".to_string()));
+            return Ok(());
+        }
+
+        // Logged once the install starts, which may wait on dialogs.
+        if let Some(pruned) = &pruned {
+            self.install_notes.push(format!("[OK] {}", pruned));
+        }
+        self.start_install(pending)?;
+        if self.current_view == View::List {
+            self.install_notes.clear();
+            if let Some(pruned) = pruned {
+                self.status_message = Some(pruned);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn mirror_prunes_orphans_then_queues_pending_items_of_the_checked_tabs() {
+        use crate::app::View;
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crossterm::event::KeyCode;
+
+        let dir = temp_dir("mirror");
+        let orphan = dir.join("old.md");
+        std::fs::write(&orphan, "x").unwrap();
+        let component = |comp_type, name: &str, status| {
+            Component::new(comp_type, name.into(), dir.join(name), dir.join(name), status)
+        };
+
+        let mut app = App::for_test();
+        app.dest_dir = dir.clone();
+        app.tab = Tab::Agents;
+        app.available_tabs = vec![Tab::Agents, Tab::Rules, Tab::McpServers];
+        app.components = vec![
+            component(ComponentType::Rules, "old.md", InstallStatus::Orphaned),
+            component(ComponentType::Agents, "a.md", InstallStatus::New),
+            component(ComponentType::Agents, "b.md", InstallStatus::Unchanged),
+            component(ComponentType::Rules, "r.md", InstallStatus::Modified),
+        ];
+
+        app.open_mirror();
+        assert_eq!(app.mirror_tabs, [(Tab::Agents, true), (Tab::Rules, false)]);
+        assert_eq!(app.mirror_counts(Tab::Rules).orphaned, 1);
+        app.handle_mirror_key(KeyCode::Down).unwrap();
+        app.handle_mirror_key(KeyCode::Char(' ')).unwrap();
+        app.handle_mirror_key(KeyCode::Enter).unwrap();
+        assert!(app.current_view == View::ConflictResolve);
+        app.handle_conflict_key(KeyCode::Char('o')).unwrap();
+        let orphan_gone = !orphan.exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(orphan_gone);
+        assert!(app.current_view == View::Installing);
+        let queued: Vec<&str> = app.processing_queue.iter().map(|&i| app.components[i].name.as_str()).collect();
+        assert_eq!(queued, ["a.md", "r.md"]);
+        assert!(app.processing_log.iter().any(|l| l.starts_with("[OK] Pruned 1 orphaned file(s)")));
+    }
+}
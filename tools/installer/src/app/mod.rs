@@ -6,17 +6,62 @@ mod input;
 mod settings;
 pub mod sources;
 mod source_wizard;
+mod hook_detail;
+mod hook_logs;
+mod hook_scaffold;
+mod keyword_edit;
+pub mod search;
+mod mcp_updates;
+mod cli_probe;
+mod mcp_health;
+mod undo;
+mod sort;
+mod profiles;
+mod report;
+mod settings_history;
+mod settings_keys;
+pub mod mcp_review;
+mod ui_state;
+mod context_budget;
+mod hunks;
+mod preview;
+mod trust;
+mod summary;
+mod adopt;
+mod git_status;
+mod prune;
+mod mirror;
+mod conflicts;
+mod pending;
+mod hosts;
+mod actions;
+mod hints;
+mod dangling;
+mod palette;
+mod failures;
+mod migration;
+mod resume;
 
 pub use types::{TargetCli, Tab, View, SyncStatus, FocusArea};
+pub use processing::{BatchStage, DeferredRefresh};
+pub use trust::TrustPending;
+pub use summary::SessionSummary;
+pub use sort::{sort_options, ListSort};
+pub use context_budget::format_tokens;
+pub use hints::HintProgress;
+pub use failures::ItemFailure;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 use anyhow::Result;
 
 use crate::component::{Component, ComponentType};
+use crate::fs::scanner::ScanOptions;
 use crate::mcp::{McpServer, McpScope};
 use crate::plugin::Plugin;
 use crate::source::{ResolvedSource, SourceEntry, SourceKind};
+use crate::source::config::RefreshAfterBatch;
+use crate::source::config::{BatchHooks, DirectoryMap, NotifyConfig};
 use crate::tree::TreeView;
 use crate::theme::Theme;
 
@@ -24,31 +69,65 @@ pub struct App {
     pub target_cli: Option<TargetCli>,
     pub available_tabs: Vec<Tab>,
     pub tab: Tab,
+    /// Tab requested with `--tab`, applied once a CLI is selected.
+    pub startup_tab: Option<Tab>,
+    /// CLI used last time, selected at startup instead of asking again;
+    /// cleared by `--choose-cli`.
+    pub startup_cli: Option<TargetCli>,
     pub current_view: View,
     pub focus: FocusArea,
     /// Cursor row on the initial CLI-selection screen.
-    /// 0 = Claude Code, 1 = Codex CLI, 2 = Manage Sources.
+    /// Indexes `TargetCli::all()`, then Manage Sources. Starts on the CLI
+    /// used last time.
     pub cli_selection_index: usize,
     pub should_quit: bool,
     pub theme: Theme,
+    /// Set once `t` switches themes; only a theme picked that way is
+    /// remembered for the next run.
+    pub theme_changed: bool,
 
     pub components: Vec<Component>,
     pub list_index: usize, // Index within current tab's filtered list (legacy, for MCP/Plugins)
     pub tree_views: HashMap<Tab, TreeView>, // Tree views for component tabs
+    pub token_budget: u64, // Estimated tokens the always-loaded files may use
 
     pub mcp_servers: Vec<McpServer>,
     pub mcp_index: usize,
     pub mcp_scope: McpScope,
     pub mcp_project_path: String, // Project path for local scope
+    pub trusted_projects: Vec<PathBuf>, // Projects local scope may write into (sources.yaml)
+    pub trust_pending: Option<TrustPending>, // Action waiting on the trust prompt
+    pub session_summary: SessionSummary,     // What the session changed, printed on exit
+    /// Server name -> newer npm version than its pinned `version:`.
+    pub mcp_updates: HashMap<String, String>,
+    pub mcp_update_rx: Option<std::sync::mpsc::Receiver<mcp_updates::McpUpdatePayload>>,
+    pub mcp_health: HashMap<String, crate::mcp::McpHealth>,
+    pub mcp_health_rx: Option<std::sync::mpsc::Receiver<mcp_health::McpHealthPayload>>,
+    pub mcp_health_interval: Option<u64>, // Seconds between health checks; None = off
+    pub mcp_health_polled_at: Option<std::time::Instant>,
+    pub cli_infos: Vec<(TargetCli, crate::fs::CliInfo)>,
+    pub cli_probe_rx: Option<std::sync::mpsc::Receiver<cli_probe::CliProbePayload>>,
 
     pub plugins: Vec<Plugin>,
     pub plugin_index: usize,
 
     pub diff_content: Option<String>,
+    pub diff_summary: Vec<String>, // Frontmatter changes shown above an agent's or skill's diff
     pub diff_scroll: u16,
+    pub diff_hunks: Option<crate::fs::hunks::HunkDiff>, // Hunks of the diffed file, when it can be applied per hunk
+    pub hunk_staged: Vec<bool>, // Hunks picked for `a` in the diff view
+    pub hunk_index: usize,      // Current hunk in the diff view
+    /// Highlighted component's source file and its first lines, for the
+    /// List view's preview pane.
+    pub preview: Option<(PathBuf, Vec<String>)>,
 
     pub source_dir: PathBuf,
     pub sources: Vec<ResolvedSource>,
+    /// Local sources with uncommitted git changes, from the last full scan.
+    pub source_changes: Vec<crate::source::SourceChanges>,
+    /// `committed_only:` — leave uncommitted files out of installs.
+    pub committed_only: bool,
+    pub git_status_scroll: usize,
     pub dest_dir: PathBuf,
 
     pub status_message: Option<String>,
@@ -56,18 +135,42 @@ pub struct App {
     // Current defaults from settings.json
     pub current_output_style: Option<String>,
     pub current_statusline: Option<String>,
+    // Of those, the ones whose file is missing (`F` fixes them)
+    pub dangling_settings: Vec<crate::fs::installer::DanglingSetting>,
+    /// Registered hook command -> (1-based position, event array length),
+    /// read from settings.json so the Hooks tab can show execution order.
+    pub hook_positions: HashMap<String, (usize, usize)>,
 
     // Processing state (install/remove)
     pub processing_progress: Option<usize>,
     pub processing_total: Option<usize>,
     pub processing_log: Vec<String>,
+    pub backup_snapshot: Option<crate::fs::backup::Snapshot>, // Where this batch saves overwritten files
+    pub journal_baseline: Option<crate::fs::journal::Baseline>, // Pre-batch state for the undo journal
+    pub undo_armed: bool,             // First Ctrl+Z pressed; the next one runs the undo
+    pub adopt_armed: bool,            // First A pressed in the diff; the next A adopts
     pub processing_queue: Vec<usize>, // Indices of items to process
+    pub batch_hooks: BatchHooks,      // Commands from sources.yaml run around each batch
+    pub strict_mode: bool,            // Abort and roll back a batch on its first failed item
+    pub show_all_hosts: bool,         // List components other machines' `hosts:` filters claim
+    pub notify: NotifyConfig,         // Desktop notification / webhook when a batch finishes
+    pub notify_rx: Option<std::sync::mpsc::Receiver<Vec<String>>>, // Errors from the last notification
+    pub batch_aborted: bool,          // Strict mode stopped this batch after a failure
+    pub batch_hook_queue: Vec<(BatchStage, String)>, // Batch hooks still to run, ahead of the item queue
+    pub processed_items: Vec<(usize, bool)>, // Finished items and whether they succeeded
     pub is_removing: bool,            // true = removing, false = installing
-    pub animation_frame: usize,       // For spinner animation
     pub needs_refresh: bool,          // True after processing, before refresh
+    pub refresh_after_batch: RefreshAfterBatch, // When statuses refresh after a batch (sources.yaml)
+    pub scan_options: ScanOptions,    // Thread cap for scans (`scan_threads:` in sources.yaml)
+    pub deferred_refresh: Option<DeferredRefresh>, // Refresh waiting out refresh_delay_secs
     pub refreshing: bool,             // True while refresh thread is running
     pub processing_complete: bool,    // True when everything is done (including refresh)
     pub cancelling: bool,             // True when cancel signal sent, waiting for process to stop
+    pub skipping_item: bool,          // The pending cancel is a skip (`s`): log `[SKIP]`, not a failure
+    pub show_commands: bool,          // Show the `[CMD]` lines of the processing log (`c`)
+    pub item_failures: Vec<ItemFailure>, // Output of the items that failed this batch
+    pub failure_detail: Option<usize>, // Index into item_failures shown in the popup (`e`)
+    pub failure_scroll: u16,          // First line shown in the failure popup
 
     // Env input state (for MCP servers requiring env vars)
     pub env_input_server_idx: Option<usize>,   // Index of MCP server being configured
@@ -76,12 +179,112 @@ pub struct App {
     pub env_input_buffer: String,              // Current input text
     pub env_input_values: Vec<(String, String)>, // Collected (name, value) pairs
 
+    // MCP batch review state (multi-server installs)
+    pub mcp_review_rows: Vec<mcp_review::McpReviewRow>,
+    pub mcp_review_cursor: usize,              // Selected row
+    pub mcp_review_editing: bool,              // true while typing a value
+    pub mcp_review_buffer: String,             // Value being typed
+    pub mcp_env_values: HashMap<usize, Vec<(String, String)>>, // Server index -> entered env
+
+    // Hook detail state (Hooks tab `e`)
+    pub hook_detail_idx: Option<usize>,        // Component index of the hook being shown
+    pub hook_env_rows: Vec<(String, String)>,  // Env (name, value) pairs, sorted by name
+    pub hook_env_cursor: usize,                // Selected env row
+    pub hook_env_editing: bool,                // true while editing the selected value
+    pub hook_env_buffer: String,               // Value being typed
+
+    // Keyword editor (`e` on the Agents/Skills tabs)
+    pub keyword_edit_idx: Option<usize>,       // Index in components being edited
+    pub keyword_rows: Vec<String>,             // Keywords in file order
+    pub keyword_cursor: usize,                 // Selected keyword
+    pub keyword_editing: bool,                 // true while typing into the selected row
+    pub keyword_buffer: String,                // Keyword being typed
+    pub keywords_dirty: bool,                  // Rows differ from what's on disk
+
+    // Hook log viewer state
+    pub hook_log_lines: Vec<crate::hook_log::LogLine>,
+    pub hook_log_filter: Option<String>,       // Only lines for this hook
+    pub hook_log_level: Option<crate::hook_log::LogLevel>, // Minimum level shown
+    pub hook_log_scroll: usize,                // Lines scrolled back from the newest
+    pub hook_log_stamp: Vec<(std::time::SystemTime, u64)>, // Log (mtime, len) at last read
+
+    // Marketplaces left without installed plugins after a removal
+    pub pending_marketplace_removals: Vec<String>,
+
+    // Pack files dropped upstream, awaiting confirmation before an install
+    pub stale_pack_files: Vec<PathBuf>,
+
+    // Prune dialog: every tab's orphans instead of the current tab's
+    pub prune_all_tabs: bool,
+
+    // Mirror dialog: component tabs and whether each is checked
+    pub mirror_tabs: Vec<(Tab, bool)>,
+    pub mirror_index: usize,
+
+    // Modified files of the pending install still awaiting keep/overwrite
+    pub conflicts: Vec<usize>,
+    // Lines added to the processing log once the pending install starts
+    pub install_notes: Vec<String>,
+
+    // Offline queue: whether this install is queued instead of run, the
+    // operations queued earlier, and a retry running in the background
+    pub queue_offline: bool,
+    pub pending_ops: Vec<crate::fs::pending::PendingOp>,
+    pub pending_flush_rx: Option<std::sync::mpsc::Receiver<pending::FlushPayload>>,
+
+    // Global search state (Ctrl+F)
+    pub search_query: String,
+    pub search_results: Vec<search::SearchHit>,
+    pub search_index: usize,
+
+    // Command palette (Ctrl+P)
+    pub palette_query: String,
+    pub palette_results: Vec<palette::PaletteEntry>,
+    pub palette_index: usize,
+
+    // List ordering (`S`)
+    pub sorts: HashMap<Tab, ListSort>,         // Tabs not in here keep source order
+    pub sort_menu_index: usize,                // Highlighted row in the sort menu
+    pub hook_scaffold_index: usize,            // Highlighted event when creating a hook.yaml
+    pub mcp_source_order: Vec<String>,         // MCP server names in scan order
+    pub plugin_source_order: Vec<String>,      // Plugin names in scan order
+
+    // Key help overlay (`?`)
+    pub help_open: bool,
+    // Onboarding tips dismissed or learned, and key uses towards them
+    pub hints: HintProgress,
+
+    // Claude -> Codex migration offer (`P` on Codex tabs)
+    pub migration_plan: Option<crate::fs::migration::MigrationPlan>, // Set by the load when Codex is picked
+    pub migration_declined: bool,     // "Don't ask again", remembered in ui.yaml
+    pub migration_followup: Vec<usize>, // MCP servers to install once the skills batch closes
+
+    // Install batch left unfinished by the last run (see `resume`)
+    pub interrupted_queue: Option<crate::fs::interrupted::InterruptedQueue>,
+    pub resumed_progress: Option<usize>, // Items the interrupted batch finished; the resumed one counts on from here
+
+    // settings.json history (`H` on the Config tab); the diff pane reuses diff_content
+    pub settings_versions: Vec<crate::fs::settings_history::SettingsVersion>,
+    pub settings_version_index: usize,
+    pub settings_restore_armed: bool,          // First r pressed; the next r restores
+
+    // Per-key owners of settings.json (`v` on the Config tab)
+    pub settings_keys: Vec<crate::fs::installer::SettingsKey>,
+    pub settings_key_index: usize,
+
+    // Install profiles from each source's profiles.yaml
+    pub profiles: Vec<crate::profile::Profile>,
+    pub profile_index: Option<usize>,          // Profile picked on the CLI selection screen
+    pub profile_pending: bool,                 // Apply the profile when the picked CLI's items load
+
     // Project path input state (for local scope MCP)
     pub project_path_buffer: String,           // Current project path input
+    pub project_path_error: Option<String>,    // Why the typed path was rejected
 
     // Sources management state
     pub source_entries: Vec<SourceEntry>,       // Raw config entries (from YAML)
     pub source_auto_update: bool,              // auto_update flag
+    pub directories: DirectoryMap,             // `directories:` folder remapping
     pub source_list_index: usize,              // Cursor in sources list (0 = bundled)
     pub source_add_kind: Option<SourceKind>,   // Git or Local (wizard selection)
     pub source_input_buffer: String,           // Text input buffer (URL/path/branch)
@@ -101,6 +304,18 @@ struct InitData {
     init_warnings: Option<String>,
     source_entries: Vec<SourceEntry>,
     source_auto_update: bool,
+    directories: DirectoryMap,
+    batch_hooks: BatchHooks,
+    mcp_health_interval: Option<u64>,
+    strict_mode: bool,
+    notify: NotifyConfig,
+    committed_only: bool,
+    token_budget: u64,
+    theme: Option<String>,
+    trusted_projects: Vec<PathBuf>,
+    refresh_after_batch: RefreshAfterBatch,
+    scan_options: ScanOptions,
+    profiles: Vec<crate::profile::Profile>,
     dest_dir: PathBuf,
     default_project: String,
 }
@@ -108,13 +323,17 @@ struct InitData {
 fn load_init_data() -> Result<InitData> {
     let source_dir = crate::source::find_source_dir()?;
 
-    // One-time cleanup of the orphaned bundled-source cache (now package-embedded).
-    // Don't fail init if cleanup errors — collect it as a warning instead.
+    // Migrate installer state from older versions before reading it.
+    // Don't fail init if a migration errors — collect it as a warning instead.
     let mut warnings = collect_startup_warnings();
 
     let resolve_result = crate::source::resolve_all_sources(&source_dir)?;
     let sources = resolve_result.sources;
+    let directories = resolve_result.directories;
     warnings.extend(resolve_result.warnings);
+    let (profiles, profile_warnings) = crate::profile::load_profiles(&sources);
+    warnings.extend(profile_warnings);
+    warnings.extend(register_custom_targets());
     let init_warnings = if warnings.is_empty() {
         None
     } else {
@@ -122,6 +341,19 @@ fn load_init_data() -> Result<InitData> {
     };
     let (source_entries, source_auto_update) = crate::source::config::load_config()
         .unwrap_or((Vec::new(), true));
+    let batch_hooks = crate::source::config::load_batch_hooks().unwrap_or_default();
+    let mcp_health_interval = crate::source::config::load_mcp_health_interval().unwrap_or(None);
+    let strict_mode = crate::source::config::load_strict_mode().unwrap_or(false);
+    let notify = crate::source::config::load_notify().unwrap_or_default();
+    let committed_only = crate::source::config::load_committed_only().unwrap_or(false);
+    let token_budget = crate::source::config::load_token_budget()
+        .unwrap_or(crate::source::config::DEFAULT_TOKEN_BUDGET);
+    let theme = crate::source::config::load_theme().unwrap_or(None);
+    let trusted_projects = crate::source::config::load_trusted_projects().unwrap_or_default();
+    let refresh_after_batch = crate::source::config::load_refresh_after_batch().unwrap_or_default();
+    let scan_options = ScanOptions {
+        threads: crate::source::config::load_scan_threads().unwrap_or(None),
+    };
     let dest_dir = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?
         .join(".claude");
@@ -131,70 +363,191 @@ fn load_init_data() -> Result<InitData> {
 
     Ok(InitData {
         source_dir, sources, init_warnings,
-        source_entries, source_auto_update, dest_dir, default_project,
+        source_entries, source_auto_update, directories, batch_hooks, mcp_health_interval,
+        strict_mode, notify, committed_only, token_budget, theme, trusted_projects, refresh_after_batch, scan_options, profiles, dest_dir, default_project,
     })
 }
 
-/// Run the one-time orphaned-bundled-cache cleanup, returning any non-fatal
-/// message as a warning. Never fails init.
+/// Make the `targets:` CLIs from sources.yaml selectable, returning
+/// warnings for entries that were skipped.
+pub(crate) fn register_custom_targets() -> Vec<String> {
+    match crate::source::config::load_targets() {
+        Ok((targets, warnings)) => {
+            hibi_core::register_custom_targets(targets);
+            warnings
+        }
+        Err(e) => vec![format!("targets: {:#}", e)],
+    }
+}
+
+/// Run pending state migrations, returning their notes (or the failure)
+/// as warnings. Never fails init.
 fn collect_startup_warnings() -> Vec<String> {
-    let mut warnings = Vec::new();
-    match crate::source::git::cleanup_bundled_cache() {
-        Ok(true) => warnings.push("Removed orphaned bundled cache (~/.hibi/cache/bundled)".to_string()),
-        Ok(false) => {}
-        Err(e) => warnings.push(format!("Failed to clean bundled cache: {}", e)),
+    let Some(home) = dirs::home_dir() else { return Vec::new() };
+    match crate::migrate::run_pending(&home) {
+        Ok(report) => report.notes,
+        Err(e) => vec![format!("{:#}", e)],
     }
-    warnings
 }
 
 impl App {
     pub fn new() -> Result<Self> {
         let d = load_init_data()?;
+        let prefs = dirs::home_dir().map(|home| ui_state::preferences(&home)).unwrap_or_default();
+        let (mcp_scope, mcp_project) = prefs.mcp_scope.unwrap_or_default();
+        // A theme set in sources.yaml wins over the one used last.
+        let (theme, theme_errors) = Theme::load(d.theme.as_deref().or(prefs.theme.as_deref()));
+        let status_message = d.init_warnings.into_iter().chain(theme_errors).reduce(|a, b| format!("{a}; {b}"));
 
-        Ok(Self {
+        let mut app = Self {
             target_cli: None,
             available_tabs: Vec::new(), // Will be set after CLI selection
             tab: Tab::Agents,
+            startup_tab: None,
+            startup_cli: prefs.cli,
             current_view: View::CliSelection,
             focus: FocusArea::default(),
-            cli_selection_index: 0,
+            cli_selection_index: prefs.cli
+                .and_then(|cli| TargetCli::all().iter().position(|c| *c == cli))
+                .unwrap_or(0),
             should_quit: false,
-            theme: Theme::default(),
+            theme,
+            theme_changed: false,
             components: Vec::new(),
             list_index: 0,
             tree_views: HashMap::new(),
+            token_budget: d.token_budget,
             mcp_servers: Vec::new(),
             mcp_index: 0,
-            mcp_scope: McpScope::default(),
-            mcp_project_path: d.default_project.clone(),
+            mcp_scope,
+            mcp_project_path: mcp_project.unwrap_or_else(|| d.default_project.clone()),
+            trusted_projects: d.trusted_projects,
+            trust_pending: None,
+            session_summary: SessionSummary::default(),
+            mcp_updates: HashMap::new(),
+            mcp_update_rx: None,
+            mcp_health: HashMap::new(),
+            mcp_health_rx: None,
+            mcp_health_interval: d.mcp_health_interval,
+            mcp_health_polled_at: None,
+            cli_infos: Vec::new(),
+            cli_probe_rx: None,
             plugins: Vec::new(),
             plugin_index: 0,
             diff_content: None,
+            diff_summary: Vec::new(),
             diff_scroll: 0,
+            preview: None,
+            diff_hunks: None,
+            hunk_staged: Vec::new(),
+            hunk_index: 0,
             source_dir: d.source_dir,
             sources: d.sources,
+            source_changes: Vec::new(),
+            committed_only: d.committed_only,
+            git_status_scroll: 0,
             dest_dir: d.dest_dir,
-            status_message: d.init_warnings,
+            status_message,
             current_output_style: None,
             current_statusline: None,
+            dangling_settings: Vec::new(),
+            hook_positions: HashMap::new(),
             processing_progress: None,
             processing_total: None,
             processing_log: Vec::new(),
+            backup_snapshot: None,
+            journal_baseline: None,
+            undo_armed: false,
+            adopt_armed: false,
+            processed_items: Vec::new(),
             processing_queue: Vec::new(),
+            batch_hooks: d.batch_hooks,
+            strict_mode: d.strict_mode,
+            show_all_hosts: false,
+            notify: d.notify,
+            notify_rx: None,
+            batch_aborted: false,
+            batch_hook_queue: Vec::new(),
             is_removing: false,
-            animation_frame: 0,
             needs_refresh: false,
+            refresh_after_batch: d.refresh_after_batch,
+            scan_options: d.scan_options,
+            deferred_refresh: None,
             refreshing: false,
             processing_complete: false,
             cancelling: false,
+            skipping_item: false,
+            show_commands: false,
+            item_failures: Vec::new(),
+            failure_detail: None,
+            failure_scroll: 0,
             env_input_server_idx: None,
             env_input_vars: Vec::new(),
             env_input_current: 0,
             env_input_buffer: String::new(),
             env_input_values: Vec::new(),
+            mcp_review_rows: Vec::new(),
+            mcp_review_cursor: 0,
+            mcp_review_editing: false,
+            mcp_review_buffer: String::new(),
+            mcp_env_values: HashMap::new(),
+            hook_detail_idx: None,
+            hook_env_rows: Vec::new(),
+            hook_env_cursor: 0,
+            hook_env_editing: false,
+            hook_env_buffer: String::new(),
+            keyword_edit_idx: None,
+            keyword_rows: Vec::new(),
+            keyword_cursor: 0,
+            keyword_editing: false,
+            keyword_buffer: String::new(),
+            keywords_dirty: false,
+            hook_log_lines: Vec::new(),
+            hook_log_filter: None,
+            hook_log_level: None,
+            hook_log_scroll: 0,
+            hook_log_stamp: Vec::new(),
+            pending_marketplace_removals: Vec::new(),
+            stale_pack_files: Vec::new(),
+            prune_all_tabs: false,
+            mirror_tabs: Vec::new(),
+            mirror_index: 0,
+            conflicts: Vec::new(),
+            install_notes: Vec::new(),
+            queue_offline: false,
+            pending_ops: Vec::new(),
+            pending_flush_rx: None,
+            search_query: String::new(),
+            search_results: Vec::new(),
+            search_index: 0,
+            palette_query: String::new(),
+            palette_results: Vec::new(),
+            palette_index: 0,
+            sorts: HashMap::new(),
+            sort_menu_index: 0,
+            hook_scaffold_index: 0,
+            help_open: false,
+            hints: prefs.hints,
+            migration_plan: None,
+            migration_declined: prefs.migration_declined,
+            migration_followup: Vec::new(),
+            interrupted_queue: None,
+            resumed_progress: None,
+            mcp_source_order: Vec::new(),
+            plugin_source_order: Vec::new(),
+            settings_versions: Vec::new(),
+            settings_version_index: 0,
+            settings_restore_armed: false,
+            settings_keys: Vec::new(),
+            settings_key_index: 0,
+            profiles: d.profiles,
+            profile_index: None,
+            profile_pending: false,
             project_path_buffer: d.default_project,
+            project_path_error: None,
             source_entries: d.source_entries,
             source_auto_update: d.source_auto_update,
+            directories: d.directories,
             source_list_index: 0,
             source_add_kind: None,
             source_input_buffer: String::new(),
@@ -206,18 +559,28 @@ impl App {
             source_pending_branch: None,
             source_pending_root: None,
             source_sync_rx: None,
-        })
+        };
+        app.start_cli_probe();
+        Ok(app)
     }
 
     pub fn select_cli(&mut self, cli: TargetCli) -> Result<()> {
         self.target_cli = Some(cli);
         self.dest_dir = cli.get_dest_dir()?;
+        self.profile_pending = self.profile_index.is_some();
+        crate::fs::audit::open(&self.dest_dir);
 
-        // Set available tabs based on CLI
+        // Set available tabs based on CLI; custom types go before MCP.
         self.available_tabs = Tab::for_cli(cli);
+        let custom_at = self.available_tabs.iter()
+            .position(|t| *t == Tab::McpServers)
+            .unwrap_or(self.available_tabs.len());
+        let custom = (0..self.directories.custom_types().len()).map(Tab::Custom);
+        self.available_tabs.splice(custom_at..custom_at, custom);
 
-        // Switch to first available tab
-        self.tab = self.available_tabs.first().copied().unwrap_or(Tab::Skills);
+        // Switch to the requested, remembered or first available tab
+        self.tab = self.initial_tab(cli);
+        self.load_sorts();
 
         // Switch to loading view - actual scanning will be done in background
         self.current_view = View::Loading;
@@ -231,30 +594,76 @@ impl App {
         mcp_servers: Vec<McpServer>,
         plugins: Vec<Plugin>,
         cleaned_hooks: Vec<String>,
+        undo_report: Option<String>,
     ) {
+        // The rescan covers whatever a deferred refresh would have.
+        self.deferred_refresh = None;
         self.components = components;
         self.mcp_servers = mcp_servers;
         self.plugins = plugins;
 
         // Read current settings
-        let (current_output_style, current_statusline) = settings::read_current_settings(&self.dest_dir);
-        self.current_output_style = current_output_style;
-        self.current_statusline = current_statusline;
+        self.reload_current_settings();
+        self.hook_positions = crate::fs::installer::read_hook_positions(&self.dest_dir);
 
         // Build tree views
-        self.tree_views = build_tree_views(&self.components);
+        self.tree_views = build_tree_views(&self.components, &self.directories, self.show_all_hosts);
+        self.apply_sorts();
+        // Only the first load after picking a profile; later rescans keep
+        // the selection made since.
+        let profile_report = std::mem::take(&mut self.profile_pending)
+            .then(|| self.apply_profile())
+            .flatten();
 
         // Switch to list view
         self.current_view = View::List;
-        if !cleaned_hooks.is_empty() {
+        if let Some(report) = undo_report {
+            self.session_summary.changes.push(format!("Undo: {}", report));
+            self.status_message = Some(report);
+        } else if !cleaned_hooks.is_empty() {
             self.status_message = Some(format!(
                 "Auto-cleaned {} deprecated hook(s): {}",
                 cleaned_hooks.len(),
                 cleaned_hooks.join(", ")
             ));
+        } else if let Some(report) = profile_report {
+            self.status_message = Some(report);
         } else if let Some(cli) = self.target_cli {
             self.status_message = Some(format!("Selected {}", cli.display_name()));
         }
+        self.open_pending_retry();
+        self.offer_resume();
+        self.offer_migration();
+    }
+
+    /// Component type shown on `tab`, including custom types.
+    pub fn tab_component_type(&self, tab: Tab) -> Option<ComponentType> {
+        match tab {
+            Tab::Custom(i) => self.directories.custom_types().get(i).map(|c| c.component_type()),
+            _ => tab.to_component_type(),
+        }
+    }
+
+    /// Tab title. Custom types use their configured name; component tabs
+    /// whose source folder was remapped via `directories:` are named after
+    /// that folder (`prompts/` -> "Prompts").
+    pub fn tab_label(&self, tab: Tab) -> String {
+        if let Tab::Custom(i) = tab {
+            return self.directories.custom_types().get(i)
+                .map(|c| c.name.clone())
+                .unwrap_or_default();
+        }
+        let remapped = tab.to_component_type().and_then(|t| {
+            let source = self.directories.source_dir(&t);
+            (source != t.display_name()).then(|| source.rsplit('/').next().unwrap_or(source).to_string())
+        });
+        match remapped {
+            Some(folder) => {
+                let mut chars = folder.chars();
+                chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or(folder)
+            }
+            None => tab.display_name().to_string(),
+        }
     }
 
     pub fn has_multiple_sources(&self) -> bool {
@@ -263,7 +672,7 @@ impl App {
 
     /// Get components filtered by current tab
     pub fn current_components(&self) -> Vec<(usize, &Component)> {
-        if let Some(comp_type) = self.tab.to_component_type() {
+        if let Some(comp_type) = self.tab_component_type(self.tab) {
             self.components
                 .iter()
                 .enumerate()
@@ -275,11 +684,181 @@ impl App {
     }
 }
 
-pub(crate) fn build_tree_views(components: &[Component]) -> HashMap<Tab, TreeView> {
+#[cfg(test)]
+impl App {
+    /// An app with no sources, CLI or home directory behind it, for unit
+    /// tests. `App::new()` resolves sources on disk, which would make the
+    /// tests depend on the machine running them.
+    pub(crate) fn for_test() -> Self {
+        Self {
+            target_cli: None,
+            available_tabs: Vec::new(),
+            tab: Tab::Skills,
+            startup_tab: None,
+            startup_cli: None,
+            current_view: View::List,
+            focus: FocusArea::Content,
+            cli_selection_index: 0,
+            should_quit: false,
+            theme: Theme::default(),
+            theme_changed: false,
+            components: Vec::new(),
+            list_index: 0,
+            tree_views: HashMap::new(),
+            token_budget: crate::source::config::DEFAULT_TOKEN_BUDGET,
+            mcp_servers: Vec::new(),
+            mcp_index: 0,
+            mcp_scope: McpScope::default(),
+            mcp_project_path: String::new(),
+            trusted_projects: Vec::new(),
+            trust_pending: None,
+            session_summary: SessionSummary::default(),
+            mcp_updates: HashMap::new(),
+            mcp_update_rx: None,
+            mcp_health: HashMap::new(),
+            mcp_health_rx: None,
+            mcp_health_interval: None,
+            mcp_health_polled_at: None,
+            cli_infos: Vec::new(),
+            cli_probe_rx: None,
+            plugins: Vec::new(),
+            plugin_index: 0,
+            diff_content: None,
+            diff_summary: Vec::new(),
+            diff_scroll: 0,
+            preview: None,
+            diff_hunks: None,
+            hunk_staged: Vec::new(),
+            hunk_index: 0,
+            source_dir: PathBuf::new(),
+            sources: Vec::new(),
+            source_changes: Vec::new(),
+            committed_only: false,
+            git_status_scroll: 0,
+            dest_dir: PathBuf::new(),
+            status_message: None,
+            current_output_style: None,
+            current_statusline: None,
+            dangling_settings: Vec::new(),
+            hook_positions: HashMap::new(),
+            processing_progress: None,
+            processing_total: None,
+            processing_log: Vec::new(),
+            backup_snapshot: None,
+            journal_baseline: None,
+            undo_armed: false,
+            adopt_armed: false,
+            processed_items: Vec::new(),
+            processing_queue: Vec::new(),
+            batch_hooks: Default::default(),
+            strict_mode: false,
+            show_all_hosts: false,
+            notify: Default::default(),
+            notify_rx: None,
+            batch_aborted: false,
+            batch_hook_queue: Vec::new(),
+            is_removing: false,
+            needs_refresh: false,
+            refresh_after_batch: Default::default(),
+            scan_options: ScanOptions::default(),
+            deferred_refresh: None,
+            refreshing: false,
+            processing_complete: false,
+            cancelling: false,
+            skipping_item: false,
+            show_commands: false,
+            item_failures: Vec::new(),
+            failure_detail: None,
+            failure_scroll: 0,
+            env_input_server_idx: None,
+            env_input_vars: Vec::new(),
+            env_input_current: 0,
+            env_input_buffer: String::new(),
+            env_input_values: Vec::new(),
+            mcp_review_rows: Vec::new(),
+            mcp_review_cursor: 0,
+            mcp_review_editing: false,
+            mcp_review_buffer: String::new(),
+            mcp_env_values: HashMap::new(),
+            hook_detail_idx: None,
+            hook_env_rows: Vec::new(),
+            hook_env_cursor: 0,
+            hook_env_editing: false,
+            hook_env_buffer: String::new(),
+            keyword_edit_idx: None,
+            keyword_rows: Vec::new(),
+            keyword_cursor: 0,
+            keyword_editing: false,
+            keyword_buffer: String::new(),
+            keywords_dirty: false,
+            hook_log_lines: Vec::new(),
+            hook_log_filter: None,
+            hook_log_level: None,
+            hook_log_scroll: 0,
+            hook_log_stamp: Vec::new(),
+            pending_marketplace_removals: Vec::new(),
+            stale_pack_files: Vec::new(),
+            prune_all_tabs: false,
+            mirror_tabs: Vec::new(),
+            mirror_index: 0,
+            conflicts: Vec::new(),
+            install_notes: Vec::new(),
+            queue_offline: false,
+            pending_ops: Vec::new(),
+            pending_flush_rx: None,
+            search_query: String::new(),
+            search_results: Vec::new(),
+            search_index: 0,
+            palette_query: String::new(),
+            palette_results: Vec::new(),
+            palette_index: 0,
+            sorts: HashMap::new(),
+            sort_menu_index: 0,
+            hook_scaffold_index: 0,
+            help_open: false,
+            hints: HintProgress::default(),
+            migration_plan: None,
+            migration_declined: false,
+            migration_followup: Vec::new(),
+            interrupted_queue: None,
+            resumed_progress: None,
+            mcp_source_order: Vec::new(),
+            plugin_source_order: Vec::new(),
+            settings_versions: Vec::new(),
+            settings_version_index: 0,
+            settings_restore_armed: false,
+            settings_keys: Vec::new(),
+            settings_key_index: 0,
+            profiles: Vec::new(),
+            profile_index: None,
+            profile_pending: false,
+            project_path_buffer: String::new(),
+            project_path_error: None,
+            source_entries: Vec::new(),
+            source_auto_update: false,
+            directories: Default::default(),
+            source_list_index: 0,
+            source_add_kind: None,
+            source_input_buffer: String::new(),
+            source_edit_index: None,
+            source_sync_status: None,
+            source_sync_cancel_tx: None,
+            source_input_error: None,
+            source_pending_url: String::new(),
+            source_pending_branch: None,
+            source_pending_root: None,
+            source_sync_rx: None,
+        }
+    }
+}
+
+/// Components another machine's `hosts:` filter claims are left out
+/// unless `all_hosts` is set.
+pub(crate) fn build_tree_views(components: &[Component], directories: &DirectoryMap, all_hosts: bool) -> HashMap<Tab, TreeView> {
     let mut tree_views = HashMap::new();
 
     // Build tree view for each component-based tab
-    let component_tabs = [
+    let builtin_tabs = [
         (Tab::Agents, ComponentType::Agents),
         (Tab::Commands, ComponentType::Commands),
         (Tab::Contexts, ComponentType::Contexts),
@@ -290,12 +869,15 @@ pub(crate) fn build_tree_views(components: &[Component]) -> HashMap<Tab, TreeVie
         (Tab::Statusline, ComponentType::Statusline),
         (Tab::Config, ComponentType::ConfigFile),
     ];
+    let custom_tabs = directories.custom_types().iter()
+        .enumerate()
+        .map(|(i, c)| (Tab::Custom(i), c.component_type()));
 
-    for (tab, comp_type) in component_tabs {
+    for (tab, comp_type) in builtin_tabs.into_iter().chain(custom_tabs) {
         let filtered: Vec<(usize, &Component)> = components
             .iter()
             .enumerate()
-            .filter(|(_, c)| c.component_type == comp_type)
+            .filter(|(_, c)| c.component_type == comp_type && (all_hosts || c.for_this_host()))
             .collect();
 
         let tree = TreeView::build_from_components(components, &filtered);
@@ -129,6 +129,71 @@ impl App {
         }
     }
 
+    /// Collapse (`z`) or expand (`X`) every folder of the current tab.
+    pub fn set_all_folders_expanded(&mut self, expanded: bool) {
+        if let Some(tree) = self.tree_views.get_mut(&self.tab) {
+            if expanded { tree.expand_all() } else { tree.collapse_all() }
+        }
+    }
+
+    /// Jump to the first (`g`) or last (`G`) row of the current list.
+    pub fn goto_list_edge(&mut self, bottom: bool) {
+        if self.tab == Tab::McpServers {
+            self.mcp_index = if bottom { self.mcp_servers.len().saturating_sub(1) } else { 0 };
+        } else if self.tab == Tab::Plugins {
+            self.plugin_index = if bottom { self.plugins.len().saturating_sub(1) } else { 0 };
+        } else if let Some(tree) = self.tree_views.get_mut(&self.tab) {
+            if bottom { tree.goto_bottom() } else { tree.goto_top() }
+        }
+    }
+
+    /// Switch to `tab`, as if it had been reached with the arrow keys.
+    pub fn select_tab(&mut self, tab: Tab) {
+        if tab != self.tab && self.available_tabs.contains(&tab) {
+            self.tab = tab;
+            self.list_index = 0;
+        }
+    }
+
+    /// Number of rows in the current tab's list.
+    pub fn list_len(&self) -> usize {
+        match self.tab {
+            Tab::McpServers => self.mcp_servers.len(),
+            Tab::Plugins => self.plugins.len(),
+            _ => self.tree_views.get(&self.tab).map_or(0, |t| t.visible_indices.len()),
+        }
+    }
+
+    /// Current row of the current tab's list.
+    fn list_cursor(&self) -> usize {
+        match self.tab {
+            Tab::McpServers => self.mcp_index,
+            Tab::Plugins => self.plugin_index,
+            _ => self.tree_views.get(&self.tab).map_or(0, |t| t.cursor),
+        }
+    }
+
+    /// Put the cursor on `row` of the current tab's list, clamped to its
+    /// last row. Unlike `next_item`/`prev_item` this never wraps.
+    pub fn select_list_row(&mut self, row: usize) {
+        let Some(last) = self.list_len().checked_sub(1) else { return };
+        let row = row.min(last);
+        match self.tab {
+            Tab::McpServers => self.mcp_index = row,
+            Tab::Plugins => self.plugin_index = row,
+            _ => {
+                if let Some(tree) = self.tree_views.get_mut(&self.tab) {
+                    tree.cursor = row;
+                }
+            }
+        }
+    }
+
+    /// Move the cursor `delta` rows, stopping at either end of the list.
+    pub fn move_list_cursor(&mut self, delta: isize) {
+        self.select_list_row(self.list_cursor().saturating_add_signed(delta));
+    }
+
     /// Get current tree view
     pub fn get_tree_view(&self) -> Option<&TreeView> {
         self.tree_views.get(&self.tab)
@@ -140,71 +205,9 @@ mod tests {
     use super::*;
     use crate::app::App;
 
-    fn fresh_app() -> App {
-        // App::new() does filesystem I/O for source resolution which would
-        // make the test brittle, so we hand-construct only the fields the
-        // focus helpers actually touch.
-        App {
-            target_cli: None,
-            available_tabs: Vec::new(),
-            tab: Tab::Skills,
-            current_view: crate::app::View::List,
-            focus: FocusArea::Content,
-            cli_selection_index: 0,
-            should_quit: false,
-            theme: crate::theme::Theme::default(),
-            components: Vec::new(),
-            list_index: 0,
-            tree_views: std::collections::HashMap::new(),
-            mcp_servers: Vec::new(),
-            mcp_index: 0,
-            mcp_scope: crate::mcp::McpScope::default(),
-            mcp_project_path: String::new(),
-            plugins: Vec::new(),
-            plugin_index: 0,
-            diff_content: None,
-            diff_scroll: 0,
-            source_dir: std::path::PathBuf::new(),
-            sources: Vec::new(),
-            dest_dir: std::path::PathBuf::new(),
-            status_message: None,
-            current_output_style: None,
-            current_statusline: None,
-            processing_progress: None,
-            processing_total: None,
-            processing_log: Vec::new(),
-            processing_queue: Vec::new(),
-            is_removing: false,
-            animation_frame: 0,
-            needs_refresh: false,
-            refreshing: false,
-            processing_complete: false,
-            cancelling: false,
-            env_input_server_idx: None,
-            env_input_vars: Vec::new(),
-            env_input_current: 0,
-            env_input_buffer: String::new(),
-            env_input_values: Vec::new(),
-            project_path_buffer: String::new(),
-            source_entries: Vec::new(),
-            source_auto_update: false,
-            source_list_index: 0,
-            source_add_kind: None,
-            source_input_buffer: String::new(),
-            source_edit_index: None,
-            source_sync_status: None,
-            source_sync_cancel_tx: None,
-            source_input_error: None,
-            source_pending_url: String::new(),
-            source_pending_branch: None,
-            source_pending_root: None,
-            source_sync_rx: None,
-        }
-    }
-
     #[test]
     fn toggle_focus_cycles_between_panes() {
-        let mut app = fresh_app();
+        let mut app = App::for_test();
         assert_eq!(app.focus, FocusArea::Content);
         app.toggle_focus();
         assert_eq!(app.focus, FocusArea::Tabs);
@@ -214,7 +217,7 @@ mod tests {
 
     #[test]
     fn focus_content_is_idempotent() {
-        let mut app = fresh_app();
+        let mut app = App::for_test();
         app.focus = FocusArea::Tabs;
         app.focus_content();
         assert_eq!(app.focus, FocusArea::Content);
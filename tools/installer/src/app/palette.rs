@@ -0,0 +1,176 @@
+//! Command palette (Ctrl+P): every action available in the list view by
+//! name, filtered as you type. Running a command replays its key through
+//! the normal key dispatch, so the palette can't drift from what the keys
+//! do; tab actions come from the `TAB_ACTIONS` registry.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::types::{FocusArea, Tab, View};
+use super::App;
+
+/// What choosing a palette row does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaletteAction {
+    /// Press this key in the list view.
+    Key(KeyEvent),
+    /// Switch to this tab.
+    Tab(Tab),
+}
+
+/// One row of the palette.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    /// Key that runs the action directly, shown next to it.
+    pub keys: String,
+    pub action: PaletteAction,
+}
+
+/// Commands available on every tab, with the key they replay.
+const GLOBAL_COMMANDS: &[(&str, KeyCode, KeyModifiers)] = &[
+    ("Install selected", KeyCode::Char('i'), KeyModifiers::NONE),
+    ("Remove selected", KeyCode::Char('r'), KeyModifiers::NONE),
+    ("Toggle selection", KeyCode::Char(' '), KeyModifiers::NONE),
+    ("Select all", KeyCode::Char('a'), KeyModifiers::NONE),
+    ("Select none", KeyCode::Char('n'), KeyModifiers::NONE),
+    ("Search all tabs", KeyCode::Char('f'), KeyModifiers::CONTROL),
+    ("Sort", KeyCode::Char('S'), KeyModifiers::NONE),
+    ("Rescan sources", KeyCode::Char('R'), KeyModifiers::NONE),
+    ("Next theme", KeyCode::Char('t'), KeyModifiers::NONE),
+    ("Key help", KeyCode::Char('?'), KeyModifiers::NONE),
+    ("Quit", KeyCode::Char('q'), KeyModifiers::NONE),
+];
+
+fn key_label(key: &KeyEvent) -> String {
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{:?}", other),
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl+{}", name.to_uppercase())
+    } else {
+        name
+    }
+}
+
+/// Score of `query` as a subsequence of `text`, ignoring case; `None`
+/// when it isn't one. Consecutive letters and letters that start a word
+/// score higher, so "ins" ranks "Install selected" over "settings.json
+/// history".
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut at = 0;
+    let mut previous = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = at + text[at..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(found);
+        at = found + 1;
+    }
+    Some(score)
+}
+
+impl App {
+    /// Every command available right now: the global ones, this tab's
+    /// actions, then switching to each other tab.
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let key_entry = |label: &str, key: KeyEvent| PaletteEntry {
+            label: label.to_string(),
+            keys: key_label(&key),
+            action: PaletteAction::Key(key),
+        };
+        let mut entries: Vec<PaletteEntry> = GLOBAL_COMMANDS.iter()
+            .map(|&(label, code, modifiers)| key_entry(label, KeyEvent::new(code, modifiers)))
+            .collect();
+        for action in self.tab_actions() {
+            for (&code, &label) in action.keys.iter().zip(action.commands) {
+                entries.push(key_entry(label, KeyEvent::new(code, KeyModifiers::NONE)));
+            }
+        }
+        for &tab in self.available_tabs.iter().filter(|&&tab| tab != self.tab) {
+            entries.push(PaletteEntry {
+                label: format!("Go to {}", self.tab_label(tab)),
+                keys: String::new(),
+                action: PaletteAction::Tab(tab),
+            });
+        }
+        entries
+    }
+
+    pub fn open_palette(&mut self) {
+        self.palette_query.clear();
+        self.palette_index = 0;
+        self.palette_results = self.palette_entries();
+        self.current_view = View::Palette;
+    }
+
+    fn refresh_palette(&mut self) {
+        let mut scored: Vec<(i32, PaletteEntry)> = self.palette_entries()
+            .into_iter()
+            .filter_map(|entry| Some((fuzzy_score(&self.palette_query, &entry.label)?, entry)))
+            .collect();
+        // Stable, so equal scores keep registry order.
+        scored.sort_by_key(|(score, _)| -score);
+        self.palette_results = scored.into_iter().map(|(_, entry)| entry).collect();
+        self.palette_index = 0;
+    }
+
+    /// Handle a key in the palette. Returns the key to replay in the list
+    /// view when a key command was chosen.
+    pub fn handle_palette_key(&mut self, key: KeyCode) -> Option<KeyEvent> {
+        match key {
+            KeyCode::Esc => self.current_view = View::List,
+            KeyCode::Enter => return self.run_palette_entry(),
+            KeyCode::Down if self.palette_index + 1 < self.palette_results.len() => {
+                self.palette_index += 1;
+            }
+            KeyCode::Up => self.palette_index = self.palette_index.saturating_sub(1),
+            KeyCode::Backspace => {
+                self.palette_query.pop();
+                self.refresh_palette();
+            }
+            KeyCode::Char(c) => {
+                self.palette_query.push(c);
+                self.refresh_palette();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn run_palette_entry(&mut self) -> Option<KeyEvent> {
+        let entry = self.palette_results.get(self.palette_index)?.clone();
+        self.current_view = View::List;
+        self.focus = FocusArea::Content;
+        match entry.action {
+            PaletteAction::Key(key) => Some(key),
+            PaletteAction::Tab(tab) => {
+                self.select_tab(tab);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_rank_word_starts_and_runs_first() {
+        assert_eq!(fuzzy_score("xyz", "Install selected"), None);
+        assert_eq!(fuzzy_score("", "Quit"), Some(0));
+        let install = fuzzy_score("ins", "Install selected").unwrap();
+        let history = fuzzy_score("ins", "settings.json history").unwrap();
+        assert!(install > history);
+        assert!(fuzzy_score("exp all", "Expand all folders").is_some());
+    }
+}
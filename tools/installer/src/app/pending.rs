@@ -0,0 +1,179 @@
+use std::sync::mpsc::{self, TryRecvError};
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::View;
+use super::{App, TargetCli};
+use crate::fs::pending::{self, FlushResult, PendingOp};
+use crate::mcp::McpStatus;
+use crate::plugin::PluginStatus;
+
+/// Result of retrying the offline queue in the background.
+pub type FlushPayload = Result<Vec<FlushResult>>;
+
+impl App {
+    /// Offer to retry the installs queued while offline, once the CLI's
+    /// items have loaded.
+    pub(super) fn open_pending_retry(&mut self) {
+        self.pending_ops = pending::load(&self.dest_dir).unwrap_or_default();
+        if !self.pending_ops.is_empty() && self.current_view == View::List {
+            self.current_view = View::PendingRetry;
+        }
+    }
+
+    /// The network check before an MCP or plugin install failed. `Enter`
+    /// queues the install for later, `c` tries anyway, `Esc` cancels.
+    pub fn handle_offline_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Enter => {
+                self.queue_offline = true;
+                self.complete_install_setup()
+            }
+            KeyCode::Char('c') => self.complete_install_setup(),
+            KeyCode::Esc => {
+                self.processing_queue.clear();
+                self.install_notes.clear();
+                self.status_message = Some("Cancelled".to_string());
+                self.current_view = View::List;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `r` retries the queued installs now, `d` discards them, `Esc`
+    /// leaves them for later.
+    pub fn handle_pending_retry_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Char('r') => self.start_pending_flush(),
+            KeyCode::Char('d') => {
+                pending::discard(&self.dest_dir)?;
+                let count = std::mem::take(&mut self.pending_ops).len();
+                self.status_message = Some(format!("Discarded {} queued install(s)", count));
+                self.current_view = View::List;
+            }
+            KeyCode::Esc => {
+                self.status_message = Some("Queued installs kept; run `hibi flush-pending` or restart to retry".to_string());
+                self.current_view = View::List;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn start_pending_flush(&mut self) {
+        self.current_view = View::List;
+        if self.pending_flush_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let dest_dir = self.dest_dir.clone();
+        let target = self.target_cli.unwrap_or(TargetCli::Claude);
+        std::thread::spawn(move || {
+            let _ = tx.send(pending::flush(&dest_dir, target));
+        });
+        self.pending_flush_rx = Some(rx);
+        self.status_message = Some(format!("Retrying {} queued install(s)...", self.pending_ops.len()));
+    }
+
+    /// Apply a finished retry, if any. Called every loop tick.
+    pub fn poll_pending_flush(&mut self) {
+        let Some(rx) = &self.pending_flush_rx else { return };
+        let results = match rx.try_recv() {
+            Ok(Ok(results)) => results,
+            Ok(Err(e)) => {
+                self.status_message = Some(format!("Couldn't retry queued installs: {:#}", e));
+                self.pending_flush_rx = None;
+                return;
+            }
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.status_message = Some("Retry of queued installs stopped unexpectedly".to_string());
+                self.pending_flush_rx = None;
+                return;
+            }
+        };
+        self.pending_flush_rx = None;
+
+        let (done, failed): (Vec<_>, Vec<_>) = results.into_iter().partition(|r| r.error.is_none());
+        for result in &done {
+            self.mark_installed(&result.op);
+            self.session_summary.changes.push(format!(
+                "Installed queued {}: {}", result.op.kind_label(), result.op.name(),
+            ));
+        }
+        self.status_message = Some(match failed.first() {
+            None => format!("Installed {} queued item(s)", done.len()),
+            Some(first) => format!(
+                "Installed {} queued item(s), {} still queued ({}: {})",
+                done.len(),
+                failed.len(),
+                first.op.name(),
+                first.error.as_deref().unwrap_or_default(),
+            ),
+        });
+        self.pending_ops = failed.into_iter().map(|r| r.op).collect();
+    }
+
+    fn mark_installed(&mut self, op: &PendingOp) {
+        match op {
+            PendingOp::McpServer { server, .. } => {
+                for m in self.mcp_servers.iter_mut().filter(|m| m.def.name == server.name) {
+                    m.status = McpStatus::Installed;
+                }
+            }
+            PendingOp::Plugin { plugin } => {
+                for p in self.plugins.iter_mut().filter(|p| p.def.name == plugin.name) {
+                    p.status = PluginStatus::Installed;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn offline_installs_are_queued_with_their_env_values() {
+        use crate::app::{TargetCli, View};
+        use crate::mcp::{McpServer, McpServerDef, McpStatus};
+        use crate::process_exec::{execute, prepare, ProcessData};
+        use crossterm::event::KeyCode;
+
+        let mut app = App::for_test();
+        app.dest_dir = temp_dir("offline_queue");
+        app.tab = Tab::McpServers;
+        app.mcp_servers = vec![McpServer::new(McpServerDef {
+            name: "keyed".into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env: vec!["HIBI_TEST_UNSET_TOKEN".into()], version: None, env_file: None,
+        }, McpStatus::NotInstalled)];
+        app.processing_queue = vec![0];
+        app.current_view = View::OfflineQueue;
+
+        app.handle_offline_key(KeyCode::Enter).unwrap();
+        assert!(app.current_view == View::EnvInput);
+        app.env_input_buffer = "typed".into();
+        app.env_input_submit().unwrap();
+        assert!(app.current_view == View::Installing);
+
+        let data = prepare(&app, 0).unwrap();
+        assert!(matches!(data, ProcessData::Queued { .. }));
+        let (_cancel_tx, cancel_rx) = std::sync::mpsc::channel();
+        let (progress_tx, _progress_rx) = std::sync::mpsc::channel();
+        let line = execute(data, false, TargetCli::Claude, cancel_rx, progress_tx).unwrap();
+        let queued = crate::fs::pending::load(&app.dest_dir).unwrap();
+        let _ = std::fs::remove_dir_all(&app.dest_dir);
+
+        assert!(line.starts_with("[QUEUED] keyed:"));
+        let [crate::fs::pending::PendingOp::McpServer { server, env_values, .. }] = queued.as_slice() else {
+            panic!("expected one queued MCP server");
+        };
+        assert_eq!(server.name, "keyed");
+        assert_eq!(env_values, &[("HIBI_TEST_UNSET_TOKEN".to_string(), "typed".to_string())]);
+    }
+}
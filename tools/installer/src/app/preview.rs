@@ -0,0 +1,78 @@
+//! Head of the highlighted component's source file, shown in the List
+//! view's preview pane. Read once per cursor move rather than per frame.
+
+use std::io::Read;
+use std::path::Path;
+
+use super::App;
+
+/// Lines of the file the preview shows.
+const PREVIEW_LINES: usize = 40;
+/// Most bytes read for a preview; enough for `PREVIEW_LINES` of prose.
+const PREVIEW_BYTES: u64 = 64 * 1024;
+
+impl App {
+    /// Reload the preview if the highlighted component changed. Folders
+    /// and the MCP and plugin tabs have none.
+    pub fn refresh_preview(&mut self) {
+        let path = self.get_tree_view()
+            .and_then(|_| self.selected_component_index())
+            .and_then(|idx| self.components.get(idx))
+            .map(|c| c.source_path.clone());
+        if self.preview.as_ref().map(|(p, _)| p) == path.as_ref() {
+            return;
+        }
+        self.preview = path.map(|p| {
+            let lines = read_preview(&p);
+            (p, lines)
+        });
+    }
+}
+
+/// First `PREVIEW_LINES` lines of `path` (its entries, for a directory),
+/// or a one-line note when it is binary or can't be read.
+fn read_preview(path: &Path) -> Vec<String> {
+    if path.is_dir() {
+        let mut names: Vec<String> = std::fs::read_dir(path)
+            .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.file_name().to_string_lossy().into_owned()).collect())
+            .unwrap_or_default();
+        names.sort();
+        if names.is_empty() {
+            names.push("(empty directory)".to_string());
+        }
+        return names.into_iter().take(PREVIEW_LINES).collect();
+    }
+    let mut bytes = Vec::new();
+    if let Err(e) = std::fs::File::open(path).and_then(|f| f.take(PREVIEW_BYTES).read_to_end(&mut bytes)) {
+        return vec![format!("(can't read {}: {})", path.display(), e)];
+    }
+    if bytes.contains(&0) {
+        return vec!["(binary file)".to_string()];
+    }
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .take(PREVIEW_LINES)
+        .map(|line| line.replace('\t', "    "))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hibi_core::test_util::temp_dir;
+
+    #[test]
+    fn preview_keeps_the_head_of_text_files_only() {
+        let dir = temp_dir("preview");
+        let text: String = (1..=60).map(|i| format!("line {i}\n")).collect();
+        std::fs::write(dir.join("agent.md"), format!("---\nname: x\n---\n{text}")).unwrap();
+        std::fs::write(dir.join("hook"), [0x7f, b'E', b'L', b'F', 0, 1]).unwrap();
+
+        let lines = read_preview(&dir.join("agent.md"));
+        assert_eq!(lines.len(), PREVIEW_LINES);
+        assert_eq!(lines[..2], ["---", "name: x"]);
+        assert_eq!(read_preview(&dir.join("hook")), ["(binary file)"]);
+        assert!(read_preview(&dir.join("gone.md"))[0].starts_with("(can't read"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
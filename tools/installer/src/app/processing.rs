@@ -1,10 +1,15 @@
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
+use crossterm::event::KeyCode;
 
+use super::trust::TrustPending;
 use super::types::{Tab, View};
 use super::{App, build_tree_views};
-use crate::component::Component;
-use crate::mcp::McpServer;
-use crate::plugin::Plugin;
+use crate::component::{Component, InstallStatus};
+use crate::mcp::{McpServer, McpStatus};
+use crate::fs;
+use crate::plugin::{unused_marketplaces, Plugin, PluginStatus};
 
 /// Run the CLI presence pre-flight only for tabs that actually shell out
 /// to `claude`/`codex`. Components are pure filesystem ops and don't need
@@ -13,8 +18,39 @@ pub(crate) fn needs_cli_preflight(tab: Tab) -> bool {
     matches!(tab, Tab::Plugins | Tab::McpServers)
 }
 
+/// When a configured batch hook runs relative to the batch's items.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BatchStage {
+    Pre,
+    Post,
+}
+
+impl BatchStage {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pre => "pre-batch",
+            Self::Post => "post-batch",
+        }
+    }
+}
+
+/// A post-batch status refresh put off by `refresh_delay_secs:`, with
+/// what `apply_targeted_refresh` needs to run it later.
+#[derive(Debug)]
+pub struct DeferredRefresh {
+    due: Instant,
+    tab: Tab,
+    removing: bool,
+    processed: Vec<(usize, bool)>,
+}
+
 impl App {
     pub fn install_selected(&mut self) -> Result<()> {
+        // A batch started by hand replaces a pending migration step.
+        self.migration_followup.clear();
+        // Statuses from an earlier batch must be current before the next.
+        self.run_deferred_refresh(true);
+        self.queue_offline = false;
         // Build install queue
         let indices: Vec<usize> = if self.tab == Tab::McpServers {
             self.mcp_servers
@@ -30,7 +66,7 @@ impl App {
                 .filter(|(_, p)| p.selected)
                 .map(|(i, _)| i)
                 .collect()
-        } else if let Some(comp_type) = self.tab.to_component_type() {
+        } else if let Some(comp_type) = self.tab_component_type(self.tab) {
             self.components
                 .iter()
                 .enumerate()
@@ -42,15 +78,67 @@ impl App {
         } else {
             Vec::new()
         };
+        self.start_install(indices)
+    }
+
+    /// Install the items at `indices` on the current tab: the part of
+    /// `install_selected` after the selection is read.
+    pub(super) fn start_install(&mut self, indices: Vec<usize>) -> Result<()> {
+        self.start_install_from(indices, None)
+    }
+
+    /// `start_install` for a batch whose progress counts on from
+    /// `resumed_progress` finished items; `None` for a new batch, which
+    /// isn't the rest of an interrupted one.
+    pub(super) fn start_install_from(&mut self, mut indices: Vec<usize>, resumed_progress: Option<usize>) -> Result<()> {
+        self.resumed_progress = resumed_progress;
+        let held_back = if self.tab_component_type(self.tab).is_some() {
+            self.hold_back_uncommitted(&mut indices)
+        } else {
+            Vec::new()
+        };
+        if !held_back.is_empty() {
+            let note = format!("Left out (uncommitted changes): {}", held_back.join(", "));
+            self.session_summary.warnings.push(format!("[WARN] {}", note));
+            self.status_message = Some(note);
+        }
 
         if indices.is_empty() {
-            self.status_message = Some("No items selected".to_string());
+            if held_back.is_empty() {
+                self.status_message = Some("No items selected".to_string());
+            }
+            return Ok(());
+        }
+
+        if !self.require_trusted_project(TrustPending::Install) {
             return Ok(());
         }
 
         self.processing_queue = indices;
         self.is_removing = false;
 
+        if self.open_conflicts() {
+            return Ok(());
+        }
+        self.continue_install()
+    }
+
+    /// The rest of `start_install`, once modified files are settled.
+    pub(super) fn continue_install(&mut self) -> Result<()> {
+        // Skill packs: files recorded from an earlier install that the
+        // source no longer ships are listed for removal first.
+        if self.tab_component_type(self.tab).is_some() {
+            let installing: Vec<&Component> = self.processing_queue.iter()
+                .filter_map(|&i| self.components.get(i))
+                .collect();
+            let stale = fs::installer::stale_pack_files(&self.dest_dir, &installing, &self.components);
+            if !stale.is_empty() {
+                self.stale_pack_files = stale;
+                self.current_view = View::StaleFilesConfirm;
+                return Ok(());
+            }
+        }
+
         // Plugin/MCP install shells out to the CLI; hand off to the
         // Preflighting view so the 8-second `--version` probe runs on
         // a background thread instead of blocking the TUI tick.
@@ -65,6 +153,7 @@ impl App {
     }
 
     pub fn remove_selected(&mut self) -> Result<()> {
+        self.run_deferred_refresh(true);
         // Build remove queue
         let indices: Vec<usize> = if self.tab == Tab::McpServers {
             self.mcp_servers
@@ -80,11 +169,13 @@ impl App {
                 .filter(|(_, p)| p.selected)
                 .map(|(i, _)| i)
                 .collect()
-        } else if let Some(comp_type) = self.tab.to_component_type() {
+        } else if let Some(comp_type) = self.tab_component_type(self.tab) {
             self.components
                 .iter()
                 .enumerate()
-                .filter(|(_, c)| c.selected && c.component_type == comp_type)
+                .filter(|(_, c)| {
+                    c.selected && c.component_type == comp_type && c.status != InstallStatus::Unconfigured
+                })
                 .map(|(i, _)| i)
                 .collect()
         } else {
@@ -96,6 +187,10 @@ impl App {
             return Ok(());
         }
 
+        if !self.require_trusted_project(TrustPending::Remove) {
+            return Ok(());
+        }
+
         self.processing_queue = indices;
         self.is_removing = true;
 
@@ -118,13 +213,26 @@ impl App {
     /// no preflight) and `handle_preflighting_view` after the background
     /// `--version` probe returns Ok.
     pub(crate) fn complete_install_setup(&mut self) -> Result<()> {
-        // For MCP servers, check if any have missing env vars
+        if self.tab == Tab::McpServers {
+            self.prefill_env_from_files();
+        }
+
+        // Multi-server MCP batches go through the review table, which
+        // collects every missing value up front.
+        if self.tab == Tab::McpServers && self.processing_queue.len() > 1 {
+            self.start_mcp_review();
+            return Ok(());
+        }
+
+        // For a single MCP server, prompt for any missing env vars
         if self.tab == Tab::McpServers {
             let indices = self.processing_queue.clone();
             for &idx in &indices {
                 if let Some(server) = self.mcp_servers.get(idx) {
+                    let from_file = self.mcp_env_values.get(&idx);
                     let missing: Vec<String> = server.def.env.iter()
                         .filter(|e| std::env::var(e).is_err())
+                        .filter(|e| !from_file.is_some_and(|values| values.iter().any(|(var, _)| var == *e)))
                         .cloned()
                         .collect();
 
@@ -136,12 +244,30 @@ impl App {
             }
         }
 
+        // hibi writes settings.json back as plain JSON.
+        if matches!(self.tab, Tab::Hooks | Tab::Config | Tab::OutputStyles | Tab::Statusline) {
+            let settings = std::fs::read_to_string(self.dest_dir.join("settings.json")).unwrap_or_default();
+            if fs::jsonc::has_extensions(&settings) {
+                self.install_notes.push("[WARN] settings.json: comments and trailing commas are dropped if it is rewritten (the backup keeps them)".to_string());
+            }
+        }
+
         self.processing_total = Some(self.processing_queue.len());
         self.processing_progress = Some(0);
         self.processing_log.clear();
+        self.item_failures.clear();
         self.processing_log.push(format!("Starting installation of {} items...", self.processing_queue.len()));
+        self.processing_log.append(&mut self.install_notes);
         self.is_removing = false;
         self.cancelling = false;
+        self.queue_batch_hooks(BatchStage::Pre);
+        // Component installs snapshot what they overwrite; the directory
+        // only appears on disk if something was actually saved.
+        self.backup_snapshot = self.tab_component_type(self.tab)
+            .map(|_| fs::backup::Snapshot::new(&self.dest_dir));
+        self.capture_journal_baseline();
+        self.apply_resumed_progress();
+        self.save_install_queue();
         self.current_view = View::Installing;
 
         Ok(())
@@ -155,12 +281,44 @@ impl App {
         self.processing_total = Some(self.processing_queue.len());
         self.processing_progress = Some(0);
         self.processing_log.clear();
+        self.item_failures.clear();
         self.processing_log.push(format!("Starting removal of {} items...", self.processing_queue.len()));
         self.is_removing = true;
         self.cancelling = false;
+        self.queue_batch_hooks(BatchStage::Pre);
+        self.capture_journal_baseline();
         self.current_view = View::Installing;
     }
 
+    /// Read the queued servers' `env_file`s into `mcp_env_values`, for
+    /// vars not already exported, and note in the log which file supplied
+    /// which values.
+    fn prefill_env_from_files(&mut self) {
+        self.mcp_env_values.clear();
+        let project = crate::source::config::expand_tilde(std::path::Path::new(&self.mcp_project_path));
+        for &idx in &self.processing_queue {
+            let Some(server) = self.mcp_servers.get(idx) else { continue };
+            let values: Vec<(String, String)> = server.def.env_file_values(&project).into_iter()
+                .filter(|(var, _)| std::env::var(var).is_err())
+                .collect();
+            let Some(file) = server.def.env_file_path(&project).filter(|_| !values.is_empty()) else { continue };
+            let vars: Vec<&str> = values.iter().map(|(var, _)| var.as_str()).collect();
+            crate::fs::audit::record(
+                crate::fs::audit::EventKind::EnvFile,
+                &format!("env for MCP server {}", server.def.name),
+                &format!("{} from {}", vars.join(", "), file.display()),
+                &Ok::<(), String>(()),
+            );
+            self.install_notes.push(format!(
+                "[OK] {}: {} from {}",
+                server.def.name,
+                vars.join(", "),
+                crate::source::config::contract_tilde(&file),
+            ));
+            self.mcp_env_values.insert(idx, values);
+        }
+    }
+
     pub(super) fn start_env_input(&mut self, server_idx: usize, missing_vars: Vec<String>) {
         self.env_input_server_idx = Some(server_idx);
         self.env_input_vars = missing_vars;
@@ -175,40 +333,269 @@ impl App {
         self.processing_total = Some(self.processing_queue.len());
         self.processing_progress = Some(0);
         self.processing_log.clear();
+        self.item_failures.clear();
         self.processing_log.push(format!("Starting installation of {} items...", self.processing_queue.len()));
+        self.processing_log.append(&mut self.install_notes);
         self.is_removing = false;
+        self.queue_batch_hooks(BatchStage::Pre);
+        self.apply_resumed_progress();
+        self.save_install_queue();
         self.current_view = View::Installing;
         Ok(())
     }
 
     pub fn start_finish_processing(&mut self) {
-        let action = if self.is_removing { "Removal" } else { "Installation" };
-        self.processing_log.push(format!("[OK] {} complete!", action));
-        self.processing_log.push("".to_string());  // Empty line for spacing
-        self.processing_log.push("Refreshing status...".to_string());
+        self.summarize_batch();
+        self.clear_install_queue();
+        self.queue_offline = false;
+        if self.batch_aborted {
+            self.rollback_batch();
+        } else {
+            let action = if self.is_removing { "Removal" } else { "Installation" };
+            self.processing_log.push(format!("[OK] {} complete!", action));
+            self.record_journal();
+        }
+        if let Some(snapshot) = self.backup_snapshot.take()
+            && snapshot.dir().exists()
+        {
+            self.processing_log.push(format!(
+                "Previous versions saved to {} (restore with `hibi --restore-backup latest`)",
+                crate::source::config::contract_tilde(snapshot.dir()),
+            ));
+        }
+        self.queue_batch_hooks(BatchStage::Post);
         self.needs_refresh = true;
     }
 
+    /// An install skipped as `[SKIP] already configured`: shown installed
+    /// and deselected, but kept out of `processed_items` so the summary,
+    /// undo journal and rollback leave it alone.
+    pub(crate) fn mark_already_configured(&mut self, idx: usize) {
+        match self.tab {
+            Tab::McpServers => if let Some(server) = self.mcp_servers.get_mut(idx) {
+                server.selected = false;
+                server.status = McpStatus::Installed;
+            },
+            Tab::Plugins => if let Some(plugin) = self.plugins.get_mut(idx) {
+                plugin.selected = false;
+                plugin.status = PluginStatus::Installed;
+            },
+            _ => {}
+        }
+    }
+
+    /// Strict mode: stop the batch at its first failed item. The rest of
+    /// the queue is dropped and `start_finish_processing` rolls back what
+    /// the batch already changed.
+    pub(crate) fn abort_batch_on_error(&mut self) {
+        if !self.strict_mode || self.batch_aborted {
+            return;
+        }
+        self.batch_aborted = true;
+        let skipped = std::mem::take(&mut self.processing_queue).len();
+        self.processing_log.push(format!("[ERR] Strict mode: stopping batch, {} item(s) not run", skipped));
+    }
+
+    /// Drop every item still queued, and any pre-batch hook not yet run,
+    /// logging the items that won't run. The item in flight is left to the
+    /// caller to cancel.
+    pub(crate) fn cancel_remaining_queue(&mut self) {
+        self.batch_hook_queue.retain(|(stage, _)| *stage != BatchStage::Pre);
+        let skipped = std::mem::take(&mut self.processing_queue);
+        if skipped.is_empty() {
+            return;
+        }
+        let names: Vec<String> = skipped.iter()
+            .map(|&idx| self.processed_item_name(idx).unwrap_or_else(|| format!("item {}", idx + 1)))
+            .collect();
+        self.processing_log.push(format!("[WARN] Cancelled {} remaining item(s): {}", names.len(), names.join(", ")));
+    }
+
+    /// Queue the configured commands for `stage`; the Installing view runs
+    /// them one at a time ahead of any remaining items.
+    pub(crate) fn queue_batch_hooks(&mut self, stage: BatchStage) {
+        let commands = match stage {
+            BatchStage::Pre => &self.batch_hooks.pre,
+            BatchStage::Post => &self.batch_hooks.post,
+        };
+        self.batch_hook_queue = commands.iter().map(|c| (stage, c.clone())).collect();
+    }
+
+    /// Record a finished batch hook. A failed or cancelled pre-batch hook
+    /// stops the batch before any item runs; post-batch failures are
+    /// only logged.
+    pub(crate) fn finish_batch_hook(&mut self, stage: BatchStage, result: Result<String>) {
+        let err = match result {
+            Ok(msg) => {
+                self.processing_log.push(msg);
+                // Every item was cancelled while the pre hooks ran.
+                if stage == BatchStage::Pre && self.processing_queue.is_empty() && self.batch_hook_queue.is_empty() {
+                    self.backup_snapshot = None;
+                    self.processing_log.push("[WARN] Batch cancelled".to_string());
+                    self.processing_complete = true;
+                }
+                return;
+            }
+            Err(e) => e.to_string(),
+        };
+        let prefix = if err.contains("Cancelled by user") { "[WARN]" } else { "[ERR]" };
+        self.processing_log.push(format!("{} {}", prefix, err));
+        if stage == BatchStage::Pre {
+            self.processing_queue.clear();
+            self.batch_hook_queue.clear();
+            self.backup_snapshot = None;
+            self.processing_log.push(format!("{} Batch skipped: {} hook did not succeed", prefix, stage.label()));
+            self.processing_complete = true;
+        }
+    }
+
+    /// Re-evaluate only the items the finished batch touched, plus the
+    /// settings-derived state, instead of rescanning every source. MCP and
+    /// plugin statuses follow from which items succeeded, so no `mcp list`
+    /// shell-out is needed. Returns `false` when a component's status
+    /// could not be re-read; the caller then falls back to a full scan.
+    pub fn apply_targeted_refresh(&mut self) -> bool {
+        let processed = std::mem::take(&mut self.processed_items);
+        match self.tab {
+            Tab::McpServers => {
+                let status = if self.is_removing { McpStatus::NotInstalled } else { McpStatus::Installed };
+                let mut servers = self.mcp_servers.clone();
+                for (idx, ok) in processed {
+                    if let Some(server) = servers.get_mut(idx) {
+                        server.selected = false;
+                        if ok {
+                            server.status = status.clone();
+                        }
+                    }
+                }
+                self.apply_mcp_refresh(servers);
+            }
+            Tab::Plugins => {
+                let status = if self.is_removing { PluginStatus::NotInstalled } else { PluginStatus::Installed };
+                let mut plugins = self.plugins.clone();
+                for (idx, ok) in processed {
+                    if let Some(plugin) = plugins.get_mut(idx) {
+                        plugin.selected = false;
+                        if ok {
+                            plugin.status = status.clone();
+                        }
+                    }
+                }
+                self.apply_plugins_refresh(plugins);
+            }
+            _ => {
+                let mut statuses = Vec::new();
+                for (idx, _) in processed {
+                    let Some(component) = self.components.get(idx) else { continue };
+                    match fs::scanner::component_status(component) {
+                        Ok(status) => statuses.push((idx, status)),
+                        Err(_) => return false,
+                    }
+                }
+
+                let mut components = std::mem::take(&mut self.components);
+                let mut gone = Vec::new();
+                for (idx, status) in statuses {
+                    match status {
+                        Some(status) => {
+                            let component = &mut components[idx];
+                            component.selected = !matches!(status, InstallStatus::Unchanged | InstallStatus::External | InstallStatus::Orphaned);
+                            component.status = status;
+                        }
+                        None => gone.push(idx),
+                    }
+                }
+                gone.sort_unstable();
+                for idx in gone.into_iter().rev() {
+                    components.remove(idx);
+                }
+
+                self.reload_current_settings();
+                self.apply_components_refresh(components);
+            }
+        }
+        true
+    }
+
+    /// End a batch without refreshing statuses (`auto_refresh: false`);
+    /// the list keeps the old ones until `R`.
+    pub fn skip_refresh(&mut self) {
+        self.processed_items.clear();
+        self.processing_log.push("Status refresh skipped (auto_refresh: false); press R to rescan".to_string());
+        self.finish_refresh_status();
+    }
+
+    /// End a batch now and refresh its items' statuses `secs` seconds
+    /// later, from the list, so the refresh doesn't compete for the disk
+    /// right after the batch.
+    pub fn defer_refresh(&mut self, secs: u64) {
+        self.deferred_refresh = Some(DeferredRefresh {
+            due: Instant::now() + Duration::from_secs(secs),
+            tab: self.tab,
+            removing: self.is_removing,
+            processed: std::mem::take(&mut self.processed_items),
+        });
+        self.processing_log.push(format!("Status refresh in {}s (refresh_delay_secs)", secs));
+        self.finish_refresh_status();
+    }
+
+    /// Run the deferred refresh once it is due and the list is showing,
+    /// or right away with `now`. Called every loop tick.
+    pub fn run_deferred_refresh(&mut self, now: bool) {
+        let Some(deferred) = &self.deferred_refresh else { return };
+        if !now && (deferred.due > Instant::now() || self.current_view != View::List) {
+            return;
+        }
+        let Some(deferred) = self.deferred_refresh.take() else { return };
+
+        // apply_targeted_refresh works on the batch's tab and direction.
+        let (tab, removing) = (self.tab, self.is_removing);
+        self.tab = deferred.tab;
+        self.is_removing = deferred.removing;
+        self.processed_items = deferred.processed;
+        let refreshed = self.apply_targeted_refresh();
+        self.tab = tab;
+        self.is_removing = removing;
+        self.processing_log.clear();
+        self.processing_complete = false;
+
+        self.status_message = Some(if refreshed {
+            "Statuses refreshed after the last batch".to_string()
+        } else {
+            "Couldn't refresh statuses after the last batch; press R to rescan".to_string()
+        });
+        if !self.pending_marketplace_removals.is_empty() && self.current_view == View::List {
+            self.current_view = View::MarketplaceConfirmRemove;
+        }
+    }
+
     /// Swap only the component list (and rebuild its tree views), then
     /// mark the refresh complete. Used when the just-finished install /
     /// remove targeted filesystem-backed components — MCP and plugin
     /// data are untouched, so we skip both.
     pub fn apply_components_refresh(&mut self, components: Vec<Component>) {
         self.components = components;
-        self.tree_views = build_tree_views(&self.components);
+        self.tree_views = build_tree_views(&self.components, &self.directories, self.show_all_hosts);
+        self.sort_trees();
+        self.hook_positions = crate::fs::installer::read_hook_positions(&self.dest_dir);
         self.finish_refresh_status();
     }
 
     /// Swap only the MCP server list. Tree views and other tabs stay as
     /// they were because this scope is reached only from the MCP tab.
     pub fn apply_mcp_refresh(&mut self, mcp_servers: Vec<McpServer>) {
-        self.mcp_servers = mcp_servers;
+        self.replace_mcp_servers(mcp_servers);
         self.finish_refresh_status();
     }
 
-    /// Swap only the plugin list.
+    /// Swap only the plugin list. After a removal, remember any
+    /// marketplace that no longer backs an installed plugin so closing the
+    /// progress view can offer to unregister it.
     pub fn apply_plugins_refresh(&mut self, plugins: Vec<Plugin>) {
-        self.plugins = plugins;
+        if self.is_removing {
+            self.pending_marketplace_removals = unused_marketplaces(&self.plugins, &plugins);
+        }
+        self.replace_plugins(plugins);
         self.finish_refresh_status();
     }
 
@@ -216,8 +603,12 @@ impl App {
     /// apply method calls this last so the user-visible status line and
     /// internal flags stay consistent across scopes.
     fn finish_refresh_status(&mut self) {
-        let verb = if self.is_removing { "Removed" } else { "Installed" };
-        self.status_message = Some(format!("{} {} items", verb, self.processing_total.unwrap_or(0)));
+        self.status_message = Some(if self.batch_aborted {
+            "Batch rolled back after an error (strict mode)".to_string()
+        } else {
+            let verb = if self.is_removing { "Removed" } else { "Installed" };
+            format!("{} {} items", verb, self.processing_total.unwrap_or(0))
+        });
         self.processing_log.push("[OK] Status refresh complete!".to_string());
         self.needs_refresh = false;
         self.refreshing = false;
@@ -225,26 +616,88 @@ impl App {
     }
 
     pub fn close_processing(&mut self) {
-        self.current_view = View::List;
+        self.summarize_batch_warnings();
+        self.current_view = if self.pending_marketplace_removals.is_empty() {
+            View::List
+        } else {
+            View::MarketplaceConfirmRemove
+        };
         self.processing_queue.clear();
+        self.batch_hook_queue.clear();
+        self.clear_install_queue();
+        self.resumed_progress = None;
+        self.journal_baseline = None;
+        self.processed_items.clear();
+        self.mcp_env_values.clear();
         self.processing_progress = None;
         self.processing_total = None;
         self.processing_log.clear();
+        self.item_failures.clear();
+        self.failure_detail = None;
         self.is_removing = false;
+        self.batch_aborted = false;
         self.needs_refresh = false;
         self.refreshing = false;
         self.processing_complete = false;
     }
 
-    pub fn tick(&mut self) {
-        // Update animation frame for spinner
-        self.animation_frame = (self.animation_frame + 1) % 10;
+    /// `y` runs `claude plugin marketplace remove` for every pending
+    /// marketplace; `n`/`Esc` keeps them registered.
+    pub fn handle_marketplace_confirm_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('y') => {
+                let pending = std::mem::take(&mut self.pending_marketplace_removals);
+                let failures: Vec<String> = pending.iter()
+                    .filter_map(|m| fs::installer::remove_marketplace(m).err())
+                    .map(|e| e.to_string())
+                    .collect();
+                self.status_message = Some(if failures.is_empty() {
+                    format!("Removed marketplace(s): {}", pending.join(", "))
+                } else {
+                    failures.join("; ")
+                });
+                self.current_view = View::List;
+            }
+            KeyCode::Esc | KeyCode::Char('n') => {
+                self.pending_marketplace_removals.clear();
+                self.current_view = View::List;
+            }
+            _ => {}
+        }
+    }
+
+    /// `y` deletes the stale pack files, `n` keeps them; both continue the
+    /// install. `Esc` aborts the install.
+    pub fn handle_stale_files_confirm_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Char('y') => {
+                let stale = std::mem::take(&mut self.stale_pack_files);
+                let removed = fs::installer::remove_stale_pack_files(&self.dest_dir, &stale)?;
+                self.complete_install_setup()?;
+                self.processing_log.push(format!("[OK] Removed {} stale file(s)", removed));
+            }
+            KeyCode::Char('n') => {
+                self.stale_pack_files.clear();
+                self.complete_install_setup()?;
+            }
+            KeyCode::Esc => {
+                self.stale_pack_files.clear();
+                self.install_notes.clear();
+                self.processing_queue.clear();
+                self.status_message = Some("Install cancelled".to_string());
+                self.current_view = View::List;
+            }
+            _ => {}
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
 
     #[test]
     fn preflight_runs_for_plugin_and_mcp_tabs() {
@@ -261,4 +714,166 @@ mod tests {
         assert!(!needs_cli_preflight(Tab::Hooks));
         assert!(!needs_cli_preflight(Tab::Skills));
     }
+
+    #[test]
+    fn env_file_values_skip_the_prompt_and_are_logged() {
+        use crate::app::View;
+        use crate::mcp::{McpServer, McpServerDef, McpStatus};
+
+        let project = temp_dir("env_file_app");
+        std::fs::write(project.join(".env.mcp"), "HIBI_TEST_FILE_TOKEN=from-file\n").unwrap();
+        let mut app = App::for_test();
+        app.tab = Tab::McpServers;
+        app.mcp_project_path = project.to_string_lossy().to_string();
+        app.mcp_servers = vec![McpServer::new(McpServerDef {
+            name: "keyed".into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env: vec!["HIBI_TEST_FILE_TOKEN".into(), "HIBI_TEST_UNSET_TOKEN".into()],
+            version: None, env_file: Some(".env.mcp".into()),
+        }, McpStatus::NotInstalled)];
+        app.processing_queue = vec![0];
+        app.complete_install_setup().unwrap();
+        let _ = std::fs::remove_dir_all(&project);
+
+        assert!(app.current_view == View::EnvInput);
+        assert_eq!(app.env_input_vars, ["HIBI_TEST_UNSET_TOKEN"]);
+        app.env_input_buffer = "typed".into();
+        app.env_input_submit().unwrap();
+        assert!(app.current_view == View::Installing);
+        let Some(crate::process_exec::ProcessData::McpServer { env_values, .. }) = crate::process_exec::prepare(&app, 0) else {
+            panic!("expected MCP process data");
+        };
+        assert_eq!(env_values, [
+            ("HIBI_TEST_FILE_TOKEN".to_string(), "from-file".to_string()),
+            ("HIBI_TEST_UNSET_TOKEN".to_string(), "typed".to_string()),
+        ]);
+        assert!(app.processing_log.iter().any(|l| l.starts_with("[OK] keyed: HIBI_TEST_FILE_TOKEN from ") && l.ends_with(".env.mcp")));
+    }
+
+    #[test]
+    fn targeted_refresh_updates_only_succeeded_mcp_servers() {
+        use crate::mcp::{McpServer, McpServerDef, McpStatus};
+
+        let def = |name: &str| McpServerDef {
+            name: name.into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env: Vec::new(), version: None, env_file: None,
+        };
+        let mut app = App::for_test();
+        app.tab = Tab::McpServers;
+        app.mcp_servers = ["ok", "failed", "untouched"].iter()
+            .map(|n| McpServer::new(def(n), McpStatus::NotInstalled))
+            .collect();
+        app.processed_items = vec![(0, true), (1, false)];
+
+        assert!(app.apply_targeted_refresh());
+        let statuses: Vec<McpStatus> = app.mcp_servers.iter().map(|s| s.status.clone()).collect();
+        assert_eq!(statuses, [McpStatus::Installed, McpStatus::NotInstalled, McpStatus::NotInstalled]);
+        assert!(app.processed_items.is_empty());
+        assert!(app.processing_complete);
+    }
+
+    #[test]
+    fn deferred_refresh_runs_once_due_from_the_list() {
+        use crate::app::View;
+        use crate::mcp::{McpServer, McpServerDef, McpStatus};
+
+        let mut app = App::for_test();
+        app.tab = Tab::McpServers;
+        app.mcp_servers = vec![McpServer::new(McpServerDef {
+            name: "ok".into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env: Vec::new(), version: None, env_file: None,
+        }, McpStatus::NotInstalled)];
+        app.processed_items = vec![(0, true)];
+
+        app.defer_refresh(60);
+        assert!(app.processing_complete, "the progress view can close right away");
+        assert!(app.processed_items.is_empty());
+        assert_eq!(app.mcp_servers[0].status, McpStatus::NotInstalled);
+
+        app.close_processing();
+        app.tab = Tab::Agents;
+        app.run_deferred_refresh(false);
+        assert!(app.deferred_refresh.is_some(), "not due yet");
+
+        app.run_deferred_refresh(true);
+        assert!(app.deferred_refresh.is_none());
+        assert_eq!(app.mcp_servers[0].status, McpStatus::Installed);
+        assert_eq!(app.tab, Tab::Agents);
+        assert!(app.current_view == View::List && !app.processing_complete);
+    }
+
+    #[test]
+    fn failed_pre_batch_hook_skips_the_batch() {
+        use crate::app::BatchStage;
+
+        let mut app = App::for_test();
+        app.batch_hooks.pre = vec!["false".into(), "true".into()];
+        app.batch_hooks.post = vec!["claude --version".into()];
+        app.processing_queue = vec![0, 1];
+        app.queue_batch_hooks(BatchStage::Pre);
+        assert_eq!(app.batch_hook_queue.len(), 2);
+
+        app.batch_hook_queue.remove(0);
+        app.finish_batch_hook(BatchStage::Pre, Err(anyhow::anyhow!("Failed to run false")));
+        assert!(app.processing_queue.is_empty());
+        assert!(app.batch_hook_queue.is_empty());
+        assert!(app.processing_complete);
+
+        // Post-batch failures are only logged.
+        let mut app = App::for_test();
+        app.finish_batch_hook(BatchStage::Post, Err(anyhow::anyhow!("Failed to run x")));
+        assert!(!app.processing_complete);
+    }
+
+    #[test]
+    fn strict_mode_stops_the_batch_and_rolls_back_installed_items() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+
+        let dir = temp_dir("strict");
+        let mut app = App::for_test();
+        app.strict_mode = true;
+        app.tab = Tab::Agents;
+        app.dest_dir = dir.clone();
+        app.components = ["a.md", "b.md", "c.md"].iter()
+            .map(|n| Component::new(ComponentType::Agents, (*n).into(), dir.join("src").join(n), dir.join(n), InstallStatus::New))
+            .collect();
+        app.processing_queue = vec![0, 1, 2];
+        app.capture_journal_baseline();
+
+        // `a` installs, `b` fails: `c` never runs and `a` is removed again.
+        app.processing_queue.remove(0);
+        std::fs::write(dir.join("a.md"), "a").unwrap();
+        app.processed_items.push((0, true));
+        app.processing_queue.remove(0);
+        app.processed_items.push((1, false));
+        app.abort_batch_on_error();
+        assert!(app.processing_queue.is_empty());
+        app.start_finish_processing();
+
+        assert!(!dir.join("a.md").exists());
+        assert!(app.processing_log.iter().any(|l| l.starts_with("[WARN] Rolled back.")));
+        assert!(crate::fs::journal::Journal::load(&dir).unwrap().is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cancelling_the_queue_logs_the_items_that_will_not_run() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+
+        let mut app = App::for_test();
+        app.tab = Tab::Agents;
+        app.components = ["a.md", "b.md", "c.md"].iter()
+            .map(|n| Component::new(ComponentType::Agents, (*n).into(), (*n).into(), (*n).into(), InstallStatus::New))
+            .collect();
+        app.processing_queue = vec![1, 2];
+        use crate::app::BatchStage;
+        app.batch_hook_queue = vec![(BatchStage::Pre, "true".into()), (BatchStage::Post, "true".into())];
+        app.cancel_remaining_queue();
+
+        assert!(app.processing_queue.is_empty());
+        assert_eq!(app.batch_hook_queue, vec![(BatchStage::Post, "true".to_string())]);
+        assert_eq!(app.processing_log, vec!["[WARN] Cancelled 2 remaining item(s): b.md, c.md"]);
+    }
 }
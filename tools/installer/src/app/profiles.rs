@@ -0,0 +1,121 @@
+use crate::component::InstallStatus;
+use crate::profile::{entry_matches, Profile};
+
+use super::App;
+
+impl App {
+    /// Profile picked on the CLI selection screen, if any.
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.profile_index.and_then(|i| self.profiles.get(i))
+    }
+
+    /// Re-read `profiles.yaml` after the sources changed, keeping the
+    /// picked profile when it still exists. Returns parse warnings.
+    pub(super) fn reload_profiles(&mut self) -> Vec<String> {
+        let picked = self.active_profile().map(|p| p.name.clone());
+        let (profiles, warnings) = crate::profile::load_profiles(&self.sources);
+        self.profiles = profiles;
+        self.profile_index = picked.and_then(|name| self.profiles.iter().position(|p| p.name == name));
+        warnings
+    }
+
+    /// Step through "no profile" and each profile in turn (`p`).
+    pub fn cycle_profile(&mut self) {
+        self.profile_index = match self.profile_index {
+            None if !self.profiles.is_empty() => Some(0),
+            Some(i) if i + 1 < self.profiles.len() => Some(i + 1),
+            _ => None,
+        };
+    }
+
+    /// Select exactly the loaded items the active profile lists, on every
+    /// tab, leaving out components hidden for other hosts. Returns a
+    /// status line, or `None` without a profile.
+    pub(super) fn apply_profile(&mut self) -> Option<String> {
+        let profile = self.active_profile()?.clone();
+        let mut used_components = vec![false; profile.components.len()];
+        let mut count = 0;
+        let show_all_hosts = self.show_all_hosts;
+
+        let mut select = |patterns: &[String], used: &mut [bool], key: &str| {
+            let mut hit = false;
+            for (i, pattern) in patterns.iter().enumerate() {
+                if entry_matches(pattern, key) {
+                    used[i] = true;
+                    hit = true;
+                }
+            }
+            count += usize::from(hit);
+            hit
+        };
+
+        for c in &mut self.components {
+            let key = format!("{}/{}", c.component_type.display_name(), c.name);
+            // External files have no source to install from, and a batch
+            // must not pick up items the list hides.
+            c.selected = !matches!(c.status, InstallStatus::External | InstallStatus::Orphaned)
+                && (show_all_hosts || c.for_this_host())
+                && select(&profile.components, &mut used_components, &key);
+        }
+        let mut used_mcp = vec![false; profile.mcp_servers.len()];
+        for m in &mut self.mcp_servers {
+            m.selected = select(&profile.mcp_servers, &mut used_mcp, &m.def.name);
+        }
+        let mut used_plugins = vec![false; profile.plugins.len()];
+        for p in &mut self.plugins {
+            p.selected = select(&profile.plugins, &mut used_plugins, &p.def.name);
+        }
+
+        let unmatched: Vec<&str> = [
+            (&profile.components, used_components),
+            (&profile.mcp_servers, used_mcp),
+            (&profile.plugins, used_plugins),
+        ]
+        .iter()
+        .flat_map(|(patterns, used)| patterns.iter().zip(used).filter(|(_, u)| !**u).map(|(p, _)| p.as_str()))
+        .collect();
+
+        let mut report = format!("Profile '{}': selected {} item(s)", profile.name, count);
+        if !unmatched.is_empty() {
+            report.push_str(&format!("; no match for {}", unmatched.join(", ")));
+        }
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::App;
+
+    #[test]
+    fn profile_selects_only_its_items() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+        let component = |comp_type, name: &str, status| {
+            let mut c = Component::new(comp_type, name.into(), name.into(), name.into(), status);
+            c.selected = true;
+            c
+        };
+        let mut app = App::for_test();
+        app.components = vec![
+            component(ComponentType::Agents, "architect.md", InstallStatus::New),
+            component(ComponentType::Agents, "tdd-guide.md", InstallStatus::New),
+            component(ComponentType::Rules, "perf.md", InstallStatus::Unchanged),
+            component(ComponentType::Rules, "mine.md", InstallStatus::External),
+            Component { hosts: vec!["hibi-elsewhere".into()], ..component(ComponentType::Rules, "work.md", InstallStatus::New) },
+        ];
+        app.profiles = vec![crate::profile::Profile {
+            name: "minimal".into(),
+            components: vec!["agents/architect".into(), "rules/*".into(), "skills/gone".into()],
+            ..Default::default()
+        }];
+        app.cycle_profile();
+
+        let report = app.apply_profile().unwrap();
+        let selected: Vec<bool> = app.components.iter().map(|c| c.selected).collect();
+
+        assert_eq!(selected, [true, false, true, false, false]);
+        assert_eq!(report, "Profile 'minimal': selected 2 item(s); no match for skills/gone");
+        app.cycle_profile();
+        assert!(app.active_profile().is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::View;
+use super::{App, build_tree_views};
+use crate::component::InstallStatus;
+use crate::fs;
+
+impl App {
+    /// Orphaned components on the current tab, or on every tab.
+    pub fn orphan_indices(&self, all_tabs: bool) -> Vec<usize> {
+        let comp_type = self.tab_component_type(self.tab);
+        self.components.iter()
+            .enumerate()
+            .filter(|(_, c)| c.status == InstallStatus::Orphaned)
+            .filter(|(_, c)| all_tabs || Some(&c.component_type) == comp_type.as_ref())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// `p` in the list: ask before deleting the orphans. Starts with the
+    /// current tab's, or every tab's when this one has none.
+    pub fn open_prune_confirm(&mut self) {
+        if self.orphan_indices(true).is_empty() {
+            self.status_message = Some("No orphaned files to prune".to_string());
+            return;
+        }
+        self.prune_all_tabs = self.orphan_indices(false).is_empty();
+        self.current_view = View::PruneConfirm;
+    }
+
+    /// `y` deletes, `b` backs up then deletes, `t` switches between this
+    /// tab's orphans and every tab's.
+    pub fn handle_prune_confirm_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Char('t') if self.tab_component_type(self.tab).is_some() => {
+                self.prune_all_tabs = !self.prune_all_tabs;
+            }
+            KeyCode::Char('y') => self.prune_orphans(false)?,
+            KeyCode::Char('b') => self.prune_orphans(true)?,
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => self.current_view = View::List,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Prune the orphans the dialog lists.
+    fn prune_orphans(&mut self, backup: bool) -> Result<()> {
+        self.current_view = View::List;
+        // Statuses from an earlier batch must be current before the
+        // orphans are picked.
+        self.run_deferred_refresh(true);
+        let indices = self.orphan_indices(self.prune_all_tabs);
+        self.status_message = Some(if indices.is_empty() {
+            "No orphaned files to prune".to_string()
+        } else {
+            self.prune_components(&indices, backup)
+        });
+        Ok(())
+    }
+
+    /// Delete the components at `indices` and drop them from the list;
+    /// orphans have no source, so a rescan wouldn't list them either.
+    /// Returns a one-line report. Callers run the deferred refresh before
+    /// taking `indices`, since it replaces the list.
+    pub(super) fn prune_components(&mut self, indices: &[usize], backup: bool) -> String {
+        let snapshot = backup.then(|| fs::backup::Snapshot::new(&self.dest_dir));
+
+        let mut pruned = Vec::new();
+        let mut failed = Vec::new();
+        for &idx in indices {
+            let component = &self.components[idx];
+            let result = match &snapshot {
+                Some(snapshot) => snapshot.save(&component.dest_path).map(|_| ()),
+                None => Ok(()),
+            }
+            .and_then(|_| fs::installer::remove_component(component, &self.dest_dir));
+            match result {
+                Ok(()) => pruned.push(idx),
+                Err(e) => failed.push(format!("[ERR] Prune {}: {:#}", component.name, e)),
+            }
+        }
+
+        let names: Vec<String> = pruned.iter().map(|&i| self.components[i].display_name()).collect();
+        self.components = std::mem::take(&mut self.components).into_iter()
+            .enumerate()
+            .filter(|(i, _)| !pruned.contains(i))
+            .map(|(_, c)| c)
+            .collect();
+        self.tree_views = build_tree_views(&self.components, &self.directories, self.show_all_hosts);
+        self.sort_trees();
+
+        if !names.is_empty() {
+            self.session_summary.changes.push(format!("Pruned orphans: {}", names.join(", ")));
+        }
+        let mut message = format!("Pruned {} orphaned file(s)", names.len());
+        if let Some(snapshot) = snapshot.filter(|s| s.dir().exists()) {
+            message.push_str(&format!(", backed up to {}", crate::source::config::contract_tilde(snapshot.dir())));
+        }
+        if !failed.is_empty() {
+            message.push_str(&format!("; {} failed", failed.len()));
+            self.session_summary.warnings.extend(failed);
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn prune_deletes_orphans_of_the_tab_or_of_every_tab() {
+        use crate::app::View;
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crossterm::event::KeyCode;
+
+        let dir = temp_dir("prune");
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+        std::fs::create_dir_all(dir.join("rules")).unwrap();
+        let installed = |comp_type, rel: &str, status| {
+            let dest = dir.join(rel);
+            std::fs::write(&dest, "x").unwrap();
+            Component::new(comp_type, rel.rsplit('/').next().unwrap().into(), "".into(), dest, status)
+        };
+
+        let mut app = App::for_test();
+        app.dest_dir = dir.clone();
+        app.tab = Tab::Agents;
+        app.components = vec![
+            installed(ComponentType::Agents, "agents/old.md", InstallStatus::Orphaned),
+            installed(ComponentType::Agents, "agents/mine.md", InstallStatus::External),
+            installed(ComponentType::Rules, "rules/old.md", InstallStatus::Orphaned),
+        ];
+
+        app.open_prune_confirm();
+        assert!(app.current_view == View::PruneConfirm);
+        assert!(!app.prune_all_tabs);
+        app.handle_prune_confirm_key(KeyCode::Char('b')).unwrap();
+        let agent_gone = !dir.join("agents/old.md").exists();
+        let backups = crate::fs::backup::list_snapshots(&dir).unwrap().len();
+        let names: Vec<String> = app.components.iter().map(|c| c.name.clone()).collect();
+
+        app.open_prune_confirm();
+        let all_tabs_once_the_tab_is_clean = app.prune_all_tabs;
+        app.handle_prune_confirm_key(KeyCode::Char('y')).unwrap();
+        let rule_gone = !dir.join("rules/old.md").exists();
+        let external_kept = dir.join("agents/mine.md").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(agent_gone && rule_gone && external_kept);
+        assert!(all_tabs_once_the_tab_is_clean);
+        assert_eq!(backups, 1);
+        assert_eq!(names, ["mine.md", "old.md"]);
+        assert_eq!(app.components.len(), 1);
+        assert!(app.current_view == View::List);
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use crate::fs::drift::{self, DriftFormat};
+use crate::fs::hooks_script;
+use crate::fs::report::{default_file_name, is_pending, render_report, ReportFormat};
+
+use super::App;
+
+impl App {
+    /// Write a Markdown report of every pending (new or modified)
+    /// component to the working directory (`E`). When any pending component
+    /// is selected, only the selected ones are included.
+    pub fn export_report(&mut self) -> Result<()> {
+        let pending: Vec<_> = self.components.iter().filter(|c| is_pending(c)).collect();
+        let selected: Vec<_> = pending.iter().copied().filter(|c| c.selected).collect();
+        let included = if selected.is_empty() { pending } else { selected };
+        if included.is_empty() {
+            self.status_message = Some("No new or modified components to report".to_string());
+            return Ok(());
+        }
+
+        let format = ReportFormat::Markdown;
+        let path = std::env::current_dir()?.join(default_file_name(format));
+        std::fs::write(&path, render_report(&included, format))?;
+        self.status_message = Some(format!("Wrote report of {} change(s) to {}", included.len(), path.display()));
+        Ok(())
+    }
+
+    /// Write a JSON drift report of every component of the current CLI to
+    /// the working directory (`D`).
+    pub fn export_drift_report(&mut self) -> Result<()> {
+        let target = self.target_cli.unwrap_or(super::TargetCli::Claude);
+        let report = drift::drift_report(&self.components, target.display_name(), &self.dest_dir);
+        let format = DriftFormat::Json;
+        let path = std::env::current_dir()?.join(drift::default_file_name(format));
+        std::fs::write(&path, drift::render_drift_report(&report, format))?;
+        self.status_message = Some(format!("Wrote drift report of {} component(s) to {}", report.components.len(), path.display()));
+        Ok(())
+    }
+
+    /// Write the hooks registered in settings.json as a shell script to the
+    /// working directory (`x` on the Hooks tab).
+    pub fn export_hooks_script(&mut self) -> Result<()> {
+        let configs: Vec<_> = self.components.iter().filter_map(|c| c.hook_config.clone()).collect();
+        let path = std::env::current_dir()?.join(hooks_script::default_file_name());
+        std::fs::write(&path, hooks_script::hooks_script(&self.dest_dir, &configs)?)?;
+        self.status_message = Some(format!("Wrote hooks script to {}", path.display()));
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Resuming an install batch that never finished (see `fs::interrupted`).
+//! The queue is saved as the batch starts and after every item; when hibi
+//! starts and finds one left behind, it offers to select the remaining
+//! items again and pick up where the batch stopped.
+
+use std::path::Path;
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::App;
+use crate::fs::interrupted::{self, InterruptedQueue};
+
+impl App {
+    /// Where the queue is kept; `dest_dir` is only set once a CLI is picked.
+    fn queue_dest_dir(&self) -> Option<&Path> {
+        self.target_cli.map(|_| self.dest_dir.as_path())
+    }
+
+    /// Record what is left of the running install batch. Removals aren't
+    /// resumed: running one again after a crash is rarely what was meant.
+    pub(crate) fn save_install_queue(&self) {
+        let Some(dest_dir) = self.queue_dest_dir().filter(|_| !self.is_removing) else { return };
+        let queue = InterruptedQueue {
+            tab: self.tab_label(self.tab),
+            items: self.processing_queue.iter().map(|&idx| crate::process_exec::get_item_name(self, idx)).collect(),
+            done: self.processing_progress.unwrap_or(0),
+            total: self.processing_total.unwrap_or(0),
+        };
+        // Best effort: failing to save only loses the resume offer.
+        let _ = interrupted::save(dest_dir, &queue);
+    }
+
+    /// The batch ran to the end (or was cancelled); nothing to resume.
+    pub(crate) fn clear_install_queue(&self) {
+        if let Some(dest_dir) = self.queue_dest_dir() {
+            let _ = interrupted::clear(dest_dir);
+        }
+    }
+
+    /// Offer to resume a batch the last run left unfinished, once the
+    /// CLI's items have loaded.
+    pub(super) fn offer_resume(&mut self) {
+        self.interrupted_queue = interrupted::load(&self.dest_dir).ok().flatten();
+        if self.interrupted_queue.is_some() && self.current_view == View::List {
+            self.current_view = View::ResumeQueue;
+        }
+    }
+
+    /// `Enter` resumes the batch, `d` discards it, `Esc` asks again at
+    /// the next start.
+    pub fn handle_resume_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Enter => return self.resume_install_queue(),
+            KeyCode::Char('d') => {
+                self.interrupted_queue = None;
+                interrupted::clear(&self.dest_dir)?;
+                self.status_message = Some("Discarded the interrupted installation".to_string());
+                self.current_view = View::List;
+            }
+            KeyCode::Esc => self.current_view = View::List,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Select the items the interrupted batch had left on its tab and
+    /// install them, with the progress counting on from where it stopped.
+    fn resume_install_queue(&mut self) -> Result<()> {
+        self.current_view = View::List;
+        let Some(queue) = self.interrupted_queue.take() else { return Ok(()) };
+        let tab = self.available_tabs.iter()
+            .copied()
+            .find(|&tab| self.tab_label(tab).eq_ignore_ascii_case(&queue.tab));
+        let Some(tab) = tab else {
+            interrupted::clear(&self.dest_dir)?;
+            self.status_message = Some(format!("The {} tab is gone; discarded the interrupted installation", queue.tab));
+            return Ok(());
+        };
+        self.show_tab(tab);
+        self.deselect_all();
+        let (indices, missing) = self.select_by_name(&queue.items);
+        if indices.is_empty() {
+            interrupted::clear(&self.dest_dir)?;
+            self.status_message = Some("None of the interrupted items are in the sources any more".to_string());
+            return Ok(());
+        }
+
+        self.install_notes.push(format!(
+            "[OK] Resuming an interrupted installation: {} of {} item(s) were done",
+            queue.done, queue.total,
+        ));
+        if !missing.is_empty() {
+            self.install_notes.push(format!("[WARN] No longer in the sources: {}", missing.join(", ")));
+        }
+        self.migration_followup.clear();
+        self.run_deferred_refresh(true);
+        self.queue_offline = false;
+        self.start_install_from(indices, Some(queue.done))
+    }
+
+    /// Select the current tab's items named in `names`. Returns their
+    /// indices and the names that matched nothing.
+    fn select_by_name(&mut self, names: &[String]) -> (Vec<usize>, Vec<String>) {
+        let comp_type = self.tab_component_type(self.tab);
+        let mut found = Vec::new();
+        if self.tab == Tab::McpServers {
+            for (i, m) in self.mcp_servers.iter_mut().enumerate().filter(|(_, m)| names.contains(&m.def.name)) {
+                m.selected = true;
+                found.push((i, m.def.name.clone()));
+            }
+        } else if self.tab == Tab::Plugins {
+            for (i, p) in self.plugins.iter_mut().enumerate().filter(|(_, p)| names.contains(&p.def.name)) {
+                p.selected = true;
+                found.push((i, p.def.name.clone()));
+            }
+        } else if let Some(comp_type) = comp_type {
+            let matching = self.components.iter_mut()
+                .enumerate()
+                .filter(|(_, c)| c.component_type == comp_type && c.is_install_eligible() && names.contains(&c.name));
+            for (i, c) in matching {
+                c.selected = true;
+                found.push((i, c.name.clone()));
+            }
+        }
+        let missing = names.iter().filter(|name| !found.iter().any(|(_, n)| n == *name)).cloned().collect();
+        (found.into_iter().map(|(i, _)| i).collect(), missing)
+    }
+
+    /// Start the progress count of a resumed batch where the interrupted
+    /// one stopped. Called as the batch starts.
+    pub(super) fn apply_resumed_progress(&mut self) {
+        if let Some(done) = self.resumed_progress.take() {
+            self.processing_progress = Some(done);
+            self.processing_total = Some(done + self.processing_queue.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hibi_core::test_util::temp_dir;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn interrupted_batch_resumes_with_its_progress() {
+        use crate::app::View;
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crate::fs::interrupted::{self, InterruptedQueue};
+        use crossterm::event::KeyCode;
+
+        let dest = temp_dir("resume");
+        let component = |name: &str| Component::new(ComponentType::Agents, name.into(), name.into(), dest.join(name), InstallStatus::New);
+        let mut app = App::for_test();
+        app.target_cli = Some(crate::app::TargetCli::Claude);
+        app.dest_dir = dest.clone();
+        app.tab = Tab::Rules;
+        app.available_tabs = vec![Tab::Agents, Tab::Rules];
+        app.components = vec![component("a.md"), component("b.md"), component("c.md")];
+        interrupted::save(&dest, &InterruptedQueue {
+            tab: "Agents".into(),
+            items: vec!["b.md".into(), "gone.md".into(), "c.md".into()],
+            done: 2,
+            total: 5,
+        }).unwrap();
+
+        app.offer_resume();
+        assert!(app.current_view == View::ResumeQueue);
+        app.handle_resume_key(KeyCode::Enter).unwrap();
+        let saved = interrupted::load(&dest).unwrap();
+        app.processing_queue.remove(0);
+        app.processing_progress = Some(3);
+        app.save_install_queue();
+        let after_one = interrupted::load(&dest).unwrap();
+        app.processing_queue.clear();
+        app.start_finish_processing();
+        let finished = interrupted::load(&dest).unwrap();
+        let _ = std::fs::remove_dir_all(&dest);
+
+        assert_eq!(app.tab, Tab::Agents);
+        let selected: Vec<bool> = app.components.iter().map(|c| c.selected).collect();
+        assert_eq!(selected, [false, true, true]);
+        assert!(app.processing_log.iter().any(|l| l == "[WARN] No longer in the sources: gone.md"));
+        assert_eq!(app.processing_total, Some(4));
+        assert_eq!(saved.map(|q| (q.items, q.done)), Some((vec!["b.md".into(), "c.md".into()], 2)));
+        assert_eq!(after_one.map(|q| (q.items, q.done)), Some((vec!["c.md".into()], 3)));
+        assert_eq!(finished, None);
+    }
+}
@@ -0,0 +1,148 @@
+use crossterm::event::KeyCode;
+
+use super::types::{FocusArea, Tab, View};
+use super::App;
+
+/// What a search result points at, by index into the owning list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchTarget {
+    Component(usize),
+    Mcp(usize),
+    Plugin(usize),
+}
+
+/// One row in the Ctrl+F picker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub tab: Tab,
+    pub target: SearchTarget,
+    pub label: String,
+}
+
+/// Case-insensitive substring match; an empty query matches nothing so the
+/// picker starts blank instead of listing every item.
+fn matches(query: &str, haystack: &str) -> bool {
+    !query.is_empty() && haystack.to_lowercase().contains(query)
+}
+
+impl App {
+    pub fn open_search(&mut self) {
+        self.search_query.clear();
+        self.search_results.clear();
+        self.search_index = 0;
+        self.current_view = View::Search;
+    }
+
+    /// Match the query against component names, MCP server names and
+    /// descriptions, and plugin names on every tab available to the CLI.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let query = query.to_lowercase();
+        let mut hits = Vec::new();
+
+        for &tab in &self.available_tabs {
+            match tab {
+                Tab::McpServers => {
+                    for (i, m) in self.mcp_servers.iter().enumerate() {
+                        if matches(&query, &m.def.name) || matches(&query, &m.def.description) {
+                            hits.push(SearchHit { tab, target: SearchTarget::Mcp(i), label: m.def.name.clone() });
+                        }
+                    }
+                }
+                Tab::Plugins => {
+                    for (i, p) in self.plugins.iter().enumerate() {
+                        if matches(&query, &p.def.name) {
+                            hits.push(SearchHit { tab, target: SearchTarget::Plugin(i), label: p.def.name.clone() });
+                        }
+                    }
+                }
+                _ => {
+                    let Some(comp_type) = self.tab_component_type(tab) else { continue };
+                    for (i, c) in self.components.iter().enumerate() {
+                        if c.component_type == comp_type && matches(&query, &c.name) {
+                            hits.push(SearchHit { tab, target: SearchTarget::Component(i), label: c.name.clone() });
+                        }
+                    }
+                }
+            }
+        }
+        hits
+    }
+
+    pub fn handle_search_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => self.current_view = View::List,
+            KeyCode::Enter => self.jump_to_search_hit(),
+            KeyCode::Down if self.search_index + 1 < self.search_results.len() => {
+                self.search_index += 1;
+            }
+            KeyCode::Up => self.search_index = self.search_index.saturating_sub(1),
+            KeyCode::Backspace => {
+                self.search_query.pop();
+                self.refresh_search();
+            }
+            KeyCode::Char(c) => {
+                self.search_query.push(c);
+                self.refresh_search();
+            }
+            _ => {}
+        }
+    }
+
+    fn refresh_search(&mut self) {
+        self.search_results = self.search(&self.search_query);
+        self.search_index = 0;
+    }
+
+    /// Switch to the hit's tab and put the cursor on it.
+    fn jump_to_search_hit(&mut self) {
+        let Some(hit) = self.search_results.get(self.search_index).cloned() else { return };
+
+        self.tab = hit.tab;
+        self.focus = FocusArea::Content;
+        match hit.target {
+            SearchTarget::Mcp(i) => self.mcp_index = i,
+            SearchTarget::Plugin(i) => self.plugin_index = i,
+            SearchTarget::Component(i) => {
+                if let Some(tree) = self.tree_views.get_mut(&hit.tab) {
+                    tree.reveal_component(i);
+                }
+            }
+        }
+        self.status_message = Some(format!("{}: {}", self.tab_label(hit.tab), hit.label));
+        self.current_view = View::List;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{App, Tab, FocusArea};
+
+    #[test]
+    fn search_jump_switches_tab_and_reveals_item() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+        use crossterm::event::KeyCode;
+
+        let mut app = App::for_test();
+        app.available_tabs = vec![Tab::Agents, Tab::Skills];
+        app.tab = Tab::Agents;
+        app.focus = FocusArea::Tabs;
+        app.components = vec![
+            Component::new(ComponentType::Agents, "reviewer.md".into(),
+                "reviewer.md".into(), "reviewer.md".into(), InstallStatus::New),
+            Component::new(ComponentType::Skills, "docs/Review-Guide.md".into(),
+                "g.md".into(), "g.md".into(), InstallStatus::New),
+        ];
+        app.tree_views = crate::app::build_tree_views(&app.components, &app.directories, app.show_all_hosts);
+
+        app.open_search();
+        for c in "guide".chars() {
+            app.handle_search_key(KeyCode::Char(c));
+        }
+        assert_eq!(app.search_results.len(), 1);
+
+        app.handle_search_key(KeyCode::Enter);
+        assert_eq!(app.tab, Tab::Skills);
+        assert_eq!(app.focus, FocusArea::Content);
+        assert_eq!(app.selected_component_index(), Some(1));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::component::InstallStatus;
+
 use super::types::Tab;
 use super::App;
 
@@ -54,15 +56,29 @@ impl App {
             for p in &mut self.plugins {
                 p.selected = true;
             }
-        } else if let Some(comp_type) = self.tab.to_component_type() {
+        } else if let Some(comp_type) = self.tab_component_type(self.tab) {
+            let all_hosts = self.show_all_hosts;
             for c in &mut self.components {
-                if c.component_type == comp_type {
+                if c.component_type == comp_type && (all_hosts || c.for_this_host()) {
                     c.selected = true;
                 }
             }
         }
     }
 
+    /// Select exactly the current tab's components with `status` (`N` for
+    /// new, `M` for modified), so only what changed gets installed.
+    pub fn select_by_status(&mut self, status: InstallStatus) {
+        let Some(comp_type) = self.tab_component_type(self.tab) else { return };
+        let mut count = 0;
+        let all_hosts = self.show_all_hosts;
+        for c in self.components.iter_mut().filter(|c| c.component_type == comp_type) {
+            c.selected = c.status == status && (all_hosts || c.for_this_host());
+            count += usize::from(c.selected);
+        }
+        self.status_message = Some(format!("Selected {} {} item(s)", count, status.display()));
+    }
+
     pub fn deselect_all(&mut self) {
         if self.tab == Tab::McpServers {
             for m in &mut self.mcp_servers {
@@ -72,7 +88,7 @@ impl App {
             for p in &mut self.plugins {
                 p.selected = false;
             }
-        } else if let Some(comp_type) = self.tab.to_component_type() {
+        } else if let Some(comp_type) = self.tab_component_type(self.tab) {
             for c in &mut self.components {
                 if c.component_type == comp_type {
                     c.selected = false;
@@ -81,3 +97,133 @@ impl App {
         }
     }
 }
+
+/// Selected items across every tab, shown in the tab bar so the scope of a
+/// pending batch is visible before pressing install.
+#[derive(Debug, Default, PartialEq)]
+pub struct SelectionSummary {
+    /// Non-zero selection counts by tab label, in tab order.
+    pub per_tab: Vec<(String, usize)>,
+    /// Combined source size of the selected components. MCP servers and
+    /// plugins are fetched by the CLI and don't contribute.
+    pub bytes: u64,
+}
+
+impl SelectionSummary {
+    pub fn total(&self) -> usize {
+        self.per_tab.iter().map(|(_, n)| n).sum()
+    }
+
+    /// e.g. `12 selected (3 MCP, 9 Skills) — est. 2.1 MB`; `None` when
+    /// nothing is selected.
+    pub fn label(&self) -> Option<String> {
+        if self.per_tab.is_empty() {
+            return None;
+        }
+        let parts: Vec<String> = self.per_tab.iter()
+            .map(|(tab, n)| format!("{} {}", n, tab))
+            .collect();
+        let mut label = format!("{} selected ({})", self.total(), parts.join(", "));
+        if self.bytes > 0 {
+            label.push_str(&format!(" — est. {}", format_size(self.bytes)));
+        }
+        Some(label)
+    }
+}
+
+/// Human-readable byte count with one decimal above 1 KB.
+pub(crate) fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    let b = bytes as f64;
+    if b >= MB {
+        format!("{:.1} MB", b / MB)
+    } else if b >= KB {
+        format!("{:.1} KB", b / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+impl App {
+    /// Count selections on every tab available to the current CLI.
+    pub fn selection_summary(&self) -> SelectionSummary {
+        let mut summary = SelectionSummary::default();
+        for &tab in &self.available_tabs {
+            let count = match tab {
+                Tab::McpServers => self.mcp_servers.iter().filter(|m| m.selected).count(),
+                Tab::Plugins => self.plugins.iter().filter(|p| p.selected).count(),
+                _ => {
+                    let Some(comp_type) = self.tab_component_type(tab) else { continue };
+                    let selected = self.components.iter()
+                        .filter(|c| c.selected && c.component_type == comp_type);
+                    let mut count = 0;
+                    for c in selected {
+                        summary.bytes += c.size_bytes;
+                        count += 1;
+                    }
+                    count
+                }
+            };
+            if count > 0 {
+                summary.per_tab.push((self.tab_label(tab), count));
+            }
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn summary_label_lists_tabs_and_size() {
+        let summary = SelectionSummary {
+            per_tab: vec![("MCP".into(), 3), ("Skills".into(), 9)],
+            bytes: 2_202_010,
+        };
+        assert_eq!(summary.total(), 12);
+        assert_eq!(
+            summary.label().as_deref(),
+            Some("12 selected (3 MCP, 9 Skills) — est. 2.1 MB"),
+        );
+    }
+
+    #[test]
+    fn summary_label_empty_and_size_free() {
+        assert_eq!(SelectionSummary::default().label(), None);
+
+        let plugins_only = SelectionSummary { per_tab: vec![("Plugins".into(), 2)], bytes: 0 };
+        assert_eq!(plugins_only.label().as_deref(), Some("2 selected (2 Plugins)"));
+    }
+
+    #[test]
+    fn format_size_scales_units() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn select_by_status_picks_only_matching_components_on_the_tab() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+
+        let mut app = App::for_test();
+        app.tab = Tab::Agents;
+        app.components = [
+            (ComponentType::Agents, InstallStatus::New),
+            (ComponentType::Agents, InstallStatus::Modified),
+            (ComponentType::Agents, InstallStatus::Unchanged),
+            (ComponentType::Rules, InstallStatus::Modified),
+        ].into_iter()
+            .map(|(t, status)| Component::new(t, "x.md".into(), "x.md".into(), "x.md".into(), status))
+            .collect();
+
+        app.select_by_status(InstallStatus::Modified);
+        let selected: Vec<bool> = app.components.iter().map(|c| c.selected).collect();
+        assert_eq!(selected, [false, true, false, true], "other tabs keep their selection");
+        assert_eq!(app.status_message.as_deref(), Some("Selected 1 modified item(s)"));
+    }
+}
@@ -6,7 +6,7 @@ use super::types::{Tab, View};
 use super::App;
 use crate::fs;
 
-pub(super) fn read_current_settings(dest_dir: &Path) -> (Option<String>, Option<String>) {
+fn read_current_settings(dest_dir: &Path) -> (Option<String>, Option<String>) {
     use serde_json::Value;
 
     let settings_path = dest_dir.join("settings.json");
@@ -19,7 +19,7 @@ pub(super) fn read_current_settings(dest_dir: &Path) -> (Option<String>, Option<
         Err(_) => return (None, None),
     };
 
-    let settings: Value = match serde_json::from_str(&content) {
+    let settings: Value = match fs::jsonc::parse(&content) {
         Ok(s) => s,
         Err(_) => return (None, None),
     };
@@ -39,6 +39,15 @@ pub(super) fn read_current_settings(dest_dir: &Path) -> (Option<String>, Option<
 }
 
 impl App {
+    /// Re-read the default style and statusline, and which of them point
+    /// at missing files.
+    pub(super) fn reload_current_settings(&mut self) {
+        let (output_style, statusline) = read_current_settings(&self.dest_dir);
+        self.current_output_style = output_style;
+        self.current_statusline = statusline;
+        self.dangling_settings = fs::installer::dangling_settings(&self.dest_dir);
+    }
+
     pub fn set_default_style(&mut self) -> Result<()> {
         // Only works for OutputStyles tab
         if self.tab != Tab::OutputStyles {
@@ -55,7 +64,8 @@ impl App {
                     .to_string();
 
                 fs::installer::set_output_style(&self.dest_dir, &style_name)?;
-                self.current_output_style = Some(style_name.clone());
+                self.reload_current_settings();
+                self.note_settings_change(format!("set default output style {}", style_name));
                 self.status_message = Some(format!("Set default output style: {}", style_name));
             }
         }
@@ -70,7 +80,8 @@ impl App {
         }
 
         fs::installer::unset_output_style(&self.dest_dir)?;
-        self.current_output_style = None;
+        self.reload_current_settings();
+        self.note_settings_change("unset default output style");
         self.status_message = Some("Unset default output style".to_string());
         Ok(())
     }
@@ -85,8 +96,10 @@ impl App {
         if let Some(idx) = self.selected_component_index() {
             if let Some(component) = self.components.get(idx) {
                 fs::installer::set_statusline(&self.dest_dir, &component.name)?;
-                self.current_statusline = Some(component.name.clone());
-                self.status_message = Some(format!("Set statusline: {}", component.name));
+                let name = component.name.clone();
+                self.reload_current_settings();
+                self.note_settings_change(format!("set statusline {}", name));
+                self.status_message = Some(format!("Set statusline: {}", name));
             }
         }
         Ok(())
@@ -100,11 +113,37 @@ impl App {
         }
 
         fs::installer::unset_statusline(&self.dest_dir)?;
-        self.current_statusline = None;
+        self.reload_current_settings();
+        self.note_settings_change("unset statusline");
         self.status_message = Some("Unset statusline".to_string());
         Ok(())
     }
 
+    /// Move the highlighted hook one slot earlier (`delta < 0`) or later
+    /// within its event's settings.json array, then re-read positions.
+    pub fn move_hook(&mut self, delta: isize) -> Result<()> {
+        if self.tab != Tab::Hooks {
+            return Ok(());
+        }
+
+        let Some(config) = self.selected_component_index()
+            .and_then(|idx| self.components.get(idx))
+            .and_then(|c| c.hook_config.clone())
+        else {
+            return Ok(());
+        };
+
+        if fs::installer::move_hook_in_settings(&self.dest_dir, &config, delta)? {
+            self.hook_positions = fs::installer::read_hook_positions(&self.dest_dir);
+            let direction = if delta < 0 { "up" } else { "down" };
+            self.note_settings_change(format!("moved hook {} {}", config.name, direction));
+            self.status_message = Some(format!("Moved hook {} {}", config.name, direction));
+        } else {
+            self.status_message = Some(format!("Cannot move hook {}", config.name));
+        }
+        Ok(())
+    }
+
     pub fn show_diff(&mut self) -> Result<()> {
         if self.tab == Tab::McpServers || self.tab == Tab::Plugins {
             return Ok(());
@@ -112,9 +151,10 @@ impl App {
 
         if let Some(idx) = self.selected_component_index() {
             if let Some(c) = self.components.get(idx) {
-                let diff = fs::diff::compare_files(&c.source_path, &c.dest_path)?;
-                self.diff_content = Some(diff);
+                self.diff_content = Some(fs::diff::component_diff(c)?);
+                self.diff_summary = fs::frontmatter::component_frontmatter_changes(c);
                 self.diff_scroll = 0;
+                self.load_diff_hunks(idx);
                 self.current_view = View::Diff;
             }
         }
@@ -122,7 +162,10 @@ impl App {
     }
 
     pub fn close_diff(&mut self) {
+        self.adopt_armed = false;
         self.diff_content = None;
+        self.diff_summary.clear();
+        self.diff_hunks = None;
         self.current_view = View::List;
     }
 
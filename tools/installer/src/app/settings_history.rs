@@ -0,0 +1,92 @@
+use crossterm::event::KeyCode;
+use anyhow::Result;
+
+use crate::fs::settings_history::{restore_managed_sections, settings_versions, version_diff};
+
+use super::types::{Tab, View};
+use super::App;
+
+impl App {
+    /// Open the settings.json history (`H` on the Config tab).
+    pub fn open_settings_history(&mut self) -> Result<()> {
+        if self.tab != Tab::Config {
+            return Ok(());
+        }
+        self.settings_versions = settings_versions(&self.dest_dir)?;
+        if self.settings_versions.is_empty() {
+            self.status_message = Some("No settings.json or backups of it yet".to_string());
+            return Ok(());
+        }
+        self.settings_version_index = 0;
+        self.settings_restore_armed = false;
+        self.load_settings_version_diff();
+        self.current_view = View::SettingsHistory;
+        Ok(())
+    }
+
+    /// Diff of the highlighted version against the one before it.
+    fn load_settings_version_diff(&mut self) {
+        self.diff_scroll = 0;
+        self.diff_content = Some(
+            version_diff(&self.settings_versions, self.settings_version_index)
+                .unwrap_or_else(|e| format!("Could not diff: {:#}", e)),
+        );
+    }
+
+    pub fn handle_settings_history_key(&mut self, key: KeyCode) -> Result<()> {
+        let armed = std::mem::take(&mut self.settings_restore_armed);
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.diff_content = None;
+                self.current_view = View::List;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.settings_version_index > 0 => {
+                self.settings_version_index -= 1;
+                self.load_settings_version_diff();
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.settings_version_index + 1 < self.settings_versions.len() => {
+                self.settings_version_index += 1;
+                self.load_settings_version_diff();
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => self.diff_scroll = self.diff_scroll.saturating_add(10),
+            KeyCode::PageUp | KeyCode::Char('K') => self.diff_scroll = self.diff_scroll.saturating_sub(10),
+            KeyCode::Char('r') => self.restore_settings_version(armed)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// First `r` names what would be restored; a second `r` in a row
+    /// puts the highlighted version's managed sections back.
+    fn restore_settings_version(&mut self, armed: bool) -> Result<()> {
+        let Some(version) = self.settings_versions.get(self.settings_version_index) else { return Ok(()) };
+        if version.snapshot.is_none() {
+            self.status_message = Some("Pick a backup to restore from".to_string());
+            return Ok(());
+        }
+        let (label, path) = (version.label().to_string(), version.path.clone());
+        if !armed {
+            self.settings_restore_armed = true;
+            self.status_message = Some(format!(
+                "Restore hooks, outputStyle and statusLine from {}? Press r again to confirm",
+                label
+            ));
+            return Ok(());
+        }
+
+        let changed = restore_managed_sections(&self.dest_dir, &path)?;
+        if !changed.is_empty() {
+            self.note_settings_change(format!("restored {} from {}", changed.join(", "), label));
+        }
+        self.status_message = Some(if changed.is_empty() {
+            format!("Managed sections already match {}", label)
+        } else {
+            format!("Restored {} from {}", changed.join(", "), label)
+        });
+        // The restore backed up the live file, adding a version.
+        self.settings_versions = settings_versions(&self.dest_dir)?;
+        self.settings_version_index = 0;
+        self.load_settings_version_diff();
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use crossterm::event::KeyCode;
+use anyhow::Result;
+
+use crate::component::ComponentType;
+use crate::fs::installer::{set_key_owner, settings_keys, KeyOwner};
+
+use super::types::{Tab, View};
+use super::App;
+
+impl App {
+    /// Switch the Config tab to its Keys view (`v`): one row per top-level
+    /// settings.json key with who owns it.
+    pub fn open_settings_keys(&mut self) -> Result<()> {
+        if self.tab != Tab::Config {
+            return Ok(());
+        }
+        self.load_settings_keys()?;
+        if self.settings_keys.is_empty() {
+            self.status_message = Some("No settings.json in source or installed yet".to_string());
+            return Ok(());
+        }
+        self.settings_key_index = 0;
+        self.current_view = View::SettingsKeys;
+        Ok(())
+    }
+
+    fn load_settings_keys(&mut self) -> Result<()> {
+        let source = self.components.iter()
+            .find(|c| c.component_type == ComponentType::ConfigFile && c.name == "settings.json")
+            .map(|c| c.source_path.clone());
+        self.settings_keys = settings_keys(source.as_deref(), &self.dest_dir)?;
+        self.settings_key_index = self.settings_key_index.min(self.settings_keys.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn handle_settings_keys_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => self.current_view = View::List,
+            KeyCode::Up | KeyCode::Char('k') => self.settings_key_index = self.settings_key_index.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.settings_key_index + 1 < self.settings_keys.len() => {
+                self.settings_key_index += 1;
+            }
+            KeyCode::Char('a') => self.set_settings_key_owner(KeyOwner::Installer)?,
+            KeyCode::Char('r') => self.set_settings_key_owner(KeyOwner::User)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Adopt (`a`) or release (`r`) the highlighted key.
+    fn set_settings_key_owner(&mut self, owner: KeyOwner) -> Result<()> {
+        let Some(key) = self.settings_keys.get(self.settings_key_index).cloned() else { return Ok(()) };
+        let changed = set_key_owner(&self.dest_dir, &key, owner)?;
+        if changed {
+            let verb = if owner == KeyOwner::Installer { "adopted" } else { "released" };
+            self.note_settings_change(format!("{} key {}", verb, key.key));
+        }
+        self.status_message = Some(match (changed, owner) {
+            (false, KeyOwner::Installer) => format!("{} is already managed by the installer", key.key),
+            (false, KeyOwner::User) => format!("{} is already yours", key.key),
+            (true, KeyOwner::Installer) => format!("Adopted {}: installs merge it, removing settings.json deletes it", key.key),
+            (true, KeyOwner::User) => format!("Released {}: installs and removals leave it alone", key.key),
+        });
+        self.load_settings_keys()
+    }
+}
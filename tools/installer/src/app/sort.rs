@@ -0,0 +1,332 @@
+//! Per-tab list ordering picked with `S` and remembered in `~/.hibi/ui.yaml`.
+//!
+//! Component tabs reorder their `TreeView`; the MCP and plugin tabs reorder
+//! `mcp_servers` / `plugins` in place, keeping the scan's order around so
+//! "Source order" can put it back.
+
+use std::cmp::Ordering;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::{ui_state, App};
+use crate::component::{Component, InstallStatus};
+use crate::mcp::{McpServer, McpStatus};
+use crate::plugin::{Plugin, PluginStatus};
+use crate::tree::{TreeNode, TreeView};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortKey {
+    /// The order sources list items in.
+    #[default]
+    Source,
+    Name,
+    /// Not-yet-installed first when ascending.
+    Status,
+    /// Oldest first when ascending. Component tabs only.
+    Modified,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+/// Menu entries, in display order. The MCP and plugin tabs get the first
+/// five: their items have no file to take a modification time from.
+const SORTS: [ListSort; 7] = [
+    ListSort::new(SortKey::Source, false),
+    ListSort::new(SortKey::Name, false),
+    ListSort::new(SortKey::Name, true),
+    ListSort::new(SortKey::Status, false),
+    ListSort::new(SortKey::Status, true),
+    ListSort::new(SortKey::Modified, true),
+    ListSort::new(SortKey::Modified, false),
+];
+
+/// Sorts offered on `tab`.
+pub fn sort_options(tab: Tab) -> &'static [ListSort] {
+    match tab {
+        Tab::McpServers | Tab::Plugins => &SORTS[..5],
+        _ => &SORTS,
+    }
+}
+
+impl ListSort {
+    const fn new(key: SortKey, descending: bool) -> Self {
+        Self { key, descending }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match (self.key, self.descending) {
+            (SortKey::Source, _) => "Source order",
+            (SortKey::Name, false) => "Name (A→Z)",
+            (SortKey::Name, true) => "Name (Z→A)",
+            (SortKey::Status, false) => "Status (new first)",
+            (SortKey::Status, true) => "Status (installed first)",
+            (SortKey::Modified, false) => "Last modified (oldest first)",
+            (SortKey::Modified, true) => "Last modified (newest first)",
+        }
+    }
+
+    /// Name stored in ui.yaml (`name`, `status-desc`, ...); `None` for
+    /// source order, which is the default and isn't stored.
+    fn pref_name(&self) -> Option<String> {
+        let key = match self.key {
+            SortKey::Source => return None,
+            SortKey::Name => "name",
+            SortKey::Status => "status",
+            SortKey::Modified => "modified",
+        };
+        Some(if self.descending { format!("{}-desc", key) } else { key.to_string() })
+    }
+
+    fn from_pref_name(name: &str) -> Option<Self> {
+        SORTS.iter().copied().find(|s| s.pref_name().as_deref() == Some(name))
+    }
+
+    fn order(&self, ordering: Ordering) -> Ordering {
+        if self.descending { ordering.reverse() } else { ordering }
+    }
+}
+
+/// How one tree node compares. Outside source order, folders come first
+/// (`group` 0) and are ordered by name.
+struct NodeKey {
+    group: u8,
+    rank: u64,
+    name: String,
+}
+
+fn install_rank(status: &InstallStatus) -> u64 {
+    match status {
+        InstallStatus::New => 0,
+        InstallStatus::Modified => 1,
+        InstallStatus::Unchanged => 2,
+        InstallStatus::Managed => 3,
+        InstallStatus::External => 4,
+        InstallStatus::Orphaned => 5,
+        InstallStatus::Unconfigured => 6,
+    }
+}
+
+fn node_key(tree: &TreeView, idx: usize, components: &[Component], key: SortKey) -> NodeKey {
+    match &tree.nodes[idx] {
+        // A folder sits where its first item was inserted.
+        TreeNode::Folder { .. } if key == SortKey::Source => {
+            let first = tree.get_folder_component_indices(idx).into_iter().min().unwrap_or(usize::MAX);
+            NodeKey { group: 0, rank: first as u64, name: String::new() }
+        }
+        TreeNode::Folder { name, .. } => NodeKey { group: 0, rank: 0, name: name.to_lowercase() },
+        TreeNode::File { component_idx, .. } => {
+            let c = &components[*component_idx];
+            let name = c.name.rsplit(['/', '\\']).next().unwrap_or(&c.name).to_lowercase();
+            match key {
+                SortKey::Source => NodeKey { group: 0, rank: *component_idx as u64, name: String::new() },
+                SortKey::Name => NodeKey { group: 1, rank: 0, name },
+                SortKey::Status => NodeKey { group: 1, rank: install_rank(&c.status), name },
+                SortKey::Modified => {
+                    let secs = crate::fs::path_modified(&c.source_path)
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    NodeKey { group: 1, rank: secs, name }
+                }
+            }
+        }
+    }
+}
+
+fn sort_tree(tree: &mut TreeView, components: &[Component], sort: ListSort) {
+    let keys: Vec<NodeKey> = (0..tree.nodes.len())
+        .map(|idx| node_key(tree, idx, components, sort.key))
+        .collect();
+    tree.sort_by(|a, b| {
+        let (a, b) = (&keys[a], &keys[b]);
+        a.group.cmp(&b.group).then_with(|| sort.order((a.rank, &a.name).cmp(&(b.rank, &b.name))))
+    });
+}
+
+/// Reorder a flat list and return where the item at `cursor` ended up.
+fn sort_flat<T>(
+    items: &mut [T],
+    cursor: usize,
+    sort: ListSort,
+    source_order: &[String],
+    name: impl Fn(&T) -> &str,
+    installed: impl Fn(&T) -> bool,
+) -> usize {
+    let current = items.get(cursor).map(|item| name(item).to_string());
+    let rank = |item: &T| match sort.key {
+        SortKey::Source => {
+            (source_order.iter().position(|n| n == name(item)).unwrap_or(usize::MAX), String::new())
+        }
+        SortKey::Status => (usize::from(installed(item)), name(item).to_lowercase()),
+        SortKey::Name | SortKey::Modified => (0, name(item).to_lowercase()),
+    };
+    items.sort_by(|a, b| sort.order(rank(a).cmp(&rank(b))));
+    current
+        .and_then(|c| items.iter().position(|item| name(item) == c))
+        .unwrap_or(cursor)
+}
+
+/// Put rescanned items back in the order of `previous` (names); items that
+/// weren't there before go last.
+fn keep_order<T>(items: &mut [T], previous: &[String], name: impl Fn(&T) -> &str) {
+    items.sort_by_key(|item| previous.iter().position(|n| n == name(item)).unwrap_or(usize::MAX));
+}
+
+impl App {
+    pub fn sort_for(&self, tab: Tab) -> ListSort {
+        self.sorts.get(&tab).copied().unwrap_or_default()
+    }
+
+    /// Pick up the sorts remembered for the selected CLI's tabs.
+    pub(super) fn load_sorts(&mut self) {
+        let remembered = dirs::home_dir().map(|home| ui_state::sorts(&home)).unwrap_or_default();
+        self.sorts = self.available_tabs.iter()
+            .filter_map(|&tab| {
+                let sort = ListSort::from_pref_name(remembered.get(&self.tab_label(tab))?)?;
+                sort_options(tab).contains(&sort).then_some((tab, sort))
+            })
+            .collect();
+    }
+
+    /// Sort freshly scanned lists, first recording the scan's order for
+    /// "Source order".
+    pub(crate) fn apply_sorts(&mut self) {
+        self.mcp_source_order = self.mcp_servers.iter().map(|m| m.def.name.clone()).collect();
+        self.plugin_source_order = self.plugins.iter().map(|p| p.def.name.clone()).collect();
+        self.sort_trees();
+        self.sort_mcp_servers();
+        self.sort_plugins();
+    }
+
+    /// Apply the component tabs' sorts to just-rebuilt tree views.
+    pub(crate) fn sort_trees(&mut self) {
+        for (tab, tree) in &mut self.tree_views {
+            match self.sorts.get(tab) {
+                Some(&sort) if sort != ListSort::default() => sort_tree(tree, &self.components, sort),
+                _ => {}
+            }
+        }
+    }
+
+    fn sort_mcp_servers(&mut self) {
+        self.mcp_index = sort_flat(
+            &mut self.mcp_servers,
+            self.mcp_index,
+            self.sorts.get(&Tab::McpServers).copied().unwrap_or_default(),
+            &self.mcp_source_order,
+            |m| &m.def.name,
+            |m| m.status == McpStatus::Installed,
+        );
+    }
+
+    fn sort_plugins(&mut self) {
+        self.plugin_index = sort_flat(
+            &mut self.plugins,
+            self.plugin_index,
+            self.sorts.get(&Tab::Plugins).copied().unwrap_or_default(),
+            &self.plugin_source_order,
+            |p| &p.def.name,
+            |p| p.status == PluginStatus::Installed,
+        );
+    }
+
+    /// Swap in a post-batch MCP rescan without moving rows: the progress
+    /// view still refers to servers by index, and a status sort would
+    /// otherwise reshuffle the ones that were just installed.
+    pub(crate) fn replace_mcp_servers(&mut self, mut mcp_servers: Vec<McpServer>) {
+        let previous: Vec<String> = self.mcp_servers.iter().map(|m| m.def.name.clone()).collect();
+        keep_order(&mut mcp_servers, &previous, |m| &m.def.name);
+        self.mcp_servers = mcp_servers;
+    }
+
+    /// Plugin counterpart of `replace_mcp_servers`.
+    pub(crate) fn replace_plugins(&mut self, mut plugins: Vec<Plugin>) {
+        let previous: Vec<String> = self.plugins.iter().map(|p| p.def.name.clone()).collect();
+        keep_order(&mut plugins, &previous, |p| &p.def.name);
+        self.plugins = plugins;
+    }
+
+    pub fn open_sort_menu(&mut self) {
+        let current = self.sort_for(self.tab);
+        self.sort_menu_index = sort_options(self.tab).iter().position(|s| *s == current).unwrap_or(0);
+        self.current_view = View::SortMenu;
+    }
+
+    pub fn handle_sort_menu_key(&mut self, key: KeyCode) -> Result<()> {
+        let options = sort_options(self.tab);
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.current_view = View::List,
+            KeyCode::Down | KeyCode::Char('j') if self.sort_menu_index + 1 < options.len() => {
+                self.sort_menu_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.sort_menu_index = self.sort_menu_index.saturating_sub(1),
+            KeyCode::Enter => {
+                let sort = options[self.sort_menu_index];
+                self.current_view = View::List;
+                self.apply_sort(self.tab, sort);
+                if let Some(home) = dirs::home_dir() {
+                    ui_state::save_sort(&home, &self.tab_label(self.tab), sort.pref_name().as_deref())?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reorder `tab` for `sort` and make it that tab's sort.
+    fn apply_sort(&mut self, tab: Tab, sort: ListSort) {
+        self.sorts.insert(tab, sort);
+        match tab {
+            Tab::McpServers => self.sort_mcp_servers(),
+            Tab::Plugins => self.sort_plugins(),
+            _ => {
+                if let Some(tree) = self.tree_views.get_mut(&tab) {
+                    sort_tree(tree, &self.components, sort);
+                }
+            }
+        }
+        self.status_message = Some(format!("{}: {}", self.tab_label(tab), sort.label()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_sort_follows_cursor_and_restores_source_order() {
+        let source: Vec<String> = ["b", "c", "a"].iter().map(|s| s.to_string()).collect();
+        let mut items: Vec<(String, bool)> = vec![("b".into(), true), ("c".into(), false), ("a".into(), true)];
+        fn name(item: &(String, bool)) -> &str { &item.0 }
+        fn installed(item: &(String, bool)) -> bool { item.1 }
+        let names = |items: &[(String, bool)]| items.iter().map(|i| i.0.clone()).collect::<Vec<_>>();
+
+        let cursor = sort_flat(&mut items, 0, ListSort::new(SortKey::Name, true), &source, name, installed);
+        assert_eq!(names(&items), ["c", "b", "a"]);
+        assert_eq!(cursor, 1);
+
+        sort_flat(&mut items, cursor, ListSort::new(SortKey::Status, false), &source, name, installed);
+        assert_eq!(names(&items), ["c", "a", "b"]);
+
+        let cursor = sort_flat(&mut items, 2, ListSort::default(), &source, name, installed);
+        assert_eq!(names(&items), ["b", "c", "a"]);
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn pref_names_round_trip() {
+        for sort in SORTS {
+            match sort.pref_name() {
+                Some(name) => assert_eq!(ListSort::from_pref_name(&name), Some(sort)),
+                None => assert_eq!(sort, ListSort::default()),
+            }
+        }
+    }
+}
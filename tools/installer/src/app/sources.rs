@@ -182,6 +182,9 @@ impl App {
         let source_dir = self.source_dir.clone();
         let dest_dir = self.dest_dir.clone();
         let target_cli = self.target_cli;
+        let directories = self.directories.clone();
+        let scan_options = self.scan_options;
+        let mcp_project = self.mcp_project();
         let (result_tx, result_rx) = mpsc::channel::<SyncPayload>();
         let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
         self.source_sync_rx = Some(result_rx);
@@ -194,8 +197,8 @@ impl App {
             // Rescan component inventory so freshly pulled files appear in the
             // UI. Skip when no CLI target is selected yet (nothing to scan for).
             let rescan = target_cli.and_then(|cli| {
-                let components = scanner::scan_all_sources(&report.resolved, &dest_dir, cli).ok()?;
-                let mcp_servers = scanner::scan_all_mcp_sources(&report.resolved, cli)
+                let components = scanner::scan_all_sources(&report.resolved, &dest_dir, cli, &directories, scan_options).ok()?;
+                let mcp_servers = scanner::scan_all_mcp_sources(&report.resolved, cli, mcp_project.as_deref())
                     .map(|(servers, _)| servers)
                     .unwrap_or_default();
                 let plugins = scanner::scan_all_plugin_sources(&report.resolved).unwrap_or_default();
@@ -235,8 +238,10 @@ impl App {
     fn source_reload(&mut self) -> Result<()> {
         let result = source::resolve_all_sources(&self.source_dir)?;
         self.sources = result.sources;
-        if !result.warnings.is_empty() {
-            self.source_sync_status = Some(SyncStatus::Error(result.warnings.join("; ")));
+        let mut warnings = result.warnings;
+        warnings.extend(self.reload_profiles());
+        if !warnings.is_empty() {
+            self.source_sync_status = Some(SyncStatus::Error(warnings.join("; ")));
         }
         Ok(())
     }
@@ -267,6 +272,10 @@ impl App {
                 } else {
                     SyncStatus::Success(payload.summaries.join("; "))
                 });
+                let profile_warnings = self.reload_profiles();
+                if !profile_warnings.is_empty() {
+                    self.source_sync_status = Some(SyncStatus::Error(profile_warnings.join("; ")));
+                }
 
                 // Apply rescan so the component list and tree views reflect
                 // whatever the sync pulled (new files, deletions, edits).
@@ -274,7 +283,8 @@ impl App {
                     self.components = rescan.components;
                     self.mcp_servers = rescan.mcp_servers;
                     self.plugins = rescan.plugins;
-                    self.tree_views = build_tree_views(&self.components);
+                    self.tree_views = build_tree_views(&self.components, &self.directories, self.show_all_hosts);
+                    self.apply_sorts();
                 }
 
                 self.current_view = View::Sources;
@@ -0,0 +1,144 @@
+use std::sync::mpsc::{self, TryRecvError};
+
+use super::types::{Tab, TargetCli};
+use super::App;
+use crate::notify::{self, BatchNotice};
+
+/// What the session changed, printed to stdout once the TUI has closed so
+/// it outlives the alternate screen and can be captured by scripts.
+#[derive(Debug, Default)]
+pub struct SessionSummary {
+    /// Per batch, e.g. "Installed on Claude Code / Agents: a.md, b.md".
+    pub changes: Vec<String>,
+    /// settings.json edits made outside a batch (default style, key owners, ...).
+    pub settings: Vec<String>,
+    /// `[WARN]` and `[ERR]` lines from the processing logs.
+    pub warnings: Vec<String>,
+}
+
+impl SessionSummary {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.settings.is_empty() && self.warnings.is_empty()
+    }
+
+    /// Plain-text report; empty when the session changed nothing.
+    pub fn lines(&self) -> Vec<String> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec!["hibi: session summary".to_string()];
+        lines.extend(self.changes.iter().map(|c| format!("  {}", c)));
+        lines.extend(self.settings.iter().map(|s| format!("  Settings: {}", s)));
+        lines.extend(self.warnings.iter().map(|w| format!("  {}", w)));
+        lines
+    }
+}
+
+impl App {
+    /// Name of a processed item on the current tab.
+    pub(super) fn processed_item_name(&self, idx: usize) -> Option<String> {
+        match self.tab {
+            Tab::McpServers => self.mcp_servers.get(idx).map(|m| m.def.name.clone()),
+            Tab::Plugins => self.plugins.get(idx).map(|p| p.def.name.clone()),
+            _ => self.components.get(idx).map(|c| c.name.clone()),
+        }
+    }
+
+    /// Add the finished batch's outcome to the session summary.
+    pub(super) fn summarize_batch(&mut self) {
+        let target = self.target_cli.unwrap_or(TargetCli::Claude).display_name().to_string();
+        let tab = self.tab_label(self.tab);
+        let place = format!("{} / {}", target, tab);
+        let names = |ok: bool| -> Vec<String> {
+            self.processed_items.iter()
+                .filter(|(_, item_ok)| *item_ok == ok)
+                .filter_map(|&(idx, _)| self.processed_item_name(idx))
+                .collect()
+        };
+        let (succeeded, failed) = (names(true), names(false));
+
+        let action = match (self.batch_aborted, self.is_removing) {
+            (true, _) => "Rolled back",
+            (false, true) => "Removed",
+            (false, false) => "Installed",
+        };
+        if !succeeded.is_empty() {
+            self.session_summary.changes.push(format!("{} on {}: {}", action, place, succeeded.join(", ")));
+        }
+        if !failed.is_empty() {
+            self.session_summary.changes.push(format!("Failed on {}: {}", place, failed.join(", ")));
+        }
+        if !self.notify.is_empty() && (!succeeded.is_empty() || !failed.is_empty()) {
+            let notice = BatchNotice::new(&target, &tab, self.is_removing, self.batch_aborted, succeeded, failed);
+            self.send_notification(notice);
+        }
+    }
+
+    /// Announce a finished batch in the background, as `notify:` asks.
+    fn send_notification(&mut self, notice: BatchNotice) {
+        let (tx, rx) = mpsc::channel();
+        let config = self.notify.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(notify::send(&config, &notice));
+        });
+        self.notify_rx = Some(rx);
+    }
+
+    /// Report a notification that couldn't be sent, if any. Called every
+    /// loop tick.
+    pub fn poll_notification(&mut self) {
+        let Some(rx) = &self.notify_rx else { return };
+        let errors = match rx.try_recv() {
+            Ok(errors) => errors,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Vec::new(),
+        };
+        self.notify_rx = None;
+        if let Some(first) = errors.first() {
+            self.status_message = Some(format!("Couldn't send the batch notification: {}", first));
+            self.session_summary.warnings.extend(errors.iter().map(|e| format!("[WARN] Notification: {}", e)));
+        }
+    }
+
+    /// Keep the batch's warnings and errors before its log is cleared.
+    pub(super) fn summarize_batch_warnings(&mut self) {
+        let warnings = self.processing_log.iter()
+            .filter(|line| line.starts_with("[WARN]") || line.starts_with("[ERR]"))
+            .cloned();
+        self.session_summary.warnings.extend(warnings);
+    }
+
+    pub(super) fn note_settings_change(&mut self, change: impl Into<String>) {
+        self.session_summary.settings.push(change.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn finished_batches_and_settings_edits_end_up_in_the_session_summary() {
+        use crate::component::{Component, ComponentType, InstallStatus};
+
+        let mut app = App::for_test();
+        assert!(app.session_summary.lines().is_empty(), "nothing changed yet");
+        app.tab = Tab::Agents;
+        app.components = ["a.md", "b.md"].iter()
+            .map(|n| Component::new(ComponentType::Agents, (*n).into(), (*n).into(), (*n).into(), InstallStatus::New))
+            .collect();
+        app.processed_items = vec![(0, true), (1, false)];
+        app.processing_log = vec!["[OK] a.md".to_string(), "[ERR] b.md: permission denied".to_string()];
+        app.start_finish_processing();
+        app.close_processing();
+        app.note_settings_change("unset statusline");
+
+        assert_eq!(app.session_summary.lines(), vec![
+            "hibi: session summary",
+            "  Installed on Claude Code / Agents: a.md",
+            "  Failed on Claude Code / Agents: b.md",
+            "  Settings: unset statusline",
+            "  [ERR] b.md: permission denied",
+        ]);
+    }
+}
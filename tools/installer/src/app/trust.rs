@@ -0,0 +1,109 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use super::types::{Tab, View};
+use super::App;
+use crate::mcp::McpScope;
+use crate::source::config;
+
+/// What was interrupted to ask whether the local-scope project is trusted,
+/// resumed once it is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TrustPending {
+    ScopeChange,
+    Install,
+    Remove,
+}
+
+impl App {
+    /// Whether MCP operations may write into the current project path:
+    /// always outside local scope, otherwise only for trusted projects.
+    pub fn project_trusted(&self) -> bool {
+        self.mcp_scope != McpScope::Local
+            || config::is_trusted_project(&self.trusted_projects, std::path::Path::new(&self.mcp_project_path))
+    }
+
+    /// `true` when `then` may go ahead; otherwise ask to trust the project
+    /// first and resume `then` if the answer is yes.
+    pub(crate) fn require_trusted_project(&mut self, then: TrustPending) -> bool {
+        if self.tab != Tab::McpServers || self.project_trusted() {
+            return true;
+        }
+        self.trust_pending = Some(then);
+        self.current_view = View::TrustProject;
+        false
+    }
+
+    pub fn handle_trust_project_key(&mut self, key: KeyCode) -> Result<()> {
+        let Some(pending) = self.trust_pending else {
+            self.current_view = View::List;
+            return Ok(());
+        };
+        match key {
+            KeyCode::Char('y') => {
+                let trusted = config::trust_project(std::path::Path::new(&self.mcp_project_path))?;
+                self.trusted_projects.push(trusted.clone());
+                self.trust_pending = None;
+                self.current_view = View::List;
+                self.status_message = Some(format!("Trusted {}", trusted.display()));
+                match pending {
+                    TrustPending::ScopeChange => {}
+                    TrustPending::Install => self.install_selected()?,
+                    TrustPending::Remove => self.remove_selected()?,
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.trust_pending = None;
+                self.current_view = View::List;
+                if pending == TrustPending::ScopeChange {
+                    self.mcp_scope = McpScope::User;
+                    self.status_message = Some("MCP scope: user (project not trusted)".to_string());
+                } else {
+                    self.status_message = Some(format!("Cancelled: {} is not a trusted project", self.mcp_project_path));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{App, Tab};
+
+    #[test]
+    fn local_scope_installs_wait_for_a_trusted_project() {
+        use crate::app::{TrustPending, View};
+        use crate::mcp::{McpScope, McpServer, McpServerDef, McpStatus};
+        use crossterm::event::KeyCode;
+
+        let mut server = McpServer::new(McpServerDef {
+            name: "plain".into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env: Vec::new(), version: None, env_file: None,
+        }, McpStatus::NotInstalled);
+        server.selected = true;
+        let mut app = App::for_test();
+        app.tab = Tab::McpServers;
+        app.mcp_servers = vec![server];
+        app.mcp_scope = McpScope::Local;
+        let project = std::env::temp_dir();
+        app.mcp_project_path = project.join("hibi-untrusted").to_string_lossy().to_string();
+
+        app.install_selected().unwrap();
+        assert!(app.current_view == View::TrustProject);
+        assert_eq!(app.trust_pending, Some(TrustPending::Install));
+        assert!(app.processing_queue.is_empty());
+
+        app.handle_trust_project_key(KeyCode::Esc).unwrap();
+        assert!(app.current_view == View::List);
+        assert!(app.processing_queue.is_empty());
+
+        // A subdirectory of a trusted project goes straight through.
+        app.trusted_projects = vec![project];
+        app.install_selected().unwrap();
+        assert!(app.current_view == View::Preflighting);
+        assert_eq!(app.processing_queue, vec![0]);
+    }
+}
@@ -1,36 +1,6 @@
-use std::path::PathBuf;
-
-use anyhow::Result;
-
 use crate::component::ComponentType;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum TargetCli {
-    Claude,
-    Codex,
-}
-
-impl TargetCli {
-    pub fn display_name(&self) -> &str {
-        match self {
-            Self::Claude => "Claude Code",
-            Self::Codex => "Codex CLI",
-        }
-    }
-
-    pub fn config_dir_name(&self) -> &str {
-        match self {
-            Self::Claude => ".claude",
-            Self::Codex => ".codex",
-        }
-    }
-
-    pub fn get_dest_dir(&self) -> Result<PathBuf> {
-        let home = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
-        Ok(home.join(self.config_dir_name()))
-    }
-}
+pub use hibi_core::TargetCli;
 
 /// Which pane the keyboard is currently driving.
 ///
@@ -58,6 +28,8 @@ pub enum Tab {
     Config,
     McpServers,
     Plugins,
+    /// Tab for the n-th custom type in `component_types:`.
+    Custom(usize),
 }
 
 impl Tab {
@@ -80,10 +52,39 @@ impl Tab {
     pub fn for_cli(cli: TargetCli) -> Vec<Tab> {
         match cli {
             TargetCli::Claude => Self::all().to_vec(),
-            TargetCli::Codex => vec![Tab::Skills, Tab::Config, Tab::McpServers],
+            TargetCli::Codex | TargetCli::OpenCode => vec![Tab::Skills, Tab::Config, Tab::McpServers],
+            TargetCli::Cursor => vec![Tab::Rules, Tab::McpServers],
+            TargetCli::Custom(_) => {
+                let Some(target) = cli.custom() else { return Vec::new() };
+                let mut tabs: Vec<Tab> = Self::all().iter()
+                    .copied()
+                    .filter(|tab| tab.to_component_type().is_some_and(|t| target.component_types.contains(&t)))
+                    .collect();
+                if !target.config_files.is_empty() {
+                    tabs.push(Tab::Config);
+                }
+                if target.mcp.is_some() {
+                    tabs.push(Tab::McpServers);
+                }
+                tabs
+            }
         }
     }
 
+    /// Parse a `--tab` value: a display name (case-insensitive) or one of
+    /// the longer aliases such as `mcp-servers` or `output-styles`.
+    pub fn from_name(name: &str) -> Option<Tab> {
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "mcp-servers" | "mcps" => return Some(Tab::McpServers),
+            "output-styles" => return Some(Tab::OutputStyles),
+            _ => {}
+        }
+        Self::all().iter()
+            .copied()
+            .find(|tab| tab.display_name().to_ascii_lowercase() == name)
+    }
+
     pub fn display_name(&self) -> &str {
         match self {
             Tab::Agents => "Agents",
@@ -97,6 +98,8 @@ impl Tab {
             Tab::Config => "Config",
             Tab::McpServers => "MCP",
             Tab::Plugins => "Plugins",
+            // Named by `App::tab_label`, which knows the config.
+            Tab::Custom(_) => "Custom",
         }
     }
 
@@ -113,6 +116,8 @@ impl Tab {
             Tab::Config => Some(ComponentType::ConfigFile),
             Tab::McpServers => None,
             Tab::Plugins => None,
+            // Resolved through `App::tab_component_type`.
+            Tab::Custom(_) => None,
         }
     }
 }
@@ -124,7 +129,28 @@ pub enum View {
     List,
     Diff,
     EnvInput,
+    McpReview,
     ProjectPath,
+    TrustProject,
+    HookDetail,
+    HookLogs,
+    GitStatus,
+    MarketplaceConfirmRemove,
+    StaleFilesConfirm,
+    PruneConfirm,
+    MirrorConfirm,
+    ConflictResolve,
+    OfflineQueue,
+    PendingRetry,
+    ResumeQueue,
+    Migration,
+    Search,
+    Palette,
+    SortMenu,
+    HookScaffold,
+    KeywordEdit,
+    SettingsHistory,
+    SettingsKeys,
     Preflighting,
     Installing,
     Sources,
@@ -143,3 +169,16 @@ pub enum SyncStatus {
     Success(String),
     Error(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_from_name_accepts_display_names_and_aliases() {
+        assert_eq!(Tab::from_name("mcp"), Some(Tab::McpServers));
+        assert_eq!(Tab::from_name("Skills"), Some(Tab::Skills));
+        assert_eq!(Tab::from_name("output-styles"), Some(Tab::OutputStyles));
+        assert_eq!(Tab::from_name("nope"), None);
+    }
+}
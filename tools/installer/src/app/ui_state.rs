@@ -0,0 +1,229 @@
+//! UI state remembered between runs, kept in `~/.hibi/ui.yaml`: the CLI,
+//! tab, theme and MCP scope in use at exit, per-tab sorts, progress
+//! through the onboarding tips, and whether the Codex migration offer was
+//! turned down.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Tab, TargetCli};
+use super::{App, HintProgress};
+use crate::fs::state::{load_yaml, save_yaml};
+use crate::mcp::McpScope;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UiState {
+    /// CLI config dir name (`.claude`, `.codex`) -> last tab's label.
+    #[serde(default)]
+    last_tab: HashMap<String, String>,
+    /// Tab label -> sort chosen with `S` (see `ListSort::pref_name`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    sort: HashMap<String, String>,
+    /// Config dir name of the CLI picked last; highlighted on the selection
+    /// screen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cli: Option<String>,
+    /// Theme name (`mocha`, `latte` or a custom palette's); the terminal's
+    /// background decides when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    theme: Option<String>,
+    /// `user` or `local`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mcp_scope: Option<String>,
+    /// Project directory for the `local` MCP scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mcp_project: Option<String>,
+    /// Ids of tips dismissed with `Esc`.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    dismissed_hints: BTreeSet<String>,
+    /// Tip id -> presses of the keys it teaches.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    hint_uses: BTreeMap<String, u32>,
+    /// Don't offer to port a Claude setup when Codex is picked.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    codex_migration_declined: bool,
+}
+
+/// Preferences restored at startup.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct Preferences {
+    pub cli: Option<TargetCli>,
+    pub theme: Option<String>,
+    /// Scope and, for `local`, its project directory.
+    pub mcp_scope: Option<(McpScope, Option<String>)>,
+    pub hints: HintProgress,
+    pub migration_declined: bool,
+}
+
+fn state_path(home: &Path) -> PathBuf {
+    home.join(".hibi").join("ui.yaml")
+}
+
+fn load_state(home: &Path) -> UiState {
+    load_yaml(&state_path(home)).unwrap_or_default()
+}
+
+/// Label of the tab last used with `cli`, if one was remembered. Labels
+/// rather than `Tab` values so custom type tabs survive config reordering.
+pub(super) fn last_tab(home: &Path, cli: TargetCli) -> Option<String> {
+    load_state(home).last_tab.get(cli.config_dir_name()).cloned()
+}
+
+/// CLI, theme and MCP scope saved at the last exit. Unknown values are
+/// ignored rather than failing startup.
+pub(super) fn preferences(home: &Path) -> Preferences {
+    let state = load_state(home);
+    let cli = state.cli.as_deref()
+        .and_then(|name| TargetCli::all().into_iter().find(|c| c.config_dir_name() == name));
+    let mcp_scope = match state.mcp_scope.as_deref() {
+        Some("user") => Some((McpScope::User, None)),
+        Some("local") => Some((McpScope::Local, state.mcp_project)),
+        _ => None,
+    };
+    let hints = HintProgress { dismissed: state.dismissed_hints, uses: state.hint_uses };
+    Preferences { cli, theme: state.theme, mcp_scope, hints, migration_declined: state.codex_migration_declined }
+}
+
+fn save_preferences(home: &Path, prefs: &Preferences, tab_label: Option<&str>) -> Result<()> {
+    let mut state = load_state(home);
+    if let Some(cli) = prefs.cli {
+        state.cli = Some(cli.config_dir_name().to_string());
+        if let Some(label) = tab_label {
+            state.last_tab.insert(cli.config_dir_name().to_string(), label.to_string());
+        }
+    }
+    if let Some(theme) = &prefs.theme {
+        state.theme = Some(theme.to_lowercase());
+    }
+    if let Some((scope, project)) = &prefs.mcp_scope {
+        state.mcp_scope = Some(scope.display().to_string());
+        state.mcp_project = project.clone();
+    }
+    state.dismissed_hints = prefs.hints.dismissed.clone();
+    state.hint_uses = prefs.hints.uses.clone();
+    state.codex_migration_declined = prefs.migration_declined;
+    save_state(home, &state)
+}
+
+/// Record tips dismissed and learned so far.
+pub(super) fn save_hints(home: &Path, hints: &HintProgress) -> Result<()> {
+    let mut state = load_state(home);
+    state.dismissed_hints = hints.dismissed.clone();
+    state.hint_uses = hints.uses.clone();
+    save_state(home, &state)
+}
+
+/// Remembered sort names, keyed by tab label.
+pub(super) fn sorts(home: &Path) -> HashMap<String, String> {
+    load_state(home).sort
+}
+
+/// Remember `pref` as the sort for the tab labelled `label`; `None`
+/// forgets it (source order).
+pub(super) fn save_sort(home: &Path, label: &str, pref: Option<&str>) -> Result<()> {
+    let mut state = load_state(home);
+    match pref {
+        Some(pref) => { state.sort.insert(label.to_string(), pref.to_string()); }
+        None => { state.sort.remove(label); }
+    }
+    save_state(home, &state)
+}
+
+fn save_state(home: &Path, state: &UiState) -> Result<()> {
+    save_yaml(&state_path(home), state)
+}
+
+impl App {
+    /// Remember the MCP scope, tip progress and migration choice, the
+    /// theme if it was switched, and the CLI and its current tab once one
+    /// was picked, for the next run.
+    pub fn remember_preferences(&self) -> Result<()> {
+        let Some(home) = dirs::home_dir() else { return Ok(()) };
+        let local_project = (self.mcp_scope == McpScope::Local).then(|| self.mcp_project_path.clone());
+        let prefs = Preferences {
+            cli: self.target_cli,
+            theme: self.theme_changed.then(|| self.theme.name().to_string()),
+            mcp_scope: Some((self.mcp_scope, local_project)),
+            hints: self.hints.clone(),
+            migration_declined: self.migration_declined,
+        };
+        let tab_label = self.target_cli.map(|_| self.tab_label(self.tab));
+        save_preferences(&home, &prefs, tab_label.as_deref())
+    }
+
+    /// Tab to open after choosing `cli`: `--tab`, then the remembered tab,
+    /// then the first tab. Tabs the CLI doesn't offer are skipped.
+    pub(super) fn initial_tab(&self, cli: TargetCli) -> Tab {
+        let remembered = dirs::home_dir()
+            .and_then(|home| last_tab(&home, cli))
+            .and_then(|label| {
+                self.available_tabs.iter().copied().find(|t| self.tab_label(*t).eq_ignore_ascii_case(&label))
+            });
+        [self.startup_tab, remembered]
+            .into_iter()
+            .flatten()
+            .find(|tab| self.available_tabs.contains(tab))
+            .or_else(|| self.available_tabs.first().copied())
+            .unwrap_or(Tab::Skills)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hibi_core::test_util::temp_dir;
+
+    fn save_last_tab(home: &Path, cli: TargetCli, label: &str) -> Result<()> {
+        let prefs = Preferences { cli: Some(cli), ..Preferences::default() };
+        save_preferences(home, &prefs, Some(label))
+    }
+
+    #[test]
+    fn last_tab_and_sorts_are_remembered() {
+        let home = temp_dir("ui_state");
+        save_last_tab(&home, TargetCli::Claude, "MCP").unwrap();
+        save_last_tab(&home, TargetCli::Codex, "Skills").unwrap();
+
+        assert_eq!(last_tab(&home, TargetCli::Claude).as_deref(), Some("MCP"));
+        assert_eq!(last_tab(&home, TargetCli::Codex).as_deref(), Some("Skills"));
+
+        save_sort(&home, "Agents", Some("name-desc")).unwrap();
+        save_sort(&home, "MCP", Some("status")).unwrap();
+        save_sort(&home, "MCP", None).unwrap();
+        assert_eq!(sorts(&home).get("Agents").map(String::as_str), Some("name-desc"));
+        assert!(!sorts(&home).contains_key("MCP"));
+        assert_eq!(last_tab(&home, TargetCli::Claude).as_deref(), Some("MCP"));
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn cli_theme_and_mcp_scope_round_trip() {
+        let home = temp_dir("ui_prefs");
+        assert_eq!(preferences(&home), Preferences::default());
+
+        let prefs = Preferences {
+            cli: Some(TargetCli::Cursor),
+            theme: Some("latte".to_string()),
+            mcp_scope: Some((McpScope::Local, Some("/work/app".to_string()))),
+            hints: HintProgress {
+                dismissed: BTreeSet::from(["folders".to_string()]),
+                uses: BTreeMap::from([("diff".to_string(), 2)]),
+            },
+            migration_declined: true,
+        };
+        save_preferences(&home, &prefs, Some("Rules")).unwrap();
+        assert_eq!(preferences(&home), prefs);
+        assert_eq!(last_tab(&home, TargetCli::Cursor).as_deref(), Some("Rules"));
+
+        // Leaving before picking a CLI keeps the one remembered.
+        let prefs = Preferences { mcp_scope: Some((McpScope::User, None)), ..Preferences::default() };
+        save_preferences(&home, &prefs, None).unwrap();
+        let restored = preferences(&home);
+        assert_eq!(restored.cli, Some(TargetCli::Cursor));
+        assert_eq!(restored.mcp_scope, Some((McpScope::User, None)));
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
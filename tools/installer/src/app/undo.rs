@@ -0,0 +1,124 @@
+use crate::fs::journal::{Baseline, Journal, JournalEntry};
+use crate::mcp::McpScope;
+
+use super::types::{Tab, TargetCli};
+use super::App;
+
+impl App {
+    /// Capture the pre-batch state of every queued component so the batch
+    /// can be undone. Removals also copy the files they are about to
+    /// delete into a fresh backup snapshot.
+    pub(super) fn capture_journal_baseline(&mut self) {
+        if self.tab_component_type(self.tab).is_none() {
+            self.journal_baseline = None;
+            return;
+        }
+        if self.is_removing {
+            self.backup_snapshot = Some(crate::fs::backup::Snapshot::new(&self.dest_dir));
+        }
+        let paths: Vec<_> = self.processing_queue.iter()
+            .filter_map(|&i| self.components.get(i))
+            .map(|c| c.dest_path.clone())
+            .collect();
+        let snapshot = if self.is_removing { self.backup_snapshot.as_ref() } else { None };
+        match Baseline::capture(&self.dest_dir, &paths, snapshot) {
+            Ok(baseline) => self.journal_baseline = Some(baseline),
+            Err(e) => {
+                self.journal_baseline = None;
+                self.processing_log.push(format!("[WARN] Undo unavailable for this batch: {:#}", e));
+            }
+        }
+    }
+
+    /// Store what the finished batch changed as the undo journal. Batches
+    /// where nothing succeeded leave the previous journal in place.
+    pub(super) fn record_journal(&mut self) -> bool {
+        let baseline = self.journal_baseline.take();
+        let succeeded: Vec<usize> = self.processed_items.iter()
+            .filter(|(_, ok)| *ok)
+            .map(|(idx, _)| *idx)
+            .collect();
+        if succeeded.is_empty() {
+            return false;
+        }
+
+        let action = if self.is_removing { "Removed" } else { "Installed" };
+        let mut journal = Journal {
+            summary: format!("{} {} item(s) on {}", action, succeeded.len(), self.tab_label(self.tab)),
+            ..Default::default()
+        };
+        match self.tab {
+            Tab::McpServers => {
+                let names = succeeded.iter().filter_map(|&i| self.mcp_servers.get(i)).map(|m| m.def.name.clone());
+                if self.is_removing {
+                    journal.irreversible = names.map(|n| format!("removed MCP server {}", n)).collect();
+                } else {
+                    let project_path = (self.mcp_scope == McpScope::Local).then(|| self.mcp_project_path.clone());
+                    journal.entries = names
+                        .map(|name| JournalEntry::McpAdded { name, scope: self.mcp_scope, project_path: project_path.clone() })
+                        .collect();
+                }
+            }
+            Tab::Plugins => {
+                let verb = if self.is_removing { "removed" } else { "installed" };
+                journal.irreversible = succeeded.iter()
+                    .filter_map(|&i| self.plugins.get(i))
+                    .map(|p| format!("{} plugin {}", verb, p.def.name))
+                    .collect();
+            }
+            _ => {
+                let Some(baseline) = baseline else { return false };
+                let touched: Vec<_> = succeeded.iter()
+                    .filter_map(|&i| self.components.get(i))
+                    .map(|c| c.dest_path.clone())
+                    .collect();
+                journal.entries = baseline.entries(&touched, self.backup_snapshot.as_ref());
+            }
+        }
+
+        if let Err(e) = journal.save(&self.dest_dir) {
+            self.processing_log.push(format!("[WARN] Could not record undo journal: {:#}", e));
+            return false;
+        }
+        true
+    }
+
+    /// Reverse what an aborted batch already applied, through the same
+    /// journal `Z` uses. The previous batch's journal is put back after,
+    /// so it can still be undone.
+    pub(super) fn rollback_batch(&mut self) {
+        let previous = Journal::load(&self.dest_dir).ok().flatten();
+        if !self.record_journal() {
+            self.processing_log.push("[WARN] Nothing to roll back".to_string());
+            return;
+        }
+        let target_cli = self.target_cli.unwrap_or(TargetCli::Claude);
+        self.processing_log.push(match crate::fs::journal::undo(&self.dest_dir, target_cli) {
+            Ok(report) => format!("[WARN] Rolled back. {}", report),
+            Err(e) => format!("[ERR] Rollback failed: {:#}", e),
+        });
+        if let Some(previous) = previous
+            && let Err(e) = previous.save(&self.dest_dir)
+        {
+            self.processing_log.push(format!("[WARN] Could not keep the earlier undo journal: {:#}", e));
+        }
+    }
+
+    /// First `Ctrl+Z` names the batch that would be undone; a second one
+    /// in a row confirms. Returns `true` when the undo should start now.
+    pub fn request_undo(&mut self) -> bool {
+        if self.undo_armed {
+            self.undo_armed = false;
+            return true;
+        }
+        self.status_message = Some(match Journal::load(&self.dest_dir) {
+            Ok(Some(journal)) => {
+                self.undo_armed = true;
+                format!("Undo \"{}\"? Press Ctrl+Z again to confirm", journal.summary)
+            }
+            Ok(None) => "Nothing to undo".to_string(),
+            Err(e) => format!("Cannot read undo journal: {:#}", e),
+        });
+        false
+    }
+}
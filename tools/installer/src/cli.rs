@@ -1,38 +1,168 @@
 use std::time::Duration;
 
-use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 use crate::app::{self, App};
 use crate::loading::{self, RefreshResult};
-use crate::source;
-use crate::fs;
+use crate::run_summary::RunSummary;
+use crate::session;
+use crate::ui::{Click, ClickMap};
+use crate::{fs, maintenance, migrate, source};
 
 /// Read a single key press, filtering out release events.
 ///
-/// Modifier flags are not surfaced to callers: the focus-aware key model
-/// distinguishes `KeyCode::Tab` from `KeyCode::BackTab` directly, so no
-/// handler currently needs `Shift`/`Ctrl`/`Alt` state.
-pub(crate) fn read_key_press() -> Result<Option<KeyCode>> {
-    if let Event::Key(key) = event::read()? {
-        if key.kind != KeyEventKind::Release {
-            return Ok(Some(key.code));
+/// Most handlers only look at `key.code`: the focus-aware key model
+/// distinguishes `KeyCode::Tab` from `KeyCode::BackTab` directly. The full
+/// event is returned so `dispatch_key` can recognise `Ctrl+F`.
+pub(crate) fn read_key_press() -> Result<Option<KeyEvent>> {
+    session::next_key(None)
+}
+
+/// Like `read_key_press`, but gives up after one 100ms tick so views with
+/// background work keep refreshing.
+pub(crate) fn poll_key_press() -> Result<Option<KeyEvent>> {
+    poll_key_press_within(Duration::from_millis(100))
+}
+
+/// Like `poll_key_press`, with the caller choosing how long to wait.
+pub(crate) fn poll_key_press_within(timeout: Duration) -> Result<Option<KeyEvent>> {
+    session::next_key(Some(timeout))
+}
+
+/// Rows (list) or lines (diff) one wheel notch moves.
+const WHEEL_STEP: isize = 3;
+
+/// Retention used by `hibi clean` without `--keep`.
+const DEFAULT_RETENTION: &str = "30d";
+
+/// Handle a click or wheel scroll. Only the List and Diff views take the
+/// mouse: a click on a tab switches to it, on a row moves the cursor there
+/// and on a checkbox also toggles it; the wheel scrolls the list or diff.
+pub(crate) fn dispatch_mouse(app: &mut App, mouse: MouseEvent, clicks: &ClickMap) {
+    let step = match mouse.kind {
+        MouseEventKind::ScrollDown => WHEEL_STEP,
+        MouseEventKind::ScrollUp => -WHEEL_STEP,
+        _ => 0,
+    };
+    match app.current_view {
+        app::View::List if step != 0 => app.move_list_cursor(step),
+        app::View::Diff if step != 0 => {
+            app.diff_scroll = app.diff_scroll.saturating_add_signed(step as i16);
+        }
+        app::View::List if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+            match clicks.click(app, mouse.column, mouse.row) {
+                Some(Click::Tab(tab)) => app.select_tab(tab),
+                Some(Click::Row { index, checkbox }) => {
+                    app.focus_content();
+                    app.select_list_row(index);
+                    if checkbox {
+                        app.toggle_selected();
+                    }
+                }
+                None => {}
+            }
         }
+        _ => {}
     }
-    Ok(None)
+}
+
+/// `?` opens the key help over the List, Diff and Installing views; while
+/// it is open, any key just closes it. Returns whether the key was used.
+pub(crate) fn handle_help_key(app: &mut App, code: KeyCode) -> bool {
+    if app.help_open {
+        app.help_open = false;
+        return true;
+    }
+    let helpable = matches!(app.current_view, app::View::List | app::View::Diff | app::View::Installing);
+    if code == KeyCode::Char('?') && helpable {
+        app.help_open = true;
+        return true;
+    }
+    false
 }
 
 /// Dispatch a key press to the appropriate handler based on current view.
 pub(crate) fn dispatch_key(
     app: &mut App,
-    code: KeyCode,
+    key: KeyEvent,
     refresh_tx: &std::sync::mpsc::Sender<Result<RefreshResult>>,
 ) -> Result<()> {
+    let code = key.code;
+    if app.current_view == app::View::List && !app.help_open {
+        app.note_hint_key(code);
+    }
+    if handle_help_key(app, code) {
+        return Ok(());
+    }
+    if app.current_view == app::View::List
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && code == KeyCode::Char('f')
+    {
+        app.open_search();
+        return Ok(());
+    }
+    if app.current_view == app::View::List
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && code == KeyCode::Char('p')
+    {
+        app.open_palette();
+        return Ok(());
+    }
+    // A command chosen in the palette runs as its key in the list view.
+    if app.current_view == app::View::Palette {
+        if let Some(replay) = app.handle_palette_key(code) {
+            return dispatch_key(app, replay, refresh_tx);
+        }
+        return Ok(());
+    }
+
+    // Full rescan on demand; post-install refreshes only re-check the
+    // processed items.
+    if app.current_view == app::View::List && code == KeyCode::Char('R') {
+        app.current_view = app::View::Loading;
+        loading::start_loading_thread(app, refresh_tx);
+        return Ok(());
+    }
+
+    // Undo needs two presses in a row; any other key disarms it.
+    if app.current_view == app::View::List
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && code == KeyCode::Char('z')
+    {
+        if app.request_undo() {
+            app.current_view = app::View::Loading;
+            loading::start_undo_thread(app, refresh_tx);
+        }
+        return Ok(());
+    }
+    app.undo_armed = false;
+
     match app.current_view {
         app::View::CliSelection => handle_cli_selection(app, code, refresh_tx),
         app::View::EnvInput => handle_env_input(app, code),
         app::View::ProjectPath => { handle_project_path_input(app, code); Ok(()) }
+        app::View::TrustProject => app.handle_trust_project_key(code),
         app::View::List => handle_list_input(app, code),
+        app::View::HookDetail => app.handle_hook_detail_key(code),
+        app::View::MarketplaceConfirmRemove => { app.handle_marketplace_confirm_key(code); Ok(()) }
+        app::View::OfflineQueue => app.handle_offline_key(code),
+        app::View::PendingRetry => app.handle_pending_retry_key(code),
+        app::View::ResumeQueue => app.handle_resume_key(code),
+        app::View::Migration => app.handle_migration_key(code),
+        app::View::StaleFilesConfirm => app.handle_stale_files_confirm_key(code),
+        app::View::PruneConfirm => app.handle_prune_confirm_key(code),
+        app::View::MirrorConfirm => app.handle_mirror_key(code),
+        app::View::ConflictResolve => app.handle_conflict_key(code),
+        app::View::McpReview => app.handle_mcp_review_key(code),
+        app::View::HookLogs => { app.handle_hook_logs_key(code); Ok(()) }
+        app::View::GitStatus => { app.handle_git_status_key(code); Ok(()) }
+        app::View::Search => { app.handle_search_key(code); Ok(()) }
+        app::View::SortMenu => app.handle_sort_menu_key(code),
+        app::View::HookScaffold => app.handle_hook_scaffold_key(code),
+        app::View::KeywordEdit => app.handle_keyword_edit_key(code),
+        app::View::SettingsHistory => app.handle_settings_history_key(code),
+        app::View::SettingsKeys => app.handle_settings_keys_key(code),
         app::View::Diff => handle_diff_input(app, code),
         app::View::Sources => app.handle_sources_key(code),
         app::View::SourceAddType => app.handle_source_type_key(code),
@@ -46,20 +176,16 @@ pub(crate) fn dispatch_key(
 
 /// Handle the SourceSyncing view with non-blocking poll.
 pub(crate) fn handle_source_syncing(app: &mut App) -> Result<()> {
-    use crossterm::event::poll;
-    if poll(Duration::from_millis(100))? {
-        if let Some(code) = read_key_press()? {
-            if code == KeyCode::Char('q') {
-                if let Some(tx) = app.source_sync_cancel_tx.take() {
-                    let _ = tx.send(());
-                }
-                app.source_sync_rx = None;
-                app.source_sync_status = Some(app::SyncStatus::Error("Cancelled".to_string()));
-                app.current_view = app::View::Sources;
-            }
+    if let Some(key) = poll_key_press()?
+        && key.code == KeyCode::Char('q')
+    {
+        if let Some(tx) = app.source_sync_cancel_tx.take() {
+            let _ = tx.send(());
         }
+        app.source_sync_rx = None;
+        app.source_sync_status = Some(app::SyncStatus::Error("Cancelled".to_string()));
+        app.current_view = app::View::Sources;
     }
-    app.tick();
     app.check_source_sync();
     Ok(())
 }
@@ -70,7 +196,8 @@ fn handle_list_input(app: &mut App, key: KeyCode) -> Result<()> {
         KeyCode::Char('q') => { app.should_quit = true; return Ok(()); }
         KeyCode::Char('t') => {
             app.theme.toggle();
-            app.status_message = Some(format!("Theme: {}", app.theme.mode().name()));
+            app.theme_changed = true;
+            app.status_message = Some(format!("Theme: {}", app.theme.name()));
             return Ok(());
         }
         KeyCode::Tab | KeyCode::BackTab => { app.toggle_focus(); return Ok(()); }
@@ -104,6 +231,7 @@ fn handle_tab_focus_keys(app: &mut App, key: KeyCode) {
 
 /// Keys consumed while the content pane holds focus — the original list
 /// view bindings without the now-removed `1`-`0`/`-` direct tab shortcuts.
+/// Keys that only apply on some tabs go through the tab action registry.
 fn handle_content_focus_keys(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
         KeyCode::Char('h') | KeyCode::Left => handle_folder_collapse(app),
@@ -112,16 +240,17 @@ fn handle_content_focus_keys(app: &mut App, key: KeyCode) -> Result<()> {
         }
         KeyCode::Down | KeyCode::Char('j') => app.next_item(),
         KeyCode::Up | KeyCode::Char('k') => app.prev_item(),
+        KeyCode::Char('g') | KeyCode::Home => app.goto_list_edge(false),
+        KeyCode::Char('G') | KeyCode::End => app.goto_list_edge(true),
         KeyCode::Char(' ') => app.toggle_selected(),
         KeyCode::Char('a') => app.select_all(),
         KeyCode::Char('n') => app.deselect_all(),
         KeyCode::Enter => handle_enter(app)?,
-        KeyCode::Char('d') => app.show_diff()?,
         KeyCode::Char('i') => app.install_selected()?,
         KeyCode::Char('r') => app.remove_selected()?,
-        KeyCode::Char('s') | KeyCode::Char('u') => handle_default_toggle(app, key)?,
-        KeyCode::Char('o') => { if app.tab == app::Tab::McpServers { app.toggle_mcp_scope(); } }
-        _ => {}
+        KeyCode::Char('S') => app.open_sort_menu(),
+        KeyCode::Esc => { app.dismiss_hint(); }
+        key => { app.run_tab_action(key)?; }
     }
     Ok(())
 }
@@ -139,18 +268,15 @@ fn handle_enter(app: &mut App) -> Result<()> {
     Ok(())
 }
 
-fn handle_default_toggle(app: &mut App, key: KeyCode) -> Result<()> {
-    match (key, app.tab) {
-        (KeyCode::Char('s'), app::Tab::OutputStyles) => app.set_default_style()?,
-        (KeyCode::Char('s'), app::Tab::Statusline) => app.set_statusline()?,
-        (KeyCode::Char('u'), app::Tab::OutputStyles) => app.unset_default_style()?,
-        (KeyCode::Char('u'), app::Tab::Statusline) => app.unset_statusline()?,
-        _ => {}
-    }
-    Ok(())
-}
-
 fn handle_diff_input(app: &mut App, key: KeyCode) -> Result<()> {
+    // Adopting needs two presses in a row; any other key disarms it.
+    if key == KeyCode::Char('A') {
+        return app.request_adopt();
+    }
+    app.adopt_armed = false;
+    if app.handle_hunk_key(key)? {
+        return Ok(());
+    }
     match key {
         KeyCode::Char('q') | KeyCode::Esc => app.close_diff(),
         KeyCode::Down | KeyCode::Char('j') => app.scroll_diff_down(),
@@ -160,8 +286,10 @@ fn handle_diff_input(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
-/// Number of rows on the CLI selection screen: Claude, Codex, Sources.
-const CLI_SELECTION_OPTIONS: usize = 3;
+/// Number of rows on the CLI selection screen: every target, then Sources.
+fn cli_selection_options() -> usize {
+    app::TargetCli::all().len() + 1
+}
 
 fn handle_cli_selection(
     app: &mut App,
@@ -175,11 +303,12 @@ fn handle_cli_selection(
             }
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if app.cli_selection_index + 1 < CLI_SELECTION_OPTIONS {
+            if app.cli_selection_index + 1 < cli_selection_options() {
                 app.cli_selection_index += 1;
             }
         }
         KeyCode::Enter => confirm_cli_selection(app, refresh_tx)?,
+        KeyCode::Char('p') => app.cycle_profile(),
         KeyCode::Char('q') => app.should_quit = true,
         _ => {}
     }
@@ -193,23 +322,33 @@ fn confirm_cli_selection(
     app: &mut App,
     refresh_tx: &std::sync::mpsc::Sender<Result<RefreshResult>>,
 ) -> Result<()> {
-    match app.cli_selection_index {
-        0 => {
-            app.select_cli(app::TargetCli::Claude)?;
-            loading::start_loading_thread(app, refresh_tx);
-        }
-        1 => {
-            app.select_cli(app::TargetCli::Codex)?;
+    match app::TargetCli::all().get(app.cli_selection_index) {
+        Some(&cli) => {
+            app.select_cli(cli)?;
             loading::start_loading_thread(app, refresh_tx);
         }
-        2 => {
-            app.current_view = app::View::Sources;
-        }
-        _ => {}
+        None => app.current_view = app::View::Sources,
     }
     Ok(())
 }
 
+/// Select the CLI used last time without showing the selection screen.
+/// If its config dir is gone or it can't be selected, the screen is shown
+/// as usual, with that CLI highlighted.
+pub(crate) fn select_startup_cli(
+    app: &mut App,
+    refresh_tx: &std::sync::mpsc::Sender<Result<RefreshResult>>,
+) {
+    let Some(cli) = app.startup_cli.take() else { return };
+    if !cli.get_dest_dir().is_ok_and(|dir| dir.is_dir()) {
+        return;
+    }
+    match app.select_cli(cli) {
+        Ok(()) => loading::start_loading_thread(app, refresh_tx),
+        Err(e) => app.status_message = Some(format!("Can't open {}: {:#}", cli.display_name(), e)),
+    }
+}
+
 fn handle_env_input(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
         KeyCode::Esc => app.env_input_cancel(),
@@ -232,28 +371,92 @@ fn handle_project_path_input(app: &mut App, key: KeyCode) {
 }
 
 pub(crate) fn print_help() {
-    println!("hibi {} - Claude Code Config Installer", fs::VERSION);
+    println!("hibi {} - Claude Code Config Installer", crate::VERSION);
     println!();
     println!("Usage: hibi [OPTIONS]");
+    println!("       hibi <COMMAND>");
     println!();
     println!("Options:");
     println!("  -h, --help       Show this help message");
     println!("  -v, --version    Show version");
+    println!("      --source-dir <dir>");
+    println!("                   Use <dir> as the bundled source instead of looking for");
+    println!("                   one (also HIBI_SOURCE=<dir>)");
     println!("      --sync       Sync git sources without TUI");
+    println!("      --maintenance-summary");
+    println!("                   Print a one-line drift notice at most once a week");
+    println!("                   (intended as a SessionStart hook command)");
+    println!("      --list-backups   List files saved before installs overwrote them");
+    println!("      --restore-backup <name|latest>");
+    println!("                   Copy a backup's files back into place");
+    println!("      --export-report <file>");
+    println!("                   Write the diffs of all pending changes (.md or .html)");
+    println!("      --drift-report <file>");
+    println!("                   Write every component's status, hashes and install time");
+    println!("                   (.json or .md)");
+    println!("      --export-hooks <file>");
+    println!("                   Write the hooks registered in settings.json as a shell");
+    println!("                   script that registers them again with jq");
+    println!("      --tab <name>     Open on a tab after CLI selection (agents, skills, mcp, ...)");
+    println!("                   Without it, the last tab used with that CLI is reopened");
+    println!("      --choose-cli Show the CLI selection screen instead of reopening the");
+    println!("                   CLI used last time");
+    println!("      --strict     Stop a batch at its first error and roll it back");
+    println!("      --record <file>");
+    println!("                   Record frames and key presses as an asciinema cast");
+    println!("      --simulate <script.yaml>");
+    println!("                   Drive the TUI headlessly from a scripted key sequence,");
+    println!("                   with an empty temporary home folder");
+    println!();
+    println!("Commands:");
+    println!("  state            Show how much space installer state takes up per CLI");
+    println!("  install [--cli <name>] [--dry-run] [--json]");
+    println!("                   Install every new or modified component for a CLI");
+    println!("                   (claude, codex, ...; default claude) without the TUI");
+    println!("  flush-pending [--json]");
+    println!("                   Retry the MCP and plugin installs queued while offline");
+    println!("  clean [--keep <period>]");
+    println!("                   Delete backups, hash caches, hook and audit logs older than");
+    println!("                   <period> (e.g. 30d, 2w, 12h; default {})", DEFAULT_RETENTION);
+    println!();
+    println!("install and flush-pending print a per-item summary (as JSON with --json)");
+    println!("and exit with 2 when some items failed, 3 when all of them did.");
     println!();
     println!("Run without options to launch the interactive installer.");
 }
 
+/// Bring installer state from older versions up to date before a headless
+/// command reads it, printing what changed. Failures are only warned about.
+fn run_migrations() {
+    migration_notes().iter().for_each(|n| println!("{}", n));
+}
+
+/// `run_migrations` for commands that report through a `RunSummary`:
+/// what changed is returned instead of printed.
+fn migration_notes() -> Vec<String> {
+    let Some(home) = dirs::home_dir() else { return Vec::new() };
+    match migrate::run_pending(&home) {
+        Ok(report) => report.notes,
+        Err(e) => {
+            eprintln!("Warning: {:#}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Make `targets:` CLIs known to headless commands that walk every target.
+fn register_targets() {
+    for warning in app::register_custom_targets() {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
 /// `hibi --sync`: fetch latest from git sources and print summary, no TUI.
 pub(crate) fn run_sync() -> Result<()> {
     let source_dir = source::find_source_dir()?;
 
-    // One-time cleanup of the orphaned bundled-source cache (now package-embedded).
-    match source::git::cleanup_bundled_cache() {
-        Ok(true) => println!("Removed orphaned bundled cache (~/.hibi/cache/bundled)"),
-        Ok(false) => {}
-        Err(e) => eprintln!("Warning: failed to clean bundled cache: {}", e),
-    }
+    // Migrate installer state from older versions before syncing.
+    run_migrations();
 
     println!("Syncing sources...");
     let (_, dummy_rx) = std::sync::mpsc::channel::<()>();
@@ -265,3 +468,242 @@ pub(crate) fn run_sync() -> Result<()> {
     println!("\nRun `hibi` to install changes.");
     Ok(())
 }
+
+/// `hibi --maintenance-summary`: weekly one-line drift notice, no TUI.
+/// Prints nothing when not due or when nothing drifted, and never fails
+/// loudly so it is safe to run from a SessionStart hook.
+pub(crate) fn run_maintenance_summary() -> Result<()> {
+    let Some(home) = dirs::home_dir() else { return Ok(()) };
+    let now = maintenance::now_secs();
+    if !maintenance::is_due(&home, now) {
+        return Ok(());
+    }
+
+    let summary = source::find_source_dir()
+        .and_then(|dir| source::resolve_all_sources(&dir))
+        .and_then(|resolved| {
+            maintenance::collect_drift(&resolved.sources, &home.join(".claude"), &resolved.directories)
+        });
+    match summary {
+        Ok(summary) if !summary.is_empty() => println!("{}", summary.notice()),
+        Ok(_) => {}
+        Err(e) => eprintln!("hibi: maintenance check failed: {:#}", e),
+    }
+
+    if let Err(e) = maintenance::mark_done(&home, now) {
+        eprintln!("hibi: {:#}", e);
+    }
+    Ok(())
+}
+
+/// `hibi --export-report <file>`: write the diffs of every new or modified
+/// Claude component to `file`, as HTML for `.html` and Markdown otherwise.
+pub(crate) fn run_export_report(path: &str) -> Result<()> {
+    let resolved = source::resolve_all_sources(&source::find_source_dir()?)?;
+    let dest_dir = app::TargetCli::Claude.get_dest_dir()?;
+    let components = fs::scanner::scan_all_sources(
+        &resolved.sources, &dest_dir, app::TargetCli::Claude, &resolved.directories, fs::scanner::ScanOptions::default(),
+    )?;
+    let pending: Vec<_> = components.iter().filter(|c| fs::report::is_pending(c)).collect();
+
+    let path = std::path::Path::new(path);
+    let report = fs::report::render_report(&pending, fs::report::ReportFormat::from_path(path));
+    std::fs::write(path, report)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Wrote report of {} change(s) to {}", pending.len(), path.display());
+    Ok(())
+}
+
+/// `hibi --drift-report <file>`: write the drift of every Claude component
+/// to `file`, as JSON for `.json` and Markdown otherwise.
+pub(crate) fn run_drift_report(path: &str) -> Result<()> {
+    let resolved = source::resolve_all_sources(&source::find_source_dir()?)?;
+    let dest_dir = app::TargetCli::Claude.get_dest_dir()?;
+    let components = fs::scanner::scan_all_sources(
+        &resolved.sources, &dest_dir, app::TargetCli::Claude, &resolved.directories, fs::scanner::ScanOptions::default(),
+    )?;
+
+    let path = std::path::Path::new(path);
+    let report = fs::drift::drift_report(&components, app::TargetCli::Claude.display_name(), &dest_dir);
+    std::fs::write(path, fs::drift::render_drift_report(&report, fs::drift::DriftFormat::from_path(path)))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Wrote drift report of {} component(s) to {}", report.components.len(), path.display());
+    Ok(())
+}
+
+/// `hibi --export-hooks <file>`: write the hooks registered in Claude's
+/// settings.json as a shell script, annotated from the hooks in the sources.
+pub(crate) fn run_export_hooks(path: &str) -> Result<()> {
+    let resolved = source::resolve_all_sources(&source::find_source_dir()?)?;
+    let dest_dir = app::TargetCli::Claude.get_dest_dir()?;
+    let components = fs::scanner::scan_all_sources(
+        &resolved.sources, &dest_dir, app::TargetCli::Claude, &resolved.directories, fs::scanner::ScanOptions::default(),
+    )?;
+    let configs: Vec<_> = components.into_iter().filter_map(|c| c.hook_config).collect();
+
+    let path = std::path::Path::new(path);
+    std::fs::write(path, fs::hooks_script::hooks_script(&dest_dir, &configs)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Wrote hooks script to {}", path.display());
+    Ok(())
+}
+
+/// Snapshots for every CLI config dir, newest first.
+fn all_backups() -> Result<Vec<(app::TargetCli, fs::backup::SnapshotInfo)>> {
+    register_targets();
+    let mut all = Vec::new();
+    for cli in app::TargetCli::all() {
+        let dest = cli.get_dest_dir()?;
+        all.extend(fs::backup::list_snapshots(&dest)?.into_iter().map(|s| (cli, s)));
+    }
+    all.sort_by(|a, b| b.1.name.cmp(&a.1.name));
+    Ok(all)
+}
+
+/// `hibi --list-backups`: print the snapshots taken before installs.
+pub(crate) fn run_list_backups() -> Result<()> {
+    run_migrations();
+    let backups = all_backups()?;
+    if backups.is_empty() {
+        println!("No backups found.");
+        return Ok(());
+    }
+    for (cli, snapshot) in &backups {
+        println!(
+            "{}  {}  {} ({} file(s))",
+            snapshot.name,
+            hibi_core::local_time::snapshot_display(&snapshot.name),
+            cli.config_dir_name(),
+            snapshot.files.len(),
+        );
+        for file in &snapshot.files {
+            println!("    {}", file.display());
+        }
+    }
+    println!("\nRestore one with `hibi --restore-backup <name>` (or `latest`).");
+    Ok(())
+}
+
+/// `hibi --restore-backup <name|latest>`: copy a snapshot's files back.
+pub(crate) fn run_restore_backup(name: &str) -> Result<()> {
+    run_migrations();
+    let backups = all_backups()?;
+    let Some((cli, snapshot)) = (if name == "latest" {
+        backups.first()
+    } else {
+        backups.iter().find(|(_, s)| s.name == name)
+    }) else {
+        anyhow::bail!("No backup named '{}' (see `hibi --list-backups`)", name);
+    };
+
+    let restored = fs::backup::restore(&cli.get_dest_dir()?, &snapshot.name)?;
+    for path in &restored {
+        println!("Restored {}", source::config::contract_tilde(path));
+    }
+    println!("{} file(s) restored from {}", restored.len(), snapshot.name);
+    Ok(())
+}
+
+/// `hibi state`: how much space the installer state of each CLI takes up.
+pub(crate) fn run_state_report() -> Result<()> {
+    run_migrations();
+    register_targets();
+    let mut total = 0;
+    for cli in app::TargetCli::all() {
+        let dest = cli.get_dest_dir()?;
+        let usage = fs::state::usage(&dest);
+        let cli_total: u64 = usage.iter().map(|u| u.bytes).sum();
+        if cli_total == 0 {
+            continue;
+        }
+        total += cli_total;
+        println!("{}  {}", source::config::contract_tilde(&fs::state::state_dir(&dest)), fs::state::format_bytes(cli_total));
+        for part in usage.iter().filter(|u| u.bytes > 0) {
+            let note = if part.kind.prunable() { "" } else { "  (kept)" };
+            println!("    {:<14} {:>10}{}", part.label, fs::state::format_bytes(part.bytes), note);
+        }
+    }
+    if total == 0 {
+        println!("No installer state found.");
+        return Ok(());
+    }
+    println!("\nTotal {}. Prune old backups, caches and logs with `hibi clean --keep 30d`.", fs::state::format_bytes(total));
+    Ok(())
+}
+
+/// `hibi flush-pending`: retry the installs each CLI queued while offline.
+/// The ones that fail again stay queued.
+pub(crate) fn run_flush_pending(json: bool) -> Result<()> {
+    let mut summary = RunSummary { notes: migration_notes(), ..Default::default() };
+    register_targets();
+    let mut found = false;
+    for cli in app::TargetCli::all() {
+        let dest = cli.get_dest_dir()?;
+        if fs::pending::load(&dest)?.is_empty() {
+            continue;
+        }
+        found = true;
+        fs::audit::open(&dest);
+        for result in fs::pending::flush(&dest, cli)? {
+            match result.error {
+                None => summary.installed(cli.display_name(), result.op.name()),
+                Some(e) => summary.failed(cli.display_name(), result.op.name(), format!("{} (stays queued)", e)),
+            }
+        }
+    }
+    if !found && !json {
+        summary.notes.iter().for_each(|note| println!("{}", note));
+        println!("No queued installs.");
+        return Ok(());
+    }
+    summary.finish(json, "installed")
+}
+
+/// `hibi install [--cli <name>] [--dry-run] [--json]`: install every new
+/// or modified component for one CLI (Claude Code by default) without
+/// the TUI, via `hibi_core::sync`.
+pub(crate) fn run_install(cli: Option<&str>, dry_run: bool, json: bool) -> Result<()> {
+    register_targets();
+    let target = match cli {
+        None => app::TargetCli::Claude,
+        Some(name) => app::TargetCli::all()
+            .into_iter()
+            .find(|t| t.program_name().eq_ignore_ascii_case(name) || t.display_name().eq_ignore_ascii_case(name))
+            .with_context(|| format!("Unknown CLI '{}'", name))?,
+    };
+    let mut options = hibi_core::SyncOptions::new(target);
+    options.dry_run = dry_run;
+    let report = hibi_core::sync(&options)?;
+
+    let cli = target.display_name();
+    let mut summary = RunSummary { notes: report.notes, ..Default::default() };
+    report.installed.into_iter().for_each(|name| summary.installed(cli, name));
+    report.unchanged.into_iter().for_each(|name| summary.skipped(cli, name));
+    report.failed.into_iter().for_each(|(name, error)| summary.failed(cli, name, error));
+    summary.finish(json, if dry_run { "would install" } else { "installed" })
+}
+
+/// `hibi clean [--keep <period>]`: delete backups, hash caches and hook
+/// logs older than the retention period (default `DEFAULT_RETENTION`).
+pub(crate) fn run_clean(keep: Option<&str>) -> Result<()> {
+    run_migrations();
+    register_targets();
+    let period = keep.unwrap_or(DEFAULT_RETENTION);
+    let keep = fs::state::parse_retention(period)?;
+    let now = std::time::SystemTime::now();
+    let (mut removed, mut freed) = (0, 0);
+    for cli in app::TargetCli::all() {
+        let report = fs::state::clean(&cli.get_dest_dir()?, keep, now)?;
+        for path in &report.removed {
+            println!("Removed {}", source::config::contract_tilde(path));
+        }
+        removed += report.removed.len();
+        freed += report.freed_bytes;
+    }
+    if removed == 0 {
+        println!("Nothing older than {} to clean.", period);
+    } else {
+        println!("{} item(s) removed, {} freed", removed, fs::state::format_bytes(freed));
+    }
+    Ok(())
+}
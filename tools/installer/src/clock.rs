@@ -0,0 +1,38 @@
+//! Wall-clock animation timing shared by every view.
+//!
+//! Spinners used to advance once per view tick, so they sped up, slowed down
+//! or froze depending on how long each view's event handling took. Deriving
+//! the frame from elapsed time keeps every animation at the same pace.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Time between animation frames.
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+fn started() -> Instant {
+    static START: OnceLock<Instant> = OnceLock::new();
+    *START.get_or_init(Instant::now)
+}
+
+/// Current animation frame. Increases by one every `FRAME_INTERVAL`
+/// however often the caller redraws.
+pub fn frame() -> usize {
+    frame_at(started().elapsed())
+}
+
+fn frame_at(elapsed: Duration) -> usize {
+    (elapsed.as_millis() / FRAME_INTERVAL.as_millis()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_follows_elapsed_time() {
+        assert_eq!(frame_at(Duration::ZERO), 0);
+        assert_eq!(frame_at(Duration::from_millis(99)), 0);
+        assert_eq!(frame_at(Duration::from_millis(250)), 2);
+    }
+}
@@ -1,341 +0,0 @@
-use std::path::Path;
-use anyhow::Result;
-use serde_json::Value;
-
-use crate::component::HookConfig;
-
-/// Convert a Windows path to MSYS-style path for Git Bash compatibility.
-/// `C:\Users\...` -> `/c/Users/...`, handles any drive letter.
-#[allow(dead_code)]
-fn to_msys_path(path: &Path) -> String {
-    let s = path.to_string_lossy().replace('\\', "/");
-    if s.len() >= 3 && s.as_bytes()[1] == b':' && s.as_bytes()[0].is_ascii_alphabetic() {
-        let drive = (s.as_bytes()[0] as char).to_ascii_lowercase();
-        format!("/{}{}", drive, &s[2..])
-    } else {
-        s
-    }
-}
-
-/// Read settings.json from dest_dir, returning empty object if file doesn't exist.
-fn read_settings(dest_dir: &Path) -> Result<Value> {
-    let settings_path = dest_dir.join("settings.json");
-    if settings_path.exists() {
-        let content = std::fs::read_to_string(&settings_path)?;
-        Ok(serde_json::from_str(&content)?)
-    } else {
-        Ok(serde_json::json!({}))
-    }
-}
-
-/// Write settings.json to dest_dir, creating parent directories if needed.
-fn write_settings(dest_dir: &Path, settings: &Value) -> Result<()> {
-    let settings_path = dest_dir.join("settings.json");
-    if let Some(parent) = settings_path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let output = serde_json::to_string_pretty(settings)?;
-    std::fs::write(&settings_path, output)?;
-    Ok(())
-}
-
-pub fn set_output_style(dest_dir: &Path, style_name: &str) -> Result<()> {
-    let mut settings = read_settings(dest_dir)?;
-    settings["outputStyle"] = serde_json::json!(style_name);
-    write_settings(dest_dir, &settings)
-}
-
-pub fn set_statusline(dest_dir: &Path, script_name: &str) -> Result<()> {
-    let mut settings = read_settings(dest_dir)?;
-
-    // Windows: convert to MSYS-style path for Git Bash compatibility
-    // macOS/Linux: use ~ shorthand derived from dest_dir
-    let statusline_command = if cfg!(windows) {
-        let abs_path = dest_dir.join("statusline").join(script_name);
-        let unix_path = to_msys_path(&abs_path);
-        format!("cat | {}", unix_path)
-    } else {
-        let dir_name = dest_dir.file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| ".claude".to_string());
-        format!("~/{}/statusline/{}", dir_name, script_name)
-    };
-    settings["statusLine"] = serde_json::json!({
-        "type": "command",
-        "command": statusline_command
-    });
-
-    write_settings(dest_dir, &settings)
-}
-
-pub fn unset_output_style(dest_dir: &Path) -> Result<()> {
-    let settings_path = dest_dir.join("settings.json");
-    if !settings_path.exists() {
-        return Ok(());
-    }
-
-    let mut settings = read_settings(dest_dir)?;
-    if let Value::Object(ref mut map) = settings {
-        map.remove("outputStyle");
-    }
-    write_settings(dest_dir, &settings)
-}
-
-/// Remove `outputStyle` from settings.json ONLY if it currently points at
-/// `style_name`. Used during remove flow so that deleting an unrelated style
-/// file doesn't clear the user's chosen default.
-///
-/// `style_name` may carry a `.md` suffix (component name) or not (settings
-/// value); both forms compare equal after stripping.
-pub(super) fn unregister_output_style_if_matches(dest_dir: &Path, style_name: &str) -> Result<()> {
-    let settings_path = dest_dir.join("settings.json");
-    if !settings_path.exists() {
-        return Ok(());
-    }
-
-    let mut settings = read_settings(dest_dir)?;
-    let target = style_name.strip_suffix(".md").unwrap_or(style_name);
-    let current = settings.get("outputStyle").and_then(|v| v.as_str());
-
-    if current == Some(target) {
-        if let Value::Object(ref mut map) = settings {
-            map.remove("outputStyle");
-        }
-        write_settings(dest_dir, &settings)?;
-    }
-    Ok(())
-}
-
-pub fn unset_statusline(dest_dir: &Path) -> Result<()> {
-    let settings_path = dest_dir.join("settings.json");
-    if !settings_path.exists() {
-        return Ok(());
-    }
-
-    let mut settings = read_settings(dest_dir)?;
-    if let Value::Object(ref mut map) = settings {
-        map.remove("statusLine");
-    }
-    write_settings(dest_dir, &settings)
-}
-
-/// Check if a hook with the given command path already exists in the event array.
-fn hook_exists_in_array(arr: &[Value], hook_command: &str) -> bool {
-    arr.iter().any(|item| {
-        item.get("hooks")
-            .and_then(|h| h.as_array())
-            .map(|hooks_arr| {
-                hooks_arr.iter().any(|hook| {
-                    hook.get("command")
-                        .and_then(|c| c.as_str())
-                        .map(|cmd| cmd == hook_command)
-                        .unwrap_or(false)
-                })
-            })
-            .unwrap_or(false)
-    })
-}
-
-/// Build a hook entry JSON value from HookConfig.
-fn build_hook_entry(config: &HookConfig, hook_command: &str) -> Value {
-    let mut hook_entry = serde_json::json!({
-        "type": config.hook_type,
-        "command": hook_command
-    });
-    if let Some(timeout) = config.timeout {
-        hook_entry["timeout"] = serde_json::json!(timeout);
-    }
-    serde_json::json!({
-        "hooks": [hook_entry]
-    })
-}
-
-pub(super) fn register_hook_in_settings(dest_dir: &Path, config: &HookConfig) -> Result<()> {
-    let hook_command = config.hook_command_path(dest_dir);
-    let mut settings = read_settings(dest_dir)?;
-
-    // Ensure hooks object and event array exist
-    if settings.get("hooks").is_none() {
-        settings["hooks"] = serde_json::json!({});
-    }
-    let event_name = &config.event;
-    let hooks = settings.get_mut("hooks").unwrap();
-    if hooks.get(event_name).is_none() {
-        hooks[event_name] = serde_json::json!([]);
-    }
-
-    let event_hooks = hooks.get_mut(event_name).unwrap();
-    if let Value::Array(arr) = event_hooks {
-        if !hook_exists_in_array(arr, &hook_command) {
-            arr.push(build_hook_entry(config, &hook_command));
-        }
-    }
-
-    write_settings(dest_dir, &settings)
-}
-
-pub(crate) fn unregister_hook_from_settings(dest_dir: &Path, config: &HookConfig) -> Result<()> {
-    let settings_path = dest_dir.join("settings.json");
-    if !settings_path.exists() {
-        return Ok(());
-    }
-
-    let mut settings = read_settings(dest_dir)?;
-
-    // Get hooks object
-    let hooks = match settings.get_mut("hooks") {
-        Some(Value::Object(h)) => h,
-        _ => return Ok(()),
-    };
-
-    // Get event array
-    let event_name = &config.event;
-    let event_hooks = match hooks.get_mut(event_name) {
-        Some(Value::Array(arr)) => arr,
-        _ => return Ok(()),
-    };
-
-    // Remove hook entries that match config.name
-    event_hooks.retain(|item| {
-        !item.get("hooks")
-            .and_then(|h| h.as_array())
-            .map(|hooks_arr| {
-                hooks_arr.iter().any(|hook| {
-                    hook.get("command")
-                        .and_then(|c| c.as_str())
-                        .map(|cmd| cmd.contains(&config.name))
-                        .unwrap_or(false)
-                })
-            })
-            .unwrap_or(false)
-    });
-
-    // Clean up empty structures
-    if event_hooks.is_empty() {
-        hooks.remove(event_name);
-    }
-    if hooks.is_empty() {
-        if let Value::Object(ref mut map) = settings {
-            map.remove("hooks");
-        }
-    }
-
-    write_settings(dest_dir, &settings)
-}
-
-/// Removes installer-managed sections from settings.json
-/// This includes: hooks, outputStyle (if it's a known installed style), statusLine (if it's a known installed statusline)
-/// Preserves user settings like env, model, enabledPlugins, etc.
-pub fn remove_managed_settings_sections(dest_dir: &Path) -> Result<()> {
-    let settings_path = dest_dir.join("settings.json");
-    if !settings_path.exists() {
-        return Ok(());
-    }
-
-    let mut settings = read_settings(dest_dir)?;
-    if let Value::Object(ref mut map) = settings {
-        map.remove("hooks");
-        map.remove("outputStyle");
-        map.remove("statusLine");
-    }
-    write_settings(dest_dir, &settings)
-}
-
-/// Auto-register an output style in settings.json if no style is currently set
-pub(super) fn register_output_style_in_settings(dest_dir: &Path, style_name: &str) -> Result<()> {
-    let mut settings = read_settings(dest_dir)?;
-
-    // Only set if outputStyle is not already configured
-    if settings.get("outputStyle").is_none() {
-        let style_name = style_name.strip_suffix(".md").unwrap_or(style_name);
-        settings["outputStyle"] = serde_json::json!(style_name);
-        write_settings(dest_dir, &settings)?;
-    }
-
-    Ok(())
-}
-
-/// Auto-register a statusline in settings.json if no statusline is currently set
-pub(super) fn register_statusline_in_settings(dest_dir: &Path, statusline_name: &str) -> Result<()> {
-    let mut settings = read_settings(dest_dir)?;
-
-    // Only set if statusLine is not already configured
-    if settings.get("statusLine").is_none() {
-        settings["statusLine"] = serde_json::json!(statusline_name);
-        write_settings(dest_dir, &settings)?;
-    }
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn unique_dest(label: &str) -> std::path::PathBuf {
-        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
-        let dir = std::env::temp_dir().join(format!("hibi_settings_{label}_{nanos}"));
-        std::fs::create_dir_all(&dir).unwrap();
-        dir
-    }
-
-    fn read_output_style(dest: &Path) -> Option<String> {
-        let path = dest.join("settings.json");
-        let raw = std::fs::read_to_string(&path).ok()?;
-        let v: Value = serde_json::from_str(&raw).ok()?;
-        v.get("outputStyle").and_then(|x| x.as_str()).map(String::from)
-    }
-
-    #[test]
-    fn unregister_clears_when_current_matches() {
-        let dest = unique_dest("clears");
-        std::fs::write(dest.join("settings.json"), r#"{"outputStyle":"hibi_default"}"#).unwrap();
-
-        // Component name carries .md; settings value does not -- both forms match.
-        unregister_output_style_if_matches(&dest, "hibi_default.md").unwrap();
-
-        assert!(read_output_style(&dest).is_none(), "matching style should be cleared");
-
-        let _ = std::fs::remove_dir_all(&dest);
-    }
-
-    #[test]
-    fn unregister_preserves_when_current_differs() {
-        let dest = unique_dest("preserves");
-        std::fs::write(dest.join("settings.json"), r#"{"outputStyle":"important"}"#).unwrap();
-
-        // Removing an unrelated style must NOT clear the user's chosen default.
-        unregister_output_style_if_matches(&dest, "other.md").unwrap();
-
-        assert_eq!(read_output_style(&dest).as_deref(), Some("important"));
-
-        let _ = std::fs::remove_dir_all(&dest);
-    }
-
-    #[test]
-    fn unregister_is_noop_without_settings_file() {
-        let dest = unique_dest("nofile");
-        // No settings.json.
-        unregister_output_style_if_matches(&dest, "anything.md").unwrap();
-        assert!(!dest.join("settings.json").exists(), "must not create settings.json");
-
-        let _ = std::fs::remove_dir_all(&dest);
-    }
-
-    #[test]
-    fn unregister_is_noop_when_output_style_absent() {
-        let dest = unique_dest("absent");
-        std::fs::write(dest.join("settings.json"), r#"{"theme":"dark"}"#).unwrap();
-
-        unregister_output_style_if_matches(&dest, "anything.md").unwrap();
-
-        // Other keys must remain intact.
-        let raw = std::fs::read_to_string(dest.join("settings.json")).unwrap();
-        let v: Value = serde_json::from_str(&raw).unwrap();
-        assert_eq!(v.get("theme").and_then(|x| x.as_str()), Some("dark"));
-        assert!(v.get("outputStyle").is_none());
-
-        let _ = std::fs::remove_dir_all(&dest);
-    }
-}
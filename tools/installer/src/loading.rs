@@ -3,14 +3,33 @@ use std::thread;
 use std::time::Duration;
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, poll};
+use crossterm::event::KeyCode;
 
-use crate::app::{App, Tab, TargetCli, View};
+use crate::app::{App, BatchStage, Tab, TargetCli, View};
+use crate::cli;
 use crate::component;
 use crate::fs;
 use crate::mcp;
 use crate::plugin;
 use crate::process_exec;
+use crate::source::config::RefreshAfterBatch;
+
+/// Installing-view poll interval while messages are arriving.
+const FAST_POLL: Duration = Duration::from_millis(100);
+/// Longest the Installing view waits between polls once it goes quiet, e.g.
+/// while a CLI install runs for minutes without printing anything.
+const SLOW_POLL: Duration = Duration::from_millis(500);
+
+/// Next Installing-view poll interval: back to `FAST_POLL` after any
+/// activity, otherwise doubled up to `SLOW_POLL`. Key presses end the wait
+/// early either way, so only background messages are delayed.
+fn next_poll_interval(current: Duration, active: bool) -> Duration {
+    if active {
+        FAST_POLL
+    } else {
+        (current * 2).min(SLOW_POLL)
+    }
+}
 
 /// Outcome of a scan thread, scoped to the kind of data it actually
 /// touched. Used to skip the expensive `claude/codex mcp list` shell
@@ -25,6 +44,11 @@ pub(crate) enum RefreshResult {
         mcp_servers: Vec<mcp::McpServer>,
         plugins: Vec<plugin::Plugin>,
         cleaned_hooks: Vec<String>,
+        /// Outcome of the undo that ran before this scan, if any.
+        undo_report: Option<String>,
+        source_changes: Vec<crate::source::SourceChanges>,
+        /// What a Claude setup would carry over, when Codex was picked.
+        migration: Option<fs::migration::MigrationPlan>,
     },
     /// Refresh limited to filesystem-backed component types — Agents,
     /// Commands, Contexts, Rules, Skills, Hooks, Styles, Statusline,
@@ -65,12 +89,23 @@ pub(crate) struct ProcessingChannels {
     cancel_tx: Sender<()>,
     cancel_rx: Receiver<()>,
     current_cancel_tx: Sender<()>,
+    /// Item index of the running process, recorded into
+    /// `app.processed_items` when it finishes.
+    current_idx: Option<usize>,
+    /// Stage of the running batch hook, when the running process is one
+    /// rather than an item.
+    current_hook: Option<BatchStage>,
+    /// Progress lines (e.g. cleanup status) streamed from the process thread.
+    progress_tx: Sender<String>,
+    progress_rx: Receiver<String>,
     pub(crate) processing_active: bool,
     pub(crate) refresh_tx: Sender<Result<RefreshResult>>,
     pub(crate) refresh_rx: Receiver<Result<RefreshResult>>,
-    pub(crate) preflight_tx: Sender<Result<()>>,
-    pub(crate) preflight_rx: Receiver<Result<()>>,
+    pub(crate) preflight_tx: Sender<Result<bool>>,
+    pub(crate) preflight_rx: Receiver<Result<bool>>,
     pub(crate) preflight_active: bool,
+    /// How long the Installing view waits for a key each tick.
+    poll_interval: Duration,
 }
 
 impl ProcessingChannels {
@@ -78,8 +113,9 @@ impl ProcessingChannels {
         let (process_tx, process_rx) = mpsc::channel::<Result<String>>();
         let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
         let current_cancel_tx = cancel_tx.clone();
+        let (progress_tx, progress_rx) = mpsc::channel::<String>();
         let (refresh_tx, refresh_rx) = mpsc::channel::<Result<RefreshResult>>();
-        let (preflight_tx, preflight_rx) = mpsc::channel::<Result<()>>();
+        let (preflight_tx, preflight_rx) = mpsc::channel::<Result<bool>>();
 
         Self {
             process_tx,
@@ -87,12 +123,17 @@ impl ProcessingChannels {
             cancel_tx,
             cancel_rx,
             current_cancel_tx,
+            current_idx: None,
+            current_hook: None,
+            progress_tx,
+            progress_rx,
             processing_active: false,
             refresh_tx,
             refresh_rx,
             preflight_tx,
             preflight_rx,
             preflight_active: false,
+            poll_interval: FAST_POLL,
         }
     }
 
@@ -100,7 +141,7 @@ impl ProcessingChannels {
     /// each new preflight so any stale send from a previously-cancelled
     /// thread cannot bleed into the next attempt's result.
     pub(crate) fn reset_preflight_channel(&mut self) {
-        let (tx, rx) = mpsc::channel::<Result<()>>();
+        let (tx, rx) = mpsc::channel::<Result<bool>>();
         self.preflight_tx = tx;
         self.preflight_rx = rx;
     }
@@ -121,35 +162,76 @@ impl ProcessingChannels {
     }
 }
 
+/// Move streamed progress lines into the processing log.
+fn drain_progress(app: &mut App, channels: &ProcessingChannels) {
+    while let Ok(line) = channels.progress_rx.try_recv() {
+        app.processing_log.push(line);
+    }
+}
+
 /// Handle completion of a processing thread.
 fn handle_process_completion(app: &mut App, channels: &mut ProcessingChannels) {
     match channels.process_rx.try_recv() {
+        Ok(result) if channels.current_hook.is_some() => {
+            drain_progress(app, channels);
+            channels.processing_active = false;
+            app.cancelling = false;
+            channels.reset_cancel_channel();
+            if let Some(stage) = channels.current_hook.take() {
+                app.finish_batch_hook(stage, result);
+            }
+        }
         Ok(result) => {
+            drain_progress(app, channels);
             channels.processing_active = false;
             app.cancelling = false;
+            let cancelled = matches!(&result, Err(e) if e.to_string().contains("Cancelled by user"));
+            // A skip is a cancel the user asked to move past: the item is
+            // neither done nor failed.
+            let skipped = std::mem::take(&mut app.skipping_item) && cancelled;
+            let idx = channels.current_idx.take();
+            if let Some(idx) = idx.filter(|_| !skipped) {
+                match &result {
+                    Ok(msg) if msg.starts_with("[SKIP]") => app.mark_already_configured(idx),
+                    _ => app.processed_items.push((idx, result.is_ok())),
+                }
+                match &result {
+                    Ok(msg) if msg.starts_with("[ERR]") => {
+                        app.record_failure(idx, &anyhow::anyhow!("{}", msg.trim_start_matches("[ERR] ")));
+                    }
+                    Err(e) if !cancelled => app.record_failure(idx, e),
+                    _ => {}
+                }
+            }
             match result {
-                Ok(msg) => app.processing_log.push(msg),
-                Err(e) => {
-                    let err_msg = e.to_string();
-                    if err_msg.contains("Cancelled by user") {
-                        app.processing_log.push("[WARN] Cancelled by user".to_string());
-                        if !app.is_removing {
-                            app.processing_log.push("[INFO] Cleaning up cancelled installation...".to_string());
-                        }
-                        app.processing_queue.clear();
-                    } else if err_msg.contains("timed out") {
-                        app.processing_log.push(format!("[ERR] {}", err_msg));
-                        if !app.is_removing {
-                            app.processing_log.push("[INFO] Cleaning up timed out installation...".to_string());
-                        }
-                    } else {
-                        app.processing_log.push(format!("[ERR] {}", err_msg));
+                Ok(msg) => {
+                    // Component failures come back as an `[ERR]` line.
+                    let failed = msg.starts_with("[ERR]");
+                    app.processing_log.push(msg);
+                    if failed {
+                        app.abort_batch_on_error();
                     }
                 }
+                // Cleanup progress has already been streamed into the log;
+                // the error carries the cleanup verdict.
+                Err(e) if skipped => {
+                    let name = idx.map(|idx| process_exec::get_item_name(app, idx)).unwrap_or_default();
+                    app.processing_log.push(format!("[SKIP] {}: skipped ({})", name, e));
+                }
+                // Cancelling stops the batch; `s` is for this item only.
+                Err(e) if cancelled => {
+                    app.processing_log.push(format!("[WARN] {}", e));
+                    app.cancel_remaining_queue();
+                }
+                Err(e) => {
+                    app.processing_log.push(format!("[ERR] {}", e));
+                    app.abort_batch_on_error();
+                }
             }
 
             let progress = app.processing_progress.unwrap_or(0) + 1;
             app.processing_progress = Some(progress);
+            app.save_install_queue();
             channels.reset_cancel_channel();
 
             if app.processing_queue.is_empty() {
@@ -157,9 +239,19 @@ fn handle_process_completion(app: &mut App, channels: &mut ProcessingChannels) {
             }
         }
         Err(TryRecvError::Empty) => {}
+        Err(TryRecvError::Disconnected) if channels.current_hook.is_some() => {
+            channels.processing_active = false;
+            if let Some(stage) = channels.current_hook.take() {
+                app.finish_batch_hook(stage, Err(anyhow::anyhow!("Hook thread crashed")));
+            }
+        }
         Err(TryRecvError::Disconnected) => {
             channels.processing_active = false;
+            if let Some(idx) = channels.current_idx.take() {
+                app.processed_items.push((idx, false));
+            }
             app.processing_log.push("[ERR] Process thread crashed".to_string());
+            app.abort_batch_on_error();
             if app.processing_queue.is_empty() {
                 app.start_finish_processing();
             }
@@ -167,6 +259,25 @@ fn handle_process_completion(app: &mut App, channels: &mut ProcessingChannels) {
     }
 }
 
+/// Dequeue and spawn the next configured batch hook.
+fn dispatch_next_hook(app: &mut App, channels: &mut ProcessingChannels) {
+    let (stage, command) = app.batch_hook_queue.remove(0);
+    channels.processing_active = true;
+    channels.current_hook = Some(stage);
+    app.processing_log.push(format!("Running {} hook: {}", stage.label(), command));
+
+    let tx_clone = channels.process_tx.clone();
+    let output_tx = channels.progress_tx.clone();
+    channels.current_cancel_tx = channels.cancel_tx.clone();
+    let cancel_rx_for_thread = channels.take_cancel_rx();
+
+    thread::spawn(move || {
+        let result = fs::installer::run_batch_hook(&command, &cancel_rx_for_thread, Some(&output_tx))
+            .map(|_| format!("[OK] {} hook finished", stage.label()));
+        let _ = tx_clone.send(result);
+    });
+}
+
 /// Dequeue and spawn the next processing task.
 fn dispatch_next_process(app: &mut App, channels: &mut ProcessingChannels) {
     let idx = app.processing_queue.remove(0);
@@ -177,6 +288,7 @@ fn dispatch_next_process(app: &mut App, channels: &mut ProcessingChannels) {
     app.processing_log.push(format!("{} {}...", action, item_name));
 
     let tx_clone = channels.process_tx.clone();
+    let progress_tx = channels.progress_tx.clone();
     let is_removing = app.is_removing;
     let target_cli = app.target_cli.unwrap_or(TargetCli::Claude);
     let data = match process_exec::prepare(app, idx) {
@@ -188,16 +300,39 @@ fn dispatch_next_process(app: &mut App, channels: &mut ProcessingChannels) {
         }
     };
 
+    channels.current_idx = Some(idx);
+
     // Update current_cancel_tx BEFORE spawning the thread
     channels.current_cancel_tx = channels.cancel_tx.clone();
     let cancel_rx_for_thread = channels.take_cancel_rx();
 
     thread::spawn(move || {
-        let result = process_exec::execute(data, is_removing, target_cli, cancel_rx_for_thread);
+        let result = process_exec::execute(data, is_removing, target_cli, cancel_rx_for_thread, progress_tx);
         let _ = tx_clone.send(result);
     });
 }
 
+/// Refresh after an install/remove batch. Only the processed items are
+/// re-evaluated (see `App::apply_targeted_refresh`); the background scan
+/// below is the fallback when that fails.
+fn refresh_after_processing(app: &mut App, refresh_tx: &Sender<Result<RefreshResult>>) {
+    app.processing_log.push("".to_string());  // Empty line for spacing
+    match app.refresh_after_batch {
+        RefreshAfterBatch::Manual => return app.skip_refresh(),
+        RefreshAfterBatch::Delayed(secs) if !app.batch_aborted => return app.defer_refresh(secs),
+        _ => {}
+    }
+    app.processing_log.push("Refreshing status...".to_string());
+    // A rolled-back batch left nothing the targeted refresh could infer
+    // from the per-item results; rescan instead.
+    if app.batch_aborted {
+        start_refresh_thread(app, refresh_tx);
+    } else if !app.apply_targeted_refresh() {
+        app.processing_log.push("[WARN] Targeted refresh failed; rescanning".to_string());
+        start_refresh_thread(app, refresh_tx);
+    }
+}
+
 /// Start a background thread to rescan only the data the just-finished
 /// install/remove could have changed.
 ///
@@ -214,13 +349,16 @@ fn start_refresh_thread(app: &mut App, refresh_tx: &Sender<Result<RefreshResult>
     let sources = app.sources.clone();
     let dest_dir = app.dest_dir.clone();
     let target_cli = app.target_cli.unwrap_or(TargetCli::Claude);
+    let directories = app.directories.clone();
+    let scan_options = app.scan_options;
+    let mcp_project = app.mcp_project();
     let scope = RefreshScope::for_tab(app.tab);
 
     thread::spawn(move || {
         let result = match scope {
-            RefreshScope::Components => fs::scanner::scan_all_sources(&sources, &dest_dir, target_cli)
+            RefreshScope::Components => fs::scanner::scan_all_sources(&sources, &dest_dir, target_cli, &directories, scan_options)
                 .map(RefreshResult::Components),
-            RefreshScope::Mcp => fs::scanner::scan_all_mcp_sources(&sources, target_cli)
+            RefreshScope::Mcp => fs::scanner::scan_all_mcp_sources(&sources, target_cli, mcp_project.as_deref())
                 .map(|(servers, _warning)| RefreshResult::Mcp(servers)),
             RefreshScope::Plugins => fs::scanner::scan_all_plugin_sources(&sources)
                 .map(RefreshResult::Plugins),
@@ -245,8 +383,8 @@ fn check_refresh_completion(app: &mut App, refresh_rx: &Receiver<Result<RefreshR
         // doesn't end up partially fresh.
         Ok(Ok(RefreshResult::InitialLoad { components, mcp_servers, plugins, .. })) => {
             app.apply_components_refresh(components);
-            app.mcp_servers = mcp_servers;
-            app.plugins = plugins;
+            app.replace_mcp_servers(mcp_servers);
+            app.replace_plugins(plugins);
         }
         Ok(Err(e)) => {
             app.processing_log.push(format!("[ERROR] Refresh failed: {}", e));
@@ -265,27 +403,51 @@ fn check_refresh_completion(app: &mut App, refresh_rx: &Receiver<Result<RefreshR
 }
 
 /// Handle input during the Installing view.
-fn handle_installing_input(
-    app: &mut App,
-    key: KeyCode,
-    cancel_tx: &Sender<()>,
-    processing_active: &bool,
-) -> Result<()> {
+fn handle_installing_input(app: &mut App, key: KeyCode, channels: &ProcessingChannels) -> Result<()> {
+    let cancel_tx = &channels.current_cancel_tx;
+    let processing_active = &channels.processing_active;
+    if app.failure_detail.is_some() {
+        app.handle_failure_key(key);
+        return Ok(());
+    }
     match key {
         KeyCode::Esc => {
             if *processing_active && !app.cancelling {
                 let _ = cancel_tx.send(());
                 app.processing_log.push("[WARN] Cancelling current operation...".to_string());
                 app.cancelling = true;
+            } else if *processing_active {
+                // Second Esc while cancelling: abort the cleanup command.
+                let _ = cancel_tx.send(());
+                app.processing_log.push("[WARN] Aborting cleanup...".to_string());
             } else if app.processing_complete {
                 app.close_processing();
+                app.continue_migration()?;
             }
         }
         KeyCode::Char('q') | KeyCode::Enter => {
             if app.processing_complete {
                 app.close_processing();
+                app.continue_migration()?;
             }
         }
+        // Items only: a skipped batch hook would just be a failed one.
+        KeyCode::Char('s') if channels.current_idx.is_some() && !app.cancelling => {
+            let _ = cancel_tx.send(());
+            app.processing_log.push("[WARN] Skipping current item...".to_string());
+            app.cancelling = true;
+            app.skipping_item = true;
+        }
+        KeyCode::Char('C') if !app.processing_complete => {
+            app.cancel_remaining_queue();
+            if *processing_active && !app.cancelling {
+                let _ = cancel_tx.send(());
+                app.processing_log.push("[WARN] Cancelling current operation...".to_string());
+                app.cancelling = true;
+            }
+        }
+        KeyCode::Char('c') => app.show_commands = !app.show_commands,
+        KeyCode::Char('e') => app.open_failure_detail(),
         _ => {}
     }
     Ok(())
@@ -293,24 +455,31 @@ fn handle_installing_input(
 
 /// Handle a single tick of the Installing view.
 pub(crate) fn handle_installing_view(app: &mut App, channels: &mut ProcessingChannels) -> Result<()> {
-    if poll(Duration::from_millis(100))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Release {
-                handle_installing_input(app, key.code, &channels.current_cancel_tx, &channels.processing_active)?;
-            }
-        }
+    let key = cli::poll_key_press_within(channels.poll_interval)?;
+    let was_active = channels.processing_active;
+    let log_len = app.processing_log.len();
+    if let Some(key) = key
+        && !cli::handle_help_key(app, key.code)
+    {
+        handle_installing_input(app, key.code, channels)?;
     }
 
-    app.tick();
-
     if channels.processing_active {
+        drain_progress(app, channels);
         handle_process_completion(app, channels);
     }
-
-    if !channels.processing_active && !app.processing_queue.is_empty() {
+    let active = key.is_some()
+        || app.processing_log.len() != log_len
+        || channels.processing_active != was_active
+        || app.refreshing;
+    channels.poll_interval = next_poll_interval(channels.poll_interval, active);
+
+    if !channels.processing_active && !app.batch_hook_queue.is_empty() {
+        dispatch_next_hook(app, channels);
+    } else if !channels.processing_active && !app.processing_queue.is_empty() {
         dispatch_next_process(app, channels);
     } else if !channels.processing_active && app.processing_queue.is_empty() && app.needs_refresh && !app.refreshing {
-        start_refresh_thread(app, &channels.refresh_tx);
+        refresh_after_processing(app, &channels.refresh_tx);
     } else if app.refreshing {
         check_refresh_completion(app, &channels.refresh_rx);
     }
@@ -320,19 +489,17 @@ pub(crate) fn handle_installing_view(app: &mut App, channels: &mut ProcessingCha
 
 /// Handle a single tick of the Loading view.
 pub(crate) fn handle_loading_view(app: &mut App, refresh_rx: &Receiver<Result<RefreshResult>>) -> Result<()> {
-    if poll(Duration::from_millis(100))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Release && key.code == KeyCode::Char('q') {
-                app.should_quit = true;
-            }
-        }
+    if let Some(key) = cli::poll_key_press()?
+        && key.code == KeyCode::Char('q')
+    {
+        app.should_quit = true;
     }
 
-    app.tick();
-
     match refresh_rx.try_recv() {
-        Ok(Ok(RefreshResult::InitialLoad { components, mcp_servers, plugins, cleaned_hooks })) => {
-            app.finish_loading(components, mcp_servers, plugins, cleaned_hooks);
+        Ok(Ok(RefreshResult::InitialLoad { components, mcp_servers, plugins, cleaned_hooks, undo_report, source_changes, migration })) => {
+            app.source_changes = source_changes;
+            app.migration_plan = migration;
+            app.finish_loading(components, mcp_servers, plugins, cleaned_hooks, undo_report);
         }
         // The refresh channel is shared with start_refresh_thread, but
         // that thread only runs from the Installing view; the Loading
@@ -365,12 +532,14 @@ pub(crate) fn handle_loading_view(app: &mut App, refresh_rx: &Receiver<Result<Re
 /// matching receiver each tick.
 fn start_preflight_thread(
     app: &App,
-    preflight_tx: &Sender<Result<()>>,
+    preflight_tx: &Sender<Result<bool>>,
 ) {
     let tx = preflight_tx.clone();
     let target_cli = app.target_cli.unwrap_or(TargetCli::Claude);
+    let check_network = !app.is_removing && fs::pending::needs_network(target_cli, app.tab == Tab::Plugins);
     thread::spawn(move || {
-        let result = fs::installer::preflight_cli_available(target_cli);
+        let result = fs::installer::preflight_cli_available(target_cli)
+            .map(|()| !check_network || fs::pending::is_online());
         let _ = tx.send(result);
     });
 }
@@ -397,26 +566,22 @@ pub(crate) fn handle_preflighting_view(
         channels.preflight_active = true;
     }
 
-    if poll(Duration::from_millis(100))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Release {
-                handle_preflighting_input(app, key.code, channels);
-            }
-        }
+    if let Some(key) = cli::poll_key_press()? {
+        handle_preflighting_input(app, key.code, channels);
     }
 
-    app.tick();
-
     if !channels.preflight_active {
         // User cancelled this tick; the channel was already reset.
         return Ok(());
     }
 
     match channels.preflight_rx.try_recv() {
-        Ok(Ok(())) => {
+        Ok(Ok(online)) => {
             channels.preflight_active = false;
             if app.is_removing {
                 app.complete_remove_setup();
+            } else if !online {
+                app.current_view = View::OfflineQueue;
             } else {
                 app.complete_install_setup()?;
             }
@@ -471,19 +636,49 @@ fn handle_preflighting_input(
 pub(crate) fn start_loading_thread(
     app: &App,
     refresh_tx: &Sender<Result<RefreshResult>>,
+) {
+    spawn_full_scan(app, refresh_tx, false);
+}
+
+/// Undo the last install/remove batch (see `fs::journal::undo`), then
+/// rescan everything like `start_loading_thread`, so every tab reflects
+/// the restored state.
+pub(crate) fn start_undo_thread(
+    app: &App,
+    refresh_tx: &Sender<Result<RefreshResult>>,
+) {
+    spawn_full_scan(app, refresh_tx, true);
+}
+
+fn spawn_full_scan(
+    app: &App,
+    refresh_tx: &Sender<Result<RefreshResult>>,
+    undo_first: bool,
 ) {
     let tx_clone = refresh_tx.clone();
     let source_dir = app.source_dir.clone();
     let sources = app.sources.clone();
     let dest_dir = app.dest_dir.clone();
     let target_cli = app.target_cli.unwrap_or(TargetCli::Claude);
+    let directories = app.directories.clone();
+    let scan_options = app.scan_options;
+    let mcp_project = app.mcp_project();
 
     thread::spawn(move || {
+        let undo_report = undo_first.then(|| {
+            fs::journal::undo(&dest_dir, target_cli)
+                .unwrap_or_else(|e| format!("Undo failed: {:#}", e))
+        });
         let cleaned = fs::installer::auto_cleanup_deprecated_hooks(&source_dir, &dest_dir);
 
-        let components = fs::scanner::scan_all_sources(&sources, &dest_dir, target_cli);
-        let mcp_result = fs::scanner::scan_all_mcp_sources(&sources, target_cli);
+        let components = fs::scanner::scan_all_sources(&sources, &dest_dir, target_cli, &directories, scan_options);
+        let mcp_result = fs::scanner::scan_all_mcp_sources(&sources, target_cli, mcp_project.as_deref());
         let plugins = fs::scanner::scan_all_plugin_sources(&sources);
+        let source_changes = crate::source::uncommitted_source_changes(&sources);
+        // A plan that can't be worked out is simply not offered.
+        let migration = (target_cli == TargetCli::Codex)
+            .then(|| fs::migration::claude_to_codex(&sources, &directories).ok())
+            .flatten();
 
         let result = match (components, mcp_result, plugins) {
             (Ok(c), Ok((m, _mcp_warning)), Ok(p)) => Ok(RefreshResult::InitialLoad {
@@ -491,6 +686,9 @@ pub(crate) fn start_loading_thread(
                 mcp_servers: m,
                 plugins: p,
                 cleaned_hooks: cleaned,
+                undo_report,
+                source_changes,
+                migration,
             }),
             (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => Err(e),
         };
@@ -510,7 +708,7 @@ mod tests {
         // attempt — otherwise pressing 'i' twice would skip the probe.
         let mut channels = ProcessingChannels::new();
         let stale_tx = channels.preflight_tx.clone();
-        let _ = stale_tx.send(Ok(()));
+        let _ = stale_tx.send(Ok(true));
 
         channels.reset_preflight_channel();
 
@@ -528,12 +726,24 @@ mod tests {
         channels.reset_preflight_channel();
 
         let fresh_tx = channels.preflight_tx.clone();
-        fresh_tx.send(Ok(())).expect("send on fresh channel");
+        fresh_tx.send(Ok(true)).expect("send on fresh channel");
 
         match channels.preflight_rx.try_recv() {
-            Ok(Ok(())) => {}
-            other => panic!("expected Ok(Ok(())), got {:?}", other.map(|_| "?")),
+            Ok(Ok(true)) => {}
+            other => panic!("expected Ok(Ok(true)), got {:?}", other.map(|_| "?")),
+        }
+    }
+
+    #[test]
+    fn poll_interval_backs_off_when_idle_and_snaps_back_on_activity() {
+        let mut interval = FAST_POLL;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            interval = next_poll_interval(interval, false);
+            seen.push(interval.as_millis());
         }
+        assert_eq!(seen, [200, 400, 500, 500]);
+        assert_eq!(next_poll_interval(interval, true), FAST_POLL);
     }
 
     #[test]
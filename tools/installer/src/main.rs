@@ -1,31 +1,39 @@
 mod app;
+mod clock;
 mod cli;
-mod component;
-mod mcp;
-mod plugin;
-mod fs;
-mod source;
 mod tree;
 mod ui;
 mod theme;
 mod loading;
 mod process_exec;
+mod session;
+mod run_summary;
 
 use std::io;
+use std::path::Path;
 use std::thread;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use std::time::Duration;
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, Clear, ClearType},
     cursor::MoveTo,
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::{CrosstermBackend, TestBackend}, Terminal};
 
 use app::App;
 use loading::ProcessingChannels;
 
+// Core modules keep their historical `crate::` paths inside the frontend.
+use hibi_core::{component, fs, hook_log, maintenance, mcp, migrate, notify, plugin, profile, source};
+
+/// Application version string, derived from Cargo.toml at compile time.
+pub const VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
+
 fn main() -> Result<()> {
+    // Reads the local UTC offset, which is only safe while single-threaded.
+    hibi_core::local_time::init(source::config::load_log_utc().unwrap_or(false));
     let args: Vec<String> = std::env::args().collect();
     if args.iter().any(|a| a == "--help" || a == "-h") {
         cli::print_help();
@@ -33,21 +41,85 @@ fn main() -> Result<()> {
     }
 
     if args.iter().any(|a| a == "--version" || a == "-v") {
-        println!("hibi {} (Config Installer)", fs::VERSION);
+        println!("hibi {} (Config Installer)", VERSION);
         return Ok(());
     }
 
+    if let Some(dir) = flag_value(&args, "--source-dir")? {
+        source::set_source_dir(dir.into());
+        // Fail up front, before the TUI starts, on a path without a source.
+        source::find_source_dir()?;
+    }
+
     if args.iter().any(|a| a == "--sync") {
         return cli::run_sync();
     }
 
+    if args.iter().any(|a| a == "--maintenance-summary") {
+        return cli::run_maintenance_summary();
+    }
+
+    if args.iter().any(|a| a == "--list-backups") {
+        return cli::run_list_backups();
+    }
+
+    if let Some(name) = flag_value(&args, "--restore-backup")? {
+        return cli::run_restore_backup(name);
+    }
+
+    if let Some(path) = flag_value(&args, "--export-report")? {
+        return cli::run_export_report(path);
+    }
+
+    if let Some(path) = flag_value(&args, "--drift-report")? {
+        return cli::run_drift_report(path);
+    }
+
+    if let Some(path) = flag_value(&args, "--export-hooks")? {
+        return cli::run_export_hooks(path);
+    }
+
+    match subcommand(&args) {
+        Some("state") => return cli::run_state_report(),
+        Some("install") => {
+            let json = args.iter().any(|a| a == "--json");
+            let dry_run = args.iter().any(|a| a == "--dry-run");
+            return run_summary::with_json_errors(json, || cli::run_install(flag_value(&args, "--cli")?, dry_run, json));
+        }
+        Some("flush-pending") => {
+            let json = args.iter().any(|a| a == "--json");
+            return run_summary::with_json_errors(json, || cli::run_flush_pending(json));
+        }
+        Some("clean") => return cli::run_clean(flag_value(&args, "--keep")?),
+        _ => {}
+    }
+
+    let record_path = flag_value(&args, "--record")?;
+    let startup_tab = match flag_value(&args, "--tab")? {
+        Some(name) => Some(app::Tab::from_name(name).with_context(|| {
+            let names: Vec<&str> = app::Tab::all().iter().map(|t| t.display_name()).collect();
+            format!("Unknown tab '{}' (expected one of: {})", name, names.join(", "))
+        })?),
+        None => None,
+    };
+    let strict = args.iter().any(|a| a == "--strict");
+    let choose_cli = args.iter().any(|a| a == "--choose-cli");
+    if let Some(script_path) = flag_value(&args, "--simulate")? {
+        return run_simulation(Path::new(script_path), record_path, startup_tab, strict);
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, Clear(ClearType::All), MoveTo(0, 0))?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, Clear(ClearType::All), MoveTo(0, 0))?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    if let Some(path) = record_path {
+        let size = terminal.size()?;
+        session::start_recording(session::Recorder::create(Path::new(path), size.width, size.height)?);
+    }
+
     // Create app in background thread while showing loading animation
     use std::sync::{Arc, Mutex};
 
@@ -59,26 +131,36 @@ fn main() -> Result<()> {
         *app_result_clone.lock().unwrap() = Some(result);
     });
 
-    let mut frame_idx = 0;
     loop {
-        terminal.draw(|f| ui::loading_screen::draw(f, frame_idx))?;
+        let frame = terminal.draw(|f| ui::loading_screen::draw(f, clock::frame()))?;
+        session::record_frame(frame.buffer)?;
         let result_lock = app_result.lock().unwrap();
         if result_lock.is_some() {
             break;
         }
         drop(result_lock);
-        frame_idx += 1;
         std::thread::sleep(Duration::from_millis(100));
     }
 
     let mut app = app_result.lock().unwrap().take().unwrap()?;
+    app.startup_tab = startup_tab;
+    app.strict_mode |= strict;
+    if choose_cli {
+        app.startup_cli = None;
+    }
     let result = run_app(&mut terminal, &mut app);
+    if let Err(err) = app.remember_preferences() {
+        eprintln!("Warning: {err:#}");
+    }
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), DisableMouseCapture, LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
+    for line in app.session_summary.lines() {
+        println!("{}", line);
+    }
     if let Err(err) = result {
         eprintln!("Error: {err:?}");
     }
@@ -91,9 +173,15 @@ where
     <B as ratatui::backend::Backend>::Error: Send + Sync + 'static,
 {
     let mut channels = ProcessingChannels::new();
+    let mut clicks = ui::ClickMap::default();
+    cli::select_startup_cli(app, &channels.refresh_tx);
 
     loop {
-        terminal.draw(|f| ui::draw(f, app))?;
+        if app.current_view == app::View::List {
+            app.refresh_preview();
+        }
+        let frame = terminal.draw(|f| clicks = ui::draw(f, app))?;
+        session::record_frame(frame.buffer)?;
 
         match app.current_view {
             app::View::Installing => loading::handle_installing_view(app, &mut channels)?,
@@ -101,14 +189,139 @@ where
             app::View::Preflighting => loading::handle_preflighting_view(app, &mut channels)?,
             app::View::SourceSyncing => cli::handle_source_syncing(app)?,
             _ => {
-                if let Some(code) = cli::read_key_press()? {
-                    cli::dispatch_key(app, code, &channels.refresh_tx)?;
+                // Poll instead of blocking while an MCP update check or the
+                // startup CLI probe runs, MCP health is being watched, a
+                // refresh is deferred, or the hook log viewer is tailing, so
+                // updates show up without waiting for a key press.
+                let tailing = app.current_view == app::View::HookLogs;
+                let background = app.mcp_update_rx.is_some()
+                    || app.pending_flush_rx.is_some()
+                    || app.notify_rx.is_some()
+                    || app.cli_probe_rx.is_some()
+                    || app.mcp_health_polling()
+                    || app.deferred_refresh.is_some();
+                let key = if background || tailing {
+                    cli::poll_key_press()?
+                } else {
+                    cli::read_key_press()?
+                };
+                if let Some(key) = key {
+                    cli::dispatch_key(app, key, &channels.refresh_tx)?;
+                }
+                app.poll_mcp_updates();
+                app.poll_pending_flush();
+                app.poll_notification();
+                app.poll_cli_probe();
+                app.tick_mcp_health();
+                app.run_deferred_refresh(false);
+                if app.current_view == app::View::HookLogs {
+                    app.refresh_hook_logs();
                 }
             }
         }
 
-        if app.should_quit {
+        if let Some(mouse) = session::take_mouse() {
+            cli::dispatch_mouse(app, mouse, &clicks);
+        }
+
+        if app.should_quit || session::script_finished() {
             return Ok(());
         }
     }
 }
+
+/// Value following `flag` on the command line, if the flag is present.
+/// The subcommand, e.g. `install`: the first argument after any
+/// `--source-dir <dir>`, which may come before it.
+fn subcommand(args: &[String]) -> Option<&str> {
+    let mut rest = args.iter().skip(1).map(String::as_str);
+    loop {
+        match rest.next()? {
+            "--source-dir" => { rest.next(); }
+            arg => return Some(arg),
+        }
+    }
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>> {
+    let Some(pos) = args.iter().position(|a| a == flag) else { return Ok(None) };
+    match args.get(pos + 1) {
+        Some(value) if !value.starts_with('-') => Ok(Some(value)),
+        _ => bail!("{} requires a value", flag),
+    }
+}
+
+/// `hibi --simulate script.yaml`: run the TUI on a headless backend, feed it
+/// the script's keys, then print the final frame as plain text. The run
+/// gets an empty home folder of its own, so scripted installs, removals
+/// and saved preferences never reach the real one.
+fn run_simulation(
+    script_path: &Path,
+    record_path: Option<&str>,
+    startup_tab: Option<app::Tab>,
+    strict: bool,
+) -> Result<()> {
+    let script = session::Script::load(script_path)?;
+    let home = simulation_home()?;
+    let result = simulate(script, record_path, startup_tab, strict);
+    let _ = std::fs::remove_dir_all(&home);
+    result
+}
+
+/// Point `HOME` at a fresh temp dir for the rest of the process.
+fn simulation_home() -> Result<std::path::PathBuf> {
+    if cfg!(windows) {
+        // The home folder comes from the shell API there, not `HOME`.
+        bail!("--simulate isn't supported on Windows: it can't keep the run out of your home folder");
+    }
+    let home = std::env::temp_dir().join(format!("hibi-simulate-{}", std::process::id()));
+    std::fs::create_dir_all(&home).with_context(|| format!("Failed to create {}", home.display()))?;
+    // Still single-threaded: nothing else reads the environment yet.
+    unsafe { std::env::set_var("HOME", &home); }
+    Ok(home)
+}
+
+fn simulate(
+    script: session::Script,
+    record_path: Option<&str>,
+    startup_tab: Option<app::Tab>,
+    strict: bool,
+) -> Result<()> {
+    let mut terminal = Terminal::new(TestBackend::new(script.width, script.height))?;
+
+    if let Some(path) = record_path {
+        session::start_recording(session::Recorder::create(Path::new(path), script.width, script.height)?);
+    }
+    session::start_simulation(script);
+
+    let mut app = App::new()?;
+    app.startup_tab = startup_tab;
+    app.strict_mode |= strict;
+    run_app(&mut terminal, &mut app)?;
+
+    // Render the effect of the last scripted key.
+    if app.current_view == app::View::List {
+        app.refresh_preview();
+    }
+    let frame = terminal.draw(|f| { ui::draw(f, &app); })?;
+    session::record_frame(frame.buffer)?;
+    println!("{}", session::buffer_to_text(terminal.backend().buffer()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn subcommand_may_follow_the_source_dir() {
+        assert_eq!(subcommand(&args("hibi install --json")), Some("install"));
+        assert_eq!(subcommand(&args("hibi --source-dir /opt/hibi install --dry-run")), Some("install"));
+        assert_eq!(subcommand(&args("hibi --source-dir /opt/hibi")), None);
+        assert_eq!(subcommand(&args("hibi --tab skills")), Some("--tab"));
+    }
+}
@@ -1,10 +1,10 @@
 use std::path::PathBuf;
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, Sender};
 
 use anyhow::Result;
 
 use crate::app::{App, Tab, TargetCli};
-use crate::component::Component;
+use crate::component::{Component, HookConfig};
 use crate::fs;
 use crate::mcp;
 use crate::plugin;
@@ -16,6 +16,8 @@ pub(crate) enum ProcessData {
         component: Component,
         source_dir: PathBuf,
         dest_dir: PathBuf,
+        hook_peers: Vec<HookConfig>,
+        backup: Option<fs::backup::Snapshot>,
     },
     McpServer {
         server: mcp::McpServer,
@@ -26,6 +28,11 @@ pub(crate) enum ProcessData {
     Plugin {
         plugin: plugin::Plugin,
     },
+    /// An MCP or plugin install saved for later instead of run (offline).
+    Queued {
+        op: fs::pending::PendingOp,
+        dest_dir: PathBuf,
+    },
 }
 
 /// Extract the display name for the item being processed.
@@ -44,26 +51,46 @@ pub(crate) fn get_item_name(app: &App, idx: usize) -> String {
 pub(crate) fn prepare(app: &App, idx: usize) -> Option<ProcessData> {
     if app.tab == Tab::McpServers {
         let server = app.mcp_servers.get(idx)?.clone();
-        let env_values = if app.env_input_server_idx == Some(idx) {
-            app.env_input_values.clone()
-        } else {
-            Vec::new()
+        // Values from the server's env file, then any typed at the prompt.
+        let mut env_values = app.mcp_env_values.get(&idx).cloned().unwrap_or_default();
+        if app.env_input_server_idx == Some(idx) {
+            env_values.extend(app.env_input_values.iter().cloned());
+        }
+        // A removal goes to the scope the server was found installed in.
+        let scope = match server.scope {
+            Some(scope) if app.is_removing => scope,
+            _ => app.mcp_scope,
         };
-        let project_path = if app.mcp_scope == mcp::McpScope::Local {
+        let project_path = if scope == mcp::McpScope::Local {
             Some(app.mcp_project_path.clone())
         } else {
             None
         };
-        Some(ProcessData::McpServer { server, scope: app.mcp_scope, project_path, env_values })
+        if app.queue_offline {
+            let op = fs::pending::PendingOp::McpServer { server: server.def, scope, project_path, env_values };
+            return Some(ProcessData::Queued { op, dest_dir: app.dest_dir.clone() });
+        }
+        Some(ProcessData::McpServer { server, scope, project_path, env_values })
     } else if app.tab == Tab::Plugins {
         let plugin = app.plugins.get(idx)?.clone();
+        if app.queue_offline {
+            let op = fs::pending::PendingOp::Plugin { plugin: plugin.def };
+            return Some(ProcessData::Queued { op, dest_dir: app.dest_dir.clone() });
+        }
         Some(ProcessData::Plugin { plugin })
     } else {
         let component = app.components.get(idx)?.clone();
+        let hook_peers = if component.hook_config.is_some() {
+            app.components.iter().filter_map(|c| c.hook_config.clone()).collect()
+        } else {
+            Vec::new()
+        };
         Some(ProcessData::Component {
             component,
             source_dir: app.source_dir.clone(),
             dest_dir: app.dest_dir.clone(),
+            hook_peers,
+            backup: app.backup_snapshot.clone(),
         })
     }
 }
@@ -74,14 +101,22 @@ pub(crate) fn execute(
     is_removing: bool,
     target_cli: TargetCli,
     cancel_rx: Receiver<()>,
+    progress_tx: Sender<String>,
 ) -> Result<String> {
     match data {
         ProcessData::McpServer { server, scope, project_path, env_values } => {
             let name = server.def.name.clone();
             let timeout = if is_removing { 30 } else { 120 };
+            if !is_removing && fs::installer::mcp_server_configured(
+                &server, target_cli, scope, project_path.as_deref(), &env_values,
+            ) {
+                return Ok(skipped_message(&name));
+            }
 
             let result = if is_removing {
-                fs::installer::remove_mcp_server(&server, target_cli, timeout, &cancel_rx)
+                fs::installer::remove_mcp_server(
+                    &server, target_cli, scope, project_path.as_deref(), timeout, &cancel_rx, Some(&progress_tx),
+                )
             } else {
                 fs::installer::install_mcp_server(
                     &server,
@@ -92,6 +127,7 @@ pub(crate) fn execute(
                         target_cli,
                         timeout_secs: timeout,
                         cancel_rx: &cancel_rx,
+                        progress_tx: Some(&progress_tx),
                     },
                 )
             };
@@ -101,27 +137,37 @@ pub(crate) fn execute(
         ProcessData::Plugin { plugin } => {
             let name = plugin.def.name.clone();
             let timeout = if is_removing { 30 } else { 60 };
+            if !is_removing && fs::installer::plugin_enabled(&plugin) {
+                return Ok(skipped_message(&name));
+            }
 
             let result = if is_removing {
-                fs::installer::remove_plugin(&plugin, timeout, &cancel_rx)
+                fs::installer::remove_plugin(&plugin, timeout, &cancel_rx, Some(&progress_tx))
             } else {
-                fs::installer::install_plugin(&plugin, timeout, &cancel_rx)
+                fs::installer::install_plugin(&plugin, timeout, &cancel_rx, Some(&progress_tx))
             };
 
             format_result(&name, is_removing, result)
         }
-        ProcessData::Component { component, source_dir, dest_dir } => {
+        ProcessData::Queued { op, dest_dir } => {
+            let name = op.name().to_string();
+            fs::pending::queue(&dest_dir, op)?;
+            Ok(format!("[QUEUED] {}: installs once back online (hibi flush-pending)", name))
+        }
+        ProcessData::Component { component, source_dir, dest_dir, hook_peers, backup } => {
             let name = component.name.clone();
+            let merging = !is_removing && fs::installer::merged_content(&component, &dest_dir).is_some();
 
             let result = if is_removing {
                 fs::installer::remove_component(&component, &dest_dir)
             } else {
-                fs::installer::install_component(&component, &source_dir, &dest_dir)
+                fs::installer::install_component(&component, &source_dir, &dest_dir, &hook_peers, backup.as_ref())
             };
 
             // Component errors are non-fatal: report as [ERR] line, not Err
             let action = if is_removing { "Removed" } else { "Installed" };
             match result {
+                Ok(_) if merging => Ok(format!("[OK] Merged {} (local edits kept)", name)),
                 Ok(_) => Ok(format!("[OK] {} {}", action, name)),
                 Err(e) => Ok(format!("[ERR] {}: {}", name, e)),
             }
@@ -137,3 +183,8 @@ fn format_result(name: &str, is_removing: bool, result: Result<()>) -> Result<St
         Err(e) => Err(e),
     }
 }
+
+/// Log line for an install that found its target already configured.
+fn skipped_message(name: &str) -> String {
+    format!("[SKIP] {}: already configured", name)
+}
@@ -0,0 +1,135 @@
+//! What a headless command that installs things did, item by item, for
+//! provisioning scripts. `--json` prints it as one JSON object on stdout;
+//! otherwise it is printed as `[OK]`/`[SKIP]`/`[ERR]` lines. Either way
+//! the exit status tells the outcomes apart:
+//!
+//! | status | meaning                                               |
+//! |--------|-------------------------------------------------------|
+//! | 0      | every item installed or already up to date            |
+//! | 1      | the command couldn't run (no sources, bad arguments)  |
+//! | 2      | partial failure: some items failed, others installed  |
+//! | 3      | total failure: every item that was tried failed       |
+
+use serde::Serialize;
+
+pub(crate) const EXIT_PARTIAL_FAILURE: i32 = 2;
+pub(crate) const EXIT_TOTAL_FAILURE: i32 = 3;
+
+/// One item of a run.
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct RunItem {
+    /// The CLI it was installed for, e.g. `Claude Code`.
+    pub target: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct RunSummary {
+    pub installed: Vec<RunItem>,
+    /// Already installed and up to date, or already configured.
+    pub skipped: Vec<RunItem>,
+    pub failed: Vec<RunItem>,
+    /// Migration, source and fetch messages.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    pub exit_code: i32,
+}
+
+impl RunSummary {
+    pub fn installed(&mut self, target: &str, name: impl Into<String>) {
+        self.installed.push(RunItem { target: target.to_string(), name: name.into(), error: None });
+    }
+
+    pub fn skipped(&mut self, target: &str, name: impl Into<String>) {
+        self.skipped.push(RunItem { target: target.to_string(), name: name.into(), error: None });
+    }
+
+    pub fn failed(&mut self, target: &str, name: impl Into<String>, error: impl Into<String>) {
+        self.failed.push(RunItem { target: target.to_string(), name: name.into(), error: Some(error.into()) });
+    }
+
+    fn compute_exit_code(&self) -> i32 {
+        match (self.failed.is_empty(), self.installed.is_empty()) {
+            (true, _) => 0,
+            (false, false) => EXIT_PARTIAL_FAILURE,
+            (false, true) => EXIT_TOTAL_FAILURE,
+        }
+    }
+
+    /// Print the summary and exit with its status when something failed.
+    /// `verb` names what happened to installed items in the text output,
+    /// e.g. `installed` or `would install`.
+    pub fn finish(mut self, json: bool, verb: &str) -> anyhow::Result<()> {
+        self.exit_code = self.compute_exit_code();
+        if json {
+            println!("{}", serde_json::to_string_pretty(&self)?);
+        } else {
+            self.notes.iter().for_each(|note| println!("{}", note));
+            for item in &self.installed {
+                println!("[OK] {} {}: {}", item.target, item.name, verb);
+            }
+            for item in &self.skipped {
+                println!("[SKIP] {} {}: up to date", item.target, item.name);
+            }
+            for item in &self.failed {
+                println!("[ERR] {} {}: {}", item.target, item.name, item.error.as_deref().unwrap_or_default());
+            }
+            println!(
+                "{} {}, {} up to date, {} failed",
+                self.installed.len(), verb, self.skipped.len(), self.failed.len(),
+            );
+        }
+        if self.exit_code != 0 {
+            std::process::exit(self.exit_code);
+        }
+        Ok(())
+    }
+}
+
+/// Run a command that reports through a `RunSummary`. With `--json`, an
+/// error that stops it before the summary (no sources, bad arguments) is
+/// also printed on stdout as `{"error": ..., "exit_code": 1}`, so scripts
+/// always get an object to parse; the error still goes to stderr.
+pub(crate) fn with_json_errors(json: bool, run: impl FnOnce() -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let result = run();
+    if let (true, Err(e)) = (json, &result) {
+        println!("{}", error_json(e));
+    }
+    result
+}
+
+fn error_json(error: &anyhow::Error) -> String {
+    let object = serde_json::json!({ "error": format!("{:#}", error), "exit_code": 1 });
+    serde_json::to_string_pretty(&object).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_code_tells_partial_from_total_failure() {
+        let mut summary = RunSummary::default();
+        summary.skipped("Claude Code", "agents/same.md");
+        assert_eq!(summary.compute_exit_code(), 0);
+        summary.failed("Claude Code", "agents/bad.md", "permission denied");
+        assert_eq!(summary.compute_exit_code(), EXIT_TOTAL_FAILURE);
+        summary.installed("Claude Code", "agents/new.md");
+        assert_eq!(summary.compute_exit_code(), EXIT_PARTIAL_FAILURE);
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["failed"][0]["error"], "permission denied");
+        assert!(json["installed"][0].get("error").is_none());
+        assert!(json.get("notes").is_none());
+    }
+
+    #[test]
+    fn errors_before_the_summary_are_reported_as_json() {
+        let error = anyhow::anyhow!("no sources configured").context("Failed to resolve sources");
+        let json: serde_json::Value = serde_json::from_str(&error_json(&error)).unwrap();
+        assert_eq!(json["error"], "Failed to resolve sources: no sources configured");
+        assert_eq!(json["exit_code"], 1);
+    }
+}
@@ -0,0 +1,358 @@
+//! Record and simulate modes.
+//!
+//! `--record <file>` writes every rendered frame and key press to an
+//! asciinema v2 cast. `--simulate <script.yaml>` replaces the terminal with
+//! a headless backend and feeds keys from a script, so demos and end-to-end
+//! UI checks run without a human at the keyboard.
+//!
+//! All key reads go through `next_key`, which consults the active script
+//! (if any) before falling back to crossterm. Mouse clicks and wheel
+//! scrolls seen along the way are held for `take_mouse`.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::buffer::Buffer;
+use serde::Deserialize;
+
+/// Terminal size used when a script doesn't specify one.
+const DEFAULT_WIDTH: u16 = 120;
+const DEFAULT_HEIGHT: u16 = 40;
+
+/// One entry in a simulate script's `steps:` list.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Step {
+    /// A single key, e.g. `Enter`, `Down`, `Ctrl+f`, `j`.
+    Key { key: String },
+    /// Type each character of a string.
+    Text { text: String },
+    /// Idle for a while so background work (loading, installs) can finish.
+    Wait { wait_ms: u64 },
+    /// Left click at `[column, row]`.
+    Click { click: [u16; 2] },
+    /// One wheel notch, `up` or `down`.
+    Scroll { scroll: String },
+}
+
+/// Top-level YAML structure for `--simulate` scripts.
+#[derive(Deserialize, Debug)]
+struct ScriptFile {
+    width: Option<u16>,
+    height: Option<u16>,
+    #[serde(default)]
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Wait(Duration),
+}
+
+/// A parsed `--simulate` script.
+#[derive(Debug)]
+pub(crate) struct Script {
+    pub width: u16,
+    pub height: u16,
+    actions: VecDeque<Action>,
+}
+
+impl Script {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid script {}", path.display()))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let file: ScriptFile = serde_yaml_bw::from_str(content)?;
+        let mut actions = VecDeque::new();
+        for step in file.steps {
+            match step {
+                Step::Key { key } => actions.push_back(Action::Key(parse_key(&key)?)),
+                Step::Text { text } => actions.extend(
+                    text.chars().map(|c| Action::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))),
+                ),
+                Step::Wait { wait_ms } => actions.push_back(Action::Wait(Duration::from_millis(wait_ms))),
+                Step::Click { click: [column, row] } => {
+                    actions.push_back(Action::Mouse(mouse_event(MouseEventKind::Down(MouseButton::Left), column, row)));
+                }
+                Step::Scroll { scroll } => {
+                    let kind = match scroll.as_str() {
+                        "up" => MouseEventKind::ScrollUp,
+                        "down" => MouseEventKind::ScrollDown,
+                        _ => bail!("Unknown scroll direction '{}' (expected up or down)", scroll),
+                    };
+                    actions.push_back(Action::Mouse(mouse_event(kind, 0, 0)));
+                }
+            }
+        }
+        Ok(Self {
+            width: file.width.unwrap_or(DEFAULT_WIDTH),
+            height: file.height.unwrap_or(DEFAULT_HEIGHT),
+            actions,
+        })
+    }
+}
+
+fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+}
+
+/// Parse a script key name. Accepts named keys, single characters and a
+/// `Ctrl+` prefix.
+fn parse_key(name: &str) -> Result<KeyEvent> {
+    let (modifiers, base) = match name.strip_prefix("Ctrl+") {
+        Some(rest) => (KeyModifiers::CONTROL, rest),
+        None => (KeyModifiers::NONE, name),
+    };
+    let code = match base {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Backspace" => KeyCode::Backspace,
+        "Space" => KeyCode::Char(' '),
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        _ => {
+            let mut chars = base.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => bail!("Unknown key '{}'", name),
+            }
+        }
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Inverse of `parse_key`, used for the cast's input events so a recording
+/// can be turned back into a script.
+fn key_name(key: &KeyEvent) -> String {
+    let base = match key.code {
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "BackTab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{:?}", other),
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl+{}", base)
+    } else {
+        base
+    }
+}
+
+/// Writes an asciinema v2 cast: a JSON header line, then one
+/// `[elapsed, "o"|"i", data]` event per line.
+pub(crate) struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+    last_frame: String,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, width: u16, height: u16) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let header = serde_json::json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": timestamp,
+        });
+        writeln!(out, "{}", header)?;
+        out.flush()?;
+        Ok(Self { out, started: Instant::now(), last_frame: String::new() })
+    }
+
+    fn event(&mut self, kind: &str, data: &str) -> Result<()> {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        writeln!(self.out, "{}", serde_json::json!([elapsed, kind, data]))?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Record a rendered frame; unchanged frames are skipped.
+    pub fn frame(&mut self, buffer: &Buffer) -> Result<()> {
+        let text = buffer_to_text(buffer);
+        if text == self.last_frame {
+            return Ok(());
+        }
+        // Home + clear, then the frame with CRLF line endings so players
+        // reproduce it on a raw terminal.
+        let data = format!("\x1b[H\x1b[2J{}", text.replace('\n', "\r\n"));
+        self.event("o", &data)?;
+        self.last_frame = text;
+        Ok(())
+    }
+}
+
+/// Plain-text rendering of a buffer, one line per row, trailing spaces
+/// trimmed.
+pub(crate) fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut lines = Vec::with_capacity(area.height as usize);
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        for x in area.left()..area.right() {
+            line.push_str(buffer[(x, y)].symbol());
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+struct Session {
+    script: Option<VecDeque<Action>>,
+    recorder: Option<Recorder>,
+    /// Last click or wheel scroll not yet handled.
+    mouse: Option<MouseEvent>,
+}
+
+static SESSION: Mutex<Session> = Mutex::new(Session { script: None, recorder: None, mouse: None });
+
+pub(crate) fn start_simulation(script: Script) {
+    SESSION.lock().unwrap().script = Some(script.actions);
+}
+
+pub(crate) fn start_recording(recorder: Recorder) {
+    SESSION.lock().unwrap().recorder = Some(recorder);
+}
+
+/// True once a simulate script has run out of steps.
+pub(crate) fn script_finished() -> bool {
+    SESSION.lock().unwrap().script.as_ref().is_some_and(|s| s.is_empty())
+}
+
+/// Record a frame if `--record` is active.
+pub(crate) fn record_frame(buffer: &Buffer) -> Result<()> {
+    match SESSION.lock().unwrap().recorder.as_mut() {
+        Some(recorder) => recorder.frame(buffer),
+        None => Ok(()),
+    }
+}
+
+/// The click or wheel scroll that ended the last `next_key` wait, if any.
+pub(crate) fn take_mouse() -> Option<MouseEvent> {
+    SESSION.lock().unwrap().mouse.take()
+}
+
+/// Next key press, waiting up to `timeout` (`None` blocks). Release events
+/// are dropped; a click or wheel scroll ends the wait with `None` and is
+/// kept for `take_mouse`. Under `--simulate` input comes from the script
+/// instead.
+pub(crate) fn next_key(timeout: Option<Duration>) -> Result<Option<KeyEvent>> {
+    let scripted = SESSION.lock().unwrap().script.as_mut().map(|s| s.pop_front());
+    let key = match scripted {
+        Some(Some(Action::Key(key))) => Some(key),
+        Some(Some(Action::Mouse(mouse))) => {
+            SESSION.lock().unwrap().mouse = Some(mouse);
+            None
+        }
+        Some(Some(Action::Wait(duration))) => {
+            std::thread::sleep(duration);
+            None
+        }
+        Some(None) => None,
+        None => read_terminal_key(timeout)?,
+    };
+
+    if let Some(key) = &key
+        && let Some(recorder) = SESSION.lock().unwrap().recorder.as_mut()
+    {
+        recorder.event("i", &key_name(key))?;
+    }
+    Ok(key)
+}
+
+fn read_terminal_key(timeout: Option<Duration>) -> Result<Option<KeyEvent>> {
+    if let Some(timeout) = timeout
+        && !event::poll(timeout)?
+    {
+        return Ok(None);
+    }
+    match event::read()? {
+        Event::Key(key) if key.kind != KeyEventKind::Release => Ok(Some(key)),
+        // Moves and drags would only wake the loop for nothing.
+        Event::Mouse(mouse) if matches!(
+            mouse.kind,
+            MouseEventKind::Down(MouseButton::Left) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+        ) => {
+            SESSION.lock().unwrap().mouse = Some(mouse);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn parse_key_round_trips_through_key_name() {
+        for name in ["Enter", "Esc", "Space", "Down", "j", "K", "Ctrl+f", "/"] {
+            assert_eq!(key_name(&parse_key(name).unwrap()), name);
+        }
+        assert!(parse_key("Bogus").is_err());
+    }
+
+    #[test]
+    fn script_expands_text_and_waits() {
+        let script = Script::parse(
+            "width: 80\nsteps:\n  - key: Enter\n  - text: ab\n  - wait_ms: 50\n  - click: [4, 7]\n  - scroll: down\n",
+        ).unwrap();
+        assert_eq!((script.width, script.height), (80, DEFAULT_HEIGHT));
+        let actions: Vec<Action> = script.actions.into_iter().collect();
+        assert_eq!(actions, vec![
+            Action::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Action::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Action::Key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE)),
+            Action::Wait(Duration::from_millis(50)),
+            Action::Mouse(mouse_event(MouseEventKind::Down(MouseButton::Left), 4, 7)),
+            Action::Mouse(mouse_event(MouseEventKind::ScrollDown, 0, 0)),
+        ]);
+        assert!(Script::parse("steps:\n  - scroll: sideways\n").is_err());
+    }
+
+    #[test]
+    fn buffer_to_text_trims_trailing_spaces() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "hi", ratatui::style::Style::default());
+        assert_eq!(buffer_to_text(&buffer), "hi\n");
+    }
+}
@@ -1,165 +1,334 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
 use ratatui::style::Color;
+use serde::Deserialize;
+
+/// The colours a theme is made of, named after the Catppuccin palette the
+/// built-in themes use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub base: Color,
+    pub mantle: Color,
+    pub surface0: Color,
+    pub surface1: Color,
+    pub overlay0: Color,
+    pub text: Color,
+    pub subtext0: Color,
+    pub blue: Color,
+    pub sapphire: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub red: Color,
+    pub mauve: Color,
+    pub peach: Color,
+}
+
+impl Palette {
+    // Catppuccin Mocha
+    const MOCHA: Self = Self {
+        base: Color::Rgb(30, 30, 46),        // #1e1e2e
+        mantle: Color::Rgb(24, 24, 37),      // #181825
+        surface0: Color::Rgb(49, 50, 68),    // #313244
+        surface1: Color::Rgb(69, 71, 90),    // #45475a
+        overlay0: Color::Rgb(108, 112, 134), // #6c7086
+        text: Color::Rgb(205, 214, 244),     // #cdd6f4
+        subtext0: Color::Rgb(166, 173, 200), // #a6adc8
+        blue: Color::Rgb(137, 180, 250),     // #89b4fa
+        sapphire: Color::Rgb(116, 199, 236), // #74c7ec
+        green: Color::Rgb(166, 227, 161),    // #a6e3a1
+        yellow: Color::Rgb(249, 226, 175),   // #f9e2af
+        red: Color::Rgb(243, 139, 168),      // #f38ba8
+        mauve: Color::Rgb(203, 166, 247),    // #cba6f7
+        peach: Color::Rgb(250, 179, 135),    // #fab387
+    };
+
+    // Catppuccin Latte, with the lighter text, accent and status colours
+    // darkened to meet the minimum contrast on its pale backgrounds.
+    const LATTE: Self = Self {
+        base: Color::Rgb(239, 241, 245),     // #eff1f5
+        mantle: Color::Rgb(230, 233, 239),   // #e6e9ef
+        surface0: Color::Rgb(204, 208, 218), // #ccd0da
+        surface1: Color::Rgb(188, 192, 204), // #bcc0cc
+        overlay0: Color::Rgb(127, 130, 147), // #7f8293
+        text: Color::Rgb(76, 79, 105),       // #4c4f69
+        subtext0: Color::Rgb(100, 103, 124), // #64677c
+        blue: Color::Rgb(30, 102, 245),      // #1e66f5
+        sapphire: Color::Rgb(29, 145, 165),  // #1d91a5
+        green: Color::Rgb(60, 150, 40),      // #3c9628
+        yellow: Color::Rgb(183, 116, 24),    // #b77418
+        red: Color::Rgb(210, 15, 57),        // #d20f39
+        mauve: Color::Rgb(136, 57, 239),     // #8839ef
+        peach: Color::Rgb(224, 88, 10),      // #e0580a
+    };
+
+    // High contrast on black, with Okabe-Ito colours so status never hinges
+    // on telling red from green: new is blue, modified yellow, errors
+    // vermillion.
+    const HIGH_CONTRAST: Self = Self {
+        base: Color::Rgb(0, 0, 0),           // #000000
+        mantle: Color::Rgb(0, 0, 0),         // #000000
+        surface0: Color::Rgb(58, 58, 58),    // #3a3a3a
+        surface1: Color::Rgb(160, 160, 160), // #a0a0a0
+        overlay0: Color::Rgb(192, 192, 192), // #c0c0c0
+        text: Color::Rgb(255, 255, 255),     // #ffffff
+        subtext0: Color::Rgb(232, 232, 232), // #e8e8e8
+        blue: Color::Rgb(204, 121, 167),     // #cc79a7 reddish purple
+        sapphire: Color::Rgb(230, 159, 0),   // #e69f00 orange
+        green: Color::Rgb(86, 180, 233),     // #56b4e9 sky blue
+        yellow: Color::Rgb(240, 228, 66),    // #f0e442 yellow
+        red: Color::Rgb(213, 94, 0),         // #d55e00 vermillion
+        mauve: Color::Rgb(255, 255, 255),    // #ffffff
+        peach: Color::Rgb(230, 159, 0),      // #e69f00 orange
+    };
+
+    /// Foreground/background pairs the UI draws that fall below their
+    /// minimum contrast, e.g. `overlay0 on mantle: 2.63 < 3`.
+    fn contrast_failures(&self) -> Vec<String> {
+        let backgrounds = [("base", self.base), ("mantle", self.mantle)];
+        let foregrounds = [
+            ("text", self.text, MIN_TEXT_CONTRAST),
+            ("subtext0", self.subtext0, MIN_TEXT_CONTRAST),
+            ("overlay0", self.overlay0, MIN_ACCENT_CONTRAST),
+            ("blue", self.blue, MIN_ACCENT_CONTRAST),
+            ("sapphire", self.sapphire, MIN_ACCENT_CONTRAST),
+            ("green", self.green, MIN_ACCENT_CONTRAST),
+            ("yellow", self.yellow, MIN_ACCENT_CONTRAST),
+            ("red", self.red, MIN_ACCENT_CONTRAST),
+            ("mauve", self.mauve, MIN_ACCENT_CONTRAST),
+            ("peach", self.peach, MIN_ACCENT_CONTRAST),
+        ];
+        let pairs = backgrounds.iter()
+            .flat_map(|&(bg_name, bg)| foregrounds.iter().map(move |&(fg_name, fg, min)| (fg_name, fg, bg_name, bg, min)))
+            // The selected row: text on surface0.
+            .chain([("text", self.text, "surface0", self.surface0, MIN_TEXT_CONTRAST)]);
+        pairs
+            .filter_map(|(fg_name, fg, bg_name, bg, min)| {
+                let ratio = contrast_ratio(fg, bg)?;
+                (ratio < min).then(|| format!("{} on {}: {:.2} < {}", fg_name, bg_name, ratio, min))
+            })
+            .collect()
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ThemeMode {
-    Mocha,  // Catppuccin Dark
-    Latte,  // Catppuccin Light
+    /// The colour called `name` in a palette file.
+    fn slot(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "base" => &mut self.base,
+            "mantle" => &mut self.mantle,
+            "surface0" => &mut self.surface0,
+            "surface1" => &mut self.surface1,
+            "overlay0" => &mut self.overlay0,
+            "text" => &mut self.text,
+            "subtext0" => &mut self.subtext0,
+            "blue" => &mut self.blue,
+            "sapphire" => &mut self.sapphire,
+            "green" => &mut self.green,
+            "yellow" => &mut self.yellow,
+            "red" => &mut self.red,
+            "mauve" => &mut self.mauve,
+            "peach" => &mut self.peach,
+            _ => return None,
+        })
+    }
 }
 
-impl ThemeMode {
-    pub fn toggle(&self) -> Self {
-        match self {
-            Self::Mocha => Self::Latte,
-            Self::Latte => Self::Mocha,
-        }
+/// Minimum contrast ratios, after WCAG 2: 4.5:1 for body text, 3:1 for
+/// muted text, accents and status colours, which are bold or secondary.
+const MIN_TEXT_CONTRAST: f64 = 4.5;
+const MIN_ACCENT_CONTRAST: f64 = 3.0;
+
+/// WCAG relative luminance of an RGB colour; `None` for named terminal
+/// colours, whose actual shade depends on the terminal.
+fn relative_luminance(color: Color) -> Option<f64> {
+    let Color::Rgb(r, g, b) = color else { return None };
+    let channel = |v: u8| {
+        let v = f64::from(v) / 255.0;
+        if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+    };
+    Some(0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b))
+}
+
+/// Contrast ratio between two RGB colours, from 1 (same) to 21.
+pub fn contrast_ratio(a: Color, b: Color) -> Option<f64> {
+    let (a, b) = (relative_luminance(a)?, relative_luminance(b)?);
+    Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+}
+
+/// A `~/.hibi/themes/*.yaml` file. Colours it leaves out come from Mocha,
+/// or from Latte when `light` is set.
+#[derive(Debug, Deserialize)]
+struct PaletteFile {
+    name: Option<String>,
+    #[serde(default)]
+    light: bool,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct NamedPalette {
+    name: String,
+    palette: Palette,
+}
+
+/// Directory custom palette files are loaded from.
+pub fn themes_dir(home: &Path) -> PathBuf {
+    home.join(".hibi").join("themes")
+}
+
+/// Parse a palette file; the file name is the theme's name unless it sets one.
+fn load_palette(path: &Path) -> Result<NamedPalette> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let file: PaletteFile = serde_yaml_bw::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let mut palette = if file.light { Palette::LATTE } else { Palette::MOCHA };
+    for (slot, value) in &file.colors {
+        let Some(color) = palette.slot(slot) else {
+            bail!("{}: unknown colour '{}'", path.display(), slot);
+        };
+        *color = Color::from_str(value)
+            .map_err(|_| anyhow::anyhow!("{}: '{}' is not a colour (use #rrggbb)", path.display(), value))?;
     }
+    let name = file.name.unwrap_or_else(|| {
+        path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+    });
+    Ok(NamedPalette { name, palette })
+}
 
-    pub fn name(&self) -> &str {
-        match self {
-            Self::Mocha => "Mocha",
-            Self::Latte => "Latte",
+/// Palettes in `dir`, sorted by file name, and an error for each file that
+/// couldn't be loaded.
+fn load_palettes(dir: &Path) -> (Vec<NamedPalette>, Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return (Vec::new(), Vec::new()) };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "yaml" || e == "yml"))
+        .collect();
+    paths.sort();
+
+    let (mut palettes, mut errors) = (Vec::new(), Vec::new());
+    for path in paths {
+        match load_palette(&path) {
+            Ok(palette) => palettes.push(palette),
+            Err(e) => errors.push(format!("{:#}", e)),
         }
     }
+    (palettes, errors)
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Theme names match ignoring case and `-`/`_`/space, so `high-contrast`
+/// picks "High Contrast".
+fn same_name(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| s.chars().filter(|c| !matches!(c, '-' | '_' | ' ')).collect::<String>().to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+/// The current theme and the others `t` cycles through: Mocha, Latte, High
+/// Contrast, then any custom palettes.
+#[derive(Debug, Clone)]
 pub struct Theme {
-    mode: ThemeMode,
+    themes: Vec<NamedPalette>,
+    current: usize,
 }
 
 impl Default for Theme {
     fn default() -> Self {
-        // Detect system theme preference
-        let mode = Self::detect_system_theme();
-        Self { mode }
+        Self::with_palettes(Vec::new(), None)
     }
 }
 
 impl Theme {
-    /// Detect system theme preference (light/dark)
-    fn detect_system_theme() -> ThemeMode {
+    /// Built-in themes plus the palettes in `~/.hibi/themes`, starting on
+    /// `preferred` if one has that name. Also returns palette load errors.
+    pub fn load(preferred: Option<&str>) -> (Self, Vec<String>) {
+        let (custom, errors) = dirs::home_dir()
+            .map(|home| load_palettes(&themes_dir(&home)))
+            .unwrap_or_default();
+        (Self::with_palettes(custom, preferred), errors)
+    }
+
+    fn with_palettes(custom: Vec<NamedPalette>, preferred: Option<&str>) -> Self {
+        let mut themes = vec![
+            NamedPalette { name: "Mocha".to_string(), palette: Palette::MOCHA },  // Catppuccin Dark
+            NamedPalette { name: "Latte".to_string(), palette: Palette::LATTE },  // Catppuccin Light
+            NamedPalette { name: "High Contrast".to_string(), palette: Palette::HIGH_CONTRAST },
+        ];
+        debug_assert!(
+            themes.iter().all(|t| t.palette.contrast_failures().is_empty()),
+            "built-in theme below minimum contrast",
+        );
+        themes.extend(custom);
+        let current = preferred
+            .and_then(|name| themes.iter().position(|t| same_name(&t.name, name)))
+            .unwrap_or_else(Self::detect_system_theme);
+        Self { themes, current }
+    }
+
+    /// Detect system theme preference (light/dark): Latte or Mocha.
+    fn detect_system_theme() -> usize {
         match terminal_light::luma() {
-            Ok(luma) if luma > 0.6 => ThemeMode::Latte,  // Light terminal
-            _ => ThemeMode::Mocha,  // Dark terminal or unable to detect
+            Ok(luma) if luma > 0.6 => 1,  // Light terminal
+            _ => 0,  // Dark terminal or unable to detect
         }
     }
 
-    pub fn mode(&self) -> ThemeMode {
-        self.mode
+    pub fn name(&self) -> &str {
+        &self.themes[self.current].name
     }
 
+    /// Switch to the next theme, wrapping around.
     pub fn toggle(&mut self) {
-        self.mode = self.mode.toggle();
-    }
-
-    // Catppuccin Mocha base colors
-    const MOCHA_BASE: Color = Color::Rgb(30, 30, 46);      // #1e1e2e
-    const MOCHA_MANTLE: Color = Color::Rgb(24, 24, 37);    // #181825
-    const MOCHA_SURFACE0: Color = Color::Rgb(49, 50, 68);  // #313244
-    const MOCHA_SURFACE1: Color = Color::Rgb(69, 71, 90);  // #45475a
-    const MOCHA_OVERLAY0: Color = Color::Rgb(108, 112, 134); // #6c7086
-    const MOCHA_TEXT: Color = Color::Rgb(205, 214, 244);   // #cdd6f4
-    const MOCHA_SUBTEXT0: Color = Color::Rgb(166, 173, 200); // #a6adc8
-
-    // Catppuccin Mocha accent colors
-    const MOCHA_BLUE: Color = Color::Rgb(137, 180, 250);   // #89b4fa
-    const MOCHA_SAPPHIRE: Color = Color::Rgb(116, 199, 236); // #74c7ec
-    const MOCHA_GREEN: Color = Color::Rgb(166, 227, 161);  // #a6e3a1
-    const MOCHA_YELLOW: Color = Color::Rgb(249, 226, 175); // #f9e2af
-    const MOCHA_RED: Color = Color::Rgb(243, 139, 168);    // #f38ba8
-    const MOCHA_MAUVE: Color = Color::Rgb(203, 166, 247);  // #cba6f7
-    const MOCHA_PEACH: Color = Color::Rgb(250, 179, 135);  // #fab387
-
-    // Catppuccin Latte base colors
-    const LATTE_BASE: Color = Color::Rgb(239, 241, 245);   // #eff1f5
-    const LATTE_MANTLE: Color = Color::Rgb(230, 233, 239); // #e6e9ef
-    const LATTE_SURFACE0: Color = Color::Rgb(204, 208, 218); // #ccd0da
-    const LATTE_SURFACE1: Color = Color::Rgb(188, 192, 204); // #bcc0cc
-    const LATTE_OVERLAY0: Color = Color::Rgb(140, 143, 161); // #8c8fa1
-    const LATTE_TEXT: Color = Color::Rgb(76, 79, 105);     // #4c4f69
-    const LATTE_SUBTEXT0: Color = Color::Rgb(108, 111, 133); // #6c6f85
-
-    // Catppuccin Latte accent colors
-    const LATTE_BLUE: Color = Color::Rgb(30, 102, 245);    // #1e66f5
-    const LATTE_SAPPHIRE: Color = Color::Rgb(32, 159, 181); // #209fb5
-    const LATTE_GREEN: Color = Color::Rgb(64, 160, 43);    // #40a02b
-    const LATTE_YELLOW: Color = Color::Rgb(223, 142, 29);  // #df8e1d
-    const LATTE_RED: Color = Color::Rgb(210, 15, 57);      // #d20f39
-    const LATTE_MAUVE: Color = Color::Rgb(136, 57, 239);   // #8839ef
-    const LATTE_PEACH: Color = Color::Rgb(254, 100, 11);   // #fe640b
+        self.current = (self.current + 1) % self.themes.len();
+    }
+
+    fn palette(&self) -> &Palette {
+        &self.themes[self.current].palette
+    }
 
     // Background colors
     pub fn bg_primary(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_BASE,
-            ThemeMode::Latte => Self::LATTE_BASE,
-        }
+        self.palette().base
     }
 
     pub fn bg_secondary(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_MANTLE,
-            ThemeMode::Latte => Self::LATTE_MANTLE,
-        }
+        self.palette().mantle
     }
 
     // Text colors
     pub fn text_primary(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_TEXT,
-            ThemeMode::Latte => Self::LATTE_TEXT,
-        }
+        self.palette().text
     }
 
     pub fn text_secondary(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_SUBTEXT0,
-            ThemeMode::Latte => Self::LATTE_SUBTEXT0,
-        }
+        self.palette().subtext0
     }
 
     pub fn text_muted(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_OVERLAY0,
-            ThemeMode::Latte => Self::LATTE_OVERLAY0,
-        }
+        self.palette().overlay0
     }
 
     // Accent colors
     pub fn accent_primary(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_BLUE,
-            ThemeMode::Latte => Self::LATTE_BLUE,
-        }
+        self.palette().blue
     }
 
     pub fn accent_secondary(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_SAPPHIRE,
-            ThemeMode::Latte => Self::LATTE_SAPPHIRE,
-        }
+        self.palette().sapphire
     }
 
     // Status colors
     pub fn success(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_GREEN,
-            ThemeMode::Latte => Self::LATTE_GREEN,
-        }
+        self.palette().green
     }
 
     pub fn warning(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_YELLOW,
-            ThemeMode::Latte => Self::LATTE_YELLOW,
-        }
+        self.palette().yellow
     }
 
     pub fn error(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_RED,
-            ThemeMode::Latte => Self::LATTE_RED,
-        }
+        self.palette().red
     }
 
     pub fn info(&self) -> Color {
@@ -168,10 +337,7 @@ impl Theme {
 
     // UI element colors
     pub fn border(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_SURFACE1,
-            ThemeMode::Latte => Self::LATTE_SURFACE1,
-        }
+        self.palette().surface1
     }
 
     pub fn border_focused(&self) -> Color {
@@ -179,10 +345,7 @@ impl Theme {
     }
 
     pub fn selection_bg(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_SURFACE0,
-            ThemeMode::Latte => Self::LATTE_SURFACE0,
-        }
+        self.palette().surface0
     }
 
     pub fn selection_fg(&self) -> Color {
@@ -191,10 +354,7 @@ impl Theme {
 
     // Special colors
     pub fn highlight(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_MAUVE,
-            ThemeMode::Latte => Self::LATTE_MAUVE,
-        }
+        self.palette().mauve
     }
 
     pub fn diff_added(&self) -> Color {
@@ -210,9 +370,54 @@ impl Theme {
     }
 
     pub fn peach(&self) -> Color {
-        match self.mode {
-            ThemeMode::Mocha => Self::MOCHA_PEACH,
-            ThemeMode::Latte => Self::LATTE_PEACH,
+        self.palette().peach
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hibi_core::test_util::temp_dir;
+
+    #[test]
+    fn custom_palettes_fill_gaps_from_a_builtin_and_join_the_cycle() {
+        let dir = temp_dir("themes");
+        std::fs::write(dir.join("nord.yaml"), "colors:\n  base: \"#2e3440\"\n  blue: \"#88c0d0\"\n").unwrap();
+        std::fs::write(dir.join("paper.yml"), "name: Paper\nlight: true\ncolors:\n  text: black\n").unwrap();
+        std::fs::write(dir.join("broken.yaml"), "colors:\n  bluee: \"#000000\"\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+
+        let (palettes, errors) = load_palettes(&dir);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("unknown colour 'bluee'"), "{}", errors[0]);
+        let theme = Theme::with_palettes(palettes, Some("nord"));
+        assert_eq!(theme.name(), "nord");
+        assert_eq!(theme.bg_primary(), Color::Rgb(0x2e, 0x34, 0x40));
+        assert_eq!(theme.accent_primary(), Color::Rgb(0x88, 0xc0, 0xd0));
+        assert_eq!(theme.text_primary(), Palette::MOCHA.text);
+
+        let mut theme = theme;
+        theme.toggle();
+        assert_eq!(theme.name(), "Paper");
+        assert_eq!(Theme::with_palettes(Vec::new(), Some("high-contrast")).name(), "High Contrast");
+        assert_eq!(theme.text_primary(), Color::Black);
+        assert_eq!(theme.bg_primary(), Palette::LATTE.base);
+        theme.toggle();
+        assert_eq!(theme.name(), "Mocha");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn builtin_themes_keep_minimum_contrast() {
+        for palette in [Palette::MOCHA, Palette::LATTE, Palette::HIGH_CONTRAST] {
+            assert_eq!(palette.contrast_failures(), Vec::<String>::new(), "{:?}", palette.base);
         }
+        let ratio = contrast_ratio(Color::Rgb(0, 0, 0), Color::Rgb(255, 255, 255)).unwrap();
+        assert!((ratio - 21.0).abs() < 1e-9);
+        assert_eq!(contrast_ratio(Color::Black, Color::White), None);
+
+        let mut washed_out = Palette::LATTE;
+        washed_out.overlay0 = Color::Rgb(140, 143, 161);
+        assert_eq!(washed_out.contrast_failures(), ["overlay0 on base: 2.83 < 3", "overlay0 on mantle: 2.63 < 3"]);
     }
 }
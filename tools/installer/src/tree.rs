@@ -1,6 +1,7 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
-use crate::component::Component;
+use crate::component::{Component, InstallStatus};
 
 #[derive(Clone, Debug)]
 pub enum TreeNode {
@@ -187,6 +188,24 @@ impl TreeView {
         // For simplicity, we skip sorting and rely on insertion order
     }
 
+    /// Reorder the top level and every folder's children with `cmp`, which
+    /// compares node indices. The cursor stays on the node it was on.
+    pub fn sort_by(&mut self, mut cmp: impl FnMut(usize, usize) -> Ordering) {
+        let current = self.current_node_idx();
+
+        self.root_children.sort_by(|a, b| cmp(*a, *b));
+        for idx in 0..self.nodes.len() {
+            if let TreeNode::Folder { children, .. } = &mut self.nodes[idx] {
+                children.sort_by(|a, b| cmp(*a, *b));
+            }
+        }
+
+        self.rebuild_visible();
+        if let Some(pos) = current.and_then(|c| self.visible_indices.iter().position(|&idx| idx == c)) {
+            self.cursor = pos;
+        }
+    }
+
     /// Rebuild visible_indices based on expanded state
     pub fn rebuild_visible(&mut self) {
         self.visible_indices.clear();
@@ -302,6 +321,59 @@ impl TreeView {
         }
     }
 
+    /// Collapse every folder. The cursor stays on its node, or moves to the
+    /// top-level folder that now hides it.
+    pub fn collapse_all(&mut self) {
+        let current = self.current_node_idx();
+        self.set_all_expanded(false);
+        if let Some(mut idx) = current {
+            while let Some(parent) = self.parent_of(idx) {
+                idx = parent;
+            }
+            self.move_cursor_to(idx);
+        }
+    }
+
+    /// Expand every folder, keeping the cursor on the same node.
+    pub fn expand_all(&mut self) {
+        let current = self.current_node_idx();
+        self.set_all_expanded(true);
+        if let Some(idx) = current {
+            self.move_cursor_to(idx);
+        }
+    }
+
+    /// Move the cursor to the first visible node.
+    pub fn goto_top(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Move the cursor to the last visible node.
+    pub fn goto_bottom(&mut self) {
+        self.cursor = self.visible_indices.len().saturating_sub(1);
+    }
+
+    fn set_all_expanded(&mut self, value: bool) {
+        for node in &mut self.nodes {
+            if let TreeNode::Folder { expanded, .. } = node {
+                *expanded = value;
+            }
+        }
+        self.rebuild_visible();
+    }
+
+    fn parent_of(&self, node_idx: usize) -> Option<usize> {
+        match &self.nodes[node_idx] {
+            TreeNode::Folder { parent_idx, .. } | TreeNode::File { parent_idx, .. } => *parent_idx,
+        }
+    }
+
+    fn move_cursor_to(&mut self, node_idx: usize) {
+        if let Some(pos) = self.visible_indices.iter().position(|&idx| idx == node_idx) {
+            self.cursor = pos;
+        }
+    }
+
     /// Move cursor down
     pub fn next(&mut self) {
         if !self.visible_indices.is_empty() {
@@ -320,6 +392,35 @@ impl TreeView {
         }
     }
 
+    /// Expand every ancestor folder of `component_idx` and move the cursor
+    /// onto it. Returns false when the component isn't in this tree.
+    pub fn reveal_component(&mut self, component_idx: usize) -> bool {
+        let Some(node_idx) = self.nodes.iter().position(|n| {
+            matches!(n, TreeNode::File { component_idx: c, .. } if *c == component_idx)
+        }) else {
+            return false;
+        };
+
+        let mut parent = match &self.nodes[node_idx] {
+            TreeNode::File { parent_idx, .. } | TreeNode::Folder { parent_idx, .. } => *parent_idx,
+        };
+        while let Some(idx) = parent {
+            parent = match &mut self.nodes[idx] {
+                TreeNode::Folder { expanded, parent_idx, .. } => {
+                    *expanded = true;
+                    *parent_idx
+                }
+                TreeNode::File { .. } => None,
+            };
+        }
+
+        self.rebuild_visible();
+        if let Some(pos) = self.visible_indices.iter().position(|&idx| idx == node_idx) {
+            self.cursor = pos;
+        }
+        true
+    }
+
     /// Get all component indices under a folder (recursive)
     pub fn get_folder_component_indices(&self, folder_idx: usize) -> Vec<usize> {
         let mut indices = Vec::new();
@@ -354,12 +455,38 @@ impl TreeView {
         let indices = self.get_folder_component_indices(folder_idx);
         indices.iter().any(|&idx| components.get(idx).map(|c| c.selected).unwrap_or(false))
     }
+
+    /// Components under a folder counted by install status, in the order
+    /// new, modified, installed, managed, external, unconfigured; zero
+    /// counts are left out.
+    pub fn folder_status_counts(&self, folder_idx: usize, components: &[Component]) -> Vec<(InstallStatus, usize)> {
+        let statuses: Vec<&InstallStatus> = self.get_folder_component_indices(folder_idx)
+            .iter()
+            .filter_map(|&idx| components.get(idx).map(|c| &c.status))
+            .collect();
+        [
+            InstallStatus::New,
+            InstallStatus::Modified,
+            InstallStatus::Unchanged,
+            InstallStatus::Managed,
+            InstallStatus::External,
+            InstallStatus::Orphaned,
+            InstallStatus::Unconfigured,
+        ]
+            .into_iter()
+            .map(|status| {
+                let count = statuses.iter().filter(|s| ***s == status).count();
+                (status, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::component::{ComponentType, InstallStatus};
+    use crate::component::ComponentType;
     use std::path::PathBuf;
 
     fn make_component(name: &str) -> Component {
@@ -409,6 +536,111 @@ mod tests {
         assert!(tree.visible_indices.len() < initial_visible);
     }
 
+    #[test]
+    fn test_reveal_component_expands_collapsed_parents() {
+        let components = vec![
+            make_component("top.md"),
+            make_component("folder/sub/deep.md"),
+        ];
+
+        let filtered: Vec<(usize, &Component)> = components.iter().enumerate().collect();
+        let mut tree = TreeView::build_from_components(&components, &filtered);
+        for node in &mut tree.nodes {
+            if let TreeNode::Folder { expanded, .. } = node {
+                *expanded = false;
+            }
+        }
+        tree.rebuild_visible();
+
+        assert!(tree.reveal_component(1));
+        assert_eq!(tree.current_component_idx(), Some(1));
+        assert!(!tree.reveal_component(99));
+    }
+
+    #[test]
+    fn test_sort_by_reorders_children_and_keeps_cursor() {
+        let components = vec![
+            make_component("b.md"),
+            make_component("folder/y.md"),
+            make_component("folder/x.md"),
+            make_component("a.md"),
+        ];
+
+        let filtered: Vec<(usize, &Component)> = components.iter().enumerate().collect();
+        let mut tree = TreeView::build_from_components(&components, &filtered);
+        tree.reveal_component(1);
+
+        // Files by name, descending; folders after files.
+        let key = |node: &TreeNode| match node {
+            TreeNode::File { component_idx, .. } => (0, components[*component_idx].name.clone()),
+            TreeNode::Folder { name, .. } => (1, name.clone()),
+        };
+        let nodes = tree.nodes.clone();
+        tree.sort_by(|a, b| {
+            let (ka, kb) = (key(&nodes[a]), key(&nodes[b]));
+            ka.0.cmp(&kb.0).then_with(|| kb.1.cmp(&ka.1))
+        });
+
+        let order: Vec<Option<usize>> = tree.visible_indices.iter()
+            .map(|&i| match &tree.nodes[i] {
+                TreeNode::File { component_idx, .. } => Some(*component_idx),
+                TreeNode::Folder { .. } => None,
+            })
+            .collect();
+        assert_eq!(order, vec![Some(0), Some(3), None, Some(1), Some(2)]);
+        assert_eq!(tree.current_component_idx(), Some(1));
+    }
+
+    #[test]
+    fn test_folder_status_counts_roll_up_descendants() {
+        let mut components = vec![
+            make_component("tools/a/SKILL.md"),
+            make_component("tools/b/SKILL.md"),
+            make_component("tools/nested/c/SKILL.md"),
+            make_component("other/SKILL.md"),
+        ];
+        components[1].status = InstallStatus::Modified;
+        components[2].status = InstallStatus::Unchanged;
+        let filtered: Vec<(usize, &Component)> = components.iter().enumerate().collect();
+        let tree = TreeView::build_from_components(&components, &filtered);
+
+        let tools = tree.nodes.iter()
+            .position(|n| matches!(n, TreeNode::Folder { name, .. } if name == "tools"))
+            .unwrap();
+        assert_eq!(
+            tree.folder_status_counts(tools, &components),
+            [(InstallStatus::New, 1), (InstallStatus::Modified, 1), (InstallStatus::Unchanged, 1)],
+        );
+    }
+
+    #[test]
+    fn test_collapse_and_expand_all_keep_the_cursor_in_place() {
+        let components = vec![
+            make_component("a/deep/one.md"),
+            make_component("a/two.md"),
+            make_component("b/three.md"),
+        ];
+        let filtered: Vec<(usize, &Component)> = components.iter().enumerate().collect();
+        let mut tree = TreeView::build_from_components(&components, &filtered);
+        tree.expand_all();
+        assert!(tree.reveal_component(0));
+        let file = tree.current_node_idx();
+
+        tree.collapse_all();
+        assert_eq!(tree.visible_indices.len(), 2, "only the top-level folders remain");
+        assert!(matches!(tree.current_node(), Some(TreeNode::Folder { name, .. }) if name == "a"));
+
+        let folder = tree.current_node_idx();
+        tree.expand_all();
+        assert_eq!(tree.current_node_idx(), folder);
+        assert!(tree.visible_indices.contains(&file.unwrap()));
+
+        tree.goto_bottom();
+        assert_eq!(tree.cursor, tree.visible_indices.len() - 1);
+        tree.goto_top();
+        assert_eq!(tree.cursor, 0);
+    }
+
     #[test]
     fn test_tree_debug() {
         let components = vec![
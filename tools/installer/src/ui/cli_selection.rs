@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -6,7 +8,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, TargetCli};
 
 /// ANSI Shadow figlet rendering of "HIBI AI". 6 lines tall, ~50 cols wide.
 const BRAND_ART: &[&str] = &[
@@ -29,25 +31,52 @@ const BRAND_HEIGHT: u16 = 1 + 6 + 1 + 1;
 
 /// Options shown on the initial screen. Order is the cursor index order.
 struct OptionRow {
-    label: &'static str,
-    detail: &'static str,
+    label: Cow<'static, str>,
+    detail: Cow<'static, str>,
+    /// CLI whose resolved binary is shown under the detail line.
+    cli: Option<TargetCli>,
 }
 
-const OPTIONS: &[OptionRow] = &[
+const BUILTIN_OPTIONS: &[OptionRow] = &[
+    OptionRow {
+        label: Cow::Borrowed("Claude Code"),
+        detail: Cow::Borrowed("Anthropic's official CLI for Claude (~/.claude)"),
+        cli: Some(TargetCli::Claude),
+    },
     OptionRow {
-        label: "Claude Code",
-        detail: "Anthropic's official CLI for Claude (~/.claude)",
+        label: Cow::Borrowed("Codex CLI"),
+        detail: Cow::Borrowed("OpenAI's ChatGPT-based CLI (~/.codex)"),
+        cli: Some(TargetCli::Codex),
     },
     OptionRow {
-        label: "Codex CLI",
-        detail: "OpenAI's ChatGPT-based CLI (~/.codex)",
+        label: Cow::Borrowed("Cursor"),
+        detail: Cow::Borrowed("Cursor editor rules and MCP servers (~/.cursor)"),
+        cli: Some(TargetCli::Cursor),
     },
     OptionRow {
-        label: "Manage Sources",
-        detail: "Configure component sources (~/.hibi/sources.yaml)",
+        label: Cow::Borrowed("OpenCode"),
+        detail: Cow::Borrowed("Open-source terminal coding agent (~/.config/opencode)"),
+        cli: Some(TargetCli::OpenCode),
     },
 ];
 
+/// Built-in targets, then the `targets:` ones, then Manage Sources; the
+/// same order `TargetCli::all()` gives the cursor.
+fn options() -> Vec<OptionRow> {
+    let builtin = BUILTIN_OPTIONS.iter().map(|o| OptionRow { label: o.label.clone(), detail: o.detail.clone(), cli: o.cli });
+    let custom = TargetCli::all().into_iter().filter(|cli| cli.custom().is_some()).map(|cli| OptionRow {
+        label: Cow::Owned(cli.display_name().to_string()),
+        detail: Cow::Owned(format!("Custom target from sources.yaml (~/{})", cli.config_dir_name())),
+        cli: Some(cli),
+    });
+    let sources = OptionRow {
+        label: Cow::Borrowed("Manage Sources"),
+        detail: Cow::Borrowed("Configure component sources (~/.hibi/sources.yaml)"),
+        cli: None,
+    };
+    builtin.chain(custom).chain(std::iter::once(sources)).collect()
+}
+
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
     // Three rows: brand banner, options, single-line version footer.
     let chunks = Layout::default()
@@ -103,12 +132,14 @@ fn render_brand(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_options(f: &mut Frame, app: &App, area: Rect) {
-    // Two visual rows per option (label + detail) plus a blank spacer; the
+    // Label, detail, CLI path/version rows plus a blank spacer; the
     // whole block is centered horizontally so the longest detail line
     // anchors the column.
-    let mut lines: Vec<Line> = Vec::with_capacity(OPTIONS.len() * 3);
+    let options = options();
+    let mut lines: Vec<Line> = Vec::with_capacity(options.len() * 4);
+    let mut selected_end = 0;
 
-    for (idx, opt) in OPTIONS.iter().enumerate() {
+    for (idx, opt) in options.into_iter().enumerate() {
         let selected = idx == app.cli_selection_index;
         let (marker, label_color, label_mod) = if selected {
             ("▶ ", app.theme.accent_primary(), Modifier::BOLD)
@@ -130,8 +161,19 @@ fn render_options(f: &mut Frame, app: &App, area: Rect) {
             format!("    {}", opt.detail),
             Style::default().fg(app.theme.text_muted()),
         )));
+        if let Some(cli) = opt.cli {
+            lines.extend(cli_info_lines(app, cli));
+        }
+        if selected {
+            selected_end = lines.len();
+        }
         lines.push(Line::from(""));
     }
+    // On short terminals, scroll just far enough to keep the highlighted
+    // option in view.
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let scroll = selected_end.saturating_sub(inner_height) as u16;
+    lines.extend(profile_lines(app));
 
     let paragraph = Paragraph::new(lines)
         .alignment(Alignment::Left)
@@ -141,15 +183,73 @@ fn render_options(f: &mut Frame, app: &App, area: Rect) {
                 .border_style(Style::default().fg(app.theme.border()))
                 .title(" Select target ")
                 .title_style(Style::default().fg(app.theme.text_primary())),
-        );
+        )
+        .scroll((scroll, 0));
 
     f.render_widget(paragraph, area);
 }
 
+/// Resolved binary path and version for `cli`, plus a warning line when
+/// PATH holds more than one candidate.
+fn cli_info_lines(app: &App, cli: TargetCli) -> Vec<Line<'static>> {
+    let muted = Style::default().fg(app.theme.text_muted());
+    let warn = Style::default().fg(app.theme.warning());
+
+    let Some(info) = app.cli_info(cli) else {
+        return vec![Line::from(Span::styled("    Resolving CLI...", muted))];
+    };
+    let Some(path) = &info.path else {
+        return vec![Line::from(Span::styled("    Not found in PATH", warn))];
+    };
+
+    let version = info.version.as_deref().unwrap_or("version unknown");
+    let mut lines = vec![Line::from(Span::styled(
+        format!("    {} · {}", crate::source::config::contract_tilde(path), version),
+        muted,
+    ))];
+    if info.candidates.len() > 1 {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    ⚠ {} candidates on PATH — run `type -a {}` to check which one you use",
+                info.candidates.len(),
+                path.file_stem().and_then(|s| s.to_str()).unwrap_or("cli"),
+            ),
+            warn,
+        )));
+    }
+    lines
+}
+
+/// Install profile picker row, shown when a source has `profiles.yaml`.
+fn profile_lines(app: &App) -> Vec<Line<'static>> {
+    if app.profiles.is_empty() {
+        return Vec::new();
+    }
+    let muted = Style::default().fg(app.theme.text_muted());
+    let (name, detail) = match app.active_profile() {
+        Some(profile) => (
+            profile.name.clone(),
+            profile.description.clone().unwrap_or_else(|| {
+                let count = profile.components.len() + profile.mcp_servers.len() + profile.plugins.len();
+                format!("{} entries", count)
+            }),
+        ),
+        None => ("none".to_string(), "keep the default selection".to_string()),
+    };
+    vec![
+        Line::from(vec![
+            Span::styled("  Profile: ", Style::default().fg(app.theme.text_secondary())),
+            Span::styled(name, Style::default().fg(app.theme.accent_secondary()).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  [p] Change ({} available)", app.profiles.len()), muted),
+        ]),
+        Line::from(Span::styled(format!("    {}", detail), muted)),
+    ]
+}
+
 fn render_version_footer(f: &mut Frame, app: &App, area: Rect) {
     // Single trailing space keeps the version one column off the right
     // border, matching the visual rhythm of the rest of the screen.
-    let text = format!("{} ", crate::fs::VERSION);
+    let text = format!("{} ", crate::VERSION);
     let paragraph = Paragraph::new(Line::from(Span::styled(
         text,
         Style::default().fg(app.theme.text_muted()),
@@ -1,5 +1,5 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
@@ -11,28 +11,20 @@ use crate::app::App;
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let content = app.diff_content.as_deref().unwrap_or("No diff available");
 
-    let lines: Vec<Line> = content
-        .lines()
-        .map(|line| {
-            let style = if line.starts_with('+') && !line.starts_with("+++") {
-                Style::default().fg(app.theme.diff_added())
-            } else if line.starts_with('-') && !line.starts_with("---") {
-                Style::default().fg(app.theme.diff_removed())
-            } else if line.starts_with("@@") {
-                Style::default().fg(app.theme.accent_secondary())
-            } else if line.starts_with("---") || line.starts_with("+++") {
-                Style::default().fg(app.theme.warning())
-            } else {
-                Style::default().fg(app.theme.text_primary())
-            };
-
-            Line::from(Span::styled(line, style))
-        })
-        .collect();
+    let lines = diff_lines(app, content);
 
     let title = if let Some(idx) = app.selected_component_index() {
         if let Some(c) = app.components.get(idx) {
-            format!(" Diff: {} ", c.display_name())
+            match &app.diff_hunks {
+                Some(diff) => format!(
+                    " Diff: {} (hunk {}/{}, {} staged) ",
+                    c.display_name(),
+                    app.hunk_index + 1,
+                    diff.hunks.len(),
+                    app.hunk_staged.iter().filter(|s| **s).count(),
+                ),
+                None => format!(" Diff: {} ", c.display_name()),
+            }
         } else {
             " Diff ".to_string()
         }
@@ -50,5 +42,53 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         )
         .scroll((app.diff_scroll, 0));
 
+    let area = render_summary(f, app, area);
     f.render_widget(paragraph, area);
 }
+
+/// Frontmatter changes in a box above the diff; returns the area left for
+/// the diff itself.
+fn render_summary(f: &mut Frame, app: &App, area: Rect) -> Rect {
+    if app.diff_summary.is_empty() {
+        return area;
+    }
+    let height = (app.diff_summary.len() as u16 + 2).min(area.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(height), Constraint::Min(0)])
+        .split(area);
+    let lines: Vec<Line> = app.diff_summary.iter()
+        .map(|change| Line::from(Span::styled(format!(" {}", change), Style::default().fg(app.theme.text_primary()))))
+        .collect();
+    let summary = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border()))
+            .title(" Frontmatter changes ")
+            .title_style(Style::default().fg(app.theme.accent_secondary())),
+    );
+    f.render_widget(summary, chunks[0]);
+    chunks[1]
+}
+
+/// Diff text as lines colored by `+`/`-`/`@@` prefix.
+pub(super) fn diff_lines<'a>(app: &App, content: &'a str) -> Vec<Line<'a>> {
+    content
+        .lines()
+        .map(|line| {
+            let style = if line.starts_with('+') && !line.starts_with("+++") {
+                Style::default().fg(app.theme.diff_added())
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                Style::default().fg(app.theme.diff_removed())
+            } else if line.starts_with("@@") {
+                Style::default().fg(app.theme.accent_secondary())
+            } else if line.starts_with("---") || line.starts_with("+++") {
+                Style::default().fg(app.theme.warning())
+            } else {
+                Style::default().fg(app.theme.text_primary())
+            };
+
+            Line::from(Span::styled(line, style))
+        })
+        .collect()
+}
@@ -0,0 +1,46 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Scrollable popup over the processing log with a failed item's full
+/// message and captured output.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let Some(failure) = app.current_failure() else { return };
+    let dialog = super::source_wizard::centered_rect(85, area.height.saturating_sub(4), area);
+    f.render_widget(Clear, dialog);
+
+    let lines: Vec<Line> = failure.detail_lines()
+        .into_iter()
+        .map(|line| {
+            let style = if line.starts_with("$ ") {
+                Style::default().fg(app.theme.text_muted())
+            } else if line.starts_with("── ") {
+                Style::default().fg(app.theme.text_secondary()).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text_primary())
+            };
+            Line::from(Span::styled(line, style))
+        })
+        .collect();
+
+    let position = app.failure_detail.unwrap_or(0) + 1;
+    let title = format!(" {} ({}/{} failed) ", failure.name, position, app.item_failures.len());
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((app.failure_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.error()))
+                .title(title)
+                .title_style(Style::default().fg(app.theme.error()).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+    f.render_widget(paragraph, dialog);
+}
@@ -0,0 +1,40 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// `git status` of the local sources with uncommitted changes.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let dialog = super::source_wizard::centered_rect(80, area.height.saturating_sub(4), area);
+    f.render_widget(Clear, dialog);
+
+    let lines: Vec<Line> = app.git_status_lines()
+        .into_iter()
+        .skip(app.git_status_scroll)
+        .map(|line| {
+            let style = if line.starts_with(' ') {
+                Style::default().fg(app.theme.text_primary())
+            } else {
+                Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+            };
+            Line::from(Span::styled(line, style))
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(" Uncommitted Source Changes ")
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+
+    f.render_widget(paragraph, dialog);
+}
@@ -0,0 +1,145 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, Tab, View};
+
+/// A titled group of `(keys, action)` rows.
+type Section = (&'static str, &'static [(&'static str, &'static str)]);
+
+const LIST: Section = ("List", &[
+    ("Tab", "Focus tab bar / list"),
+    ("↑/↓ j/k", "Move"),
+    ("g/G Home/End", "Jump to top/bottom"),
+    ("←/→ h/l", "Collapse/expand folder"),
+    ("z / Z", "Collapse/expand all folders"),
+    ("Enter", "Open folder or diff"),
+    ("Space", "Toggle selection"),
+    ("a / n", "Select all / none"),
+    ("N / M / O", "Select new / modified / orphaned"),
+    ("p", "Prune orphaned files"),
+    ("m", "Mirror: make tabs match the source"),
+    ("i / r", "Install / remove selected"),
+    ("k / o / s", "Keep / overwrite / skip a modified file"),
+    ("Enter / c", "Queue / try an MCP or plugin install while offline"),
+    ("d", "Diff against installed file"),
+    ("E", "Export diff report"),
+    ("D", "Export drift report (JSON)"),
+    ("s / u", "Set/unset default style/statusline"),
+    ("F", "Fix a default whose file is missing: install it or unset it"),
+    ("K / J", "Move hook up/down (Hooks)"),
+    ("e / L", "Hook details / logs (Hooks)"),
+    ("e", "Edit keywords (Agents, Skills)"),
+    ("c", "Create hook.yaml for unconfigured hook"),
+    ("x", "Export registered hooks as a shell script (Hooks)"),
+    ("H", "settings.json history (Config)"),
+    ("v", "settings.json keys and owners (Config)"),
+    ("W", "git status of sources with uncommitted changes"),
+    ("P", "Port Claude Code skills and MCP servers (Codex)"),
+    ("T", "Show/hide items meant for other hosts"),
+    ("Ctrl+F", "Search all tabs"),
+    ("Ctrl+P", "Command palette: run any action by name"),
+    ("S", "Sort"),
+    ("R", "Rescan"),
+    ("Ctrl+Z (twice)", "Undo last install or remove"),
+    ("t", "Next theme"),
+    ("Esc", "Hide the tip in the status bar for good (list focused)"),
+    ("q", "Quit"),
+]);
+
+const MCP: Section = ("MCP servers & plugins", &[
+    ("↑/↓ j/k", "Move"),
+    ("Space", "Toggle selection"),
+    ("i / r", "Install / remove selected"),
+    ("o", "Switch MCP scope (user/local)"),
+    ("U", "Check MCP servers for updates"),
+    ("Ctrl+F / S", "Search / sort"),
+]);
+
+const DIFF: Section = ("Diff", &[
+    ("↑/↓ j/k", "Scroll"),
+    ("n / p", "Next/previous hunk"),
+    ("Space", "Stage or unstage hunk"),
+    ("a", "Apply staged hunks"),
+    ("A (twice)", "Adopt: copy the installed file into the source"),
+    ("q / Esc", "Close"),
+]);
+
+const INSTALLING: Section = ("Installing", &[
+    ("Esc / C", "Cancel the item and everything still queued"),
+    ("Esc (twice)", "Abort the cleanup of a cancelled item"),
+    ("s", "Skip the item and go on with the next"),
+    ("c", "Show/hide the commands run"),
+    ("e", "Full output of failed items (n/p: next/prev)"),
+    ("Enter / q", "Close when done"),
+]);
+
+/// Which section describes what's under the overlay.
+fn current_section(app: &App) -> &'static str {
+    match app.current_view {
+        View::Diff => DIFF.0,
+        View::Installing => INSTALLING.0,
+        _ if matches!(app.tab, Tab::McpServers | Tab::Plugins) => MCP.0,
+        _ => LIST.0,
+    }
+}
+
+/// Centered overlay listing the keys of every view: the list on the left,
+/// the other views on the right, the one under the overlay highlighted.
+/// Any key closes it.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let current = current_section(app);
+    let key_width = [LIST, MCP, DIFF, INSTALLING].iter()
+        .flat_map(|(_, keys)| keys.iter().map(|(k, _)| k.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let left = section_lines(app, &[LIST], current, key_width);
+    let right = section_lines(app, &[MCP, DIFF, INSTALLING], current, key_width);
+
+    let height = (left.len().max(right.len()) as u16 + 2).min(area.height);
+    let dialog = super::source_wizard::centered_rect(90, height, area);
+    f.render_widget(Clear, dialog);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_focused()))
+        .title(" Keys ")
+        .title_bottom(Line::from(" any key to close ").right_aligned())
+        .title_style(Style::default().fg(app.theme.text_primary()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+    let inner = block.inner(dialog);
+    f.render_widget(block, dialog);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    f.render_widget(Paragraph::new(left), columns[0]);
+    f.render_widget(Paragraph::new(right), columns[1]);
+}
+
+fn section_lines(app: &App, sections: &[Section], current: &str, key_width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (title, keys) in sections {
+        let style = if *title == current {
+            Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD)
+        };
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(format!(" {}", title), style)));
+        for (key, action) in *keys {
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {:<width$}  ", key, width = key_width), Style::default().fg(app.theme.accent_secondary())),
+                Span::styled(*action, Style::default().fg(app.theme.text_secondary())),
+            ]));
+        }
+    }
+    lines
+}
@@ -0,0 +1,103 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let Some(config) = app.current_hook_detail() else { return };
+
+    let env_rows = app.hook_env_rows.len().max(1) as u16;
+    let height = 12 + env_rows;
+
+    // Center the dialog
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let horizontal = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .split(vertical[1]);
+
+    let dialog_area = horizontal[1];
+
+    // Clear background
+    f.render_widget(Clear, dialog_area);
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(app.theme.text_secondary()));
+    let value = |text: String| Span::styled(text, Style::default().fg(app.theme.text_primary()));
+
+    let timeout = config.timeout.map(|t| format!("{}s", t)).unwrap_or_else(|| "-".to_string());
+    let position = app.hook_positions
+        .get(&config.hook_command_path(&app.dest_dir))
+        .map(|(pos, total)| format!("#{}/{}", pos, total))
+        .unwrap_or_else(|| "not registered".to_string());
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![label("  Event:       "), value(config.event.clone())]),
+        Line::from(vec![label("  Type:        "), value(config.hook_type.clone())]),
+        Line::from(vec![label("  Timeout:     "), value(timeout)]),
+        Line::from(vec![label("  Priority:    "), value(config.effective_priority().to_string())]),
+        Line::from(vec![label("  Position:    "), value(position)]),
+        Line::from(vec![label("  Description: "), value(config.description.clone().unwrap_or_default())]),
+        Line::from(""),
+        Line::from(Span::styled("  Environment:", Style::default().fg(app.theme.text_muted()))),
+    ];
+
+    if app.hook_env_rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    (none declared in hook.yaml)",
+            Style::default().fg(app.theme.text_muted()),
+        )));
+    }
+
+    for (i, (name, current)) in app.hook_env_rows.iter().enumerate() {
+        let selected = i == app.hook_env_cursor;
+        let marker = if selected { "  > " } else { "    " };
+        let name_style = if selected {
+            Style::default().fg(app.theme.warning()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_secondary())
+        };
+
+        let mut spans = vec![
+            Span::styled(format!("{}{} = ", marker, name), name_style),
+        ];
+        if selected && app.hook_env_editing {
+            spans.push(Span::styled(&app.hook_env_buffer, Style::default().fg(app.theme.text_primary())));
+            spans.push(Span::styled("_", Style::default().fg(app.theme.accent_secondary()).add_modifier(Modifier::SLOW_BLINK)));
+        } else {
+            spans.push(Span::styled(current, Style::default().fg(app.theme.success())));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_secondary()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(format!(" Hook: {} ", config.name))
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(paragraph, dialog_area);
+}
@@ -0,0 +1,57 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::hook_log::LogLevel;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let dialog = super::source_wizard::centered_rect(90, area.height.saturating_sub(2), area);
+    f.render_widget(Clear, dialog);
+
+    let visible = app.visible_hook_logs();
+    let height = dialog.height.saturating_sub(2) as usize;
+    // Scroll counts back from the newest line; show the window ending there.
+    let end = visible.len().saturating_sub(app.hook_log_scroll);
+    let start = end.saturating_sub(height);
+
+    let lines: Vec<Line> = if visible.is_empty() {
+        vec![Line::from(Span::styled(
+            format!("  No log lines in {}", app.dest_dir.join("sessions").display()),
+            Style::default().fg(app.theme.text_muted()),
+        ))]
+    } else {
+        visible[start..end].iter().map(|l| {
+            let color = match l.level {
+                Some(LogLevel::Error) => app.theme.error(),
+                Some(LogLevel::Warn) => app.theme.warning(),
+                Some(LogLevel::Debug) => app.theme.text_muted(),
+                _ => app.theme.text_primary(),
+            };
+            Line::from(Span::styled(l.text.clone(), Style::default().fg(color)))
+        }).collect()
+    };
+
+    let title = format!(
+        " Hook Logs — hook: {}  level: {}{} ",
+        app.hook_log_filter.as_deref().unwrap_or("all"),
+        app.hook_log_level.map(|l| format!("{}+", l.display())).unwrap_or_else(|| "all".to_string()),
+        if app.hook_log_scroll > 0 { "  (scrolled)" } else { "" },
+    );
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(title)
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+
+    f.render_widget(paragraph, dialog);
+}
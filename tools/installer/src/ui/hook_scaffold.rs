@@ -0,0 +1,41 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::component::HOOK_EVENTS;
+
+/// Event picker for the hook.yaml being scaffolded.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let dialog = super::source_wizard::centered_rect(40, HOOK_EVENTS.len() as u16 + 4, area);
+    f.render_widget(Clear, dialog);
+
+    let mut lines = vec![Line::from("")];
+    for (i, event) in HOOK_EVENTS.iter().enumerate() {
+        let selected = i == app.hook_scaffold_index;
+        let marker = if selected { "  > " } else { "    " };
+        let style = if selected {
+            Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_primary())
+        };
+        lines.push(Line::from(vec![Span::styled(marker, style), Span::styled(*event, style)]));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_secondary()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(" Run the hook on ")
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+
+    f.render_widget(paragraph, dialog);
+}
@@ -28,10 +28,10 @@ fn render_title(f: &mut Frame, app: &App, area: Rect) {
     let (title_text, title_color) = if app.processing_complete {
         ("✓ Complete".to_string(), app.theme.success())
     } else if app.needs_refresh {
-        let spinner = super::get_spinner(app.animation_frame);
+        let spinner = super::get_spinner(crate::clock::frame());
         (format!("{} Refreshing status...", spinner), app.theme.warning())
     } else {
-        let spinner = super::get_spinner(app.animation_frame);
+        let spinner = super::get_spinner(crate::clock::frame());
         let text = if app.is_removing {
             format!("{} Removing...", spinner)
         } else {
@@ -72,17 +72,24 @@ fn render_log(f: &mut Frame, app: &App, area: Rect) {
     let log_items: Vec<ListItem> = app
         .processing_log
         .iter()
-        .map(|msg| {
+        .filter_map(|msg| {
+            // External commands sit under the item they ran for, shown on `c`.
+            if let Some(command) = msg.strip_prefix("[CMD] ") {
+                return app.show_commands.then(|| ListItem::new(Line::from(Span::styled(
+                    format!("    $ {}", command),
+                    Style::default().fg(app.theme.text_muted()),
+                ))));
+            }
             let style = if msg.starts_with("[OK]") {
                 Style::default().fg(app.theme.success())
             } else if msg.starts_with("[ERR]") {
                 Style::default().fg(app.theme.error())
-            } else if msg.starts_with("[SKIP]") {
+            } else if msg.starts_with("[SKIP]") || msg.starts_with("[QUEUED]") {
                 Style::default().fg(app.theme.warning())
             } else {
                 Style::default().fg(app.theme.text_secondary())
             };
-            ListItem::new(Line::from(Span::styled(msg.clone(), style)))
+            Some(ListItem::new(Line::from(Span::styled(msg.clone(), style))))
         })
         .collect();
 
@@ -92,7 +99,7 @@ fn render_log(f: &mut Frame, app: &App, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(app.theme.border()))
-                .title(" Log ")
+                .title(log_title(app))
                 .title_style(Style::default().fg(app.theme.text_primary())),
         );
 
@@ -103,3 +110,20 @@ fn render_log(f: &mut Frame, app: &App, area: Rect) {
     }
     f.render_stateful_widget(log_list, area, &mut log_state);
 }
+
+/// Log title with the keys that apply: `c` once commands were logged, `e`
+/// once an item failed.
+fn log_title(app: &App) -> String {
+    let mut keys = Vec::new();
+    if app.processing_log.iter().any(|l| l.starts_with("[CMD] ")) {
+        keys.push(if app.show_commands { "[c] hide commands" } else { "[c] show commands" });
+    }
+    if !app.item_failures.is_empty() {
+        keys.push("[e] error details");
+    }
+    if keys.is_empty() {
+        " Log ".to_string()
+    } else {
+        format!(" Log ({}) ", keys.join(", "))
+    }
+}
@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Editable keyword list of the agent or skill being tuned.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let Some(component) = app.keyword_edit_idx.and_then(|idx| app.components.get(idx)) else { return };
+
+    let rows = app.keyword_rows.len().max(1) as u16;
+    let dialog = super::source_wizard::centered_rect(60, (rows + 4).min(area.height), area);
+    f.render_widget(Clear, dialog);
+
+    let mut lines = vec![Line::from("")];
+    if app.keyword_rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    (no keywords; press a to add one)",
+            Style::default().fg(app.theme.text_muted()),
+        )));
+    }
+
+    // Keep the cursor visible when the list is taller than the dialog.
+    let visible = dialog.height.saturating_sub(4).max(1) as usize;
+    let skip = app.keyword_cursor.saturating_sub(visible - 1);
+    for (i, keyword) in app.keyword_rows.iter().enumerate().skip(skip).take(visible) {
+        let selected = i == app.keyword_cursor;
+        let marker = if selected { "  > " } else { "    " };
+        let style = if selected {
+            Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_primary())
+        };
+
+        let mut spans = vec![Span::styled(marker, style)];
+        if selected && app.keyword_editing {
+            spans.push(Span::styled(&app.keyword_buffer, Style::default().fg(app.theme.text_primary())));
+            spans.push(Span::styled("_", Style::default().fg(app.theme.accent_secondary()).add_modifier(Modifier::SLOW_BLINK)));
+        } else {
+            spans.push(Span::styled(keyword, style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let modified = if app.keywords_dirty { " (modified)" } else { "" };
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_secondary()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(format!(" Keywords: {}{} ", component.name, modified))
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+
+    f.render_widget(paragraph, dialog);
+}
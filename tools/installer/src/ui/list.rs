@@ -1,26 +1,179 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use crate::app::{App, Tab};
+use crate::app::{format_tokens, App, Tab};
 use crate::component::InstallStatus;
+use crate::source::config::contract_tilde;
 use crate::tree::TreeNode;
 
-pub fn render(f: &mut Frame, app: &App, area: Rect) {
+use super::ListArea;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) -> Option<ListArea> {
+    let area = match app.source_changes_banner() {
+        Some(banner) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            let line = Line::from(Span::styled(
+                format!(" ⚠ {}", banner),
+                Style::default().fg(app.theme.warning()).add_modifier(Modifier::BOLD),
+            ));
+            f.render_widget(Paragraph::new(line).style(Style::default().bg(app.theme.bg_primary())), rows[0]);
+            rows[1]
+        }
+        None => area,
+    };
     // Use tree-based rendering for component tabs
     if let Some(tree) = app.get_tree_view() {
-        render_tree(f, app, tree, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+        let list_area = if chunks[0].width >= PREVIEW_MIN_WIDTH {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(chunks[0]);
+            render_preview(f, app, panes[1]);
+            panes[0]
+        } else {
+            chunks[0]
+        };
+        let list = render_tree(f, app, tree, list_area);
+        render_path_info(f, app, chunks[1]);
+        Some(list)
     } else {
         // Fallback to flat list (shouldn't happen for component tabs)
         render_flat(f, app, area);
+        None
     }
 }
 
-fn render_tree(f: &mut Frame, app: &App, tree: &crate::tree::TreeView, area: Rect) {
+/// Narrowest list area that still gets a preview pane beside it.
+const PREVIEW_MIN_WIDTH: u16 = 100;
+
+/// Head of the highlighted file, frontmatter dimmed and headings bold.
+fn render_preview(f: &mut Frame, app: &App, area: Rect) {
+    let (title, lines) = match &app.preview {
+        Some((path, lines)) => {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            (format!(" {} ", name), preview_lines(app, lines))
+        }
+        None => (" Preview ".to_string(), Vec::new()),
+    };
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border()))
+            .title(title)
+            .title_style(Style::default().fg(app.theme.text_primary()))
+            .style(Style::default().bg(app.theme.bg_primary())),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn preview_lines<'a>(app: &App, lines: &'a [String]) -> Vec<Line<'a>> {
+    let mut in_frontmatter = lines.first().is_some_and(|l| l == "---");
+    lines.iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let style = if in_frontmatter {
+                if i > 0 && line == "---" {
+                    in_frontmatter = false;
+                }
+                Style::default().fg(app.theme.text_muted())
+            } else if line.starts_with('#') {
+                Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text_secondary())
+            };
+            Line::from(Span::styled(line.as_str(), style))
+        })
+        .collect()
+}
+
+/// One-line `source → dest` mapping for the highlighted component, so
+/// identically named files in different folders can be told apart.
+fn render_path_info(f: &mut Frame, app: &App, area: Rect) {
+    let Some(component) = app.selected_component_index().and_then(|idx| app.components.get(idx)) else {
+        return;
+    };
+    let mut spans = vec![
+        Span::styled(format!(" {}", contract_tilde(&component.source_path)), Style::default().fg(app.theme.text_muted())),
+        Span::styled(" → ", Style::default().fg(app.theme.text_secondary())),
+        Span::styled(contract_tilde(&component.dest_path), Style::default().fg(app.theme.text_muted())),
+    ];
+    if app.uncommitted_source(component).is_some() {
+        spans.push(Span::styled("  (uncommitted)", Style::default().fg(app.theme.warning())));
+    }
+    let line = Line::from(spans);
+    f.render_widget(Paragraph::new(line).style(Style::default().bg(app.theme.bg_primary())), area);
+}
+
+/// List title plus the tab's estimated context tokens and, on those tabs,
+/// the always-loaded budget warning.
+fn title_with_tokens(app: &App, title: String) -> Line<'static> {
+    let Some(tokens) = app.tab_context_tokens(app.tab) else {
+        return Line::from(title);
+    };
+    let mut spans = vec![Span::raw(format!("{} [~{} tokens] ", title.trim_end(), format_tokens(tokens)))];
+    if let Some(warning) = app.token_budget_warning() {
+        spans.push(Span::styled(
+            format!("⚠ {} ", warning),
+            Style::default().fg(app.theme.warning()).add_modifier(Modifier::BOLD),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn status_style(app: &App, status: &InstallStatus) -> Style {
+    match status {
+        InstallStatus::New => Style::default().fg(app.theme.success()),
+        InstallStatus::Modified => Style::default().fg(app.theme.warning()),
+        InstallStatus::Unchanged => Style::default().fg(app.theme.text_secondary()),
+        InstallStatus::Managed => Style::default().fg(app.theme.accent_primary()),
+        InstallStatus::External => Style::default().fg(app.theme.text_muted()),
+        InstallStatus::Orphaned => Style::default().fg(app.theme.error()),
+        InstallStatus::Unconfigured => Style::default().fg(app.theme.peach()),
+    }
+}
+
+/// Short status name for folder rollups.
+fn rollup_label(status: &InstallStatus) -> &'static str {
+    match status {
+        InstallStatus::New => "new",
+        InstallStatus::Modified => "mod",
+        InstallStatus::Unchanged => "ok",
+        InstallStatus::Managed => "managed",
+        InstallStatus::External => "ext",
+        InstallStatus::Orphaned => "orphan",
+        InstallStatus::Unconfigured => "unconfig",
+    }
+}
+
+/// Tab label, plus the current default on the Styles and Statusline tabs
+/// and a warning when its file is missing.
+fn list_title(app: &App) -> String {
+    let label = app.tab_label(app.tab);
+    let current = match app.tab {
+        Tab::OutputStyles => app.current_output_style.as_ref(),
+        Tab::Statusline => app.current_statusline.as_ref(),
+        _ => return format!(" {} ", label),
+    };
+    match (current, app.dangling_warning()) {
+        (Some(current), Some(warning)) => format!(" {} [Default: {}, {}: F to fix] ", label, current, warning),
+        (Some(current), None) => format!(" {} [Default: {}] ", label, current),
+        (None, _) => format!(" {} [No default set] ", label),
+    }
+}
+
+fn render_tree(f: &mut Frame, app: &App, tree: &crate::tree::TreeView, area: Rect) -> ListArea {
     let items: Vec<ListItem> = tree.visible_indices
         .iter()
         .map(|&node_idx| {
@@ -29,28 +182,14 @@ fn render_tree(f: &mut Frame, app: &App, tree: &crate::tree::TreeView, area: Rec
         })
         .collect();
 
-    // Show current default status in title
-    let mut title = format!(" {} ", app.tab.display_name());
-    if app.tab == Tab::OutputStyles {
-        if let Some(ref style) = app.current_output_style {
-            title = format!("{} [Default: {}] ", title.trim(), style);
-        } else {
-            title = format!("{} [No default set] ", title.trim());
-        }
-    } else if app.tab == Tab::Statusline {
-        if let Some(ref statusline) = app.current_statusline {
-            title = format!("{} [Default: {}] ", title.trim(), statusline);
-        } else {
-            title = format!("{} [No default set] ", title.trim());
-        }
-    }
+    let title = list_title(app);
 
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(app.theme.border()))
-                .title(title)
+                .title(title_with_tokens(app, title))
                 .title_style(Style::default().fg(app.theme.text_primary()))
                 .style(Style::default().bg(app.theme.bg_primary())),
         )
@@ -69,6 +208,7 @@ fn render_tree(f: &mut Frame, app: &App, tree: &crate::tree::TreeView, area: Rec
     }
 
     f.render_stateful_widget(list, area, &mut state);
+    ListArea { inner: area.inner(Margin::new(1, 1)), offset: state.offset(), item_height: 1 }
 }
 
 fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, node_idx: usize) -> ListItem<'static> {
@@ -89,7 +229,7 @@ fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, no
                 ("[ ]", Style::default().fg(app.theme.text_muted()))
             };
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::raw(format!("{}{}", indent, checkbox)),
                 Span::styled(" ", checkbox_style),
                 Span::styled(
@@ -100,7 +240,20 @@ fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, no
                     format!("{}/", name),
                     Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD),
                 ),
-            ]);
+            ];
+
+            // Status rollup of everything below, e.g. `3 new · 1 mod · 12 ok`.
+            let counts = tree.folder_status_counts(node_idx, &app.components);
+            for (i, (status, count)) in counts.iter().enumerate() {
+                let sep = if i == 0 { "  " } else { " · " };
+                spans.push(Span::styled(sep, Style::default().fg(app.theme.text_muted())));
+                spans.push(Span::styled(
+                    format!("{} {}", count, rollup_label(status)),
+                    status_style(app, status),
+                ));
+            }
+
+            let line = Line::from(spans);
 
             ListItem::new(line)
         }
@@ -108,13 +261,7 @@ fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, no
             let c = &app.components[*component_idx];
             let checkbox = if c.selected { "[x]" } else { "[ ]" };
 
-            let status_style = match c.status {
-                InstallStatus::New => Style::default().fg(app.theme.success()),
-                InstallStatus::Modified => Style::default().fg(app.theme.warning()),
-                InstallStatus::Unchanged => Style::default().fg(app.theme.text_secondary()),
-                InstallStatus::Managed => Style::default().fg(app.theme.accent_primary()),
-                InstallStatus::External => Style::default().fg(app.theme.text_muted()),
-            };
+            let status_style = status_style(app, &c.status);
 
             // Check if this is the default item
             let is_default = match app.tab {
@@ -155,6 +302,12 @@ fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, no
                 Span::styled(format!("({:^9})", c.status.display()), status_style),
                 Span::styled(default_marker, Style::default().fg(app.theme.peach()).add_modifier(Modifier::BOLD)),
             ];
+            if c.is_context_file() {
+                spans.push(Span::styled(
+                    format!(" ~{} tok", format_tokens(c.estimated_tokens())),
+                    Style::default().fg(app.theme.text_muted()),
+                ));
+            }
 
             if app.tab == Tab::Hooks {
                 if let Some(ref config) = c.hook_config {
@@ -165,9 +318,15 @@ fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, no
                             Style::default().fg(app.theme.warning()).add_modifier(Modifier::BOLD),
                         ));
                     }
-                    // Add event info
+                    // Add event info, with the effective execution order
+                    // when the hook is registered in settings.json
+                    let position = app.hook_positions.get(&config.hook_command_path(&app.dest_dir));
+                    let event_label = match position {
+                        Some((pos, total)) => format!(" [{} #{}/{}]", config.event, pos, total),
+                        None => format!(" [{}]", config.event),
+                    };
                     spans.push(Span::styled(
-                        format!(" [{}]", config.event),
+                        event_label,
                         Style::default().fg(app.theme.highlight()),
                     ));
                     // Add description if available
@@ -180,6 +339,9 @@ fn render_tree_node(app: &App, tree: &crate::tree::TreeView, node: &TreeNode, no
                 }
             }
 
+            if !c.for_this_host() {
+                spans.push(Span::styled(" [other host]", Style::default().fg(app.theme.text_muted())));
+            }
             if app.has_multiple_sources() {
                 spans.push(super::source_tag_span(&c.source_name, &app.theme));
             }
@@ -198,13 +360,7 @@ fn render_flat(f: &mut Frame, app: &App, area: Rect) {
         .map(|(_, c)| {
             let checkbox = if c.selected { "[x]" } else { "[ ]" };
 
-            let status_style = match c.status {
-                InstallStatus::New => Style::default().fg(app.theme.success()),
-                InstallStatus::Modified => Style::default().fg(app.theme.warning()),
-                InstallStatus::Unchanged => Style::default().fg(app.theme.text_secondary()),
-                InstallStatus::Managed => Style::default().fg(app.theme.accent_primary()),
-                InstallStatus::External => Style::default().fg(app.theme.text_muted()),
-            };
+            let status_style = status_style(app, &c.status);
 
             // Check if this is the default item
             let is_default = match app.tab {
@@ -241,7 +397,16 @@ fn render_flat(f: &mut Frame, app: &App, area: Rect) {
                 Span::styled(format!("({:^9})", c.status.display()), status_style),
                 Span::styled(default_marker, Style::default().fg(app.theme.peach()).add_modifier(Modifier::BOLD)),
             ];
+            if c.is_context_file() {
+                spans.push(Span::styled(
+                    format!(" ~{} tok", format_tokens(c.estimated_tokens())),
+                    Style::default().fg(app.theme.text_muted()),
+                ));
+            }
 
+            if !c.for_this_host() {
+                spans.push(Span::styled(" [other host]", Style::default().fg(app.theme.text_muted())));
+            }
             if app.has_multiple_sources() {
                 spans.push(super::source_tag_span(&c.source_name, &app.theme));
             }
@@ -251,28 +416,14 @@ fn render_flat(f: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
-    // Show current default status in title
-    let mut title = format!(" {} ", app.tab.display_name());
-    if app.tab == Tab::OutputStyles {
-        if let Some(ref style) = app.current_output_style {
-            title = format!("{} [Default: {}] ", title.trim(), style);
-        } else {
-            title = format!("{} [No default set] ", title.trim());
-        }
-    } else if app.tab == Tab::Statusline {
-        if let Some(ref statusline) = app.current_statusline {
-            title = format!("{} [Default: {}] ", title.trim(), statusline);
-        } else {
-            title = format!("{} [No default set] ", title.trim());
-        }
-    }
+    let title = list_title(app);
 
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(app.theme.border()))
-                .title(title)
+                .title(title_with_tokens(app, title))
                 .title_style(Style::default().fg(app.theme.text_primary())),
         )
         .highlight_style(
@@ -290,3 +441,205 @@ fn render_flat(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_stateful_widget(list, area, &mut state);
 }
+
+/// Most stale files listed before collapsing the rest into a count.
+const STALE_FILES_SHOWN: usize = 12;
+
+pub fn render_confirm_stale_files(f: &mut Frame, app: &App, area: Rect) {
+    let files = &app.stale_pack_files;
+    let shown = files.len().min(STALE_FILES_SHOWN);
+    let extra_line = u16::from(files.len() > shown);
+    let dialog = super::source_wizard::centered_rect(70, 7 + shown as u16 + extra_line, area);
+    f.render_widget(Clear, dialog);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  These installed files were removed from their source:",
+            Style::default().fg(app.theme.warning()),
+        )),
+    ];
+    text.extend(files.iter().take(shown).map(|path| {
+        let rel = path.strip_prefix(&app.dest_dir).unwrap_or(path);
+        Line::from(Span::styled(
+            format!("    {}", rel.display()),
+            Style::default().fg(app.theme.text_primary()),
+        ))
+    }));
+    if extra_line > 0 {
+        text.push(Line::from(Span::styled(
+            format!("    ... and {} more", files.len() - shown),
+            Style::default().fg(app.theme.text_muted()),
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("  [y] ", Style::default().fg(app.theme.error())),
+        Span::raw("Remove  "),
+        Span::styled("[n] ", Style::default().fg(app.theme.text_secondary())),
+        Span::raw("Keep  "),
+        Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+        Span::raw("Cancel install"),
+    ]));
+
+    let block = Block::default()
+        .title(" Stale Files ")
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.warning()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+
+    f.render_widget(Paragraph::new(text).block(block), dialog);
+}
+
+pub fn render_confirm_prune(f: &mut Frame, app: &App, area: Rect) {
+    let orphans = app.orphan_indices(app.prune_all_tabs);
+    let shown = orphans.len().min(STALE_FILES_SHOWN);
+    let extra_line = u16::from(orphans.len() > shown);
+    let dialog = super::source_wizard::centered_rect(70, 7 + shown as u16 + extra_line, area);
+    f.render_widget(Clear, dialog);
+
+    let scope = if app.prune_all_tabs { "every tab".to_string() } else { app.tab_label(app.tab) };
+    let mut text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  Installed by hibi, no longer in any source ({}):", scope),
+            Style::default().fg(app.theme.warning()),
+        )),
+    ];
+    text.extend(orphans.iter().take(shown).filter_map(|&i| app.components.get(i)).map(|c| {
+        let rel = c.dest_path.strip_prefix(&app.dest_dir).unwrap_or(&c.dest_path);
+        Line::from(Span::styled(
+            format!("    {}", rel.display()),
+            Style::default().fg(app.theme.text_primary()),
+        ))
+    }));
+    if extra_line > 0 {
+        text.push(Line::from(Span::styled(
+            format!("    ... and {} more", orphans.len() - shown),
+            Style::default().fg(app.theme.text_muted()),
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("  [y] ", Style::default().fg(app.theme.error())),
+        Span::raw("Delete  "),
+        Span::styled("[b] ", Style::default().fg(app.theme.warning())),
+        Span::raw("Back up and delete  "),
+        Span::styled("[t] ", Style::default().fg(app.theme.text_secondary())),
+        Span::raw(if app.prune_all_tabs { "This tab  " } else { "All tabs  " }),
+        Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+        Span::raw("Cancel"),
+    ]));
+
+    let block = Block::default()
+        .title(" Prune Orphans ")
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.warning()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+
+    f.render_widget(Paragraph::new(text).block(block), dialog);
+}
+
+pub fn render_mirror(f: &mut Frame, app: &App, area: Rect) {
+    let dialog = super::source_wizard::centered_rect(70, 7 + app.mirror_tabs.len() as u16, area);
+    f.render_widget(Clear, dialog);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Install new and modified items, delete orphans (backed up first):",
+            Style::default().fg(app.theme.text_secondary()),
+        )),
+    ];
+    text.extend(app.mirror_tabs.iter().enumerate().map(|(i, &(tab, checked))| {
+        let counts = app.mirror_counts(tab);
+        let pending = if counts.is_empty() {
+            "in sync".to_string()
+        } else {
+            format!("{} new, {} modified, {} orphaned", counts.new, counts.modified, counts.orphaned)
+        };
+        let style = if i == app.mirror_index {
+            Style::default().fg(app.theme.highlight()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_primary())
+        };
+        Line::from(vec![
+            Span::styled(format!("  [{}] {:<16}", if checked { "x" } else { " " }, app.tab_label(tab)), style),
+            Span::styled(pending, Style::default().fg(app.theme.text_muted())),
+        ])
+    }));
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("  [Enter] ", Style::default().fg(app.theme.warning())),
+        Span::raw("Mirror checked tabs  "),
+        Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+        Span::raw("Cancel"),
+    ]));
+
+    let block = Block::default()
+        .title(" Mirror Source ")
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.warning()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+
+    f.render_widget(Paragraph::new(text).block(block), dialog);
+}
+
+/// One modified file of a pending install: keep the local copy or
+/// overwrite it. The diff, when shown, fills most of the screen.
+pub fn render_conflict(f: &mut Frame, app: &App, area: Rect) {
+    let Some(c) = app.conflicts.first().and_then(|&i| app.components.get(i)) else { return };
+    let dialog = match app.diff_content {
+        Some(_) => super::source_wizard::centered_rect(90, area.height.saturating_sub(2), area),
+        None => super::source_wizard::centered_rect(70, 9, area),
+    };
+    f.render_widget(Clear, dialog);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Installed copy differs from the source: ", Style::default().fg(app.theme.warning())),
+            Span::styled(c.display_name(), Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD)),
+        ]),
+    ];
+    let remaining = app.conflicts.len() - 1;
+    text.push(Line::from(Span::styled(
+        if remaining == 0 {
+            "  Last modified file in this install".to_string()
+        } else {
+            format!("  {} more modified file(s) after this one", remaining)
+        },
+        Style::default().fg(app.theme.text_muted()),
+    )));
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("  [k] ", Style::default().fg(app.theme.success())),
+        Span::raw("Keep local  "),
+        Span::styled("[o] ", Style::default().fg(app.theme.error())),
+        Span::raw("Overwrite  "),
+        Span::styled("[O] ", Style::default().fg(app.theme.error())),
+        Span::raw("Overwrite all  "),
+        Span::styled("[v] ", Style::default().fg(app.theme.text_secondary())),
+        Span::raw(if app.diff_content.is_some() { "Hide diff  " } else { "View diff  " }),
+        Span::styled("[s] ", Style::default().fg(app.theme.text_secondary())),
+        Span::raw("Skip  "),
+        Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+        Span::raw("Cancel"),
+    ]));
+    if let Some(diff) = &app.diff_content {
+        text.push(Line::from(""));
+        text.extend(super::diff::diff_lines(app, diff).into_iter().skip(app.diff_scroll as usize));
+    }
+
+    let block = Block::default()
+        .title(" Local Changes ")
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.warning()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+
+    f.render_widget(Paragraph::new(text).block(block), dialog);
+}
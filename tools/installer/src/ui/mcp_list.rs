@@ -1,5 +1,5 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState},
@@ -7,9 +7,12 @@ use ratatui::{
 };
 
 use crate::app::App;
-use crate::mcp::McpStatus;
+use crate::mcp::{McpHealth, McpStatus};
 
-pub fn render(f: &mut Frame, app: &App, area: Rect) {
+use super::ListArea;
+
+/// Draw the list, returning where its rows went (`None` when empty).
+pub fn render(f: &mut Frame, app: &App, area: Rect) -> Option<ListArea> {
     if app.mcp_servers.is_empty() {
         let message = "No MCP servers found. Create mcps/mcps.yaml to add servers.";
 
@@ -25,7 +28,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
                 .title_style(Style::default().fg(app.theme.text_primary())),
         );
         f.render_widget(empty, area);
-        return;
+        return None;
     }
 
     let items: Vec<ListItem> = app
@@ -67,6 +70,32 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
                 },
             ];
 
+            if m.status == McpStatus::Installed
+                && let Some(health) = app.mcp_health.get(&m.def.name)
+            {
+                let (mark, color) = match health {
+                    McpHealth::Connected => ("✓", app.theme.success()),
+                    McpHealth::Failed => ("✗", app.theme.error()),
+                };
+                line1_spans.push(Span::styled(
+                    format!(" {} {}", mark, health.display()),
+                    Style::default().fg(color),
+                ));
+            }
+
+            if let Some(version) = &m.def.version {
+                line1_spans.push(Span::styled(
+                    format!(" @{}", version),
+                    Style::default().fg(app.theme.text_muted()),
+                ));
+            }
+            if let Some(latest) = app.mcp_updates.get(&m.def.name) {
+                line1_spans.push(Span::styled(
+                    format!(" ↑ {}", latest),
+                    Style::default().fg(app.theme.warning()),
+                ));
+            }
+
             if app.has_multiple_sources() {
                 line1_spans.push(super::source_tag_span(&m.source_name, &app.theme));
             }
@@ -86,7 +115,13 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
-    let title = format!(" MCP Servers (scope: {}) ", app.mcp_scope.display());
+    let title = match app.mcp_health_interval {
+        Some(secs) if app.mcp_health_polling() => format!(
+            " MCP Servers (scope: {}, health every {}s) ",
+            app.mcp_scope.display(), secs
+        ),
+        _ => format!(" MCP Servers (scope: {}) ", app.mcp_scope.display()),
+    };
     let list = List::new(items)
         .block(
             Block::default()
@@ -107,4 +142,5 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     state.select(Some(app.mcp_index));
 
     f.render_stateful_widget(list, area, &mut state);
+    Some(ListArea { inner: area.inner(Margin::new(1, 1)), offset: state.offset(), item_height: 2 })
 }
@@ -0,0 +1,137 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::mcp_review::EnvValueStatus;
+use crate::app::App;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let rows_height = app.mcp_review_rows.len() as u16;
+    let dialog = super::source_wizard::centered_rect(85, (rows_height + 7).min(area.height), area);
+    f.render_widget(Clear, dialog);
+
+    let scope = match app.mcp_scope {
+        crate::mcp::McpScope::Local => format!("local ({})", app.mcp_project_path),
+        other => other.display().to_string(),
+    };
+
+    let mut prev_server = None;
+    let rows: Vec<Row> = app.mcp_review_rows.iter().enumerate().map(|(i, row)| {
+        let selected = i == app.mcp_review_cursor;
+        let server = app.mcp_servers.get(row.server_idx);
+
+        // Server, scope and transport only on the first line of each server.
+        let first = prev_server != Some(row.server_idx);
+        prev_server = Some(row.server_idx);
+        let (name, scope_cell, transport) = match server {
+            Some(s) if first => (
+                s.def.name.clone(),
+                scope.clone(),
+                if s.is_http() { "http" } else { "stdio" }.to_string(),
+            ),
+            _ => (String::new(), String::new(), String::new()),
+        };
+
+        let var = row.var.clone().unwrap_or_else(|| "-".to_string());
+        let value = if selected && app.mcp_review_editing {
+            Span::styled(format!("{}_", app.mcp_review_buffer), Style::default().fg(app.theme.text_primary()))
+        } else {
+            match (&row.var, &row.status) {
+                (None, _) => Span::styled("no env", Style::default().fg(app.theme.text_muted())),
+                (_, EnvValueStatus::FromEnv) => Span::styled("set (env)", Style::default().fg(app.theme.success())),
+                (_, EnvValueStatus::FromFile { file, .. }) => Span::styled(format!("set ({})", file), Style::default().fg(app.theme.success())),
+                (_, EnvValueStatus::Entered(_)) => Span::styled("set (entered)", Style::default().fg(app.theme.success())),
+                (_, EnvValueStatus::Missing) => Span::styled("missing", Style::default().fg(app.theme.error())),
+            }
+        };
+
+        Row::new(vec![
+            Cell::from(name),
+            Cell::from(scope_cell),
+            Cell::from(transport),
+            Cell::from(var),
+            Cell::from(Line::from(value)),
+        ])
+        .style(Style::default().fg(app.theme.text_primary()))
+    }).collect();
+
+    let header = Row::new(["Server", "Scope", "Transport", "Env var", "Value"])
+        .style(Style::default().fg(app.theme.text_secondary()).add_modifier(Modifier::BOLD));
+
+    let missing = app.mcp_review_missing();
+    let title = if missing == 0 {
+        " Review MCP Install — ready ".to_string()
+    } else {
+        format!(" Review MCP Install — {} missing ", missing)
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_focused()))
+        .title(title)
+        .title_style(Style::default().fg(app.theme.text_primary()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+    let inner = block.inner(dialog);
+    f.render_widget(block, dialog);
+
+    let table_area = Rect { height: inner.height.saturating_sub(2), ..inner };
+    let table = Table::new(rows, [
+        Constraint::Percentage(24),
+        Constraint::Percentage(18),
+        Constraint::Length(10),
+        Constraint::Percentage(26),
+        Constraint::Min(14),
+    ])
+    .header(header)
+    .row_highlight_style(Style::default().fg(app.theme.warning()).add_modifier(Modifier::BOLD))
+    .highlight_symbol("> ");
+
+    // Long reviews scroll to keep the cursor row, and its value, in view.
+    let mut state = TableState::default().with_selected(Some(app.mcp_review_cursor));
+    f.render_stateful_widget(table, table_area, &mut state);
+
+    let start_style = if missing == 0 {
+        Style::default().fg(app.theme.success())
+    } else {
+        Style::default().fg(app.theme.text_muted()).add_modifier(Modifier::CROSSED_OUT)
+    };
+    let footer = Line::from(vec![
+        Span::styled("  [s] Start", start_style),
+        Span::styled("  [Enter] Edit value  [Esc] Cancel", Style::default().fg(app.theme.text_muted())),
+    ]);
+    let footer_area = Rect { y: inner.y + inner.height.saturating_sub(1), height: 1, ..inner };
+    f.render_widget(Paragraph::new(footer), footer_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::mcp_review::McpReviewRow;
+    use crate::mcp::{McpServer, McpServerDef, McpStatus};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn rows_below_the_dialog_scroll_into_view() {
+        let mut app = App::for_test();
+        app.mcp_servers = vec![McpServer::new(McpServerDef {
+            name: "keyed".into(), description: String::new(), r#type: None,
+            command: Some("npx -y pkg".into()), url: None, category: String::new(),
+            env: (0..30).map(|i| format!("TOKEN_{i}")).collect(), version: None, env_file: None,
+        }, McpStatus::NotInstalled)];
+        app.mcp_review_rows = (0..30)
+            .map(|i| McpReviewRow { server_idx: 0, var: Some(format!("TOKEN_{i}")), status: EnvValueStatus::Missing })
+            .collect();
+        app.mcp_review_cursor = 29;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|f| render(f, &app, f.area())).unwrap();
+        let text = crate::session::buffer_to_text(terminal.backend().buffer());
+
+        assert!(text.contains("> ") && text.contains("TOKEN_29"), "{text}");
+        assert!(!text.contains("TOKEN_0 "));
+    }
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Names listed per portable kind before "and N more".
+const NAMES_SHOWN: usize = 6;
+
+fn names_line(names: &[&str]) -> String {
+    let mut line = names.iter().take(NAMES_SHOWN).copied().collect::<Vec<_>>().join(", ");
+    if names.len() > NAMES_SHOWN {
+        line.push_str(&format!(" and {} more", names.len() - NAMES_SHOWN));
+    }
+    line
+}
+
+/// The Claude Code -> Codex plan: what Codex can take over, what is still
+/// to install for it, and what has no Codex equivalent.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let Some(plan) = &app.migration_plan else { return };
+    let (skills, mcp) = app.migration_todo();
+    let primary = Style::default().fg(app.theme.text_primary());
+    let muted = Style::default().fg(app.theme.text_muted());
+    let heading = |text: &'static str| Line::from(Span::styled(
+        text,
+        Style::default().fg(app.theme.text_secondary()).add_modifier(Modifier::BOLD),
+    ));
+
+    let mut lines = vec![Line::from(""), heading("  Portable (Codex reads these too)")];
+    let todo_skills: Vec<&str> = skills.iter().filter_map(|&i| app.components.get(i)).map(|c| c.name.as_str()).collect();
+    let todo_mcp: Vec<&str> = mcp.iter().filter_map(|&i| app.mcp_servers.get(i)).map(|m| m.def.name.as_str()).collect();
+    for (label, total, todo) in [("Skills", plan.skills.len(), &todo_skills), ("MCP servers", plan.mcp_servers.len(), &todo_mcp)] {
+        if total == 0 {
+            lines.push(Line::from(Span::styled(format!("    {}: none for Claude", label), muted)));
+            continue;
+        }
+        let mut spans = vec![Span::styled(format!("    {}: {} for Claude, ", label, total), primary)];
+        if todo.is_empty() {
+            spans.push(Span::styled("all installed for Codex", Style::default().fg(app.theme.success())));
+        } else {
+            spans.push(Span::styled(format!("{} to install", todo.len()), Style::default().fg(app.theme.warning())));
+            spans.push(Span::styled(format!(" ({})", names_line(todo)), muted));
+        }
+        lines.push(Line::from(spans));
+    }
+    // Sources that don't ship them can't be installed from here.
+    let unsourced = plan.mcp_servers.iter().filter(|name| !app.mcp_servers.iter().any(|m| &m.def.name == *name)).count();
+    if unsourced > 0 {
+        lines.push(Line::from(Span::styled(format!("    {} MCP server(s) not in any source; add them by hand", unsourced), muted)));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading("  Not portable (no Codex equivalent)"));
+    if plan.not_portable.is_empty() {
+        lines.push(Line::from(Span::styled("    nothing", muted)));
+    } else {
+        let counts: Vec<String> = plan.not_portable.iter().map(|(kind, n)| format!("{} {}", n, kind)).collect();
+        lines.push(Line::from(Span::styled(format!("    {}", counts.join(", ")), primary)));
+    }
+
+    lines.push(Line::from(""));
+    let mut keys = Vec::new();
+    if !skills.is_empty() || !mcp.is_empty() {
+        keys.push(Span::styled("  [Enter] ", Style::default().fg(app.theme.success())));
+        keys.push(Span::raw("Install portable items  "));
+    }
+    keys.push(Span::styled("[x] ", Style::default().fg(app.theme.text_muted())));
+    keys.push(Span::raw("Don't ask again  "));
+    keys.push(Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())));
+    keys.push(Span::raw("Close"));
+    lines.push(Line::from(keys));
+
+    let dialog = super::source_wizard::centered_rect(70, lines.len() as u16 + 4, area);
+    f.render_widget(Clear, dialog);
+    let block = Block::default()
+        .title(" Port from Claude Code ")
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent_primary()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), dialog);
+}
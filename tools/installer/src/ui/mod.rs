@@ -2,14 +2,30 @@ mod tabs;
 pub mod list;
 mod mcp_list;
 mod plugin_list;
+mod pending;
 mod diff;
 mod env_input;
+mod mcp_review;
 mod project_path;
+mod hook_detail;
+mod hook_logs;
+mod git_status;
+mod search;
+mod palette;
+mod sort_menu;
+mod hook_scaffold;
+mod keyword_edit;
+mod settings_history;
+mod settings_keys;
 mod installing;
 mod cli_selection;
 pub mod loading_screen;
 mod sources;
 mod source_wizard;
+mod mouse;
+mod help;
+mod failure_detail;
+mod migration;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -22,6 +38,8 @@ use ratatui::text::Span;
 use crate::app::{App, FocusArea, Tab, View};
 use crate::theme::Theme;
 
+pub use mouse::{Click, ClickMap, ListArea};
+
 /// Render a source tag (e.g., " [bundled]") for multi-source display.
 pub fn source_tag_span(source_name: &str, theme: &Theme) -> Span<'static> {
     Span::styled(
@@ -37,7 +55,8 @@ pub fn get_spinner(frame: usize) -> &'static str {
     SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]
 }
 
-pub fn draw(f: &mut Frame, app: &App) {
+/// Draw the whole screen for `app`, returning where the clickable parts went.
+pub fn draw(f: &mut Frame, app: &App) -> ClickMap {
     use ratatui::style::Style;
 
     // Clear entire background with theme color
@@ -53,66 +72,66 @@ pub fn draw(f: &mut Frame, app: &App) {
     // impression uncluttered.
     if app.current_view == View::CliSelection {
         cli_selection::render(f, app, f.area());
-        return;
+        return ClickMap::default();
     }
 
     // Loading screen takes full screen
     if app.current_view == View::Loading {
         render_loading_screen(f, app);
-        return;
+        return ClickMap::default();
     }
 
     // Preflighting (CLI `--version` probe) reuses the loading screen.
     if app.current_view == View::Preflighting {
         render_preflighting_screen(f, app);
-        return;
+        return ClickMap::default();
     }
 
     // Sources views take full screen (like CLI selection)
     match app.current_view {
         View::Sources => {
             sources::render(f, app, f.area());
-            return;
+            return ClickMap::default();
         }
         View::SourceAddType => {
             sources::render(f, app, f.area());
             source_wizard::render_type_select(f, app, f.area());
-            return;
+            return ClickMap::default();
         }
         View::SourceAddUrl => {
             sources::render(f, app, f.area());
             source_wizard::render_text_input(f, app, f.area(), "Git URL", "URL");
-            return;
+            return ClickMap::default();
         }
         View::SourceAddBranch => {
             sources::render(f, app, f.area());
             source_wizard::render_text_input(f, app, f.area(), "Git Branch (optional)", "Branch");
-            return;
+            return ClickMap::default();
         }
         View::SourceAddPath => {
             sources::render(f, app, f.area());
             source_wizard::render_text_input(f, app, f.area(), "Local Path", "Path");
-            return;
+            return ClickMap::default();
         }
         View::SourceAddRoot => {
             sources::render(f, app, f.area());
             source_wizard::render_text_input(f, app, f.area(), "Subdirectory (optional)", "Root");
-            return;
+            return ClickMap::default();
         }
         View::SourceAddMapTo => {
             sources::render(f, app, f.area());
             source_wizard::render_map_to_select(f, app, f.area());
-            return;
+            return ClickMap::default();
         }
         View::SourceConfirmRemove => {
             sources::render(f, app, f.area());
             source_wizard::render_confirm_remove(f, app, f.area());
-            return;
+            return ClickMap::default();
         }
         View::SourceSyncing => {
             sources::render(f, app, f.area());
             source_wizard::render_syncing(f, app, f.area());
-            return;
+            return ClickMap::default();
         }
         _ => {}
     }
@@ -126,23 +145,30 @@ pub fn draw(f: &mut Frame, app: &App) {
         ])
         .split(f.area());
 
-    tabs::render(f, app, chunks[0]);
+    let mut clicks = ClickMap::default();
+    let tab_areas = tabs::render(f, app, chunks[0]);
 
     match app.current_view {
         View::CliSelection => unreachable!(),
         View::Loading => unreachable!(),
         View::Preflighting => unreachable!(),
         View::List => {
-            if app.tab == Tab::McpServers {
-                mcp_list::render(f, app, chunks[1]);
+            clicks.tabs = tab_areas;
+            clicks.list = if app.tab == Tab::McpServers {
+                mcp_list::render(f, app, chunks[1])
             } else if app.tab == Tab::Plugins {
-                plugin_list::render(f, app, chunks[1]);
+                plugin_list::render(f, app, chunks[1])
             } else {
-                list::render(f, app, chunks[1]);
-            }
+                list::render(f, app, chunks[1])
+            };
         }
         View::Diff => {
             diff::render(f, app, chunks[1]);
+            clicks.diff = Some(chunks[1]);
+        }
+        View::McpReview => {
+            mcp_list::render(f, app, chunks[1]);
+            mcp_review::render(f, app, chunks[1]);
         }
         View::EnvInput => {
             // Show MCP list in background, then overlay env input dialog
@@ -154,8 +180,109 @@ pub fn draw(f: &mut Frame, app: &App) {
             mcp_list::render(f, app, chunks[1]);
             project_path::render(f, app, chunks[1]);
         }
+        View::TrustProject => {
+            mcp_list::render(f, app, chunks[1]);
+            project_path::render_trust(f, app, chunks[1]);
+        }
+        View::HookLogs => {
+            list::render(f, app, chunks[1]);
+            hook_logs::render(f, app, chunks[1]);
+        }
+        View::GitStatus => {
+            list::render(f, app, chunks[1]);
+            git_status::render(f, app, chunks[1]);
+        }
+        View::HookDetail => {
+            // Show hook list in background, then overlay the detail dialog
+            list::render(f, app, chunks[1]);
+            hook_detail::render(f, app, chunks[1]);
+        }
+        View::Search => {
+            // Keep the current tab in the background, then overlay the picker
+            if app.tab == Tab::McpServers {
+                mcp_list::render(f, app, chunks[1]);
+            } else if app.tab == Tab::Plugins {
+                plugin_list::render(f, app, chunks[1]);
+            } else {
+                list::render(f, app, chunks[1]);
+            }
+            search::render(f, app, chunks[1]);
+        }
+        View::Palette => {
+            if app.tab == Tab::McpServers {
+                mcp_list::render(f, app, chunks[1]);
+            } else if app.tab == Tab::Plugins {
+                plugin_list::render(f, app, chunks[1]);
+            } else {
+                list::render(f, app, chunks[1]);
+            }
+            palette::render(f, app, chunks[1]);
+        }
+        View::SortMenu => {
+            if app.tab == Tab::McpServers {
+                mcp_list::render(f, app, chunks[1]);
+            } else if app.tab == Tab::Plugins {
+                plugin_list::render(f, app, chunks[1]);
+            } else {
+                list::render(f, app, chunks[1]);
+            }
+            sort_menu::render(f, app, chunks[1]);
+        }
+        View::SettingsHistory => {
+            settings_history::render(f, app, chunks[1]);
+        }
+        View::HookScaffold => {
+            list::render(f, app, chunks[1]);
+            hook_scaffold::render(f, app, chunks[1]);
+        }
+        View::KeywordEdit => {
+            list::render(f, app, chunks[1]);
+            keyword_edit::render(f, app, chunks[1]);
+        }
+        View::SettingsKeys => {
+            settings_keys::render(f, app, chunks[1]);
+        }
+        View::StaleFilesConfirm => {
+            list::render(f, app, chunks[1]);
+            list::render_confirm_stale_files(f, app, chunks[1]);
+        }
+        View::PruneConfirm => {
+            list::render(f, app, chunks[1]);
+            list::render_confirm_prune(f, app, chunks[1]);
+        }
+        View::MirrorConfirm => {
+            list::render(f, app, chunks[1]);
+            list::render_mirror(f, app, chunks[1]);
+        }
+        View::ConflictResolve => {
+            list::render(f, app, chunks[1]);
+            list::render_conflict(f, app, chunks[1]);
+        }
+        View::MarketplaceConfirmRemove => {
+            plugin_list::render(f, app, chunks[1]);
+            plugin_list::render_confirm_marketplace_remove(f, app, chunks[1]);
+        }
+        View::OfflineQueue | View::PendingRetry | View::ResumeQueue => {
+            if app.tab == Tab::McpServers {
+                mcp_list::render(f, app, chunks[1]);
+            } else if app.tab == Tab::Plugins {
+                plugin_list::render(f, app, chunks[1]);
+            } else {
+                list::render(f, app, chunks[1]);
+            }
+            pending::render(f, app, chunks[1]);
+        }
+        View::Migration => {
+            if app.tab == Tab::McpServers {
+                mcp_list::render(f, app, chunks[1]);
+            } else {
+                list::render(f, app, chunks[1]);
+            }
+            migration::render(f, app, chunks[1]);
+        }
         View::Installing => {
             installing::render(f, app, chunks[1]);
+            failure_detail::render(f, app, chunks[1]);
         }
         // Sources views are handled above (full-screen early return)
         View::Sources | View::SourceAddType | View::SourceAddUrl
@@ -164,6 +291,10 @@ pub fn draw(f: &mut Frame, app: &App) {
     }
 
     render_status_bar(f, app, chunks[2]);
+    if app.help_open {
+        help::render(f, app, chunks[1]);
+    }
+    clicks
 }
 
 /// Build the List view's status-bar help text.
@@ -171,22 +302,16 @@ pub fn draw(f: &mut Frame, app: &App) {
 /// The help reflects which pane currently holds focus so the user always
 /// sees the keys that will actually do something: when the tab bar is
 /// focused, the action keys (Space, i, r, ...) are intentionally hidden to
-/// avoid the implication that they'd work in that mode. The global keys
-/// (Tab to switch focus, q to quit, t for theme) are listed in both views.
-fn list_view_help(app: &App) -> &'static str {
+/// avoid the implication that they'd work in that mode. Only the essential
+/// keys and this tab's actions are listed; `?` shows the rest.
+fn list_view_help(app: &App) -> String {
     if app.focus == FocusArea::Tabs {
-        return "[Tab] Focus content  [h/l/←/→] Switch tab  [Enter] Confirm  [t] Theme  [q] Quit";
-    }
-    match app.tab {
-        Tab::McpServers =>
-            "[Tab] Focus tabs  [Space] Toggle  [i] Install  [r] Remove  [o] Scope  [t] Theme  [q] Quit",
-        Tab::Plugins =>
-            "[Tab] Focus tabs  [Space] Toggle  [i] Install  [r] Remove  [t] Theme  [q] Quit",
-        Tab::OutputStyles | Tab::Statusline =>
-            "[Tab] Focus tabs  [Space] Toggle  [i] Install  [r] Remove  [d] Diff  [s] Set  [u] Unset  [t] Theme  [q] Quit",
-        _ =>
-            "[Tab] Focus tabs  [Space] Toggle  [i] Install  [r] Remove  [d] Diff  [h/l/←/→] Folder  [t] Theme  [q] Quit",
+        return "[Tab] Focus content  [h/l/←/→] Switch tab  [Enter] Confirm  [?] Help  [q] Quit".to_string();
     }
+    let mut hints = vec!["[Tab] Focus tabs  [Space] Toggle  [i] Install  [r] Remove"];
+    hints.extend(app.tab_actions().map(|action| action.hint));
+    hints.push("[^P] Commands  [?] Help  [q] Quit");
+    hints.join("  ")
 }
 
 fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
@@ -196,6 +321,7 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         widgets::{Block, Borders, Paragraph},
     };
 
+    let list_help = list_view_help(app);
     let help_text = match app.current_view {
         // CliSelection has its own version footer and skips the global
         // status bar (see early-return in `draw`); this arm exists only
@@ -203,17 +329,56 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         View::CliSelection => "",
         View::Loading => "Loading...  [q] Quit",
         View::Preflighting => "Checking CLI...  [Esc] Cancel  [q] Quit",
-        View::List => list_view_help(app),
-        View::Diff => "[j/k/↑/↓] Scroll  [q/Esc] Close",
+        View::List => &list_help,
+        View::Diff if app.adopt_armed => "[A] Confirm adopt  [any other key] Cancel",
+        View::Diff if app.diff_hunks.is_some() =>
+            "[n/p] Next/prev hunk  [Space] Stage  [a] Apply staged  [A] Adopt  [j/k/↑/↓] Scroll  [?] Help  [q/Esc] Close",
+        View::Diff => "[A] Adopt  [j/k/↑/↓] Scroll  [?] Help  [q/Esc] Close",
         View::EnvInput => "[Enter] Submit  [Esc] Cancel  [Backspace] Delete",
+        View::McpReview => {
+            if app.mcp_review_editing {
+                "[Enter] Save  [Esc] Cancel  [Backspace] Delete"
+            } else {
+                "[↑/↓] Select  [Enter] Edit value  [s] Start  [Esc] Cancel"
+            }
+        }
         View::ProjectPath => "[Enter] Confirm  [Esc] Cancel  [Backspace] Delete",
+        View::TrustProject => "[y] Trust this project  [n/Esc] Cancel",
+        View::MarketplaceConfirmRemove => "[y] Remove  [n/Esc] Keep",
+        View::OfflineQueue => "[Enter] Queue for later  [c] Try anyway  [Esc] Cancel",
+        View::PendingRetry => "[r] Retry now  [d] Discard  [Esc] Later",
+        View::ResumeQueue => "[Enter] Resume  [d] Discard  [Esc] Later",
+        View::Migration => "[Enter] Install portable items  [x] Don't ask again  [Esc] Close",
+        View::StaleFilesConfirm => "[y] Remove and install  [n] Keep and install  [Esc] Cancel",
+        View::PruneConfirm => "[y] Delete  [b] Back up and delete  [t] This tab/all tabs  [n/Esc] Cancel",
+        View::MirrorConfirm => "[j/k/↑/↓] Select  [Space] Check  [a] All  [Enter] Mirror  [q/Esc] Cancel",
+        View::ConflictResolve => "[k] Keep local  [o] Overwrite  [O] Overwrite all  [v] View diff  [s] Skip  [Esc] Cancel install",
+        View::GitStatus => "[j/k/↑/↓] Scroll  [W/q/Esc] Close",
+        View::HookLogs => "[h] Hook filter  [l] Level filter  [↑/↓] Scroll  [G] Newest  [Esc] Close",
+        View::SortMenu => "[j/k/↑/↓] Select  [Enter] Apply  [q/Esc] Close",
+        View::HookScaffold => "[j/k/↑/↓] Select event  [Enter] Create hook.yaml  [q/Esc] Cancel",
+        View::KeywordEdit if app.keyword_editing => "[Enter] Save  [Esc] Cancel  [Backspace] Delete",
+        View::KeywordEdit => "[j/k/↑/↓] Select  [Enter] Edit  [a] Add  [d] Delete  [w] Save  [i] Save & install  [q/Esc] Close",
+        View::SettingsHistory => "[j/k/↑/↓] Version  [J/K/PgDn/PgUp] Scroll  [r] Restore managed sections  [q/Esc] Close",
+        View::SettingsKeys => "[j/k/↑/↓] Select  [a] Adopt (installer manages)  [r] Release (yours)  [v/q/Esc] Back to files",
+        View::Search => "[Type] Filter  [↑/↓] Select  [Enter] Jump  [Esc] Close",
+        View::Palette => "[Type] Filter  [↑/↓] Select  [Enter] Run  [Esc] Close",
+        View::HookDetail => {
+            if app.hook_env_editing {
+                "[Enter] Save  [Esc] Cancel  [Backspace] Delete"
+            } else {
+                "[j/k/↑/↓] Select  [Enter] Edit value  [q/Esc] Close"
+            }
+        }
         View::Installing => {
-            if app.processing_complete {
+            if app.failure_detail.is_some() {
+                "[j/k] Scroll  [n/p] Next/prev failure  [Esc] Close"
+            } else if app.processing_complete {
                 "[Enter/q] Close"
             } else if app.is_removing {
-                "Removing..."
+                "Removing...  [s] Skip item  [Esc/C] Cancel all"
             } else {
-                "Installing..."
+                "Installing...  [s] Skip item  [Esc/C] Cancel all"
             }
         }
         // Sources views have their own footer
@@ -223,7 +388,7 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let status = app.status_message.as_deref().unwrap_or("");
-    let version = crate::fs::VERSION;
+    let version = crate::VERSION;
 
     let spans = vec![
         Span::styled(help_text, Style::default().fg(app.theme.text_secondary())),
@@ -241,10 +406,21 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     all_spans.push(Span::raw(" ".repeat(padding)));
     all_spans.push(Span::styled(version, Style::default().fg(app.theme.text_secondary())));
 
-    let paragraph = Paragraph::new(Line::from(all_spans))
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(app.theme.border())));
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border()));
+    // Onboarding tip in the top border, out of the way of the key list.
+    // Esc only hides it from the list; in the tab bar it returns focus.
+    if app.current_view == View::List
+        && let Some(hint) = app.hint_text()
+    {
+        let hide = if app.focus == FocusArea::Content { "  [Esc] Hide" } else { "" };
+        block = block.title(Span::styled(
+            format!(" Tip: {}{} ", hint, hide),
+            Style::default().fg(app.theme.accent_primary()),
+        ));
+    }
+    let paragraph = Paragraph::new(Line::from(all_spans)).block(block);
 
     f.render_widget(paragraph, area);
 }
@@ -302,7 +478,7 @@ fn render_spinner_box(f: &mut Frame, app: &App, title: &str, message: &str) {
         ])
         .split(vertical[1]);
 
-    let spinner = get_spinner(app.animation_frame);
+    let spinner = get_spinner(crate::clock::frame());
 
     let text = vec![
         Line::from(""),
@@ -0,0 +1,98 @@
+//! Mouse hit-testing. Each frame reports where it drew the tab titles, the
+//! list and the diff, and clicks are mapped back onto what was under them.
+
+use ratatui::layout::{Position, Rect};
+
+use crate::app::{App, Tab};
+
+/// Columns the `> ` highlight symbol reserves in front of every list row.
+const HIGHLIGHT_WIDTH: u16 = 2;
+/// Width of a `[x]` checkbox.
+const CHECKBOX_WIDTH: u16 = 3;
+/// Indent per tree level (`"  "` in `render_tree_node`).
+const INDENT_WIDTH: u16 = 2;
+
+/// Where the last frame drew the parts of the screen that take clicks.
+#[derive(Clone, Debug, Default)]
+pub struct ClickMap {
+    /// Each tab title on the tab bar, with the tab a click switches to.
+    pub tabs: Vec<(Rect, Tab)>,
+    /// The list of the List view, when one is shown.
+    pub list: Option<ListArea>,
+    /// The diff body, when the Diff view is shown.
+    pub diff: Option<Rect>,
+}
+
+/// A rendered list: its inside (within the border), the index of the first
+/// item shown and how many rows each item takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ListArea {
+    pub inner: Rect,
+    pub offset: usize,
+    pub item_height: u16,
+}
+
+impl ListArea {
+    /// Item under `(column, row)` and whether the click is on the item's
+    /// first line, where its checkbox is.
+    fn item_at(&self, column: u16, row: u16) -> Option<(usize, bool)> {
+        if !self.inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        let line = row - self.inner.y;
+        let item_height = self.item_height.max(1);
+        Some((self.offset + usize::from(line / item_height), line.is_multiple_of(item_height)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Click {
+    Tab(Tab),
+    /// A list row, and whether the click landed on its checkbox.
+    Row { index: usize, checkbox: bool },
+}
+
+impl ClickMap {
+    /// What a left click at `(column, row)` hit.
+    pub fn click(&self, app: &App, column: u16, row: u16) -> Option<Click> {
+        let position = Position::new(column, row);
+        if let Some((_, tab)) = self.tabs.iter().find(|(area, _)| area.contains(position)) {
+            return Some(Click::Tab(*tab));
+        }
+
+        let list = self.list?;
+        let (index, first_line) = list.item_at(column, row)?;
+        if index >= app.list_len() {
+            return None;
+        }
+        let checkbox_x = list.inner.x + HIGHLIGHT_WIDTH + INDENT_WIDTH * row_depth(app, index);
+        let checkbox = first_line && (checkbox_x..checkbox_x + CHECKBOX_WIDTH).contains(&column);
+        Some(Click::Row { index, checkbox })
+    }
+}
+
+/// Tree depth of list row `index`; MCP and plugin rows are flat.
+fn row_depth(app: &App, index: usize) -> u16 {
+    app.get_tree_view()
+        .and_then(|tree| tree.visible_indices.get(index).map(|&node| tree.nodes[node].depth()))
+        .map_or(0, |depth| u16::try_from(depth).unwrap_or(u16::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_rows_map_through_scroll_offset_and_item_height() {
+        let list = ListArea { inner: Rect::new(1, 4, 40, 10), offset: 5, item_height: 1 };
+        assert_eq!(list.item_at(3, 4), Some((5, true)));
+        assert_eq!(list.item_at(3, 13), Some((14, true)));
+        assert_eq!(list.item_at(0, 4), None, "on the border");
+        assert_eq!(list.item_at(3, 14), None, "below the list");
+
+        // MCP and plugin items are two lines; only the first has a checkbox.
+        let list = ListArea { offset: 0, item_height: 2, ..list };
+        assert_eq!(list.item_at(3, 6), Some((1, true)));
+        assert_eq!(list.item_at(3, 7), Some((1, false)));
+    }
+}
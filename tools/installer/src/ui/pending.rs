@@ -0,0 +1,107 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, View};
+
+/// Queued installs listed in the retry and resume dialogs before
+/// "... and N more".
+const PENDING_SHOWN: usize = 8;
+
+/// The offline prompt before an install, or the queued installs offered
+/// for retry at startup, or the rest of an interrupted batch offered for
+/// resuming.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let (title, mut text, keys) = if app.current_view == View::ResumeQueue {
+        let Some(queue) = &app.interrupted_queue else { return };
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("  The last installation on {} stopped after {} of {} item(s).", queue.tab, queue.done, queue.total),
+                Style::default().fg(app.theme.warning()),
+            )),
+            Line::from(Span::styled("  Resume previous installation?", Style::default().fg(app.theme.text_primary()))),
+        ];
+        lines.extend(queue.items.iter().take(PENDING_SHOWN).map(|name| Line::from(Span::styled(
+            format!("    {}", name),
+            Style::default().fg(app.theme.text_primary()),
+        ))));
+        if queue.items.len() > PENDING_SHOWN {
+            lines.push(Line::from(Span::styled(
+                format!("    ... and {} more", queue.items.len() - PENDING_SHOWN),
+                Style::default().fg(app.theme.text_muted()),
+            )));
+        }
+        let keys = vec![
+            Span::styled("  [Enter] ", Style::default().fg(app.theme.success())),
+            Span::raw("Resume  "),
+            Span::styled("[d] ", Style::default().fg(app.theme.error())),
+            Span::raw("Discard  "),
+            Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+            Span::raw("Later"),
+        ];
+        (" Interrupted Installation ", lines, keys)
+    } else if app.current_view == View::OfflineQueue {
+        let lines = vec![
+            Line::from(Span::styled(
+                "  No network connection: this install is likely to fail.",
+                Style::default().fg(app.theme.warning()),
+            )),
+            Line::from(Span::styled(
+                format!("  Queue {} item(s) to install once back online?", app.processing_queue.len()),
+                Style::default().fg(app.theme.text_primary()),
+            )),
+        ];
+        let keys = vec![
+            Span::styled("  [Enter] ", Style::default().fg(app.theme.success())),
+            Span::raw("Queue  "),
+            Span::styled("[c] ", Style::default().fg(app.theme.warning())),
+            Span::raw("Try anyway  "),
+            Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+            Span::raw("Cancel"),
+        ];
+        (" Offline ", lines, keys)
+    } else {
+        let ops = &app.pending_ops;
+        let mut lines = vec![Line::from(Span::styled(
+            "  Installs queued while offline:",
+            Style::default().fg(app.theme.warning()),
+        ))];
+        lines.extend(ops.iter().take(PENDING_SHOWN).map(|op| Line::from(Span::styled(
+            format!("    {} ({})", op.name(), op.kind_label()),
+            Style::default().fg(app.theme.text_primary()),
+        ))));
+        if ops.len() > PENDING_SHOWN {
+            lines.push(Line::from(Span::styled(
+                format!("    ... and {} more", ops.len() - PENDING_SHOWN),
+                Style::default().fg(app.theme.text_muted()),
+            )));
+        }
+        let keys = vec![
+            Span::styled("  [r] ", Style::default().fg(app.theme.success())),
+            Span::raw("Retry now  "),
+            Span::styled("[d] ", Style::default().fg(app.theme.error())),
+            Span::raw("Discard  "),
+            Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+            Span::raw("Later"),
+        ];
+        (" Queued Installs ", lines, keys)
+    };
+    text.insert(0, Line::from(""));
+    text.push(Line::from(""));
+    text.push(Line::from(keys));
+
+    let dialog = super::source_wizard::centered_rect(60, text.len() as u16 + 2, area);
+    f.render_widget(Clear, dialog);
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.warning()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+
+    f.render_widget(Paragraph::new(text).block(block), dialog);
+}
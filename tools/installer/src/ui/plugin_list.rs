@@ -1,15 +1,18 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::app::App;
 use crate::plugin::PluginStatus;
 
-pub fn render(f: &mut Frame, app: &App, area: Rect) {
+use super::ListArea;
+
+/// Draw the list, returning where its rows went (`None` when empty).
+pub fn render(f: &mut Frame, app: &App, area: Rect) -> Option<ListArea> {
     if app.plugins.is_empty() {
         let empty = List::new(vec![ListItem::new(Line::from(vec![Span::styled(
             "No plugins found. Create plugins/plugins.yaml to add plugins.",
@@ -23,7 +26,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
                 .title_style(Style::default().fg(app.theme.text_primary())),
         );
         f.render_widget(empty, area);
-        return;
+        return None;
     }
 
     let items: Vec<ListItem> = app
@@ -107,4 +110,39 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     state.select(Some(app.plugin_index));
 
     f.render_stateful_widget(list, area, &mut state);
+    Some(ListArea { inner: area.inner(Margin::new(1, 1)), offset: state.offset(), item_height: 2 })
+}
+
+/// Offer to unregister marketplaces left without installed plugins.
+pub fn render_confirm_marketplace_remove(f: &mut Frame, app: &App, area: Rect) {
+    let names = &app.pending_marketplace_removals;
+    let dialog = super::source_wizard::centered_rect(50, 7 + names.len() as u16, area);
+    f.render_widget(Clear, dialog);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  No installed plugins remain from these marketplaces:",
+            Style::default().fg(app.theme.warning()),
+        )),
+    ];
+    text.extend(names.iter().map(|name| {
+        Line::from(Span::styled(format!("    {}", name), Style::default().fg(app.theme.text_primary())))
+    }));
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("  [y] ", Style::default().fg(app.theme.error())),
+        Span::raw("Remove marketplace(s)  "),
+        Span::styled("[Esc] ", Style::default().fg(app.theme.text_muted())),
+        Span::raw("Keep"),
+    ]));
+
+    let block = Block::default()
+        .title(" Confirm ")
+        .title_style(Style::default().fg(app.theme.text_primary()).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.warning()))
+        .style(Style::default().bg(app.theme.bg_secondary()));
+
+    f.render_widget(Paragraph::new(text).block(block), dialog);
 }
@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, TrustPending};
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
     // Center the dialog
@@ -14,7 +14,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(35),
-            Constraint::Length(9),
+            Constraint::Length(10),
             Constraint::Percentage(35),
         ])
         .split(area);
@@ -33,6 +33,10 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     // Clear background
     f.render_widget(Clear, dialog_area);
 
+    let feedback = match &app.project_path_error {
+        Some(error) => Span::styled(format!("  {}", error), Style::default().fg(app.theme.error())),
+        None => Span::raw(""),
+    };
     let lines = vec![
         Line::from(""),
         Line::from(vec![
@@ -44,6 +48,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
             Span::styled(&app.project_path_buffer, Style::default().fg(app.theme.text_primary())),
             Span::styled("_", Style::default().fg(app.theme.accent_secondary()).add_modifier(Modifier::SLOW_BLINK)),
         ]),
+        Line::from(feedback),
         Line::from(""),
         Line::from(vec![
             Span::styled("  [Enter] Confirm  [Esc] Cancel (revert to user scope)", Style::default().fg(app.theme.text_muted())),
@@ -64,3 +69,55 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(paragraph, dialog_area);
 }
+
+/// Ask before local-scope MCP changes write into a project that isn't in
+/// `trusted_projects:` yet.
+pub fn render_trust(f: &mut Frame, app: &App, area: Rect) {
+    let dialog = super::source_wizard::centered_rect(70, 10, area);
+    f.render_widget(Clear, dialog);
+
+    let path = std::path::Path::new(&app.mcp_project_path);
+    let action = match app.trust_pending {
+        Some(TrustPending::Install) => "Installing MCP servers with local scope writes into:",
+        Some(TrustPending::Remove) => "Removing MCP servers with local scope writes into:",
+        _ => "Local-scope MCP changes will write into:",
+    };
+    let mut path_line = vec![
+        Span::styled("  ", Style::default()),
+        Span::styled(
+            crate::source::config::project_key(path).display().to_string(),
+            Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if !path.is_dir() {
+        path_line.push(Span::styled("  (not a directory)", Style::default().fg(app.theme.error())));
+    }
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(format!("  {}", action), Style::default().fg(app.theme.text_secondary()))),
+        Line::from(""),
+        Line::from(path_line),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Not trusted yet. Trusting adds it to trusted_projects in ~/.hibi/sources.yaml.",
+            Style::default().fg(app.theme.text_muted()),
+        )),
+        Line::from(""),
+        Line::from(Span::styled("  [y] Trust and continue  [n/Esc] Cancel", Style::default().fg(app.theme.text_muted()))),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_secondary()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.warning()))
+                .title(" Trust Project? ")
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(paragraph, dialog);
+}
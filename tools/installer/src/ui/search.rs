@@ -0,0 +1,67 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Rows of results shown below the query line.
+const MAX_VISIBLE_RESULTS: usize = 12;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let dialog = super::source_wizard::centered_rect(60, MAX_VISIBLE_RESULTS as u16 + 6, area);
+    f.render_widget(Clear, dialog);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Search: ", Style::default().fg(app.theme.text_secondary())),
+            Span::styled(&app.search_query, Style::default().fg(app.theme.text_primary())),
+            Span::styled("_", Style::default().fg(app.theme.accent_secondary()).add_modifier(Modifier::SLOW_BLINK)),
+        ]),
+        Line::from(""),
+    ];
+
+    if app.search_results.is_empty() && !app.search_query.is_empty() {
+        lines.push(Line::from(Span::styled("  No matches", Style::default().fg(app.theme.text_muted()))));
+    }
+
+    // Scroll the window so the highlighted row stays visible.
+    let start = app.search_index.saturating_sub(MAX_VISIBLE_RESULTS - 1);
+    for (i, hit) in app.search_results.iter().enumerate().skip(start).take(MAX_VISIBLE_RESULTS) {
+        let selected = i == app.search_index;
+        let marker = if selected { "  > " } else { "    " };
+        let label_style = if selected {
+            Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_primary())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, label_style),
+            Span::styled(format!("[{}] ", app.tab_label(hit.tab)), Style::default().fg(app.theme.text_muted())),
+            Span::styled(hit.label.clone(), label_style),
+        ]));
+    }
+
+    let title = if app.search_results.is_empty() {
+        " Search All Tabs ".to_string()
+    } else {
+        format!(" Search All Tabs ({}/{}) ", app.search_index + 1, app.search_results.len())
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_secondary()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(title)
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+
+    f.render_widget(paragraph, dialog);
+}
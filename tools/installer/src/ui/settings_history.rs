@@ -0,0 +1,58 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Width of the version list; fits `▶ YYYYMMDD-HHMMSS-N` plus borders.
+const LIST_WIDTH: u16 = 24;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(LIST_WIDTH), Constraint::Min(0)])
+        .split(area);
+
+    let versions: Vec<Line> = app.settings_versions.iter().enumerate().map(|(i, v)| {
+        if i == app.settings_version_index {
+            Line::from(Span::styled(
+                format!("▶ {}", v.label()),
+                Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD),
+            ))
+        } else {
+            Line::from(Span::styled(format!("  {}", v.label()), Style::default().fg(app.theme.text_primary())))
+        }
+    }).collect();
+    f.render_widget(
+        Paragraph::new(versions).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(" settings.json ")
+                .title_style(Style::default().fg(app.theme.text_primary())),
+        ),
+        chunks[0],
+    );
+
+    let content = app.diff_content.as_deref().unwrap_or("");
+    let title = match app.settings_versions.get(app.settings_version_index + 1) {
+        Some(previous) => format!(" Changes since {} ", previous.label()),
+        None => " Oldest kept version ".to_string(),
+    };
+    f.render_widget(
+        Paragraph::new(super::diff::diff_lines(app, content))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(app.theme.border()))
+                    .title(title)
+                    .title_style(Style::default().fg(app.theme.text_primary())),
+            )
+            .scroll((app.diff_scroll, 0)),
+        chunks[1],
+    );
+}
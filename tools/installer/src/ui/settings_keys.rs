@@ -0,0 +1,70 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::fs::installer::KeyOwner;
+
+/// The Config tab's Keys view: each top-level settings.json key, who owns
+/// it, and whether source sets it and it is installed.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let rows: Vec<Row> = app.settings_keys.iter().map(|key| {
+        let owner = match (key.owner, key.overridden) {
+            (KeyOwner::Installer, false) => Span::styled("installer", Style::default().fg(app.theme.accent_primary())),
+            (KeyOwner::Installer, true) => Span::styled("installer (adopted)", Style::default().fg(app.theme.accent_primary())),
+            (KeyOwner::User, false) => Span::styled("user", Style::default().fg(app.theme.text_primary())),
+            (KeyOwner::User, true) => Span::styled("user (released)", Style::default().fg(app.theme.warning())),
+        };
+        let source = if key.in_source {
+            Span::styled("set", Style::default().fg(app.theme.text_secondary()))
+        } else {
+            Span::styled("absent", Style::default().fg(app.theme.text_muted()))
+        };
+        let installed = if key.installed {
+            Span::styled("yes", Style::default().fg(app.theme.success()))
+        } else {
+            Span::styled("no", Style::default().fg(app.theme.text_muted()))
+        };
+        Row::new(vec![
+            Cell::from(key.key.clone()),
+            Cell::from(Line::from(owner)),
+            Cell::from(Line::from(source)),
+            Cell::from(Line::from(installed)),
+        ])
+        .style(Style::default().fg(app.theme.text_primary()))
+    }).collect();
+
+    let header = Row::new(["Key", "Owner", "Source", "Installed"])
+        .style(Style::default().fg(app.theme.text_secondary()).add_modifier(Modifier::BOLD));
+
+    // Second-level tabs of the Config tab, Keys being the current one.
+    let title = Line::from(vec![
+        Span::styled(" Files ", Style::default().fg(app.theme.text_muted())),
+        Span::styled("│", Style::default().fg(app.theme.border())),
+        Span::styled(" Keys ", Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)),
+    ]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_focused()))
+        .title(title)
+        .title_bottom(Line::from(" settings.json ").right_aligned())
+        .title_style(Style::default().fg(app.theme.text_primary()));
+
+    let table = Table::new(rows, [
+        Constraint::Percentage(40),
+        Constraint::Length(20),
+        Constraint::Length(8),
+        Constraint::Length(10),
+    ])
+    .header(header)
+    .block(block)
+    .row_highlight_style(Style::default().fg(app.theme.warning()).add_modifier(Modifier::BOLD))
+    .highlight_symbol("> ");
+
+    let mut state = TableState::default().with_selected(Some(app.settings_key_index));
+    f.render_stateful_widget(table, area, &mut state);
+}
@@ -0,0 +1,45 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let options = crate::app::sort_options(app.tab);
+    let dialog = super::source_wizard::centered_rect(40, options.len() as u16 + 4, area);
+    f.render_widget(Clear, dialog);
+
+    let current = app.sort_for(app.tab);
+    let mut lines = vec![Line::from("")];
+    for (i, sort) in options.iter().enumerate() {
+        let selected = i == app.sort_menu_index;
+        let marker = if selected { "  > " } else { "    " };
+        let style = if selected {
+            Style::default().fg(app.theme.accent_primary()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text_primary())
+        };
+        let mut spans = vec![Span::styled(marker, style), Span::styled(sort.label(), style)];
+        if *sort == current {
+            spans.push(Span::styled("  ✓", Style::default().fg(app.theme.success())));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_secondary()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_focused()))
+                .title(format!(" Sort {} ", app.tab_label(app.tab)))
+                .title_style(Style::default().fg(app.theme.text_primary()))
+                .style(Style::default().bg(app.theme.bg_secondary())),
+        );
+
+    f.render_widget(paragraph, dialog);
+}
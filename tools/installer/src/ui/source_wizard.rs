@@ -153,7 +153,7 @@ pub fn render_syncing(f: &mut Frame, app: &App, area: Rect) {
     let dialog = centered_rect(35, 5, area);
     f.render_widget(Clear, dialog);
 
-    let spinner = super::get_spinner(app.animation_frame);
+    let spinner = super::get_spinner(crate::clock::frame());
     let text = vec![
         Line::from(""),
         Line::from(Span::styled(
@@ -171,7 +171,7 @@ pub fn render_syncing(f: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Create a centered rectangle within the given area.
-fn centered_rect(width_pct: u16, height: u16, area: Rect) -> Rect {
+pub(super) fn centered_rect(width_pct: u16, height: u16, area: Rect) -> Rect {
     let v = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{App, FocusArea};
+use crate::app::{App, FocusArea, Tab};
 
 /// Glyphs shown when one or more tabs are scrolled off-screen.
 const LEFT_INDICATOR: &str = "‹";
@@ -18,21 +18,29 @@ const INDICATOR_WIDTH: usize = 1;
 /// Width of the divider `ratatui::Tabs` inserts between titles (" │ ").
 const DIVIDER_WIDTH: usize = 3;
 
-pub fn render(f: &mut Frame, app: &App, area: Rect) {
+/// Draw the tab bar. Returns where each visible title landed and the tab
+/// a click on it selects; the `‹`/`›` markers select the nearest hidden tab.
+pub fn render(f: &mut Frame, app: &App, area: Rect) -> Vec<(Rect, Tab)> {
     // Titles are just the display names now — keyboard nav has replaced the
     // legacy 1-0/- direct shortcuts, so there's no reason to spend columns
     // on number prefixes.
     let titles: Vec<String> = app.available_tabs
         .iter()
-        .map(|tab| tab.display_name().to_string())
+        .map(|tab| app.tab_label(*tab))
         .collect();
 
     let title = if let Some(cli) = app.target_cli {
-        format!(" {} Config Installer ({}) ", cli.display_name(), app.theme.mode().name())
+        format!(" {} Config Installer ({}) ", cli.display_name(), app.theme.name())
     } else {
         String::from(" Config Installer ")
     };
 
+    // Cross-tab batch scope, right-aligned on the top border.
+    let selection_label = app.selection_summary()
+        .label()
+        .map(|l| format!(" {} ", l))
+        .unwrap_or_default();
+
     let selected_idx = app.available_tabs
         .iter()
         .position(|t| *t == app.tab)
@@ -44,6 +52,17 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let (visible_titles, visible_selected) =
         build_visible_tabs(&titles, selected_idx, inner_width);
 
+    let has_left = visible_titles.first().is_some_and(|t| t == LEFT_INDICATOR);
+    let first_shown = (selected_idx + usize::from(has_left)).saturating_sub(visible_selected);
+    let clickable: Vec<(Rect, Tab)> = title_areas(area, &visible_titles)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, rect)| {
+            let tab_idx = (first_shown + i).checked_sub(usize::from(has_left))?;
+            app.available_tabs.get(tab_idx).map(|tab| (rect, *tab))
+        })
+        .collect();
+
     // Focus styling: when the user has the tab bar focused, brighten the
     // border and bold/underline the selected title so it's obvious which
     // pane the next keystroke will affect. Without this cue the focus
@@ -58,6 +77,11 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color))
                 .title(title)
+                .title_top(
+                    Line::from(selection_label)
+                        .style(Style::default().fg(app.theme.warning()))
+                        .right_aligned(),
+                )
                 .title_style(Style::default().fg(app.theme.text_primary())),
         )
         .select(visible_selected)
@@ -69,6 +93,25 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         );
 
     f.render_widget(tabs, area);
+    clickable
+}
+
+/// Where `ratatui::Tabs` puts each title inside a bordered `area`: one
+/// column of padding either side and a one-column divider between them.
+fn title_areas(area: Rect, titles: &[String]) -> Vec<Rect> {
+    let right = area.right().saturating_sub(1);
+    let mut x = area.x + 1;
+    let mut areas = Vec::with_capacity(titles.len());
+    for title in titles {
+        let start = x.saturating_add(1);
+        if start >= right {
+            break;
+        }
+        let width = u16::try_from(Line::from(title.as_str()).width()).unwrap_or(u16::MAX).min(right - start);
+        areas.push(Rect::new(start, area.y + 1, width, 1));
+        x = start.saturating_add(width).saturating_add(1 + 1);
+    }
+    areas
 }
 
 /// Pick a slice of `titles` that keeps `selected_idx` visible inside
@@ -162,6 +205,15 @@ mod tests {
         ])
     }
 
+    #[test]
+    fn title_areas_skip_padding_and_dividers() {
+        let areas = title_areas(Rect::new(0, 0, 40, 3), &titles(&["Agents", "MCP"]));
+        // "│ Agents │ MCP │": border, pad, title, pad, divider, pad, title.
+        assert_eq!(areas, [Rect::new(2, 1, 6, 1), Rect::new(11, 1, 3, 1)]);
+        let clipped = title_areas(Rect::new(0, 0, 12, 3), &titles(&["Agents", "MCP"]));
+        assert_eq!(clipped, [Rect::new(2, 1, 6, 1)], "no room left for MCP");
+    }
+
     #[test]
     fn all_tabs_fit_returns_full_slice() {
         let t = titles(&["A", "B", "C"]);
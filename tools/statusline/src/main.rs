@@ -1,5 +1,7 @@
 use chrono::Local;
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
 use std::env;
 use std::fs;
 use std::io::{self, BufRead, Write};
@@ -9,42 +11,142 @@ use std::process::Command;
 // ============================================================================
 // Input schema (subset of Claude Code statusline JSON)
 // ============================================================================
+//
+// The payload has changed shape between Claude Code releases: percentages
+// arrive as integers or floats, older releases only report token counts,
+// and fields come and go. Every field is read leniently, so one field in
+// an unexpected shape drops that value instead of blanking the statusline.
 
 #[derive(Deserialize)]
 struct StatusInput {
+    #[serde(default, deserialize_with = "lenient")]
     model: Option<Model>,
+    #[serde(default, deserialize_with = "lenient")]
     cwd: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     context_window: Option<ContextWindow>,
+    #[serde(default, deserialize_with = "lenient")]
     transcript_path: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     version: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     rate_limits: Option<RateLimits>,
 }
 
+/// `{"display_name": ..., "id": ...}`, or a bare model id string.
 #[derive(Deserialize)]
-struct Model {
-    display_name: Option<String>,
+#[serde(untagged)]
+enum Model {
+    Object {
+        #[serde(default, deserialize_with = "lenient")]
+        display_name: Option<String>,
+        #[serde(default, deserialize_with = "lenient")]
+        id: Option<String>,
+    },
+    Id(String),
+}
+
+impl Model {
+    fn name(self) -> Option<String> {
+        match self {
+            Model::Object { display_name, id } => display_name.or(id),
+            Model::Id(id) => Some(id),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 struct ContextWindow {
-    used_percentage: Option<u32>,
-    remaining_percentage: Option<u32>,
-    total_input_tokens: Option<u64>,
-    total_output_tokens: Option<u64>,
-    context_window_size: Option<u64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    used_percentage: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    remaining_percentage: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    total_input_tokens: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    total_output_tokens: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    context_window_size: Option<f64>,
+    /// Token usage of the last request; `null` before the first one.
+    #[serde(default, alias = "usage", deserialize_with = "lenient")]
+    current_usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    #[serde(default, deserialize_with = "lenient_number")]
+    input_tokens: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    cache_creation_input_tokens: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_number")]
+    cache_read_input_tokens: Option<f64>,
+}
+
+impl Usage {
+    /// Tokens the last request put in the context window.
+    fn context_tokens(&self) -> f64 {
+        [self.input_tokens, self.cache_creation_input_tokens, self.cache_read_input_tokens]
+            .iter()
+            .flatten()
+            .sum()
+    }
+}
+
+impl ContextWindow {
+    /// Remaining percentage, from whichever field this release reports:
+    /// `remaining_percentage`, `used_percentage`, or usage against the
+    /// window size.
+    fn remaining(&self) -> Option<u32> {
+        let pct = self
+            .remaining_percentage
+            .or_else(|| self.used_percentage.map(|used| 100.0 - used))
+            .or_else(|| {
+                let size = self.context_window_size.filter(|s| *s > 0.0)?;
+                let used = self.current_usage.as_ref()?.context_tokens();
+                Some(100.0 - used / size * 100.0)
+            })?;
+        Some(pct.clamp(0.0, 100.0).round() as u32)
+    }
 }
 
 #[derive(Deserialize)]
 struct RateLimits {
+    #[serde(default, deserialize_with = "lenient")]
     five_hour: Option<RateLimitWindow>,
+    #[serde(default, deserialize_with = "lenient")]
     seven_day: Option<RateLimitWindow>,
 }
 
 #[derive(Deserialize)]
 struct RateLimitWindow {
+    #[serde(default, deserialize_with = "lenient_number")]
     used_percentage: Option<f64>,
 }
 
+/// `T`, or `None` when the field is null or has an unexpected shape.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+/// A number given as an integer, a float, or a numeric string such as
+/// `"42.5"` or `"42%"`.
+fn lenient_number<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().trim_end_matches('%').trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|n: &f64| n.is_finite()))
+}
+
 // ============================================================================
 // Display values (extracted/computed for rendering)
 // ============================================================================
@@ -86,6 +188,19 @@ const TOKEN_MILLION: u64 = 1_000_000;
 const TOKEN_TEN_THOUSAND: u64 = 10_000;
 const TOKEN_THOUSAND: u64 = 1_000;
 
+/// Sample input for `--demo`, in the shape current releases send.
+const DEMO_INPUT: &str = r#"{
+    "model": {"id": "claude-opus-4-1", "display_name": "Opus"},
+    "cwd": "/home/me/project", "version": "2.1.2",
+    "context_window": {
+        "total_input_tokens": 15234, "total_output_tokens": 4521,
+        "context_window_size": 200000, "used_percentage": 42, "remaining_percentage": 58,
+        "current_usage": {"input_tokens": 8500, "output_tokens": 1200,
+            "cache_creation_input_tokens": 5000, "cache_read_input_tokens": 2000}
+    },
+    "rate_limits": {"five_hour": {"used_percentage": 23.5}, "seven_day": {"used_percentage": 41}}
+}"#;
+
 // ============================================================================
 // Environment helpers
 // ============================================================================
@@ -219,13 +334,13 @@ fn extract_values(status: StatusInput) -> StatusValues {
     let (branch, git_status) = get_git_info(&cwd_raw);
 
     let ctx = status.context_window;
-    let remaining = ctx.as_ref().and_then(|c| {
-        c.remaining_percentage
-            .or_else(|| c.used_percentage.map(|u| 100u32.saturating_sub(u)))
-    });
-    let total_input_tokens = ctx.as_ref().and_then(|c| c.total_input_tokens);
-    let total_output_tokens = ctx.as_ref().and_then(|c| c.total_output_tokens);
-    let context_window_size = ctx.as_ref().and_then(|c| c.context_window_size);
+    let remaining = ctx.as_ref().and_then(ContextWindow::remaining);
+    let tokens = |field: fn(&ContextWindow) -> Option<f64>| {
+        ctx.as_ref().and_then(field).filter(|n| *n >= 0.0).map(|n| n as u64)
+    };
+    let total_input_tokens = tokens(|c| c.total_input_tokens);
+    let total_output_tokens = tokens(|c| c.total_output_tokens);
+    let context_window_size = tokens(|c| c.context_window_size);
 
     let rate_limits = status.rate_limits;
     let five_hour_pct = rate_limits
@@ -239,7 +354,7 @@ fn extract_values(status: StatusInput) -> StatusValues {
 
     let model = status
         .model
-        .and_then(|m| m.display_name)
+        .and_then(Model::name)
         .unwrap_or_else(|| "Unknown".to_string());
 
     StatusValues {
@@ -319,11 +434,98 @@ fn format_line2(v: &StatusValues) -> String {
     line
 }
 
+/// Each segment's value in plain text, in display order; absent
+/// segments are left out.
+fn segment_breakdown(v: &StatusValues) -> Vec<(&'static str, String)> {
+    let mut segments = vec![("user", v.user.clone()), ("cwd", v.cwd.clone())];
+    if !v.branch.is_empty() {
+        segments.push(("branch", format!("{}{}", v.branch, v.git_status)));
+    }
+    segments.push(("model", v.model.clone()));
+    segments.push(("time", v.time.clone()));
+    if let Some(pct) = v.remaining {
+        segments.push(("ctx", format!("{pct}% remaining")));
+    }
+    if let Some(tokens) = format_token_summary(v) {
+        segments.push(("tokens", tokens));
+    }
+    if let Some(pct) = v.five_hour_pct {
+        segments.push(("5h", format!("{pct:.0}% used")));
+    }
+    if let Some(pct) = v.seven_day_pct {
+        segments.push(("7d", format!("{pct:.0}% used")));
+    }
+    if let Some(version) = &v.version {
+        segments.push(("version", version.clone()));
+    }
+    if v.todo_count > 0 {
+        segments.push(("todos", v.todo_count.to_string()));
+    }
+    segments
+}
+
+// ============================================================================
+// Debug mode
+// ============================================================================
+
+/// Input for `--demo` / `--input <file>`, or `None` for normal stdin mode.
+fn debug_input(args: &[String]) -> Option<Result<String, String>> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--demo" => return Some(Ok(DEMO_INPUT.to_string())),
+            "--input" => {
+                let Some(path) = args.next() else {
+                    return Some(Err("--input needs a JSON file".to_string()));
+                };
+                return Some(fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}")));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Render `json` once and print the output, its escaped form and each
+/// segment's value. Unlike stdin mode, a bad payload is reported.
+fn run_debug(json: &str) -> Result<(), String> {
+    let status: StatusInput = serde_json::from_str(json).map_err(|e| format!("invalid input: {e}"))?;
+    let values = extract_values(status);
+    let lines: Vec<String> = [format_line1(&values), format_line2(&values)]
+        .into_iter()
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    for line in &lines {
+        println!("{line}");
+    }
+    println!();
+    println!("Escaped:");
+    for line in &lines {
+        println!("  {}", line.escape_debug());
+    }
+    println!();
+    println!("Segments:");
+    for (name, value) in segment_breakdown(&values) {
+        println!("  {name:<8} {value}");
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Entry point
 // ============================================================================
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(input) = debug_input(&args) {
+        if let Err(e) = input.and_then(|json| run_debug(&json)) {
+            eprintln!("statusline: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let mut input = String::new();
     if io::stdin().lock().read_line(&mut input).is_err() || input.is_empty() {
         return;
@@ -419,6 +621,121 @@ mod tests {
         assert!(sep);
     }
 
+    // Payload shapes seen from different Claude Code releases, trimmed to
+    // the fields the statusline reads.
+
+    /// Releases that send percentages as floats, and only the used one.
+    const PAYLOAD_FLOAT_PERCENT: &str = r#"{
+        "model": {"display_name": "Sonnet"},
+        "context_window": {"used_percentage": 41.7, "total_input_tokens": 83400.0, "context_window_size": 200000}
+    }"#;
+
+    /// Releases before the percentage fields: usage of the last request
+    /// against the window size.
+    const PAYLOAD_USAGE_ONLY: &str = r#"{
+        "model": {"display_name": "Opus"},
+        "context_window": {
+            "total_input_tokens": 15234, "total_output_tokens": 4521, "context_window_size": 200000,
+            "current_usage": {"input_tokens": 30000, "output_tokens": 900,
+                "cache_creation_input_tokens": 10000, "cache_read_input_tokens": 60000}
+        }
+    }"#;
+
+    /// Start of a session: no request yet, so `current_usage` is null.
+    const PAYLOAD_NO_USAGE_YET: &str = r#"{
+        "model": {"display_name": "Opus"},
+        "context_window": {"total_input_tokens": 0, "total_output_tokens": 0,
+            "context_window_size": 200000, "current_usage": null}
+    }"#;
+
+    /// Oldest releases: no context window or rate limits at all.
+    const PAYLOAD_MINIMAL: &str = r#"{"model": {"id": "claude-3-7-sonnet"}, "cwd": "/tmp", "version": "1.0.20"}"#;
+
+    fn parse(json: &str) -> StatusInput {
+        serde_json::from_str(json).expect("payload should parse")
+    }
+
+    fn remaining(input: &StatusInput) -> Option<u32> {
+        input.context_window.as_ref().and_then(ContextWindow::remaining)
+    }
+
+    #[test]
+    fn payload_with_percentages_uses_remaining_percentage() {
+        let input = parse(DEMO_INPUT);
+        assert_eq!(remaining(&input), Some(58));
+        let limits = input.rate_limits.unwrap();
+        assert_eq!(limits.five_hour.unwrap().used_percentage, Some(23.5));
+        assert_eq!(limits.seven_day.unwrap().used_percentage, Some(41.0));
+        assert_eq!(input.model.and_then(Model::name).as_deref(), Some("Opus"));
+    }
+
+    #[test]
+    fn payload_with_float_used_percentage_is_rounded() {
+        let input = parse(PAYLOAD_FLOAT_PERCENT);
+        assert_eq!(remaining(&input), Some(58));
+        assert_eq!(input.context_window.unwrap().total_input_tokens, Some(83400.0));
+    }
+
+    #[test]
+    fn payload_without_percentages_derives_them_from_usage() {
+        // 30k + 10k + 60k of 200k used.
+        assert_eq!(remaining(&parse(PAYLOAD_USAGE_ONLY)), Some(50));
+    }
+
+    #[test]
+    fn payload_before_first_request_has_no_percentage() {
+        let input = parse(PAYLOAD_NO_USAGE_YET);
+        assert_eq!(remaining(&input), None);
+        assert_eq!(input.context_window.unwrap().context_window_size, Some(200000.0));
+    }
+
+    #[test]
+    fn minimal_payload_falls_back_to_model_id() {
+        let input = parse(PAYLOAD_MINIMAL);
+        assert!(input.context_window.is_none() && input.rate_limits.is_none());
+        assert_eq!(input.model.and_then(Model::name).as_deref(), Some("claude-3-7-sonnet"));
+    }
+
+    #[test]
+    fn unexpected_field_shapes_drop_only_that_value() {
+        let input = parse(r#"{
+            "model": "claude-sonnet-4-5", "version": 2, "cwd": "/tmp",
+            "context_window": {"remaining_percentage": "42%", "used_percentage": [1], "current_usage": 7},
+            "rate_limits": "unavailable"
+        }"#);
+        assert_eq!(remaining(&input), Some(42));
+        assert!(input.version.is_none() && input.rate_limits.is_none());
+        assert_eq!(input.cwd.as_deref(), Some("/tmp"));
+        assert_eq!(input.model.and_then(Model::name).as_deref(), Some("claude-sonnet-4-5"));
+    }
+
+    #[test]
+    fn out_of_range_percentages_are_clamped() {
+        let input = parse(r#"{"context_window": {"used_percentage": 104.2}}"#);
+        assert_eq!(remaining(&input), Some(0));
+    }
+
+    #[test]
+    fn debug_input_reads_demo_or_file() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(debug_input(&args(&[])).is_none());
+        assert_eq!(debug_input(&args(&["--demo"])), Some(Ok(DEMO_INPUT.to_string())));
+        assert!(matches!(debug_input(&args(&["--input"])), Some(Err(_))));
+        assert!(matches!(debug_input(&args(&["--input", "/nonexistent/x.json"])), Some(Err(_))));
+    }
+
+    #[test]
+    fn segment_breakdown_lists_present_segments_in_order() {
+        let mut values = extract_values(parse(DEMO_INPUT));
+        values.branch = "main".to_string();
+        values.git_status = "*".to_string();
+        values.todo_count = 0;
+        let names: Vec<&str> = segment_breakdown(&values).iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["user", "cwd", "branch", "model", "time", "ctx", "tokens", "5h", "7d", "version"]);
+        let tokens = segment_breakdown(&values).into_iter().find(|(n, _)| *n == "tokens").unwrap();
+        assert_eq!(tokens.1, "15k/200k");
+    }
+
     #[test]
     fn append_segment_chained_three_calls() {
         let mut line = String::new();